@@ -1,18 +1,68 @@
 //! Compile command implementation.
 
+use crate::output::{HasSuccess, TextRender};
 use anyhow::{Context, Result};
-use nectar_compiler::{Compiler, CompileOptions, Lockfile, OutputFormat};
+use nectar_compiler::tsr;
+use nectar_compiler::{CompileOptions, Compiler, Lockfile, OutputFormat as RulesFormat};
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
-use tracing::info;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+/// Result of compiling a policy to Refinery rules.
+#[derive(Debug, Serialize)]
+pub struct CompileResult {
+    /// Whether compilation (and, if requested, lockfile sealing) succeeded.
+    pub success: bool,
+    /// The compiled policy's name.
+    pub policy_name: String,
+    /// Number of rules in the compiled policy.
+    pub rule_count: usize,
+    /// Path the compiled rules were written to.
+    pub output_path: String,
+    /// Output format used for the compiled rules ("yaml" or "json").
+    pub output_format: String,
+    /// Path to the lockfile, if `--lock` was requested.
+    pub lockfile_path: Option<String>,
+    /// Whether the lockfile was sealed with a trusted timestamp token.
+    pub tsa_sealed: bool,
+    /// Warning raised while obtaining a trusted timestamp, if any.
+    pub tsa_warning: Option<String>,
+}
+
+impl TextRender for CompileResult {
+    fn render_text(&self) {
+        println!(
+            "Compiled policy '{}' ({} rules) to {}",
+            self.policy_name, self.rule_count, self.output_path
+        );
+        if let Some(lock_path) = &self.lockfile_path {
+            println!("Lockfile written to: {lock_path}");
+            if self.tsa_sealed {
+                println!("Sealed with a trusted timestamp");
+            }
+        }
+        if let Some(warning) = &self.tsa_warning {
+            println!("Warning: {warning}");
+        }
+    }
+}
+
+impl HasSuccess for CompileResult {
+    fn success(&self) -> bool {
+        self.success
+    }
+}
 
 /// Runs the compile command.
-pub fn run(
+pub async fn run(
     policy_path: &str,
     output_path: &str,
     format: &str,
     create_lockfile: bool,
-) -> Result<()> {
+    tsa_url: Option<&str>,
+) -> Result<CompileResult> {
     info!("Compiling policy: {}", policy_path);
 
     // Read policy file
@@ -27,8 +77,8 @@ pub fn run(
 
     // Determine output format
     let output_format = match format.to_lowercase().as_str() {
-        "json" => OutputFormat::Json,
-        "yaml" | "yml" => OutputFormat::Yaml,
+        "json" => RulesFormat::Json,
+        "yaml" | "yml" => RulesFormat::Yaml,
         _ => {
             anyhow::bail!("Unknown output format: {format}. Use 'yaml' or 'json'.");
         }
@@ -46,21 +96,83 @@ pub fn run(
     fs::write(output_path, &output)
         .with_context(|| format!("Failed to write output file: {output_path}"))?;
 
-    info!("Compiled policy written to: {}", output_path);
+    let mut lockfile_path = None;
+    let mut tsa_sealed = false;
+    let mut tsa_warning = None;
 
     // Create lockfile if requested
     if create_lockfile {
-        let lockfile = Lockfile::new(&policy, &policy_content, &output).with_timestamp();
+        let mut lockfile = Lockfile::new(&policy, &policy_content, &output).with_timestamp();
+
+        if let Some(tsa_url) = tsa_url {
+            match request_trusted_timestamp(tsa_url, &output).await {
+                Ok(token) => {
+                    lockfile = lockfile.with_tsa_token(tsa_url, &output, &token);
+                    tsa_sealed = true;
+                }
+                Err(e) => {
+                    let message = format!("Failed to obtain a trusted timestamp from {tsa_url}: {e}");
+                    warn!("{}", message);
+                    tsa_warning = Some(message);
+                }
+            }
+        }
+
         let lock_path = format!("{}.lock", policy_path.trim_end_matches(".toon"));
         let lock_path = Path::new(&lock_path);
 
         lockfile.save(lock_path)
             .with_context(|| format!("Failed to write lockfile: {}", lock_path.display()))?;
 
-        info!("Lockfile written to: {}", lock_path.display());
+        lockfile_path = Some(lock_path.display().to_string());
     }
 
-    Ok(())
+    Ok(CompileResult {
+        success: true,
+        policy_name: policy.name.clone(),
+        rule_count: policy.rules.len(),
+        output_path: output_path.to_string(),
+        output_format: format.to_lowercase(),
+        lockfile_path,
+        tsa_sealed,
+        tsa_warning,
+    })
+}
+
+/// Requests an RFC 3161 trusted timestamp over `content` from `tsa_url`.
+///
+/// Builds a `TimeStampReq` with a fresh nonce, POSTs it as
+/// `application/timestamp-query`, and parses the response into a
+/// [`tsr::TimestampToken`].
+async fn request_trusted_timestamp(tsa_url: &str, content: &str) -> Result<tsr::TimestampToken> {
+    let digest = tsr::sha256(content.as_bytes());
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+
+    let request = tsr::TimestampRequest::new(digest, nonce);
+    let body = request.to_der();
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(tsa_url)
+        .header("Content-Type", "application/timestamp-query")
+        .body(body)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach TSA at {tsa_url}"))?;
+
+    let response_bytes = response
+        .bytes()
+        .await
+        .with_context(|| "Failed to read TSA response body")?;
+
+    let token = tsr::parse_timestamp_response(&response_bytes);
+    tsr::verify_timestamp_token(&token, &digest)
+        .with_context(|| "TSA response did not attest to the compiled output")?;
+
+    Ok(token)
 }
 
 /// Verifies a policy against its lockfile.
@@ -1,12 +1,42 @@
 //! Explain command implementation.
 
+use crate::output::{HasSuccess, TextRender};
 use anyhow::{Context, Result};
 use nectar_compiler::waggle::generate_waggle_report;
+use serde::Serialize;
 use std::fs;
 use tracing::info;
 
+/// Result of generating a waggle report explaining a policy.
+#[derive(Debug, Serialize)]
+pub struct ExplainResult {
+    /// Whether the report was generated successfully.
+    pub success: bool,
+    /// The policy's name.
+    pub policy_name: String,
+    /// Number of rules in the policy.
+    pub rule_count: usize,
+    /// Path the waggle report was written to.
+    pub output_path: String,
+}
+
+impl TextRender for ExplainResult {
+    fn render_text(&self) {
+        println!(
+            "Generated waggle report for '{}' ({} rules) to {}",
+            self.policy_name, self.rule_count, self.output_path
+        );
+    }
+}
+
+impl HasSuccess for ExplainResult {
+    fn success(&self) -> bool {
+        self.success
+    }
+}
+
 /// Runs the explain command.
-pub fn run(policy_path: &str, output_path: &str) -> Result<()> {
+pub fn run(policy_path: &str, output_path: &str) -> Result<ExplainResult> {
     info!("Generating waggle report for: {}", policy_path);
 
     // Read policy file
@@ -23,6 +53,10 @@ pub fn run(policy_path: &str, output_path: &str) -> Result<()> {
     fs::write(output_path, &report)
         .with_context(|| format!("Failed to write output file: {output_path}"))?;
 
-    info!("Waggle report written to: {}", output_path);
-    Ok(())
+    Ok(ExplainResult {
+        success: true,
+        policy_name: policy.name.clone(),
+        rule_count: policy.rules.len(),
+        output_path: output_path.to_string(),
+    })
 }
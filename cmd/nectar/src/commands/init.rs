@@ -1,14 +1,56 @@
 //! Init command implementation.
 
+use crate::output::{HasSuccess, TextRender};
 use anyhow::{Context, Result};
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 use tracing::info;
 
+/// Result of initializing a new Nectar project.
+#[derive(Debug, Serialize)]
+pub struct InitResult {
+    /// Whether initialization succeeded.
+    pub success: bool,
+    /// Path to the initialized project.
+    pub project_path: String,
+    /// Files created by this run, plus an existing `.gitignore` that this
+    /// run appended to.
+    pub created: Vec<String>,
+    /// Files that already existed and were left untouched.
+    pub skipped: Vec<String>,
+}
+
+impl TextRender for InitResult {
+    fn render_text(&self) {
+        for path in &self.created {
+            println!("Created: {path}");
+        }
+        for path in &self.skipped {
+            println!("Skipped: {path} (already exists)");
+        }
+        println!("Nectar project initialized successfully!");
+        println!();
+        println!("Next steps:");
+        println!("  1. Edit policy.toon to define your sampling rules");
+        println!("  2. Add trace exemplars to corpus/");
+        println!("  3. Run 'nectar prove' to verify");
+        println!("  4. Run 'nectar compile' to generate rules.yaml");
+    }
+}
+
+impl HasSuccess for InitResult {
+    fn success(&self) -> bool {
+        self.success
+    }
+}
+
 /// Runs the init command.
-pub fn run(path: &str) -> Result<()> {
+pub fn run(path: &str) -> Result<InitResult> {
     let project_path = Path::new(path);
-    
+    let mut created = Vec::new();
+    let mut skipped = Vec::new();
+
     info!("Initializing Nectar project at: {}", project_path.display());
 
     // Create directories
@@ -29,11 +71,11 @@ pub fn run(path: &str) -> Result<()> {
 
     let policy_path = project_path.join("policy.toon");
     if policy_path.exists() {
-        info!("Skipped: {} (already exists)", policy_path.display());
+        skipped.push(policy_path.display().to_string());
     } else {
         fs::write(&policy_path, default_policy)
             .with_context(|| "Failed to create policy.toon")?;
-        info!("Created: {}", policy_path.display());
+        created.push(policy_path.display().to_string());
     }
 
     // Create .gitignore additions
@@ -56,12 +98,12 @@ waggle.md
             content.push_str(gitignore_content);
             fs::write(&gitignore_path, content)
                 .with_context(|| "Failed to update .gitignore")?;
-            info!("Updated: {}", gitignore_path.display());
+            created.push(gitignore_path.display().to_string());
         }
     } else {
         fs::write(&gitignore_path, gitignore_content)
             .with_context(|| "Failed to create .gitignore")?;
-        info!("Created: {}", gitignore_path.display());
+        created.push(gitignore_path.display().to_string());
     }
 
     // Create README
@@ -100,19 +142,18 @@ nectar explain
 "#;
 
     let readme_path = project_path.join("README.md");
-    if !readme_path.exists() {
+    if readme_path.exists() {
+        skipped.push(readme_path.display().to_string());
+    } else {
         fs::write(&readme_path, readme_content)
             .with_context(|| "Failed to create README.md")?;
-        info!("Created: {}", readme_path.display());
+        created.push(readme_path.display().to_string());
     }
 
-    info!("Nectar project initialized successfully!");
-    info!("");
-    info!("Next steps:");
-    info!("  1. Edit policy.toon to define your sampling rules");
-    info!("  2. Add trace exemplars to corpus/");
-    info!("  3. Run 'nectar prove' to verify");
-    info!("  4. Run 'nectar compile' to generate rules.yaml");
-
-    Ok(())
+    Ok(InitResult {
+        success: true,
+        project_path: project_path.display().to_string(),
+        created,
+        skipped,
+    })
 }
@@ -0,0 +1,84 @@
+//! Lint command implementation.
+
+use crate::output::{HasSuccess, TextRender};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fs;
+use toon_policy::Diagnostic;
+use tracing::info;
+
+/// Result of linting a policy.
+#[derive(Debug, Serialize)]
+pub struct LintResult {
+    /// Whether linting found no errors (warnings/info still allow success).
+    pub success: bool,
+    /// Path to the policy file that was linted.
+    pub policy_path: String,
+    /// Diagnostics found, sorted by source line.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Whether `--fix` was passed and fixes were applied.
+    pub fixed: bool,
+}
+
+impl TextRender for LintResult {
+    fn render_text(&self) {
+        for diagnostic in &self.diagnostics {
+            println!(
+                "{}:{}:{}: [{:?}] {}",
+                self.policy_path, diagnostic.line, diagnostic.column, diagnostic.severity, diagnostic.message
+            );
+        }
+
+        if self.diagnostics.is_empty() {
+            println!("No lint findings in '{}'", self.policy_path);
+        } else {
+            println!("{} finding(s) in '{}'", self.diagnostics.len(), self.policy_path);
+        }
+
+        if self.fixed {
+            println!("Applied fixes and rewrote '{}'", self.policy_path);
+        }
+    }
+}
+
+impl HasSuccess for LintResult {
+    fn success(&self) -> bool {
+        self.success
+    }
+}
+
+/// Runs the lint command.
+///
+/// When `fix` is true, every diagnostic's suggested [`toon_policy::Fix`]
+/// (if any) is applied and the result is written back to `policy_path`
+/// before the (now-reduced) diagnostic list is reported.
+pub fn run(policy_path: &str, fix: bool) -> Result<LintResult> {
+    info!("Linting policy: {}", policy_path);
+
+    let source = fs::read_to_string(policy_path)
+        .with_context(|| format!("Failed to read policy file: {policy_path}"))?;
+
+    let diagnostics = toon_policy::lint(&source).with_context(|| "Failed to lint policy")?;
+
+    let (diagnostics, fixed) = if fix && diagnostics.iter().any(|d| d.fix.is_some()) {
+        let rewritten = toon_policy::apply_fixes(&source, &diagnostics);
+        fs::write(policy_path, &rewritten)
+            .with_context(|| format!("Failed to write fixed policy to: {policy_path}"))?;
+        let remaining =
+            toon_policy::lint(&rewritten).with_context(|| "Failed to re-lint fixed policy")?;
+        (remaining, true)
+    } else {
+        (diagnostics, false)
+    };
+
+    let success = !diagnostics
+        .iter()
+        .any(|d| d.severity == toon_policy::Severity::Error);
+
+    Ok(LintResult {
+        success,
+        policy_path: policy_path.to_string(),
+        diagnostics,
+        fixed,
+    })
+}
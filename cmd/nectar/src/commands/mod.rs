@@ -0,0 +1,10 @@
+//! CLI subcommand implementations.
+
+pub mod compile;
+pub mod explain;
+pub mod init;
+pub mod lint;
+pub mod propose;
+pub mod prove;
+pub mod serve;
+pub mod watch;
@@ -1,19 +1,51 @@
 //! Propose command implementation.
 
+use crate::output::{HasSuccess, TextRender};
 use anyhow::{Context, Result};
 use nectar_claude::{Client, ClientConfig};
 use nectar_corpus::Corpus;
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 use tracing::{info, warn};
 
+/// Result of generating a policy from a natural-language intent.
+#[derive(Debug, Serialize)]
+pub struct ProposeResult {
+    /// Whether a policy was generated successfully.
+    pub success: bool,
+    /// The generated policy's name.
+    pub policy_name: String,
+    /// Number of rules in the generated policy.
+    pub rule_count: usize,
+    /// Number of corpus traces used for context.
+    pub corpus_size: usize,
+    /// Path the generated policy was written to.
+    pub output_path: String,
+}
+
+impl TextRender for ProposeResult {
+    fn render_text(&self) {
+        println!(
+            "Generated policy '{}' ({} rules) to {}",
+            self.policy_name, self.rule_count, self.output_path
+        );
+    }
+}
+
+impl HasSuccess for ProposeResult {
+    fn success(&self) -> bool {
+        self.success
+    }
+}
+
 /// Runs the propose command.
 pub async fn run(
     intent: &str,
     corpus_path: Option<&str>,
     policy_path: Option<&str>,
     output_path: &str,
-) -> Result<()> {
+) -> Result<ProposeResult> {
     info!("Generating policy for intent: {}", intent);
 
     // Load API key from environment
@@ -37,6 +69,7 @@ pub async fn run(
     if !corpus.is_empty() {
         info!("Loaded {} traces from corpus", corpus.len());
     }
+    let corpus_size = corpus.len();
 
     // Load existing policy if provided
     let current_policy = if let Some(path) = policy_path {
@@ -66,8 +99,13 @@ pub async fn run(
     fs::write(output_path, &output)
         .with_context(|| format!("Failed to write output file: {output_path}"))?;
 
-    info!("Generated policy written to: {}", output_path);
-    Ok(())
+    Ok(ProposeResult {
+        success: true,
+        policy_name: policy.name.clone(),
+        rule_count: policy.rules.len(),
+        corpus_size,
+        output_path: output_path.to_string(),
+    })
 }
 
 fn load_corpus(path: &str) -> Result<Corpus> {
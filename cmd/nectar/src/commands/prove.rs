@@ -1,14 +1,84 @@
 //! Prove command implementation.
 
+use crate::output::{HasSuccess, TextRender};
 use anyhow::{Context, Result};
+use nectar_compiler::{Compiler, Lockfile};
 use nectar_corpus::Corpus;
-use nectar_prover::{Prover, ProverConfig};
+use nectar_prover::{Prover, ProverConfig, Violation, Warning};
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
 use tracing::{error, info, warn};
 
+/// Result of verifying a policy against a trace corpus.
+#[derive(Debug, Serialize)]
+pub struct ProveResult {
+    /// Whether the policy passed verification (and, under `--strict`, had no warnings).
+    pub success: bool,
+    /// Path to the policy file that was verified.
+    pub policy_path: String,
+    /// Path to the corpus that was verified against.
+    pub corpus_path: String,
+    /// Number of traces loaded from the corpus.
+    pub corpus_size: usize,
+    /// Number of checks that passed.
+    pub checks_passed: usize,
+    /// Total number of checks run.
+    pub checks_total: usize,
+    /// Critical violations that cause rejection.
+    pub violations: Vec<Violation>,
+    /// Non-critical warnings.
+    pub warnings: Vec<Warning>,
+    /// Whether `--strict` was passed (warnings are treated as failures).
+    pub strict: bool,
+    /// `genTime` of a verified trusted timestamp token, if the lockfile has one.
+    pub tsa_gen_time: Option<String>,
+    /// Message describing why trusted timestamp verification failed, if it did.
+    pub tsa_warning: Option<String>,
+}
+
+impl TextRender for ProveResult {
+    fn render_text(&self) {
+        if let Some(gen_time) = &self.tsa_gen_time {
+            println!("Trusted timestamp verified (genTime: {gen_time})");
+        }
+        if let Some(warning) = &self.tsa_warning {
+            println!("Warning: {warning}");
+        }
+
+        println!("Checks passed: {}/{}", self.checks_passed, self.checks_total);
+
+        for violation in &self.violations {
+            println!("[{}] {}: {}", violation.severity, violation.check, violation.message);
+        }
+        for warning in &self.warnings {
+            println!("[{}] {}: {}", warning.severity, warning.check, warning.message);
+        }
+
+        if self.success {
+            println!("Policy verification passed!");
+        } else if !self.violations.is_empty() {
+            println!(
+                "Policy verification failed with {} violation(s)",
+                self.violations.len()
+            );
+        } else {
+            println!(
+                "Policy verification failed with {} warning(s) (strict mode)",
+                self.warnings.len()
+            );
+        }
+    }
+}
+
+impl HasSuccess for ProveResult {
+    fn success(&self) -> bool {
+        self.success
+    }
+}
+
 /// Runs the prove command.
-pub fn run(policy_path: &str, corpus_path: &str, strict: bool) -> Result<()> {
+pub fn run(policy_path: &str, corpus_path: &str, strict: bool) -> Result<ProveResult> {
     info!("Verifying policy: {}", policy_path);
     info!("Against corpus: {}", corpus_path);
 
@@ -19,9 +89,13 @@ pub fn run(policy_path: &str, corpus_path: &str, strict: bool) -> Result<()> {
     // Parse policy
     let policy = toon_policy::parse(&policy_content).with_context(|| "Failed to parse policy")?;
 
+    // Check for a trusted timestamp token on the lockfile, if one exists
+    let (tsa_gen_time, tsa_warning) = check_trusted_timestamp(policy_path, &policy, strict)?;
+
     // Load corpus
     let corpus = load_corpus(corpus_path)?;
     info!("Loaded {} traces from corpus", corpus.len());
+    let corpus_size = corpus.len();
 
     // Create prover
     let prover = Prover::new(ProverConfig {
@@ -34,19 +108,12 @@ pub fn run(policy_path: &str, corpus_path: &str, strict: bool) -> Result<()> {
         .verify(&policy, &corpus)
         .with_context(|| "Prover failed")?;
 
-    // Report results
-    info!(
-        "Checks passed: {}/{}",
-        result.checks_passed, result.checks_total
-    );
-
     for violation in &result.violations {
         error!(
             "[{}] {}: {}",
             violation.severity, violation.check, violation.message
         );
     }
-
     for warning in &result.warnings {
         warn!(
             "[{}] {}: {}",
@@ -54,23 +121,64 @@ pub fn run(policy_path: &str, corpus_path: &str, strict: bool) -> Result<()> {
         );
     }
 
-    // Determine exit status
-    if result.is_rejected() {
-        anyhow::bail!(
-            "Policy verification failed with {} violation(s)",
-            result.violations.len()
-        );
-    }
+    let success = !result.is_rejected() && !(strict && !result.warnings.is_empty());
+
+    Ok(ProveResult {
+        success,
+        policy_path: policy_path.to_string(),
+        corpus_path: corpus_path.to_string(),
+        corpus_size,
+        checks_passed: result.checks_passed,
+        checks_total: result.checks_total,
+        violations: result.violations,
+        warnings: result.warnings,
+        strict,
+        tsa_gen_time,
+        tsa_warning,
+    })
+}
 
-    if strict && !result.warnings.is_empty() {
-        anyhow::bail!(
-            "Policy verification failed with {} warning(s) (strict mode)",
-            result.warnings.len()
-        );
+/// Re-verifies the lockfile's trusted timestamp token, if one is present,
+/// against the policy's current compiled output.
+///
+/// Missing lockfiles or lockfiles without a TSA token are not an error -
+/// the timestamp is an optional seal, not a requirement. A digest that no
+/// longer matches the token's `messageImprint` is reported as a warning
+/// (or a hard failure under `--strict`, matching this command's existing
+/// warning-escalation convention). Returns the verified `genTime` and/or a
+/// drift warning message for inclusion in [`ProveResult`].
+fn check_trusted_timestamp(
+    policy_path: &str,
+    policy: &toon_policy::Policy,
+    strict: bool,
+) -> Result<(Option<String>, Option<String>)> {
+    let lock_path = format!("{}.lock", policy_path.trim_end_matches(".toon"));
+    let lock_path = Path::new(&lock_path);
+
+    if !lock_path.exists() {
+        return Ok((None, None));
     }
 
-    info!("Policy verification passed!");
-    Ok(())
+    let lockfile = Lockfile::load(lock_path)
+        .with_context(|| format!("Failed to read lockfile: {}", lock_path.display()))?;
+
+    let compiler = Compiler::new();
+    let output = compiler
+        .compile(policy)
+        .with_context(|| "Failed to compile policy")?;
+
+    match lockfile.verify_tsa_token(&output) {
+        Ok(Some(token)) => Ok((Some(token.gen_time.unwrap_or_else(|| "unknown".to_string())), None)),
+        Ok(None) => Ok((None, None)),
+        Err(e) => {
+            if strict {
+                anyhow::bail!("Trusted timestamp verification failed (strict mode): {e}");
+            }
+            let message = format!("Trusted timestamp verification failed: {e}");
+            warn!("{}", message);
+            Ok((None, Some(message)))
+        }
+    }
 }
 
 fn load_corpus(path: &str) -> Result<Corpus> {
@@ -0,0 +1,349 @@
+//! Serve command implementation.
+//!
+//! Exposes the pure `Compiler` and `Prover` over HTTP so non-Rust tooling
+//! (dashboards, CI checks, a browser playground) can call them without
+//! shelling out to the CLI. As with [`super::watch::run_otlp_receiver`],
+//! there's no general-purpose HTTP server dependency in this binary, so
+//! this hand-rolls just enough of HTTP/1.1 to serve a handful of routes.
+
+use anyhow::{Context, Result};
+use nectar_compiler::{CompileOptions, Compiler, OutputFormat as RulesFormat};
+use nectar_corpus::{Corpus, Trace};
+use nectar_prover::{Prover, ProverConfig};
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, error, info, warn};
+
+/// Server configuration.
+#[derive(Debug, Clone)]
+pub struct ServeConfig {
+    /// Port to listen on.
+    pub port: u16,
+    /// Value for `Access-Control-Allow-Origin`, if CORS should be enabled.
+    pub cors_origin: Option<String>,
+}
+
+/// Request body for `POST /compile`.
+#[derive(Debug, Deserialize)]
+struct CompileRequest {
+    /// Policy source, always TOON. Sent as the whole raw request body, or
+    /// as this field of a JSON body when the request's `Content-Type` is
+    /// `application/json`.
+    policy: String,
+}
+
+/// Request body for `POST /verify`.
+#[derive(Debug, Deserialize)]
+struct VerifyRequest {
+    /// Policy source, same convention as [`CompileRequest::policy`].
+    policy: String,
+    /// Trace corpus to verify against.
+    #[serde(default)]
+    corpus: Vec<Trace>,
+}
+
+/// Runs the HTTP server. Never returns under normal operation; stops on
+/// `Ctrl+C` or a fatal bind error.
+pub async fn run(config: ServeConfig) -> Result<()> {
+    let addr = format!("0.0.0.0:{}", config.port);
+    let listener = TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("Failed to bind server on {addr}"))?;
+
+    info!("Nectar server listening on {}", addr);
+    if let Some(origin) = &config.cors_origin {
+        info!("CORS enabled for origin: {}", origin);
+    }
+
+    let config = Arc::new(config);
+
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, peer) = match accepted {
+                    Ok(pair) => pair,
+                    Err(e) => {
+                        warn!("Server accept error: {}", e);
+                        continue;
+                    }
+                };
+
+                let config = Arc::clone(&config);
+                tokio::spawn(async move {
+                    if let Err(e) = handle_connection(stream, &config).await {
+                        debug!("Connection from {} failed: {}", peer, e);
+                    }
+                });
+            }
+
+            _ = tokio::signal::ctrl_c() => {
+                info!("Received shutdown signal");
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Upper bound on a request's `Content-Length`, guarding against a remote
+/// client claiming a huge body and trickling bytes to force unbounded
+/// buffer growth.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// Reads one HTTP/1.1 request off `stream`, routes it, and writes the
+/// response.
+async fn handle_connection(mut stream: TcpStream, config: &ServeConfig) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            anyhow::bail!("connection closed before headers were complete");
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = find_header_end(&buf) {
+            break pos;
+        }
+        if buf.len() > 64 * 1024 {
+            anyhow::bail!("request headers too large");
+        }
+    };
+
+    let headers = std::str::from_utf8(&buf[..header_end]).unwrap_or_default();
+    let mut lines = headers.lines();
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let content_length = parse_header(headers, "content-length")
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let wants_json = parse_header(headers, "accept")
+        .is_some_and(|v| v.eq_ignore_ascii_case("application/json"));
+    let content_type_json = parse_header(headers, "content-type")
+        .is_some_and(|v| v.to_lowercase().contains("application/json"));
+
+    if content_length > MAX_BODY_SIZE {
+        let response = Response::new(413, json!({"error": "request body too large"}));
+        write_response(&mut stream, &response, config).await?;
+        return Ok(());
+    }
+
+    let body_start = header_end + 4;
+    while buf.len() < body_start + content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            anyhow::bail!("connection closed before body was complete");
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    let body = &buf[body_start..body_start + content_length];
+
+    let response = if method.eq_ignore_ascii_case("OPTIONS") {
+        Response::new(204, json!(null))
+    } else {
+        match (method.as_str(), path.as_str()) {
+            ("GET", "/healthz") => Response::new(200, json!({"status": "ok"})),
+            ("POST", "/compile") => handle_compile(body, content_type_json, wants_json),
+            ("POST", "/verify") => handle_verify(body, content_type_json),
+            _ => Response::new(404, json!({"error": "not found"})),
+        }
+    };
+
+    write_response(&mut stream, &response, config).await?;
+    Ok(())
+}
+
+/// Handles `POST /compile`: reads the TOON policy (the raw body, or the
+/// `policy` field of a JSON body when `Content-Type: application/json`),
+/// compiles it, and returns the
+/// Refinery output in the format negotiated via `Accept`.
+fn handle_compile(body: &[u8], content_type_json: bool, wants_json: bool) -> Response {
+    let request: CompileRequest = match parse_body(body, content_type_json) {
+        Ok(req) => req,
+        Err(e) => return Response::new(422, json!({"error": e.to_string()})),
+    };
+
+    let policy = match toon_policy::parse(&request.policy) {
+        Ok(policy) => policy,
+        Err(e) => return Response::new(422, json!({"error": format!("invalid policy: {e}")})),
+    };
+
+    let format = if wants_json { RulesFormat::Json } else { RulesFormat::Yaml };
+    let compiler = Compiler::with_options(CompileOptions {
+        format,
+        include_comments: true,
+    });
+
+    match compiler.compile(&policy) {
+        Ok(output) => Response::new(200, json!({"rules": output})),
+        Err(e) => compile_error_response(&e),
+    }
+}
+
+/// Handles `POST /verify`: parses the policy and corpus and returns the
+/// `ProverResult` as JSON.
+fn handle_verify(body: &[u8], content_type_json: bool) -> Response {
+    let request: VerifyRequest = match parse_body(body, content_type_json) {
+        Ok(req) => req,
+        Err(e) => return Response::new(422, json!({"error": e.to_string()})),
+    };
+
+    let policy = match toon_policy::parse(&request.policy) {
+        Ok(policy) => policy,
+        Err(e) => return Response::new(422, json!({"error": format!("invalid policy: {e}")})),
+    };
+
+    let mut corpus = Corpus::new();
+    for trace in request.corpus {
+        corpus.add(trace);
+    }
+
+    let prover = Prover::new(ProverConfig::default());
+    match prover.verify(&policy, &corpus) {
+        Ok(result) => Response::new(200, serde_json::to_value(result).unwrap_or(json!(null))),
+        Err(e) => prove_error_response(&e),
+    }
+}
+
+/// Parses a request body either as JSON (`{"policy": ..., ...}`) or, when
+/// the client didn't send JSON, as a raw TOON policy body.
+fn parse_body<T>(body: &[u8], content_type_json: bool) -> Result<T>
+where
+    T: for<'de> Deserialize<'de> + FromToonBody,
+{
+    if content_type_json {
+        serde_json::from_slice(body).context("invalid JSON request body")
+    } else {
+        let text = std::str::from_utf8(body).context("request body is not valid UTF-8")?;
+        Ok(T::from_toon_body(text))
+    }
+}
+
+/// Builds a request type from a raw TOON-policy request body (no JSON
+/// envelope - the whole body is the `policy` field).
+trait FromToonBody {
+    fn from_toon_body(body: &str) -> Self;
+}
+
+impl FromToonBody for CompileRequest {
+    fn from_toon_body(body: &str) -> Self {
+        Self { policy: body.to_string() }
+    }
+}
+
+impl FromToonBody for VerifyRequest {
+    fn from_toon_body(body: &str) -> Self {
+        Self { policy: body.to_string(), corpus: Vec::new() }
+    }
+}
+
+/// Maps a compiler error to an HTTP response, giving validation failures
+/// a 422 and anything else a 500.
+fn compile_error_response(error: &nectar_compiler::Error) -> Response {
+    use nectar_compiler::Error;
+    match error {
+        Error::Unsupported(_)
+        | Error::InvalidMatch { .. }
+        | Error::TypeMismatch { .. }
+        | Error::ClauseLimitExceeded { .. }
+        | Error::RefineryImport { .. } => {
+            Response::new(422, json!({"error": error.to_string()}))
+        }
+        Error::Serialization(_) | Error::Yaml(_) | Error::Json(_) => {
+            error!("Compile request failed: {}", error);
+            Response::new(500, json!({"error": "internal compiler error"}))
+        }
+    }
+}
+
+/// Maps a prover error to an HTTP response.
+fn prove_error_response(error: &nectar_prover::Error) -> Response {
+    use nectar_prover::Error;
+    match error {
+        Error::InvalidPolicy(_)
+        | Error::InvalidCorpus(_)
+        | Error::InvalidTraffic(_)
+        | Error::Policy(_) => {
+            Response::new(422, json!({"error": error.to_string()}))
+        }
+        Error::SimulationError(_) | Error::Io(_) | Error::Internal(_) => {
+            error!("Verify request failed: {}", error);
+            Response::new(500, json!({"error": "internal prover error"}))
+        }
+    }
+}
+
+/// A pending HTTP response: status code plus a JSON body.
+struct Response {
+    status: u16,
+    body: serde_json::Value,
+}
+
+impl Response {
+    const fn new(status: u16, body: serde_json::Value) -> Self {
+        Self { status, body }
+    }
+}
+
+/// Writes `response` to `stream` as a complete HTTP/1.1 reply, including
+/// CORS headers when `config.cors_origin` is set.
+async fn write_response(stream: &mut TcpStream, response: &Response, config: &ServeConfig) -> Result<()> {
+    let reason = status_reason(response.status);
+    let body = if response.body.is_null() {
+        String::new()
+    } else {
+        serde_json::to_string(&response.body).unwrap_or_default()
+    };
+
+    let mut head = format!(
+        "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        body.len()
+    );
+
+    if let Some(origin) = &config.cors_origin {
+        head.push_str(&format!("Access-Control-Allow-Origin: {origin}\r\n"));
+        head.push_str("Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n");
+        head.push_str("Access-Control-Allow-Headers: Content-Type, Accept\r\n");
+    }
+
+    head.push_str("\r\n");
+
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    Ok(())
+}
+
+const fn status_reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        404 => "Not Found",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Finds the index of the `\r\n\r\n` separator between headers and body.
+fn find_header_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n")
+}
+
+/// Extracts a header value by (case-insensitive) name.
+fn parse_header(headers: &str, name: &str) -> Option<String> {
+    headers.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        if key.trim().eq_ignore_ascii_case(name) {
+            Some(value.trim().to_string())
+        } else {
+            None
+        }
+    })
+}
@@ -7,10 +7,13 @@
 
 use anyhow::{Context, Result};
 use nectar_corpus::{Corpus, Reservoir, ReservoirConfig, SamplingStrategy};
+use notify::Watcher as _;
 use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::mpsc;
 use tokio::time::interval;
 use tracing::{debug, error, info, warn};
@@ -42,6 +45,10 @@ pub struct WatchConfig {
     pub preserve_errors: bool,
     /// Threshold for slow traces (in milliseconds).
     pub slow_threshold_ms: Option<u64>,
+    /// How edits to `policy_path` are detected for automatic reload.
+    pub reload_method: WatchConfigMethod,
+    /// Port to serve a Prometheus `/metrics` endpoint on, if set.
+    pub metrics_port: Option<u16>,
 }
 
 impl Default for WatchConfig {
@@ -59,10 +66,33 @@ impl Default for WatchConfig {
             sampling_strategy: SamplingStrategy::Stratified,
             preserve_errors: true,
             slow_threshold_ms: Some(5000), // 5 seconds
+            reload_method: WatchConfigMethod::default(),
+            metrics_port: None,
         }
     }
 }
 
+/// How the watcher detects edits to `policy_path` so it can reload
+/// automatically, without waiting for a SIGHUP.
+#[derive(Debug, Clone)]
+pub enum WatchConfigMethod {
+    /// Stat the policy file's mtime on this interval and reload on
+    /// change. More robust than `Notify` on networked filesystems where
+    /// inotify events can be dropped, at the cost of up to one interval's
+    /// worth of reload latency.
+    Poll(Duration),
+    /// Watch the policy file's parent directory for filesystem events
+    /// and reload as soon as a matching event arrives, debounced to
+    /// coalesce editor write-rename storms.
+    Notify,
+}
+
+impl Default for WatchConfigMethod {
+    fn default() -> Self {
+        Self::Notify
+    }
+}
+
 /// Events that can occur during watch mode.
 #[derive(Debug, Clone)]
 pub enum WatchEvent {
@@ -74,6 +104,9 @@ pub enum WatchEvent {
     BudgetViolation(BudgetViolationReport),
     /// Refinement suggestion generated.
     RefinementSuggested(RefinementSuggestion),
+    /// Policy was reloaded from disk (via SIGHUP, filesystem notify, or
+    /// mtime polling), either successfully or with a parse error.
+    PolicyReloaded(std::result::Result<toon_policy::Policy, String>),
     /// Error occurred.
     Error(String),
     /// Shutdown requested.
@@ -130,6 +163,10 @@ pub struct WatchState {
     pub budget_violations: u64,
     /// Start time.
     pub started_at: chrono::DateTime<chrono::Utc>,
+    /// The policy currently loaded from `WatchConfig::policy_path`, used
+    /// as the baseline `check_for_drift` compares traffic against.
+    /// Replaced in place by a SIGHUP-triggered reload.
+    pub policy: Option<toon_policy::Policy>,
 }
 
 impl WatchState {
@@ -148,10 +185,144 @@ impl WatchState {
             drift_events: 0,
             budget_violations: 0,
             started_at: chrono::Utc::now(),
+            policy: read_and_parse_policy(&config.policy_path).ok(),
+        }
+    }
+}
+
+/// Backoff parameters for supervised input-source tasks (see
+/// `Watcher::spawn_supervised`).
+#[derive(Debug, Clone, Copy)]
+struct SupervisionPolicy {
+    /// Backoff before the first restart.
+    initial_backoff: Duration,
+    /// Backoff is doubled after each unhealthy exit, capped at this.
+    max_backoff: Duration,
+    /// A task that stays up at least this long before exiting is
+    /// considered healthy again, resetting the backoff and retry count.
+    healthy_after: Duration,
+    /// Maximum number of consecutive restarts after unhealthy exits; the
+    /// next unhealthy exit gives up restarting (and emits a final
+    /// `WatchEvent::Error`).
+    max_retries: u32,
+}
+
+impl Default for SupervisionPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+            healthy_after: Duration::from_secs(60),
+            max_retries: 10,
         }
     }
 }
 
+/// Live counters/gauges backing the `/metrics` endpoint. Kept in sync with
+/// `WatchState` by `Watcher::sync_metrics`, called on every
+/// `handle_event`/`check_for_drift` tick, and read concurrently by the
+/// metrics HTTP server task - hence the atomics rather than plain fields.
+#[derive(Debug)]
+struct WatchMetrics {
+    started_at_unix: i64,
+    total_seen: AtomicU64,
+    current_size: AtomicU64,
+    error_count: AtomicU64,
+    slow_count: AtomicU64,
+    eviction_count: AtomicU64,
+    drift_events: AtomicU64,
+    budget_violations: AtomicU64,
+}
+
+impl WatchMetrics {
+    fn new() -> Self {
+        Self {
+            started_at_unix: chrono::Utc::now().timestamp(),
+            total_seen: AtomicU64::new(0),
+            current_size: AtomicU64::new(0),
+            error_count: AtomicU64::new(0),
+            slow_count: AtomicU64::new(0),
+            eviction_count: AtomicU64::new(0),
+            drift_events: AtomicU64::new(0),
+            budget_violations: AtomicU64::new(0),
+        }
+    }
+
+    /// Renders the current values in Prometheus text exposition format.
+    fn render(&self) -> String {
+        use std::fmt::Write as _;
+        use std::sync::atomic::Ordering::Relaxed;
+
+        let uptime_secs = (chrono::Utc::now().timestamp() - self.started_at_unix).max(0);
+        let mut out = String::new();
+
+        let mut metric = |out: &mut String, name: &str, kind: &str, help: &str, value: u64| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "{name} {value}");
+        };
+
+        metric(
+            &mut out,
+            "nectar_watch_uptime_seconds",
+            "gauge",
+            "Seconds since the watcher started.",
+            u64::try_from(uptime_secs).unwrap_or(0),
+        );
+        metric(
+            &mut out,
+            "nectar_watch_traces_seen_total",
+            "counter",
+            "Traces seen by the reservoir.",
+            self.total_seen.load(Relaxed),
+        );
+        metric(
+            &mut out,
+            "nectar_watch_reservoir_size",
+            "gauge",
+            "Traces currently held in the reservoir.",
+            self.current_size.load(Relaxed),
+        );
+        metric(
+            &mut out,
+            "nectar_watch_error_traces",
+            "gauge",
+            "Error traces currently retained in the reservoir.",
+            self.error_count.load(Relaxed),
+        );
+        metric(
+            &mut out,
+            "nectar_watch_slow_traces",
+            "gauge",
+            "Slow traces currently retained in the reservoir.",
+            self.slow_count.load(Relaxed),
+        );
+        metric(
+            &mut out,
+            "nectar_watch_evictions_total",
+            "counter",
+            "Traces evicted from the reservoir.",
+            self.eviction_count.load(Relaxed),
+        );
+        metric(
+            &mut out,
+            "nectar_watch_drift_events_total",
+            "counter",
+            "Policy drift events detected.",
+            self.drift_events.load(Relaxed),
+        );
+        metric(
+            &mut out,
+            "nectar_watch_budget_violations_total",
+            "counter",
+            "Budget violations detected.",
+            self.budget_violations.load(Relaxed),
+        );
+
+        out
+    }
+}
+
 /// The main watcher for continuous policy monitoring.
 pub struct Watcher {
     config: WatchConfig,
@@ -159,6 +330,12 @@ pub struct Watcher {
     running: Arc<AtomicBool>,
     event_tx: mpsc::Sender<WatchEvent>,
     event_rx: mpsc::Receiver<WatchEvent>,
+    /// Handles of supervised input-source tasks (see
+    /// `spawn_supervised`), aborted on `shutdown`.
+    source_handles: Vec<tokio::task::JoinHandle<()>>,
+    /// Live metrics backing the `/metrics` endpoint (see
+    /// `WatchConfig::metrics_port`).
+    metrics: Arc<WatchMetrics>,
 }
 
 impl Watcher {
@@ -172,6 +349,8 @@ impl Watcher {
             running: Arc::new(AtomicBool::new(false)),
             event_tx,
             event_rx,
+            source_handles: Vec::new(),
+            metrics: Arc::new(WatchMetrics::new()),
         }
     }
 
@@ -218,9 +397,20 @@ impl Watcher {
 
         // Start input sources
         self.start_input_sources();
+        self.start_policy_reload_watcher();
+        self.start_metrics_endpoint();
 
         // Main event loop
         let mut check_interval = interval(Duration::from_secs(self.config.check_interval_secs));
+        #[cfg(unix)]
+        let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+            .context("Failed to install SIGHUP handler")?;
+
+        let mut poll_interval = match self.config.reload_method {
+            WatchConfigMethod::Poll(period) => Some(interval(period)),
+            WatchConfigMethod::Notify => None,
+        };
+        let mut last_policy_mtime = poll_policy_mtime(&self.config.policy_path);
 
         while self.running.load(Ordering::SeqCst) {
             tokio::select! {
@@ -234,6 +424,26 @@ impl Watcher {
                     self.check_for_drift();
                 }
 
+                // Reload the policy in place, without disturbing the
+                // reservoir or any counters (Unix only; see `reload_policy`).
+                #[cfg(unix)]
+                Some(()) = hangup.recv() => {
+                    self.reload_policy();
+                }
+
+                // `Poll` reload mode: stat the policy file's mtime and
+                // reload on change. A no-op future (that never resolves)
+                // in `Notify` mode, since `poll_interval` is `None`.
+                Some(()) = tick_opt(&mut poll_interval) => {
+                    let mtime = poll_policy_mtime(&self.config.policy_path);
+                    if mtime != last_policy_mtime {
+                        last_policy_mtime = mtime;
+                        info!("Detected policy file change, reloading {}", self.config.policy_path);
+                        let event = WatchEvent::PolicyReloaded(read_and_parse_policy(&self.config.policy_path));
+                        self.handle_event(event);
+                    }
+                }
+
                 // Handle Ctrl+C
                 _ = tokio::signal::ctrl_c() => {
                     info!("Received shutdown signal");
@@ -268,18 +478,22 @@ impl Watcher {
         Ok(())
     }
 
-    /// Starts input sources (OTLP receiver, Honeycomb polling, etc.).
-    fn start_input_sources(&self) {
-        // OTLP receiver (placeholder - implemented in #8)
+    /// Starts input sources (OTLP receiver, Honeycomb polling, etc.),
+    /// each supervised so a crash (socket error, API 5xx, auth expiry)
+    /// doesn't permanently blind the watcher.
+    fn start_input_sources(&mut self) {
         if let Some(port) = self.config.otlp_port {
-            info!("OTLP receiver would start on port {} (not yet implemented)", port);
-            // TODO: Start OTLP gRPC receiver (#8)
+            info!("OTLP receiver listening on port {}", port);
+            self.spawn_supervised("OTLP receiver", move |event_tx| {
+                run_otlp_receiver(port, event_tx)
+            });
         }
 
         // Honeycomb polling (placeholder - implemented in #12)
         if self.config.honeycomb_dataset.is_some() {
             info!("Honeycomb polling would start (not yet implemented)");
-            // TODO: Start Honeycomb API polling (#12)
+            // TODO: Start Honeycomb API polling, supervised like the OTLP
+            // receiver above (#12)
         }
 
         // If no input source configured, warn
@@ -288,6 +502,62 @@ impl Watcher {
         }
     }
 
+    /// Spawns `make_task` as a supervised child task: if it ever returns
+    /// (the source crashed or exited), it is restarted with exponential
+    /// backoff, reported via `WatchEvent::Error`. A task that stays up for
+    /// at least `SupervisionPolicy::healthy_after` before exiting resets
+    /// the backoff and retry count, so a single flaky restart doesn't
+    /// count against a source that's otherwise been running fine for
+    /// hours. After `SupervisionPolicy::max_retries` consecutive restarts,
+    /// the next unhealthy exit makes the supervisor give up and report a
+    /// final error.
+    fn spawn_supervised<F, Fut>(&mut self, name: &'static str, mut make_task: F)
+    where
+        F: FnMut(mpsc::Sender<WatchEvent>) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let event_tx = self.event_tx.clone();
+        let policy = SupervisionPolicy::default();
+
+        let handle = tokio::spawn(async move {
+            let mut backoff = policy.initial_backoff;
+            let mut attempts: u32 = 0;
+
+            loop {
+                let started_at = std::time::Instant::now();
+                make_task(event_tx.clone()).await;
+
+                if started_at.elapsed() >= policy.healthy_after {
+                    attempts = 0;
+                    backoff = policy.initial_backoff;
+                } else {
+                    attempts += 1;
+                }
+
+                if attempts > policy.max_retries {
+                    let _ = event_tx
+                        .send(WatchEvent::Error(format!(
+                            "{name} exited {attempts} times without staying healthy; giving up"
+                        )))
+                        .await;
+                    return;
+                }
+
+                let _ = event_tx
+                    .send(WatchEvent::Error(format!(
+                        "{name} exited unexpectedly, restarting in {backoff:?} (attempt {attempts}/{})",
+                        policy.max_retries
+                    )))
+                    .await;
+
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(policy.max_backoff);
+            }
+        });
+
+        self.source_handles.push(handle);
+    }
+
     /// Handles a watch event.
     fn handle_event(&mut self, event: WatchEvent) {
         match event {
@@ -303,6 +573,13 @@ impl Watcher {
             WatchEvent::RefinementSuggested(suggestion) => {
                 self.handle_suggestion(&suggestion);
             }
+            WatchEvent::PolicyReloaded(Ok(policy)) => {
+                self.state.policy = Some(policy);
+                info!("Policy reloaded from {}", self.config.policy_path);
+            }
+            WatchEvent::PolicyReloaded(Err(reason)) => {
+                error!("Policy reload failed, keeping previous policy: {}", reason);
+            }
             WatchEvent::Error(msg) => {
                 error!("Watch error: {}", msg);
             }
@@ -311,6 +588,8 @@ impl Watcher {
                 self.running.store(false, Ordering::SeqCst);
             }
         }
+
+        self.sync_metrics();
     }
 
     /// Handles a new trace using reservoir sampling.
@@ -380,6 +659,51 @@ impl Watcher {
         }
     }
 
+    /// Re-reads `self.config.policy_path` from disk and replaces the
+    /// baseline `check_for_drift` compares against, in response to SIGHUP.
+    ///
+    /// Only `WatchState::policy` is replaced - `reservoir`, `drift_events`,
+    /// and `budget_violations` are left untouched, so a reload never loses
+    /// in-memory trace exemplars or counters.
+    #[cfg(unix)]
+    fn reload_policy(&mut self) {
+        info!("Received SIGHUP, reloading policy from {}", self.config.policy_path);
+        let event = WatchEvent::PolicyReloaded(read_and_parse_policy(&self.config.policy_path));
+        self.handle_event(event);
+    }
+
+    /// Starts a background task that watches `policy_path` for change and
+    /// feeds a reload through the same channel a SIGHUP would, per
+    /// `self.config.reload_method`.
+    fn start_policy_reload_watcher(&self) {
+        match &self.config.reload_method {
+            WatchConfigMethod::Poll(_) => {
+                // Handled by the `poll_interval` branch in the `select!`
+                // loop in `run`, which already has access to `&mut self`.
+            }
+            WatchConfigMethod::Notify => {
+                let policy_path = self.config.policy_path.clone();
+                let event_tx = self.event_tx.clone();
+                tokio::task::spawn_blocking(move || watch_policy_with_notify(&policy_path, &event_tx));
+            }
+        }
+    }
+
+    /// Starts the Prometheus `/metrics` endpoint, if `metrics_port` is
+    /// configured. Supervised like the other input sources, so a bind
+    /// failure (e.g. port already in use) is retried with backoff.
+    fn start_metrics_endpoint(&mut self) {
+        let Some(port) = self.config.metrics_port else {
+            return;
+        };
+
+        info!("Metrics endpoint listening on port {}", port);
+        let metrics = self.metrics.clone();
+        self.spawn_supervised("metrics endpoint", move |event_tx| {
+            serve_metrics(port, metrics.clone(), event_tx)
+        });
+    }
+
     /// Checks for policy drift.
     #[allow(clippy::unused_self)] // Will use self when #10 is implemented
     fn check_for_drift(&self) {
@@ -390,12 +714,43 @@ impl Watcher {
         // - Compare current traffic patterns against policy rules
         // - Detect rules that no longer match
         // - Detect new patterns not covered by policy
+
+        self.sync_metrics();
+    }
+
+    /// Copies the latest reservoir/drift/budget counters into
+    /// `self.metrics`, which backs the `/metrics` endpoint.
+    fn sync_metrics(&self) {
+        let stats = self.state.reservoir.stats();
+        self.metrics.total_seen.store(stats.total_seen, Ordering::Relaxed);
+        self.metrics
+            .current_size
+            .store(stats.current_size as u64, Ordering::Relaxed);
+        self.metrics
+            .error_count
+            .store(stats.error_count as u64, Ordering::Relaxed);
+        self.metrics
+            .slow_count
+            .store(stats.slow_count as u64, Ordering::Relaxed);
+        self.metrics
+            .eviction_count
+            .store(stats.eviction_count, Ordering::Relaxed);
+        self.metrics
+            .drift_events
+            .store(self.state.drift_events, Ordering::Relaxed);
+        self.metrics
+            .budget_violations
+            .store(self.state.budget_violations, Ordering::Relaxed);
     }
 
     /// Performs cleanup on shutdown.
     fn shutdown(&self) {
         info!("Shutting down watch mode...");
 
+        for handle in &self.source_handles {
+            handle.abort();
+        }
+
         let uptime = chrono::Utc::now() - self.state.started_at;
         let stats = self.state.reservoir.stats();
 
@@ -419,6 +774,294 @@ impl Watcher {
     }
 }
 
+/// Binds a single dual-stack listener on `[::]:port` that accepts both
+/// native IPv6 connections and IPv4-mapped ones, rather than assuming
+/// IPv4-only - the common deployment has collectors and instrumented
+/// services reaching the receiver over mixed network stacks.
+///
+/// Plain `TcpListener::bind` leaves `IPV6_V6ONLY` at the OS default (which
+/// varies by platform), so this goes through `socket2` to clear it
+/// explicitly before handing the socket to Tokio.
+fn bind_dual_stack(port: u16) -> std::io::Result<TcpListener> {
+    use socket2::{Domain, Socket, Type};
+
+    let socket = Socket::new(Domain::IPV6, Type::STREAM, None)?;
+    socket.set_only_v6(false)?;
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+
+    let addr: std::net::SocketAddr = (std::net::Ipv6Addr::UNSPECIFIED, port).into();
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+
+    TcpListener::from_std(socket.into())
+}
+
+/// Serves the Prometheus `/metrics` endpoint: every connection gets the
+/// current `metrics` snapshot rendered as text, regardless of request path
+/// or method - there's only one thing to scrape.
+async fn serve_metrics(port: u16, metrics: Arc<WatchMetrics>, event_tx: mpsc::Sender<WatchEvent>) {
+    let listener = match bind_dual_stack(port) {
+        Ok(listener) => listener,
+        Err(e) => {
+            let _ = event_tx
+                .send(WatchEvent::Error(format!(
+                    "Failed to bind metrics endpoint on port {port}: {e}"
+                )))
+                .await;
+            return;
+        }
+    };
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(pair) => pair,
+            Err(e) => {
+                warn!("Metrics endpoint accept error: {}", e);
+                continue;
+            }
+        };
+
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_metrics_connection(stream, &metrics).await {
+                debug!("Metrics connection from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
+
+/// Handles a single `/metrics` scrape: reads (and discards) the request,
+/// then writes back the current metrics snapshot as the response body.
+async fn handle_metrics_connection(mut stream: TcpStream, metrics: &WatchMetrics) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+
+    loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            anyhow::bail!("connection closed before headers were complete");
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if find_header_end(&buf).is_some() {
+            break;
+        }
+        if buf.len() > 8 * 1024 {
+            anyhow::bail!("request headers too large");
+        }
+    }
+
+    let body = metrics.render();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+/// Runs a minimal OTLP/HTTP receiver, accepting `ExportTraceServiceRequest`
+/// protobuf bodies and forwarding each decoded trace as a `WatchEvent`.
+///
+/// This implements just enough of HTTP/1.1 to serve the OTLP/HTTP trace
+/// export endpoint (a single `POST` with a `Content-Length` body) - there's
+/// no general-purpose HTTP server dependency in this binary, so collectors
+/// and the OpenTelemetry Collector's OTLP/HTTP exporter are the intended
+/// clients, not browsers. The listener itself is dual-stack (see
+/// `bind_dual_stack`) so one port serves both IPv4 and IPv6 clients.
+async fn run_otlp_receiver(port: u16, event_tx: mpsc::Sender<WatchEvent>) {
+    let listener = match bind_dual_stack(port) {
+        Ok(listener) => listener,
+        Err(e) => {
+            let _ = event_tx
+                .send(WatchEvent::Error(format!(
+                    "Failed to bind OTLP receiver on port {port}: {e}"
+                )))
+                .await;
+            return;
+        }
+    };
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(pair) => pair,
+            Err(e) => {
+                warn!("OTLP receiver accept error: {}", e);
+                continue;
+            }
+        };
+
+        let tx = event_tx.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_otlp_connection(stream, &tx).await {
+                debug!("OTLP connection from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
+
+/// Upper bound on a request's `Content-Length`, guarding against a remote
+/// client claiming a huge body and trickling bytes to force unbounded
+/// buffer growth.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// Handles a single OTLP/HTTP request: reads headers and body, decodes the
+/// body as an OTLP trace export, and forwards each trace to the watcher.
+async fn handle_otlp_connection(
+    mut stream: TcpStream,
+    event_tx: &mpsc::Sender<WatchEvent>,
+) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            anyhow::bail!("connection closed before headers were complete");
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = find_header_end(&buf) {
+            break pos;
+        }
+        if buf.len() > 64 * 1024 {
+            anyhow::bail!("request headers too large");
+        }
+    };
+
+    let headers = std::str::from_utf8(&buf[..header_end]).unwrap_or_default();
+    let content_length = parse_content_length(headers).unwrap_or(0);
+    if content_length > MAX_BODY_SIZE {
+        anyhow::bail!("request body of {content_length} bytes exceeds {MAX_BODY_SIZE} byte cap");
+    }
+    let body_start = header_end + 4; // past the blank line separating headers and body
+
+    while buf.len() < body_start + content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            anyhow::bail!("connection closed before body was complete");
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let body = &buf[body_start..body_start + content_length];
+
+    match Corpus::ingest_with_content_type(body, Some("application/x-protobuf")) {
+        Ok(corpus) => {
+            let count = corpus.len();
+            for trace in corpus.into_traces() {
+                let _ = event_tx.send(WatchEvent::TraceReceived(trace)).await;
+            }
+            debug!("OTLP receiver ingested {} trace(s)", count);
+        }
+        Err(e) => {
+            warn!("OTLP receiver failed to decode request: {}", e);
+        }
+    }
+
+    // An empty body is a valid (empty) `ExportTraceServiceResponse`.
+    let response = b"HTTP/1.1 200 OK\r\nContent-Type: application/x-protobuf\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+    stream.write_all(response).await?;
+    Ok(())
+}
+
+/// Reads and parses the policy at `path`. Shared by every reload path
+/// (SIGHUP, filesystem notify, and mtime polling) so they all fail and
+/// succeed identically.
+fn read_and_parse_policy(path: &str) -> std::result::Result<toon_policy::Policy, String> {
+    let source =
+        std::fs::read_to_string(path).map_err(|e| format!("failed to read policy file: {e}"))?;
+    toon_policy::parse(&source).map_err(|e| format!("failed to parse policy: {e}"))
+}
+
+/// Returns the policy file's last-modified time, or `None` if it can't be
+/// stat'd (e.g. it doesn't exist yet).
+fn poll_policy_mtime(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).ok()?.modified().ok()
+}
+
+/// Resolves to `Some(())` when `interval` ticks, or never resolves if
+/// `interval` is `None` - lets a `tokio::select!` branch be conditionally
+/// enabled depending on the configured reload method.
+async fn tick_opt(interval: &mut Option<tokio::time::Interval>) -> Option<()> {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+            Some(())
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Watches `policy_path`'s parent directory for filesystem events
+/// (debounced to coalesce editor write-rename storms), and sends a
+/// reload through `event_tx` whenever an event touches `policy_path`.
+///
+/// Runs on a blocking thread: the underlying `notify` watcher delivers
+/// events over a synchronous channel, so this is driven by a blocking
+/// `recv` loop rather than async code.
+fn watch_policy_with_notify(policy_path: &str, event_tx: &mpsc::Sender<WatchEvent>) {
+    let path = Path::new(policy_path);
+    let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) else {
+        warn!("Policy path '{}' has no parent directory to watch", policy_path);
+        return;
+    };
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut debouncer = match notify_debouncer_mini::new_debouncer(Duration::from_millis(200), tx) {
+        Ok(debouncer) => debouncer,
+        Err(e) => {
+            error!("Failed to start policy file watcher: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = debouncer
+        .watcher()
+        .watch(parent, notify::RecursiveMode::NonRecursive)
+    {
+        error!("Failed to watch '{}': {}", parent.display(), e);
+        return;
+    }
+
+    for result in rx {
+        let events = match result {
+            Ok(events) => events,
+            Err(e) => {
+                warn!("Policy file watcher error: {:?}", e);
+                continue;
+            }
+        };
+
+        if !events.iter().any(|event| event.path == path) {
+            continue;
+        }
+
+        info!("Detected policy file change via notify, reloading {}", policy_path);
+        let reload = WatchEvent::PolicyReloaded(read_and_parse_policy(policy_path));
+        if event_tx.blocking_send(reload).is_err() {
+            break;
+        }
+    }
+}
+
+/// Finds the index of the `\r\n\r\n` separator between headers and body.
+fn find_header_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n")
+}
+
+/// Extracts the `Content-Length` header value, if present.
+fn parse_content_length(headers: &str) -> Option<usize> {
+    headers.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            value.trim().parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
 /// Runs the watch command.
 #[allow(clippy::too_many_arguments)]
 pub async fn run(
@@ -465,6 +1108,8 @@ pub async fn run(
         sampling_strategy: SamplingStrategy::Stratified,
         preserve_errors: true,
         slow_threshold_ms: Some(5000), // 5 seconds
+        reload_method: WatchConfigMethod::default(),
+        metrics_port: None,
     };
 
     let mut watcher = Watcher::new(config);
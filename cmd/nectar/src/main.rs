@@ -5,12 +5,15 @@
 //! - `nectar prove` - Verify policy against corpus
 //! - `nectar propose` - Generate policy from intent (uses Claude)
 //! - `nectar explain` - Generate waggle report
+//! - `nectar lint` - Check a policy for non-fatal issues
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use output::OutputFormat;
 use tracing_subscriber::EnvFilter;
 
 mod commands;
+mod output;
 
 #[derive(Parser)]
 #[command(name = "nectar")]
@@ -21,6 +24,10 @@ struct Cli {
     #[arg(short, long, global = true)]
     verbose: bool,
 
+    /// Output format for command results
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    format: OutputFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -44,6 +51,11 @@ enum Commands {
         /// Create a policy.lock file for verification
         #[arg(long)]
         lock: bool,
+
+        /// RFC 3161 Time-Stamp Authority URL to seal the lockfile with a
+        /// trusted timestamp token (requires --lock)
+        #[arg(long)]
+        tsa_url: Option<String>,
     },
 
     /// Verify a policy against a trace corpus
@@ -90,12 +102,34 @@ enum Commands {
         output: String,
     },
 
+    /// Lint a TOON policy for non-fatal issues
+    Lint {
+        /// Path to policy.toon file
+        #[arg(short, long, default_value = "policy.toon")]
+        policy: String,
+
+        /// Apply suggested fixes in place
+        #[arg(long)]
+        fix: bool,
+    },
+
     /// Initialize a new Nectar project
     Init {
         /// Project name
         #[arg(default_value = ".")]
         path: String,
     },
+
+    /// Serve the compiler and prover over HTTP
+    Serve {
+        /// Port to listen on
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// Enable CORS for the given origin (e.g. for a browser playground)
+        #[arg(long)]
+        cors_origin: Option<String>,
+    },
 }
 
 #[tokio::main]
@@ -108,27 +142,81 @@ async fn main() -> Result<()> {
     } else {
         EnvFilter::new("info")
     };
-    tracing_subscriber::fmt().with_env_filter(filter).init();
+    // Progress logs always go to stderr, so stdout carries only the
+    // command's rendered result (text summary or JSON), regardless of format.
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+
+    let format = cli.format;
 
     match cli.command {
         Commands::Compile {
             policy,
             output,
-            format,
+            format: rules_format,
             lock,
-        } => commands::compile::run(&policy, &output, &format, lock),
+            tsa_url,
+        } => {
+            run_reporting(
+                format,
+                commands::compile::run(&policy, &output, &rules_format, lock, tsa_url.as_deref())
+                    .await,
+            )
+        }
         Commands::Prove {
             policy,
             corpus,
             strict,
-        } => commands::prove::run(&policy, &corpus, strict),
+        } => run_reporting(format, commands::prove::run(&policy, &corpus, strict)),
         Commands::Propose {
             intent,
             corpus,
             policy,
             output,
-        } => commands::propose::run(&intent, corpus.as_deref(), policy.as_deref(), &output).await,
-        Commands::Explain { policy, output } => commands::explain::run(&policy, &output),
-        Commands::Init { path } => commands::init::run(&path),
+        } => {
+            run_reporting(
+                format,
+                commands::propose::run(&intent, corpus.as_deref(), policy.as_deref(), &output)
+                    .await,
+            )
+        }
+        Commands::Explain { policy, output } => {
+            run_reporting(format, commands::explain::run(&policy, &output))
+        }
+        Commands::Lint { policy, fix } => run_reporting(format, commands::lint::run(&policy, fix)),
+        Commands::Init { path } => run_reporting(format, commands::init::run(&path)),
+        Commands::Serve { port, cors_origin } => {
+            commands::serve::run(commands::serve::ServeConfig { port, cors_origin }).await
+        }
+    }
+}
+
+/// Renders a command's result (text or JSON, per the global `--format`
+/// flag) and translates its success/failure into the process exit status.
+///
+/// A command that ran to completion but reports `success: false` (e.g.
+/// `prove` finding violations) still emits its full result before exiting
+/// non-zero - that's the point of structured output, versus an error that
+/// aborted before a result could be built.
+fn run_reporting<T: serde::Serialize + output::TextRender + output::HasSuccess>(
+    format: OutputFormat,
+    result: Result<T>,
+) -> Result<()> {
+    match result {
+        Ok(value) => {
+            let success = value.success();
+            output::emit(format, &value);
+            if success {
+                Ok(())
+            } else {
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            output::emit_error(format, &e);
+            std::process::exit(1);
+        }
     }
 }
@@ -0,0 +1,71 @@
+//! Output formatting for CLI commands.
+//!
+//! Each subcommand builds a serde-serializable result type, and the same
+//! value drives both the human-readable text summary and the
+//! machine-readable JSON emitted under `--format json`. This keeps CI
+//! pipelines and bots from having to scrape free-form log lines.
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Output format selected via the global `--format` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text (the default).
+    #[default]
+    Text,
+    /// Machine-readable JSON.
+    Json,
+}
+
+/// A command result that knows how to render itself as human-readable text.
+pub trait TextRender {
+    /// Prints a human-readable summary of this result to stdout.
+    fn render_text(&self);
+}
+
+/// A command result that reports whether the command it describes succeeded.
+///
+/// Distinct from an `Err` returned by a command's `run`: a command can run
+/// to completion and still report `success: false` (e.g. `prove` finding
+/// policy violations), in which case the full result is still emitted
+/// before the process exits non-zero.
+pub trait HasSuccess {
+    /// Whether the command succeeded.
+    fn success(&self) -> bool;
+}
+
+/// Emits a command result in the requested format.
+pub fn emit<T: Serialize + TextRender>(format: OutputFormat, result: &T) {
+    match format {
+        OutputFormat::Text => result.render_text(),
+        OutputFormat::Json => match serde_json::to_string_pretty(result) {
+            Ok(json) => println!("{json}"),
+            Err(e) => eprintln!("Failed to serialize result as JSON: {e}"),
+        },
+    }
+}
+
+/// Emits a fatal error (a command that did not run to completion) in the
+/// requested format, so scripted callers get a JSON object on stdout
+/// instead of having to parse prose from stderr.
+pub fn emit_error(format: OutputFormat, err: &anyhow::Error) {
+    match format {
+        OutputFormat::Text => {
+            eprintln!("Error: {err}");
+            for cause in err.chain().skip(1) {
+                eprintln!("Caused by: {cause}");
+            }
+        }
+        OutputFormat::Json => {
+            let body = serde_json::json!({
+                "success": false,
+                "error": err.to_string(),
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&body).unwrap_or_else(|_| body.to_string())
+            );
+        }
+    }
+}
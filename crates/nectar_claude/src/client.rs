@@ -4,19 +4,41 @@ use crate::error::{Error, Result};
 use crate::prompt::PromptBuilder;
 use crate::response::MessageResponse;
 use nectar_corpus::Corpus;
-use serde::Serialize;
-use toon_policy::Policy;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use toon_policy::{Policy, Rule};
 use tracing::{debug, info, warn};
 
 const API_URL: &str = "https://api.anthropic.com/v1/messages";
 const API_VERSION: &str = "2023-06-01";
 const MAX_TOKENS: u32 = 4096;
 
+/// Name of the tool Claude is forced to call to emit a structured policy.
+const EMIT_POLICY_TOOL: &str = "emit_policy";
+
+/// Async sleep hook used between retry attempts. A plain `tokio::time::sleep`
+/// call would do for production, but tests need to substitute a no-op so
+/// backoff delays don't actually slow the test suite down - the same
+/// "never call the ambient time source directly" discipline the prover
+/// crate applies to its own injectable clock for paced replay.
+type SleepFn = Arc<dyn Fn(Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+fn tokio_sleep(duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+    Box::pin(tokio::time::sleep(duration))
+}
+
 /// Claude API client for policy generation.
 pub struct Client {
     api_key: String,
     http: reqwest::Client,
     model: String,
+    retry: RetryPolicy,
+    sleep: SleepFn,
 }
 
 /// Configuration for the Claude client.
@@ -28,6 +50,8 @@ pub struct ClientConfig {
     pub model: String,
     /// Request timeout in seconds.
     pub timeout_seconds: u64,
+    /// Retry policy for rate-limited and transient API failures.
+    pub retry: RetryPolicy,
 }
 
 impl Default for ClientConfig {
@@ -36,6 +60,38 @@ impl Default for ClientConfig {
             api_key: String::new(),
             model: "claude-sonnet-4-20250514".to_string(),
             timeout_seconds: 120,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+/// Retry policy for requests that fail with a rate limit (429) or a
+/// transient (5xx/connection) error. 401s and other 4xx validation errors
+/// are never retried - see [`Client::send_request_once`].
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first. `1` disables
+    /// retrying entirely.
+    pub max_attempts: u32,
+    /// Base delay for exponential backoff, before jitter is applied.
+    pub base_delay: Duration,
+    /// Upper bound on any single backoff delay, before jitter is applied.
+    pub max_delay: Duration,
+    /// Whether to randomize each backoff delay ("full jitter": a random
+    /// duration between zero and the computed delay) instead of sleeping
+    /// for the computed delay exactly. Leave this on unless a caller
+    /// needs deterministic backoff timing (e.g. a test asserting on
+    /// exact delays).
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
         }
     }
 }
@@ -47,6 +103,9 @@ struct MessageRequest {
     max_tokens: u32,
     messages: Vec<Message>,
     system: Option<String>,
+    tools: Vec<Tool>,
+    tool_choice: ToolChoice,
+    stream: bool,
 }
 
 /// A message in the conversation.
@@ -56,6 +115,122 @@ struct Message {
     content: String,
 }
 
+/// A tool definition sent to the API.
+#[derive(Debug, Serialize)]
+struct Tool {
+    name: String,
+    description: String,
+    input_schema: serde_json::Value,
+}
+
+/// Forces the model to call a specific tool instead of responding with
+/// free text.
+#[derive(Debug, Serialize)]
+struct ToolChoice {
+    #[serde(rename = "type")]
+    choice_type: String,
+    name: String,
+}
+
+/// The structured input `emit_policy` is called with, mirroring
+/// [`toon_policy::Policy`]/[`toon_policy::Rule`] except for `match` and
+/// `action`, which arrive as the same TOON expression/action strings
+/// `toon_policy::parser` already knows how to parse and validate.
+#[derive(Debug, Deserialize)]
+struct EmitPolicyInput {
+    version: u32,
+    name: String,
+    budget_per_second: Option<u64>,
+    rules: Vec<EmitRuleInput>,
+}
+
+/// One rule within [`EmitPolicyInput`].
+#[derive(Debug, Deserialize)]
+struct EmitRuleInput {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(rename = "match")]
+    match_expr: String,
+    action: String,
+    priority: u8,
+}
+
+/// JSON schema for the `emit_policy` tool, mirroring the TOON policy
+/// model so Claude's structured output can be deserialized straight
+/// into [`EmitPolicyInput`].
+fn emit_policy_tool() -> Tool {
+    Tool {
+        name: EMIT_POLICY_TOOL.to_string(),
+        description: "Emit a Nectar sampling policy as structured data instead of TOON text."
+            .to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "version": { "type": "integer", "description": "Policy schema version" },
+                "name": { "type": "string", "description": "Human-readable policy name" },
+                "budget_per_second": {
+                    "type": ["integer", "null"],
+                    "description": "Maximum traces per second budget, if any"
+                },
+                "rules": {
+                    "type": "array",
+                    "description": "Ordered sampling rules, highest priority first",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "description": { "type": "string" },
+                            "match": {
+                                "type": "string",
+                                "description": "Match expression, e.g. \"http.status >= 500\" or \"true\" for the fallback rule"
+                            },
+                            "action": {
+                                "type": "string",
+                                "description": "One of \"keep\", \"drop\", \"sample(rate)\", or \"circuit_breaker(closed_rate,open_rate,window,failure_threshold,min_samples)\""
+                            },
+                            "priority": { "type": "integer", "minimum": 0, "maximum": 255 },
+                        },
+                        "required": ["name", "match", "action", "priority"],
+                    },
+                },
+            },
+            "required": ["version", "name", "rules"],
+        }),
+    }
+}
+
+/// Outcome of a single [`Client::send_request_once`] attempt.
+enum Attempt {
+    /// The request succeeded.
+    Success(reqwest::Response),
+    /// Rate-limited (429); retry after the server-specified delay.
+    RetryAfter(Duration, Error),
+    /// A transient (5xx/connection) failure; retry with exponential backoff.
+    Retryable(Error),
+    /// Not retryable - e.g. invalid API key or a 4xx validation error.
+    Fatal(Error),
+}
+
+/// Computes a capped exponential backoff delay for the given retry
+/// attempt (1 = first retry): `min(max_delay, base_delay * 2^(attempt -
+/// 1))`. When `jitter` is set, returns a random duration between zero and
+/// that value instead ("full jitter") - this spreads concurrent retries
+/// out instead of having them all collide on the same schedule, which
+/// plain exponential backoff without jitter doesn't.
+fn backoff_delay(attempt: u32, base_delay: Duration, max_delay: Duration, jitter: bool) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(32);
+    let factor = 2u32.checked_pow(exponent).unwrap_or(u32::MAX);
+    let capped = base_delay.saturating_mul(factor).min(max_delay);
+
+    if jitter {
+        let jitter: f64 = rand::thread_rng().gen_range(0.0..=1.0);
+        capped.mul_f64(jitter)
+    } else {
+        capped
+    }
+}
+
 impl Client {
     /// Creates a new Claude client.
     ///
@@ -75,6 +250,8 @@ impl Client {
             api_key: config.api_key,
             http,
             model: config.model,
+            retry: config.retry,
+            sleep: Arc::new(tokio_sleep),
         })
     }
 
@@ -90,8 +267,9 @@ impl Client {
     ///
     /// Returns an error if:
     /// - API request fails
-    /// - Response is not valid TOON
-    /// - Parsed policy is invalid
+    /// - Claude responds with text instead of calling `emit_policy`
+    ///   ([`Error::ClarificationNeeded`])
+    /// - The tool call's structured input is malformed or invalid
     pub async fn generate_policy(
         &self,
         intent: &str,
@@ -112,17 +290,126 @@ impl Client {
         Self::parse_policy_response(&response)
     }
 
+    /// Like [`Self::generate_policy`], but streams Claude's response
+    /// incrementally instead of waiting for the full message - useful for
+    /// large multi-rule policies, where `generate_policy` can otherwise
+    /// block for a long time with no feedback.
+    ///
+    /// Performs the request handshake synchronously (so a rate-limit or
+    /// unauthorized response is returned as a normal error, exactly as in
+    /// [`Self::generate_policy`]), then spawns a task that decodes the
+    /// server-sent-event stream and forwards [`crate::stream::StreamEvent`]s
+    /// over the returned channel: a `Delta` per token/JSON chunk, followed
+    /// by exactly one `Complete` (or an error) once `message_stop` arrives.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the initial request fails to send, is
+    /// rate-limited, is unauthorized, or otherwise fails before the stream
+    /// begins.
+    pub async fn generate_policy_stream(
+        &self,
+        intent: &str,
+        corpus: &Corpus,
+        current_policy: Option<&Policy>,
+    ) -> Result<mpsc::Receiver<Result<crate::stream::StreamEvent>>> {
+        info!("Generating policy (streaming) for intent: {}", intent);
+
+        let prompt = PromptBuilder::new()
+            .with_intent(intent)
+            .with_corpus(corpus)
+            .with_current_policy(current_policy)
+            .build()?;
+
+        debug!("Built prompt with {} chars", prompt.len());
+
+        let response = self.send_request(&prompt, true).await?;
+
+        let (tx, rx) = mpsc::channel(32);
+        tokio::spawn(crate::stream::consume(response, tx));
+        Ok(rx)
+    }
+
     async fn call_api(&self, prompt: &str) -> Result<MessageResponse> {
-        let system_prompt = r"You are a sampling policy expert for Honeycomb Refinery.
-Your task is to generate TOON-formatted sampling policies based on user requirements.
+        let response = self.send_request(prompt, false).await?;
+
+        let msg_response: MessageResponse = response
+            .json()
+            .await
+            .map_err(|e| Error::ParseError(format!("Failed to parse API response: {e}")))?;
+
+        info!(
+            "Received response: {} input tokens, {} output tokens",
+            msg_response.usage.input_tokens, msg_response.usage.output_tokens
+        );
+
+        Ok(msg_response)
+    }
+
+    /// Sends the `emit_policy` tool-call request, retrying on rate limits
+    /// and transient failures according to [`RetryPolicy`], and runs the
+    /// handshake (status checks for rate-limiting/auth/other failures)
+    /// shared by both the blocking ([`Self::call_api`]) and streaming
+    /// ([`Self::generate_policy_stream`]) paths. Returns the still-open
+    /// response so the caller can read it as a single JSON body or as an
+    /// SSE stream, depending on `stream`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::RetriesExhausted`] if every attempt fails, wrapping
+    /// the last attempt's error. 401s and other 4xx validation errors are
+    /// never retried and are returned immediately.
+    async fn send_request(&self, prompt: &str, stream: bool) -> Result<reqwest::Response> {
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            let (err, delay) = match self.send_request_once(prompt, stream).await {
+                Attempt::Success(response) => return Ok(response),
+                Attempt::Fatal(e) => return Err(e),
+                Attempt::RetryAfter(delay, e) => (e, delay.min(self.retry.max_delay)),
+                Attempt::Retryable(e) => (
+                    e,
+                    backoff_delay(
+                        attempt,
+                        self.retry.base_delay,
+                        self.retry.max_delay,
+                        self.retry.jitter,
+                    ),
+                ),
+            };
+
+            if attempt >= self.retry.max_attempts {
+                return Err(Error::RetriesExhausted {
+                    attempts: attempt,
+                    source: Box::new(err),
+                });
+            }
+
+            warn!(
+                "Attempt {} failed ({}), retrying in {:?}",
+                attempt, err, delay
+            );
+            (self.sleep)(delay).await;
+        }
+    }
+
+    /// Makes a single attempt at the `emit_policy` tool-call request,
+    /// classifying the outcome so [`Self::send_request`] knows whether and
+    /// how to retry.
+    async fn send_request_once(&self, prompt: &str, stream: bool) -> Attempt {
+        let system_prompt = r#"You are a sampling policy expert for Honeycomb Refinery.
+Your task is to design a sampling policy based on user requirements and emit it by
+calling the emit_policy tool - never describe the policy in prose or TOON text.
 
 IMPORTANT:
-- Always output valid TOON format
-- Include explicit array counts [N] that match actual items
 - Include descriptions for all rules
 - Never drop error traces (status >= 500)
-- Always include a fallback rule (match: true)
-- Order rules by priority (highest first)";
+- Always include a fallback rule (match: "true")
+- Order rules by priority (highest first)
+- If the request is ambiguous, respond with text asking for clarification instead
+  of guessing and calling the tool anyway"#;
 
         let request = MessageRequest {
             model: self.model.clone(),
@@ -132,11 +419,17 @@ IMPORTANT:
                 content: prompt.to_string(),
             }],
             system: Some(system_prompt.to_string()),
+            tools: vec![emit_policy_tool()],
+            tool_choice: ToolChoice {
+                choice_type: "tool".to_string(),
+                name: EMIT_POLICY_TOOL.to_string(),
+            },
+            stream,
         };
 
-        debug!("Sending request to Claude API");
+        debug!("Sending request to Claude API (stream: {})", stream);
 
-        let response = self
+        let response = match self
             .http
             .post(API_URL)
             .header("x-api-key", &self.api_key)
@@ -144,7 +437,13 @@ IMPORTANT:
             .header("content-type", "application/json")
             .json(&request)
             .send()
-            .await?;
+            .await
+        {
+            Ok(response) => response,
+            // A connection-level failure (timeout, DNS, reset) never got a
+            // status code at all - treat it the same as a transient 5xx.
+            Err(e) => return Attempt::Retryable(Error::Network(e)),
+        };
 
         let status = response.status();
         debug!("Received response with status: {}", status);
@@ -156,46 +455,46 @@ IMPORTANT:
                 .and_then(|v| v.to_str().ok())
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(60);
-            return Err(Error::RateLimited {
-                retry_after_seconds: retry_after,
-            });
+            return Attempt::RetryAfter(
+                Duration::from_secs(retry_after),
+                Error::RateLimited {
+                    retry_after_seconds: retry_after,
+                },
+            );
         }
 
         if status == reqwest::StatusCode::UNAUTHORIZED {
-            return Err(Error::InvalidApiKey);
+            return Attempt::Fatal(Error::InvalidApiKey);
         }
 
-        if !status.is_success() {
+        if status.is_server_error() {
             let error_text = response.text().await.unwrap_or_default();
-            return Err(Error::ApiError(format!(
+            return Attempt::Retryable(Error::ApiError(format!(
                 "API request failed with status {status}: {error_text}"
             )));
         }
 
-        let msg_response: MessageResponse = response
-            .json()
-            .await
-            .map_err(|e| Error::ParseError(format!("Failed to parse API response: {e}")))?;
-
-        info!(
-            "Received response: {} input tokens, {} output tokens",
-            msg_response.usage.input_tokens, msg_response.usage.output_tokens
-        );
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Attempt::Fatal(Error::ApiError(format!(
+                "API request failed with status {status}: {error_text}"
+            )));
+        }
 
-        Ok(msg_response)
+        Attempt::Success(response)
     }
 
-    fn parse_policy_response(response: &MessageResponse) -> Result<Policy> {
-        let toon = response
-            .extract_toon()
-            .ok_or_else(|| Error::ParseError("No TOON code block found in response".to_string()))?;
+    pub(crate) fn parse_policy_response(response: &MessageResponse) -> Result<Policy> {
+        let Some(input) = response.tool_input(EMIT_POLICY_TOOL) else {
+            let text = response.text();
+            warn!("Claude did not call emit_policy: {}", text);
+            return Err(Error::ClarificationNeeded(text));
+        };
 
-        debug!("Extracted TOON:\n{}", toon);
+        let emitted: EmitPolicyInput = serde_json::from_value(input.clone())
+            .map_err(|e| Error::ParseError(format!("Invalid emit_policy input: {e}")))?;
 
-        let policy = toon_policy::parse(&toon).map_err(|e| {
-            warn!("Failed to parse TOON: {}", e);
-            Error::ToonValidationError(format!("Invalid TOON from Claude: {e}"))
-        })?;
+        let policy = Self::build_policy(emitted)?;
 
         // Validate basic invariants
         if !policy.has_fallback() {
@@ -211,6 +510,33 @@ IMPORTANT:
         );
         Ok(policy)
     }
+
+    /// Converts an [`EmitPolicyInput`] into a validated [`Policy`],
+    /// reusing `toon_policy`'s own action grammar to parse each rule's
+    /// `action` string - the same parsing/validation a hand-written TOON
+    /// rule goes through.
+    fn build_policy(emitted: EmitPolicyInput) -> Result<Policy> {
+        let mut rules = Vec::with_capacity(emitted.rules.len());
+        for rule in emitted.rules {
+            let action = toon_policy::parse_action(&rule.action).map_err(|e| {
+                warn!("Failed to parse action from emit_policy: {}", e);
+                Error::ToonValidationError(format!("Invalid action '{}': {e}", rule.action))
+            })?;
+
+            let mut built = Rule::new(rule.name, rule.match_expr, action, rule.priority);
+            if let Some(description) = rule.description {
+                built = built.with_description(description);
+            }
+            rules.push(built);
+        }
+
+        Ok(Policy {
+            version: emitted.version,
+            name: emitted.name,
+            budget_per_second: emitted.budget_per_second,
+            rules,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -232,56 +558,103 @@ mod tests {
         assert!(Client::new(config).is_ok());
     }
 
-    #[test]
-    fn parse_policy_from_response() {
-        let response = MessageResponse {
+    fn tool_use_response(input: serde_json::Value) -> MessageResponse {
+        MessageResponse {
             id: "msg_123".to_string(),
             model: "claude-sonnet-4-20250514".to_string(),
-            stop_reason: Some("end_turn".to_string()),
-            content: vec![crate::response::ContentBlock::Text {
-                text: r"Here's the policy:
-
-```toon
-nectar_policy{version,name,budget_per_second,rules}:
-  1
-  test-policy
-  5000
-  rules[2]{name,description,match,action,priority}:
-    keep-errors,Keep all errors,http.status >= 500,keep,100
-    sample-rest,Sample remaining traffic,true,sample(0.01),0
-```
-"
-                .to_string(),
+            stop_reason: Some("tool_use".to_string()),
+            content: vec![crate::response::ContentBlock::ToolUse {
+                id: "toolu_1".to_string(),
+                name: EMIT_POLICY_TOOL.to_string(),
+                input,
             }],
             usage: crate::response::Usage {
                 input_tokens: 100,
                 output_tokens: 50,
             },
-        };
+        }
+    }
+
+    #[test]
+    fn parse_policy_from_tool_use_response() {
+        let response = tool_use_response(serde_json::json!({
+            "version": 1,
+            "name": "test-policy",
+            "budget_per_second": 5000,
+            "rules": [
+                {
+                    "name": "keep-errors",
+                    "description": "Keep all errors",
+                    "match": "http.status >= 500",
+                    "action": "keep",
+                    "priority": 100,
+                },
+                {
+                    "name": "sample-rest",
+                    "description": "Sample remaining traffic",
+                    "match": "true",
+                    "action": "sample(0.01)",
+                    "priority": 0,
+                },
+            ],
+        }));
 
         let policy = Client::parse_policy_response(&response).unwrap();
         assert_eq!(policy.name, "test-policy");
+        assert_eq!(policy.budget_per_second, Some(5000));
         assert_eq!(policy.rules.len(), 2);
         assert!(policy.has_fallback());
     }
 
     #[test]
     fn rejects_policy_without_fallback() {
+        let response = tool_use_response(serde_json::json!({
+            "version": 1,
+            "name": "test-policy",
+            "budget_per_second": 5000,
+            "rules": [
+                {
+                    "name": "keep-errors",
+                    "description": "Keep all errors",
+                    "match": "http.status >= 500",
+                    "action": "keep",
+                    "priority": 100,
+                },
+            ],
+        }));
+
+        let result = Client::parse_policy_response(&response);
+        assert!(matches!(result, Err(Error::ToonValidationError(_))));
+    }
+
+    #[test]
+    fn rejects_malformed_action_string() {
+        let response = tool_use_response(serde_json::json!({
+            "version": 1,
+            "name": "test-policy",
+            "budget_per_second": null,
+            "rules": [
+                {
+                    "name": "fallback",
+                    "match": "true",
+                    "action": "not-a-real-action",
+                    "priority": 0,
+                },
+            ],
+        }));
+
+        let result = Client::parse_policy_response(&response);
+        assert!(matches!(result, Err(Error::ToonValidationError(_))));
+    }
+
+    #[test]
+    fn surfaces_clarification_text_as_a_recoverable_error() {
         let response = MessageResponse {
             id: "msg_123".to_string(),
             model: "claude-sonnet-4-20250514".to_string(),
             stop_reason: Some("end_turn".to_string()),
             content: vec![crate::response::ContentBlock::Text {
-                text: r"```toon
-nectar_policy{version,name,budget_per_second,rules}:
-  1
-  test-policy
-  5000
-  rules[1]{name,description,match,action,priority}:
-    keep-errors,Keep all errors,http.status >= 500,keep,100
-```
-"
-                .to_string(),
+                text: "What budget_per_second should the policy use?".to_string(),
             }],
             usage: crate::response::Usage {
                 input_tokens: 100,
@@ -290,6 +663,48 @@ nectar_policy{version,name,budget_per_second,rules}:
         };
 
         let result = Client::parse_policy_response(&response);
-        assert!(matches!(result, Err(Error::ToonValidationError(_))));
+        assert!(matches!(result, Err(Error::ClarificationNeeded(ref text)) if text.contains("budget_per_second")));
+    }
+
+    #[test]
+    fn backoff_delay_never_exceeds_the_cap() {
+        let base = Duration::from_millis(100);
+        let max = Duration::from_secs(10);
+
+        for attempt in 1..=50 {
+            let delay = backoff_delay(attempt, base, max, true);
+            assert!(delay <= max, "attempt {attempt} produced {delay:?} > {max:?}");
+        }
+    }
+
+    #[test]
+    fn backoff_delay_grows_with_attempt_number() {
+        let base = Duration::from_millis(100);
+        let max = Duration::from_secs(60);
+
+        // Jitter makes any single sample noisy, so compare the upper bound
+        // each attempt number can reach rather than a single sample.
+        let bound = |attempt: u32| backoff_delay(attempt, base, max, false);
+        assert!(bound(1) < bound(4));
+        assert!(bound(4) < bound(8));
+    }
+
+    #[test]
+    fn backoff_delay_without_jitter_is_exactly_the_capped_value() {
+        let base = Duration::from_millis(100);
+        let max = Duration::from_secs(10);
+
+        assert_eq!(backoff_delay(1, base, max, false), base);
+        assert_eq!(backoff_delay(3, base, max, false), base * 4);
+        assert_eq!(backoff_delay(10, base, max, false), max);
+    }
+
+    #[test]
+    fn retries_exhausted_reports_the_attempt_count() {
+        let err = Error::RetriesExhausted {
+            attempts: 3,
+            source: Box::new(Error::InvalidApiKey),
+        };
+        assert!(err.to_string().contains('3'));
     }
 }
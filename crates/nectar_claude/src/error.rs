@@ -28,6 +28,21 @@ pub enum Error {
     #[error("TOON validation failed: {0}")]
     ToonValidationError(String),
 
+    /// Claude responded with text instead of calling the expected tool -
+    /// recoverable by re-prompting with the clarification it asked for.
+    #[error("Claude asked for clarification instead of emitting a policy: {0}")]
+    ClarificationNeeded(String),
+
+    /// Every retry attempt failed.
+    #[error("giving up after {attempts} attempt(s): {source}")]
+    RetriesExhausted {
+        /// Total attempts made, including the first.
+        attempts: u32,
+        /// The error from the final attempt.
+        #[source]
+        source: Box<Error>,
+    },
+
     /// Network error.
     #[error(transparent)]
     Network(#[from] reqwest::Error),
@@ -24,6 +24,8 @@ pub mod error;
 pub mod client;
 pub mod prompt;
 pub mod response;
+pub mod stream;
 
 pub use error::{Error, Result};
-pub use client::{Client, ClientConfig};
+pub use client::{Client, ClientConfig, RetryPolicy};
+pub use stream::StreamEvent;
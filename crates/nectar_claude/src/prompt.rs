@@ -73,25 +73,14 @@ impl<'a> PromptBuilder<'a> {
 
 {corpus_section}{current_policy_section}## Instructions
 
-Generate a Nectar sampling policy in TOON format that achieves the stated intent.
+Generate a Nectar sampling policy that achieves the stated intent and emit it by
+calling the emit_policy tool - do not describe the policy in prose or TOON text.
 
 Requirements:
-1. Use TOON format with explicit counts and field headers
-2. Include a description for each rule
-3. Ensure a fallback rule exists (match: true)
-4. Order rules by priority (highest first)
-5. Never drop error traces (status >= 500)
-
-Output only the TOON code block, nothing else.
-
-```toon
-nectar_policy{{version,name,budget_per_second,rules}}:
-  1
-  <policy-name>
-  <budget>
-  rules[N]{{name,description,match,action,priority}}:
-    <rules>
-```
+1. Include a description for each rule
+2. Ensure a fallback rule exists (match: \"true\")
+3. Order rules by priority (highest first)
+4. Never drop error traces (status >= 500)
 "
         ))
     }
@@ -109,7 +98,7 @@ mod tests {
             .unwrap();
 
         assert!(prompt.contains("Keep all errors"));
-        assert!(prompt.contains("nectar_policy"));
+        assert!(prompt.contains("emit_policy"));
     }
 
     #[test]
@@ -27,6 +27,18 @@ pub enum ContentBlock {
         /// The text content.
         text: String,
     },
+    /// A forced tool invocation - present when the request set
+    /// `tool_choice` and the model responded with structured input
+    /// instead of free text.
+    #[serde(rename = "tool_use")]
+    ToolUse {
+        /// Unique ID for this tool call.
+        id: String,
+        /// Name of the invoked tool.
+        name: String,
+        /// The tool's structured input.
+        input: serde_json::Value,
+    },
 }
 
 /// Token usage statistics.
@@ -44,42 +56,28 @@ impl MessageResponse {
     pub fn text(&self) -> String {
         self.content
             .iter()
-            .map(|block| match block {
-                ContentBlock::Text { text } => text.as_str(),
+            .filter_map(|block| match block {
+                ContentBlock::Text { text } => Some(text.as_str()),
+                ContentBlock::ToolUse { .. } => None,
             })
             .collect::<Vec<_>>()
             .join("")
     }
 
-    /// Extracts TOON code blocks from the response.
+    /// Returns the input of the first `tool_use` block invoking `name`,
+    /// if the model called that tool.
     #[must_use]
-    pub fn extract_toon(&self) -> Option<String> {
-        let text = self.text();
-        
-        // Look for ```toon ... ``` blocks
-        if let Some(start) = text.find("```toon") {
-            let content_start = start + 7;
-            if let Some(end) = text[content_start..].find("```") {
-                let toon = text[content_start..content_start + end].trim();
-                return Some(toon.to_string());
-            }
-        }
-        
-        // Fallback: look for ``` ... ``` blocks
-        if let Some(start) = text.find("```") {
-            let content_start = start + 3;
-            // Skip language identifier if present
-            let content_start = text[content_start..]
-                .find('\n')
-                .map_or(content_start, |n| content_start + n + 1);
-            if let Some(end) = text[content_start..].find("```") {
-                let toon = text[content_start..content_start + end].trim();
-                return Some(toon.to_string());
-            }
-        }
-        
-        None
+    pub fn tool_input(&self, name: &str) -> Option<&serde_json::Value> {
+        self.content.iter().find_map(|block| match block {
+            ContentBlock::ToolUse {
+                name: tool_name,
+                input,
+                ..
+            } if tool_name == name => Some(input),
+            _ => None,
+        })
     }
+
 }
 
 #[cfg(test)]
@@ -87,13 +85,15 @@ mod tests {
     use super::*;
 
     #[test]
-    fn extract_toon_from_response() {
+    fn tool_input_finds_the_named_tool_use_block() {
         let response = MessageResponse {
             id: "msg_123".to_string(),
             model: "claude-sonnet-4-20250514".to_string(),
-            stop_reason: Some("end_turn".to_string()),
-            content: vec![ContentBlock::Text {
-                text: "Here's the policy:\n\n```toon\npolicy[1]{name}:\n  test\n```\n".to_string(),
+            stop_reason: Some("tool_use".to_string()),
+            content: vec![ContentBlock::ToolUse {
+                id: "toolu_1".to_string(),
+                name: "emit_policy".to_string(),
+                input: serde_json::json!({"name": "test-policy"}),
             }],
             usage: Usage {
                 input_tokens: 100,
@@ -101,7 +101,33 @@ mod tests {
             },
         };
 
-        let toon = response.extract_toon().unwrap();
-        assert!(toon.contains("policy[1]"));
+        let input = response.tool_input("emit_policy").unwrap();
+        assert_eq!(input["name"], "test-policy");
+        assert!(response.tool_input("some_other_tool").is_none());
+    }
+
+    #[test]
+    fn text_ignores_tool_use_blocks() {
+        let response = MessageResponse {
+            id: "msg_123".to_string(),
+            model: "claude-sonnet-4-20250514".to_string(),
+            stop_reason: Some("end_turn".to_string()),
+            content: vec![
+                ContentBlock::Text {
+                    text: "Could you clarify the budget?".to_string(),
+                },
+                ContentBlock::ToolUse {
+                    id: "toolu_1".to_string(),
+                    name: "emit_policy".to_string(),
+                    input: serde_json::json!({}),
+                },
+            ],
+            usage: Usage {
+                input_tokens: 100,
+                output_tokens: 50,
+            },
+        };
+
+        assert_eq!(response.text(), "Could you clarify the budget?");
     }
 }
@@ -0,0 +1,266 @@
+//! Streaming (server-sent-events) policy generation from Claude.
+//!
+//! Anthropic's streaming API replaces the single JSON body
+//! [`crate::client::Client::call_api`] parses with a sequence of
+//! `text/event-stream` events. This module reassembles those events back
+//! into the same [`MessageResponse`] shape
+//! [`crate::client::Client::parse_policy_response`] already knows how to
+//! validate, so the tool/validation parsing isn't duplicated between the
+//! blocking and streaming paths.
+
+use crate::client::Client;
+use crate::error::{Error, Result};
+use crate::response::{ContentBlock, MessageResponse, Usage};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use tokio::sync::mpsc;
+use toon_policy::Policy;
+use tracing::{debug, warn};
+
+/// One event emitted while a policy is being generated incrementally.
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    /// A chunk of the response arrived - raw text, or a fragment of a
+    /// streamed tool call's JSON input. Useful for rendering live
+    /// progress; not meaningful to parse on its own.
+    Delta(String),
+    /// The stream finished and produced a validated policy.
+    Complete(Policy),
+}
+
+/// One in-progress content block, keyed by its `index` in the stream.
+enum BlockState {
+    Text(String),
+    ToolUse { id: String, name: String, json: String },
+}
+
+/// State accumulated across a stream, until `message_stop` lets it be
+/// converted back into a [`MessageResponse`].
+struct Accumulator {
+    id: String,
+    model: String,
+    stop_reason: Option<String>,
+    usage: Usage,
+    blocks: BTreeMap<usize, BlockState>,
+}
+
+impl Default for Accumulator {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            model: String::new(),
+            stop_reason: None,
+            usage: Usage {
+                input_tokens: 0,
+                output_tokens: 0,
+            },
+            blocks: BTreeMap::new(),
+        }
+    }
+}
+
+impl Accumulator {
+    fn into_response(self) -> MessageResponse {
+        let content = self
+            .blocks
+            .into_values()
+            .map(|block| match block {
+                BlockState::Text(text) => ContentBlock::Text { text },
+                BlockState::ToolUse { id, name, json } => {
+                    let input = serde_json::from_str(&json).unwrap_or(serde_json::Value::Null);
+                    ContentBlock::ToolUse { id, name, input }
+                }
+            })
+            .collect();
+
+        MessageResponse {
+            id: self.id,
+            model: self.model,
+            stop_reason: self.stop_reason,
+            content,
+            usage: self.usage,
+        }
+    }
+}
+
+/// One decoded SSE event payload. Anthropic's stream is forward-compatible
+/// (new event types may appear), so an event we don't recognize is skipped
+/// rather than treated as an error - see the `Err(e)` arm in [`handle_event`].
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RawEvent {
+    MessageStart {
+        message: RawMessageStart,
+    },
+    ContentBlockStart {
+        index: usize,
+        content_block: RawContentBlock,
+    },
+    ContentBlockDelta {
+        index: usize,
+        delta: RawDelta,
+    },
+    ContentBlockStop {
+        index: usize,
+    },
+    MessageDelta {
+        delta: RawMessageDelta,
+        usage: RawDeltaUsage,
+    },
+    MessageStop,
+    Ping,
+    Error {
+        error: RawStreamError,
+    },
+}
+
+#[derive(Deserialize)]
+struct RawMessageStart {
+    id: String,
+    model: String,
+    usage: Usage,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RawContentBlock {
+    Text {
+        #[serde(default)]
+        text: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RawDelta {
+    TextDelta { text: String },
+    InputJsonDelta { partial_json: String },
+}
+
+#[derive(Deserialize)]
+struct RawMessageDelta {
+    stop_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawDeltaUsage {
+    output_tokens: u64,
+}
+
+#[derive(Deserialize)]
+struct RawStreamError {
+    message: String,
+}
+
+/// Reads `response` as an SSE stream, forwarding [`StreamEvent`]s to `tx`
+/// as they arrive. Runs until the stream ends, `message_stop` is seen, or
+/// the receiver is dropped.
+pub(crate) async fn consume(mut response: reqwest::Response, tx: mpsc::Sender<Result<StreamEvent>>) {
+    let mut acc = Accumulator::default();
+    let mut buf = String::new();
+
+    loop {
+        let chunk = match response.chunk().await {
+            Ok(Some(bytes)) => bytes,
+            Ok(None) => break,
+            Err(e) => {
+                let _ = tx.send(Err(Error::Network(e))).await;
+                return;
+            }
+        };
+        buf.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(pos) = buf.find("\n\n") {
+            let event_block: String = buf.drain(..=pos + 1).collect();
+            let Some(data) = extract_data(&event_block) else {
+                continue;
+            };
+            if !handle_event(data, &mut acc, &tx).await {
+                return;
+            }
+        }
+    }
+}
+
+/// Pulls the JSON payload out of one `data: ...` line within an SSE event
+/// block. `event:` lines are ignored - the payload's own `type` field
+/// already identifies the event - as are `:`-prefixed keep-alive comments.
+fn extract_data(event_block: &str) -> Option<&str> {
+    event_block.lines().find_map(|line| {
+        line.strip_prefix("data: ")
+            .or_else(|| line.strip_prefix("data:"))
+    })
+}
+
+/// Applies one decoded SSE event to the in-progress accumulator, emitting
+/// a [`StreamEvent`] as needed. Returns `false` once the stream is
+/// finished (successfully or not) and no more events should be read.
+async fn handle_event(
+    data: &str,
+    acc: &mut Accumulator,
+    tx: &mpsc::Sender<Result<StreamEvent>>,
+) -> bool {
+    let event: RawEvent = match serde_json::from_str(data) {
+        Ok(event) => event,
+        Err(e) => {
+            warn!("Ignoring unrecognized stream event: {} (raw: {})", e, data);
+            return true;
+        }
+    };
+
+    match event {
+        RawEvent::MessageStart { message } => {
+            acc.id = message.id;
+            acc.model = message.model;
+            acc.usage = message.usage;
+        }
+        RawEvent::ContentBlockStart { index, content_block } => {
+            let state = match content_block {
+                RawContentBlock::Text { text } => BlockState::Text(text),
+                RawContentBlock::ToolUse { id, name } => BlockState::ToolUse {
+                    id,
+                    name,
+                    json: String::new(),
+                },
+            };
+            acc.blocks.insert(index, state);
+        }
+        RawEvent::ContentBlockDelta { index, delta } => {
+            let chunk = match delta {
+                RawDelta::TextDelta { text } => text,
+                RawDelta::InputJsonDelta { partial_json } => partial_json,
+            };
+            if let Some(state) = acc.blocks.get_mut(&index) {
+                match state {
+                    BlockState::Text(buffer) => buffer.push_str(&chunk),
+                    BlockState::ToolUse { json, .. } => json.push_str(&chunk),
+                }
+            }
+            debug!("Received {} byte stream delta", chunk.len());
+            if tx.send(Ok(StreamEvent::Delta(chunk))).await.is_err() {
+                return false;
+            }
+        }
+        RawEvent::ContentBlockStop { .. } => {}
+        RawEvent::MessageDelta { delta, usage } => {
+            acc.stop_reason = delta.stop_reason;
+            acc.usage.output_tokens = usage.output_tokens;
+        }
+        RawEvent::MessageStop => {
+            let response = std::mem::take(acc).into_response();
+            let result = Client::parse_policy_response(&response).map(StreamEvent::Complete);
+            let _ = tx.send(result).await;
+            return false;
+        }
+        RawEvent::Error { error } => {
+            let _ = tx.send(Err(Error::ApiError(error.message))).await;
+            return false;
+        }
+    }
+
+    true
+}
@@ -1,7 +1,7 @@
 //! Main compiler implementation.
 
 use crate::error::{Error, Result};
-use crate::match_expr::MatchExpr;
+use crate::match_expr::{MatchExpr, DEFAULT_MAX_DNF_CLAUSES};
 use crate::refinery::{RefineryConfig, RefineryRule};
 use toon_policy::{Action, Policy};
 use tracing::warn;
@@ -74,40 +74,68 @@ impl Compiler {
         let mut config = RefineryConfig::new();
 
         for rule in &policy.rules {
-            let refinery_rule = Self::compile_rule(rule)?;
-            config.add_rule(refinery_rule);
+            for refinery_rule in Self::compile_rule(rule)? {
+                config.add_rule(refinery_rule);
+            }
         }
 
         Ok(config)
     }
 
-    fn compile_rule(rule: &toon_policy::Rule) -> Result<RefineryRule> {
-        let mut refinery_rule = match &rule.action {
-            Action::Keep => RefineryRule::keep(&rule.name),
-            Action::Drop => RefineryRule::drop(&rule.name),
-            Action::Sample(rate) => RefineryRule::sample(&rule.name, *rate),
+    /// Compiles one policy rule into one or more Refinery rules.
+    ///
+    /// A plain `AND`-only (or `true`) match compiles to a single rule
+    /// carrying every condition. A match containing `OR` - or a
+    /// `thresh(k, ...)` combinator, which expands into the OR of its
+    /// qualifying sub-combinations - expands into disjunctive normal
+    /// form and compiles to one rule per DNF clause, all sharing `rule`'s
+    /// action and name suffixed `__or0`, `__or1`, … in clause order,
+    /// since Refinery rules have no `OR` primitive.
+    fn compile_rule(rule: &toon_policy::Rule) -> Result<Vec<RefineryRule>> {
+        if let Action::CircuitBreaker { closed_rate, .. } = &rule.action {
+            // Refinery rules have no rolling-window primitive, so the
+            // stateful open/closed escalation can't be represented
+            // statically - compile the resting (closed) rate and warn.
+            warn!(
+                "Rule '{}' uses a stateful circuit-breaker action; compiling its closed-state sample rate {closed_rate} only",
+                rule.name
+            );
+        }
+
+        let make_rule = |name: String| match &rule.action {
+            Action::Keep => RefineryRule::keep(name),
+            Action::Drop => RefineryRule::drop(name),
+            Action::Sample(rate) => RefineryRule::sample(name, *rate),
+            Action::CircuitBreaker { closed_rate, .. } => RefineryRule::sample(name, *closed_rate),
         };
 
-        // Parse match expression and convert to conditions
+        crate::match_expr::type_check(&rule.match_expr)?;
+
+        // Parse match expression and expand it into disjunctive normal
+        // form - a single clause for a plain AND/true match, or one
+        // clause per OR branch.
         let match_expr = MatchExpr::parse(&rule.match_expr)?;
+        let clauses = match_expr.to_dnf(&rule.match_expr, DEFAULT_MAX_DNF_CLAUSES)?;
 
-        match match_expr.to_refinery_conditions() {
-            Ok(conditions) => {
-                for condition in conditions {
-                    refinery_rule = refinery_rule.with_condition(condition);
-                }
-            }
-            Err(e) => {
-                // Log warning but don't fail compilation
-                // Some expressions (like OR) may need special handling
-                warn!(
-                    "Could not convert match expression '{}' to conditions: {}",
-                    rule.match_expr, e
-                );
+        if clauses.len() <= 1 {
+            let mut refinery_rule = make_rule(rule.name.clone());
+            for condition in clauses.into_iter().flatten() {
+                refinery_rule = refinery_rule.with_condition(condition);
             }
+            return Ok(vec![refinery_rule]);
         }
 
-        Ok(refinery_rule)
+        Ok(clauses
+            .into_iter()
+            .enumerate()
+            .map(|(i, conditions)| {
+                let mut refinery_rule = make_rule(format!("{}__or{i}", rule.name));
+                for condition in conditions {
+                    refinery_rule = refinery_rule.with_condition(condition);
+                }
+                refinery_rule
+            })
+            .collect())
     }
 }
 
@@ -143,6 +171,156 @@ mod tests {
         assert!(output.contains("\"name\": \"fallback\""));
     }
 
+    #[test]
+    fn compile_circuit_breaker_uses_closed_rate() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "fallback",
+            "true",
+            Action::CircuitBreaker {
+                closed_rate: 0.02,
+                open_rate: 0.9,
+                window: 100,
+                failure_threshold: 0.2,
+                min_samples: 20,
+            },
+            0,
+        ));
+
+        let compiler = Compiler::new();
+        let config = compiler.to_refinery_config(&policy).unwrap();
+
+        // Refinery sample rates are "keep 1 in N"; 0.02 -> N = 50.
+        assert_eq!(config.rules_based_sampler.rules[0].sample_rate, 50);
+    }
+
+    #[test]
+    fn compile_rule_expands_or_into_suffixed_rules() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "keep-errors-or-slow",
+            "status >= 500 || duration > 5s",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        let compiler = Compiler::new();
+        let config = compiler.to_refinery_config(&policy).unwrap();
+
+        let names: Vec<&str> = config
+            .rules_based_sampler
+            .rules
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert!(names.contains(&"keep-errors-or-slow__or0"));
+        assert!(names.contains(&"keep-errors-or-slow__or1"));
+        assert!(names.contains(&"fallback"));
+
+        let or0 = config
+            .rules_based_sampler
+            .rules
+            .iter()
+            .find(|r| r.name == "keep-errors-or-slow__or0")
+            .unwrap();
+        assert_eq!(or0.conditions.len(), 1);
+        assert_eq!(or0.conditions[0].field, "status");
+
+        let or1 = config
+            .rules_based_sampler
+            .rules
+            .iter()
+            .find(|r| r.name == "keep-errors-or-slow__or1")
+            .unwrap();
+        assert_eq!(or1.conditions.len(), 1);
+        assert_eq!(or1.conditions[0].field, "duration_ms");
+    }
+
+    #[test]
+    fn compile_rule_keeps_and_clauses_within_each_or_branch() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "compound",
+            "status >= 500 && error == true || status == 429",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        let compiler = Compiler::new();
+        let config = compiler.to_refinery_config(&policy).unwrap();
+
+        let or0 = config
+            .rules_based_sampler
+            .rules
+            .iter()
+            .find(|r| r.name == "compound__or0")
+            .unwrap();
+        assert_eq!(or0.conditions.len(), 2, "first branch keeps both AND'd conditions");
+
+        let or1 = config
+            .rules_based_sampler
+            .rules
+            .iter()
+            .find(|r| r.name == "compound__or1")
+            .unwrap();
+        assert_eq!(or1.conditions.len(), 1);
+    }
+
+    #[test]
+    fn compile_rule_rejects_excessive_clause_expansion() {
+        let huge_or = (0..100)
+            .map(|i| format!("status == {i}"))
+            .collect::<Vec<_>>()
+            .join(" || ");
+
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("huge", &huge_or, Action::Keep, 100));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        let compiler = Compiler::new();
+        let result = compiler.to_refinery_config(&policy);
+
+        assert!(matches!(result, Err(Error::ClauseLimitExceeded { .. })));
+    }
+
+    #[test]
+    fn compile_rule_expands_threshold_into_suffixed_rules() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "two-of-three",
+            "thresh(2, status == 500, status == 502, status == 503)",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        let compiler = Compiler::new();
+        let config = compiler.to_refinery_config(&policy).unwrap();
+
+        let names: Vec<&str> = config
+            .rules_based_sampler
+            .rules
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        // C(3, 2) == 3 qualifying pairs, one rule per pair, each AND-ing
+        // two of the three conditions.
+        assert!(names.contains(&"two-of-three__or0"));
+        assert!(names.contains(&"two-of-three__or1"));
+        assert!(names.contains(&"two-of-three__or2"));
+        assert!(names.contains(&"fallback"));
+
+        let or0 = config
+            .rules_based_sampler
+            .rules
+            .iter()
+            .find(|r| r.name == "two-of-three__or0")
+            .unwrap();
+        assert_eq!(or0.conditions.len(), 2);
+    }
+
     #[test]
     fn compiler_is_deterministic() {
         let mut policy = Policy::new("test");
@@ -14,14 +14,52 @@ pub enum Error {
     Serialization(String),
 
     /// Invalid match expression.
-    #[error("invalid match expression '{expr}': {reason}")]
+    #[error("invalid match expression '{expr}' at byte {}..{}: {reason}", span.start, span.end)]
     InvalidMatch {
         /// The invalid expression string.
         expr: String,
+        /// Byte span of the offending token within `expr`.
+        span: std::ops::Range<usize>,
         /// Reason why the expression is invalid.
         reason: String,
     },
 
+    /// A match expression compared an attribute against a value of the
+    /// wrong type (e.g. `service.name` against a number, or `duration`
+    /// without a unit).
+    #[error("type error in '{expr}' at byte {}..{}: {reason}", span.0, span.1)]
+    TypeMismatch {
+        /// The full expression the offending comparison was found in.
+        expr: String,
+        /// Byte span of the offending comparison within `expr`.
+        span: (usize, usize),
+        /// Reason the comparison is type-invalid.
+        reason: String,
+    },
+
+    /// Expanding an `Or`/`And` match expression into disjunctive normal
+    /// form would exceed the configured clause limit.
+    #[error("match expression '{expr}' expands to {clauses} DNF clauses, exceeding the limit of {limit}")]
+    ClauseLimitExceeded {
+        /// The expression that was being expanded.
+        expr: String,
+        /// The configured clause limit.
+        limit: usize,
+        /// The clause count the expansion reached before giving up.
+        clauses: usize,
+    },
+
+    /// A condition couldn't be imported from a hand-edited Refinery
+    /// config: its declared (or inferred) datatype doesn't parse, or its
+    /// operator is incompatible with the coerced value's type.
+    #[error("invalid refinery condition on field '{field}': {reason}")]
+    RefineryImport {
+        /// The condition's field name.
+        field: String,
+        /// Reason the condition is invalid.
+        reason: String,
+    },
+
     /// YAML serialization error.
     #[error(transparent)]
     Yaml(#[from] serde_yaml::Error),
@@ -31,5 +69,90 @@ pub enum Error {
     Json(#[from] serde_json::Error),
 }
 
+impl Error {
+    /// Renders a rustc-style caret diagnostic pointing at this error's
+    /// source span, or `None` if this error doesn't carry one.
+    ///
+    /// ```text
+    /// error: unknown operator '>=='
+    ///   --> line 1, column 13
+    ///   |
+    /// 1 | http.status >== 500
+    ///   |             ^^^
+    /// ```
+    #[must_use]
+    pub fn render_caret(&self) -> Option<String> {
+        match self {
+            Self::InvalidMatch { expr, span, reason } => {
+                Some(render_caret_diagnostic(expr, span.clone(), reason))
+            }
+            Self::TypeMismatch { expr, span, reason } => {
+                Some(render_caret_diagnostic(expr, span.0..span.1, reason))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Renders `message` as a single caret diagnostic pointing at `span`
+/// within `source`. `span` is resolved against whichever line of
+/// `source` it falls in, so multi-line or indented policy files get
+/// the right line number and column.
+fn render_caret_diagnostic(source: &str, span: std::ops::Range<usize>, message: &str) -> String {
+    let line_start = source[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[span.start..]
+        .find('\n')
+        .map_or(source.len(), |i| span.start + i);
+    let line = &source[line_start..line_end];
+    let line_no = source[..span.start].matches('\n').count() + 1;
+    let column = span.start - line_start + 1;
+    let underline_offset = span.start - line_start;
+    let underline_len = span.end.saturating_sub(span.start).max(1);
+
+    let gutter = line_no.to_string();
+    let pad = " ".repeat(gutter.len());
+    format!(
+        "error: {message}\n{pad} --> line {line_no}, column {column}\n{pad} |\n{gutter} | {line}\n{pad} | {}{}",
+        " ".repeat(underline_offset),
+        "^".repeat(underline_len),
+    )
+}
+
 /// Result type alias for compiler operations.
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_caret_underlines_the_span() {
+        let err = Error::InvalidMatch {
+            expr: "http.status >== 500".to_string(),
+            span: 12..15,
+            reason: "unknown operator '>=='".to_string(),
+        };
+        let rendered = err.render_caret().unwrap();
+        assert!(rendered.contains("http.status >== 500"));
+        assert!(rendered.contains("^^^"));
+        assert!(rendered.contains("line 1, column 13"));
+    }
+
+    #[test]
+    fn render_caret_finds_the_right_line_in_multiline_source() {
+        let err = Error::InvalidMatch {
+            expr: "true &&\n  status >== 500".to_string(),
+            span: 17..20,
+            reason: "unknown operator '>=='".to_string(),
+        };
+        let rendered = err.render_caret().unwrap();
+        assert!(rendered.contains("line 2"));
+        assert!(rendered.contains("  status >== 500"));
+    }
+
+    #[test]
+    fn render_caret_is_none_for_spanless_errors() {
+        let err = Error::Unsupported("nope".to_string());
+        assert!(err.render_caret().is_none());
+    }
+}
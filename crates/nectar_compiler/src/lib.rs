@@ -27,6 +27,7 @@ pub mod error;
 pub mod lockfile;
 pub mod match_expr;
 pub mod refinery;
+pub mod tsr;
 pub mod waggle;
 
 pub use compiler::{CompileOptions, Compiler, OutputFormat};
@@ -7,9 +7,11 @@
 //! - Metadata about the compilation
 
 use crate::error::Result;
+use crate::match_expr::{Condition, MatchExpr, Operator, Value};
+use crate::tsr::{self, TimestampToken};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use toon_policy::Policy;
+use toon_policy::{Action, Policy, Rule};
 use xxhash_rust::xxh64::xxh64;
 
 /// Seed for xxhash to ensure deterministic hashing.
@@ -24,6 +26,14 @@ pub struct Lockfile {
     pub source_hash: String,
     /// Hash of the compiled output.
     pub compiled_hash: String,
+    /// Hash of the policy's canonical semantic form (see
+    /// [`Lockfile::verify_semantic`]), unaffected by cosmetic changes -
+    /// whitespace, key ordering, comments - to the compiled output.
+    /// Defaults to empty for lockfiles saved before this field existed;
+    /// such a lockfile simply fails [`Lockfile::verify_semantic`] until
+    /// regenerated.
+    #[serde(default)]
+    pub semantic_hash: String,
     /// Policy name.
     pub policy_name: String,
     /// Number of rules in the policy.
@@ -31,6 +41,19 @@ pub struct Lockfile {
     /// Timestamp when the lock was created (ISO 8601).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
+    /// SHA-256 digest (hex) of the compiled output that was timestamped,
+    /// as attested by `tsa_token_base64`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tsa_digest_sha256: Option<String>,
+    /// URL of the RFC 3161 Time-Stamp Authority that issued the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tsa_url: Option<String>,
+    /// The raw RFC 3161 `TimeStampToken`, base64-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tsa_token_base64: Option<String>,
+    /// The `genTime` the TSA attested to, recovered from the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tsa_gen_time: Option<String>,
 }
 
 impl Lockfile {
@@ -41,9 +64,14 @@ impl Lockfile {
             version: 1,
             source_hash: hash_content(source_toon),
             compiled_hash: hash_content(compiled_output),
+            semantic_hash: semantic_hash_of(policy),
             policy_name: policy.name.clone(),
             rule_count: policy.rules.len(),
             created_at: None,
+            tsa_digest_sha256: None,
+            tsa_url: None,
+            tsa_token_base64: None,
+            tsa_gen_time: None,
         }
     }
 
@@ -54,6 +82,42 @@ impl Lockfile {
         self
     }
 
+    /// Attaches an RFC 3161 trusted-timestamp token obtained from a TSA
+    /// over `compiled_output`, recording its digest, source URL, and the
+    /// `genTime` recovered from the token.
+    #[must_use]
+    pub fn with_tsa_token(mut self, tsa_url: impl Into<String>, compiled_output: &str, token: &TimestampToken) -> Self {
+        let digest = tsr::sha256(compiled_output.as_bytes());
+        self.tsa_digest_sha256 = Some(tsr::hex(&digest));
+        self.tsa_url = Some(tsa_url.into());
+        self.tsa_token_base64 = Some(tsr::base64_encode(&token.raw_der));
+        self.tsa_gen_time = token.gen_time.clone();
+        self
+    }
+
+    /// Re-verifies a stored RFC 3161 token against the current compiled
+    /// output: recomputes the SHA-256 digest, re-parses the stored
+    /// token, and confirms its `messageImprint` still matches.
+    ///
+    /// Returns `Ok(None)` if this lockfile carries no TSA token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a token is present but its stored bytes are
+    /// not valid base64, or if the digest no longer matches (the
+    /// compiled output has drifted since it was timestamped).
+    pub fn verify_tsa_token(&self, compiled_output: &str) -> Result<Option<TimestampToken>> {
+        let Some(token_base64) = &self.tsa_token_base64 else {
+            return Ok(None);
+        };
+
+        let raw_der = tsr::base64_decode(token_base64)?;
+        let token = tsr::parse_timestamp_response(&raw_der);
+        let digest = tsr::sha256(compiled_output.as_bytes());
+        tsr::verify_timestamp_token(&token, &digest)?;
+        Ok(Some(token))
+    }
+
     /// Verifies that the compiled output matches the lockfile.
     ///
     /// Returns `true` if the hashes match.
@@ -80,6 +144,17 @@ impl Lockfile {
         self.compiled_hash == hash_content(compiled_output)
     }
 
+    /// Verifies that `policy` is semantically equivalent to the policy
+    /// this lockfile was created from: same rules (by priority and
+    /// name), match expressions, actions, and budget, ignoring anything
+    /// cosmetic. Unlike [`Self::verify_compiled`], this tolerates
+    /// reformatting the compiled output - only a real change in what the
+    /// policy actually does trips it.
+    #[must_use]
+    pub fn verify_semantic(&self, policy: &Policy) -> bool {
+        self.semantic_hash == semantic_hash_of(policy)
+    }
+
     /// Loads a lockfile from a path.
     ///
     /// # Errors
@@ -127,6 +202,150 @@ fn hash_content(content: &str) -> String {
     format!("{hash:016x}")
 }
 
+/// Hashes `policy`'s canonical semantic form, for [`Lockfile::new`] and
+/// [`Lockfile::verify_semantic`].
+fn semantic_hash_of(policy: &Policy) -> String {
+    let canonical = canonicalize(policy);
+    let json = serde_json::to_string(&canonical).unwrap_or_default();
+    hash_content(&json)
+}
+
+/// Lifts `policy` into a canonical form that hashes the same regardless
+/// of insertion order, match-expression formatting, description text, or
+/// floating-point rendering: rules sorted by priority (descending,
+/// matching [`Policy::add_rule`]) then name, descriptions dropped
+/// (cosmetic only), each match expression parsed and re-rendered into a
+/// deterministic textual form (or left trimmed as-is if it doesn't
+/// parse), and action rates rounded to avoid float-formatting jitter.
+fn canonicalize(policy: &Policy) -> Policy {
+    let mut rules: Vec<Rule> = policy.rules.iter().map(canonicalize_rule).collect();
+    rules.sort_by(|a, b| {
+        b.priority
+            .cmp(&a.priority)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    Policy {
+        version: policy.version,
+        name: policy.name.clone(),
+        budget_per_second: policy.budget_per_second,
+        rules,
+    }
+}
+
+fn canonicalize_rule(rule: &Rule) -> Rule {
+    Rule {
+        name: rule.name.clone(),
+        description: None,
+        match_expr: canonicalize_match_expr(&rule.match_expr),
+        action: canonicalize_action(&rule.action),
+        priority: rule.priority,
+    }
+}
+
+/// Parses `expr` and re-renders it into [`render_match_expr`]'s canonical
+/// form, or falls back to the trimmed source if it doesn't parse - an
+/// unparseable `match_expr` still hashes deterministically, just without
+/// the formatting-insensitivity parsing would otherwise buy it.
+fn canonicalize_match_expr(expr: &str) -> String {
+    MatchExpr::parse(expr).map_or_else(
+        |_| expr.trim().to_string(),
+        |parsed| render_match_expr(&parsed),
+    )
+}
+
+fn render_match_expr(expr: &MatchExpr) -> String {
+    match expr {
+        MatchExpr::True => "true".to_string(),
+        MatchExpr::Condition(condition) => render_condition(condition),
+        MatchExpr::Not(inner) => format!("!({})", render_match_expr(inner)),
+        MatchExpr::And(exprs) => render_combinator("&&", exprs),
+        MatchExpr::Or(exprs) => render_combinator("||", exprs),
+        MatchExpr::Threshold { k, subs } => {
+            let mut parts: Vec<String> = subs.iter().map(render_match_expr).collect();
+            parts.sort_unstable();
+            format!("thresh({k}, {})", parts.join(", "))
+        }
+    }
+}
+
+/// Renders an `And`/`Or`'s children in sorted order, so e.g. `a && b` and
+/// `b && a` canonicalize identically.
+fn render_combinator(op: &str, exprs: &[MatchExpr]) -> String {
+    let mut parts: Vec<String> = exprs.iter().map(render_match_expr).collect();
+    parts.sort_unstable();
+    format!("({})", parts.join(&format!(" {op} ")))
+}
+
+fn render_condition(condition: &Condition) -> String {
+    format!(
+        "{} {} {}",
+        condition.field,
+        operator_symbol(condition.operator),
+        render_value(&condition.value)
+    )
+}
+
+const fn operator_symbol(operator: Operator) -> &'static str {
+    match operator {
+        Operator::Eq => "==",
+        Operator::Ne => "!=",
+        Operator::Gt => ">",
+        Operator::Ge => ">=",
+        Operator::Lt => "<",
+        Operator::Le => "<=",
+        Operator::Contains => "contains",
+        Operator::StartsWith => "starts-with",
+        Operator::Exists => "exists",
+        Operator::In => "in",
+        Operator::NotIn => "not-in",
+        Operator::Matches => "matches",
+    }
+}
+
+fn render_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => format!("{s:?}"),
+        Value::Int(n) => n.to_string(),
+        Value::Float(f) => format!("{:.6}", round6(*f)),
+        Value::Bool(b) => b.to_string(),
+        Value::Duration(ms) => format!("{ms}ms"),
+        Value::List(items) => {
+            // Set-membership order doesn't affect semantics.
+            let mut parts: Vec<String> = items.iter().map(render_value).collect();
+            parts.sort_unstable();
+            format!("[{}]", parts.join(","))
+        }
+    }
+}
+
+fn canonicalize_action(action: &Action) -> Action {
+    match action {
+        Action::Keep => Action::Keep,
+        Action::Drop => Action::Drop,
+        Action::Sample(rate) => Action::Sample(round6(*rate)),
+        Action::CircuitBreaker {
+            closed_rate,
+            open_rate,
+            window,
+            failure_threshold,
+            min_samples,
+        } => Action::CircuitBreaker {
+            closed_rate: round6(*closed_rate),
+            open_rate: round6(*open_rate),
+            window: *window,
+            failure_threshold: round6(*failure_threshold),
+            min_samples: *min_samples,
+        },
+    }
+}
+
+/// Rounds to 6 decimal places, so two rates that differ only in
+/// floating-point noise canonicalize identically.
+fn round6(x: f64) -> f64 {
+    (x * 1_000_000.0).round() / 1_000_000.0
+}
+
 /// Simple timestamp generator (no chrono dependency).
 fn chrono_lite_now() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -268,6 +487,106 @@ mod tests {
         assert_eq!(lockfile.policy_name, parsed.policy_name);
     }
 
+    #[test]
+    fn tsa_token_round_trips_and_verifies() {
+        let policy = Policy::new("test");
+        let compiled = "RulesBasedSampler: ...";
+        let digest = tsr::sha256(compiled.as_bytes());
+
+        // Build a minimal synthetic token DER containing just the
+        // algorithm identifier + digest, as a real TSTInfo would nest it.
+        let mut raw_der = vec![0x30, 0x00]; // placeholder SEQUENCE wrapper
+        raw_der.extend(tsr::TimestampRequest::new(digest, 1).to_der());
+        let token = tsr::parse_timestamp_response(&raw_der);
+
+        let lockfile = Lockfile::new(&policy, "source", compiled).with_tsa_token(
+            "https://tsa.example.com",
+            compiled,
+            &token,
+        );
+
+        assert_eq!(lockfile.tsa_url.as_deref(), Some("https://tsa.example.com"));
+        assert!(lockfile.tsa_token_base64.is_some());
+
+        let verified = lockfile.verify_tsa_token(compiled).unwrap();
+        assert!(verified.is_some());
+    }
+
+    #[test]
+    fn verify_tsa_token_fails_when_output_drifts() {
+        let policy = Policy::new("test");
+        let compiled = "RulesBasedSampler: original";
+        let digest = tsr::sha256(compiled.as_bytes());
+        let raw_der = tsr::TimestampRequest::new(digest, 1).to_der();
+        let token = tsr::parse_timestamp_response(&raw_der);
+
+        let lockfile =
+            Lockfile::new(&policy, "source", compiled).with_tsa_token("https://tsa.example.com", compiled, &token);
+
+        assert!(lockfile.verify_tsa_token("RulesBasedSampler: tampered").is_err());
+    }
+
+    #[test]
+    fn semantic_hash_survives_rule_reordering_and_reformatted_match_exprs() {
+        let mut policy_a = Policy::new("test");
+        policy_a.add_rule(Rule::new("keep-errors", "status >= 500", Action::Keep, 100));
+        policy_a.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        // Same semantics, rules added in the opposite order, and the
+        // match expression reformatted with extra spacing - a real
+        // formatter change to the compiled output shouldn't look like a
+        // semantic change.
+        let mut policy_b = Policy::new("test");
+        policy_b.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+        policy_b.add_rule(Rule::new(
+            "keep-errors",
+            "status   >=   500",
+            Action::Keep,
+            100,
+        ));
+
+        let lockfile = Lockfile::new(&policy_a, "source a", "compiled a");
+        assert!(lockfile.verify_semantic(&policy_b));
+    }
+
+    #[test]
+    fn semantic_hash_ignores_description_and_float_formatting_noise() {
+        let mut policy_a = Policy::new("test");
+        policy_a.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        let mut policy_b = Policy::new("test");
+        policy_b.add_rule(
+            Rule::new("fallback", "true", Action::Sample(0.010_000_01), 0)
+                .with_description("catch-all"),
+        );
+
+        let lockfile = Lockfile::new(&policy_a, "source", "compiled");
+        assert!(lockfile.verify_semantic(&policy_b));
+    }
+
+    #[test]
+    fn semantic_hash_catches_a_real_behavior_change() {
+        let mut policy_a = Policy::new("test");
+        policy_a.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        let mut policy_b = Policy::new("test");
+        policy_b.add_rule(Rule::new("fallback", "true", Action::Sample(0.5), 0));
+
+        let lockfile = Lockfile::new(&policy_a, "source", "compiled");
+        assert!(!lockfile.verify_semantic(&policy_b));
+    }
+
+    #[test]
+    fn verify_semantic_tolerates_compiled_output_drift_verify_does_not() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        let lockfile = Lockfile::new(&policy, "source", "compiled v1");
+
+        assert!(lockfile.verify_semantic(&policy));
+        assert!(!lockfile.verify_compiled("compiled v2 (reformatted)"));
+    }
+
     #[test]
     fn timestamp_generation() {
         let policy = Policy::new("test");
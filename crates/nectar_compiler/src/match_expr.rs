@@ -7,10 +7,39 @@
 //! - `error == true`
 //! - `true` (match all)
 //! - `http.status >= 500 || error == true` (compound)
+//! - `(status >= 500 && error == true) || latency > 1s` (grouped)
+//! - `!(service.name == "checkout")` (negated)
+//! - `thresh(2, http.status >= 500, duration > 1s, error == true)` (at
+//!   least 2 of the 3 sub-expressions hold)
+//!
+//! Grammar, in increasing precedence (`||` binds loosest, `!` tightest):
+//!
+//! ```text
+//! or        := and ('||' and)*
+//! and       := unary ('&&' unary)*
+//! unary     := '!' unary | primary
+//! primary   := '(' or ')' | threshold | condition
+//! threshold := 'thresh' '(' INT (',' or)+ ')'
+//! ```
+
+use std::collections::HashMap;
 
 use crate::error::{Error, Result};
 use crate::refinery::{ConditionValue, RefineryCondition};
 
+/// Default cap on the number of DNF clauses [`MatchExpr::to_dnf`] will
+/// expand an `Or`/`And` tree into before giving up - an `And` of
+/// several wide `Or`s distributes into a cross product, and without a
+/// cap a pathological policy could blow up compile time/memory.
+pub const DEFAULT_MAX_DNF_CLAUSES: usize = 64;
+
+/// Cap on parser recursion depth - each nested `(`, `!`, or `thresh(...)`
+/// sub-expression descends one level deeper through
+/// `parse_or`/`parse_and`/`parse_unary`/`parse_primary`, and without a
+/// limit a few thousand of them in the input would overflow the stack
+/// instead of producing a catchable [`Error`].
+const MAX_PARSE_DEPTH: usize = 256;
+
 /// A parsed match expression.
 #[derive(Debug, Clone, PartialEq)]
 pub enum MatchExpr {
@@ -22,6 +51,18 @@ pub enum MatchExpr {
     And(Vec<Self>),
     /// Logical OR of multiple expressions.
     Or(Vec<Self>),
+    /// Logical negation of an expression.
+    Not(Box<Self>),
+    /// "At least `k` of n" gate: matches if at least `k` of `subs` match.
+    /// `And(subs)` is `Threshold(subs.len(), subs)` and `Or(subs)` is
+    /// `Threshold(1, subs)`, but thresholds between those two extremes
+    /// (e.g. "2 of 3") have no equivalent flat `And`/`Or` spelling.
+    Threshold {
+        /// Minimum number of `subs` that must match.
+        k: usize,
+        /// Sub-expressions the threshold counts over.
+        subs: Vec<Self>,
+    },
 }
 
 /// A simple comparison condition.
@@ -33,6 +74,10 @@ pub struct Condition {
     pub operator: Operator,
     /// Value to compare against.
     pub value: Value,
+    /// Byte span of this condition (field, operator and value together)
+    /// within the original, untrimmed source the [`MatchExpr`] was
+    /// parsed from.
+    pub span: std::ops::Range<usize>,
 }
 
 /// Comparison operators.
@@ -56,6 +101,13 @@ pub enum Operator {
     StartsWith,
     /// Exists (has value).
     Exists,
+    /// Set membership: field equals one of a bracketed list of values.
+    In,
+    /// Negated set membership: field equals none of a bracketed list of
+    /// values.
+    NotIn,
+    /// Regex match against a string field.
+    Matches,
 }
 
 /// A value in a condition.
@@ -71,6 +123,130 @@ pub enum Value {
     Bool(bool),
     /// Duration value in milliseconds.
     Duration(u64),
+    /// A bracketed list literal, e.g. `[500, 502, 503]` - only valid as
+    /// the operand of [`Operator::In`]/[`Operator::NotIn`].
+    List(Vec<Self>),
+}
+
+/// Declared type for a match-expression field, used by
+/// [`MatchExpr::parse_with_schema`] to coerce a parsed literal to the
+/// field's real type instead of trusting [`parse_value`]'s best-effort
+/// inference (which would otherwise let a status code written as
+/// `"500"` silently become a [`Value::String`]) and to reject
+/// operator/type combinations that could never match, such as
+/// `Contains` against a `Duration` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Coerces into [`Value::String`].
+    String,
+    /// Coerces into [`Value::Int`].
+    Int,
+    /// Coerces into [`Value::Float`].
+    Float,
+    /// Coerces into [`Value::Bool`].
+    Bool,
+    /// Coerces into [`Value::Duration`].
+    Duration,
+}
+
+impl FieldKind {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::String => "string",
+            Self::Int => "int",
+            Self::Float => "float",
+            Self::Bool => "bool",
+            Self::Duration => "duration",
+        }
+    }
+
+    /// True if `operator` is meaningful for a field of this kind:
+    /// `Contains`/`StartsWith` only make sense on strings, the ordering
+    /// operators only on numeric or duration fields, and `Eq`/`Ne`/
+    /// `Exists` are fine on anything.
+    const fn supports_operator(self, operator: Operator) -> bool {
+        match operator {
+            Operator::Eq | Operator::Ne | Operator::Exists | Operator::In | Operator::NotIn => {
+                true
+            }
+            Operator::Contains | Operator::StartsWith | Operator::Matches => {
+                matches!(self, Self::String)
+            }
+            Operator::Gt | Operator::Ge | Operator::Lt | Operator::Le => {
+                matches!(self, Self::Int | Self::Float | Self::Duration)
+            }
+        }
+    }
+
+    /// Coerces an already-parsed literal into this kind, reparsing it
+    /// from its source text if [`parse_value`]'s inference guessed a
+    /// different type (e.g. a quoted `"500"` against a declared `Int`).
+    fn coerce(self, value: Value) -> std::result::Result<Value, String> {
+        match (self, value) {
+            (Self::String, Value::String(s)) => Ok(Value::String(s)),
+            (Self::Int, Value::Int(n)) => Ok(Value::Int(n)),
+            (Self::Int, Value::String(s)) => s
+                .parse::<i64>()
+                .map(Value::Int)
+                .map_err(|e| format!("'{s}' is not an integer: {e}")),
+            (Self::Float, Value::Float(f)) => Ok(Value::Float(f)),
+            #[allow(clippy::cast_precision_loss)]
+            (Self::Float, Value::Int(n)) => Ok(Value::Float(n as f64)),
+            (Self::Float, Value::String(s)) => s
+                .parse::<f64>()
+                .map(Value::Float)
+                .map_err(|e| format!("'{s}' is not a float: {e}")),
+            (Self::Bool, Value::Bool(b)) => Ok(Value::Bool(b)),
+            (Self::Bool, Value::String(s)) => match s.as_str() {
+                "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                other => Err(format!("'{other}' is not a boolean")),
+            },
+            (Self::Duration, Value::Duration(ms)) => Ok(Value::Duration(ms)),
+            // The operand of `In`/`NotIn` - coerce every element to this
+            // kind so e.g. `http.status in ["500", "502"]` against an
+            // `Int` field behaves the same as coercing each arm of the
+            // equivalent `||` chain.
+            (kind, Value::List(items)) => items
+                .into_iter()
+                .map(|item| kind.coerce(item))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map(Value::List),
+            (Self::Duration, _) => {
+                Err("requires a duration with a unit (e.g. 500ms, 5s)".to_string())
+            }
+            (kind, value) => Err(format!(
+                "declared as {} but '{value:?}' can't be coerced to it",
+                kind.name()
+            )),
+        }
+    }
+}
+
+/// Maps field names to their declared [`FieldKind`], so
+/// [`MatchExpr::parse_with_schema`] can coerce each condition's literal
+/// and validate its operator up front instead of relying on
+/// [`parse_value`]'s best-effort type inference.
+#[derive(Debug, Clone, Default)]
+pub struct FieldSchema(HashMap<String, FieldKind>);
+
+impl FieldSchema {
+    /// Creates an empty schema.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Declares `field`'s type, returning the schema for chaining.
+    #[must_use]
+    pub fn with_field(mut self, field: impl Into<String>, kind: FieldKind) -> Self {
+        self.0.insert(field.into(), kind);
+        self
+    }
+
+    fn get(&self, field: &str) -> Option<FieldKind> {
+        self.0.get(field).copied()
+    }
 }
 
 impl MatchExpr {
@@ -80,28 +256,66 @@ impl MatchExpr {
     ///
     /// Returns an error if the expression is invalid.
     pub fn parse(input: &str) -> Result<Self> {
-        let input = input.trim();
-
-        // Handle "true" literal
-        if input.eq_ignore_ascii_case("true") {
-            return Ok(Self::True);
+        let base = input.len() - input.trim_start().len();
+        let trimmed = input.trim();
+        let (expr, rest, rest_base) = parse_or(trimmed, base, 0)?;
+        let skip = rest.len() - rest.trim_start().len();
+        let rest = &rest[skip..];
+        let rest_base = rest_base + skip;
+        if !rest.is_empty() {
+            return Err(Error::InvalidMatch {
+                expr: input.to_string(),
+                span: rest_base..rest_base + rest.len(),
+                reason: format!("unexpected trailing input: '{rest}'"),
+            });
         }
+        Ok(expr)
+    }
 
-        // Handle OR expressions
-        if let Some(parts) = split_logical(input, "||") {
-            let exprs: Result<Vec<_>> = parts.iter().map(|p| Self::parse(p)).collect();
-            return Ok(Self::Or(exprs?));
-        }
+    /// Parses a match expression string like [`Self::parse`], then
+    /// coerces each condition's value to its field's declared type in
+    /// `schema` and rejects operators that type can't support (e.g.
+    /// `Contains` on a `Duration` field). Fields not present in `schema`
+    /// keep [`parse_value`]'s best-effort inference, so callers without
+    /// a full schema can still use this and only opt in field-by-field.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expression is invalid, or
+    /// [`Error::TypeMismatch`] if a condition's value can't be coerced
+    /// to its field's declared kind or its operator isn't supported by
+    /// that kind.
+    pub fn parse_with_schema(input: &str, schema: &FieldSchema) -> Result<Self> {
+        Self::parse(input)?.coerce_to_schema(input, schema)
+    }
 
-        // Handle AND expressions
-        if let Some(parts) = split_logical(input, "&&") {
-            let exprs: Result<Vec<_>> = parts.iter().map(|p| Self::parse(p)).collect();
-            return Ok(Self::And(exprs?));
+    fn coerce_to_schema(self, source: &str, schema: &FieldSchema) -> Result<Self> {
+        match self {
+            Self::True => Ok(Self::True),
+            Self::Condition(c) => Ok(Self::Condition(c.coerce_to_schema(source, schema)?)),
+            Self::And(exprs) => Ok(Self::And(
+                exprs
+                    .into_iter()
+                    .map(|e| e.coerce_to_schema(source, schema))
+                    .collect::<Result<_>>()?,
+            )),
+            Self::Or(exprs) => Ok(Self::Or(
+                exprs
+                    .into_iter()
+                    .map(|e| e.coerce_to_schema(source, schema))
+                    .collect::<Result<_>>()?,
+            )),
+            Self::Not(inner) => Ok(Self::Not(Box::new(
+                inner.coerce_to_schema(source, schema)?,
+            ))),
+            Self::Threshold { k, subs } => Ok(Self::Threshold {
+                k,
+                subs: subs
+                    .into_iter()
+                    .map(|e| e.coerce_to_schema(source, schema))
+                    .collect::<Result<_>>()?,
+            }),
         }
-
-        // Parse as simple condition
-        let condition = parse_condition(input)?;
-        Ok(Self::Condition(condition))
     }
 
     /// Converts this expression to Refinery conditions.
@@ -114,7 +328,10 @@ impl MatchExpr {
     pub fn to_refinery_conditions(&self) -> Result<Vec<RefineryCondition>> {
         match self {
             Self::True => Ok(Vec::new()),
-            Self::Condition(cond) => Ok(vec![cond.to_refinery()]),
+            Self::Condition(cond) => match cond.expand_set_membership() {
+                Some(expanded) => expanded.to_refinery_conditions(),
+                None => Ok(vec![cond.to_refinery()]),
+            },
             Self::And(exprs) => {
                 let mut conditions = Vec::new();
                 for expr in exprs {
@@ -130,11 +347,308 @@ impl MatchExpr {
                         .to_string(),
                 ))
             }
+            Self::Not(inner) => inner.negate()?.to_refinery_conditions(),
+            Self::Threshold { .. } => {
+                // Like a bare `Or`, "at least k of n" isn't flattenable
+                // into a single rule's AND-only condition list - split
+                // into multiple rules via `to_dnf` instead.
+                Err(Error::Unsupported(
+                    "threshold expressions not supported in single rule, split into multiple rules via to_dnf"
+                        .to_string(),
+                ))
+            }
+        }
+    }
+
+    /// Expands this expression into disjunctive normal form - an OR of
+    /// AND-clauses, each clause a flat list of [`RefineryCondition`]s -
+    /// so [`crate::compiler::Compiler`] can emit one Refinery rule per
+    /// clause instead of rejecting `Or` outright like
+    /// [`Self::to_refinery_conditions`] does.
+    ///
+    /// `True` expands to a single empty clause (matches everything);
+    /// `Or` concatenates its branches' clauses; `And` distributes over
+    /// its branches' clauses via the standard DNF cross product; `Not`
+    /// is pushed down to its leaves via [`MatchExpr::negate`] before
+    /// expansion, inverting each negated condition's operator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ClauseLimitExceeded`] if distributing `And` over
+    /// wide `Or` branches would produce more than `max_clauses` clauses.
+    /// `expr` is only used to label that error.
+    pub fn to_dnf(&self, expr: &str, max_clauses: usize) -> Result<Vec<Vec<RefineryCondition>>> {
+        let clauses = self.dnf_clauses(expr, max_clauses)?;
+        let clauses = if clauses.is_empty() {
+            vec![Vec::new()]
+        } else {
+            clauses
+        };
+        Ok(clauses
+            .into_iter()
+            .map(|clause| clause.iter().map(Condition::to_refinery).collect())
+            .collect())
+    }
+
+    /// Convenience wrapper around [`Self::to_dnf`] for callers that don't
+    /// have the original expression text handy (e.g. a rule rebuilt from
+    /// `RefineryCondition`s rather than parsed from source) and are happy
+    /// with [`DEFAULT_MAX_DNF_CLAUSES`]. [`crate::compiler::Compiler`]
+    /// uses `to_dnf` directly so it can label errors with the expression
+    /// as written in the policy; prefer that where the source text is
+    /// available.
+    pub fn to_refinery_rule_sets(&self) -> Result<Vec<Vec<RefineryCondition>>> {
+        self.to_dnf(&format!("{self:?}"), DEFAULT_MAX_DNF_CLAUSES)
+    }
+
+    fn dnf_clauses(&self, expr: &str, max_clauses: usize) -> Result<Vec<Vec<Condition>>> {
+        let clauses = match self {
+            Self::True => vec![Vec::new()],
+            Self::Condition(c) => match c.expand_set_membership() {
+                Some(expanded) => expanded.dnf_clauses(expr, max_clauses)?,
+                None => vec![vec![c.clone()]],
+            },
+            Self::Or(exprs) => {
+                let mut clauses = Vec::new();
+                for sub in exprs {
+                    clauses.extend(sub.dnf_clauses(expr, max_clauses)?);
+                    if clauses.len() > max_clauses {
+                        return Err(too_many_clauses(expr, max_clauses, clauses.len()));
+                    }
+                }
+                clauses
+            }
+            Self::And(exprs) => {
+                let mut clauses = vec![Vec::new()];
+                for sub in exprs {
+                    let sub_clauses = sub.dnf_clauses(expr, max_clauses)?;
+                    let mut combined = Vec::with_capacity(clauses.len() * sub_clauses.len().max(1));
+                    for prefix in &clauses {
+                        for sub_clause in &sub_clauses {
+                            let mut merged = prefix.clone();
+                            merged.extend(sub_clause.iter().cloned());
+                            combined.push(merged);
+                        }
+                    }
+                    if combined.len() > max_clauses {
+                        return Err(too_many_clauses(expr, max_clauses, combined.len()));
+                    }
+                    clauses = combined;
+                }
+                clauses
+            }
+            Self::Not(inner) => inner.negate()?.dnf_clauses(expr, max_clauses)?,
+            Self::Threshold { k, subs } => {
+                if *k == 0 {
+                    vec![Vec::new()]
+                } else {
+                    // "At least k of n" is the OR, over every k-sized
+                    // subset of `subs`, of that subset's AND - any
+                    // satisfying assignment with more than k subs true
+                    // still satisfies at least one such subset, so
+                    // exactly-k subsets already cover the full region.
+                    // `parse_threshold` rejects `k > subs.len()` up
+                    // front, so every subset chosen here is non-empty.
+                    if n_choose_k(subs.len(), *k) > max_clauses {
+                        return Err(too_many_clauses(
+                            expr,
+                            max_clauses,
+                            n_choose_k(subs.len(), *k),
+                        ));
+                    }
+
+                    let mut clauses = Vec::new();
+                    for combo in combinations(subs, *k) {
+                        let mut combo_clauses = vec![Vec::new()];
+                        for sub in combo {
+                            let sub_clauses = sub.dnf_clauses(expr, max_clauses)?;
+                            let mut combined =
+                                Vec::with_capacity(combo_clauses.len() * sub_clauses.len().max(1));
+                            for prefix in &combo_clauses {
+                                for sub_clause in &sub_clauses {
+                                    let mut merged = prefix.clone();
+                                    merged.extend(sub_clause.iter().cloned());
+                                    combined.push(merged);
+                                }
+                            }
+                            if combined.len() > max_clauses {
+                                return Err(too_many_clauses(expr, max_clauses, combined.len()));
+                            }
+                            combo_clauses = combined;
+                        }
+                        clauses.extend(combo_clauses);
+                        if clauses.len() > max_clauses {
+                            return Err(too_many_clauses(expr, max_clauses, clauses.len()));
+                        }
+                    }
+                    clauses
+                }
+            }
+        };
+        Ok(clauses)
+    }
+
+    /// Returns the logical negation of this expression, pushing `Not`
+    /// down to conditions via De Morgan's laws (`!(a && b)` becomes
+    /// `!a || !b`, and vice versa) instead of wrapping it - Refinery
+    /// conditions have no negation of their own, so this is the only
+    /// way a `!` can flow through to a rule.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Unsupported`] if negating `true` (there's no
+    /// "never matches" expression to produce) or if a negated
+    /// condition's operator has no inverse (`Contains`, `StartsWith`,
+    /// `Exists`, `Matches`).
+    fn negate(&self) -> Result<Self> {
+        match self {
+            Self::True => Err(Error::Unsupported(
+                "negating 'true' (match-all) has no representable match expression".to_string(),
+            )),
+            Self::Condition(c) => Ok(Self::Condition(c.negate()?)),
+            Self::And(exprs) => Ok(Self::Or(
+                exprs.iter().map(Self::negate).collect::<Result<_>>()?,
+            )),
+            Self::Or(exprs) => Ok(Self::And(
+                exprs.iter().map(Self::negate).collect::<Result<_>>()?,
+            )),
+            Self::Not(inner) => Ok((**inner).clone()),
+            Self::Threshold { k: 0, .. } => Err(Error::Unsupported(
+                "negating 'thresh(0, ...)' (always matches) has no representable match expression"
+                    .to_string(),
+            )),
+            Self::Threshold { k, subs } => {
+                // "Fewer than k of n hold" is itself a threshold: at
+                // least (n - k + 1) of the negated subs must hold.
+                let negated = subs.iter().map(Self::negate).collect::<Result<Vec<_>>>()?;
+                Ok(Self::Threshold {
+                    k: subs.len() - k + 1,
+                    subs: negated,
+                })
+            }
+        }
+    }
+}
+
+/// Yields every `k`-sized subset of `items`, via simple backtracking -
+/// bounded at the call site by [`n_choose_k`] against
+/// [`MatchExpr::dnf_clauses`]'s own `max_clauses` guard before any
+/// subsets are actually generated.
+fn combinations<T>(items: &[T], k: usize) -> Vec<Vec<&T>> {
+    fn backtrack<'a, T>(
+        items: &'a [T],
+        k: usize,
+        start: usize,
+        current: &mut Vec<&'a T>,
+        result: &mut Vec<Vec<&'a T>>,
+    ) {
+        if current.len() == k {
+            result.push(current.clone());
+            return;
+        }
+        for i in start..items.len() {
+            current.push(&items[i]);
+            backtrack(items, k, i + 1, current, result);
+            current.pop();
         }
     }
+
+    let mut result = Vec::new();
+    backtrack(items, k, 0, &mut Vec::with_capacity(k), &mut result);
+    result
+}
+
+/// Number of `k`-sized subsets of an `n`-element set, saturating at
+/// `usize::MAX` instead of overflowing - used only as a cheap upper
+/// bound to reject a threshold expansion before paying to generate it.
+fn n_choose_k(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        result = result.saturating_mul((n - i) as u128) / (i as u128 + 1);
+        if result > u128::from(usize::MAX) {
+            return usize::MAX;
+        }
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    let result = result as usize;
+    result
+}
+
+fn too_many_clauses(expr: &str, limit: usize, clauses: usize) -> Error {
+    Error::ClauseLimitExceeded {
+        expr: expr.to_string(),
+        limit,
+        clauses,
+    }
 }
 
 impl Condition {
+    /// Expands an `In`/`NotIn` condition into the `Or`/`And` of
+    /// per-element `Eq`/`Ne` conditions it's shorthand for (`status in
+    /// [a, b]` becomes `status == a || status == b`), so the rest of
+    /// the DNF/Refinery pipeline never has to special-case set
+    /// membership. Returns `None` for every other operator, or if the
+    /// value isn't the `[ ... ]` list literal the grammar expects.
+    fn expand_set_membership(&self) -> Option<MatchExpr> {
+        let (operator, combinator): (Operator, fn(Vec<MatchExpr>) -> MatchExpr) =
+            match self.operator {
+                Operator::In => (Operator::Eq, MatchExpr::Or),
+                Operator::NotIn => (Operator::Ne, MatchExpr::And),
+                _ => return None,
+            };
+        let Value::List(items) = &self.value else {
+            return None;
+        };
+        Some(combinator(
+            items
+                .iter()
+                .map(|item| {
+                    MatchExpr::Condition(Self {
+                        field: self.field.clone(),
+                        operator,
+                        value: item.clone(),
+                        span: self.span.clone(),
+                    })
+                })
+                .collect(),
+        ))
+    }
+
+    /// Coerces this condition's value to its field's declared
+    /// [`FieldKind`] in `schema`, and checks its operator is supported
+    /// by that kind. Leaves the condition unchanged if `schema` has no
+    /// entry for its field.
+    fn coerce_to_schema(mut self, source: &str, schema: &FieldSchema) -> Result<Self> {
+        let Some(kind) = schema.get(&self.field) else {
+            return Ok(self);
+        };
+
+        if !kind.supports_operator(self.operator) {
+            return Err(Error::TypeMismatch {
+                expr: source.to_string(),
+                span: (self.span.start, self.span.end),
+                reason: format!(
+                    "'{}' is {} and does not support operator {:?}",
+                    self.field,
+                    kind.name(),
+                    self.operator
+                ),
+            });
+        }
+
+        self.value = kind.coerce(self.value).map_err(|reason| Error::TypeMismatch {
+            expr: source.to_string(),
+            span: (self.span.start, self.span.end),
+            reason: format!("field '{}': {reason}", self.field),
+        })?;
+
+        Ok(self)
+    }
+
     fn to_refinery(&self) -> RefineryCondition {
         let operator = match self.operator {
             Operator::Eq => "=",
@@ -146,6 +660,14 @@ impl Condition {
             Operator::Contains => "contains",
             Operator::StartsWith => "starts-with",
             Operator::Exists => "exists",
+            Operator::Matches => "matches",
+            // `to_refinery_conditions`/`dnf_clauses` always expand `In`/
+            // `NotIn` into an Or/And of `Eq`/`Ne` leaves before a
+            // `Condition` ever reaches here - these strings only exist
+            // so this match stays exhaustive as the `Operator` enum
+            // grows.
+            Operator::In => "in",
+            Operator::NotIn => "not-in",
         };
 
         let value = match &self.value {
@@ -162,6 +684,10 @@ impl Condition {
                 #[allow(clippy::cast_possible_wrap)]
                 ConditionValue::Number(*ms as i64)
             }
+            // Like `In`/`NotIn` above, a bare `Value::List` should never
+            // reach here - `expand_set_membership` unpacks it into
+            // individual scalar conditions first.
+            Value::List(items) => ConditionValue::String(format!("{items:?}")),
         };
 
         // Handle duration field name mapping
@@ -177,6 +703,23 @@ impl Condition {
             value,
         }
     }
+
+    /// Inverts this condition's operator, or errors if the operator has
+    /// no inverse.
+    fn negate(&self) -> Result<Self> {
+        let Some(operator) = self.operator.invert() else {
+            return Err(Error::Unsupported(format!(
+                "'{:?}' on field '{}' has no negation, rewrite the rule without '!'",
+                self.operator, self.field
+            )));
+        };
+        Ok(Self {
+            field: self.field.clone(),
+            operator,
+            value: self.value.clone(),
+            span: self.span.clone(),
+        })
+    }
 }
 
 impl Operator {
@@ -191,197 +734,942 @@ impl Operator {
             "contains" => Some(Self::Contains),
             "starts-with" | "startsWith" => Some(Self::StartsWith),
             "exists" => Some(Self::Exists),
+            "in" => Some(Self::In),
+            "not-in" => Some(Self::NotIn),
+            "matches" => Some(Self::Matches),
             _ => None,
         }
     }
+
+    /// Returns the operator that negates this one (`a == b` negated is
+    /// `a != b`, and so on), or `None` if this operator has no inverse
+    /// (`Contains`, `StartsWith`, `Exists`, `Matches`).
+    const fn invert(self) -> Option<Self> {
+        match self {
+            Self::Eq => Some(Self::Ne),
+            Self::Ne => Some(Self::Eq),
+            Self::Gt => Some(Self::Le),
+            Self::Le => Some(Self::Gt),
+            Self::Ge => Some(Self::Lt),
+            Self::Lt => Some(Self::Ge),
+            Self::In => Some(Self::NotIn),
+            Self::NotIn => Some(Self::In),
+            Self::Contains | Self::StartsWith | Self::Exists | Self::Matches => None,
+        }
+    }
 }
 
-/// Splits an input by a logical operator (|| or &&), respecting parentheses.
-fn split_logical(input: &str, op: &str) -> Option<Vec<String>> {
-    let mut parts = Vec::new();
-    let mut current = String::new();
-    let mut paren_depth = 0;
-    let mut chars = input.chars().peekable();
+/// A source of attribute values a [`MatchExpr`] can be [`MatchExpr::eval`]'d
+/// against - typically a wrapper around some concrete trace type. Kept as a
+/// trait rather than a concrete struct so this crate doesn't need a
+/// dependency on whatever owns that trace type; callers (e.g.
+/// `nectar_prover`) implement it over their own data with a thin local
+/// adapter.
+pub trait AttributeSource {
+    /// Returns the value of `field`, or `None` if this source has no
+    /// value for it. A missing value makes every operator except
+    /// [`Operator::Exists`] evaluate to `false`, matching the "absent
+    /// attribute never matches" convention the rest of this module
+    /// follows.
+    fn attribute(&self, field: &str) -> Option<Value>;
+}
 
-    while let Some(c) = chars.next() {
-        match c {
-            '(' => {
-                paren_depth += 1;
-                current.push(c);
-            }
-            ')' => {
-                paren_depth -= 1;
-                current.push(c);
-            }
-            '|' if op == "||" && paren_depth == 0 => {
-                if chars.peek() == Some(&'|') {
-                    chars.next();
-                    parts.push(current.trim().to_string());
-                    current = String::new();
-                } else {
-                    current.push(c);
+impl MatchExpr {
+    /// Evaluates this expression against `attrs`, following the usual
+    /// short-circuiting boolean semantics for `And`/`Or`/`Not`.
+    #[must_use]
+    pub fn eval(&self, attrs: &dyn AttributeSource) -> bool {
+        match self {
+            Self::True => true,
+            Self::Condition(condition) => condition.eval(attrs),
+            Self::And(exprs) => exprs.iter().all(|e| e.eval(attrs)),
+            Self::Or(exprs) => exprs.iter().any(|e| e.eval(attrs)),
+            Self::Not(inner) => !inner.eval(attrs),
+            Self::Threshold { k, subs } => subs.iter().filter(|e| e.eval(attrs)).count() >= *k,
+        }
+    }
+}
+
+impl Condition {
+    /// Evaluates this leaf condition against `attrs`. A missing attribute
+    /// evaluates to `false` for every operator except [`Operator::Exists`],
+    /// which checks presence directly.
+    fn eval(&self, attrs: &dyn AttributeSource) -> bool {
+        if self.operator == Operator::Exists {
+            return attrs.attribute(&self.field).is_some();
+        }
+
+        let Some(actual) = attrs.attribute(&self.field) else {
+            return false;
+        };
+
+        match self.operator {
+            Operator::Eq => values_equal(&actual, &self.value),
+            Operator::Ne => !values_equal(&actual, &self.value),
+            Operator::Gt | Operator::Ge | Operator::Lt | Operator::Le => {
+                match (numeric_value(&actual), numeric_value(&self.value)) {
+                    (Some(a), Some(b)) => compare_numeric(self.operator, a, b),
+                    _ => false,
                 }
             }
-            '&' if op == "&&" && paren_depth == 0 => {
-                if chars.peek() == Some(&'&') {
-                    chars.next();
-                    parts.push(current.trim().to_string());
-                    current = String::new();
+            Operator::Contains => match (&actual, &self.value) {
+                (Value::String(a), Value::String(b)) => a.contains(b.as_str()),
+                _ => false,
+            },
+            Operator::StartsWith => match (&actual, &self.value) {
+                (Value::String(a), Value::String(b)) => a.starts_with(b.as_str()),
+                _ => false,
+            },
+            Operator::In | Operator::NotIn => {
+                let Value::List(items) = &self.value else {
+                    return false;
+                };
+                let is_member = items.iter().any(|item| values_equal(&actual, item));
+                if self.operator == Operator::In {
+                    is_member
                 } else {
-                    current.push(c);
+                    !is_member
                 }
             }
-            _ => current.push(c),
+            Operator::Matches => match (&actual, &self.value) {
+                (Value::String(a), Value::String(pattern)) => {
+                    regex::Regex::new(pattern).is_ok_and(|re| re.is_match(a))
+                }
+                _ => false,
+            },
+            Operator::Exists => unreachable!("handled above"),
         }
     }
+}
+
+/// Compares two [`Value`]s for equality, treating any combination of
+/// [`Value::Int`]/[`Value::Float`]/[`Value::Duration`] as numerically
+/// comparable (so `duration == 500` and `http.status == 500.0` both work
+/// as expected) rather than requiring identical variants.
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::String(a), Value::String(b)) => a == b,
+        (Value::Bool(a), Value::Bool(b)) => a == b,
+        (Value::List(_), _) | (_, Value::List(_)) => false,
+        _ => match (numeric_value(a), numeric_value(b)) {
+            (Some(a), Some(b)) => (a - b).abs() < f64::EPSILON,
+            _ => false,
+        },
+    }
+}
 
-    if !current.is_empty() {
-        parts.push(current.trim().to_string());
+/// Returns a numeric reading of `value` for relational comparisons, or
+/// `None` for value kinds that have no natural ordering.
+fn numeric_value(value: &Value) -> Option<f64> {
+    match value {
+        #[allow(clippy::cast_precision_loss)]
+        Value::Int(n) => Some(*n as f64),
+        Value::Float(f) => Some(*f),
+        #[allow(clippy::cast_precision_loss)]
+        Value::Duration(ms) => Some(*ms as f64),
+        Value::String(_) | Value::Bool(_) | Value::List(_) => None,
     }
+}
 
-    if parts.len() > 1 {
-        Some(parts)
-    } else {
-        None
+/// Applies a relational operator to two already-extracted numeric
+/// operands.
+fn compare_numeric(operator: Operator, a: f64, b: f64) -> bool {
+    match operator {
+        Operator::Gt => a > b,
+        Operator::Ge => a >= b,
+        Operator::Lt => a < b,
+        Operator::Le => a <= b,
+        _ => false,
     }
 }
 
-/// Parses a simple condition like "http.status >= 500".
-fn parse_condition(input: &str) -> Result<Condition> {
-    let input = input.trim();
+/// The type a well-known attribute is expected to hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttrType {
+    /// `http.status`.
+    Integer,
+    /// `duration` (requires a unit suffix such as `ms`, `s`, `m`).
+    Duration,
+    /// `error`.
+    Boolean,
+    /// `service.name` / `http.route`.
+    String,
+}
 
-    // Try each operator in order of length (longest first to avoid prefix conflicts)
-    let operators = [">=", "<=", "!=", "==", ">", "<", "=", "contains", "starts-with", "exists"];
+impl AttrType {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::Duration => "duration",
+            Self::Boolean => "boolean",
+            Self::String => "string",
+        }
+    }
+}
 
-    for op_str in operators {
-        if let Some(idx) = input.find(op_str) {
-            let field = input[..idx].trim().to_string();
-            let value_str = input[idx + op_str.len()..].trim();
+/// Returns the expected type of a well-known attribute, or `None` if the
+/// field isn't one this analysis models.
+fn attr_type(field: &str) -> Option<AttrType> {
+    match field {
+        "http.status" => Some(AttrType::Integer),
+        "duration" => Some(AttrType::Duration),
+        "error" => Some(AttrType::Boolean),
+        "service.name" | "http.route" => Some(AttrType::String),
+        _ => None,
+    }
+}
 
-            let operator =
-                Operator::from_str(op_str).ok_or_else(|| Error::InvalidMatch {
-                    expr: input.to_string(),
-                    reason: format!("unknown operator: {op_str}"),
-                })?;
+/// Type-checks a match expression against the well-known attribute
+/// domains, rejecting mismatched comparisons (string-vs-int,
+/// boolean-vs-relational-operator, a duration missing its unit) before
+/// they can silently slip through to the prover.
+///
+/// # Errors
+///
+/// Returns [`Error::TypeMismatch`] carrying the byte span of the
+/// offending comparison within `input`, or [`Error::InvalidMatch`] if
+/// `input` doesn't parse as a match expression at all.
+pub fn type_check(input: &str) -> Result<()> {
+    for (span, field, operator, value) in leaf_conditions(input)? {
+        let Some(expected) = attr_type(&field) else {
+            continue;
+        };
 
-            // Handle exists operator (no value needed)
-            if operator == Operator::Exists {
-                return Ok(Condition {
-                    field,
-                    operator,
-                    value: Value::Bool(true),
-                });
-            }
+        let actual_ok = match (expected, &value) {
+            (AttrType::Integer, Value::Int(_)) => true,
+            (AttrType::Duration, Value::Duration(_)) => true,
+            (AttrType::Boolean, Value::Bool(_)) => true,
+            (AttrType::String, Value::String(_)) => true,
+            _ => false,
+        };
 
-            let value = parse_value(value_str);
+        if !actual_ok {
+            let found = match &value {
+                Value::String(_) => "string",
+                Value::Int(_) => "integer",
+                Value::Float(_) => "float",
+                Value::Bool(_) => "boolean",
+                Value::Duration(_) => "duration",
+                Value::List(_) => "list",
+            };
+            let reason = if expected == AttrType::Duration {
+                format!("'{field}' requires a duration with a unit (e.g. 500ms, 5s), found {found}")
+            } else {
+                format!("'{field}' expects a {} value, found {found}", expected.name())
+            };
+            return Err(Error::TypeMismatch {
+                expr: input.to_string(),
+                span,
+                reason,
+            });
+        }
 
-            return Ok(Condition {
-                field,
-                operator,
-                value,
+        if matches!(expected, AttrType::Boolean | AttrType::String)
+            && matches!(operator, Operator::Gt | Operator::Ge | Operator::Lt | Operator::Le)
+        {
+            return Err(Error::TypeMismatch {
+                expr: input.to_string(),
+                span,
+                reason: format!(
+                    "'{field}' is {} and does not support relational operators",
+                    expected.name()
+                ),
             });
         }
     }
 
-    Err(Error::InvalidMatch {
-        expr: input.to_string(),
-        reason: "no valid operator found".to_string(),
-    })
+    Ok(())
 }
 
-/// Parses a value string.
-fn parse_value(s: &str) -> Value {
-    let s = s.trim();
+/// Walks a match expression's AND/OR structure and returns every leaf
+/// condition together with its byte span within the *original* `input`,
+/// parsed field/operator/value.
+fn leaf_conditions(input: &str) -> Result<Vec<((usize, usize), String, Operator, Value)>> {
+    fn walk(
+        input: &str,
+        base: usize,
+        out: &mut Vec<((usize, usize), String, Operator, Value)>,
+    ) -> Result<()> {
+        let trimmed_start = input.len() - input.trim_start().len();
+        let trimmed = input.trim();
+        let base = base + trimmed_start;
 
-    // Boolean
-    if s.eq_ignore_ascii_case("true") {
-        return Value::Bool(true);
-    }
-    if s.eq_ignore_ascii_case("false") {
-        return Value::Bool(false);
-    }
+        if trimmed.eq_ignore_ascii_case("true") {
+            return Ok(());
+        }
 
-    // Duration (e.g., "5s", "100ms")
-    if let Some(dur) = parse_duration_value(s) {
-        return Value::Duration(dur);
+        for op in ["||", "&&"] {
+            if let Some(spans) = split_logical_with_offsets(trimmed, op) {
+                for (offset, part) in spans {
+                    walk(part, base + offset, out)?;
+                }
+                return Ok(());
+            }
+        }
+
+        // `!`/parens don't change a leaf condition's field/value types,
+        // so for type-checking purposes it's enough to see through them
+        // rather than track how they nest - full span-aware parsing of
+        // this grammar is tracked separately.
+        if let Some(inner) = trimmed.strip_prefix('!') {
+            return walk(inner, base + 1, out);
+        }
+        if trimmed.starts_with('(') && trimmed.ends_with(')') && is_outer_paren_pair(trimmed) {
+            return walk(&trimmed[1..trimmed.len() - 1], base + 1, out);
+        }
+
+        if let Some(after_open) = trimmed.strip_prefix("thresh(") {
+            let open_len = trimmed.len() - after_open.len();
+            let args_base = base + open_len;
+            if let Some(close_idx) = find_matching_close_paren(after_open) {
+                let args = &after_open[..close_idx];
+                for (part, part_base) in split_top_level_args(args, args_base).into_iter().skip(1) {
+                    walk(part, part_base, out)?;
+                }
+                return Ok(());
+            }
+        }
+
+        let condition = parse_condition(trimmed, base)?;
+        out.push((
+            (condition.span.start, condition.span.end),
+            condition.field,
+            condition.operator,
+            condition.value,
+        ));
+        Ok(())
     }
 
-    // Quoted string
-    if (s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\'')) {
-        let inner = &s[1..s.len() - 1];
-        return Value::String(inner.to_string());
+    let mut out = Vec::new();
+    walk(input, 0, &mut out)?;
+    Ok(out)
+}
+
+/// Splits `input` by a logical operator (`||` or `&&`), respecting
+/// parentheses, and also returns each part's byte offset within `input`.
+fn split_logical_with_offsets<'a>(input: &'a str, op: &str) -> Option<Vec<(usize, &'a str)>> {
+    let mut parts = Vec::new();
+    let mut part_start = 0;
+    let mut paren_depth = 0;
+    let bytes = input.as_bytes();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' => paren_depth += 1,
+            b')' => paren_depth -= 1,
+            b'|' if op == "||" && paren_depth == 0 && bytes.get(i + 1) == Some(&b'|') => {
+                parts.push((part_start, &input[part_start..i]));
+                i += 1;
+                part_start = i + 1;
+            }
+            b'&' if op == "&&" && paren_depth == 0 && bytes.get(i + 1) == Some(&b'&') => {
+                parts.push((part_start, &input[part_start..i]));
+                i += 1;
+                part_start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
     }
 
-    // Integer
-    if let Ok(n) = s.parse::<i64>() {
-        return Value::Int(n);
+    if part_start < input.len() {
+        parts.push((part_start, &input[part_start..]));
     }
 
-    // Float
-    if let Ok(f) = s.parse::<f64>() {
-        return Value::Float(f);
+    if parts.len() > 1 {
+        Some(parts)
+    } else {
+        None
     }
+}
 
-    // Unquoted string
-    Value::String(s.to_string())
+/// True if `input`'s first `(` and last `)` are a matching pair (i.e.
+/// paren depth doesn't return to zero before the final byte), so
+/// stripping them leaves a single inner expression rather than two
+/// siblings like `(a) && (b)`.
+fn is_outer_paren_pair(input: &str) -> bool {
+    let bytes = input.as_bytes();
+    let mut depth = 0i32;
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i == bytes.len() - 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
 }
 
-/// Parses a duration string like "5s", "100ms" to milliseconds.
-fn parse_duration_value(s: &str) -> Option<u64> {
-    if let Some(ms_str) = s.strip_suffix("ms") {
-        return ms_str.trim().parse().ok();
+/// Increments `depth` for a recursive descent into a nested `(`, `!`, or
+/// `thresh(...)` sub-expression starting at `base` within `input`,
+/// rejecting the expression once [`MAX_PARSE_DEPTH`] would be exceeded
+/// instead of recursing further.
+fn check_depth(input: &str, base: usize, depth: usize) -> Result<usize> {
+    let depth = depth + 1;
+    if depth > MAX_PARSE_DEPTH {
+        return Err(Error::InvalidMatch {
+            expr: input.to_string(),
+            span: base..base + input.len().min(1),
+            reason: format!("match expression nesting exceeds the limit of {MAX_PARSE_DEPTH}"),
+        });
     }
+    Ok(depth)
+}
 
-    if let Some(s_str) = s.strip_suffix('s') {
-        let secs: f64 = s_str.trim().parse().ok()?;
-        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-        return Some((secs * 1000.0) as u64);
+/// Parses the `or := and ('||' and)*` production, returning the parsed
+/// expression, whatever of `input` is left unconsumed, and that
+/// remainder's absolute byte offset (`base` is `input`'s own offset
+/// within the original source).
+fn parse_or(input: &str, base: usize, depth: usize) -> Result<(MatchExpr, &str, usize)> {
+    let (first, mut rest, mut rest_base) = parse_and(input, base, depth)?;
+    let mut terms = vec![first];
+    loop {
+        let skip = rest.len() - rest.trim_start().len();
+        let trimmed = &rest[skip..];
+        let trimmed_base = rest_base + skip;
+        let Some(after) = trimmed.strip_prefix("||") else {
+            rest = trimmed;
+            rest_base = trimmed_base;
+            break;
+        };
+        let (next, r, r_base) = parse_and(after, trimmed_base + 2, depth)?;
+        terms.push(next);
+        rest = r;
+        rest_base = r_base;
     }
+    let expr = if terms.len() == 1 {
+        terms.into_iter().next().expect("just checked len == 1")
+    } else {
+        MatchExpr::Or(terms)
+    };
+    Ok((expr, rest, rest_base))
+}
 
-    if let Some(m_str) = s.strip_suffix('m') {
-        let mins: f64 = m_str.trim().parse().ok()?;
-        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
-        return Some((mins * 60.0 * 1000.0) as u64);
+/// Parses the `and := unary ('&&' unary)*` production.
+fn parse_and(input: &str, base: usize, depth: usize) -> Result<(MatchExpr, &str, usize)> {
+    let (first, mut rest, mut rest_base) = parse_unary(input, base, depth)?;
+    let mut terms = vec![first];
+    loop {
+        let skip = rest.len() - rest.trim_start().len();
+        let trimmed = &rest[skip..];
+        let trimmed_base = rest_base + skip;
+        let Some(after) = trimmed.strip_prefix("&&") else {
+            rest = trimmed;
+            rest_base = trimmed_base;
+            break;
+        };
+        let (next, r, r_base) = parse_unary(after, trimmed_base + 2, depth)?;
+        terms.push(next);
+        rest = r;
+        rest_base = r_base;
     }
+    let expr = if terms.len() == 1 {
+        terms.into_iter().next().expect("just checked len == 1")
+    } else {
+        MatchExpr::And(terms)
+    };
+    Ok((expr, rest, rest_base))
+}
 
-    None
+/// Parses the `unary := '!' unary | primary` production.
+fn parse_unary(input: &str, base: usize, depth: usize) -> Result<(MatchExpr, &str, usize)> {
+    let skip = input.len() - input.trim_start().len();
+    let trimmed = &input[skip..];
+    let base = base + skip;
+    if let Some(after) = trimmed.strip_prefix('!') {
+        let depth = check_depth(trimmed, base, depth)?;
+        let (inner, rest, rest_base) = parse_unary(after, base + 1, depth)?;
+        return Ok((MatchExpr::Not(Box::new(inner)), rest, rest_base));
+    }
+    parse_primary(trimmed, base, depth)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Parses the `primary := '(' or ')' | condition` production, plus the
+/// `true` literal.
+fn parse_primary(input: &str, base: usize, depth: usize) -> Result<(MatchExpr, &str, usize)> {
+    let skip = input.len() - input.trim_start().len();
+    let trimmed = &input[skip..];
+    let base = base + skip;
 
-    #[test]
-    fn parse_true() {
-        assert_eq!(MatchExpr::parse("true").unwrap(), MatchExpr::True);
-        assert_eq!(MatchExpr::parse("TRUE").unwrap(), MatchExpr::True);
+    if let Some(after) = trimmed.strip_prefix('(') {
+        let depth = check_depth(trimmed, base, depth)?;
+        let (inner, rest, rest_base) = parse_or(after, base + 1, depth)?;
+        let close_skip = rest.len() - rest.trim_start().len();
+        let rest = &rest[close_skip..];
+        let rest_base = rest_base + close_skip;
+        let Some(rest) = rest.strip_prefix(')') else {
+            return Err(Error::InvalidMatch {
+                expr: trimmed.to_string(),
+                span: rest_base..rest_base + 1,
+                reason: "unclosed '('".to_string(),
+            });
+        };
+        return Ok((inner, rest, rest_base + 1));
     }
 
-    #[test]
-    fn parse_simple_comparison() {
-        let expr = MatchExpr::parse("http.status >= 500").unwrap();
-        if let MatchExpr::Condition(cond) = expr {
-            assert_eq!(cond.field, "http.status");
-            assert_eq!(cond.operator, Operator::Ge);
-            assert_eq!(cond.value, Value::Int(500));
-        } else {
-            panic!("expected Condition");
-        }
+    if let Some(rest) = strip_true_literal(trimmed) {
+        return Ok((MatchExpr::True, rest, base + (trimmed.len() - rest.len())));
     }
 
-    #[test]
-    fn parse_string_comparison() {
-        let expr = MatchExpr::parse("service.name == \"checkout\"").unwrap();
-        if let MatchExpr::Condition(cond) = expr {
-            assert_eq!(cond.field, "service.name");
-            assert_eq!(cond.operator, Operator::Eq);
-            assert_eq!(cond.value, Value::String("checkout".to_string()));
-        } else {
-            panic!("expected Condition");
-        }
+    if trimmed.starts_with("thresh(") {
+        let depth = check_depth(trimmed, base, depth)?;
+        return parse_threshold(trimmed, base, depth);
     }
 
-    #[test]
+    let (chunk, rest) = take_condition_chunk(trimmed).ok_or_else(|| Error::InvalidMatch {
+        expr: trimmed.to_string(),
+        span: base..base + trimmed.len().min(1),
+        reason: "expected a condition, '(' or '!'".to_string(),
+    })?;
+    let condition = parse_condition(chunk, base)?;
+    let rest_base = base + (trimmed.len() - rest.len());
+    Ok((MatchExpr::Condition(condition), rest, rest_base))
+}
+
+/// If `input` starts with the `true` literal (case-insensitive, not
+/// merely as a prefix of a longer identifier), returns the remainder
+/// after it.
+fn strip_true_literal(input: &str) -> Option<&str> {
+    let boundary = input.len() >= 4 && input.is_char_boundary(4);
+    if boundary && input[..4].eq_ignore_ascii_case("true") {
+        match input[4..].chars().next() {
+            Some(c) if c.is_alphanumeric() || c == '_' => None,
+            _ => Some(&input[4..]),
+        }
+    } else {
+        None
+    }
+}
+
+/// Parses a `thresh(k, sub1, sub2, ...)` primary - `input` must already
+/// start with `"thresh("`. `base` is `input`'s absolute byte offset
+/// within the original source.
+fn parse_threshold(input: &str, base: usize, depth: usize) -> Result<(MatchExpr, &str, usize)> {
+    let open_len = "thresh(".len();
+    let args_base = base + open_len;
+    let after_open = &input[open_len..];
+
+    let Some(close_idx) = find_matching_close_paren(after_open) else {
+        return Err(Error::InvalidMatch {
+            expr: input.to_string(),
+            span: base..base + input.len(),
+            reason: "unclosed 'thresh('".to_string(),
+        });
+    };
+
+    let args = &after_open[..close_idx];
+    let rest = &after_open[close_idx + 1..];
+    let rest_base = args_base + close_idx + 1;
+
+    let parts = split_top_level_args(args, args_base);
+    let [k_part, sub_parts @ ..] = parts.as_slice() else {
+        return Err(Error::InvalidMatch {
+            expr: input.to_string(),
+            span: base..rest_base,
+            reason: "'thresh(...)' requires a count and at least one sub-expression".to_string(),
+        });
+    };
+    if sub_parts.is_empty() {
+        return Err(Error::InvalidMatch {
+            expr: input.to_string(),
+            span: base..rest_base,
+            reason: "'thresh(...)' requires at least one sub-expression".to_string(),
+        });
+    }
+
+    let (k_text, k_base) = *k_part;
+    let k_trim_start = k_text.len() - k_text.trim_start().len();
+    let k: usize = k_text.trim().parse().map_err(|_| Error::InvalidMatch {
+        expr: input.to_string(),
+        span: (k_base + k_trim_start)..(k_base + k_text.trim_end().len()),
+        reason: format!("'{}' is not a valid threshold count", k_text.trim()),
+    })?;
+
+    if k > sub_parts.len() {
+        return Err(Error::InvalidMatch {
+            expr: input.to_string(),
+            span: base..rest_base,
+            reason: format!(
+                "'thresh({k}, ...)' requires at least {k} sub-expressions, only {} given",
+                sub_parts.len()
+            ),
+        });
+    }
+
+    let mut subs = Vec::with_capacity(sub_parts.len());
+    for (part, part_base) in sub_parts {
+        let (sub, sub_rest, sub_rest_base) = parse_or(part, *part_base, depth)?;
+        let trailing_skip = sub_rest.len() - sub_rest.trim_start().len();
+        let trailing = &sub_rest[trailing_skip..];
+        if !trailing.is_empty() {
+            return Err(Error::InvalidMatch {
+                expr: input.to_string(),
+                span: (sub_rest_base + trailing_skip)..(sub_rest_base + sub_rest.len()),
+                reason: format!("unexpected trailing input in thresh() argument: '{trailing}'"),
+            });
+        }
+        subs.push(sub);
+    }
+
+    Ok((MatchExpr::Threshold { k, subs }, rest, rest_base))
+}
+
+/// Finds the index (relative to `input`, which is everything after an
+/// already-consumed `"thresh("`'s opening paren) of the `)` that closes
+/// it, tracking nested `(`/`)` depth and quoted strings so a
+/// sub-expression's own parens or quoted commas/parens don't confuse
+/// the scan.
+fn find_matching_close_paren(input: &str) -> Option<usize> {
+    let bytes = input.as_bytes();
+    let mut paren_depth = 1i32;
+    let mut quote: Option<u8> = None;
+    for (i, &b) in bytes.iter().enumerate() {
+        if let Some(q) = quote {
+            if b == q {
+                quote = None;
+            }
+            continue;
+        }
+        match b {
+            b'"' | b'\'' => quote = Some(b),
+            b'(' => paren_depth += 1,
+            b')' => {
+                paren_depth -= 1;
+                if paren_depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Splits a `thresh(...)`'s argument list on top-level commas -
+/// respecting nested parens, bracketed list literals and quoted strings
+/// the way [`split_top_level_commas`] respects quotes for a list
+/// literal's items - and returns each part paired with its absolute
+/// byte offset within the original source (`base` is `input`'s own
+/// offset). Parts are trimmed of surrounding whitespace.
+fn split_top_level_args(input: &str, base: usize) -> Vec<(&str, usize)> {
+    fn push_part<'a>(
+        input: &'a str,
+        base: usize,
+        start: usize,
+        end: usize,
+        parts: &mut Vec<(&'a str, usize)>,
+    ) {
+        let raw = &input[start..end];
+        let lead = raw.len() - raw.trim_start().len();
+        parts.push((raw.trim(), base + start + lead));
+    }
+
+    let bytes = input.as_bytes();
+    let mut parts = Vec::new();
+    let mut start = 0usize;
+    let mut paren_depth = 0i32;
+    let mut bracket_depth = 0i32;
+    let mut quote: Option<u8> = None;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        if let Some(q) = quote {
+            if b == q {
+                quote = None;
+            }
+            continue;
+        }
+        match b {
+            b'"' | b'\'' => quote = Some(b),
+            b'(' => paren_depth += 1,
+            b')' => paren_depth -= 1,
+            b'[' => bracket_depth += 1,
+            b']' => bracket_depth -= 1,
+            b',' if paren_depth == 0 && bracket_depth == 0 => {
+                push_part(input, base, start, i, &mut parts);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    push_part(input, base, start, input.len(), &mut parts);
+    parts
+}
+
+/// Consumes the leading condition chunk of `input`, stopping at the
+/// first top-level `)`, `||` or `&&` (or the end of the string).
+/// Doesn't track quoting, matching [`split_logical_with_offsets`]'s
+/// existing limitation - a literal containing `)`, `||` or `&&` inside
+/// quotes isn't supported by this grammar.
+fn take_condition_chunk(input: &str) -> Option<(&str, &str)> {
+    let bytes = input.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b')' => break,
+            b'|' if bytes.get(i + 1) == Some(&b'|') => break,
+            b'&' if bytes.get(i + 1) == Some(&b'&') => break,
+            _ => i += 1,
+        }
+    }
+    if i == 0 {
+        None
+    } else {
+        Some((&input[..i], &input[i..]))
+    }
+}
+
+/// Parses a simple condition like "http.status >= 500". `base` is
+/// `input`'s absolute byte offset within the original source, used to
+/// populate [`Condition::span`].
+fn parse_condition(input: &str, base: usize) -> Result<Condition> {
+    let trim_start = input.len() - input.trim_start().len();
+    let trimmed = input.trim();
+    let base = base + trim_start;
+
+    let Some((op_idx, op_str)) = find_operator(trimmed) else {
+        return Err(Error::InvalidMatch {
+            expr: trimmed.to_string(),
+            span: base..base + trimmed.len().max(1),
+            reason: "no valid operator found".to_string(),
+        });
+    };
+
+    let Some(operator) = Operator::from_str(op_str) else {
+        return Err(Error::InvalidMatch {
+            expr: trimmed.to_string(),
+            span: base + op_idx..base + op_idx + op_str.len(),
+            reason: format!("unknown operator '{op_str}'"),
+        });
+    };
+
+    let field = trimmed[..op_idx].trim().to_string();
+    let span = base..base + trimmed.len();
+
+    // Handle exists operator (no value needed)
+    if operator == Operator::Exists {
+        return Ok(Condition {
+            field,
+            operator,
+            value: Value::Bool(true),
+            span,
+        });
+    }
+
+    let value_str = trimmed[op_idx + op_str.len()..].trim();
+    let value = parse_value(value_str);
+
+    if operator == Operator::Matches {
+        if let Value::String(pattern) = &value {
+            if let Err(e) = regex::Regex::new(pattern) {
+                return Err(Error::InvalidMatch {
+                    expr: trimmed.to_string(),
+                    span: base + op_idx + op_str.len()..base + trimmed.len(),
+                    reason: format!("invalid regex '{pattern}': {e}"),
+                });
+            }
+        }
+    }
+
+    Ok(Condition {
+        field,
+        operator,
+        value,
+        span,
+    })
+}
+
+/// Word-like operators recognized by [`find_word_operator`], checked at
+/// each candidate position. Distinct leading characters mean position
+/// alone (leftmost wins) resolves every ambiguity here, including
+/// `not-in` vs. the `in` that appears inside it - `not-in`'s own start
+/// is always to the left of its embedded `in`.
+const WORD_OPERATORS: [&str; 6] = ["contains", "starts-with", "exists", "in", "not-in", "matches"];
+
+/// Scans `input` left to right for the first condition operator, either
+/// a maximal run of `=`/`!`/`<`/`>` characters (so a malformed operator
+/// like `>==` is caught as a single unrecognized token rather than
+/// silently truncated to a valid `>=` with `= ...` folded into the
+/// value) or one of [`WORD_OPERATORS`]. Returns the operator's byte
+/// offset within `input` and the operator text itself.
+fn find_operator(input: &str) -> Option<(usize, &str)> {
+    let bytes = input.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if matches!(bytes[i], b'=' | b'!' | b'<' | b'>') {
+            let start = i;
+            while i < bytes.len() && matches!(bytes[i], b'=' | b'!' | b'<' | b'>') {
+                i += 1;
+            }
+            return Some((start, &input[start..i]));
+        }
+        i += 1;
+    }
+
+    find_word_operator(input)
+}
+
+/// True if `c` could be part of an identifier (field name), used so
+/// [`find_word_operator`] doesn't match a keyword like `in` in the
+/// middle of an unrelated word such as `domain`.
+const fn is_ident_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+/// Finds the leftmost occurrence of one of [`WORD_OPERATORS`] in
+/// `input` that's a whole word - not a substring of a longer
+/// identifier - by requiring a non-identifier character (or start/end
+/// of input) on both sides of the match.
+fn find_word_operator(input: &str) -> Option<(usize, &str)> {
+    let bytes = input.as_bytes();
+    for i in 0..bytes.len() {
+        if !input.is_char_boundary(i) {
+            continue;
+        }
+        for word in WORD_OPERATORS {
+            if input[i..].starts_with(word) {
+                let before_ok = i == 0 || !is_ident_char(bytes[i - 1]);
+                let after = i + word.len();
+                let after_ok = after == bytes.len() || !is_ident_char(bytes[after]);
+                if before_ok && after_ok {
+                    return Some((i, word));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Parses a value string.
+fn parse_value(s: &str) -> Value {
+    let s = s.trim();
+
+    // Bracketed list literal (e.g., "[500, 502, 503]"), for `in`/`not-in`.
+    if let Some(inner) = s.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+        return Value::List(split_top_level_commas(inner).map(parse_value).collect());
+    }
+
+    // Boolean
+    if s.eq_ignore_ascii_case("true") {
+        return Value::Bool(true);
+    }
+    if s.eq_ignore_ascii_case("false") {
+        return Value::Bool(false);
+    }
+
+    // Duration (e.g., "5s", "100ms")
+    if let Some(dur) = parse_duration_value(s) {
+        return Value::Duration(dur);
+    }
+
+    // Quoted string
+    if (s.starts_with('"') && s.ends_with('"')) || (s.starts_with('\'') && s.ends_with('\'')) {
+        let inner = &s[1..s.len() - 1];
+        return Value::String(inner.to_string());
+    }
+
+    // Integer
+    if let Ok(n) = s.parse::<i64>() {
+        return Value::Int(n);
+    }
+
+    // Float
+    if let Ok(f) = s.parse::<f64>() {
+        return Value::Float(f);
+    }
+
+    // Unquoted string
+    Value::String(s.to_string())
+}
+
+/// Splits `input` on top-level commas for a bracketed list literal like
+/// `"a,b", "c"`, skipping over commas inside a quoted item so a literal
+/// containing a comma isn't split in the middle, and trims whitespace
+/// from each part. Empty input yields no parts, so `[]` parses as an
+/// empty list.
+fn split_top_level_commas(input: &str) -> impl Iterator<Item = &str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_quote: Option<char> = None;
+    for (i, c) in input.char_indices() {
+        match (in_quote, c) {
+            (Some(q), c) if c == q => in_quote = None,
+            (None, '"' | '\'') => in_quote = Some(c),
+            (None, ',') => {
+                parts.push(input[start..i].trim());
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    let last = input[start..].trim();
+    if !last.is_empty() || !parts.is_empty() {
+        parts.push(last);
+    }
+    parts.into_iter().filter(|s| !s.is_empty())
+}
+
+/// Parses a duration string like "5s", "100ms" to milliseconds.
+fn parse_duration_value(s: &str) -> Option<u64> {
+    if let Some(ms_str) = s.strip_suffix("ms") {
+        return ms_str.trim().parse().ok();
+    }
+
+    if let Some(s_str) = s.strip_suffix('s') {
+        let secs: f64 = s_str.trim().parse().ok()?;
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        return Some((secs * 1000.0) as u64);
+    }
+
+    if let Some(m_str) = s.strip_suffix('m') {
+        let mins: f64 = m_str.trim().parse().ok()?;
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        return Some((mins * 60.0 * 1000.0) as u64);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_true() {
+        assert_eq!(MatchExpr::parse("true").unwrap(), MatchExpr::True);
+        assert_eq!(MatchExpr::parse("TRUE").unwrap(), MatchExpr::True);
+    }
+
+    #[test]
+    fn parse_simple_comparison() {
+        let expr = MatchExpr::parse("http.status >= 500").unwrap();
+        if let MatchExpr::Condition(cond) = expr {
+            assert_eq!(cond.field, "http.status");
+            assert_eq!(cond.operator, Operator::Ge);
+            assert_eq!(cond.value, Value::Int(500));
+        } else {
+            panic!("expected Condition");
+        }
+    }
+
+    #[test]
+    fn parse_string_comparison() {
+        let expr = MatchExpr::parse("service.name == \"checkout\"").unwrap();
+        if let MatchExpr::Condition(cond) = expr {
+            assert_eq!(cond.field, "service.name");
+            assert_eq!(cond.operator, Operator::Eq);
+            assert_eq!(cond.value, Value::String("checkout".to_string()));
+        } else {
+            panic!("expected Condition");
+        }
+    }
+
+    #[test]
     fn parse_boolean_comparison() {
         let expr = MatchExpr::parse("error == true").unwrap();
         if let MatchExpr::Condition(cond) = expr {
@@ -424,6 +1712,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_respects_parenthesized_grouping() {
+        let expr = MatchExpr::parse("(status >= 500 && error == true) || duration > 1s").unwrap();
+        let MatchExpr::Or(parts) = expr else {
+            panic!("expected Or");
+        };
+        assert_eq!(parts.len(), 2);
+        assert!(matches!(parts[0], MatchExpr::And(_)));
+        assert!(matches!(parts[1], MatchExpr::Condition(_)));
+    }
+
+    #[test]
+    fn parse_and_binds_tighter_than_or_without_parens() {
+        // a || b && c should parse as a || (b && c), not (a || b) && c.
+        let expr = MatchExpr::parse("status == 1 || status == 2 && status == 3").unwrap();
+        let MatchExpr::Or(parts) = expr else {
+            panic!("expected Or");
+        };
+        assert_eq!(parts.len(), 2);
+        assert!(matches!(parts[1], MatchExpr::And(_)));
+    }
+
+    #[test]
+    fn parse_rejects_unclosed_paren() {
+        let err = MatchExpr::parse("(status == 1").unwrap_err();
+        assert!(matches!(err, Error::InvalidMatch { .. }));
+    }
+
+    #[test]
+    fn parse_rejects_trailing_garbage() {
+        let err = MatchExpr::parse("status == 1)").unwrap_err();
+        assert!(matches!(err, Error::InvalidMatch { .. }));
+    }
+
+    #[test]
+    fn parse_not_wraps_a_negated_condition() {
+        let expr = MatchExpr::parse("!(service.name == \"checkout\")").unwrap();
+        let MatchExpr::Not(inner) = expr else {
+            panic!("expected Not");
+        };
+        assert!(matches!(*inner, MatchExpr::Condition(_)));
+    }
+
+    #[test]
+    fn to_refinery_conditions_inverts_a_negated_condition() {
+        let expr = MatchExpr::parse("!(http.status == 500)").unwrap();
+        let conditions = expr.to_refinery_conditions().unwrap();
+        assert_eq!(conditions.len(), 1);
+        assert_eq!(conditions[0].operator, "!=");
+    }
+
+    #[test]
+    fn to_refinery_conditions_pushes_not_through_and_via_de_morgan() {
+        // !(a && b) == !a || !b, which can't be expressed as a single
+        // rule's flat AND-list of conditions.
+        let expr = MatchExpr::parse("!(http.status == 500 && error == true)").unwrap();
+        let err = expr.to_refinery_conditions().unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
+    #[test]
+    fn to_dnf_pushes_not_through_and_via_de_morgan() {
+        // !(a && b) == !a || !b - two single-condition clauses.
+        let expr = MatchExpr::parse("!(http.status == 500 && error == true)").unwrap();
+        let clauses = expr
+            .to_dnf("!(http.status == 500 && error == true)", DEFAULT_MAX_DNF_CLAUSES)
+            .unwrap();
+        assert_eq!(clauses.len(), 2);
+        for clause in &clauses {
+            assert_eq!(clause.len(), 1);
+        }
+    }
+
+    #[test]
+    fn negating_an_operator_with_no_inverse_errors() {
+        let expr = MatchExpr::parse("!(service.name contains \"checkout\")").unwrap();
+        let err = expr.to_refinery_conditions().unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
+    #[test]
+    fn negating_true_errors() {
+        let expr = MatchExpr::parse("!true").unwrap();
+        let err = expr.to_refinery_conditions().unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
     #[test]
     fn to_refinery_conditions() {
         let expr = MatchExpr::parse("http.status >= 500").unwrap();
@@ -447,10 +1822,564 @@ mod tests {
         assert_eq!(conditions.len(), 2);
     }
 
+    #[test]
+    fn type_check_rejects_string_compared_to_number() {
+        let err = type_check("service.name == 5").unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn type_check_rejects_duration_without_unit() {
+        let err = type_check("duration > 500").unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn type_check_rejects_relational_operator_on_boolean() {
+        let err = type_check("error > true").unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn type_check_accepts_well_typed_expression() {
+        assert!(type_check("http.status >= 500 && duration > 5s").is_ok());
+    }
+
+    #[test]
+    fn type_check_reports_span_of_offending_condition() {
+        let err = type_check("http.status >= 500 && duration > 500").unwrap_err();
+        if let Error::TypeMismatch { span, .. } = err {
+            assert_eq!(span, (22, 36));
+        } else {
+            panic!("expected TypeMismatch");
+        }
+    }
+
     #[test]
     fn duration_field_renamed() {
         let expr = MatchExpr::parse("duration > 1000ms").unwrap();
         let conditions = expr.to_refinery_conditions().unwrap();
         assert_eq!(conditions[0].field, "duration_ms");
     }
+
+    #[test]
+    fn to_dnf_of_true_is_a_single_empty_clause() {
+        let clauses = MatchExpr::True.to_dnf("true", 10).unwrap();
+        assert_eq!(clauses, vec![Vec::new()]);
+    }
+
+    #[test]
+    fn to_dnf_of_or_concatenates_branch_clauses() {
+        let expr = MatchExpr::parse("status == 200 || status == 429").unwrap();
+        let clauses = expr.to_dnf("status == 200 || status == 429", 10).unwrap();
+        assert_eq!(clauses.len(), 2);
+        assert_eq!(clauses[0].len(), 1);
+        assert_eq!(clauses[1].len(), 1);
+    }
+
+    #[test]
+    fn to_dnf_distributes_and_over_nested_or_branches() {
+        // (a || b) && (c || d), built directly as an AST - should
+        // distribute into the 4 AND-clauses [a,c] [a,d] [b,c] [b,d].
+        let cond = |field: &str, n: i64| {
+            MatchExpr::Condition(Condition {
+                field: field.to_string(),
+                operator: Operator::Eq,
+                value: Value::Int(n),
+                span: 0..0,
+            })
+        };
+        let expr = MatchExpr::And(vec![
+            MatchExpr::Or(vec![cond("a", 1), cond("b", 2)]),
+            MatchExpr::Or(vec![cond("c", 3), cond("d", 4)]),
+        ]);
+
+        let clauses = expr.to_dnf("(a||b)&&(c||d)", 10).unwrap();
+
+        assert_eq!(clauses.len(), 4);
+        for clause in &clauses {
+            assert_eq!(clause.len(), 2, "each clause ANDs one branch from each side");
+        }
+    }
+
+    #[test]
+    fn to_dnf_rejects_expansion_past_the_clause_limit() {
+        let branches: Vec<MatchExpr> = (0..10)
+            .map(|i| MatchExpr::Condition(Condition {
+                field: "status".to_string(),
+                operator: Operator::Eq,
+                value: Value::Int(i),
+                span: 0..0,
+            }))
+            .collect();
+        let expr = MatchExpr::And(vec![
+            MatchExpr::Or(branches.clone()),
+            MatchExpr::Or(branches),
+        ]);
+
+        // 10 * 10 = 100 clauses, past a limit of 50.
+        let err = expr.to_dnf("big", 50).unwrap_err();
+        assert!(matches!(err, Error::ClauseLimitExceeded { limit: 50, .. }));
+    }
+
+    #[test]
+    fn parse_rejects_deeply_nested_negation_instead_of_overflowing_the_stack() {
+        let input = format!("{}true", "!".repeat(MAX_PARSE_DEPTH + 1));
+        let err = MatchExpr::parse(&input).unwrap_err();
+        let Error::InvalidMatch { reason, .. } = err else {
+            panic!("expected InvalidMatch");
+        };
+        assert!(reason.contains("nesting exceeds"));
+    }
+
+    #[test]
+    fn parse_rejects_deeply_nested_parens_instead_of_overflowing_the_stack() {
+        let input = format!(
+            "{}true{}",
+            "(".repeat(MAX_PARSE_DEPTH + 1),
+            ")".repeat(MAX_PARSE_DEPTH + 1)
+        );
+        let err = MatchExpr::parse(&input).unwrap_err();
+        let Error::InvalidMatch { reason, .. } = err else {
+            panic!("expected InvalidMatch");
+        };
+        assert!(reason.contains("nesting exceeds"));
+    }
+
+    #[test]
+    fn parse_accepts_nesting_up_to_the_depth_limit() {
+        let input = format!("{}true", "!".repeat(MAX_PARSE_DEPTH));
+        assert!(MatchExpr::parse(&input).is_ok());
+    }
+
+    #[test]
+    fn parse_rejects_a_malformed_operator_instead_of_truncating_it() {
+        // A naive "find the longest known operator" parser would match
+        // the leading ">=" of ">==" and silently fold the leftover "="
+        // into the value. This should instead reject ">==" as a whole.
+        let err = MatchExpr::parse("http.status >== 500").unwrap_err();
+        let Error::InvalidMatch { span, reason, .. } = err else {
+            panic!("expected InvalidMatch");
+        };
+        assert_eq!(span, 12..15);
+        assert!(reason.contains("unknown operator"));
+    }
+
+    #[test]
+    fn parse_error_span_renders_a_caret_under_the_bad_operator() {
+        let err = MatchExpr::parse("http.status >== 500").unwrap_err();
+        let rendered = err.render_caret().unwrap();
+        assert!(rendered.contains("http.status >== 500"));
+        assert!(rendered.contains("^^^"));
+    }
+
+    #[test]
+    fn condition_span_covers_field_operator_and_value() {
+        let expr = MatchExpr::parse("  http.status >= 500").unwrap();
+        let MatchExpr::Condition(cond) = expr else {
+            panic!("expected Condition");
+        };
+        assert_eq!(cond.span, 2..20);
+    }
+
+    #[test]
+    fn to_refinery_rule_sets_matches_to_dnf_with_the_default_limit() {
+        let expr = MatchExpr::parse("status == 200 || status == 429").unwrap();
+        let rule_sets = expr.to_refinery_rule_sets().unwrap();
+        let dnf = expr
+            .to_dnf("status == 200 || status == 429", DEFAULT_MAX_DNF_CLAUSES)
+            .unwrap();
+        assert_eq!(format!("{rule_sets:?}"), format!("{dnf:?}"));
+    }
+
+    #[test]
+    fn parse_with_schema_coerces_a_quoted_number_to_int() {
+        let schema = FieldSchema::new().with_field("http.status", FieldKind::Int);
+        let expr = MatchExpr::parse_with_schema("http.status == \"500\"", &schema).unwrap();
+        let MatchExpr::Condition(cond) = expr else {
+            panic!("expected Condition");
+        };
+        assert_eq!(cond.value, Value::Int(500));
+    }
+
+    #[test]
+    fn parse_with_schema_rejects_contains_on_a_non_string_field() {
+        let schema = FieldSchema::new().with_field("http.status", FieldKind::Int);
+        let err = MatchExpr::parse_with_schema("http.status contains \"50\"", &schema).unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn parse_with_schema_rejects_a_value_that_cant_be_coerced() {
+        let schema = FieldSchema::new().with_field("http.status", FieldKind::Int);
+        let err = MatchExpr::parse_with_schema("http.status == \"not-a-number\"", &schema).unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn parse_with_schema_leaves_unknown_fields_to_best_effort_inference() {
+        let schema = FieldSchema::new().with_field("http.status", FieldKind::Int);
+        let expr = MatchExpr::parse_with_schema("service.name == \"checkout\"", &schema).unwrap();
+        let MatchExpr::Condition(cond) = expr else {
+            panic!("expected Condition");
+        };
+        assert_eq!(cond.value, Value::String("checkout".to_string()));
+    }
+
+    #[test]
+    fn parse_with_schema_allows_ordering_operators_on_duration_fields() {
+        let schema = FieldSchema::new().with_field("duration", FieldKind::Duration);
+        let expr = MatchExpr::parse_with_schema("duration > 5s", &schema).unwrap();
+        let MatchExpr::Condition(cond) = expr else {
+            panic!("expected Condition");
+        };
+        assert_eq!(cond.value, Value::Duration(5000));
+    }
+
+    #[test]
+    fn parse_value_reads_a_bracketed_list() {
+        let value = parse_value("[500, 502, 503]");
+        assert_eq!(
+            value,
+            Value::List(vec![Value::Int(500), Value::Int(502), Value::Int(503)])
+        );
+    }
+
+    #[test]
+    fn parse_value_list_respects_quoted_commas() {
+        let value = parse_value("[\"a, b\", \"c\"]");
+        assert_eq!(
+            value,
+            Value::List(vec![
+                Value::String("a, b".to_string()),
+                Value::String("c".to_string())
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_value_empty_list() {
+        assert_eq!(parse_value("[]"), Value::List(Vec::new()));
+    }
+
+    #[test]
+    fn parse_recognizes_in_and_not_in() {
+        let expr = MatchExpr::parse("http.status in [500, 502, 503]").unwrap();
+        let MatchExpr::Condition(cond) = expr else {
+            panic!("expected Condition");
+        };
+        assert_eq!(cond.operator, Operator::In);
+        assert_eq!(cond.field, "http.status");
+
+        let expr = MatchExpr::parse("http.status not-in [500, 502, 503]").unwrap();
+        let MatchExpr::Condition(cond) = expr else {
+            panic!("expected Condition");
+        };
+        assert_eq!(cond.operator, Operator::NotIn);
+    }
+
+    #[test]
+    fn find_word_operator_does_not_match_in_inside_domain() {
+        // "domain" contains "in" - the word-boundary check must skip it
+        // and find the real operator instead.
+        let expr = MatchExpr::parse("domain == \"x\"").unwrap();
+        let MatchExpr::Condition(cond) = expr else {
+            panic!("expected Condition");
+        };
+        assert_eq!(cond.operator, Operator::Eq);
+        assert_eq!(cond.field, "domain");
+        assert_eq!(find_word_operator("domain exists"), Some((7, "exists")));
+    }
+
+    #[test]
+    fn in_expands_to_or_of_eq_in_dnf() {
+        let expr = MatchExpr::parse("http.status in [500, 502, 503]").unwrap();
+        let clauses = expr
+            .to_dnf("http.status in [500, 502, 503]", DEFAULT_MAX_DNF_CLAUSES)
+            .unwrap();
+        assert_eq!(clauses.len(), 3);
+        for clause in &clauses {
+            assert_eq!(clause.len(), 1);
+            assert_eq!(clause[0].operator, "=");
+        }
+    }
+
+    #[test]
+    fn not_in_expands_to_and_of_ne_in_refinery_conditions() {
+        let expr = MatchExpr::parse("http.status not-in [500, 502]").unwrap();
+        let conditions = expr.to_refinery_conditions().unwrap();
+        assert_eq!(conditions.len(), 2);
+        for condition in &conditions {
+            assert_eq!(condition.operator, "!=");
+        }
+    }
+
+    #[test]
+    fn in_is_rejected_directly_by_to_refinery_conditions_like_any_other_or() {
+        // `in` expands to an Or of Eq conditions, and Or isn't
+        // representable as a single Refinery rule - same restriction
+        // as writing the equivalent `||` chain by hand.
+        let expr = MatchExpr::parse("http.status in [500, 502]").unwrap();
+        let err = expr.to_refinery_conditions().unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
+    #[test]
+    fn matches_parses_and_maps_to_refinery_matches_operator() {
+        let expr = MatchExpr::parse("service.name matches \"^checkout-.*\"").unwrap();
+        let conditions = expr.to_refinery_conditions().unwrap();
+        assert_eq!(conditions[0].operator, "matches");
+    }
+
+    #[test]
+    fn matches_rejects_an_invalid_regex_at_parse_time() {
+        let err = MatchExpr::parse("service.name matches \"(unclosed\"").unwrap_err();
+        let Error::InvalidMatch { reason, .. } = err else {
+            panic!("expected InvalidMatch");
+        };
+        assert!(reason.contains("invalid regex"));
+    }
+
+    #[test]
+    fn field_schema_allows_matches_only_on_string_fields() {
+        let schema = FieldSchema::new().with_field("http.status", FieldKind::Int);
+        let err =
+            MatchExpr::parse_with_schema("http.status matches \"5..\"", &schema).unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { .. }));
+    }
+
+    /// Test-only [`AttributeSource`] backed by a plain map, so `eval` tests
+    /// can stand up attributes without a real trace type.
+    struct Attrs(HashMap<&'static str, Value>);
+
+    impl Attrs {
+        fn new(pairs: Vec<(&'static str, Value)>) -> Self {
+            Self(pairs.into_iter().collect())
+        }
+    }
+
+    impl AttributeSource for Attrs {
+        fn attribute(&self, field: &str) -> Option<Value> {
+            self.0.get(field).cloned()
+        }
+    }
+
+    #[test]
+    fn eval_true_always_matches() {
+        let attrs = Attrs::new(vec![]);
+        assert!(MatchExpr::True.eval(&attrs));
+    }
+
+    #[test]
+    fn eval_comparison_matches_a_present_attribute() {
+        let expr = MatchExpr::parse("http.status >= 500").unwrap();
+        let attrs = Attrs::new(vec![("http.status", Value::Int(503))]);
+        assert!(expr.eval(&attrs));
+
+        let attrs = Attrs::new(vec![("http.status", Value::Int(200))]);
+        assert!(!expr.eval(&attrs));
+    }
+
+    #[test]
+    fn eval_missing_attribute_never_matches_except_exists() {
+        let expr = MatchExpr::parse("http.status >= 500").unwrap();
+        assert!(!expr.eval(&Attrs::new(vec![])));
+
+        let expr = MatchExpr::parse("http.status exists").unwrap();
+        assert!(!expr.eval(&Attrs::new(vec![])));
+        assert!(expr.eval(&Attrs::new(vec![("http.status", Value::Int(200))])));
+    }
+
+    #[test]
+    fn eval_and_or_not_compose_as_expected() {
+        let attrs = Attrs::new(vec![
+            ("http.status", Value::Int(503)),
+            ("error", Value::Bool(true)),
+        ]);
+
+        let and_expr = MatchExpr::parse("http.status >= 500 && error == true").unwrap();
+        assert!(and_expr.eval(&attrs));
+
+        let or_expr = MatchExpr::parse("http.status == 200 || error == true").unwrap();
+        assert!(or_expr.eval(&attrs));
+
+        let not_expr = MatchExpr::parse("!(http.status == 200)").unwrap();
+        assert!(not_expr.eval(&attrs));
+    }
+
+    #[test]
+    fn eval_contains_and_in_use_real_string_and_set_semantics() {
+        let attrs = Attrs::new(vec![("service.name", Value::String("checkout-api".to_string()))]);
+
+        let contains = MatchExpr::parse("service.name contains \"checkout\"").unwrap();
+        assert!(contains.eval(&attrs));
+
+        let in_expr = MatchExpr::parse("service.name in [\"checkout-api\", \"payments\"]").unwrap();
+        assert!(in_expr.eval(&attrs));
+
+        let not_in = MatchExpr::parse("service.name not-in [\"payments\"]").unwrap();
+        assert!(not_in.eval(&attrs));
+    }
+
+    #[test]
+    fn parse_threshold_reads_k_and_sub_expressions() {
+        let expr = MatchExpr::parse("thresh(2, http.status >= 500, duration > 1s, error == true)")
+            .unwrap();
+        let MatchExpr::Threshold { k, subs } = expr else {
+            panic!("expected Threshold");
+        };
+        assert_eq!(k, 2);
+        assert_eq!(subs.len(), 3);
+    }
+
+    #[test]
+    fn parse_threshold_sub_expressions_can_be_compound() {
+        let expr =
+            MatchExpr::parse("thresh(1, http.status >= 500 && error == true, duration > 5s)")
+                .unwrap();
+        let MatchExpr::Threshold { k, subs } = expr else {
+            panic!("expected Threshold");
+        };
+        assert_eq!(k, 1);
+        assert!(matches!(subs[0], MatchExpr::And(_)));
+        assert!(matches!(subs[1], MatchExpr::Condition(_)));
+    }
+
+    #[test]
+    fn parse_threshold_composes_with_and_or() {
+        let expr =
+            MatchExpr::parse("thresh(2, status == 1, status == 2, status == 3) && error == true")
+                .unwrap();
+        let MatchExpr::And(parts) = expr else {
+            panic!("expected And");
+        };
+        assert!(matches!(parts[0], MatchExpr::Threshold { .. }));
+    }
+
+    #[test]
+    fn parse_threshold_rejects_a_count_above_the_sub_expression_count() {
+        let err = MatchExpr::parse("thresh(4, status == 1, status == 2)").unwrap_err();
+        assert!(matches!(err, Error::InvalidMatch { .. }));
+    }
+
+    #[test]
+    fn parse_threshold_rejects_unclosed_paren() {
+        let err = MatchExpr::parse("thresh(2, status == 1, status == 2").unwrap_err();
+        assert!(matches!(err, Error::InvalidMatch { .. }));
+    }
+
+    #[test]
+    fn parse_threshold_rejects_a_non_numeric_count() {
+        let err = MatchExpr::parse("thresh(two, status == 1, status == 2)").unwrap_err();
+        assert!(matches!(err, Error::InvalidMatch { .. }));
+    }
+
+    #[test]
+    fn eval_threshold_matches_when_at_least_k_subs_match() {
+        let expr = MatchExpr::parse("thresh(2, http.status >= 500, duration > 1s, error == true)")
+            .unwrap();
+
+        let attrs = Attrs::new(vec![
+            ("http.status", Value::Int(503)),
+            ("duration", Value::Duration(200)),
+            ("error", Value::Bool(true)),
+        ]);
+        assert!(expr.eval(&attrs), "2 of 3 (status, error) should match");
+
+        let attrs = Attrs::new(vec![
+            ("http.status", Value::Int(200)),
+            ("duration", Value::Duration(200)),
+            ("error", Value::Bool(false)),
+        ]);
+        assert!(!expr.eval(&attrs), "0 of 3 should not match");
+    }
+
+    #[test]
+    fn negate_threshold_flips_k_by_de_morgan_duality() {
+        let expr = MatchExpr::parse("thresh(2, status == 1, status == 2, status == 3)").unwrap();
+        let MatchExpr::Threshold { k, subs } = expr.negate().unwrap() else {
+            panic!("expected Threshold");
+        };
+        // Negating "at least 2 of 3" is "at least 2 of 3 negated".
+        assert_eq!(k, 2);
+        assert_eq!(subs.len(), 3);
+    }
+
+    #[test]
+    fn negating_thresh_zero_errors_like_negating_true() {
+        let expr = MatchExpr::Threshold {
+            k: 0,
+            subs: vec![MatchExpr::parse("status == 1").unwrap()],
+        };
+        let err = expr.negate().unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
+    #[test]
+    fn threshold_is_rejected_directly_by_to_refinery_conditions_like_or() {
+        let expr = MatchExpr::parse("thresh(2, status == 1, status == 2, status == 3)").unwrap();
+        let err = expr.to_refinery_conditions().unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)));
+    }
+
+    #[test]
+    fn threshold_expands_to_one_dnf_clause_per_qualifying_combination() {
+        let expr = MatchExpr::parse("thresh(2, status == 1, status == 2, status == 3)").unwrap();
+        let clauses = expr
+            .to_dnf("thresh(2, status == 1, status == 2, status == 3)", 10)
+            .unwrap();
+        // C(3, 2) == 3 combinations, each AND-ing 2 of the 3 conditions.
+        assert_eq!(clauses.len(), 3);
+        for clause in &clauses {
+            assert_eq!(clause.len(), 2);
+        }
+    }
+
+    #[test]
+    fn threshold_of_zero_expands_like_true() {
+        let expr = MatchExpr::Threshold {
+            k: 0,
+            subs: vec![MatchExpr::parse("status == 1").unwrap()],
+        };
+        assert_eq!(
+            expr.to_dnf("thresh(0, status == 1)", 10).unwrap(),
+            vec![Vec::new()]
+        );
+    }
+
+    #[test]
+    fn threshold_rejects_expansion_past_the_clause_limit() {
+        let subs: Vec<MatchExpr> = (0..10)
+            .map(|i| MatchExpr::parse(&format!("status == {i}")).unwrap())
+            .collect();
+        let expr = MatchExpr::Threshold { k: 5, subs };
+
+        // C(10, 5) == 252 combinations, past a limit of 50.
+        let err = expr.to_dnf("big thresh", 50).unwrap_err();
+        assert!(matches!(err, Error::ClauseLimitExceeded { limit: 50, .. }));
+    }
+
+    #[test]
+    fn coerce_to_schema_recurses_into_threshold_subs() {
+        let schema = FieldSchema::new().with_field("http.status", FieldKind::Int);
+        let expr = MatchExpr::parse_with_schema(
+            "thresh(1, http.status == \"500\", error == true)",
+            &schema,
+        )
+        .unwrap();
+        let MatchExpr::Threshold { subs, .. } = expr else {
+            panic!("expected Threshold");
+        };
+        let MatchExpr::Condition(cond) = &subs[0] else {
+            panic!("expected Condition");
+        };
+        assert_eq!(cond.value, Value::Int(500));
+    }
+
+    #[test]
+    fn type_check_walks_into_threshold_sub_expressions() {
+        let err = type_check("thresh(1, duration > 500, error == true)").unwrap_err();
+        assert!(matches!(err, Error::TypeMismatch { .. }));
+        assert!(type_check("thresh(1, duration > 5s, error == true)").is_ok());
+    }
 }
@@ -1,7 +1,11 @@
 //! Refinery output format types.
 
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::{Error, Result};
+
 /// Refinery configuration root.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RefineryConfig {
@@ -66,6 +70,218 @@ impl RefineryConfig {
     pub fn add_rule(&mut self, rule: RefineryRule) {
         self.rules_based_sampler.rules.push(rule);
     }
+
+    /// Imports a hand-edited Refinery `RulesBasedSampler` YAML config.
+    ///
+    /// Real-world configs often carry a condition's value as a quoted
+    /// string alongside a separate `Datatype` field rather than relying
+    /// on YAML's own scalar typing, so each condition's value is coerced
+    /// according to its declared datatype (falling back to YAML's own
+    /// scalar type when no `Datatype` is present), and its operator is
+    /// checked for compatibility with the coerced type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the YAML doesn't match the expected shape, a
+    /// condition's value can't be coerced to its declared datatype, or an
+    /// operator is incompatible with the coerced value's type.
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        let raw: RawRefineryConfig = serde_yaml::from_str(yaml)?;
+
+        let rules = raw
+            .rules_based_sampler
+            .rules
+            .into_iter()
+            .map(|rule| {
+                let conditions = rule
+                    .conditions
+                    .into_iter()
+                    .map(RawRefineryCondition::into_condition)
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(RefineryRule {
+                    name: rule.name,
+                    sample_rate: rule.sample_rate,
+                    conditions,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            rules_based_sampler: RulesBasedSampler { rules },
+        })
+    }
+}
+
+/// Declared datatype for a condition's value, used when importing a
+/// hand-edited Refinery config. Mirrors
+/// [`nectar_corpus::conversion::Conversion`]'s "parse the type name, then
+/// coerce" shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueType {
+    /// Coerce into [`ConditionValue::Number`].
+    Number,
+    /// Coerce into [`ConditionValue::Number`], rounding the parsed float.
+    /// Refinery has no native float type; `Condition::to_refinery` in
+    /// `nectar_compiler::match_expr` rounds for the same reason.
+    Float,
+    /// Coerce into [`ConditionValue::Bool`].
+    Bool,
+    /// Coerce into [`ConditionValue::String`].
+    String,
+}
+
+impl FromStr for ValueType {
+    type Err = String;
+
+    /// Parses a datatype name, e.g. `"int"`, `"float"`, or `"bool"`.
+    fn from_str(s: &str) -> std::result::Result<Self, String> {
+        match s {
+            "int" | "integer" => Ok(Self::Number),
+            "float" => Ok(Self::Float),
+            "bool" | "boolean" => Ok(Self::Bool),
+            "string" => Ok(Self::String),
+            other => Err(format!("unknown datatype '{other}'")),
+        }
+    }
+}
+
+impl ValueType {
+    /// Coerces a raw scalar string into the [`ConditionValue`] variant
+    /// this type implies.
+    fn coerce(self, raw: &str) -> std::result::Result<ConditionValue, String> {
+        match self {
+            Self::Number => raw
+                .parse::<i64>()
+                .map(ConditionValue::Number)
+                .map_err(|e| format!("'{raw}' is not an integer: {e}")),
+            Self::Float => raw
+                .parse::<f64>()
+                .map(|f| {
+                    #[allow(clippy::cast_possible_truncation)]
+                    ConditionValue::Number(f.round() as i64)
+                })
+                .map_err(|e| format!("'{raw}' is not a float: {e}")),
+            Self::Bool => match raw {
+                "true" => Ok(ConditionValue::Bool(true)),
+                "false" => Ok(ConditionValue::Bool(false)),
+                other => Err(format!("'{other}' is not a boolean")),
+            },
+            Self::String => Ok(ConditionValue::String(raw.to_string())),
+        }
+    }
+}
+
+/// Checks that `operator` is meaningful for a value of `value`'s type,
+/// e.g. rejecting `service.name > "frontend"` or `duration_ms contains 500`.
+/// Only the value's type is checked, not the field's.
+fn check_operator_compatibility(
+    operator: &str,
+    value: &ConditionValue,
+) -> std::result::Result<(), String> {
+    let numeric_only = matches!(operator, ">" | ">=" | "<" | "<=");
+    let string_only = matches!(operator, "contains" | "starts-with");
+
+    match value {
+        ConditionValue::String(_) if numeric_only => Err(format!(
+            "operator '{operator}' requires a numeric value, found a string"
+        )),
+        ConditionValue::Number(_) if string_only => Err(format!(
+            "operator '{operator}' requires a string value, found a number"
+        )),
+        ConditionValue::Bool(_) if numeric_only || string_only => Err(format!(
+            "operator '{operator}' is not valid for a boolean value"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Converts a YAML scalar into its raw string form, for coercion via a
+/// declared [`ValueType`].
+fn scalar_to_raw_string(value: &serde_yaml::Value) -> std::result::Result<String, String> {
+    match value {
+        serde_yaml::Value::String(s) => Ok(s.clone()),
+        serde_yaml::Value::Number(n) => Ok(n.to_string()),
+        serde_yaml::Value::Bool(b) => Ok(b.to_string()),
+        other => Err(format!("condition value '{other:?}' is not a scalar")),
+    }
+}
+
+/// On-disk shape of a condition when importing a config: the value is
+/// always a YAML scalar, and an optional `Datatype` field says how to
+/// interpret it.
+#[derive(Debug, Clone, Deserialize)]
+struct RawRefineryCondition {
+    field: String,
+    operator: String,
+    value: serde_yaml::Value,
+    #[serde(rename = "Datatype", default)]
+    datatype: Option<String>,
+}
+
+impl RawRefineryCondition {
+    fn into_condition(self) -> Result<RefineryCondition> {
+        let value = match &self.datatype {
+            Some(datatype) => {
+                let value_type = datatype.parse::<ValueType>().map_err(|reason| {
+                    Error::RefineryImport {
+                        field: self.field.clone(),
+                        reason,
+                    }
+                })?;
+                let raw = scalar_to_raw_string(&self.value).map_err(|reason| {
+                    Error::RefineryImport {
+                        field: self.field.clone(),
+                        reason,
+                    }
+                })?;
+                value_type
+                    .coerce(&raw)
+                    .map_err(|reason| Error::RefineryImport {
+                        field: self.field.clone(),
+                        reason,
+                    })?
+            }
+            None => {
+                serde_yaml::from_value(self.value.clone()).map_err(|e| Error::RefineryImport {
+                    field: self.field.clone(),
+                    reason: format!("could not infer a type for this value: {e}"),
+                })?
+            }
+        };
+
+        check_operator_compatibility(&self.operator, &value).map_err(|reason| {
+            Error::RefineryImport {
+                field: self.field.clone(),
+                reason,
+            }
+        })?;
+
+        Ok(RefineryCondition {
+            field: self.field,
+            operator: self.operator,
+            value,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawRefineryConfig {
+    #[serde(rename = "RulesBasedSampler")]
+    rules_based_sampler: RawRulesBasedSampler,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawRulesBasedSampler {
+    rules: Vec<RawRefineryRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawRefineryRule {
+    name: String,
+    #[serde(rename = "SampleRate")]
+    sample_rate: u32,
+    #[serde(default)]
+    conditions: Vec<RawRefineryCondition>,
 }
 
 impl Default for RefineryConfig {
@@ -151,4 +367,130 @@ mod tests {
         assert!(yaml.contains("keep-errors"));
         assert!(yaml.contains("SampleRate: 1"));
     }
+
+    #[test]
+    fn from_yaml_infers_types_without_a_datatype() {
+        let yaml = "
+RulesBasedSampler:
+  rules:
+    - name: keep-slow
+      SampleRate: 1
+      conditions:
+        - field: duration_ms
+          operator: \">=\"
+          value: 500
+        - field: error
+          operator: \"=\"
+          value: true
+";
+        let config = RefineryConfig::from_yaml(yaml).unwrap();
+        let conditions = &config.rules_based_sampler.rules[0].conditions;
+        assert!(matches!(conditions[0].value, ConditionValue::Number(500)));
+        assert!(matches!(conditions[1].value, ConditionValue::Bool(true)));
+    }
+
+    #[test]
+    fn from_yaml_coerces_declared_integer_datatype() {
+        let yaml = "
+RulesBasedSampler:
+  rules:
+    - name: keep-slow
+      SampleRate: 1
+      conditions:
+        - field: http.status_code
+          operator: \">=\"
+          value: \"500\"
+          Datatype: int
+";
+        let config = RefineryConfig::from_yaml(yaml).unwrap();
+        let condition = &config.rules_based_sampler.rules[0].conditions[0];
+        assert!(matches!(condition.value, ConditionValue::Number(500)));
+    }
+
+    #[test]
+    fn from_yaml_coerces_declared_float_datatype_by_rounding() {
+        let yaml = "
+RulesBasedSampler:
+  rules:
+    - name: keep-slow
+      SampleRate: 1
+      conditions:
+        - field: duration_ms
+          operator: \">=\"
+          value: \"499.6\"
+          Datatype: float
+";
+        let config = RefineryConfig::from_yaml(yaml).unwrap();
+        let condition = &config.rules_based_sampler.rules[0].conditions[0];
+        assert!(matches!(condition.value, ConditionValue::Number(500)));
+    }
+
+    #[test]
+    fn from_yaml_coerces_declared_bool_datatype() {
+        let yaml = "
+RulesBasedSampler:
+  rules:
+    - name: keep-errors
+      SampleRate: 1
+      conditions:
+        - field: error
+          operator: \"=\"
+          value: \"true\"
+          Datatype: bool
+";
+        let config = RefineryConfig::from_yaml(yaml).unwrap();
+        let condition = &config.rules_based_sampler.rules[0].conditions[0];
+        assert!(matches!(condition.value, ConditionValue::Bool(true)));
+    }
+
+    #[test]
+    fn from_yaml_rejects_unknown_datatype() {
+        let yaml = "
+RulesBasedSampler:
+  rules:
+    - name: keep-errors
+      SampleRate: 1
+      conditions:
+        - field: error
+          operator: \"=\"
+          value: \"true\"
+          Datatype: wat
+";
+        let err = RefineryConfig::from_yaml(yaml).unwrap_err();
+        assert!(matches!(err, Error::RefineryImport { .. }));
+    }
+
+    #[test]
+    fn from_yaml_rejects_numeric_operator_on_string_value() {
+        let yaml = "
+RulesBasedSampler:
+  rules:
+    - name: keep-frontend
+      SampleRate: 1
+      conditions:
+        - field: service.name
+          operator: \">\"
+          value: \"frontend\"
+          Datatype: string
+";
+        let err = RefineryConfig::from_yaml(yaml).unwrap_err();
+        assert!(matches!(err, Error::RefineryImport { .. }));
+    }
+
+    #[test]
+    fn from_yaml_rejects_contains_operator_on_numeric_value() {
+        let yaml = "
+RulesBasedSampler:
+  rules:
+    - name: keep-slow
+      SampleRate: 1
+      conditions:
+        - field: duration_ms
+          operator: contains
+          value: \"500\"
+          Datatype: int
+";
+        let err = RefineryConfig::from_yaml(yaml).unwrap_err();
+        assert!(matches!(err, Error::RefineryImport { .. }));
+    }
 }
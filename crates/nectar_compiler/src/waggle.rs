@@ -55,6 +55,9 @@ pub fn generate_waggle_report(policy: &Policy) -> String {
     let drop_rules: Vec<_> = policy.rules.iter()
         .filter(|r| matches!(r.action, toon_policy::Action::Drop))
         .collect();
+    let circuit_breaker_rules: Vec<_> = policy.rules.iter()
+        .filter(|r| matches!(r.action, toon_policy::Action::CircuitBreaker { .. }))
+        .collect();
 
     if !keep_rules.is_empty() {
         report.push_str("**Always kept**: ");
@@ -71,6 +74,11 @@ pub fn generate_waggle_report(policy: &Policy) -> String {
         report.push_str(&drop_rules.iter().map(|r| r.name.as_str()).collect::<Vec<_>>().join(", "));
         report.push('\n');
     }
+    if !circuit_breaker_rules.is_empty() {
+        report.push_str("**Circuit breakers**: ");
+        report.push_str(&circuit_breaker_rules.iter().map(|r| r.name.as_str()).collect::<Vec<_>>().join(", "));
+        report.push('\n');
+    }
 
     report
 }
@@ -80,6 +88,20 @@ fn format_action(action: &toon_policy::Action) -> String {
         toon_policy::Action::Keep => "Keep all".to_string(),
         toon_policy::Action::Drop => "Drop all".to_string(),
         toon_policy::Action::Sample(rate) => format!("Sample at {:.1}%", rate * 100.0),
+        toon_policy::Action::CircuitBreaker {
+            closed_rate,
+            open_rate,
+            window,
+            failure_threshold,
+            min_samples,
+        } => format!(
+            "Circuit breaker: sample at {:.1}% normally, escalating to {:.1}% once {:.0}% of the last {} traces error (min {} samples)",
+            closed_rate * 100.0,
+            open_rate * 100.0,
+            failure_threshold * 100.0,
+            window,
+            min_samples,
+        ),
     }
 }
 
@@ -104,4 +126,27 @@ mod tests {
         assert!(report.contains("Retain all HTTP 5xx errors"));
         assert!(report.contains("Sample at 1.0%"));
     }
+
+    #[test]
+    fn generate_waggle_report_describes_circuit_breaker() {
+        let mut policy = Policy::new("test-policy");
+        policy.add_rule(Rule::new(
+            "fallback",
+            "true",
+            Action::CircuitBreaker {
+                closed_rate: 0.01,
+                open_rate: 0.9,
+                window: 100,
+                failure_threshold: 0.2,
+                min_samples: 20,
+            },
+            0,
+        ));
+
+        let report = generate_waggle_report(&policy);
+
+        assert!(report.contains("Circuit breaker: sample at 1.0% normally"));
+        assert!(report.contains("escalating to 90.0%"));
+        assert!(report.contains("**Circuit breakers**: fallback"));
+    }
 }
@@ -0,0 +1,217 @@
+//! Typed attribute conversion.
+//!
+//! Trace and span attributes arrive as plain strings, which forces every
+//! comparison (policy evaluation, simulation, reporting) to re-parse them
+//! on every access. A [`Conversion`] describes how a particular attribute
+//! key should be interpreted, and [`Conversion::convert`] turns the raw
+//! string into a typed [`AttrValue`] once, with a clear error on malformed
+//! input.
+
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use std::str::FromStr;
+
+/// How a raw attribute string should be interpreted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conversion {
+    /// Keep the raw string as-is.
+    Bytes,
+    /// Parse as a signed 64-bit integer.
+    Integer,
+    /// Parse as a 64-bit float.
+    Float,
+    /// Parse as a boolean (`true`/`false`, `1`/`0`).
+    Boolean,
+    /// Parse as an RFC 3339 timestamp.
+    Timestamp,
+    /// Parse as a timestamp using the given `strftime` format.
+    TimestampFmt(String),
+    /// Parse as a timestamp with timezone using the given `strftime` format.
+    TimestampTzFmt(String),
+}
+
+impl FromStr for Conversion {
+    type Err = Error;
+
+    /// Parses a conversion name, e.g. `"int"`, `"bool"`, or
+    /// `"timestamp|%Y-%m-%d"`.
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some((kind, fmt)) = s.split_once('|') {
+            return match kind {
+                "timestamp" => Ok(Self::TimestampFmt(fmt.to_string())),
+                "timestamptz" => Ok(Self::TimestampTzFmt(fmt.to_string())),
+                other => Err(Error::InvalidTrace(format!(
+                    "unknown conversion kind '{other}' in '{s}'"
+                ))),
+            };
+        }
+
+        match s {
+            "int" | "integer" => Ok(Self::Integer),
+            "float" => Ok(Self::Float),
+            "bool" | "boolean" => Ok(Self::Boolean),
+            "string" | "bytes" | "asis" => Ok(Self::Bytes),
+            "timestamp" => Ok(Self::Timestamp),
+            other => Err(Error::InvalidTrace(format!(
+                "unknown conversion '{other}'"
+            ))),
+        }
+    }
+}
+
+impl Conversion {
+    /// Converts a raw attribute string into a typed value according to
+    /// this conversion.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `raw` cannot be parsed as the target type.
+    pub fn convert(&self, raw: &str) -> Result<AttrValue> {
+        match self {
+            Self::Bytes => Ok(AttrValue::Bytes(raw.to_string())),
+            Self::Integer => raw
+                .parse::<i64>()
+                .map(AttrValue::Integer)
+                .map_err(|e| Error::InvalidTrace(format!("'{raw}' is not an integer: {e}"))),
+            Self::Float => raw
+                .parse::<f64>()
+                .map(AttrValue::Float)
+                .map_err(|e| Error::InvalidTrace(format!("'{raw}' is not a float: {e}"))),
+            Self::Boolean => match raw {
+                "true" | "1" => Ok(AttrValue::Boolean(true)),
+                "false" | "0" => Ok(AttrValue::Boolean(false)),
+                other => Err(Error::InvalidTrace(format!(
+                    "'{other}' is not a boolean"
+                ))),
+            },
+            Self::Timestamp => DateTime::parse_from_rfc3339(raw)
+                .map(|dt| AttrValue::Timestamp(dt.with_timezone(&Utc)))
+                .map_err(|e| Error::InvalidTrace(format!("'{raw}' is not an RFC 3339 timestamp: {e}"))),
+            Self::TimestampFmt(fmt) => chrono::NaiveDateTime::parse_from_str(raw, fmt)
+                .map(|naive| AttrValue::Timestamp(DateTime::<Utc>::from_naive_utc_and_offset(naive, Utc)))
+                .map_err(|e| {
+                    Error::InvalidTrace(format!(
+                        "'{raw}' does not match timestamp format '{fmt}': {e}"
+                    ))
+                }),
+            Self::TimestampTzFmt(fmt) => DateTime::parse_from_str(raw, fmt)
+                .map(|dt| AttrValue::Timestamp(dt.with_timezone(&Utc)))
+                .map_err(|e| {
+                    Error::InvalidTrace(format!(
+                        "'{raw}' does not match timestamp format '{fmt}': {e}"
+                    ))
+                }),
+        }
+    }
+}
+
+/// A typed attribute value produced by [`Conversion::convert`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttrValue {
+    /// Raw, unconverted string.
+    Bytes(String),
+    /// Signed integer.
+    Integer(i64),
+    /// Floating point.
+    Float(f64),
+    /// Boolean.
+    Boolean(bool),
+    /// Timestamp, normalized to UTC.
+    Timestamp(DateTime<Utc>),
+}
+
+impl AttrValue {
+    /// Returns this value as an `i64` if it is an integer.
+    #[must_use]
+    pub const fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as an `f64` if it is a float.
+    #[must_use]
+    pub const fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as a `bool` if it is a boolean.
+    #[must_use]
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns this value as a string slice if it is raw bytes.
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Bytes(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversion_names_parse() {
+        assert_eq!("int".parse::<Conversion>().unwrap(), Conversion::Integer);
+        assert_eq!(
+            "integer".parse::<Conversion>().unwrap(),
+            Conversion::Integer
+        );
+        assert_eq!("float".parse::<Conversion>().unwrap(), Conversion::Float);
+        assert_eq!("bool".parse::<Conversion>().unwrap(), Conversion::Boolean);
+        assert_eq!(
+            "boolean".parse::<Conversion>().unwrap(),
+            Conversion::Boolean
+        );
+        assert_eq!("bytes".parse::<Conversion>().unwrap(), Conversion::Bytes);
+        assert_eq!("asis".parse::<Conversion>().unwrap(), Conversion::Bytes);
+        assert_eq!(
+            "timestamp".parse::<Conversion>().unwrap(),
+            Conversion::Timestamp
+        );
+        assert_eq!(
+            "timestamp|%Y-%m-%d".parse::<Conversion>().unwrap(),
+            Conversion::TimestampFmt("%Y-%m-%d".to_string())
+        );
+        assert!("nonsense".parse::<Conversion>().is_err());
+    }
+
+    #[test]
+    fn convert_integer() {
+        let value = Conversion::Integer.convert("500").unwrap();
+        assert_eq!(value.as_i64(), Some(500));
+        assert!(Conversion::Integer.convert("not-a-number").is_err());
+    }
+
+    #[test]
+    fn convert_boolean() {
+        assert_eq!(
+            Conversion::Boolean.convert("true").unwrap().as_bool(),
+            Some(true)
+        );
+        assert_eq!(
+            Conversion::Boolean.convert("0").unwrap().as_bool(),
+            Some(false)
+        );
+        assert!(Conversion::Boolean.convert("maybe").is_err());
+    }
+
+    #[test]
+    fn convert_timestamp_with_format() {
+        let conversion: Conversion = "timestamp|%Y-%m-%d".parse().unwrap();
+        let value = conversion.convert("2024-01-15").unwrap();
+        assert!(matches!(value, AttrValue::Timestamp(_)));
+    }
+}
@@ -1,5 +1,6 @@
 //! Corpus container and operations.
 
+use crate::encoder::escape_value;
 use crate::error::Result;
 use crate::ingestor::IngestorRegistry;
 use crate::trace::Trace;
@@ -64,28 +65,58 @@ impl Corpus {
 
     /// Encodes the corpus to TOON format.
     ///
+    /// Each trace is followed by a nested `spans[N]{...}` table (indented
+    /// two spaces further than the trace row) when it carries any spans,
+    /// mirroring the nested-table convention used by `toon_policy` for
+    /// `rules[N]{...}`.
+    ///
     /// # Errors
     ///
     /// Returns an error if encoding fails.
     pub fn encode_toon(&self) -> Result<String> {
-        // TODO: Implement TOON encoding
-        Ok(format!(
-            "corpus[{}]{{trace_id,duration_ms,status,service,endpoint,is_error}}:\n{}",
-            self.traces.len(),
-            self.traces
-                .iter()
-                .map(|t| format!(
-                    "  {},{},{},{},{},{}",
-                    t.trace_id,
-                    t.duration.as_millis(),
-                    t.status.map_or_else(|| "-".to_string(), |s| s.to_string()),
-                    t.service.as_deref().unwrap_or("-"),
-                    t.endpoint.as_deref().unwrap_or("-"),
-                    t.is_error
-                ))
-                .collect::<Vec<_>>()
-                .join("\n")
-        ))
+        let mut out = format!(
+            "corpus[{}]{{trace_id,duration_ms,status,service,endpoint,is_error}}:\n",
+            self.traces.len()
+        );
+
+        for trace in &self.traces {
+            out.push_str(&format!(
+                "  {},{},{},{},{},{}\n",
+                escape_value(&trace.trace_id),
+                trace.duration.as_millis(),
+                trace.status.map_or_else(|| "-".to_string(), |s| s.to_string()),
+                trace.service.as_deref().map_or_else(|| "-".to_string(), escape_value),
+                trace.endpoint.as_deref().map_or_else(|| "-".to_string(), escape_value),
+                trace.is_error
+            ));
+
+            if !trace.spans.is_empty() {
+                out.push_str(&format!(
+                    "    spans[{}]{{span_id,parent_span_id,name,service,duration_ms,kind,status}}:\n",
+                    trace.spans.len()
+                ));
+                for span in &trace.spans {
+                    out.push_str(&format!(
+                        "      {},{},{},{},{},{:?},{:?}\n",
+                        escape_value(&span.span_id),
+                        span.parent_span_id.as_deref().unwrap_or("-"),
+                        escape_value(&span.name),
+                        escape_value(&span.service),
+                        span.duration.as_millis(),
+                        span.kind,
+                        span.status.code
+                    ));
+                }
+            }
+        }
+
+        // Drop the trailing newline to match the prior placeholder's
+        // no-trailing-newline convention.
+        if out.ends_with('\n') {
+            out.pop();
+        }
+
+        Ok(out)
     }
 
     /// Consumes the corpus and returns the traces.
@@ -161,6 +192,25 @@ impl Corpus {
         Ok(Self { traces })
     }
 
+    /// Ingests trace data with content-type and content-encoding hints.
+    ///
+    /// If `content_encoding` (e.g. `"gzip"`, `"zstd"`) or the payload's
+    /// header bytes indicate a compressed body, it's transparently
+    /// decompressed before format detection runs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if decompression or parsing fails.
+    pub fn ingest_with_encoding(
+        data: &[u8],
+        content_type: Option<&str>,
+        content_encoding: Option<&str>,
+    ) -> Result<Self> {
+        let registry = IngestorRegistry::new();
+        let traces = registry.ingest_with_encoding(data, content_type, content_encoding)?;
+        Ok(Self { traces })
+    }
+
     /// Ingests trace data from a file with auto-detection.
     ///
     /// The format is auto-detected from the file contents.
@@ -226,4 +276,25 @@ mod tests {
         assert!(toon.contains("corpus[1]"));
         assert!(toon.contains("abc,150,200,api,/users,false"));
     }
+
+    #[test]
+    fn corpus_encode_toon_nests_spans() {
+        use crate::span::Span;
+
+        let trace = Trace::from_spans(
+            "abc",
+            vec![
+                Span::new("span-1", "GET /users").with_service("api"),
+                Span::new("span-2", "db.query")
+                    .with_parent("span-1")
+                    .with_service("api"),
+            ],
+        );
+        let corpus: Corpus = vec![trace].into_iter().collect();
+
+        let toon = corpus.encode_toon().unwrap();
+        assert!(toon.contains("spans[2]{span_id,parent_span_id,name,service,duration_ms,kind,status}:"));
+        assert!(toon.contains("span-1,-,"));
+        assert!(toon.contains("span-2,span-1,"));
+    }
 }
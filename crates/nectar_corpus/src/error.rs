@@ -21,6 +21,16 @@ pub enum Error {
     #[error("unknown format: {0}")]
     UnknownFormat(String),
 
+    /// A `Reservoir` snapshot file failed its magic-byte or version
+    /// check, or its body failed to parse.
+    #[error("invalid reservoir snapshot: {0}")]
+    InvalidSnapshot(String),
+
+    /// Failed to decompress a compressed payload, or the decompressed
+    /// output exceeded the configured size cap.
+    #[error("decompression error: {0}")]
+    Decompression(String),
+
     /// Parse error for a specific format.
     #[error("parse error ({format}): {message}")]
     ParseError {
@@ -14,6 +14,7 @@
 #![allow(clippy::missing_panics_doc)]
 #![allow(clippy::suboptimal_flops)]
 
+use crate::span::{Span, SpanStatus};
 use crate::{Corpus, Trace};
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
@@ -53,6 +54,155 @@ impl FixtureConfig {
     }
 }
 
+/// Service mesh topology shared by [`FixtureGenerator::microservices_topology`],
+/// [`FixtureGenerator::microservices_stream`], and
+/// [`FixtureGenerator::traces_since`].
+const MICROSERVICES: &[(&str, &[&str])] = &[
+    ("api-gateway", &["/api/v2/users/:id", "/api/v2/orders", "/api/v2/products", "/api/v2/checkout", "/health"]),
+    ("user-service", &["/internal/users/:id", "/internal/users/lookup", "/internal/auth/validate"]),
+    ("order-service", &["/internal/orders", "/internal/orders/:id", "/internal/orders/history"]),
+    ("product-service", &["/internal/products/:id", "/internal/products/search", "/internal/inventory"]),
+    ("payment-service", &["/internal/charge", "/internal/refund", "/internal/verify"]),
+    ("notification-service", &["/internal/send", "/internal/batch", "/internal/templates"]),
+    ("cache-service", &["/internal/get", "/internal/set", "/internal/invalidate"]),
+];
+
+/// Infra leaves the domain-service children of a request's root span
+/// fan out into at the bottom of the tree [`build_request_span_tree`]
+/// builds - a cache, a database, and a queue, cycled through by index.
+const SPAN_LEAVES: &[(&str, &str)] = &[
+    ("cache-service", "cache.get"),
+    ("postgres", "db.query"),
+    ("kafka", "queue.publish"),
+];
+
+/// Converts a [`Duration`] to nanoseconds, saturating at [`u64::MAX`]
+/// for the (here, never reached) case of a duration that doesn't fit.
+fn duration_to_ns(d: Duration) -> u64 {
+    u64::try_from(d.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// Builds a real span tree for one request: a root span for `service`
+/// handling `route`, fanning out into up to 6 domain-service children
+/// (drawn from [`MICROSERVICES`], skipping `service` itself so a span
+/// doesn't call itself), each of which in turn calls one [`SPAN_LEAVES`]
+/// infra dependency. Every child span is nested strictly inside its
+/// parent's `[start, start + duration)` window, so the tree has a
+/// synthesizable critical path instead of just an aggregate
+/// `span_count`. `fanout` is the caller's already-computed span count
+/// for this request; the tree uses it only to decide how many domain
+/// children to generate, clamped to a sane range.
+fn build_request_span_tree(
+    trace_id: &str,
+    service: &str,
+    route: &str,
+    duration: Duration,
+    status: u16,
+    fanout: usize,
+) -> Vec<Span> {
+    let span_status = if status >= 500 {
+        SpanStatus::error(format!("upstream returned {status}"))
+    } else {
+        SpanStatus::ok()
+    };
+
+    let root_id = format!("{trace_id}-s0");
+    let root_ns = duration_to_ns(duration);
+    let child_count = (fanout / 3).clamp(1, 6);
+
+    // Reserve the root's own routing/auth overhead up front, then split
+    // what's left evenly across its children so each child's window
+    // sits strictly inside the root's.
+    let self_time_ns = root_ns / (child_count as u64 + 1);
+    let slice_ns = root_ns.saturating_sub(self_time_ns) / child_count as u64;
+
+    let mut spans = vec![Span::new(root_id.clone(), "handle-request")
+        .with_service(service)
+        .with_duration(duration)
+        .with_start_time_ns(0)
+        .with_status(span_status.clone())
+        .with_attribute("http.route", route.to_string())];
+
+    let mut next_id = 1usize;
+    let mut child_start_ns = self_time_ns;
+    for i in 0..child_count {
+        let domain_service = MICROSERVICES
+            .iter()
+            .map(|(name, _)| *name)
+            .filter(|name| *name != service)
+            .nth(i % (MICROSERVICES.len() - 1))
+            .unwrap_or(service);
+
+        let domain_id = format!("{trace_id}-s{next_id}");
+        next_id += 1;
+        // Leave a 25% margin so the leaf span below nests inside this one.
+        let domain_ns = slice_ns.saturating_sub(slice_ns / 4);
+        spans.push(
+            Span::new(domain_id.clone(), format!("call-{domain_service}"))
+                .with_parent(root_id.clone())
+                .with_service(domain_service)
+                .with_duration(Duration::from_nanos(domain_ns))
+                .with_start_time_ns(child_start_ns)
+                .with_status(span_status.clone()),
+        );
+
+        let (leaf_service, leaf_op) = SPAN_LEAVES[i % SPAN_LEAVES.len()];
+        let leaf_ns = domain_ns / 2;
+        let leaf_id = format!("{trace_id}-s{next_id}");
+        next_id += 1;
+        spans.push(
+            Span::new(leaf_id, leaf_op)
+                .with_parent(domain_id)
+                .with_service(leaf_service)
+                .with_duration(Duration::from_nanos(leaf_ns))
+                .with_start_time_ns(child_start_ns + (domain_ns - leaf_ns) / 2),
+        );
+
+        child_start_ns += slice_ns;
+    }
+
+    spans
+}
+
+/// Builds a deep, mostly-linear call chain for the `deep_trace`
+/// observability pattern: `depth` spans, each nested strictly inside
+/// its predecessor's time window and cycling through `services_involved`
+/// distinct service names, so a real critical path exists instead of
+/// just `trace.depth`/`span_count` attributes.
+fn build_deep_chain(trace_id: &str, depth: usize, services_involved: usize, duration: Duration) -> Vec<Span> {
+    let depth = depth.max(1);
+    let services_involved = services_involved.max(1);
+
+    let mut spans = Vec::with_capacity(depth);
+    let mut window_start_ns = 0u64;
+    let mut window_ns = duration_to_ns(duration).max(depth as u64 * 1000);
+    let mut parent_id: Option<String> = None;
+
+    for i in 0..depth {
+        let id = format!("{trace_id}-s{i}");
+        let service = format!("service-{}", i % services_involved);
+
+        let mut span = Span::new(id.clone(), format!("op-{i}"))
+            .with_service(service)
+            .with_duration(Duration::from_nanos(window_ns))
+            .with_start_time_ns(window_start_ns);
+        if let Some(parent) = parent_id.take() {
+            span = span.with_parent(parent);
+        }
+        parent_id = Some(id);
+        spans.push(span);
+
+        // Shrink the window by 10% on each side so the next span nests
+        // strictly inside this one, with a floor so it never collapses
+        // to zero-width.
+        let shrink = (window_ns / 10).max(1);
+        window_start_ns += shrink;
+        window_ns = window_ns.saturating_sub(shrink * 2).max(1000);
+    }
+
+    spans
+}
+
 /// Production fixture generator.
 pub struct FixtureGenerator {
     rng: ChaCha8Rng,
@@ -67,6 +217,21 @@ impl FixtureGenerator {
         Self { rng, config }
     }
 
+    /// Creates a fixture generator from a raw entropy stream rather than
+    /// a fixed seed, so fuzzers (honggfuzz, `cargo-fuzz`) that only hand
+    /// over arbitrary bytes can still drive it: the first 8 bytes become
+    /// the `ChaCha8Rng` seed (still the default reproducibility path -
+    /// zero-padded if `bytes` is shorter) and the next byte picks a
+    /// trace count in `1..=64`. `bytes` is never required to be any
+    /// particular length; exhausted input reads as zero.
+    #[must_use]
+    pub fn from_entropy(bytes: &[u8]) -> Self {
+        let mut cursor = EntropyCursor::new(bytes);
+        let seed = cursor.next_u64();
+        let trace_count = 1 + usize::from(cursor.next_u8() % 64);
+        Self::new(FixtureConfig::default().with_seed(seed).with_count(trace_count))
+    }
+
     /// Generates a microservices topology corpus.
     ///
     /// Simulates a realistic e-commerce service mesh with:
@@ -77,64 +242,96 @@ impl FixtureGenerator {
     #[must_use]
     pub fn microservices_topology(&mut self) -> Corpus {
         let mut corpus = Corpus::new();
-        let services = [
-            ("api-gateway", vec!["/api/v2/users/:id", "/api/v2/orders", "/api/v2/products", "/api/v2/checkout", "/health"]),
-            ("user-service", vec!["/internal/users/:id", "/internal/users/lookup", "/internal/auth/validate"]),
-            ("order-service", vec!["/internal/orders", "/internal/orders/:id", "/internal/orders/history"]),
-            ("product-service", vec!["/internal/products/:id", "/internal/products/search", "/internal/inventory"]),
-            ("payment-service", vec!["/internal/charge", "/internal/refund", "/internal/verify"]),
-            ("notification-service", vec!["/internal/send", "/internal/batch", "/internal/templates"]),
-            ("cache-service", vec!["/internal/get", "/internal/set", "/internal/invalidate"]),
-        ];
-
         for i in 0..self.config.trace_count {
-            let (service, routes) = services.choose(&mut self.rng).unwrap();
-            let route = routes.choose(&mut self.rng).unwrap();
-
-            let is_error = self.rng.gen_bool(0.03);
-            let is_slow = self.rng.gen_bool(0.05);
-
-            let status = if is_error {
-                *[500u16, 502, 503, 504].choose(&mut self.rng).unwrap()
-            } else {
-                *[200u16, 201, 204].choose(&mut self.rng).unwrap()
-            };
-
-            let base_latency = match *service {
-                "api-gateway" => 50,
-                "cache-service" => 5,
-                "payment-service" => 400,
-                _ => 30,
-            };
-
-            let duration_ms = if is_slow {
-                base_latency * 50 + self.rng.gen_range(0..5000)
-            } else {
-                base_latency + self.rng.gen_range(0..base_latency * 2)
-            };
+            corpus.add(self.microservices_trace(i));
+        }
+        corpus
+    }
 
-            let span_count = match *service {
-                "api-gateway" => self.rng.gen_range(5..25),
-                "payment-service" => self.rng.gen_range(8..15),
-                _ => self.rng.gen_range(2..8),
-            };
+    /// Generates [`Self::microservices_topology`]'s traces lazily, one
+    /// at a time, without materializing the whole [`Corpus`] - useful
+    /// for multi-million-trace simulations where eagerly allocating
+    /// every trace up front is wasteful, or for consumers that want to
+    /// process traces as they're produced.
+    pub fn microservices_stream(&mut self) -> impl Iterator<Item = Trace> + '_ {
+        let trace_count = self.config.trace_count;
+        (0..trace_count).map(move |i| self.microservices_trace(i))
+    }
 
-            let mut trace = Trace::new(format!("topo-{i:08x}"))
-                .with_service(*service)
-                .with_endpoint(*route)
-                .with_status(status)
-                .with_duration(Duration::from_millis(duration_ms))
-                .with_attribute("span_count", span_count.to_string())
-                .with_attribute("scenario", "microservices_topology");
+    /// Resumes [`Self::microservices_topology`] generation
+    /// deterministically from trace index `watermark`, for a
+    /// long-running consumer that periodically asks for "only the
+    /// traces produced since my last checkpoint." Re-derives RNG state
+    /// for that offset by resetting to the configured seed and
+    /// replaying (and discarding) the first `watermark` traces, so
+    /// `traces_since(0)` yields exactly what [`Self::microservices_stream`]
+    /// would, and `traces_since(n)` picks up exactly where a consumer
+    /// that last saw trace index `n - 1` left off - regardless of what
+    /// this generator was used for before the call.
+    pub fn traces_since(&mut self, watermark: usize) -> impl Iterator<Item = Trace> + '_ {
+        self.rng = ChaCha8Rng::seed_from_u64(self.config.seed);
+        for i in 0..watermark.min(self.config.trace_count) {
+            self.microservices_trace(i);
+        }
 
-            if is_error {
-                trace = trace.with_attribute("error.type", self.random_error_type());
-            }
+        let trace_count = self.config.trace_count;
+        (watermark..trace_count).map(move |i| self.microservices_trace(i))
+    }
 
-            corpus.add(trace);
+    /// Generates the `i`-th trace of [`Self::microservices_topology`],
+    /// consuming [`Self::rng`] draws in the same order the eager
+    /// version does - the shared building block behind the eager,
+    /// streaming, and watermark-resuming entry points above.
+    fn microservices_trace(&mut self, i: usize) -> Trace {
+        let (service, routes) = MICROSERVICES.choose(&mut self.rng).unwrap();
+        let route = routes.choose(&mut self.rng).unwrap();
+
+        let is_error = self.rng.gen_bool(0.03);
+        let is_slow = self.rng.gen_bool(0.05);
+
+        let status = if is_error {
+            *[500u16, 502, 503, 504].choose(&mut self.rng).unwrap()
+        } else {
+            *[200u16, 201, 204].choose(&mut self.rng).unwrap()
+        };
+
+        let base_latency = match *service {
+            "api-gateway" => 50,
+            "cache-service" => 5,
+            "payment-service" => 400,
+            _ => 30,
+        };
+
+        let duration_ms = if is_slow {
+            base_latency * 50 + self.rng.gen_range(0..5000)
+        } else {
+            base_latency + self.rng.gen_range(0..base_latency * 2)
+        };
+
+        let span_count = match *service {
+            "api-gateway" => self.rng.gen_range(5..25),
+            "payment-service" => self.rng.gen_range(8..15),
+            _ => self.rng.gen_range(2..8),
+        };
+
+        let trace_id = format!("topo-{i:08x}");
+        let duration = Duration::from_millis(duration_ms);
+        let spans = build_request_span_tree(&trace_id, service, route, duration, status, span_count);
+
+        let mut trace = Trace::new(trace_id)
+            .with_service(*service)
+            .with_endpoint(*route)
+            .with_status(status)
+            .with_duration(duration)
+            .with_attribute("span_count", span_count.to_string())
+            .with_attribute("scenario", "microservices_topology")
+            .with_spans(spans);
+
+        if is_error {
+            trace = trace.with_attribute("error.type", self.random_error_type());
         }
 
-        corpus
+        trace
     }
 
     /// Generates a failure patterns corpus.
@@ -357,10 +554,13 @@ impl FixtureGenerator {
                 "deep_trace" => {
                     let depth = self.rng.gen_range(5..15);
                     let span_count = self.rng.gen_range(50..500);
+                    let services_involved = self.rng.gen_range(5..15);
+                    let chain = build_deep_chain(&trace.trace_id, depth, services_involved, trace.duration);
                     trace = trace
                         .with_attribute("trace.depth", depth.to_string())
                         .with_attribute("span_count", span_count.to_string())
-                        .with_attribute("trace.services_involved", self.rng.gen_range(5..15).to_string());
+                        .with_attribute("trace.services_involved", services_involved.to_string())
+                        .with_spans(chain);
                 }
                 "high_cardinality" => {
                     trace = trace
@@ -453,6 +653,44 @@ impl FixtureGenerator {
     }
 }
 
+/// A cursor over a raw byte slice that never panics on exhaustion -
+/// reads past the end saturate at zero - so fuzz harnesses can pop
+/// however many fields they need from arbitrary, possibly very short,
+/// input. Shared by [`FixtureGenerator::from_entropy`] and
+/// [`crate::fuzz::fuzz_corpus`].
+pub(crate) struct EntropyCursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> EntropyCursor<'a> {
+    pub(crate) const fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Pops the next byte, or `0` once `data` is exhausted.
+    pub(crate) fn next_u8(&mut self) -> u8 {
+        let byte = self.data.get(self.pos).copied().unwrap_or(0);
+        self.pos += 1;
+        byte
+    }
+
+    /// Pops 8 bytes (zero-padded past the end) as a little-endian `u64`.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let mut buf = [0u8; 8];
+        for b in &mut buf {
+            *b = self.next_u8();
+        }
+        u64::from_le_bytes(buf)
+    }
+
+    /// Pops a byte and reduces it into `0..bound`, for picking an index
+    /// into a small fixed-size table. `bound` must be non-zero.
+    pub(crate) fn next_index(&mut self, bound: usize) -> usize {
+        usize::from(self.next_u8()) % bound
+    }
+}
+
 /// Convenience function to generate a deterministic production corpus.
 #[must_use]
 pub fn production_corpus(seed: u64, size: usize) -> Corpus {
@@ -575,6 +813,79 @@ mod tests {
         assert!(patterns.contains("sampling_decision"));
     }
 
+    #[test]
+    fn microservices_stream_matches_the_eager_corpus() {
+        let config = FixtureConfig::default().with_count(50);
+        let eager = FixtureGenerator::new(config.clone()).microservices_topology();
+        let streamed: Vec<_> = FixtureGenerator::new(config).microservices_stream().collect();
+
+        assert_eq!(eager.len(), streamed.len());
+        for (e, s) in eager.iter().zip(streamed.iter()) {
+            assert_eq!(e.trace_id, s.trace_id);
+            assert_eq!(e.service, s.service);
+            assert_eq!(e.status, s.status);
+            assert_eq!(e.duration, s.duration);
+        }
+    }
+
+    #[test]
+    fn traces_since_zero_matches_the_full_stream() {
+        let config = FixtureConfig::default().with_count(20);
+        let mut gen = FixtureGenerator::new(config.clone());
+        let full: Vec<_> = gen.microservices_stream().collect();
+
+        let mut gen = FixtureGenerator::new(config);
+        let resumed: Vec<_> = gen.traces_since(0).collect();
+
+        assert_eq!(full.len(), resumed.len());
+        for (a, b) in full.iter().zip(resumed.iter()) {
+            assert_eq!(a.trace_id, b.trace_id);
+            assert_eq!(a.status, b.status);
+        }
+    }
+
+    #[test]
+    fn traces_since_resumes_exactly_where_a_prior_watermark_left_off() {
+        let config = FixtureConfig::default().with_count(20);
+        let full: Vec<_> = FixtureGenerator::new(config.clone())
+            .microservices_stream()
+            .collect();
+
+        let resumed: Vec<_> = FixtureGenerator::new(config).traces_since(12).collect();
+
+        assert_eq!(resumed.len(), full.len() - 12);
+        for (a, b) in full[12..].iter().zip(resumed.iter()) {
+            assert_eq!(a.trace_id, b.trace_id);
+            assert_eq!(a.status, b.status);
+        }
+    }
+
+    #[test]
+    fn traces_since_a_watermark_past_the_trace_count_yields_nothing() {
+        let config = FixtureConfig::default().with_count(10);
+        let resumed: Vec<_> = FixtureGenerator::new(config).traces_since(100).collect();
+        assert!(resumed.is_empty());
+    }
+
+    #[test]
+    fn from_entropy_is_deterministic_for_identical_input() {
+        let bytes = b"some fuzzer-provided entropy";
+        let corpus1 = FixtureGenerator::from_entropy(bytes).microservices_topology();
+        let corpus2 = FixtureGenerator::from_entropy(bytes).microservices_topology();
+
+        let ids1: Vec<_> = corpus1.iter().map(|t| t.trace_id.clone()).collect();
+        let ids2: Vec<_> = corpus2.iter().map(|t| t.trace_id.clone()).collect();
+        assert_eq!(ids1, ids2);
+    }
+
+    #[test]
+    fn from_entropy_never_panics_on_short_or_empty_input() {
+        for bytes in [&b""[..], &b"\x01"[..], &b"\x01\x02\x03"[..]] {
+            let corpus = FixtureGenerator::from_entropy(bytes).microservices_topology();
+            assert!(!corpus.is_empty());
+        }
+    }
+
     #[test]
     fn production_mix_combines_all_scenarios() {
         let corpus = production_corpus(42, 400);
@@ -0,0 +1,313 @@
+//! Coverage-guided-style fuzzing for the trace ingest path and the
+//! corpus analysis paths downstream of it.
+//!
+//! `Corpus::ingest`/`ingest_with_content_type` parse untrusted external
+//! bytes through [`crate::ingestor::IngestorRegistry`]. This module
+//! generates structured, seed-corpus-derived mutations of raw ingest
+//! input and drives them through that path, asserting it never panics
+//! and that any successfully parsed [`Corpus`] survives a TOON encode
+//! and a JSON serde round-trip.
+//!
+//! [`fuzz_corpus`] instead targets the analysis paths that consume an
+//! already-parsed `Corpus` (reservoir sampling, the prover, `vopr`),
+//! which `fuzz_ingest`'s JSON-shaped mutations rarely exercise deeply.
+//! It builds a `Corpus` directly from raw fuzzer bytes so a
+//! `fuzz_target!`/honggfuzz harness can feed it straight into those
+//! paths.
+
+use crate::corpus::Corpus;
+use crate::fixtures::EntropyCursor;
+use crate::trace::Trace;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use std::any::Any;
+use std::time::Duration;
+
+/// A single generated ingest input: raw bytes plus an optional
+/// content-type hint, mirroring the arguments to
+/// `Corpus::ingest_with_content_type`.
+#[derive(Debug, Clone)]
+pub struct FuzzInput {
+    /// The raw (possibly malformed) bytes to feed to the ingest path.
+    pub data: Vec<u8>,
+    /// An optional content-type hint, also subject to mutation.
+    pub content_type: Option<String>,
+}
+
+const SEED_CORPUS: &[&str] = &[
+    r#"[{"trace_id":"a","service":"api","status":200,"duration_ms":10}]"#,
+    r#"{"traceId":"b","serviceName":"svc","statusCode":500,"durationMs":25}"#,
+    r#"{"data":[{"resource":{"attributes":[]},"scopeSpans":[]}]}"#,
+    "",
+    "{}",
+    "[]",
+    "not json at all",
+];
+
+const CONTENT_TYPES: &[Option<&str>] = &[
+    Some("application/json"),
+    Some("application/x-ndjson"),
+    Some("application/x-protobuf"),
+    None,
+];
+
+impl FuzzInput {
+    /// Generates a mutated ingest input by picking a seed from the
+    /// built-in seed corpus and applying a handful of random byte flips,
+    /// insertions, deletions, and truncations.
+    #[must_use]
+    pub fn generate(rng: &mut ChaCha8Rng) -> Self {
+        let seed = SEED_CORPUS.choose(rng).expect("seed corpus is non-empty");
+        let mut data = seed.as_bytes().to_vec();
+
+        for _ in 0..rng.gen_range(0..4) {
+            if data.is_empty() {
+                data.push(rng.gen());
+                continue;
+            }
+            match rng.gen_range(0..4u8) {
+                0 => {
+                    let idx = rng.gen_range(0..data.len());
+                    data[idx] = rng.gen();
+                }
+                1 => {
+                    let idx = rng.gen_range(0..=data.len());
+                    data.insert(idx, rng.gen());
+                }
+                2 => {
+                    let idx = rng.gen_range(0..data.len());
+                    data.remove(idx);
+                }
+                _ => {
+                    let cut = rng.gen_range(0..=data.len());
+                    data.truncate(cut);
+                }
+            }
+        }
+
+        let content_type = CONTENT_TYPES
+            .choose(rng)
+            .expect("content type list is non-empty")
+            .map(str::to_string);
+
+        Self { data, content_type }
+    }
+}
+
+/// Outcome of fuzzing a single input through the ingest path.
+#[derive(Debug)]
+pub enum FuzzOutcome {
+    /// The input was rejected cleanly (an `Err`, not a panic).
+    Rejected,
+    /// The input parsed into a corpus that round-tripped cleanly.
+    Accepted {
+        /// Number of traces the input parsed into.
+        trace_count: usize,
+    },
+    /// Ingestion (or its round-trip check) panicked: an actual bug, with
+    /// the panic message for diagnostics.
+    Panicked(String),
+}
+
+/// Runs one fuzz input through `Corpus::ingest_with_content_type`,
+/// catching panics, and - if ingestion succeeds - verifying the
+/// resulting corpus survives a TOON encode and a per-trace JSON
+/// serde round-trip.
+#[must_use]
+pub fn fuzz_ingest_once(input: &FuzzInput) -> FuzzOutcome {
+    let data = input.data.clone();
+    let content_type = input.content_type.clone();
+
+    let ingested = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        Corpus::ingest_with_content_type(&data, content_type.as_deref())
+    }));
+
+    let corpus = match ingested {
+        Err(payload) => return FuzzOutcome::Panicked(panic_message(&payload)),
+        Ok(Err(_)) => return FuzzOutcome::Rejected,
+        Ok(Ok(corpus)) => corpus,
+    };
+
+    let trace_count = corpus.len();
+    let roundtrip = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        corpus
+            .encode_toon()
+            .expect("encode_toon must not fail on a successfully parsed corpus");
+        for trace in corpus.iter() {
+            let json = serde_json::to_string(trace).expect("trace must serialize to JSON");
+            let back: Trace =
+                serde_json::from_str(&json).expect("trace must deserialize from its own JSON");
+            assert_eq!(trace.trace_id, back.trace_id, "trace_id must survive a JSON round-trip");
+        }
+    }));
+
+    match roundtrip {
+        Err(payload) => FuzzOutcome::Panicked(panic_message(&payload)),
+        Ok(()) => FuzzOutcome::Accepted { trace_count },
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Runs `iterations` fuzz inputs derived from `seed`, returning the first
+/// input that caused a panic, if any. Deterministic in `seed`, so a
+/// crashing input can be handed back to the caller (e.g. the `vopr`
+/// harness) and replayed reproducibly.
+#[must_use]
+pub fn fuzz_ingest(seed: u64, iterations: usize) -> Option<FuzzInput> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    for _ in 0..iterations {
+        let input = FuzzInput::generate(&mut rng);
+        if matches!(fuzz_ingest_once(&input), FuzzOutcome::Panicked(_)) {
+            return Some(input);
+        }
+    }
+    None
+}
+
+/// Service/route pairs [`fuzz_corpus`] picks from - a small subset of
+/// [`crate::fixtures::FixtureGenerator::microservices_topology`]'s
+/// table, kept separate so a byte flip always lands on one of these
+/// fixed, known-valid choices.
+const FUZZ_SERVICES: &[(&str, &[&str])] = &[
+    ("api-gateway", &["/api/v2/users/:id", "/api/v2/orders", "/health"]),
+    ("order-service", &["/internal/orders", "/internal/orders/:id"]),
+    ("payment-service", &["/internal/charge", "/internal/refund"]),
+    ("cache-service", &["/internal/get", "/internal/set"]),
+];
+
+const FUZZ_OK_STATUSES: &[u16] = &[200, 201, 204];
+const FUZZ_ERROR_STATUSES: &[u16] = &[500, 502, 503, 504];
+
+/// Builds a [`Corpus`] directly from a raw entropy stream - the entry
+/// point for a `fuzz_target!`/honggfuzz harness exploring the corpus
+/// *analysis* paths (reservoir sampling, the prover, `vopr`) rather
+/// than the ingest-parsing path [`fuzz_ingest_once`] already covers.
+///
+/// Pops one byte to choose a trace count in `1..=32`, then for each
+/// trace pops bytes to pick a service/route, an error flag and status,
+/// and a latency, so a single bit flip in `data` changes one concrete
+/// trace field directly instead of being absorbed by a PRNG. Reads past
+/// the end of `data` saturate at zero ([`EntropyCursor`]), so even an
+/// empty slice produces a (degenerate but non-panicking) corpus, and
+/// identical `data` always produces an identical corpus.
+#[must_use]
+pub fn fuzz_corpus(data: &[u8]) -> Corpus {
+    let mut cursor = EntropyCursor::new(data);
+    let mut corpus = Corpus::new();
+
+    let trace_count = 1 + usize::from(cursor.next_u8() % 32);
+    for i in 0..trace_count {
+        let (service, routes) = FUZZ_SERVICES[cursor.next_index(FUZZ_SERVICES.len())];
+        let route = routes[cursor.next_index(routes.len())];
+
+        let is_error = cursor.next_u8() % 8 == 0;
+        let status = if is_error {
+            FUZZ_ERROR_STATUSES[cursor.next_index(FUZZ_ERROR_STATUSES.len())]
+        } else {
+            FUZZ_OK_STATUSES[cursor.next_index(FUZZ_OK_STATUSES.len())]
+        };
+
+        let duration_ms = u64::from(cursor.next_u8()) * 17 + u64::from(cursor.next_u8());
+
+        let trace = Trace::new(format!("fuzz-{i:08x}"))
+            .with_service(service)
+            .with_endpoint(route)
+            .with_status(status)
+            .with_duration(Duration::from_millis(duration_ms));
+
+        corpus.add(trace);
+    }
+
+    corpus
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzz_ingest_never_panics_over_many_iterations() {
+        assert!(fuzz_ingest(42, 500).is_none());
+    }
+
+    #[test]
+    fn fuzz_ingest_is_deterministic_for_a_given_seed() {
+        let mut rng_a = ChaCha8Rng::seed_from_u64(7);
+        let mut rng_b = ChaCha8Rng::seed_from_u64(7);
+
+        let input_a = FuzzInput::generate(&mut rng_a);
+        let input_b = FuzzInput::generate(&mut rng_b);
+
+        assert_eq!(input_a.data, input_b.data);
+        assert_eq!(input_a.content_type, input_b.content_type);
+    }
+
+    #[test]
+    fn well_formed_json_input_round_trips() {
+        let input = FuzzInput {
+            data: br#"[{"trace_id":"x","service":"api","status":200,"duration_ms":5}]"#.to_vec(),
+            content_type: Some("application/json".to_string()),
+        };
+
+        match fuzz_ingest_once(&input) {
+            FuzzOutcome::Accepted { trace_count } => assert_eq!(trace_count, 1),
+            other => panic!("expected Accepted, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn fuzz_corpus_never_panics_on_empty_or_short_input() {
+        for data in [&b""[..], &b"\x00"[..], &b"\x01\x02\x03"[..]] {
+            let corpus = fuzz_corpus(data);
+            assert!(!corpus.is_empty());
+        }
+    }
+
+    #[test]
+    fn fuzz_corpus_is_deterministic_for_identical_input() {
+        let data = b"whatever bytes a fuzzer hands us";
+        let corpus_a = fuzz_corpus(data);
+        let corpus_b = fuzz_corpus(data);
+
+        let traces_a: Vec<_> = corpus_a.iter().collect();
+        let traces_b: Vec<_> = corpus_b.iter().collect();
+        assert_eq!(traces_a.len(), traces_b.len());
+        for (a, b) in traces_a.iter().zip(traces_b.iter()) {
+            assert_eq!(a.trace_id, b.trace_id);
+            assert_eq!(a.service, b.service);
+            assert_eq!(a.status, b.status);
+            assert_eq!(a.duration, b.duration);
+        }
+    }
+
+    #[test]
+    fn fuzz_corpus_status_and_service_always_stay_in_the_known_valid_sets() {
+        let valid_statuses: std::collections::HashSet<u16> = FUZZ_OK_STATUSES
+            .iter()
+            .chain(FUZZ_ERROR_STATUSES)
+            .copied()
+            .collect();
+        let valid_services: std::collections::HashSet<&str> =
+            FUZZ_SERVICES.iter().map(|(s, _)| *s).collect();
+
+        for seed_byte in 0u8..=255 {
+            let corpus = fuzz_corpus(&[seed_byte, seed_byte.wrapping_mul(31), 0xAB, 0xCD]);
+            for trace in corpus.iter() {
+                assert!(trace.status.is_some_and(|s| valid_statuses.contains(&s)));
+                assert!(trace
+                    .service
+                    .as_deref()
+                    .is_some_and(|s| valid_services.contains(s)));
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! TOML-driven ingestor configuration.
+//!
+//! Lets operators declare the active ingestor set and their priority
+//! order without recompiling:
+//!
+//! ```toml
+//! [[ingestor]]
+//! format = "json"
+//! priority = 10
+//!
+//! [ingestor.options]
+//! ts_field = "startTime"
+//! ```
+
+use crate::error::{Error, Result};
+use crate::ingestor::TraceIngestor;
+
+/// Builds an ingestor from its `[ingestor.options]` table.
+///
+/// Registered per format name via [`IngestorRegistry::register_binder`]
+/// (or one of the built-ins wired up by [`default_binders`]) and invoked
+/// by [`IngestorRegistry::from_config`]/[`IngestorRegistry::load_config`].
+///
+/// [`IngestorRegistry::register_binder`]: crate::ingestor::IngestorRegistry::register_binder
+/// [`IngestorRegistry::from_config`]: crate::ingestor::IngestorRegistry::from_config
+/// [`IngestorRegistry::load_config`]: crate::ingestor::IngestorRegistry::load_config
+pub type BindFunc = fn(&toml::Value) -> Result<Box<dyn TraceIngestor>>;
+
+/// One parsed `[[ingestor]]` entry.
+pub(crate) struct IngestorEntry {
+    pub(crate) format: String,
+    pub(crate) priority: i64,
+    pub(crate) options: toml::Value,
+}
+
+/// Parses the `[[ingestor]]` array from a config document. Returns an
+/// empty list if the document has no `ingestor` key at all.
+pub(crate) fn parse_entries(config: &toml::Value) -> Result<Vec<IngestorEntry>> {
+    let table = config
+        .as_table()
+        .ok_or_else(|| Error::LoadError("ingestor config must be a TOML table".to_string()))?;
+
+    let Some(ingestor_value) = table.get("ingestor") else {
+        return Ok(Vec::new());
+    };
+
+    let array = ingestor_value.as_array().ok_or_else(|| {
+        Error::LoadError("`ingestor` must be an array of tables (`[[ingestor]]`)".to_string())
+    })?;
+
+    array.iter().map(parse_entry).collect()
+}
+
+fn parse_entry(entry: &toml::Value) -> Result<IngestorEntry> {
+    let table = entry
+        .as_table()
+        .ok_or_else(|| Error::LoadError("each `[[ingestor]]` entry must be a table".to_string()))?;
+
+    let format = table
+        .get("format")
+        .and_then(toml::Value::as_str)
+        .ok_or_else(|| Error::LoadError("`[[ingestor]]` entry missing `format`".to_string()))?
+        .to_string();
+
+    let priority = table
+        .get("priority")
+        .and_then(toml::Value::as_integer)
+        .unwrap_or(0);
+
+    let options = table
+        .get("options")
+        .cloned()
+        .unwrap_or_else(|| toml::Value::Table(toml::value::Table::new()));
+
+    Ok(IngestorEntry {
+        format,
+        priority,
+        options,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_entries_empty_without_ingestor_key() {
+        let config: toml::Value = toml::from_str("").unwrap();
+        assert!(parse_entries(&config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_entries_reads_format_priority_and_options() {
+        let config: toml::Value = toml::from_str(
+            r#"
+            [[ingestor]]
+            format = "json"
+            priority = 10
+
+            [ingestor.options]
+            ts_field = "startTime"
+            "#,
+        )
+        .unwrap();
+
+        let entries = parse_entries(&config).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].format, "json");
+        assert_eq!(entries[0].priority, 10);
+        assert_eq!(
+            entries[0].options.get("ts_field").and_then(toml::Value::as_str),
+            Some("startTime")
+        );
+    }
+
+    #[test]
+    fn parse_entries_defaults_priority_and_options() {
+        let config: toml::Value = toml::from_str(
+            r#"
+            [[ingestor]]
+            format = "honeycomb"
+            "#,
+        )
+        .unwrap();
+
+        let entries = parse_entries(&config).unwrap();
+        assert_eq!(entries[0].priority, 0);
+        assert!(entries[0].options.as_table().unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_entries_rejects_missing_format() {
+        let config: toml::Value = toml::from_str(
+            r#"
+            [[ingestor]]
+            priority = 1
+            "#,
+        )
+        .unwrap();
+
+        assert!(parse_entries(&config).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+//! Structured `Content-Type` header parsing, shared across ingestors.
+//!
+//! Replaces ad hoc `content_type.contains("...")` substring checks -
+//! which misfire on a trailing `; charset=utf-8` and can't read
+//! parameters - with a spec-correct byte-level state machine over
+//! `type/subtype; key=value; key="quoted value"`.
+
+use std::collections::HashMap;
+
+/// A parsed `Content-Type` header: `type/subtype` plus its `key=value`
+/// parameters.
+///
+/// Parameter keys are case-insensitively matched (stored lowercased);
+/// parameter values keep their original case. A quoted value has its
+/// surrounding quotes stripped and `\"` escapes unescaped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentType {
+    /// The top-level type, lowercased (e.g. `"application"`).
+    pub r#type: String,
+    /// The subtype, lowercased (e.g. `"x-ndjson"`).
+    pub subtype: String,
+    /// Parameters, keyed by lowercased name.
+    pub params: HashMap<String, String>,
+}
+
+impl ContentType {
+    /// Parses a raw `Content-Type` header value.
+    ///
+    /// Returns `None` if `header` has no `/`-separated mime type to
+    /// parse, so callers (`can_handle`) can fall back to body sniffing
+    /// instead of panicking on a malformed header.
+    #[must_use]
+    pub fn parse(header: &str) -> Option<Self> {
+        let bytes = header.as_bytes();
+        let mut pos = skip_whitespace(bytes, 0);
+
+        let type_start = pos;
+        while pos < bytes.len() && bytes[pos] != b'/' {
+            pos += 1;
+        }
+        if pos >= bytes.len() {
+            return None;
+        }
+        let r#type = header[type_start..pos].trim().to_ascii_lowercase();
+        if r#type.is_empty() {
+            return None;
+        }
+        pos += 1; // skip '/'
+
+        let subtype_start = pos;
+        while pos < bytes.len() && bytes[pos] != b';' {
+            pos += 1;
+        }
+        let subtype = header[subtype_start..pos].trim().to_ascii_lowercase();
+        if subtype.is_empty() {
+            return None;
+        }
+
+        let mut params = HashMap::new();
+        while pos < bytes.len() {
+            debug_assert_eq!(bytes[pos], b';');
+            pos += 1; // skip ';'
+            pos = skip_whitespace(bytes, pos);
+            if pos >= bytes.len() {
+                break;
+            }
+
+            let key_start = pos;
+            while pos < bytes.len() && bytes[pos] != b'=' && bytes[pos] != b';' {
+                pos += 1;
+            }
+            let key = header[key_start..pos].trim().to_ascii_lowercase();
+            if pos >= bytes.len() || bytes[pos] != b'=' || key.is_empty() {
+                // No value (or a dangling key before the next `;`) -
+                // skip to the next parameter rather than failing the
+                // whole header.
+                while pos < bytes.len() && bytes[pos] != b';' {
+                    pos += 1;
+                }
+                continue;
+            }
+            pos += 1; // skip '='
+
+            let (value, next) = parse_param_value(bytes, pos, header);
+            params.insert(key, value);
+            pos = next;
+        }
+
+        Some(Self {
+            r#type,
+            subtype,
+            params,
+        })
+    }
+
+    /// Whether this header's `type/subtype` matches `mime`
+    /// (e.g. `"application/x-ndjson"`), ignoring any parameters.
+    #[must_use]
+    pub fn is_mime(&self, mime: &str) -> bool {
+        let Some((want_type, want_subtype)) = mime.split_once('/') else {
+            return false;
+        };
+        self.r#type.eq_ignore_ascii_case(want_type)
+            && self.subtype.eq_ignore_ascii_case(want_subtype)
+    }
+
+    /// Looks up a parameter by name, case-insensitively.
+    #[must_use]
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params
+            .get(&key.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
+
+fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+        pos += 1;
+    }
+    pos
+}
+
+/// Parses one parameter value starting at `pos`, either a bare token
+/// (up to the next `;`) or a quoted string (honoring `\"` escapes).
+/// Returns the value and the position just past it.
+fn parse_param_value(bytes: &[u8], pos: usize, header: &str) -> (String, usize) {
+    if bytes.get(pos) == Some(&b'"') {
+        let mut value = String::new();
+        let mut i = pos + 1;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'"' => {
+                    i += 1;
+                    break;
+                }
+                b'\\' if i + 1 < bytes.len() => {
+                    value.push(bytes[i + 1] as char);
+                    i += 2;
+                }
+                b => {
+                    value.push(b as char);
+                    i += 1;
+                }
+            }
+        }
+        (value, i)
+    } else {
+        let start = pos;
+        let mut i = pos;
+        while i < bytes.len() && bytes[i] != b';' {
+            i += 1;
+        }
+        (header[start..i].trim().to_string(), i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bare_mime_type() {
+        let ct = ContentType::parse("application/x-ndjson").unwrap();
+        assert_eq!(ct.r#type, "application");
+        assert_eq!(ct.subtype, "x-ndjson");
+        assert!(ct.params.is_empty());
+    }
+
+    #[test]
+    fn is_mime_ignores_trailing_params() {
+        let ct = ContentType::parse("application/x-ndjson; charset=utf-8").unwrap();
+        assert!(ct.is_mime("application/x-ndjson"));
+        assert_eq!(ct.param("charset"), Some("utf-8"));
+    }
+
+    #[test]
+    fn param_keys_are_case_insensitive() {
+        let ct = ContentType::parse("application/json; Charset=UTF-8").unwrap();
+        assert_eq!(ct.param("charset"), Some("UTF-8"));
+        assert_eq!(ct.param("CHARSET"), Some("UTF-8"));
+    }
+
+    #[test]
+    fn parses_quoted_values_with_escapes() {
+        let header = "application/x-ndjson; profile=\"honeycomb \\\"export\\\"\"";
+        let ct = ContentType::parse(header).unwrap();
+        assert_eq!(ct.param("profile"), Some("honeycomb \"export\""));
+    }
+
+    #[test]
+    fn parses_multiple_params() {
+        let ct = ContentType::parse("application/x-ndjson; profile=honeycomb; version=2").unwrap();
+        assert_eq!(ct.param("profile"), Some("honeycomb"));
+        assert_eq!(ct.param("version"), Some("2"));
+    }
+
+    #[test]
+    fn returns_none_for_headers_without_a_mime_type() {
+        assert!(ContentType::parse("not-a-mime-type").is_none());
+        assert!(ContentType::parse("").is_none());
+    }
+
+    #[test]
+    fn mismatched_mime_is_not_equal() {
+        let ct = ContentType::parse("text/plain").unwrap();
+        assert!(!ct.is_mime("application/json"));
+    }
+}
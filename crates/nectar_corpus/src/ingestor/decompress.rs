@@ -0,0 +1,167 @@
+//! Transparent decompression pre-pass for the ingestor registry.
+//!
+//! Trace payloads are frequently gzip- or zstd-compressed on the wire
+//! (OTLP/HTTP exporters set `Content-Encoding: gzip`), but a
+//! [`TraceIngestor`](crate::ingestor::TraceIngestor) never needs to know
+//! that: [`detect`] recognizes a compressed payload from its magic bytes
+//! or a `Content-Encoding` hint, and [`decompress`] inflates it into a
+//! plain buffer before format detection runs.
+
+use crate::error::{Error, Result};
+
+/// A compression format this layer can transparently unwrap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Compression {
+    /// gzip (magic bytes `1f 8b`).
+    Gzip,
+    /// zstd (magic bytes `28 b5 2f fd`).
+    Zstd,
+}
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Detects whether `header` (or an explicit `Content-Encoding` hint)
+/// indicates a compressed payload.
+///
+/// The `Content-Encoding` hint is checked first, since it reflects what
+/// the sender actually did; the magic-byte sniff is the fallback for
+/// callers that don't have (or don't trust) the header.
+pub(crate) fn detect(header: &[u8], content_encoding: Option<&str>) -> Option<Compression> {
+    if let Some(encoding) = content_encoding {
+        let encoding = encoding.trim().to_ascii_lowercase();
+        if encoding == "gzip" || encoding == "x-gzip" {
+            return Some(Compression::Gzip);
+        }
+        if encoding == "zstd" {
+            return Some(Compression::Zstd);
+        }
+    }
+
+    if header.starts_with(&GZIP_MAGIC) {
+        return Some(Compression::Gzip);
+    }
+    if header.starts_with(&ZSTD_MAGIC) {
+        return Some(Compression::Zstd);
+    }
+
+    None
+}
+
+/// Decompresses `data` as the given `compression` format, refusing to
+/// produce more than `max_output_size` bytes so a malicious or corrupt
+/// payload can't exhaust memory (a decompression bomb).
+#[cfg(feature = "compression")]
+pub(crate) fn decompress(
+    data: &[u8],
+    compression: Compression,
+    max_output_size: usize,
+) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut reader: Box<dyn Read> = match compression {
+        Compression::Gzip => Box::new(flate2::read::GzDecoder::new(data)),
+        Compression::Zstd => Box::new(
+            zstd::stream::Decoder::new(data)
+                .map_err(|e| Error::Decompression(format!("failed to start zstd stream: {e}")))?,
+        ),
+    };
+
+    // Read one byte past the cap so we can tell a payload that decompresses
+    // to exactly the cap apart from one that overflows it.
+    let mut buf = Vec::new();
+    let read = reader
+        .by_ref()
+        .take(max_output_size as u64 + 1)
+        .read_to_end(&mut buf)
+        .map_err(|e| Error::Decompression(format!("failed to inflate payload: {e}")))?;
+
+    if read > max_output_size {
+        return Err(Error::Decompression(format!(
+            "decompressed payload exceeds {max_output_size} byte cap"
+        )));
+    }
+
+    Ok(buf)
+}
+
+/// Stub used when the `compression` feature is disabled: a compressed
+/// payload is detected but can't be decoded, so fail clearly instead of
+/// silently handing raw compressed bytes to an ingestor.
+#[cfg(not(feature = "compression"))]
+pub(crate) fn decompress(
+    _data: &[u8],
+    _compression: Compression,
+    _max_output_size: usize,
+) -> Result<Vec<u8>> {
+    Err(Error::Decompression(
+        "payload is compressed but this build was compiled without the `compression` feature"
+            .to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_gzip_magic_bytes() {
+        assert_eq!(detect(&[0x1f, 0x8b, 0x08, 0x00], None), Some(Compression::Gzip));
+    }
+
+    #[test]
+    fn detects_zstd_magic_bytes() {
+        assert_eq!(
+            detect(&[0x28, 0xb5, 0x2f, 0xfd, 0x00], None),
+            Some(Compression::Zstd)
+        );
+    }
+
+    #[test]
+    fn detects_content_encoding_hint() {
+        assert_eq!(detect(b"not compressed", Some("gzip")), Some(Compression::Gzip));
+        assert_eq!(detect(b"not compressed", Some("zstd")), Some(Compression::Zstd));
+        assert_eq!(detect(b"not compressed", Some("GZIP")), Some(Compression::Gzip));
+    }
+
+    #[test]
+    fn plain_data_is_not_detected_as_compressed() {
+        assert_eq!(detect(b"{\"trace_id\": \"abc\"}", None), None);
+        assert_eq!(detect(b"{\"trace_id\": \"abc\"}", Some("identity")), None);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn decompresses_gzip_round_trip() {
+        use std::io::Write;
+
+        let mut encoder =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"hello world").unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let decompressed = decompress(&compressed, Compression::Gzip, 1024).unwrap();
+        assert_eq!(decompressed, b"hello world");
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn rejects_output_over_the_size_cap() {
+        use std::io::Write;
+
+        let mut encoder =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&vec![b'a'; 1024]).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let result = decompress(&compressed, Compression::Gzip, 16);
+        assert!(matches!(result, Err(Error::Decompression(_))));
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn fails_clearly_without_the_compression_feature() {
+        let result = decompress(b"\x1f\x8b", Compression::Gzip, 1024);
+        assert!(matches!(result, Err(Error::Decompression(_))));
+    }
+}
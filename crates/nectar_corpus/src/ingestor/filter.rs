@@ -0,0 +1,509 @@
+//! `EnvFilter`-style directive filtering for ingested spans.
+//!
+//! Directives are parsed from a comma-separated string, one directive per
+//! top-level comma-separated segment (commas inside `[...]` or `{...}` do
+//! not split):
+//!
+//! ```text
+//! target[span{field=value,field2}]=level
+//! ```
+//!
+//! - `target` matches the span's service name by prefix.
+//! - The optional `[...]` block's leading `span` name matches the span's
+//!   operation name exactly.
+//! - The optional `{...}` block lists field matchers: `field=value`
+//!   requires an attribute equal to that value, a bare `field` requires
+//!   the attribute's presence (any value).
+//! - `level` is the minimum span severity to keep: `off` (drop
+//!   everything matched), `error` (errors only), `ok` (ok and error,
+//!   excluding unset), or `all` (keep regardless of status).
+//!
+//! Every span is evaluated against every directive; the most specific
+//! match (by field-matcher count, plus one each for a target and a span
+//! name) wins, with ties broken in favor of the later directive. A span
+//! matched by no directive is kept, mirroring `tracing-subscriber`'s
+//! `EnvFilter` default-on behavior.
+
+use crate::error::{Error, Result};
+use crate::span::{Span, SpanStatus, StatusCode};
+use crate::trace::Trace;
+use std::collections::{HashMap, HashSet};
+
+/// The minimum span severity a directive's matched spans must meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    /// Drop every span this directive matches.
+    Off,
+    /// Keep only spans with an error status.
+    Error,
+    /// Keep ok and error spans, dropping unset ones.
+    Ok,
+    /// Keep every span regardless of status.
+    All,
+}
+
+impl Level {
+    fn parse(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(Self::Off),
+            "error" => Ok(Self::Error),
+            "ok" => Ok(Self::Ok),
+            "all" => Ok(Self::All),
+            other => Err(Error::parse(
+                "filter",
+                format!("unknown level `{other}` (expected off, error, ok, or all)"),
+            )),
+        }
+    }
+
+    /// Returns true if a span with the given status meets this level.
+    fn allows(self, status: &SpanStatus) -> bool {
+        match self {
+            Self::Off => false,
+            Self::Error => status.code == StatusCode::Error,
+            Self::Ok => status.code != StatusCode::Unset,
+            Self::All => true,
+        }
+    }
+}
+
+/// A field predicate within a directive's `{...}` block.
+#[derive(Debug, Clone)]
+enum FieldMatcher {
+    /// `field=value`: the attribute must exist and equal this value.
+    Equals(String, String),
+    /// A bare `field`: the attribute must exist, with any value.
+    Present(String),
+}
+
+impl FieldMatcher {
+    fn matches(&self, span: &Span) -> bool {
+        match self {
+            Self::Equals(key, value) => span
+                .get_attribute(key)
+                .is_some_and(|attr| &attr.as_string() == value),
+            Self::Present(key) => span.attributes.contains_key(key),
+        }
+    }
+}
+
+/// One parsed directive.
+#[derive(Debug, Clone)]
+struct Directive {
+    /// Service name prefix to match, or `None` to match any service.
+    target: Option<String>,
+    /// Exact span operation name to match, or `None` to match any span.
+    span: Option<String>,
+    fields: Vec<FieldMatcher>,
+    level: Level,
+    /// Precomputed so the most specific matching directive can be picked
+    /// without re-deriving it on every span.
+    specificity: u32,
+}
+
+impl Directive {
+    fn matches(&self, span: &Span) -> bool {
+        if let Some(target) = &self.target {
+            if !span.service.starts_with(target.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some(name) = &self.span {
+            if &span.name != name {
+                return false;
+            }
+        }
+
+        self.fields.iter().all(|field| field.matches(span))
+    }
+}
+
+/// A parsed set of `EnvFilter`-style directives for filtering spans by
+/// service, operation name, attributes, and status severity.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SpanFilter {
+    directives: Vec<Directive>,
+}
+
+impl SpanFilter {
+    /// Parses a comma-separated directive string.
+    ///
+    /// An empty (or all-whitespace) string parses to a filter that keeps
+    /// every span.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any directive is malformed.
+    pub(crate) fn parse(directives: &str) -> Result<Self> {
+        // Split on top-level commas only: a `{field=value,field2}` block
+        // carries commas of its own.
+        let trimmed = directives.trim();
+        let mut segments = Vec::new();
+        let (mut depth, mut start) = (0i32, 0);
+        for (i, c) in trimmed.char_indices() {
+            match c {
+                '[' | '{' => depth += 1,
+                ']' | '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    segments.push(&trimmed[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        segments.push(&trimmed[start..]);
+        let directives = segments
+            .into_iter()
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+            .map(parse_directive)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { directives })
+    }
+
+    /// Returns true if the span should be kept.
+    ///
+    /// Evaluates every directive, keeps the most specific match (later
+    /// directives win ties), and defaults to keep if nothing matches.
+    fn keep_span(&self, span: &Span) -> bool {
+        let best = self
+            .directives
+            .iter()
+            .enumerate()
+            .filter(|(_, directive)| directive.matches(span))
+            .max_by_key(|(index, directive)| (directive.specificity, *index));
+
+        match best {
+            Some((_, directive)) => directive.level.allows(&span.status),
+            None => true,
+        }
+    }
+}
+
+fn parse_directive(segment: &str) -> Result<Directive> {
+    let eq_pos = top_level_rposition(segment, '=').ok_or_else(|| {
+        Error::parse(
+            "filter",
+            format!("directive `{segment}` is missing a `=level` suffix"),
+        )
+    })?;
+
+    let selector = segment[..eq_pos].trim();
+    let level = Level::parse(&segment[eq_pos + 1..])?;
+
+    let (target, bracket) = match selector.find('[') {
+        Some(open) => {
+            let close = selector.rfind(']').filter(|&c| c > open).ok_or_else(|| {
+                Error::parse("filter", format!("directive `{segment}` has an unclosed `[`"))
+            })?;
+            if close != selector.len() - 1 {
+                return Err(Error::parse(
+                    "filter",
+                    format!("directive `{segment}` has trailing characters after `]`"),
+                ));
+            }
+            let target = selector[..open].trim();
+            (
+                (!target.is_empty()).then(|| target.to_string()),
+                Some(&selector[open + 1..close]),
+            )
+        }
+        None => (
+            (!selector.is_empty()).then(|| selector.to_string()),
+            None,
+        ),
+    };
+
+    let (span, fields) = match bracket {
+        Some(inner) => match inner.find('{') {
+            Some(open) => {
+                let close = inner.rfind('}').filter(|&c| c > open).ok_or_else(|| {
+                    Error::parse("filter", format!("directive `{segment}` has an unclosed `{{`"))
+                })?;
+                if close != inner.len() - 1 {
+                    return Err(Error::parse(
+                        "filter",
+                        format!("directive `{segment}` has trailing characters after `}}`"),
+                    ));
+                }
+                let span_name = inner[..open].trim();
+                (
+                    (!span_name.is_empty()).then(|| span_name.to_string()),
+                    parse_fields(&inner[open + 1..close], segment)?,
+                )
+            }
+            None => {
+                let span_name = inner.trim();
+                (
+                    (!span_name.is_empty()).then(|| span_name.to_string()),
+                    Vec::new(),
+                )
+            }
+        },
+        None => (None, Vec::new()),
+    };
+
+    let specificity = u32::try_from(fields.len()).unwrap_or(u32::MAX)
+        + u32::from(span.is_some())
+        + u32::from(target.is_some());
+
+    Ok(Directive {
+        target,
+        span,
+        fields,
+        level,
+        specificity,
+    })
+}
+
+fn parse_fields(fields_str: &str, segment: &str) -> Result<Vec<FieldMatcher>> {
+    fields_str
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            field.split_once('=').map_or_else(
+                || Ok(FieldMatcher::Present(field.to_string())),
+                |(key, value)| {
+                    let key = key.trim();
+                    let value = value.trim();
+                    if key.is_empty() {
+                        return Err(Error::parse(
+                            "filter",
+                            format!("directive `{segment}` has a field matcher with an empty key"),
+                        ));
+                    }
+                    Ok(FieldMatcher::Equals(key.to_string(), value.to_string()))
+                },
+            )
+        })
+        .collect()
+}
+
+/// Finds the last occurrence of `needle` that isn't nested inside `[...]`
+/// or `{...}`, so a directive's trailing `=level` can be split off even
+/// when field values themselves contain `=`.
+fn top_level_rposition(s: &str, needle: char) -> Option<usize> {
+    let mut depth = 0i32;
+    let mut found = None;
+    for (i, c) in s.char_indices() {
+        match c {
+            '[' | '{' => depth += 1,
+            ']' | '}' => depth -= 1,
+            c if c == needle && depth == 0 => found = Some(i),
+            _ => {}
+        }
+    }
+    found
+}
+
+/// Applies a filter to a batch of traces, dropping spans the filter
+/// rejects and re-parenting any surviving children of a dropped span to
+/// its nearest surviving ancestor (or making them roots, if none
+/// survived).
+pub(crate) fn apply_to_traces(filter: &SpanFilter, traces: Vec<Trace>) -> Vec<Trace> {
+    traces
+        .into_iter()
+        .map(|trace| apply_to_trace(filter, trace))
+        .collect()
+}
+
+fn apply_to_trace(filter: &SpanFilter, mut trace: Trace) -> Trace {
+    if trace.spans.is_empty() {
+        return trace;
+    }
+
+    let parents: HashMap<&str, Option<&str>> = trace
+        .spans
+        .iter()
+        .map(|s| (s.span_id.as_str(), s.parent_span_id.as_deref()))
+        .collect();
+
+    let kept: HashSet<&str> = trace
+        .spans
+        .iter()
+        .filter(|s| filter.keep_span(s))
+        .map(|s| s.span_id.as_str())
+        .collect();
+
+    let spans = std::mem::take(&mut trace.spans)
+        .into_iter()
+        .filter(|s| kept.contains(s.span_id.as_str()))
+        .map(|mut span| {
+            if let Some(parent) = span.parent_span_id.as_deref() {
+                span.parent_span_id = nearest_surviving_ancestor(&parents, parent, &kept);
+            }
+            span
+        })
+        .collect::<Vec<_>>();
+
+    trace.spans = spans;
+    trace.span_count = trace.spans.len();
+    trace.compute_summary_from_spans();
+    trace
+}
+
+/// Walks up the original (pre-filter) parent chain from `start` until it
+/// finds an ancestor that survived filtering, returning `None` if the
+/// chain reaches a root (or a cycle) without one.
+fn nearest_surviving_ancestor(
+    parents: &HashMap<&str, Option<&str>>,
+    start: &str,
+    kept: &HashSet<&str>,
+) -> Option<String> {
+    let mut current = start;
+    let mut visited = HashSet::new();
+
+    loop {
+        if kept.contains(current) {
+            return Some(current.to_string());
+        }
+        if !visited.insert(current) {
+            return None;
+        }
+        current = parents.get(current).copied().flatten()?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(id: &str, parent: Option<&str>, service: &str, name: &str) -> Span {
+        let mut span = Span::new(id, name).with_service(service);
+        if let Some(p) = parent {
+            span = span.with_parent(p);
+        }
+        span
+    }
+
+    #[test]
+    fn empty_directive_string_keeps_everything() {
+        let filter = SpanFilter::parse("").unwrap();
+        let span = span("s1", None, "api", "GET /users").with_status(SpanStatus::error("boom"));
+        assert!(filter.keep_span(&span));
+    }
+
+    #[test]
+    fn target_prefix_matches_service() {
+        let filter = SpanFilter::parse("api=error").unwrap();
+        let matching = span("s1", None, "api-gateway", "GET /users");
+        let not_matching = span("s2", None, "db", "query");
+
+        assert!(!filter.keep_span(&matching)); // error level, status unset -> dropped
+        assert!(filter.keep_span(&not_matching)); // no directive matches -> default keep
+    }
+
+    #[test]
+    fn off_level_drops_matched_spans() {
+        let filter = SpanFilter::parse("db=off").unwrap();
+        let span = span("s1", None, "db", "query").with_status(SpanStatus::error("boom"));
+        assert!(!filter.keep_span(&span));
+    }
+
+    #[test]
+    fn error_level_keeps_only_error_status() {
+        let filter = SpanFilter::parse("api=error").unwrap();
+        let ok_span = span("s1", None, "api", "h").with_status(SpanStatus::ok());
+        let err_span = span("s2", None, "api", "h").with_status(SpanStatus::error("boom"));
+
+        assert!(!filter.keep_span(&ok_span));
+        assert!(filter.keep_span(&err_span));
+    }
+
+    #[test]
+    fn ok_level_excludes_unset_but_keeps_ok_and_error() {
+        let filter = SpanFilter::parse("api=ok").unwrap();
+        let unset_span = span("s1", None, "api", "h");
+        let ok_span = span("s2", None, "api", "h").with_status(SpanStatus::ok());
+        let err_span = span("s3", None, "api", "h").with_status(SpanStatus::error("boom"));
+
+        assert!(!filter.keep_span(&unset_span));
+        assert!(filter.keep_span(&ok_span));
+        assert!(filter.keep_span(&err_span));
+    }
+
+    #[test]
+    fn span_name_must_match_exactly() {
+        let filter = SpanFilter::parse("api[GET /users]=off").unwrap();
+        let matching = span("s1", None, "api", "GET /users");
+        let other = span("s2", None, "api", "POST /users");
+
+        assert!(!filter.keep_span(&matching));
+        assert!(filter.keep_span(&other));
+    }
+
+    #[test]
+    fn field_equals_and_presence_matchers() {
+        let filter = SpanFilter::parse("api[{db.system=postgres,slow}]=off").unwrap();
+
+        let matches = span("s1", None, "api", "q")
+            .with_attribute("db.system", "postgres")
+            .with_attribute("slow", true);
+        let wrong_value = span("s2", None, "api", "q").with_attribute("db.system", "mysql");
+        let missing_presence = span("s3", None, "api", "q").with_attribute("db.system", "postgres");
+
+        assert!(!filter.keep_span(&matches));
+        assert!(filter.keep_span(&wrong_value));
+        assert!(filter.keep_span(&missing_presence));
+    }
+
+    #[test]
+    fn more_specific_directive_wins() {
+        // Generic directive keeps errors for the whole service; a more
+        // specific one turns a noisy health-check span off entirely.
+        let filter = SpanFilter::parse("api=error,api[GET /health]=off").unwrap();
+        let health_error = span("s1", None, "api", "GET /health").with_status(SpanStatus::error("x"));
+        let other_error = span("s2", None, "api", "GET /users").with_status(SpanStatus::error("x"));
+
+        assert!(!filter.keep_span(&health_error));
+        assert!(filter.keep_span(&other_error));
+    }
+
+    #[test]
+    fn rejects_directive_without_level() {
+        assert!(SpanFilter::parse("api").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_level() {
+        assert!(SpanFilter::parse("api=verbose").is_err());
+    }
+
+    #[test]
+    fn apply_to_traces_drops_spans_and_reparents_survivors() {
+        let filter = SpanFilter::parse("db=off").unwrap();
+
+        let spans = vec![
+            span("root", None, "api", "GET /users"),
+            span("mid", Some("root"), "db", "query"),
+            span("leaf", Some("mid"), "api", "serialize"),
+        ];
+        let trace = Trace::from_spans("t1", spans);
+
+        let filtered = apply_to_trace(&filter, trace);
+        let ids: Vec<_> = filtered.spans.iter().map(|s| s.span_id.as_str()).collect();
+        assert_eq!(ids, vec!["root", "leaf"]);
+
+        let leaf = filtered.spans.iter().find(|s| s.span_id == "leaf").unwrap();
+        assert_eq!(leaf.parent_span_id.as_deref(), Some("root"));
+        assert_eq!(filtered.span_count, 2);
+    }
+
+    #[test]
+    fn apply_to_traces_makes_orphan_a_root_when_no_ancestor_survives() {
+        let filter = SpanFilter::parse("db=off").unwrap();
+
+        let spans = vec![
+            span("root", None, "db", "connect"),
+            span("child", Some("root"), "api", "handle"),
+        ];
+        let trace = Trace::from_spans("t1", spans);
+
+        let filtered = apply_to_trace(&filter, trace);
+        assert_eq!(filtered.spans.len(), 1);
+        assert!(filtered.spans[0].is_root());
+    }
+}
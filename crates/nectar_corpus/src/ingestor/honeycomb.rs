@@ -4,11 +4,12 @@
 //! `trace.trace_id`, `trace.span_id`, and `trace.parent_id` fields.
 
 use crate::error::{Error, Result};
-use crate::ingestor::TraceIngestor;
+use crate::ingestor::{ContentType, Detection, IngestReport, SkipEntry, TraceIngestor};
 use crate::span::{AttributeValue, Span, SpanKind, SpanStatus, StatusCode};
 use crate::trace::Trace;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read};
 use std::time::Duration;
 
 /// Ingestor for Honeycomb NDJSON export format.
@@ -29,8 +30,8 @@ impl TraceIngestor for HoneycombIngestor {
 
     fn can_handle(&self, header: &[u8], content_type: Option<&str>) -> bool {
         // Check content-type
-        if let Some(ct) = content_type {
-            if ct.contains("application/x-ndjson") || ct.contains("application/x-honeycomb") {
+        if let Some(ct) = content_type.and_then(ContentType::parse) {
+            if ct.is_mime("application/x-ndjson") || ct.is_mime("application/x-honeycomb") {
                 return true;
             }
         }
@@ -56,6 +57,44 @@ impl TraceIngestor for HoneycombIngestor {
             || (line_str.contains("\"trace_id\"") && line_str.contains("\"span_id\""))
     }
 
+    fn detect(&self, header: &[u8], content_type: Option<&str>) -> Detection {
+        if let Some(ct) = content_type.and_then(ContentType::parse) {
+            if ct.is_mime("application/x-honeycomb") {
+                return Detection::Certain;
+            }
+            if ct.is_mime("application/x-ndjson") {
+                // A `profile=honeycomb` parameter distinguishes an
+                // export explicitly tagged as Honeycomb's from generic
+                // NDJSON that merely happens to match.
+                return if ct.param("profile") == Some("honeycomb") {
+                    Detection::Certain
+                } else {
+                    Detection::Likely(100)
+                };
+            }
+        }
+
+        let first_line = get_first_line(header);
+        if first_line.is_empty() || first_line[0] != b'{' {
+            return Detection::No;
+        }
+
+        let Ok(line_str) = std::str::from_utf8(first_line) else {
+            return Detection::No;
+        };
+
+        // The namespaced `trace.*` fields are Honeycomb-specific, so
+        // they're a much stronger signal than a bare `trace_id`/`span_id`
+        // pair, which plain JSON trace exports could plausibly use too.
+        if line_str.contains("\"trace.trace_id\"") || line_str.contains("\"trace.span_id\"") {
+            Detection::Likely(200)
+        } else if line_str.contains("\"trace_id\"") && line_str.contains("\"span_id\"") {
+            Detection::Likely(150)
+        } else {
+            Detection::No
+        }
+    }
+
     fn ingest(&self, data: &[u8]) -> Result<Vec<Trace>> {
         let text = std::str::from_utf8(data)
             .map_err(|e| Error::parse("honeycomb", format!("invalid UTF-8: {e}")))?;
@@ -85,12 +124,170 @@ impl TraceIngestor for HoneycombIngestor {
         // Convert grouped spans to traces
         let mut traces = Vec::with_capacity(traces_map.len());
         for (trace_id, raw_spans) in traces_map {
-            let spans: Vec<Span> = raw_spans.into_iter().map(RawSpan::into_span).collect();
-            traces.push(Trace::from_spans(trace_id, spans));
+            traces.push(build_trace(trace_id, raw_spans));
         }
 
         Ok(traces)
     }
+
+    fn ingest_stream(
+        &self,
+        reader: Box<dyn Read>,
+    ) -> Result<Box<dyn Iterator<Item = Result<Trace>>>> {
+        Ok(Box::new(HoneycombStreamIter {
+            lines: BufReader::new(reader).lines(),
+            open: HashMap::new(),
+            order: VecDeque::new(),
+            pending: VecDeque::new(),
+        }))
+    }
+
+    fn ingest_with_report(&self, data: &[u8]) -> Result<(Vec<Trace>, IngestReport)> {
+        let text = std::str::from_utf8(data)
+            .map_err(|e| Error::parse("honeycomb", format!("invalid UTF-8: {e}")))?;
+
+        let mut traces_map: HashMap<String, Vec<RawSpan>> = HashMap::new();
+        let mut report = IngestReport::default();
+
+        for (line_num, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            report.total_lines += 1;
+
+            match parse_honeycomb_span(line) {
+                Ok(span) => {
+                    traces_map
+                        .entry(span.trace_id.clone())
+                        .or_default()
+                        .push(span);
+                    report.parsed += 1;
+                }
+                Err(error) => {
+                    report.skipped.push(SkipEntry {
+                        line_number: line_num + 1,
+                        error,
+                    });
+                }
+            }
+        }
+
+        let mut traces = Vec::with_capacity(traces_map.len());
+        for (trace_id, raw_spans) in traces_map {
+            traces.push(build_trace(trace_id, raw_spans));
+        }
+
+        Ok((traces, report))
+    }
+}
+
+/// Converts a group of a trace's raw spans into a [`Trace`].
+fn build_trace(trace_id: String, raw_spans: Vec<RawSpan>) -> Trace {
+    let spans: Vec<Span> = raw_spans.into_iter().map(RawSpan::into_span).collect();
+    Trace::from_spans(trace_id, spans)
+}
+
+/// Capacity of [`HoneycombStreamIter`]'s open-trace LRU: how many distinct
+/// trace groups it keeps buffered at once before evicting the
+/// least-recently-touched one, so a file with many trace ids can't grow
+/// memory unboundedly.
+const MAX_OPEN_TRACE_GROUPS: usize = 256;
+
+/// Lazily groups Honeycomb NDJSON spans into traces as they're read,
+/// without buffering the whole export in memory.
+///
+/// Buffers each currently-open trace id's spans in an LRU keyed by
+/// `trace_id`: every span moves its trace to the most-recently-touched
+/// end, and once more than [`MAX_OPEN_TRACE_GROUPS`] are open at once, the
+/// least-recently-touched group is flushed - even if more of its spans
+/// could in principle still arrive.
+///
+/// This gives a "trace-contiguous" fast path for Honeycomb's common
+/// export shape, where one trace's spans are emitted together: a finished
+/// trace is the least-recently-touched entry the moment
+/// [`MAX_OPEN_TRACE_GROUPS`] other distinct trace ids have started, so it
+/// flushes with a bounded lag rather than only at end of stream. Spans
+/// genuinely interleaved across many trace ids still group correctly as
+/// long as each id is touched at least once per `MAX_OPEN_TRACE_GROUPS`
+/// other ids; past that, a trace may be flushed early and reappear later
+/// as a second, separate `Trace` for the same `trace_id`.
+struct HoneycombStreamIter<R: BufRead> {
+    lines: std::io::Lines<R>,
+    /// Spans buffered so far for each currently-open trace id.
+    open: HashMap<String, Vec<RawSpan>>,
+    /// Open trace ids ordered least- to most-recently touched.
+    order: VecDeque<String>,
+    /// Traces that are complete and ready to be yielded.
+    pending: VecDeque<Trace>,
+}
+
+impl<R: BufRead> HoneycombStreamIter<R> {
+    /// Marks `trace_id` as the most-recently-touched open group.
+    fn touch(&mut self, trace_id: &str) {
+        if let Some(pos) = self.order.iter().position(|id| id == trace_id) {
+            self.order.remove(pos);
+        }
+        self.order.push_back(trace_id.to_string());
+    }
+
+    /// Flushes the least-recently-touched open group into `pending`.
+    fn evict_oldest(&mut self) {
+        if let Some(trace_id) = self.order.pop_front() {
+            if let Some(spans) = self.open.remove(&trace_id) {
+                self.pending.push_back(build_trace(trace_id, spans));
+            }
+        }
+    }
+
+    /// Flushes every remaining open group, least-recently-touched first.
+    fn flush_all(&mut self) {
+        while !self.order.is_empty() {
+            self.evict_oldest();
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for HoneycombStreamIter<R> {
+    type Item = Result<Trace>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(trace) = self.pending.pop_front() {
+                return Some(Ok(trace));
+            }
+
+            let Some(line) = self.lines.next() else {
+                self.flush_all();
+                return self.pending.pop_front().map(Ok);
+            };
+
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(Error::Io(e))),
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let span = match parse_honeycomb_span(line) {
+                Ok(span) => span,
+                Err(e) => {
+                    tracing::warn!("Skipping invalid span: {e}");
+                    continue;
+                }
+            };
+
+            let trace_id = span.trace_id.clone();
+            self.touch(&trace_id);
+            self.open.entry(trace_id).or_default().push(span);
+
+            while self.order.len() > MAX_OPEN_TRACE_GROUPS {
+                self.evict_oldest();
+            }
+        }
+    }
 }
 
 /// Raw span data parsed from Honeycomb JSON.
@@ -106,6 +303,7 @@ struct RawSpan {
     status_message: Option<String>,
     is_error: bool,
     span_kind: Option<i32>,
+    sample_rate: Option<u32>,
     attributes: HashMap<String, AttributeValue>,
 }
 
@@ -139,6 +337,8 @@ impl RawSpan {
             span = span.with_kind(SpanKind::from_otlp(kind));
         }
 
+        span = span.with_sample_rate(self.sample_rate.unwrap_or(1));
+
         // Add attributes
         for (key, value) in self.attributes {
             span = span.with_attribute(key, value);
@@ -169,16 +369,15 @@ fn parse_honeycomb_span(line: &str) -> Result<RawSpan> {
     let parent_id = get_string_field(obj, &["trace.parent_id", "parent_id"]);
 
     // Extract service name
-    let service = get_string_field(obj, &["service.name", "service_name", "service"])
-        .unwrap_or_default();
+    let service =
+        get_string_field(obj, &["service.name", "service_name", "service"]).unwrap_or_default();
 
     // Extract operation name
     let name = get_string_field(obj, &["name", "operation", "span.name"])
         .unwrap_or_else(|| "unknown".to_string());
 
     // Extract duration
-    let duration_ms = get_number_field(obj, &["duration_ms", "duration"])
-        .unwrap_or(0.0);
+    let duration_ms = get_number_field(obj, &["duration_ms", "duration"]).unwrap_or(0.0);
 
     // Extract start time
     let start_time_ms = get_number_field(obj, &["timestamp_ms", "start_time_ms", "time"]);
@@ -190,26 +389,34 @@ fn parse_honeycomb_span(line: &str) -> Result<RawSpan> {
 
     // Extract status
     #[allow(clippy::cast_possible_truncation)]
-    let status_code = get_number_field(obj, &["status.code", "status_code"])
-        .map(|n| n as i32);
+    let status_code = get_number_field(obj, &["status.code", "status_code"]).map(|n| n as i32);
     let status_message = get_string_field(obj, &["status.message", "status_message"]);
 
     // Extract span kind
     #[allow(clippy::cast_possible_truncation)]
-    let span_kind = get_number_field(obj, &["span.kind", "kind"])
-        .map(|n| n as i32);
-
-    // Extract remaining attributes
+    let span_kind = get_number_field(obj, &["span.kind", "kind"]).map(|n| n as i32);
+
+    // Extract sample rate: a retained span statistically represents this
+    // many real events. Zero/negative rates are dropped to `None` here,
+    // like a missing field; `RawSpan::into_span` turns `None` into the
+    // default weight of 1.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let sample_rate = get_number_field(obj, &["sample_rate", "meta.sample_rate"])
+        .map(|n| n as i32)
+        .filter(|&n| n > 0)
+        .map(|n| n as u32);
+
+    // Extract remaining attributes, flattening nested objects/arrays into
+    // dotted keys. `is_known_field` is only consulted at the top level, so
+    // a nested field that happens to share a name with a known field (e.g.
+    // `http.request.trace_id`) isn't accidentally dropped.
     let mut attributes = HashMap::new();
     for (key, value) in obj {
-        // Skip fields we've already processed
         if is_known_field(key) {
             continue;
         }
 
-        if let Some(attr_val) = json_to_attribute(value) {
-            attributes.insert(key.clone(), attr_val);
-        }
+        flatten_attribute(key, value, &mut attributes);
     }
 
     Ok(RawSpan {
@@ -224,6 +431,7 @@ fn parse_honeycomb_span(line: &str) -> Result<RawSpan> {
         status_message,
         is_error,
         span_kind,
+        sample_rate,
         attributes,
     })
 }
@@ -313,6 +521,8 @@ fn is_known_field(key: &str) -> bool {
             | "status_message"
             | "span.kind"
             | "kind"
+            | "sample_rate"
+            | "meta.sample_rate"
     )
 }
 
@@ -340,6 +550,35 @@ fn json_to_attribute(value: &Value) -> Option<AttributeValue> {
     }
 }
 
+/// Recursively flattens a JSON value into `attributes`, keyed under
+/// `prefix` and dotted per nested level to match Honeycomb's own
+/// convention (e.g. `http.request.method`). An array of objects flattens
+/// with an index segment (`items.0.id`); an array of scalars is left to
+/// `json_to_attribute`'s existing `StringArray` handling.
+fn flatten_attribute(
+    prefix: &str,
+    value: &Value,
+    attributes: &mut HashMap<String, AttributeValue>,
+) {
+    match value {
+        Value::Object(map) => {
+            for (key, nested) in map {
+                flatten_attribute(&format!("{prefix}.{key}"), nested, attributes);
+            }
+        }
+        Value::Array(arr) if arr.iter().any(Value::is_object) => {
+            for (index, item) in arr.iter().enumerate() {
+                flatten_attribute(&format!("{prefix}.{index}"), item, attributes);
+            }
+        }
+        _ => {
+            if let Some(attr_val) = json_to_attribute(value) {
+                attributes.insert(prefix.to_string(), attr_val);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -363,6 +602,38 @@ mod tests {
         assert!(ingestor.can_handle(b"{}", Some("application/x-ndjson")));
     }
 
+    #[test]
+    fn honeycomb_ingestor_detect_outranks_plain_json_on_trace_fields() {
+        let ingestor = HoneycombIngestor;
+        let data = br#"{"trace.trace_id":"abc","trace.span_id":"123"}"#;
+
+        assert_eq!(ingestor.detect(data, None), Detection::Likely(200));
+        assert_eq!(
+            ingestor.detect(b"{}", Some("application/x-honeycomb")),
+            Detection::Certain
+        );
+        assert_eq!(ingestor.detect(b"[1,2,3]", None), Detection::No);
+    }
+
+    #[test]
+    fn honeycomb_ingestor_can_handle_ndjson_with_trailing_params() {
+        let ingestor = HoneycombIngestor;
+        assert!(ingestor.can_handle(b"{}", Some("application/x-ndjson; charset=utf-8")));
+    }
+
+    #[test]
+    fn honeycomb_ingestor_detect_is_certain_on_honeycomb_profile_param() {
+        let ingestor = HoneycombIngestor;
+        assert_eq!(
+            ingestor.detect(b"{}", Some("application/x-ndjson; profile=honeycomb")),
+            Detection::Certain
+        );
+        assert_eq!(
+            ingestor.detect(b"{}", Some("application/x-ndjson; profile=generic")),
+            Detection::Likely(100)
+        );
+    }
+
     #[test]
     fn honeycomb_ingestor_rejects_plain_json_array() {
         let ingestor = HoneycombIngestor;
@@ -434,9 +705,109 @@ mod tests {
             span.get_attribute("custom.field"),
             Some(&AttributeValue::String("value".to_string()))
         );
+        assert_eq!(span.get_attribute("count"), Some(&AttributeValue::Int(42)));
+    }
+
+    #[test]
+    fn parse_span_reads_sample_rate() {
+        let line =
+            r#"{"trace_id":"t1","span_id":"s1","name":"op","duration_ms":1,"sample_rate":50}"#;
+        let span = parse_honeycomb_span(line).unwrap();
+        assert_eq!(span.sample_rate, Some(50));
+        assert_eq!(span.into_span().effective_weight(), 50);
+    }
+
+    #[test]
+    fn parse_span_reads_nested_meta_sample_rate() {
+        let line =
+            r#"{"trace_id":"t1","span_id":"s1","name":"op","duration_ms":1,"meta.sample_rate":10}"#;
+        let span = parse_honeycomb_span(line).unwrap();
+        assert_eq!(span.sample_rate, Some(10));
+    }
+
+    #[test]
+    fn parse_span_clamps_missing_or_nonpositive_sample_rate_to_one() {
+        let missing =
+            parse_honeycomb_span(r#"{"trace_id":"t1","span_id":"s1","name":"op","duration_ms":1}"#)
+                .unwrap();
+        assert_eq!(missing.sample_rate, None);
+        assert_eq!(missing.into_span().effective_weight(), 1);
+
+        let zero = parse_honeycomb_span(
+            r#"{"trace_id":"t1","span_id":"s1","name":"op","duration_ms":1,"sample_rate":0}"#,
+        )
+        .unwrap();
+        assert_eq!(zero.sample_rate, None);
+
+        let negative = parse_honeycomb_span(
+            r#"{"trace_id":"t1","span_id":"s1","name":"op","duration_ms":1,"sample_rate":-5}"#,
+        )
+        .unwrap();
+        assert_eq!(negative.sample_rate, None);
+    }
+
+    #[test]
+    fn sample_rate_does_not_leak_into_attributes() {
+        let line = r#"{"trace_id":"t1","span_id":"s1","name":"op","duration_ms":1,"sample_rate":50,"meta.sample_rate":50}"#;
+        let span = parse_honeycomb_span(line).unwrap();
+        assert!(span.attributes.is_empty());
+    }
+
+    #[test]
+    fn parse_span_flattens_nested_objects_into_dotted_keys() {
+        let line = r#"{"trace_id":"t1","span_id":"s1","name":"op","duration_ms":1,"http":{"request":{"method":"GET","status":200}}}"#;
+        let span = parse_honeycomb_span(line).unwrap();
+
+        assert_eq!(
+            span.attributes.get("http.request.method"),
+            Some(&AttributeValue::String("GET".to_string()))
+        );
         assert_eq!(
-            span.get_attribute("count"),
-            Some(&AttributeValue::Int(42))
+            span.attributes.get("http.request.status"),
+            Some(&AttributeValue::Int(200))
+        );
+        assert!(!span.attributes.contains_key("http"));
+        assert!(!span.attributes.contains_key("http.request"));
+    }
+
+    #[test]
+    fn parse_span_flattens_arrays_of_objects_with_index_segments() {
+        let line = r#"{"trace_id":"t1","span_id":"s1","name":"op","duration_ms":1,"items":[{"id":"a"},{"id":"b"}]}"#;
+        let span = parse_honeycomb_span(line).unwrap();
+
+        assert_eq!(
+            span.attributes.get("items.0.id"),
+            Some(&AttributeValue::String("a".to_string()))
+        );
+        assert_eq!(
+            span.attributes.get("items.1.id"),
+            Some(&AttributeValue::String("b".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_span_keeps_scalar_arrays_as_string_array() {
+        let line =
+            r#"{"trace_id":"t1","span_id":"s1","name":"op","duration_ms":1,"tags":["a","b"]}"#;
+        let span = parse_honeycomb_span(line).unwrap();
+
+        assert_eq!(
+            span.attributes.get("tags"),
+            Some(&AttributeValue::StringArray(vec![
+                "a".to_string(),
+                "b".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_span_nested_field_lookalike_is_not_treated_as_known() {
+        let line = r#"{"trace_id":"t1","span_id":"s1","name":"op","duration_ms":1,"http":{"trace_id":"nested-value"}}"#;
+        let span = parse_honeycomb_span(line).unwrap();
+
+        assert_eq!(
+            span.attributes.get("http.trace_id"),
+            Some(&AttributeValue::String("nested-value".to_string()))
         );
     }
 
@@ -452,4 +823,154 @@ mod tests {
         assert_eq!(span.service, "svc");
         assert_eq!(span.duration_ms, 100.0);
     }
+
+    #[test]
+    fn ingest_stream_groups_contiguous_spans_by_trace_id() {
+        let ingestor = HoneycombIngestor;
+        let data = b"{\"trace.trace_id\":\"t1\",\"trace.span_id\":\"s1\",\"name\":\"a\",\"duration_ms\":10}\n\
+{\"trace.trace_id\":\"t1\",\"trace.span_id\":\"s2\",\"trace.parent_id\":\"s1\",\"name\":\"b\",\"duration_ms\":5}\n\
+{\"trace.trace_id\":\"t2\",\"trace.span_id\":\"s3\",\"name\":\"c\",\"duration_ms\":1}\n";
+        let reader: Box<dyn Read> = Box::new(std::io::Cursor::new(data.to_vec()));
+
+        let traces: Vec<_> = ingestor
+            .ingest_stream(reader)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].trace_id, "t1");
+        assert_eq!(traces[0].span_count, 2);
+        assert_eq!(traces[1].trace_id, "t2");
+        assert_eq!(traces[1].span_count, 1);
+    }
+
+    #[test]
+    fn ingest_stream_groups_interleaved_spans_across_trace_ids() {
+        let ingestor = HoneycombIngestor;
+        let data = b"{\"trace.trace_id\":\"t1\",\"trace.span_id\":\"s1\",\"name\":\"a\",\"duration_ms\":10}\n\
+{\"trace.trace_id\":\"t2\",\"trace.span_id\":\"s2\",\"name\":\"b\",\"duration_ms\":5}\n\
+{\"trace.trace_id\":\"t1\",\"trace.span_id\":\"s3\",\"trace.parent_id\":\"s1\",\"name\":\"c\",\"duration_ms\":1}\n";
+        let reader: Box<dyn Read> = Box::new(std::io::Cursor::new(data.to_vec()));
+
+        let mut traces: Vec<_> = ingestor
+            .ingest_stream(reader)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        traces.sort_by(|a, b| a.trace_id.cmp(&b.trace_id));
+
+        assert_eq!(traces.len(), 2);
+        let t1 = traces.iter().find(|t| t.trace_id == "t1").unwrap();
+        assert_eq!(t1.span_count, 2);
+        let t2 = traces.iter().find(|t| t.trace_id == "t2").unwrap();
+        assert_eq!(t2.span_count, 1);
+    }
+
+    #[test]
+    fn ingest_stream_skips_malformed_lines() {
+        let ingestor = HoneycombIngestor;
+        let data = b"{\"trace.trace_id\":\"t1\",\"trace.span_id\":\"s1\",\"name\":\"a\",\"duration_ms\":10}\n\
+not valid json\n\
+{\"trace.trace_id\":\"t2\",\"trace.span_id\":\"s2\",\"name\":\"b\",\"duration_ms\":5}\n";
+        let reader: Box<dyn Read> = Box::new(std::io::Cursor::new(data.to_vec()));
+
+        let traces: Vec<_> = ingestor
+            .ingest_stream(reader)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(traces.len(), 2);
+    }
+
+    #[test]
+    fn ingest_stream_flushes_oldest_group_once_open_bound_exceeded() {
+        let ingestor = HoneycombIngestor;
+        let mut data = String::new();
+        // Open MAX_OPEN_TRACE_GROUPS + 1 distinct trace ids, one span each,
+        // so the LRU must evict the least-recently-touched (t0) before
+        // the stream ends.
+        for i in 0..=MAX_OPEN_TRACE_GROUPS {
+            data.push_str(&format!(
+                "{{\"trace.trace_id\":\"t{i}\",\"trace.span_id\":\"s{i}\",\"name\":\"op\",\"duration_ms\":1}}\n"
+            ));
+        }
+        let reader: Box<dyn Read> = Box::new(std::io::Cursor::new(data.into_bytes()));
+
+        let mut iter = ingestor.ingest_stream(reader).unwrap();
+        // The oldest group (t0) should flush as soon as the bound is
+        // exceeded, rather than only once the stream ends.
+        let first = iter.next().unwrap().unwrap();
+        assert_eq!(first.trace_id, "t0");
+    }
+
+    #[test]
+    fn ingest_with_report_counts_clean_lines() {
+        let ingestor = HoneycombIngestor;
+        let data = b"{\"trace.trace_id\":\"t1\",\"trace.span_id\":\"s1\",\"name\":\"a\",\"duration_ms\":10}\n\
+{\"trace.trace_id\":\"t1\",\"trace.span_id\":\"s2\",\"trace.parent_id\":\"s1\",\"name\":\"b\",\"duration_ms\":5}\n";
+
+        let (traces, report) = ingestor.ingest_with_report(data).unwrap();
+
+        assert_eq!(traces.len(), 1);
+        assert_eq!(report.total_lines, 2);
+        assert_eq!(report.parsed, 2);
+        assert!(report.skipped.is_empty());
+        assert_eq!(report.skip_ratio(), 0.0);
+    }
+
+    #[test]
+    fn ingest_with_report_records_why_each_line_was_skipped() {
+        let ingestor = HoneycombIngestor;
+        let data = b"{\"trace.trace_id\":\"t1\",\"trace.span_id\":\"s1\",\"name\":\"a\",\"duration_ms\":10}\n\
+not valid json\n\
+{\"span_id\":\"s2\",\"name\":\"b\",\"duration_ms\":5}\n";
+
+        let (traces, report) = ingestor.ingest_with_report(data).unwrap();
+
+        assert_eq!(traces.len(), 1);
+        assert_eq!(report.total_lines, 3);
+        assert_eq!(report.parsed, 1);
+        assert_eq!(report.skipped.len(), 2);
+        assert_eq!(report.skipped[0].line_number, 2);
+        assert_eq!(report.skipped[1].line_number, 3);
+        for entry in &report.skipped {
+            assert!(matches!(
+                entry.error,
+                Error::ParseError {
+                    format: "honeycomb",
+                    ..
+                }
+            ));
+        }
+    }
+
+    #[test]
+    fn ingest_with_report_skip_ratio_reflects_proportion_skipped() {
+        let ingestor = HoneycombIngestor;
+        let data = b"{\"trace.trace_id\":\"t1\",\"trace.span_id\":\"s1\",\"name\":\"a\",\"duration_ms\":10}\n\
+not valid json\n\
+also not valid\n\
+not valid either\n";
+
+        let (_, report) = ingestor.ingest_with_report(data).unwrap();
+
+        assert_eq!(report.total_lines, 4);
+        assert_eq!(report.parsed, 1);
+        assert_eq!(report.skipped.len(), 3);
+        assert!((report.skip_ratio() - 0.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn ingest_with_report_blank_lines_are_not_counted() {
+        let ingestor = HoneycombIngestor;
+        let data = b"\n{\"trace.trace_id\":\"t1\",\"trace.span_id\":\"s1\",\"name\":\"a\",\"duration_ms\":10}\n\n";
+
+        let (traces, report) = ingestor.ingest_with_report(data).unwrap();
+
+        assert_eq!(traces.len(), 1);
+        assert_eq!(report.total_lines, 1);
+        assert_eq!(report.parsed, 1);
+    }
 }
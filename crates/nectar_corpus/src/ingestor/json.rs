@@ -1,17 +1,20 @@
 //! JSON trace ingestor.
 //!
-//! Handles plain JSON trace data in the format expected by the existing loader.
+//! Handles plain JSON trace data in the format expected by the existing loader,
+//! as well as NDJSON (one trace object per line) for streaming exports.
 
 use crate::corpus::Corpus;
 use crate::error::{Error, Result};
-use crate::ingestor::TraceIngestor;
+use crate::ingestor::{ContentType, Detection, TraceIngestor};
 use crate::trace::Trace;
+use std::io::{BufRead, BufReader, Read};
 
 /// Ingestor for plain JSON trace data.
 ///
-/// Supports two formats:
+/// Supports three formats:
 /// - A JSON array of trace objects
 /// - A JSON object with a "traces" field containing the array
+/// - NDJSON: one trace object per line, for streaming/append-only exports
 pub struct JsonIngestor;
 
 impl TraceIngestor for JsonIngestor {
@@ -21,8 +24,8 @@ impl TraceIngestor for JsonIngestor {
 
     fn can_handle(&self, header: &[u8], content_type: Option<&str>) -> bool {
         // Check content-type first
-        if let Some(ct) = content_type {
-            if ct.contains("application/json") {
+        if let Some(ct) = content_type.and_then(ContentType::parse) {
+            if ct.is_mime("application/x-ndjson") || ct.is_mime("application/json") {
                 return true;
             }
         }
@@ -37,14 +40,149 @@ impl TraceIngestor for JsonIngestor {
         first_byte == b'[' || first_byte == b'{'
     }
 
+    fn detect(&self, header: &[u8], content_type: Option<&str>) -> Detection {
+        // JSON is the general-purpose fallback: its content-type and
+        // header checks overlap with every other format's (an OTLP
+        // protobuf body is never a JSON content-type, but a Honeycomb
+        // export's NDJSON absolutely is), so it scores low enough that
+        // any format with a sharper signal outranks it.
+        if let Some(ct) = content_type.and_then(ContentType::parse) {
+            if ct.is_mime("application/x-ndjson") || ct.is_mime("application/json") {
+                return Detection::Likely(50);
+            }
+        }
+
+        let trimmed = trim_leading_whitespace(header);
+        if trimmed.is_empty() {
+            return Detection::No;
+        }
+
+        if trimmed[0] == b'[' || trimmed[0] == b'{' {
+            Detection::Likely(10)
+        } else {
+            Detection::No
+        }
+    }
+
     fn ingest(&self, data: &[u8]) -> Result<Vec<Trace>> {
         let json_str = std::str::from_utf8(data)
             .map_err(|e| Error::parse("json", format!("invalid UTF-8: {e}")))?;
 
+        if looks_like_ndjson(json_str) {
+            return ingest_ndjson(json_str);
+        }
+
         // Use the existing Corpus::parse_json which handles the conversion
         let corpus = Corpus::parse_json(json_str)?;
         Ok(corpus.into_traces())
     }
+
+    fn ingest_stream(
+        &self,
+        reader: Box<dyn Read>,
+    ) -> Result<Box<dyn Iterator<Item = Result<Trace>>>> {
+        let mut buffered = BufReader::with_capacity(8192, reader);
+        let is_ndjson = std::str::from_utf8(buffered.fill_buf()?)
+            .map(looks_like_ndjson)
+            .unwrap_or(false);
+
+        if !is_ndjson {
+            // A JSON array or `{"traces": [...]}` wrapper isn't known to
+            // be valid until its closing bracket arrives, so there's
+            // nothing to stream - fall back to buffering the document.
+            let mut data = Vec::new();
+            buffered.read_to_end(&mut data)?;
+            return Ok(Box::new(self.ingest(&data)?.into_iter().map(Ok)));
+        }
+
+        Ok(Box::new(NdjsonTraceIter {
+            lines: buffered.lines(),
+        }))
+    }
+}
+
+/// Lazily parses one [`Trace`] per NDJSON line, skipping blank lines and
+/// logging-then-skipping malformed ones - mirroring [`ingest_ndjson`]'s
+/// per-line tolerance, but without buffering the whole stream first.
+struct NdjsonTraceIter<R: std::io::BufRead> {
+    lines: std::io::Lines<R>,
+}
+
+impl<R: std::io::BufRead> Iterator for NdjsonTraceIter<R> {
+    type Item = Result<Trace>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(Error::Io(e))),
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            match Corpus::parse_json_trace(line) {
+                Ok(trace) => return Some(Ok(trace)),
+                Err(e) => {
+                    tracing::warn!("Skipping invalid NDJSON trace: {e}");
+                }
+            }
+        }
+    }
+}
+
+/// Detects newline-delimited JSON: the payload starts with a `{` object
+/// but contains another `{` on a later line, rather than being a single
+/// JSON document (array or `{"traces": [...]}` wrapper).
+fn looks_like_ndjson(text: &str) -> bool {
+    let trimmed = text.trim_start();
+    if !trimmed.starts_with('{') {
+        return false;
+    }
+
+    trimmed
+        .lines()
+        .skip(1)
+        .any(|line| line.trim_start().starts_with('{'))
+}
+
+/// Parses NDJSON trace data, one trace object per line.
+///
+/// Blank lines are skipped. Malformed lines are logged and skipped
+/// rather than failing the whole batch, so a single bad record in a
+/// large streaming export doesn't discard the rest.
+fn ingest_ndjson(text: &str) -> Result<Vec<Trace>> {
+    let mut traces = Vec::new();
+    let mut errors = Vec::new();
+
+    for (line_num, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        match Corpus::parse_json_trace(line) {
+            Ok(trace) => traces.push(trace),
+            Err(e) => {
+                let line_no = line_num + 1;
+                errors.push(format!("line {line_no}: {e}"));
+            }
+        }
+    }
+
+    if traces.is_empty() && !errors.is_empty() {
+        return Err(Error::parse(
+            "json",
+            format!("all lines failed: {}", errors.join("; ")),
+        ));
+    }
+
+    for error in &errors {
+        tracing::warn!("Skipping invalid NDJSON trace: {error}");
+    }
+
+    Ok(traces)
 }
 
 /// Trims leading whitespace bytes from a slice.
@@ -124,4 +262,128 @@ mod tests {
         let ingestor = JsonIngestor;
         assert!(ingestor.can_handle(b"  \n  [{\"trace_id\": \"abc\"}]", None));
     }
+
+    #[test]
+    fn json_ingestor_can_handle_ndjson_content_type() {
+        let ingestor = JsonIngestor;
+        assert!(ingestor.can_handle(b"{}", Some("application/x-ndjson")));
+    }
+
+    #[test]
+    fn json_ingestor_ingest_ndjson() {
+        let ingestor = JsonIngestor;
+        let data = b"{\"trace_id\": \"abc\", \"duration_ms\": 100, \"status\": 200}\n{\"trace_id\": \"def\", \"duration_ms\": 200, \"status\": 500}\n";
+
+        let traces = ingestor.ingest(data).unwrap();
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].trace_id, "abc");
+        assert_eq!(traces[1].trace_id, "def");
+        assert!(traces[1].is_error);
+    }
+
+    #[test]
+    fn json_ingestor_ndjson_skips_blank_lines() {
+        let ingestor = JsonIngestor;
+        let data = b"{\"trace_id\": \"abc\"}\n\n   \n{\"trace_id\": \"def\"}\n";
+
+        let traces = ingestor.ingest(data).unwrap();
+        assert_eq!(traces.len(), 2);
+    }
+
+    #[test]
+    fn json_ingestor_ndjson_skips_malformed_lines_but_keeps_the_rest() {
+        let ingestor = JsonIngestor;
+        let data = b"{\"trace_id\": \"abc\"}\nnot json at all\n{\"trace_id\": \"def\"}\n";
+
+        let traces = ingestor.ingest(data).unwrap();
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].trace_id, "abc");
+        assert_eq!(traces[1].trace_id, "def");
+    }
+
+    #[test]
+    fn json_ingestor_ndjson_all_lines_malformed_is_an_error() {
+        let ingestor = JsonIngestor;
+        let data = b"not json\nstill not json\n";
+
+        assert!(ingestor.ingest(data).is_err());
+    }
+
+    #[test]
+    fn json_ingestor_single_object_is_not_treated_as_ndjson() {
+        let ingestor = JsonIngestor;
+        let data = br#"{"traces": [{"trace_id": "xyz"}]}"#;
+
+        let traces = ingestor.ingest(data).unwrap();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].trace_id, "xyz");
+    }
+
+    #[test]
+    fn json_ingestor_detect_scores_content_type_above_bare_header() {
+        let ingestor = JsonIngestor;
+        assert_eq!(
+            ingestor.detect(b"anything", Some("application/json")),
+            Detection::Likely(50)
+        );
+        assert_eq!(
+            ingestor.detect(b"[{\"trace_id\": \"abc\"}]", None),
+            Detection::Likely(10)
+        );
+        assert_eq!(ingestor.detect(b"not json", None), Detection::No);
+    }
+
+    #[test]
+    fn json_ingestor_ingest_stream_yields_ndjson_lazily() {
+        let ingestor = JsonIngestor;
+        let data = b"{\"trace_id\": \"abc\"}\n{\"trace_id\": \"def\"}\n";
+        let reader: Box<dyn Read> = Box::new(std::io::Cursor::new(data.to_vec()));
+
+        let traces: Vec<_> = ingestor
+            .ingest_stream(reader)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].trace_id, "abc");
+        assert_eq!(traces[1].trace_id, "def");
+    }
+
+    #[test]
+    fn json_ingestor_ingest_stream_skips_malformed_lines() {
+        let ingestor = JsonIngestor;
+        let data = b"{\"trace_id\": \"abc\"}\nnot json at all\n{\"trace_id\": \"def\"}\n";
+        let reader: Box<dyn Read> = Box::new(std::io::Cursor::new(data.to_vec()));
+
+        let traces: Vec<_> = ingestor
+            .ingest_stream(reader)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].trace_id, "abc");
+        assert_eq!(traces[1].trace_id, "def");
+    }
+
+    #[test]
+    fn json_ingestor_ingest_stream_falls_back_to_buffering_for_arrays() {
+        let ingestor = JsonIngestor;
+        let data = br#"[
+            {"trace_id": "abc"},
+            {"trace_id": "def"}
+        ]"#;
+        let reader: Box<dyn Read> = Box::new(std::io::Cursor::new(data.to_vec()));
+
+        let traces: Vec<_> = ingestor
+            .ingest_stream(reader)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].trace_id, "abc");
+        assert_eq!(traces[1].trace_id, "def");
+    }
 }
@@ -12,18 +12,55 @@
 //! let traces = registry.ingest(data, Some("application/json"))?;
 //! ```
 
+mod config;
+mod content_type;
+pub(crate) mod decompress;
+mod filter;
 mod honeycomb;
 mod json;
 #[cfg(feature = "otlp")]
 mod otlp;
+#[cfg(feature = "otlp")]
+mod otlp_json;
+#[cfg(feature = "zipkin")]
+mod zipkin_thrift;
 
+pub use config::BindFunc;
+pub use content_type::ContentType;
 pub use honeycomb::HoneycombIngestor;
 pub use json::JsonIngestor;
 #[cfg(feature = "otlp")]
-pub use otlp::OtlpIngestor;
+pub use otlp::{spans_from_otlp, OtlpIngestor};
+#[cfg(feature = "otlp")]
+pub use otlp_json::OtlpJsonIngestor;
+#[cfg(feature = "zipkin")]
+pub use zipkin_thrift::ZipkinThriftIngestor;
 
 use crate::error::{Error, Result};
 use crate::trace::Trace;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+/// Confidence that a [`TraceIngestor`] can handle a given payload,
+/// returned by [`TraceIngestor::detect`].
+///
+/// Field order matters: deriving `Ord` sorts candidates from least to
+/// most confident, since `No < Likely(_) < Certain` and `Likely` scores
+/// compare by their `u8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Detection {
+    /// Definitely not a match for this format.
+    No,
+    /// Might be a match, with a confidence score (higher is more
+    /// confident) for breaking ties between ingestors that both
+    /// recognize ambiguous data - e.g. Honeycomb-shaped NDJSON, which is
+    /// also valid plain JSON.
+    Likely(u8),
+    /// Unambiguously this format.
+    /// [`IngestorRegistry::ingest_with_hint`] tries this ingestor first
+    /// and doesn't bother scoring the rest.
+    Certain,
+}
 
 /// A trait for ingesting traces from a specific format.
 ///
@@ -38,20 +75,146 @@ pub trait TraceIngestor: Send + Sync {
     /// Uses header bytes and optional content-type to determine compatibility.
     fn can_handle(&self, header: &[u8], content_type: Option<&str>) -> bool;
 
+    /// Scores how confidently this ingestor can handle the given data.
+    ///
+    /// Used by [`IngestorRegistry::ingest_with_hint`] to disambiguate
+    /// between formats that could all plausibly claim the same payload
+    /// (e.g. Honeycomb NDJSON, which is also valid plain JSON) instead of
+    /// just taking whichever registered ingestor happens to match first.
+    ///
+    /// The default implementation derives a score from [`Self::can_handle`]:
+    /// a middling [`Detection::Likely`] if it returns true, [`Detection::No`]
+    /// otherwise. Override this when a format has several possible signals
+    /// that deserve different confidence (see the built-in ingestors).
+    fn detect(&self, header: &[u8], content_type: Option<&str>) -> Detection {
+        if self.can_handle(header, content_type) {
+            Detection::Likely(128)
+        } else {
+            Detection::No
+        }
+    }
+
     /// Ingests trace data and returns a vector of traces.
     ///
     /// # Errors
     ///
     /// Returns an error if the data cannot be parsed.
     fn ingest(&self, data: &[u8]) -> Result<Vec<Trace>>;
+
+    /// Ingests trace data lazily from a stream, without buffering the
+    /// whole payload into memory first.
+    ///
+    /// The reader starts at byte zero of the payload (any bytes the
+    /// registry peeked for format detection are replayed, not consumed -
+    /// see [`IngestorRegistry::ingest_stream`]).
+    ///
+    /// The default implementation buffers the entire stream and defers
+    /// to [`Self::ingest`], for formats whose framing can't be parsed
+    /// incrementally (e.g. a JSON array, which isn't known to be valid
+    /// until its closing bracket arrives). Formats with self-delimiting
+    /// records - NDJSON lines, length-delimited OTLP messages - should
+    /// override this to parse and yield as they read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading the stream fails or `Self::ingest`
+    /// fails on the buffered fallback path. Per-record errors from a
+    /// streaming override are generally better reported by skipping the
+    /// bad record (matching `ingest`'s tolerance for malformed records)
+    /// than by failing the whole iterator.
+    fn ingest_stream(
+        &self,
+        mut reader: Box<dyn Read>,
+    ) -> Result<Box<dyn Iterator<Item = Result<Trace>>>> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let traces = self.ingest(&data)?;
+        Ok(Box::new(traces.into_iter().map(Ok)))
+    }
+
+    /// Ingests trace data like [`Self::ingest`], but instead of only
+    /// logging malformed records via `tracing::warn!`, returns a
+    /// structured [`IngestReport`] the caller can inspect programmatically
+    /// - e.g. to gate on a maximum acceptable skip ratio, or to surface
+    /// exactly which lines failed and why.
+    ///
+    /// The default implementation defers entirely to [`Self::ingest`],
+    /// which already skips malformed records internally for formats that
+    /// tolerate partial input. That means the default report has no
+    /// per-record detail: `total_lines` and `parsed` both count the
+    /// traces that came out, and `skipped` is always empty. Formats with
+    /// self-delimiting records (Honeycomb NDJSON, NDJSON-mode plain JSON)
+    /// should override this to report each skipped line individually.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the data cannot be parsed at all.
+    fn ingest_with_report(&self, data: &[u8]) -> Result<(Vec<Trace>, IngestReport)> {
+        let traces = self.ingest(data)?;
+        let report = IngestReport {
+            total_lines: traces.len(),
+            parsed: traces.len(),
+            skipped: Vec::new(),
+        };
+        Ok((traces, report))
+    }
+}
+
+/// One record skipped during a lenient [`TraceIngestor::ingest_with_report`]
+/// call, with its location and why it was rejected.
+#[derive(Debug)]
+pub struct SkipEntry {
+    /// 1-based line number within the input, for line-oriented formats.
+    /// `0` if the format isn't line-oriented and no finer-grained location
+    /// is available.
+    pub line_number: usize,
+    /// Why the record was skipped.
+    pub error: Error,
+}
+
+/// Summary of a lenient [`TraceIngestor::ingest_with_report`] call: how much
+/// input was consumed, how much parsed cleanly, and why the rest didn't.
+///
+/// Lets a caller distinguish a file that's a little noisy from one that's
+/// mostly garbage, e.g. by gating acceptance on [`Self::skip_ratio`].
+#[derive(Debug, Default)]
+pub struct IngestReport {
+    /// Total number of input records seen (blank lines excluded).
+    pub total_lines: usize,
+    /// Number of records that parsed into a trace.
+    pub parsed: usize,
+    /// Records that failed to parse, in input order.
+    pub skipped: Vec<SkipEntry>,
+}
+
+impl IngestReport {
+    /// Fraction of total records that were skipped, `0.0` if there were
+    /// no records at all.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn skip_ratio(&self) -> f64 {
+        if self.total_lines == 0 {
+            0.0
+        } else {
+            self.skipped.len() as f64 / self.total_lines as f64
+        }
+    }
 }
 
+/// Default cap on how many bytes a compressed payload may decompress to,
+/// so a zip-bomb-style payload can't exhaust memory before format
+/// detection even runs.
+pub(crate) const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
 /// Registry of available trace ingestors.
 ///
 /// The registry maintains a priority-ordered list of ingestors and
 /// provides auto-detection of trace formats.
 pub struct IngestorRegistry {
     ingestors: Vec<Box<dyn TraceIngestor>>,
+    max_decompressed_size: usize,
+    binders: HashMap<String, BindFunc>,
+    filter: Option<filter::SpanFilter>,
 }
 
 impl Default for IngestorRegistry {
@@ -65,17 +228,25 @@ impl IngestorRegistry {
     ///
     /// Ingestors are registered in priority order:
     /// 1. OTLP (if feature enabled) - most specific format
-    /// 2. Honeycomb - specific NDJSON format
-    /// 3. JSON - general-purpose fallback
+    /// 2. Zipkin Thrift (if feature enabled) - content-type-gated legacy format
+    /// 3. Honeycomb - specific NDJSON format
+    /// 4. JSON - general-purpose fallback
     #[must_use]
     pub fn new() -> Self {
         let mut registry = Self {
             ingestors: Vec::new(),
+            max_decompressed_size: DEFAULT_MAX_DECOMPRESSED_SIZE,
+            binders: default_binders(),
+            filter: None,
         };
 
         // Register ingestors in priority order (most specific first)
         #[cfg(feature = "otlp")]
         registry.register(Box::new(OtlpIngestor));
+        #[cfg(feature = "otlp")]
+        registry.register(Box::new(OtlpJsonIngestor));
+        #[cfg(feature = "zipkin")]
+        registry.register(Box::new(ZipkinThriftIngestor));
 
         registry.register(Box::new(HoneycombIngestor));
         registry.register(Box::new(JsonIngestor));
@@ -83,6 +254,104 @@ impl IngestorRegistry {
         registry
     }
 
+    /// Builds a registry entirely from a TOML config document, instead of
+    /// the hard-coded built-in set.
+    ///
+    /// See [`Self::load_config`] for the config format and error cases.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any `[[ingestor]]` entry is malformed or names
+    /// a format with no registered binder.
+    pub fn from_config(config: &toml::Value) -> Result<Self> {
+        let mut registry = Self {
+            ingestors: Vec::new(),
+            max_decompressed_size: DEFAULT_MAX_DECOMPRESSED_SIZE,
+            binders: default_binders(),
+            filter: None,
+        };
+        registry.load_config(config)?;
+        Ok(registry)
+    }
+
+    /// Replaces this registry's ingestor list with the one described by
+    /// `config`'s `[[ingestor]]` entries.
+    ///
+    /// Each entry's `format` is looked up in this registry's binder table
+    /// (built-ins plus anything added via [`Self::register_binder`]) and
+    /// invoked with the entry's `[ingestor.options]` table to construct
+    /// the ingestor. Entries are registered in descending `priority`
+    /// order (ties keep declaration order), matching the "most specific
+    /// first" convention [`Self::new`] uses for the built-in set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry is malformed, names an unregistered
+    /// format, or its binder fails to construct the ingestor.
+    pub fn load_config(&mut self, config: &toml::Value) -> Result<()> {
+        let mut entries = config::parse_entries(config)?;
+        entries.sort_by(|a, b| b.priority.cmp(&a.priority));
+
+        let mut ingestors = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let binder = self
+                .binders
+                .get(entry.format.as_str())
+                .ok_or_else(|| Error::UnknownFormat(entry.format.clone()))?;
+            ingestors.push(binder(&entry.options)?);
+        }
+
+        self.ingestors = ingestors;
+        Ok(())
+    }
+
+    /// Registers a binder function for a format name, for use by
+    /// [`Self::from_config`]/[`Self::load_config`]. Overrides any
+    /// existing binder (built-in or previously registered) for that
+    /// format name.
+    pub fn register_binder(&mut self, format: impl Into<String>, binder: BindFunc) {
+        self.binders.insert(format.into(), binder);
+    }
+
+    /// Sets the cap on decompressed payload size (see
+    /// [`DEFAULT_MAX_DECOMPRESSED_SIZE`] for the default).
+    #[must_use]
+    pub fn with_max_decompressed_size(mut self, max_bytes: usize) -> Self {
+        self.max_decompressed_size = max_bytes;
+        self
+    }
+
+    /// Applies an `EnvFilter`-style directive string as a post-ingest
+    /// filter stage, so callers can keep (e.g.) only error spans from a
+    /// given service without a second pass over the corpus.
+    ///
+    /// Directives are comma-separated, each of the form
+    /// `target[span{field=value,field2}]=level`: `target` matches the
+    /// service name by prefix, the optional `span` name matches the
+    /// operation name exactly, the optional `{...}` block lists field
+    /// matchers (`field=value` or a bare `field` for presence), and
+    /// `level` is one of `off`, `error`, `ok`, or `all` - the minimum
+    /// span status to keep. The most specific matching directive wins;
+    /// a span matched by none of them is kept. An empty string keeps
+    /// every span (the default with no filter set).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any directive in `directives` is malformed.
+    pub fn with_filter(mut self, directives: &str) -> Result<Self> {
+        self.filter = Some(filter::SpanFilter::parse(directives)?);
+        Ok(self)
+    }
+
+    /// Filters a batch of ingested traces through the configured
+    /// directive filter, if any.
+    fn apply_filter(&self, traces: Vec<Trace>) -> Vec<Trace> {
+        match &self.filter {
+            Some(filter) => filter::apply_to_traces(filter, traces),
+            None => traces,
+        }
+    }
+
     /// Registers a new ingestor.
     ///
     /// The ingestor is added to the end of the priority list.
@@ -110,6 +379,47 @@ impl IngestorRegistry {
     ///
     /// Returns an error if the data cannot be parsed by any ingestor.
     pub fn ingest_with_hint(&self, data: &[u8], content_type: Option<&str>) -> Result<Vec<Trace>> {
+        self.ingest_with_encoding(data, content_type, None)
+    }
+
+    /// Ingests trace data with optional content-type and content-encoding
+    /// hints.
+    ///
+    /// If `content_encoding` names a supported compression (`gzip`,
+    /// `zstd`) or the payload's header bytes carry a recognizable
+    /// compression magic number, it's transparently decompressed before
+    /// format detection runs - so no [`TraceIngestor`] ever has to know
+    /// about compression. Decompression is capped at
+    /// `max_decompressed_size` bytes (see
+    /// [`Self::with_max_decompressed_size`]) to guard against
+    /// decompression bombs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload claims to be compressed but fails
+    /// to decompress (including exceeding the size cap), or if the
+    /// (possibly decompressed) data cannot be parsed by any ingestor.
+    pub fn ingest_with_encoding(
+        &self,
+        data: &[u8],
+        content_type: Option<&str>,
+        content_encoding: Option<&str>,
+    ) -> Result<Vec<Trace>> {
+        if data.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sniff_len = data.len().min(256);
+        let decompressed;
+        let data = match decompress::detect(&data[..sniff_len], content_encoding) {
+            Some(compression) => {
+                decompressed =
+                    decompress::decompress(data, compression, self.max_decompressed_size)?;
+                decompressed.as_slice()
+            }
+            None => data,
+        };
+
         if data.is_empty() {
             return Ok(Vec::new());
         }
@@ -118,36 +428,59 @@ impl IngestorRegistry {
         let header_len = data.len().min(256);
         let header = &data[..header_len];
 
-        // Try ingestors that claim to handle this format
-        for ingestor in &self.ingestors {
-            if ingestor.can_handle(header, content_type) {
-                match ingestor.ingest(data) {
-                    Ok(traces) => return Ok(traces),
-                    Err(e) => {
-                        tracing::debug!(
-                            "Ingestor {} failed: {}, trying next",
-                            ingestor.format_name(),
-                            e
-                        );
-                    }
+        // Score every ingestor's confidence, short-circuiting as soon as
+        // one is Certain - there's no need to score (or later try) the
+        // rest once one format has unambiguously claimed the payload.
+        let mut certain = None;
+        let mut candidates = Vec::new();
+        for (index, ingestor) in self.ingestors.iter().enumerate() {
+            match ingestor.detect(header, content_type) {
+                Detection::Certain => {
+                    certain = Some(index);
+                    break;
+                }
+                Detection::No => {}
+                detection @ Detection::Likely(_) => candidates.push((detection, index)),
+            }
+        }
+
+        let ordered: Vec<usize> = if let Some(index) = certain {
+            vec![index]
+        } else {
+            // Highest confidence first; a stable sort keeps ties in
+            // registration (priority) order.
+            candidates.sort_by(|a, b| b.0.cmp(&a.0));
+            candidates.into_iter().map(|(_, index)| index).collect()
+        };
+
+        for index in ordered {
+            let ingestor = &self.ingestors[index];
+            match ingestor.ingest(data) {
+                Ok(traces) => return Ok(self.apply_filter(traces)),
+                Err(e) => {
+                    tracing::debug!(
+                        "Ingestor {} failed: {}, trying next",
+                        ingestor.format_name(),
+                        e
+                    );
                 }
             }
         }
 
-        // If no ingestor matched or all failed, try each one as fallback
+        // If detection found nothing (or every candidate failed to
+        // parse), fall back to trying every registered ingestor.
         let mut last_error = None;
         for ingestor in &self.ingestors {
             match ingestor.ingest(data) {
-                Ok(traces) => return Ok(traces),
+                Ok(traces) => return Ok(self.apply_filter(traces)),
                 Err(e) => {
                     last_error = Some(e);
                 }
             }
         }
 
-        Err(last_error.unwrap_or_else(|| {
-            Error::UnknownFormat("no ingestors registered".to_string())
-        }))
+        Err(last_error
+            .unwrap_or_else(|| Error::UnknownFormat("no ingestors registered".to_string())))
     }
 
     /// Ingests trace data using a specific format.
@@ -159,7 +492,9 @@ impl IngestorRegistry {
     pub fn ingest_as(&self, data: &[u8], format: &str) -> Result<Vec<Trace>> {
         for ingestor in &self.ingestors {
             if ingestor.format_name() == format {
-                return ingestor.ingest(data);
+                return ingestor
+                    .ingest(data)
+                    .map(|traces| self.apply_filter(traces));
             }
         }
         Err(Error::UnknownFormat(format.to_string()))
@@ -170,6 +505,92 @@ impl IngestorRegistry {
     pub fn formats(&self) -> Vec<&'static str> {
         self.ingestors.iter().map(|i| i.format_name()).collect()
     }
+
+    /// Ingests trace data from a stream using auto-detection, yielding
+    /// traces lazily instead of buffering the whole payload - the
+    /// streaming counterpart to [`Self::ingest_with_hint`].
+    ///
+    /// Peeks the same bounded header window `ingest_with_hint` uses (via
+    /// a buffered reader's `fill_buf`, so the peeked bytes are replayed
+    /// rather than consumed) to pick an ingestor with `can_handle`, then
+    /// hands it the full stream from byte zero via
+    /// [`TraceIngestor::ingest_stream`].
+    ///
+    /// Unlike `ingest_with_hint`, there's no fallback-through-every-
+    /// ingestor pass if detection fails: once bytes are read from a
+    /// stream they can't be replayed to a second ingestor, so an
+    /// unrecognized stream is an error rather than a last-resort retry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading the stream's header fails, no
+    /// ingestor recognizes the format, or the selected ingestor's
+    /// `ingest_stream` fails.
+    pub fn ingest_stream(
+        &self,
+        reader: Box<dyn Read>,
+        content_type: Option<&str>,
+    ) -> Result<Box<dyn Iterator<Item = Result<Trace>>>> {
+        let mut buffered = BufReader::with_capacity(4096, reader);
+        let header_len = buffered.fill_buf()?.len().min(256);
+        let header = buffered.buffer()[..header_len].to_vec();
+
+        for ingestor in &self.ingestors {
+            if ingestor.can_handle(&header, content_type) {
+                return ingestor.ingest_stream(Box::new(buffered));
+            }
+        }
+
+        Err(Error::UnknownFormat(
+            "no ingestor recognized the stream".to_string(),
+        ))
+    }
+}
+
+/// Binders for the built-in ingestors, keyed by [`TraceIngestor::format_name`].
+///
+/// None of the built-ins currently read their `options` table - they're
+/// parameterless today - but the table is still threaded through so a
+/// custom binder registered via [`IngestorRegistry::register_binder`] can
+/// use it (e.g. a Honeycomb binder reading a configured dataset field, or
+/// a JSON binder reading a custom timestamp key).
+fn default_binders() -> HashMap<String, BindFunc> {
+    let mut binders: HashMap<String, BindFunc> = HashMap::new();
+    binders.insert("json".to_string(), json_binder as BindFunc);
+    binders.insert("honeycomb".to_string(), honeycomb_binder as BindFunc);
+    #[cfg(feature = "otlp")]
+    binders.insert("otlp".to_string(), otlp_binder as BindFunc);
+    #[cfg(feature = "otlp")]
+    binders.insert("otlp-json".to_string(), otlp_json_binder as BindFunc);
+    #[cfg(feature = "zipkin")]
+    binders.insert(
+        "zipkin-thrift".to_string(),
+        zipkin_thrift_binder as BindFunc,
+    );
+    binders
+}
+
+fn json_binder(_options: &toml::Value) -> Result<Box<dyn TraceIngestor>> {
+    Ok(Box::new(JsonIngestor))
+}
+
+fn honeycomb_binder(_options: &toml::Value) -> Result<Box<dyn TraceIngestor>> {
+    Ok(Box::new(HoneycombIngestor))
+}
+
+#[cfg(feature = "otlp")]
+fn otlp_binder(_options: &toml::Value) -> Result<Box<dyn TraceIngestor>> {
+    Ok(Box::new(OtlpIngestor))
+}
+
+#[cfg(feature = "otlp")]
+fn otlp_json_binder(_options: &toml::Value) -> Result<Box<dyn TraceIngestor>> {
+    Ok(Box::new(OtlpJsonIngestor))
+}
+
+#[cfg(feature = "zipkin")]
+fn zipkin_thrift_binder(_options: &toml::Value) -> Result<Box<dyn TraceIngestor>> {
+    Ok(Box::new(ZipkinThriftIngestor))
 }
 
 #[cfg(test)]
@@ -185,6 +606,20 @@ mod tests {
         assert!(formats.contains(&"honeycomb"));
     }
 
+    #[test]
+    fn ingest_prefers_honeycomb_over_json_for_ambiguous_ndjson() {
+        // This line is valid plain JSON *and* carries Honeycomb's
+        // namespaced trace fields - both ingestors' `can_handle` would
+        // have matched it, but `detect` should score Honeycomb higher so
+        // it wins instead of whichever registered first.
+        let registry = IngestorRegistry::new();
+        let data = br#"{"trace.trace_id":"abc","trace.span_id":"1","name":"op"}"#;
+
+        let traces = registry.ingest(data, None).unwrap();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].trace_id, "abc");
+    }
+
     #[test]
     fn registry_empty_data() {
         let registry = IngestorRegistry::new();
@@ -199,4 +634,102 @@ mod tests {
         let result = registry.ingest_as(b"data", "unknown");
         assert!(matches!(result, Err(Error::UnknownFormat(_))));
     }
+
+    #[test]
+    fn from_config_builds_registry_in_priority_order() {
+        let config: toml::Value = toml::from_str(
+            r#"
+            [[ingestor]]
+            format = "json"
+            priority = 5
+
+            [[ingestor]]
+            format = "honeycomb"
+            priority = 10
+            "#,
+        )
+        .unwrap();
+
+        let registry = IngestorRegistry::from_config(&config).unwrap();
+        assert_eq!(registry.formats(), vec!["honeycomb", "json"]);
+    }
+
+    #[test]
+    fn from_config_rejects_unregistered_format() {
+        let config: toml::Value = toml::from_str(
+            r#"
+            [[ingestor]]
+            format = "nonexistent"
+            "#,
+        )
+        .unwrap();
+
+        let result = IngestorRegistry::from_config(&config);
+        assert!(matches!(result, Err(Error::UnknownFormat(_))));
+    }
+
+    #[test]
+    fn register_binder_adds_a_custom_format() {
+        fn custom_binder(_options: &toml::Value) -> Result<Box<dyn TraceIngestor>> {
+            Ok(Box::new(JsonIngestor))
+        }
+
+        let mut registry = IngestorRegistry::new();
+        registry.register_binder("custom", custom_binder);
+
+        let config: toml::Value = toml::from_str(
+            r#"
+            [[ingestor]]
+            format = "custom"
+            "#,
+        )
+        .unwrap();
+
+        registry.load_config(&config).unwrap();
+        assert_eq!(registry.formats(), vec!["json"]);
+    }
+
+    #[test]
+    fn ingest_stream_detects_and_delegates_to_ndjson() {
+        let registry = IngestorRegistry::new();
+        let data = b"{\"trace_id\": \"abc\"}\n{\"trace_id\": \"def\"}\n";
+        let reader: Box<dyn Read> = Box::new(std::io::Cursor::new(data.to_vec()));
+
+        let traces: Vec<_> = registry
+            .ingest_stream(reader, None)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(traces.len(), 2);
+        assert_eq!(traces[0].trace_id, "abc");
+        assert_eq!(traces[1].trace_id, "def");
+    }
+
+    #[test]
+    fn ingest_stream_rejects_unrecognized_data() {
+        let registry = IngestorRegistry::new();
+        let reader: Box<dyn Read> = Box::new(std::io::Cursor::new(b"not a known format".to_vec()));
+
+        let result = registry.ingest_stream(reader, None);
+        assert!(matches!(result, Err(Error::UnknownFormat(_))));
+    }
+
+    #[test]
+    fn with_filter_is_applied_after_ingest() {
+        use crate::span::Span;
+
+        let registry = IngestorRegistry::new().with_filter("api=error").unwrap();
+        let ok_span = Span::new("s1", "h").with_service("api");
+        let trace = Trace::from_spans("t1", vec![ok_span]);
+
+        let filtered = registry.apply_filter(vec![trace]);
+        assert!(filtered[0].spans.is_empty());
+    }
+
+    #[test]
+    fn with_filter_rejects_malformed_directives() {
+        let result = IngestorRegistry::new().with_filter("api");
+        assert!(matches!(result, Err(Error::ParseError { .. })));
+    }
 }
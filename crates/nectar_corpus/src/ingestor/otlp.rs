@@ -4,12 +4,13 @@
 //! This module is only available when the `otlp` feature is enabled.
 
 use crate::error::{Error, Result};
-use crate::ingestor::TraceIngestor;
-use crate::span::{AttributeValue, Span, SpanKind, SpanStatus, StatusCode};
-use crate::trace::Trace;
+use crate::ingestor::{ContentType, Detection, TraceIngestor};
+use crate::span::{AttributeValue, Span, SpanEvent, SpanKind, SpanLink, SpanStatus, StatusCode};
+use crate::trace::{ResourceScope, Trace};
 use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
 use opentelemetry_proto::tonic::common::v1::any_value::Value as OtlpValue;
-use opentelemetry_proto::tonic::common::v1::AnyValue;
+use opentelemetry_proto::tonic::common::v1::{AnyValue, InstrumentationScope};
+use opentelemetry_proto::tonic::trace::v1::ResourceSpans;
 use prost::Message;
 use std::collections::HashMap;
 use std::time::Duration;
@@ -27,10 +28,10 @@ impl TraceIngestor for OtlpIngestor {
 
     fn can_handle(&self, header: &[u8], content_type: Option<&str>) -> bool {
         // Check content-type first
-        if let Some(ct) = content_type {
-            if ct.contains("application/x-protobuf")
-                || ct.contains("application/protobuf")
-                || ct.contains("application/grpc")
+        if let Some(ct) = content_type.and_then(ContentType::parse) {
+            if ct.is_mime("application/x-protobuf")
+                || ct.is_mime("application/protobuf")
+                || ct.is_mime("application/grpc")
             {
                 return true;
             }
@@ -47,96 +48,300 @@ impl TraceIngestor for OtlpIngestor {
         header[0] == 0x0A
     }
 
+    fn detect(&self, header: &[u8], content_type: Option<&str>) -> Detection {
+        if let Some(ct) = content_type.and_then(ContentType::parse) {
+            if ct.is_mime("application/x-protobuf")
+                || ct.is_mime("application/protobuf")
+                || ct.is_mime("application/grpc")
+            {
+                return Detection::Certain;
+            }
+        }
+
+        // The 0x0A leading byte is a weak signal on its own - it's just
+        // "field 1, length-delimited", which any protobuf message could
+        // start with - so it scores as merely Likely, not Certain.
+        if header.first() == Some(&0x0A) {
+            Detection::Likely(100)
+        } else {
+            Detection::No
+        }
+    }
+
     fn ingest(&self, data: &[u8]) -> Result<Vec<Trace>> {
-        let request = ExportTraceServiceRequest::decode(data)
-            .map_err(|e| Error::parse("otlp", format!("protobuf decode error: {e}")))?;
+        let request = decode_request(data)?;
 
-        let mut traces_map: HashMap<String, Vec<Span>> = HashMap::new();
+        let total_spans: usize = request
+            .resource_spans
+            .iter()
+            .flat_map(|rs| &rs.scope_spans)
+            .map(|ss| ss.spans.len())
+            .sum();
 
-        for resource_spans in request.resource_spans {
-            // Extract service name from resource attributes
-            let service_name = resource_spans
-                .resource
-                .as_ref()
-                .and_then(|r| {
-                    r.attributes.iter().find_map(|attr| {
-                        if attr.key == "service.name" {
-                            attr.value.as_ref().and_then(extract_string_value)
-                        } else {
-                            None
-                        }
-                    })
-                })
-                .unwrap_or_default();
+        let mut traces_map: HashMap<String, Vec<Span>> = HashMap::with_capacity(total_spans);
+        let mut resource_scopes_map: HashMap<String, Vec<ResourceScope>> = HashMap::new();
 
-            for scope_spans in resource_spans.scope_spans {
-                for otlp_span in scope_spans.spans {
-                    // Convert trace_id and span_id from bytes to hex strings
-                    let trace_id = hex::encode(&otlp_span.trace_id);
-                    let span_id = hex::encode(&otlp_span.span_id);
-                    let parent_span_id = if otlp_span.parent_span_id.is_empty() {
-                        None
-                    } else {
-                        Some(hex::encode(&otlp_span.parent_span_id))
-                    };
-
-                    // Calculate duration from start and end times (nanoseconds)
-                    let duration_ns = otlp_span
-                        .end_time_unix_nano
-                        .saturating_sub(otlp_span.start_time_unix_nano);
-                    let duration = Duration::from_nanos(duration_ns);
-
-                    // Build span
-                    let mut span = Span::new(&span_id, &otlp_span.name)
-                        .with_service(&service_name)
-                        .with_duration(duration)
-                        .with_start_time_ns(otlp_span.start_time_unix_nano)
-                        .with_kind(SpanKind::from_otlp(otlp_span.kind));
-
-                    if let Some(parent) = parent_span_id {
-                        span = span.with_parent(parent);
-                    }
+        for resource_spans in &request.resource_spans {
+            let resource = ResourceContext::new(resource_spans);
 
-                    // Convert status
-                    if let Some(status) = &otlp_span.status {
-                        let code = StatusCode::from_otlp(status.code);
-                        let span_status = if status.message.is_empty() {
-                            SpanStatus::new(code)
-                        } else {
-                            SpanStatus {
-                                code,
-                                message: Some(status.message.clone()),
-                            }
-                        };
-                        span = span.with_status(span_status);
-                    }
+            for scope_spans in &resource_spans.scope_spans {
+                let scope = ScopeContext::new(scope_spans.scope.as_ref());
+                let resource_scope = resource.as_resource_scope(&scope);
 
-                    // Convert attributes
-                    for attr in &otlp_span.attributes {
-                        if let Some(value) = &attr.value {
-                            if let Some(attr_value) = convert_any_value(value) {
-                                span = span.with_attribute(&attr.key, attr_value);
-                            }
-                        }
-                    }
+                for otlp_span in &scope_spans.spans {
+                    let trace_id = hex::encode(&otlp_span.trace_id);
+                    let span = convert_span(otlp_span, &resource, &scope);
+                    traces_map.entry(trace_id.clone()).or_default().push(span);
 
-                    traces_map.entry(trace_id).or_default().push(span);
+                    let groups = resource_scopes_map.entry(trace_id).or_default();
+                    if !groups.contains(&resource_scope) {
+                        groups.push(resource_scope.clone());
+                    }
                 }
             }
         }
 
-        // Convert grouped spans to traces
+        // Convert grouped spans to traces, attaching each trace's
+        // resource/scope groupings by trace ID.
         let traces = traces_map
             .into_iter()
-            .map(|(trace_id, spans)| Trace::from_spans(trace_id, spans))
+            .map(|(trace_id, spans)| {
+                let resource_scopes = resource_scopes_map.remove(&trace_id).unwrap_or_default();
+                Trace::from_spans(trace_id, spans).with_resource_scopes(resource_scopes)
+            })
             .collect();
 
         Ok(traces)
     }
 }
 
+/// A resource's resolved identity for one ingest pass: service name plus
+/// flattened non-`service.name` attributes. Computed once per
+/// `ResourceSpans` entry (via [`Self::new`]) instead of per span, and
+/// attached to each of that resource's spans by reference.
+struct ResourceContext {
+    service_name: String,
+    attributes: Vec<(String, AttributeValue)>,
+}
+
+impl ResourceContext {
+    fn new(resource_spans: &ResourceSpans) -> Self {
+        Self {
+            service_name: resource_service_name(resource_spans),
+            attributes: resource_attributes(resource_spans),
+        }
+    }
+
+    /// Summarizes this resource, paired with `scope`, as a
+    /// [`ResourceScope`] for [`Trace::resource_scopes`].
+    fn as_resource_scope(&self, scope: &ScopeContext) -> ResourceScope {
+        ResourceScope {
+            service: self.service_name.clone(),
+            scope_name: (!scope.name.is_empty()).then(|| scope.name.clone()),
+            scope_version: (!scope.version.is_empty()).then(|| scope.version.clone()),
+        }
+    }
+}
+
+/// An instrumentation scope's resolved identity, computed once per
+/// `ScopeSpans` entry (via [`Self::new`]) instead of per span.
+#[derive(Default)]
+struct ScopeContext {
+    name: String,
+    version: String,
+}
+
+impl ScopeContext {
+    fn new(scope: Option<&InstrumentationScope>) -> Self {
+        match scope {
+            Some(scope) => Self {
+                name: scope.name.clone(),
+                version: scope.version.clone(),
+            },
+            None => Self::default(),
+        }
+    }
+}
+
+/// Decodes an `ExportTraceServiceRequest` protobuf into the crate's `Span`
+/// model, without grouping spans into traces.
+///
+/// This is a lower-level alternative to [`OtlpIngestor::ingest`] for
+/// callers that only need the decoded spans themselves (e.g. counting or
+/// inspecting a live collector export) rather than trace-grouped
+/// exemplars. Because `Span` does not carry its own trace ID, grouping
+/// spans back into traces requires [`OtlpIngestor::ingest`] instead.
+///
+/// # Errors
+///
+/// Returns an error if `data` is not a valid `ExportTraceServiceRequest`.
+pub fn spans_from_otlp(data: &[u8]) -> Result<Vec<Span>> {
+    let request = decode_request(data)?;
+
+    let mut spans = Vec::new();
+    for resource_spans in &request.resource_spans {
+        let resource = ResourceContext::new(resource_spans);
+        for scope_spans in &resource_spans.scope_spans {
+            let scope = ScopeContext::new(scope_spans.scope.as_ref());
+            for otlp_span in &scope_spans.spans {
+                spans.push(convert_span(otlp_span, &resource, &scope));
+            }
+        }
+    }
+
+    Ok(spans)
+}
+
+/// Decodes raw bytes as an `ExportTraceServiceRequest` protobuf message.
+fn decode_request(data: &[u8]) -> Result<ExportTraceServiceRequest> {
+    ExportTraceServiceRequest::decode(data)
+        .map_err(|e| Error::parse("otlp", format!("protobuf decode error: {e}")))
+}
+
+/// Extracts the `service.name` resource attribute, defaulting to an empty string.
+fn resource_service_name(resource_spans: &ResourceSpans) -> String {
+    resource_spans
+        .resource
+        .as_ref()
+        .and_then(|r| {
+            r.attributes.iter().find_map(|attr| {
+                if attr.key == "service.name" {
+                    attr.value.as_ref().and_then(extract_string_value)
+                } else {
+                    None
+                }
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Flattens a resource's attributes (other than `service.name`, which is
+/// already carried via [`Span::service`]) into dotted-key leaves, so
+/// fields like `service.version`, `deployment.environment`, and
+/// `host.name` survive ingestion instead of being dropped.
+fn resource_attributes(resource_spans: &ResourceSpans) -> Vec<(String, AttributeValue)> {
+    resource_spans
+        .resource
+        .iter()
+        .flat_map(|r| &r.attributes)
+        .filter(|attr| attr.key != "service.name")
+        .flat_map(|attr| {
+            attr.value
+                .as_ref()
+                .map(|value| flatten_any_value(&attr.key, value, DEFAULT_MAX_FLATTEN_DEPTH))
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Converts a single OTLP span into the crate's `Span`, attaching the
+/// given resource and instrumentation scope context.
+///
+/// Resource attributes are applied first so a span's own attributes (and
+/// the scope's `otel.scope.name`/`otel.scope.version`) can override them
+/// on key collision, matching how more specific data wins elsewhere in
+/// this module.
+fn convert_span(
+    otlp_span: &opentelemetry_proto::tonic::trace::v1::Span,
+    resource: &ResourceContext,
+    scope: &ScopeContext,
+) -> Span {
+    let span_id = hex::encode(&otlp_span.span_id);
+    let parent_span_id = if otlp_span.parent_span_id.is_empty() {
+        None
+    } else {
+        Some(hex::encode(&otlp_span.parent_span_id))
+    };
+
+    // Calculate duration from start and end times (nanoseconds)
+    let duration_ns = otlp_span
+        .end_time_unix_nano
+        .saturating_sub(otlp_span.start_time_unix_nano);
+    let duration = Duration::from_nanos(duration_ns);
+
+    let mut span = Span::new(&span_id, &otlp_span.name)
+        .with_service(&resource.service_name)
+        .with_duration(duration)
+        .with_start_time_ns(otlp_span.start_time_unix_nano)
+        .with_kind(SpanKind::from_otlp(otlp_span.kind));
+
+    if let Some(parent) = parent_span_id {
+        span = span.with_parent(parent);
+    }
+
+    // Convert status
+    if let Some(status) = &otlp_span.status {
+        let code = StatusCode::from_otlp(status.code);
+        let span_status = if status.message.is_empty() {
+            SpanStatus::new(code)
+        } else {
+            SpanStatus {
+                code,
+                message: Some(status.message.clone()),
+            }
+        };
+        span = span.with_status(span_status);
+    }
+
+    // Carry resource attributes and instrumentation scope onto the span
+    // so they're reachable after ingestion; span-level attributes applied
+    // below can still override them by key.
+    for (key, value) in &resource.attributes {
+        span = span.with_attribute(key.clone(), value.clone());
+    }
+    if !scope.name.is_empty() {
+        span = span.with_attribute("otel.scope.name", scope.name.clone());
+    }
+    if !scope.version.is_empty() {
+        span = span.with_attribute("otel.scope.version", scope.version.clone());
+    }
+
+    // Convert attributes, flattening structured (kvlist/nested-array)
+    // values into dotted-key leaves instead of dropping or nesting them.
+    for attr in &otlp_span.attributes {
+        if let Some(value) = &attr.value {
+            for (key, attr_value) in flatten_any_value(&attr.key, value, DEFAULT_MAX_FLATTEN_DEPTH)
+            {
+                span = span.with_attribute(&key, attr_value);
+            }
+        }
+    }
+
+    // Convert events (notably `exception` events)
+    for event in &otlp_span.events {
+        let mut span_event = SpanEvent::new(&event.name, event.time_unix_nano);
+        for attr in &event.attributes {
+            if let Some(value) = &attr.value {
+                for (key, attr_value) in
+                    flatten_any_value(&attr.key, value, DEFAULT_MAX_FLATTEN_DEPTH)
+                {
+                    span_event = span_event.with_attribute(&key, attr_value);
+                }
+            }
+        }
+        span = span.with_event(span_event);
+    }
+
+    // Convert links to related spans
+    for link in &otlp_span.links {
+        let mut span_link = SpanLink::new(hex::encode(&link.trace_id), hex::encode(&link.span_id));
+        for attr in &link.attributes {
+            if let Some(value) = &attr.value {
+                for (key, attr_value) in
+                    flatten_any_value(&attr.key, value, DEFAULT_MAX_FLATTEN_DEPTH)
+                {
+                    span_link = span_link.with_attribute(&key, attr_value);
+                }
+            }
+        }
+        span = span.with_link(span_link);
+    }
+
+    span
+}
+
 /// Extracts a string value from an OTLP `AnyValue`.
-fn extract_string_value(value: &AnyValue) -> Option<String> {
+pub(crate) fn extract_string_value(value: &AnyValue) -> Option<String> {
     value.value.as_ref().and_then(|v| match v {
         OtlpValue::StringValue(s) => Some(s.clone()),
         _ => None,
@@ -144,29 +349,164 @@ fn extract_string_value(value: &AnyValue) -> Option<String> {
 }
 
 /// Converts an OTLP `AnyValue` to an `AttributeValue`.
-fn convert_any_value(value: &AnyValue) -> Option<AttributeValue> {
+///
+/// Arrays are converted to the matching homogeneous variant when every
+/// element shares the same scalar type (the common case for OTLP
+/// exporters); a mixed-type array falls back to `StringArray` via each
+/// element's string rendering so it still round-trips into something
+/// policy predicates can match against.
+pub(crate) fn convert_any_value(value: &AnyValue) -> Option<AttributeValue> {
     value.value.as_ref().and_then(|v| match v {
         OtlpValue::StringValue(s) => Some(AttributeValue::String(s.clone())),
         OtlpValue::BoolValue(b) => Some(AttributeValue::Bool(*b)),
         OtlpValue::IntValue(i) => Some(AttributeValue::Int(*i)),
         OtlpValue::DoubleValue(d) => Some(AttributeValue::Float(*d)),
-        OtlpValue::ArrayValue(arr) => {
-            let strings: Vec<String> = arr
+        OtlpValue::BytesValue(bytes) => Some(AttributeValue::Bytes(bytes.clone())),
+        OtlpValue::ArrayValue(arr) => convert_array_value(&arr.values),
+        OtlpValue::KvlistValue(kvlist) => {
+            let map: HashMap<String, AttributeValue> = kvlist
                 .values
                 .iter()
-                .filter_map(extract_string_value)
+                .filter_map(|kv| {
+                    let value = kv.value.as_ref().and_then(convert_any_value)?;
+                    Some((kv.key.clone(), value))
+                })
                 .collect();
-            if strings.is_empty() {
-                None
-            } else {
-                Some(AttributeValue::StringArray(strings))
+            Some(AttributeValue::KvList(map))
+        }
+    })
+}
+
+/// Default nesting limit for [`flatten_any_value`], bounding how many
+/// dotted-key segments a single structured attribute can expand into.
+pub(crate) const DEFAULT_MAX_FLATTEN_DEPTH: usize = 8;
+
+/// Flattens an OTLP `AnyValue` into one or more `(key, AttributeValue)`
+/// leaves, expanding `KvlistValue` entries into `parent.child` dotted keys
+/// and arrays that contain further structure (rather than a uniform scalar
+/// type) into `parent.0`, `parent.1`, ... index keys.
+///
+/// Scalars and uniform scalar arrays are left exactly as
+/// [`convert_any_value`] would produce them, under the unmodified `key`.
+/// Recursion stops once `max_depth` dotted-key segments have been added,
+/// at which point the remaining structure collapses into a single
+/// `KvList`/array attribute instead of continuing to expand keys, so a
+/// pathologically deep payload can't explode into unbounded attributes.
+pub(crate) fn flatten_any_value(
+    key: &str,
+    value: &AnyValue,
+    max_depth: usize,
+) -> Vec<(String, AttributeValue)> {
+    let mut out = Vec::new();
+    flatten_any_value_into(key, value, 0, max_depth, &mut out);
+    out
+}
+
+fn flatten_any_value_into(
+    key: &str,
+    value: &AnyValue,
+    depth: usize,
+    max_depth: usize,
+    out: &mut Vec<(String, AttributeValue)>,
+) {
+    if depth < max_depth {
+        match value.value.as_ref() {
+            Some(OtlpValue::KvlistValue(kvlist)) => {
+                for kv in &kvlist.values {
+                    if let Some(child) = &kv.value {
+                        let child_key = format!("{key}.{}", kv.key);
+                        flatten_any_value_into(&child_key, child, depth + 1, max_depth, out);
+                    }
+                }
+                return;
+            }
+            Some(OtlpValue::ArrayValue(arr)) if has_nested_structure(&arr.values) => {
+                for (i, child) in arr.values.iter().enumerate() {
+                    let child_key = format!("{key}.{i}");
+                    flatten_any_value_into(&child_key, child, depth + 1, max_depth, out);
+                }
+                return;
             }
+            _ => {}
         }
-        OtlpValue::BytesValue(bytes) => Some(AttributeValue::String(hex::encode(bytes))),
-        OtlpValue::KvlistValue(_) => None, // Skip nested key-value lists
+    }
+
+    if let Some(attr_value) = convert_any_value(value) {
+        out.push((key.to_string(), attr_value));
+    }
+}
+
+/// Whether any element of an OTLP array is itself a `KvlistValue` or
+/// `ArrayValue`, i.e. the array can't be represented as one of
+/// [`convert_array_value`]'s uniform scalar variants without losing
+/// structure.
+fn has_nested_structure(values: &[AnyValue]) -> bool {
+    values.iter().any(|v| {
+        matches!(
+            v.value,
+            Some(OtlpValue::KvlistValue(_)) | Some(OtlpValue::ArrayValue(_))
+        )
     })
 }
 
+/// Converts an OTLP array value into the most specific homogeneous
+/// `AttributeValue` array variant it can, falling back to a string array.
+fn convert_array_value(values: &[AnyValue]) -> Option<AttributeValue> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let all_ints: Option<Vec<i64>> = values
+        .iter()
+        .map(|v| match v.value.as_ref() {
+            Some(OtlpValue::IntValue(i)) => Some(*i),
+            _ => None,
+        })
+        .collect();
+    if let Some(ints) = all_ints {
+        return Some(AttributeValue::IntArray(ints));
+    }
+
+    let all_doubles: Option<Vec<f64>> = values
+        .iter()
+        .map(|v| match v.value.as_ref() {
+            Some(OtlpValue::DoubleValue(d)) => Some(*d),
+            _ => None,
+        })
+        .collect();
+    if let Some(doubles) = all_doubles {
+        return Some(AttributeValue::DoubleArray(doubles));
+    }
+
+    let all_bools: Option<Vec<bool>> = values
+        .iter()
+        .map(|v| match v.value.as_ref() {
+            Some(OtlpValue::BoolValue(b)) => Some(*b),
+            _ => None,
+        })
+        .collect();
+    if let Some(bools) = all_bools {
+        return Some(AttributeValue::BoolArray(bools));
+    }
+
+    let all_strings: Option<Vec<String>> = values.iter().map(extract_string_value).collect();
+    if let Some(strings) = all_strings {
+        return Some(AttributeValue::StringArray(strings));
+    }
+
+    // Mixed-type array: fall back to each element's string rendering.
+    let rendered: Vec<String> = values
+        .iter()
+        .filter_map(convert_any_value)
+        .map(|v| v.as_string())
+        .collect();
+    if rendered.is_empty() {
+        None
+    } else {
+        Some(AttributeValue::StringArray(rendered))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -193,8 +533,10 @@ mod tests {
                 scope_spans: vec![ScopeSpans {
                     scope: None,
                     spans: vec![OtlpSpan {
-                        trace_id: vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
-                                       0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10],
+                        trace_id: vec![
+                            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+                            0x0d, 0x0e, 0x0f, 0x10,
+                        ],
                         span_id: vec![0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18],
                         parent_span_id: vec![],
                         name: "test-operation".to_string(),
@@ -248,6 +590,17 @@ mod tests {
         assert!(ingestor.can_handle(&[0x0A, 0x10], None));
     }
 
+    #[test]
+    fn otlp_ingestor_detect_is_certain_on_content_type_but_only_likely_on_header() {
+        let ingestor = OtlpIngestor;
+        assert_eq!(
+            ingestor.detect(&[], Some("application/x-protobuf")),
+            Detection::Certain
+        );
+        assert_eq!(ingestor.detect(&[0x0A, 0x10], None), Detection::Likely(100));
+        assert_eq!(ingestor.detect(b"{}", None), Detection::No);
+    }
+
     #[test]
     fn otlp_ingestor_rejects_json() {
         let ingestor = OtlpIngestor;
@@ -275,6 +628,18 @@ mod tests {
         assert!(span.is_root());
     }
 
+    #[test]
+    fn spans_from_otlp_decodes_flat_spans() {
+        let data = create_test_request();
+
+        let spans = spans_from_otlp(&data).unwrap();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].span_id, "1112131415161718");
+        assert_eq!(spans[0].name, "test-operation");
+        assert_eq!(spans[0].service, "test-service");
+        assert_eq!(spans[0].kind, SpanKind::Server);
+    }
+
     #[test]
     fn otlp_ingestor_extracts_attributes() {
         let ingestor = OtlpIngestor;
@@ -289,6 +654,126 @@ mod tests {
         );
     }
 
+    #[test]
+    fn otlp_ingestor_carries_resource_attributes_and_scope() {
+        use opentelemetry_proto::tonic::common::v1::InstrumentationScope;
+
+        let mut data_request =
+            ExportTraceServiceRequest::decode(&create_test_request()[..]).unwrap();
+        let resource = data_request.resource_spans[0].resource.as_mut().unwrap();
+        resource.attributes.push(KeyValue {
+            key: "deployment.environment".to_string(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue("production".to_string())),
+            }),
+        });
+        data_request.resource_spans[0].scope_spans[0].scope = Some(InstrumentationScope {
+            name: "my-instrumentation".to_string(),
+            version: "1.2.3".to_string(),
+            attributes: vec![],
+            dropped_attributes_count: 0,
+        });
+
+        let data = data_request.encode_to_vec();
+        let traces = OtlpIngestor.ingest(&data).unwrap();
+        let span = &traces[0].spans()[0];
+
+        assert_eq!(
+            span.get_attribute("deployment.environment"),
+            Some(&AttributeValue::String("production".to_string()))
+        );
+        assert_eq!(
+            span.get_attribute("otel.scope.name"),
+            Some(&AttributeValue::String("my-instrumentation".to_string()))
+        );
+        assert_eq!(
+            span.get_attribute("otel.scope.version"),
+            Some(&AttributeValue::String("1.2.3".to_string()))
+        );
+        // `service.name` is still carried via `Span::service`, not duplicated as an attribute.
+        assert_eq!(span.get_attribute("service.name"), None);
+    }
+
+    #[test]
+    fn otlp_ingestor_exposes_resource_scope_grouping_on_trace() {
+        use opentelemetry_proto::tonic::common::v1::InstrumentationScope;
+
+        let mut data_request =
+            ExportTraceServiceRequest::decode(&create_test_request()[..]).unwrap();
+        data_request.resource_spans[0].scope_spans[0].scope = Some(InstrumentationScope {
+            name: "my-instrumentation".to_string(),
+            version: "1.2.3".to_string(),
+            attributes: vec![],
+            dropped_attributes_count: 0,
+        });
+
+        let data = data_request.encode_to_vec();
+        let traces = OtlpIngestor.ingest(&data).unwrap();
+
+        assert_eq!(
+            traces[0].resource_scopes,
+            vec![crate::trace::ResourceScope {
+                service: "test-service".to_string(),
+                scope_name: Some("my-instrumentation".to_string()),
+                scope_version: Some("1.2.3".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn otlp_ingestor_dedups_resource_scope_groupings_across_spans_of_the_same_trace() {
+        let mut data_request =
+            ExportTraceServiceRequest::decode(&create_test_request()[..]).unwrap();
+        let second_span = data_request.resource_spans[0].scope_spans[0].spans[0].clone();
+        data_request.resource_spans[0].scope_spans[0]
+            .spans
+            .push(second_span);
+
+        let data = data_request.encode_to_vec();
+        let traces = OtlpIngestor.ingest(&data).unwrap();
+
+        assert_eq!(traces[0].span_count, 2);
+        assert_eq!(traces[0].resource_scopes.len(), 1);
+    }
+
+    #[test]
+    fn otlp_ingestor_converts_exception_events_and_links() {
+        use opentelemetry_proto::tonic::trace::v1::span::{Event, Link};
+
+        let mut data_request =
+            ExportTraceServiceRequest::decode(&create_test_request()[..]).unwrap();
+        let otlp_span = &mut data_request.resource_spans[0].scope_spans[0].spans[0];
+        otlp_span.events.push(Event {
+            time_unix_nano: 1_050_000_000,
+            name: "exception".to_string(),
+            attributes: vec![KeyValue {
+                key: "exception.type".to_string(),
+                value: Some(AnyValue {
+                    value: Some(any_value::Value::StringValue("RuntimeError".to_string())),
+                }),
+            }],
+            dropped_attributes_count: 0,
+        });
+        otlp_span.links.push(Link {
+            trace_id: vec![0xaa; 16],
+            span_id: vec![0xbb; 8],
+            trace_state: String::new(),
+            attributes: vec![],
+            dropped_attributes_count: 0,
+            flags: 0,
+        });
+
+        let data = data_request.encode_to_vec();
+        let spans = spans_from_otlp(&data).unwrap();
+        let span = &spans[0];
+
+        assert!(span.has_exception());
+        assert_eq!(span.exception_type(), Some("RuntimeError"));
+        assert_eq!(span.links.len(), 1);
+        assert_eq!(span.links[0].trace_id, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        assert_eq!(span.links[0].span_id, "bbbbbbbbbbbbbbbb");
+    }
+
     #[test]
     fn convert_any_value_string() {
         let value = AnyValue {
@@ -315,4 +800,223 @@ mod tests {
         };
         assert_eq!(convert_any_value(&value), Some(AttributeValue::Bool(true)));
     }
+
+    #[test]
+    fn convert_any_value_bytes() {
+        let value = AnyValue {
+            value: Some(any_value::Value::BytesValue(vec![0xca, 0xfe])),
+        };
+        assert_eq!(
+            convert_any_value(&value),
+            Some(AttributeValue::Bytes(vec![0xca, 0xfe]))
+        );
+    }
+
+    #[test]
+    fn convert_any_value_homogeneous_int_array() {
+        use opentelemetry_proto::tonic::common::v1::ArrayValue;
+
+        let value = AnyValue {
+            value: Some(any_value::Value::ArrayValue(ArrayValue {
+                values: vec![
+                    AnyValue {
+                        value: Some(any_value::Value::IntValue(1)),
+                    },
+                    AnyValue {
+                        value: Some(any_value::Value::IntValue(2)),
+                    },
+                ],
+            })),
+        };
+        assert_eq!(
+            convert_any_value(&value),
+            Some(AttributeValue::IntArray(vec![1, 2]))
+        );
+    }
+
+    #[test]
+    fn convert_any_value_kvlist() {
+        use opentelemetry_proto::tonic::common::v1::KeyValueList;
+
+        let value = AnyValue {
+            value: Some(any_value::Value::KvlistValue(KeyValueList {
+                values: vec![KeyValue {
+                    key: "db.operation".to_string(),
+                    value: Some(AnyValue {
+                        value: Some(any_value::Value::StringValue("SELECT".to_string())),
+                    }),
+                }],
+            })),
+        };
+
+        let converted = convert_any_value(&value).unwrap();
+        match converted {
+            AttributeValue::KvList(map) => {
+                assert_eq!(
+                    map.get("db.operation"),
+                    Some(&AttributeValue::String("SELECT".to_string()))
+                );
+            }
+            other => panic!("expected KvList, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn flatten_any_value_expands_kvlist_into_dotted_keys() {
+        use opentelemetry_proto::tonic::common::v1::KeyValueList;
+
+        let value = AnyValue {
+            value: Some(any_value::Value::KvlistValue(KeyValueList {
+                values: vec![
+                    KeyValue {
+                        key: "content-type".to_string(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue(
+                                "application/json".to_string(),
+                            )),
+                        }),
+                    },
+                    KeyValue {
+                        key: "content-length".to_string(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::IntValue(42)),
+                        }),
+                    },
+                ],
+            })),
+        };
+
+        let mut leaves =
+            flatten_any_value("http.request.header", &value, DEFAULT_MAX_FLATTEN_DEPTH);
+        leaves.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            leaves,
+            vec![
+                (
+                    "http.request.header.content-length".to_string(),
+                    AttributeValue::Int(42)
+                ),
+                (
+                    "http.request.header.content-type".to_string(),
+                    AttributeValue::String("application/json".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn flatten_any_value_recurses_into_nested_kvlists() {
+        use opentelemetry_proto::tonic::common::v1::KeyValueList;
+
+        let inner = AnyValue {
+            value: Some(any_value::Value::KvlistValue(KeyValueList {
+                values: vec![KeyValue {
+                    key: "id".to_string(),
+                    value: Some(AnyValue {
+                        value: Some(any_value::Value::StringValue("42".to_string())),
+                    }),
+                }],
+            })),
+        };
+        let outer = AnyValue {
+            value: Some(any_value::Value::KvlistValue(KeyValueList {
+                values: vec![KeyValue {
+                    key: "params".to_string(),
+                    value: Some(inner),
+                }],
+            })),
+        };
+
+        let leaves = flatten_any_value("db.statement", &outer, DEFAULT_MAX_FLATTEN_DEPTH);
+        assert_eq!(
+            leaves,
+            vec![(
+                "db.statement.params.id".to_string(),
+                AttributeValue::String("42".to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn flatten_any_value_indexes_arrays_containing_structure() {
+        use opentelemetry_proto::tonic::common::v1::KeyValueList;
+
+        let first = AnyValue {
+            value: Some(any_value::Value::KvlistValue(KeyValueList {
+                values: vec![KeyValue {
+                    key: "name".to_string(),
+                    value: Some(AnyValue {
+                        value: Some(any_value::Value::StringValue("alice".to_string())),
+                    }),
+                }],
+            })),
+        };
+        let value = AnyValue {
+            value: Some(any_value::Value::ArrayValue(
+                opentelemetry_proto::tonic::common::v1::ArrayValue {
+                    values: vec![first],
+                },
+            )),
+        };
+
+        let leaves = flatten_any_value("users", &value, DEFAULT_MAX_FLATTEN_DEPTH);
+        assert_eq!(
+            leaves,
+            vec![(
+                "users.0.name".to_string(),
+                AttributeValue::String("alice".to_string())
+            )]
+        );
+    }
+
+    #[test]
+    fn flatten_any_value_leaves_uniform_scalar_arrays_intact() {
+        let value = AnyValue {
+            value: Some(any_value::Value::ArrayValue(
+                opentelemetry_proto::tonic::common::v1::ArrayValue {
+                    values: vec![
+                        AnyValue {
+                            value: Some(any_value::Value::IntValue(1)),
+                        },
+                        AnyValue {
+                            value: Some(any_value::Value::IntValue(2)),
+                        },
+                    ],
+                },
+            )),
+        };
+
+        let leaves = flatten_any_value("retry.delays_ms", &value, DEFAULT_MAX_FLATTEN_DEPTH);
+        assert_eq!(
+            leaves,
+            vec![(
+                "retry.delays_ms".to_string(),
+                AttributeValue::IntArray(vec![1, 2])
+            )]
+        );
+    }
+
+    #[test]
+    fn flatten_any_value_stops_at_max_depth() {
+        use opentelemetry_proto::tonic::common::v1::KeyValueList;
+
+        // A single level of nesting, but max_depth of 0 means no dotted
+        // expansion is allowed; the whole kvlist collapses into one
+        // `KvList`-valued attribute under the original key instead.
+        let value = AnyValue {
+            value: Some(any_value::Value::KvlistValue(KeyValueList {
+                values: vec![KeyValue {
+                    key: "id".to_string(),
+                    value: Some(AnyValue {
+                        value: Some(any_value::Value::StringValue("42".to_string())),
+                    }),
+                }],
+            })),
+        };
+
+        let leaves = flatten_any_value("params", &value, 0);
+        assert_eq!(leaves.len(), 1);
+        assert_eq!(leaves[0].0, "params");
+        assert!(matches!(leaves[0].1, AttributeValue::KvList(_)));
+    }
 }
@@ -0,0 +1,799 @@
+//! OTLP JSON trace ingestor.
+//!
+//! Handles OpenTelemetry Protocol (OTLP) trace data in the proto3 JSON
+//! mapping that collectors and the OTLP HTTP/JSON exporter emit, sharing
+//! its attribute-conversion logic with [`super::otlp`] so both transports
+//! produce identical results. This module is only available when the
+//! `otlp` feature is enabled.
+
+use crate::error::{Error, Result};
+use crate::ingestor::otlp::{extract_string_value, flatten_any_value, DEFAULT_MAX_FLATTEN_DEPTH};
+use crate::ingestor::{ContentType, Detection, TraceIngestor};
+use crate::span::{AttributeValue, Span, SpanEvent, SpanKind, SpanLink, SpanStatus, StatusCode};
+use crate::trace::Trace;
+use opentelemetry_proto::tonic::common::v1::any_value::Value as OtlpValue;
+use opentelemetry_proto::tonic::common::v1::{AnyValue, ArrayValue, KeyValue, KeyValueList};
+use serde::{Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Names of the `SpanKind` proto3 enum, indexed by their wire number -
+/// the proto3 JSON mapping allows an enum to arrive as either this name
+/// or its number.
+const SPAN_KIND_NAMES: [&str; 6] = [
+    "SPAN_KIND_UNSPECIFIED",
+    "SPAN_KIND_INTERNAL",
+    "SPAN_KIND_SERVER",
+    "SPAN_KIND_CLIENT",
+    "SPAN_KIND_PRODUCER",
+    "SPAN_KIND_CONSUMER",
+];
+
+/// Names of the `Status.StatusCode` proto3 enum, indexed by wire number.
+const STATUS_CODE_NAMES: [&str; 3] = ["STATUS_CODE_UNSET", "STATUS_CODE_OK", "STATUS_CODE_ERROR"];
+
+/// Ingestor for OTLP trace data encoded as proto3 JSON (as opposed to
+/// [`super::otlp::OtlpIngestor`], which handles the protobuf wire
+/// format).
+///
+/// Decodes an `ExportTraceServiceRequest` JSON document and converts it
+/// to the internal trace representation, sharing attribute-conversion
+/// logic with the protobuf ingestor so both transports agree exactly on
+/// the resulting `Span`s.
+pub struct OtlpJsonIngestor;
+
+impl TraceIngestor for OtlpJsonIngestor {
+    fn format_name(&self) -> &'static str {
+        "otlp-json"
+    }
+
+    fn can_handle(&self, header: &[u8], content_type: Option<&str>) -> bool {
+        let is_json_content_type = content_type
+            .and_then(ContentType::parse)
+            .is_some_and(|ct| ct.is_mime("application/json"));
+
+        let Ok(header_str) = std::str::from_utf8(header) else {
+            return false;
+        };
+        let trimmed = header_str.trim_start();
+        let looks_like_json_object = trimmed.starts_with('{');
+        let looks_like_otlp = looks_like_otlp_shape(header_str);
+
+        looks_like_otlp && (is_json_content_type || looks_like_json_object)
+    }
+
+    fn detect(&self, header: &[u8], content_type: Option<&str>) -> Detection {
+        let Ok(header_str) = std::str::from_utf8(header) else {
+            return Detection::No;
+        };
+
+        if !looks_like_otlp_shape(header_str) {
+            return Detection::No;
+        }
+
+        let is_json_content_type = content_type
+            .and_then(ContentType::parse)
+            .is_some_and(|ct| ct.is_mime("application/json"));
+
+        // The `resourceSpans` shape is a much sharper signal than plain
+        // JSON's bracket-sniffing, so this outranks `JsonIngestor`
+        // outright even without a content-type hint.
+        if is_json_content_type {
+            Detection::Certain
+        } else {
+            Detection::Likely(150)
+        }
+    }
+
+    fn ingest(&self, data: &[u8]) -> Result<Vec<Trace>> {
+        let request: JsonExportTraceServiceRequest = serde_json::from_slice(data)
+            .map_err(|e| Error::parse("otlp-json", format!("JSON decode error: {e}")))?;
+
+        let mut traces_map: HashMap<String, Vec<Span>> = HashMap::new();
+
+        for resource_spans in &request.resource_spans {
+            let service_name = resource_service_name(resource_spans.resource.as_ref());
+            let resource_attrs = resource_attributes(resource_spans.resource.as_ref());
+
+            for scope_spans in &resource_spans.scope_spans {
+                let scope = scope_spans.scope.as_ref();
+                for json_span in &scope_spans.spans {
+                    let trace_id = hex::encode(decode_id(&json_span.trace_id));
+                    let span = convert_json_span(json_span, &service_name, &resource_attrs, scope);
+                    traces_map.entry(trace_id).or_default().push(span);
+                }
+            }
+        }
+
+        let traces = traces_map
+            .into_iter()
+            .map(|(trace_id, spans)| Trace::from_spans(trace_id, spans))
+            .collect();
+
+        Ok(traces)
+    }
+}
+
+/// Cheap shape sniff: does this payload carry an OTLP-style
+/// `resourceSpans`/`resource_spans` array, as opposed to arbitrary JSON?
+fn looks_like_otlp_shape(header_str: &str) -> bool {
+    header_str.contains("resourceSpans") || header_str.contains("resource_spans")
+}
+
+/// Extracts the `service.name` resource attribute, defaulting to an empty string.
+fn resource_service_name(resource: Option<&JsonResource>) -> String {
+    resource
+        .and_then(|r| {
+            r.attributes.iter().find_map(|attr| {
+                if attr.key == "service.name" {
+                    attr.value
+                        .as_ref()
+                        .and_then(|v| extract_string_value(&json_any_value_to_otlp(v)))
+                } else {
+                    None
+                }
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Flattens a resource's attributes (other than `service.name`) into
+/// dotted-key leaves, the JSON-shaped counterpart to
+/// `otlp::resource_attributes`.
+fn resource_attributes(resource: Option<&JsonResource>) -> Vec<(String, AttributeValue)> {
+    resource
+        .iter()
+        .flat_map(|r| &r.attributes)
+        .filter(|attr| attr.key != "service.name")
+        .flat_map(|attr| {
+            attr.value
+                .as_ref()
+                .map(|v| {
+                    flatten_any_value(
+                        &attr.key,
+                        &json_any_value_to_otlp(v),
+                        DEFAULT_MAX_FLATTEN_DEPTH,
+                    )
+                })
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Converts a single JSON OTLP span into the crate's `Span`, attaching
+/// the given service name, resource attributes, and instrumentation
+/// scope - the JSON-shaped counterpart to `otlp::convert_span`.
+fn convert_json_span(
+    json_span: &JsonSpan,
+    service_name: &str,
+    resource_attrs: &[(String, AttributeValue)],
+    scope: Option<&JsonInstrumentationScope>,
+) -> Span {
+    let span_id = hex::encode(decode_id(&json_span.span_id));
+    let parent_span_id = if json_span.parent_span_id.is_empty() {
+        None
+    } else {
+        Some(hex::encode(decode_id(&json_span.parent_span_id)))
+    };
+
+    let duration_ns = json_span
+        .end_time_unix_nano
+        .0
+        .saturating_sub(json_span.start_time_unix_nano.0);
+    let duration = Duration::from_nanos(duration_ns);
+
+    let mut span = Span::new(&span_id, &json_span.name)
+        .with_service(service_name)
+        .with_duration(duration)
+        .with_start_time_ns(json_span.start_time_unix_nano.0)
+        .with_kind(SpanKind::from_otlp(
+            json_span.kind.as_code(&SPAN_KIND_NAMES),
+        ));
+
+    if let Some(parent) = parent_span_id {
+        span = span.with_parent(parent);
+    }
+
+    if let Some(status) = &json_span.status {
+        let code = StatusCode::from_otlp(status.code.as_code(&STATUS_CODE_NAMES));
+        let span_status = if status.message.is_empty() {
+            SpanStatus::new(code)
+        } else {
+            SpanStatus {
+                code,
+                message: Some(status.message.clone()),
+            }
+        };
+        span = span.with_status(span_status);
+    }
+
+    for (key, value) in resource_attrs {
+        span = span.with_attribute(key.clone(), value.clone());
+    }
+    if let Some(scope) = scope {
+        if !scope.name.is_empty() {
+            span = span.with_attribute("otel.scope.name", scope.name.clone());
+        }
+        if !scope.version.is_empty() {
+            span = span.with_attribute("otel.scope.version", scope.version.clone());
+        }
+    }
+
+    for attr in &json_span.attributes {
+        for (key, attr_value) in convert_json_attribute(attr) {
+            span = span.with_attribute(&key, attr_value);
+        }
+    }
+
+    for event in &json_span.events {
+        let mut span_event = SpanEvent::new(&event.name, event.time_unix_nano.0);
+        for attr in &event.attributes {
+            for (key, attr_value) in convert_json_attribute(attr) {
+                span_event = span_event.with_attribute(&key, attr_value);
+            }
+        }
+        span = span.with_event(span_event);
+    }
+
+    for link in &json_span.links {
+        let mut span_link = SpanLink::new(
+            hex::encode(decode_id(&link.trace_id)),
+            hex::encode(decode_id(&link.span_id)),
+        );
+        for attr in &link.attributes {
+            for (key, attr_value) in convert_json_attribute(attr) {
+                span_link = span_link.with_attribute(&key, attr_value);
+            }
+        }
+        span = span.with_link(span_link);
+    }
+
+    span
+}
+
+/// Converts one JSON key/value attribute via the tonic `AnyValue` it
+/// mirrors, reusing `otlp::flatten_any_value` so both transports agree,
+/// including the dotted-key expansion of structured (kvlist/nested-array)
+/// values.
+fn convert_json_attribute(attr: &JsonKeyValue) -> Vec<(String, AttributeValue)> {
+    let Some(value) = attr.value.as_ref() else {
+        return Vec::new();
+    };
+    flatten_any_value(
+        &attr.key,
+        &json_any_value_to_otlp(value),
+        DEFAULT_MAX_FLATTEN_DEPTH,
+    )
+}
+
+/// Converts a JSON-mapped OTLP `AnyValue` into the tonic `AnyValue` that
+/// `otlp::convert_any_value`/`otlp::extract_string_value` expect, so
+/// conversion logic never has to be duplicated per transport.
+fn json_any_value_to_otlp(value: &JsonAnyValue) -> AnyValue {
+    let inner = if let Some(s) = &value.string_value {
+        Some(OtlpValue::StringValue(s.clone()))
+    } else if let Some(b) = value.bool_value {
+        Some(OtlpValue::BoolValue(b))
+    } else if let Some(i) = value.int_value {
+        Some(OtlpValue::IntValue(i.0))
+    } else if let Some(d) = value.double_value {
+        Some(OtlpValue::DoubleValue(d))
+    } else if let Some(bytes) = &value.bytes_value {
+        Some(OtlpValue::BytesValue(
+            base64_decode(bytes).unwrap_or_default(),
+        ))
+    } else if let Some(arr) = &value.array_value {
+        Some(OtlpValue::ArrayValue(ArrayValue {
+            values: arr.values.iter().map(json_any_value_to_otlp).collect(),
+        }))
+    } else if let Some(kvlist) = &value.kvlist_value {
+        Some(OtlpValue::KvlistValue(KeyValueList {
+            values: kvlist.values.iter().map(json_key_value_to_otlp).collect(),
+        }))
+    } else {
+        None
+    };
+
+    AnyValue { value: inner }
+}
+
+fn json_key_value_to_otlp(kv: &JsonKeyValue) -> KeyValue {
+    KeyValue {
+        key: kv.key.clone(),
+        value: kv.value.as_ref().map(json_any_value_to_otlp),
+    }
+}
+
+/// Decodes a span/trace ID that may arrive as hex (the common case in
+/// practice, despite the proto3 JSON spec calling for base64) or as
+/// proper base64 bytes.
+fn decode_id(raw: &str) -> Vec<u8> {
+    if !raw.is_empty() && raw.len() % 2 == 0 && raw.bytes().all(|b| b.is_ascii_hexdigit()) {
+        if let Ok(bytes) = hex::decode(raw) {
+            return bytes;
+        }
+    }
+    base64_decode(raw).unwrap_or_default()
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Decodes standard (padded) base64, for `bytesValue` attributes and any
+/// trace/span ID that isn't plain hex.
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    #[allow(clippy::cast_possible_truncation)]
+    fn value(byte: u8) -> Option<u8> {
+        BASE64_ALPHABET
+            .iter()
+            .position(|&c| c == byte)
+            .map(|p| p as u8)
+    }
+
+    let clean: Vec<u8> = text.bytes().filter(|&b| b != b'=').collect();
+    let mut out = Vec::with_capacity(clean.len() * 3 / 4);
+
+    for chunk in clean.chunks(4) {
+        let values: Vec<u8> = chunk.iter().map(|&b| value(b)).collect::<Option<_>>()?;
+
+        out.push((values[0] << 2) | (values.get(1).copied().unwrap_or(0) >> 4));
+        if values.len() > 2 {
+            out.push((values[1] << 4) | (values[2] >> 2));
+        }
+        if values.len() > 3 {
+            out.push((values[2] << 6) | values[3]);
+        }
+    }
+
+    Some(out)
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct JsonExportTraceServiceRequest {
+    #[serde(default, alias = "resource_spans")]
+    resource_spans: Vec<JsonResourceSpans>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonResourceSpans {
+    #[serde(default)]
+    resource: Option<JsonResource>,
+    #[serde(default, alias = "scope_spans")]
+    scope_spans: Vec<JsonScopeSpans>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct JsonResource {
+    #[serde(default)]
+    attributes: Vec<JsonKeyValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonScopeSpans {
+    #[serde(default)]
+    scope: Option<JsonInstrumentationScope>,
+    #[serde(default)]
+    spans: Vec<JsonSpan>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct JsonInstrumentationScope {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    version: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSpan {
+    #[serde(alias = "trace_id")]
+    trace_id: String,
+    #[serde(alias = "span_id")]
+    span_id: String,
+    #[serde(default, alias = "parent_span_id")]
+    parent_span_id: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    kind: JsonEnumValue,
+    #[serde(default, alias = "start_time_unix_nano")]
+    start_time_unix_nano: JsonU64,
+    #[serde(default, alias = "end_time_unix_nano")]
+    end_time_unix_nano: JsonU64,
+    #[serde(default)]
+    attributes: Vec<JsonKeyValue>,
+    #[serde(default)]
+    status: Option<JsonStatus>,
+    #[serde(default)]
+    events: Vec<JsonEvent>,
+    #[serde(default)]
+    links: Vec<JsonLink>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct JsonStatus {
+    #[serde(default)]
+    code: JsonEnumValue,
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonEvent {
+    #[serde(default, alias = "time_unix_nano")]
+    time_unix_nano: JsonU64,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    attributes: Vec<JsonKeyValue>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonLink {
+    #[serde(alias = "trace_id")]
+    trace_id: String,
+    #[serde(alias = "span_id")]
+    span_id: String,
+    #[serde(default)]
+    attributes: Vec<JsonKeyValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonKeyValue {
+    key: String,
+    #[serde(default)]
+    value: Option<JsonAnyValue>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct JsonAnyValue {
+    #[serde(default, alias = "string_value")]
+    string_value: Option<String>,
+    #[serde(default, alias = "bool_value")]
+    bool_value: Option<bool>,
+    #[serde(default, alias = "int_value")]
+    int_value: Option<JsonI64>,
+    #[serde(default, alias = "double_value")]
+    double_value: Option<f64>,
+    #[serde(default, alias = "bytes_value")]
+    bytes_value: Option<String>,
+    #[serde(default, alias = "array_value")]
+    array_value: Option<JsonArrayValue>,
+    #[serde(default, alias = "kvlist_value")]
+    kvlist_value: Option<JsonKvListValue>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct JsonArrayValue {
+    #[serde(default)]
+    values: Vec<JsonAnyValue>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct JsonKvListValue {
+    #[serde(default)]
+    values: Vec<JsonKeyValue>,
+}
+
+/// An OTLP proto3-JSON enum, which may arrive as either its number or
+/// its name (e.g. `2` or `"SPAN_KIND_SERVER"`).
+#[derive(Debug, Clone, Default)]
+enum JsonEnumValue {
+    #[default]
+    Unset,
+    Number(i64),
+    Name(String),
+}
+
+impl JsonEnumValue {
+    /// Resolves this value to its wire number, looking it up by name in
+    /// `names` (indexed by wire number) when it arrived as a string.
+    #[allow(clippy::cast_possible_wrap)]
+    fn as_code(&self, names: &[&str]) -> i32 {
+        match self {
+            Self::Unset => 0,
+            Self::Number(n) => i32::try_from(*n).unwrap_or(0),
+            Self::Name(s) => s
+                .parse::<i32>()
+                .ok()
+                .or_else(|| names.iter().position(|n| n == s).map(|i| i as i32))
+                .unwrap_or(0),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JsonEnumValue {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Number(i64),
+            Name(String),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Number(n) => JsonEnumValue::Number(n),
+            Repr::Name(s) => JsonEnumValue::Name(s),
+        })
+    }
+}
+
+/// A proto3 int64/uint64, which the JSON mapping encodes as a string to
+/// avoid precision loss in JS number parsers, though some exporters send
+/// a plain JSON number instead - this accepts either.
+#[derive(Debug, Clone, Copy, Default)]
+struct JsonU64(u64);
+
+impl<'de> Deserialize<'de> for JsonU64 {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Number(u64),
+            Str(String),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Number(n) => JsonU64(n),
+            Repr::Str(s) => JsonU64(s.parse().unwrap_or(0)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct JsonI64(i64);
+
+impl<'de> Deserialize<'de> for JsonI64 {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Number(i64),
+            Str(String),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Number(n) => JsonI64(n),
+            Repr::Str(s) => JsonI64(s.parse().unwrap_or(0)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_json() -> Vec<u8> {
+        br#"{
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [{"key": "service.name", "value": {"stringValue": "test-service"}}]
+                },
+                "scopeSpans": [{
+                    "spans": [{
+                        "traceId": "0102030405060708090a0b0c0d0e0f10",
+                        "spanId": "1112131415161718",
+                        "name": "test-operation",
+                        "kind": "SPAN_KIND_SERVER",
+                        "startTimeUnixNano": "1000000000",
+                        "endTimeUnixNano": "1100000000",
+                        "attributes": [{"key": "http.status_code", "value": {"intValue": "200"}}],
+                        "status": {"code": "STATUS_CODE_OK"},
+                        "events": [{
+                            "timeUnixNano": "1050000000",
+                            "name": "exception",
+                            "attributes": [{"key": "exception.type", "value": {"stringValue": "RuntimeError"}}]
+                        }],
+                        "links": [{
+                            "traceId": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                            "spanId": "bbbbbbbbbbbbbbbb"
+                        }]
+                    }]
+                }]
+            }]
+        }"#
+        .to_vec()
+    }
+
+    #[test]
+    fn otlp_json_ingestor_format_name() {
+        assert_eq!(OtlpJsonIngestor.format_name(), "otlp-json");
+    }
+
+    #[test]
+    fn otlp_json_ingestor_can_handle_otlp_shaped_json() {
+        let ingestor = OtlpJsonIngestor;
+        assert!(ingestor.can_handle(br#"{"resourceSpans": []}"#, Some("application/json")));
+        assert!(ingestor.can_handle(br#"{"resourceSpans": []}"#, None));
+        assert!(!ingestor.can_handle(br#"{"traces": []}"#, Some("application/json")));
+    }
+
+    #[test]
+    fn otlp_json_ingestor_detect_outranks_plain_json() {
+        let ingestor = OtlpJsonIngestor;
+        assert_eq!(
+            ingestor.detect(br#"{"resourceSpans": []}"#, Some("application/json")),
+            Detection::Certain
+        );
+        assert_eq!(
+            ingestor.detect(br#"{"resourceSpans": []}"#, None),
+            Detection::Likely(150)
+        );
+        assert_eq!(ingestor.detect(br#"{"traces": []}"#, None), Detection::No);
+    }
+
+    #[test]
+    fn otlp_json_ingestor_ingest() {
+        let ingestor = OtlpJsonIngestor;
+        let data = sample_json();
+
+        let traces = ingestor.ingest(&data).unwrap();
+        assert_eq!(traces.len(), 1);
+
+        let trace = &traces[0];
+        assert_eq!(trace.trace_id, "0102030405060708090a0b0c0d0e0f10");
+        assert_eq!(trace.service, Some("test-service".to_string()));
+
+        let span = &trace.spans()[0];
+        assert_eq!(span.span_id, "1112131415161718");
+        assert_eq!(span.name, "test-operation");
+        assert_eq!(span.kind, SpanKind::Server);
+        assert_eq!(span.duration, Duration::from_nanos(100_000_000));
+        assert_eq!(span.status.code, StatusCode::Ok);
+        assert_eq!(
+            span.get_attribute("http.status_code"),
+            Some(&AttributeValue::Int(200))
+        );
+    }
+
+    #[test]
+    fn otlp_json_ingestor_converts_exception_events_and_links() {
+        let ingestor = OtlpJsonIngestor;
+        let data = sample_json();
+
+        let traces = ingestor.ingest(&data).unwrap();
+        let span = &traces[0].spans()[0];
+
+        assert!(span.has_exception());
+        assert_eq!(span.exception_type(), Some("RuntimeError"));
+        assert_eq!(span.links().len(), 1);
+        assert_eq!(span.links()[0].trace_id, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        assert_eq!(span.links()[0].span_id, "bbbbbbbbbbbbbbbb");
+    }
+
+    #[test]
+    fn otlp_json_ingestor_carries_resource_attributes_and_scope() {
+        let data = br#"{
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [
+                        {"key": "service.name", "value": {"stringValue": "test-service"}},
+                        {"key": "deployment.environment", "value": {"stringValue": "production"}}
+                    ]
+                },
+                "scopeSpans": [{
+                    "scope": {"name": "my-instrumentation", "version": "1.2.3"},
+                    "spans": [{
+                        "traceId": "0102030405060708090a0b0c0d0e0f10",
+                        "spanId": "1112131415161718",
+                        "name": "test-operation",
+                        "kind": "SPAN_KIND_SERVER",
+                        "startTimeUnixNano": "1000000000",
+                        "endTimeUnixNano": "1100000000"
+                    }]
+                }]
+            }]
+        }"#;
+
+        let traces = OtlpJsonIngestor.ingest(data).unwrap();
+        let span = &traces[0].spans()[0];
+
+        assert_eq!(
+            span.get_attribute("deployment.environment"),
+            Some(&AttributeValue::String("production".to_string()))
+        );
+        assert_eq!(
+            span.get_attribute("otel.scope.name"),
+            Some(&AttributeValue::String("my-instrumentation".to_string()))
+        );
+        assert_eq!(
+            span.get_attribute("otel.scope.version"),
+            Some(&AttributeValue::String("1.2.3".to_string()))
+        );
+        assert_eq!(span.get_attribute("service.name"), None);
+    }
+
+    #[test]
+    fn otlp_json_ingestor_produces_the_same_span_as_the_protobuf_ingestor() {
+        use super::super::otlp::spans_from_otlp;
+        use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+        use opentelemetry_proto::tonic::common::v1::{
+            any_value, AnyValue as PbAnyValue, KeyValue as PbKeyValue,
+        };
+        use opentelemetry_proto::tonic::resource::v1::Resource;
+        use opentelemetry_proto::tonic::trace::v1::{
+            ResourceSpans, ScopeSpans, Span as OtlpSpan, Status,
+        };
+        use prost::Message;
+
+        let protobuf_request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                resource: Some(Resource {
+                    attributes: vec![PbKeyValue {
+                        key: "service.name".to_string(),
+                        value: Some(PbAnyValue {
+                            value: Some(any_value::Value::StringValue("test-service".to_string())),
+                        }),
+                    }],
+                    dropped_attributes_count: 0,
+                    entity_refs: vec![],
+                }),
+                scope_spans: vec![ScopeSpans {
+                    scope: None,
+                    spans: vec![OtlpSpan {
+                        trace_id: vec![
+                            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+                            0x0d, 0x0e, 0x0f, 0x10,
+                        ],
+                        span_id: vec![0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18],
+                        parent_span_id: vec![],
+                        name: "test-operation".to_string(),
+                        kind: 2,
+                        start_time_unix_nano: 1_000_000_000,
+                        end_time_unix_nano: 1_100_000_000,
+                        attributes: vec![PbKeyValue {
+                            key: "http.status_code".to_string(),
+                            value: Some(PbAnyValue {
+                                value: Some(any_value::Value::IntValue(200)),
+                            }),
+                        }],
+                        status: Some(Status {
+                            code: 1,
+                            message: String::new(),
+                        }),
+                        dropped_attributes_count: 0,
+                        events: vec![],
+                        dropped_events_count: 0,
+                        links: vec![],
+                        dropped_links_count: 0,
+                        trace_state: String::new(),
+                        flags: 0,
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        };
+
+        let protobuf_span = &spans_from_otlp(&protobuf_request.encode_to_vec()).unwrap()[0];
+
+        let ingestor = OtlpJsonIngestor;
+        let json_span = &ingestor.ingest(&sample_json()).unwrap()[0].spans()[0];
+
+        assert_eq!(protobuf_span.span_id, json_span.span_id);
+        assert_eq!(protobuf_span.name, json_span.name);
+        assert_eq!(protobuf_span.kind, json_span.kind);
+        assert_eq!(protobuf_span.duration, json_span.duration);
+        assert_eq!(protobuf_span.status.code, json_span.status.code);
+        assert_eq!(
+            protobuf_span.get_attribute("http.status_code"),
+            json_span.get_attribute("http.status_code")
+        );
+    }
+}
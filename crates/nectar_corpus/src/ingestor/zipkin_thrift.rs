@@ -0,0 +1,858 @@
+//! Zipkin Thrift trace ingestor.
+//!
+//! Handles Zipkin's classic `list<Span>` wire format, encoded with
+//! Thrift's `TBinaryProtocol`, as emitted by older tracers posting to a
+//! Zipkin collector's `/api/v1/spans` endpoint. This module is only
+//! available when the `zipkin` feature is enabled.
+//!
+//! There is no vendored Thrift codec in this workspace, so decoding is a
+//! small hand-rolled binary reader (mirroring the hand-rolled base64 in
+//! [`super::otlp_json`]) rather than a dependency on a generated Thrift
+//! client.
+
+use crate::error::{Error, Result};
+use crate::ingestor::{ContentType, Detection, TraceIngestor};
+use crate::span::{AttributeValue, Span, SpanEvent, SpanKind};
+use crate::trace::Trace;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Ingestor for Zipkin spans encoded in Thrift binary format.
+///
+/// Decodes a top-level `list<Span>` TBinaryProtocol payload and converts
+/// each entry to the internal `Span`/`Trace` representation.
+pub struct ZipkinThriftIngestor;
+
+impl TraceIngestor for ZipkinThriftIngestor {
+    fn format_name(&self) -> &'static str {
+        "zipkin-thrift"
+    }
+
+    fn can_handle(&self, _header: &[u8], content_type: Option<&str>) -> bool {
+        // Thrift binary has no reliable magic byte (a `list<Span>`'s
+        // first bytes are just the element type and a count, which any
+        // other length-prefixed binary format could coincidentally
+        // produce), so this keys off content-type alone.
+        content_type
+            .and_then(ContentType::parse)
+            .is_some_and(|ct| ct.is_mime("application/x-thrift"))
+    }
+
+    fn detect(&self, header: &[u8], content_type: Option<&str>) -> Detection {
+        if self.can_handle(header, content_type) {
+            Detection::Certain
+        } else {
+            Detection::No
+        }
+    }
+
+    fn ingest(&self, data: &[u8]) -> Result<Vec<Trace>> {
+        let zipkin_spans = decode_span_list(data)?;
+
+        let mut traces_map: HashMap<String, Vec<Span>> = HashMap::new();
+        for zipkin_span in zipkin_spans {
+            let trace_id = zipkin_span.trace_id_hex();
+            traces_map
+                .entry(trace_id)
+                .or_default()
+                .push(convert_span(&zipkin_span));
+        }
+
+        let traces = traces_map
+            .into_iter()
+            .map(|(trace_id, spans)| Trace::from_spans(trace_id, spans))
+            .collect();
+
+        Ok(traces)
+    }
+}
+
+/// Annotation values that mark a span's role, per the classic Zipkin v1
+/// core annotations: `cs`/`cr` bracket the client side of an RPC, `sr`/`ss`
+/// the server side.
+const CLIENT_ANNOTATIONS: [&str; 2] = ["cs", "cr"];
+const SERVER_ANNOTATIONS: [&str; 2] = ["sr", "ss"];
+
+/// A decoded Zipkin Thrift `Annotation`: a timestamped string value,
+/// optionally attributed to a service via its `host` endpoint.
+struct ZipkinAnnotation {
+    timestamp_micros: i64,
+    value: String,
+    host: Option<ZipkinEndpoint>,
+}
+
+/// A decoded Zipkin Thrift `BinaryAnnotation`: a key/value pair tagged
+/// with its `AnnotationType`, converted here directly to the matching
+/// `AttributeValue`.
+struct ZipkinBinaryAnnotation {
+    key: String,
+    value: AttributeValue,
+}
+
+/// A decoded Zipkin Thrift `Endpoint`, reduced to the one field this
+/// ingestor needs.
+struct ZipkinEndpoint {
+    service_name: String,
+}
+
+/// A decoded Zipkin Thrift `Span`, still in wire shape (64-bit IDs,
+/// microsecond timestamps) - the analogue of `otlp_json`'s JSON mirror
+/// structs, converted to the crate's `Span` by [`convert_span`].
+struct ZipkinSpan {
+    trace_id: i64,
+    trace_id_high: Option<i64>,
+    id: i64,
+    parent_id: Option<i64>,
+    name: String,
+    timestamp_micros: Option<i64>,
+    duration_micros: Option<i64>,
+    annotations: Vec<ZipkinAnnotation>,
+    binary_annotations: Vec<ZipkinBinaryAnnotation>,
+}
+
+impl ZipkinSpan {
+    /// Renders `trace_id`/`trace_id_high` as a hex trace ID, 128-bit wide
+    /// if `trace_id_high` was present, 64-bit otherwise.
+    #[allow(clippy::cast_sign_loss)]
+    fn trace_id_hex(&self) -> String {
+        match self.trace_id_high {
+            Some(high) => format!("{:016x}{:016x}", high as u64, self.trace_id as u64),
+            None => format!("{:016x}", self.trace_id as u64),
+        }
+    }
+}
+
+/// Converts a decoded `ZipkinSpan` into the crate's `Span`.
+///
+/// The service name and [`SpanKind`] are derived from the core
+/// annotations' `host` endpoints: a span carrying `sr`/`ss` is a server
+/// span, one carrying `cs`/`cr` is a client span, named after whichever
+/// of those annotations has a `host` set.
+#[allow(clippy::cast_sign_loss)]
+fn convert_span(zipkin_span: &ZipkinSpan) -> Span {
+    let span_id = format!("{:016x}", zipkin_span.id as u64);
+    let start_time_ns = zipkin_span
+        .timestamp_micros
+        .map_or(0, |us| (us as u64).saturating_mul(1000));
+    let duration = zipkin_span
+        .duration_micros
+        .map_or(Duration::ZERO, |us| Duration::from_micros(us as u64));
+
+    let (kind, service_name) = span_role(&zipkin_span.annotations);
+
+    let mut span = Span::new(&span_id, &zipkin_span.name)
+        .with_service(service_name.unwrap_or_default())
+        .with_duration(duration)
+        .with_start_time_ns(start_time_ns)
+        .with_kind(kind);
+
+    if let Some(parent_id) = zipkin_span.parent_id {
+        span = span.with_parent(format!("{:016x}", parent_id as u64));
+    }
+
+    for annotation in &zipkin_span.annotations {
+        let mut event = SpanEvent::new(
+            &annotation.value,
+            (annotation.timestamp_micros as u64).saturating_mul(1000),
+        );
+        if let Some(host) = &annotation.host {
+            event = event.with_attribute("endpoint.service_name", host.service_name.clone());
+        }
+        span = span.with_event(event);
+    }
+
+    for binary_annotation in &zipkin_span.binary_annotations {
+        span = span.with_attribute(&binary_annotation.key, binary_annotation.value.clone());
+    }
+
+    span
+}
+
+/// Derives a span's `SpanKind` and service name from its core
+/// annotations, preferring a server role (`sr`/`ss`) over a client role
+/// (`cs`/`cr`) when (unusually) a span carries both.
+fn span_role(annotations: &[ZipkinAnnotation]) -> (SpanKind, Option<String>) {
+    let host_of = |values: &[&str; 2]| {
+        annotations
+            .iter()
+            .filter(|a| values.contains(&a.value.as_str()))
+            .find_map(|a| a.host.as_ref().map(|h| h.service_name.clone()))
+    };
+
+    let server = annotations
+        .iter()
+        .find(|a| SERVER_ANNOTATIONS.contains(&a.value.as_str()));
+    if server.is_some() {
+        return (SpanKind::Server, host_of(&SERVER_ANNOTATIONS));
+    }
+
+    let client = annotations
+        .iter()
+        .find(|a| CLIENT_ANNOTATIONS.contains(&a.value.as_str()));
+    if client.is_some() {
+        return (SpanKind::Client, host_of(&CLIENT_ANNOTATIONS));
+    }
+
+    (SpanKind::Unspecified, None)
+}
+
+// --- Thrift `TBinaryProtocol` decoding -------------------------------------
+
+/// Thrift wire type tags used by `TBinaryProtocol`.
+mod ttype {
+    pub(super) const BOOL: u8 = 2;
+    pub(super) const BYTE: u8 = 3;
+    pub(super) const DOUBLE: u8 = 4;
+    pub(super) const I16: u8 = 6;
+    pub(super) const I32: u8 = 8;
+    pub(super) const I64: u8 = 10;
+    pub(super) const STRING: u8 = 11;
+    pub(super) const STRUCT: u8 = 12;
+    pub(super) const MAP: u8 = 13;
+    pub(super) const SET: u8 = 14;
+    pub(super) const LIST: u8 = 15;
+}
+
+/// Zipkin's `AnnotationType` enum, as carried by a `BinaryAnnotation`.
+mod annotation_type {
+    pub(super) const BOOL: i32 = 0;
+    pub(super) const BYTES: i32 = 1;
+    pub(super) const I16: i32 = 2;
+    pub(super) const I32: i32 = 3;
+    pub(super) const I64: i32 = 4;
+    pub(super) const DOUBLE: i32 = 5;
+    pub(super) const STRING: i32 = 6;
+}
+
+/// A cursor over a `TBinaryProtocol`-encoded byte slice.
+struct ThriftReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ThriftReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn eof(&self) -> bool {
+        self.pos >= self.buf.len()
+    }
+
+    /// Bytes left to read. Every Thrift struct takes at least one byte (its
+    /// `TType::Stop` terminator), so this bounds how many list elements can
+    /// possibly be present regardless of what a list header's length field
+    /// claims.
+    fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.buf.len())
+            .ok_or_else(|| Error::parse("zipkin-thrift", "unexpected end of input"))?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn read_byte(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn read_i16(&mut self) -> Result<i16> {
+        Ok(i16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn read_i32(&mut self) -> Result<i32> {
+        Ok(i32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn read_i64(&mut self) -> Result<i64> {
+        Ok(i64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_double(&mut self) -> Result<f64> {
+        Ok(f64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_bool(&mut self) -> Result<bool> {
+        Ok(self.read_byte()? != 0)
+    }
+
+    fn read_binary(&mut self) -> Result<Vec<u8>> {
+        let len = self.read_i32()?;
+        let len = usize::try_from(len)
+            .map_err(|_| Error::parse("zipkin-thrift", format!("negative length: {len}")))?;
+        Ok(self.take(len)?.to_vec())
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        let bytes = self.read_binary()?;
+        String::from_utf8(bytes)
+            .map_err(|e| Error::parse("zipkin-thrift", format!("invalid UTF-8 string: {e}")))
+    }
+
+    /// Reads a struct field header, returning `None` at the field list's
+    /// `TType::Stop` terminator.
+    fn read_field_header(&mut self) -> Result<Option<(u8, i16)>> {
+        let field_type = self.read_byte()?;
+        if field_type == 0 {
+            return Ok(None);
+        }
+        let field_id = self.read_i16()?;
+        Ok(Some((field_type, field_id)))
+    }
+
+    /// Reads a list header, returning its element type and length.
+    fn read_list_header(&mut self) -> Result<(u8, usize)> {
+        let element_type = self.read_byte()?;
+        let len = self.read_i32()?;
+        let len = usize::try_from(len)
+            .map_err(|_| Error::parse("zipkin-thrift", format!("negative list length: {len}")))?;
+        Ok((element_type, len))
+    }
+
+    /// Skips a value of the given wire type, for struct fields this
+    /// ingestor doesn't care about.
+    fn skip_value(&mut self, field_type: u8) -> Result<()> {
+        match field_type {
+            ttype::BOOL | ttype::BYTE => {
+                self.read_byte()?;
+            }
+            ttype::DOUBLE => {
+                self.read_double()?;
+            }
+            ttype::I16 => {
+                self.read_i16()?;
+            }
+            ttype::I32 => {
+                self.read_i32()?;
+            }
+            ttype::I64 => {
+                self.read_i64()?;
+            }
+            ttype::STRING => {
+                self.read_binary()?;
+            }
+            ttype::STRUCT => self.skip_struct()?,
+            ttype::MAP => {
+                let key_type = self.read_byte()?;
+                let value_type = self.read_byte()?;
+                let len = self.read_i32()?;
+                let len = usize::try_from(len).unwrap_or(0);
+                for _ in 0..len {
+                    self.skip_value(key_type)?;
+                    self.skip_value(value_type)?;
+                }
+            }
+            ttype::SET | ttype::LIST => {
+                let (element_type, len) = self.read_list_header()?;
+                for _ in 0..len {
+                    self.skip_value(element_type)?;
+                }
+            }
+            other => {
+                return Err(Error::parse(
+                    "zipkin-thrift",
+                    format!("unknown Thrift field type: {other}"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Skips an entire nested struct: its fields until the `TType::Stop`
+    /// terminator, recursing via [`Self::skip_value`] for each field's
+    /// value.
+    fn skip_struct(&mut self) -> Result<()> {
+        while let Some((field_type, _field_id)) = self.read_field_header()? {
+            self.skip_value(field_type)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes a top-level `list<Span>` payload.
+fn decode_span_list(data: &[u8]) -> Result<Vec<ZipkinSpan>> {
+    let mut reader = ThriftReader::new(data);
+    if reader.eof() {
+        return Ok(Vec::new());
+    }
+
+    let (element_type, len) = reader.read_list_header()?;
+    if element_type != ttype::STRUCT {
+        return Err(Error::parse(
+            "zipkin-thrift",
+            format!("expected a list of structs, got element type {element_type}"),
+        ));
+    }
+
+    // `len` comes straight off the wire and is untrusted: cap the
+    // preallocation at the bytes actually remaining, since each claimed
+    // struct consumes at least one of them.
+    let mut spans = Vec::with_capacity(len.min(reader.remaining()));
+    for _ in 0..len {
+        spans.push(read_span(&mut reader)?);
+    }
+    Ok(spans)
+}
+
+/// Reads one `Span` struct, skipping any field this ingestor doesn't use
+/// (e.g. `debug`).
+fn read_span(reader: &mut ThriftReader) -> Result<ZipkinSpan> {
+    let mut trace_id = 0i64;
+    let mut trace_id_high = None;
+    let mut id = 0i64;
+    let mut parent_id = None;
+    let mut name = String::new();
+    let mut timestamp_micros = None;
+    let mut duration_micros = None;
+    let mut annotations = Vec::new();
+    let mut binary_annotations = Vec::new();
+
+    while let Some((field_type, field_id)) = reader.read_field_header()? {
+        match field_id {
+            1 if field_type == ttype::I64 => trace_id = reader.read_i64()?,
+            3 if field_type == ttype::STRING => name = reader.read_string()?,
+            4 if field_type == ttype::I64 => id = reader.read_i64()?,
+            5 if field_type == ttype::I64 => parent_id = Some(reader.read_i64()?),
+            6 if field_type == ttype::LIST => annotations = read_annotation_list(reader)?,
+            8 if field_type == ttype::LIST => {
+                binary_annotations = read_binary_annotation_list(reader)?;
+            }
+            10 if field_type == ttype::I64 => timestamp_micros = Some(reader.read_i64()?),
+            11 if field_type == ttype::I64 => duration_micros = Some(reader.read_i64()?),
+            12 if field_type == ttype::I64 => trace_id_high = Some(reader.read_i64()?),
+            _ => reader.skip_value(field_type)?,
+        }
+    }
+
+    Ok(ZipkinSpan {
+        trace_id,
+        trace_id_high,
+        id,
+        parent_id,
+        name,
+        timestamp_micros,
+        duration_micros,
+        annotations,
+        binary_annotations,
+    })
+}
+
+/// Reads a `list<Annotation>`.
+fn read_annotation_list(reader: &mut ThriftReader) -> Result<Vec<ZipkinAnnotation>> {
+    let (element_type, len) = reader.read_list_header()?;
+    if element_type != ttype::STRUCT {
+        for _ in 0..len {
+            reader.skip_value(element_type)?;
+        }
+        return Ok(Vec::new());
+    }
+
+    // `len` comes straight off the wire and is untrusted: cap the
+    // preallocation at the bytes actually remaining, since each claimed
+    // struct consumes at least one of them.
+    let mut out = Vec::with_capacity(len.min(reader.remaining()));
+    for _ in 0..len {
+        out.push(read_annotation(reader)?);
+    }
+    Ok(out)
+}
+
+/// Reads one `Annotation` struct.
+fn read_annotation(reader: &mut ThriftReader) -> Result<ZipkinAnnotation> {
+    let mut timestamp_micros = 0i64;
+    let mut value = String::new();
+    let mut host = None;
+
+    while let Some((field_type, field_id)) = reader.read_field_header()? {
+        match field_id {
+            1 if field_type == ttype::I64 => timestamp_micros = reader.read_i64()?,
+            2 if field_type == ttype::STRING => value = reader.read_string()?,
+            3 if field_type == ttype::STRUCT => host = Some(read_endpoint(reader)?),
+            _ => reader.skip_value(field_type)?,
+        }
+    }
+
+    Ok(ZipkinAnnotation {
+        timestamp_micros,
+        value,
+        host,
+    })
+}
+
+/// Reads an `Endpoint` struct, keeping only its `service_name`.
+fn read_endpoint(reader: &mut ThriftReader) -> Result<ZipkinEndpoint> {
+    let mut service_name = String::new();
+
+    while let Some((field_type, field_id)) = reader.read_field_header()? {
+        match field_id {
+            3 if field_type == ttype::STRING => service_name = reader.read_string()?,
+            _ => reader.skip_value(field_type)?,
+        }
+    }
+
+    Ok(ZipkinEndpoint { service_name })
+}
+
+/// Reads a `list<BinaryAnnotation>`.
+fn read_binary_annotation_list(reader: &mut ThriftReader) -> Result<Vec<ZipkinBinaryAnnotation>> {
+    let (element_type, len) = reader.read_list_header()?;
+    if element_type != ttype::STRUCT {
+        for _ in 0..len {
+            reader.skip_value(element_type)?;
+        }
+        return Ok(Vec::new());
+    }
+
+    // `len` comes straight off the wire and is untrusted: cap the
+    // preallocation at the bytes actually remaining, since each claimed
+    // struct consumes at least one of them.
+    let mut out = Vec::with_capacity(len.min(reader.remaining()));
+    for _ in 0..len {
+        out.push(read_binary_annotation(reader)?);
+    }
+    Ok(out)
+}
+
+/// Reads one `BinaryAnnotation` struct and converts its tagged value to
+/// the matching `AttributeValue` via [`convert_binary_annotation_value`].
+fn read_binary_annotation(reader: &mut ThriftReader) -> Result<ZipkinBinaryAnnotation> {
+    let mut key = String::new();
+    let mut raw_value = Vec::new();
+    let mut annotation_type_tag = annotation_type::STRING;
+
+    while let Some((field_type, field_id)) = reader.read_field_header()? {
+        match field_id {
+            1 if field_type == ttype::STRING => key = reader.read_string()?,
+            2 if field_type == ttype::STRING => raw_value = reader.read_binary()?,
+            3 if field_type == ttype::I32 => annotation_type_tag = reader.read_i32()?,
+            4 if field_type == ttype::STRUCT => {
+                // Binary annotations (e.g. `ca`/`sa`) carry a host too,
+                // but this ingestor only needs it on core `Annotation`s.
+                read_endpoint(reader)?;
+            }
+            _ => reader.skip_value(field_type)?,
+        }
+    }
+
+    let value = convert_binary_annotation_value(&raw_value, annotation_type_tag)?;
+    Ok(ZipkinBinaryAnnotation { key, value })
+}
+
+/// Converts a `BinaryAnnotation`'s raw bytes to the `AttributeValue`
+/// matching its `AnnotationType` tag.
+fn convert_binary_annotation_value(raw: &[u8], annotation_type_tag: i32) -> Result<AttributeValue> {
+    fn fixed<const N: usize>(raw: &[u8], what: &str) -> Result<[u8; N]> {
+        raw.get(..N)
+            .and_then(|s| s.try_into().ok())
+            .ok_or_else(|| Error::parse("zipkin-thrift", format!("short {what} binary annotation")))
+    }
+
+    match annotation_type_tag {
+        annotation_type::BOOL => Ok(AttributeValue::Bool(raw.first().copied().unwrap_or(0) != 0)),
+        annotation_type::BYTES => Ok(AttributeValue::Bytes(raw.to_vec())),
+        annotation_type::I16 => Ok(AttributeValue::Int(i64::from(i16::from_be_bytes(fixed(
+            raw, "i16",
+        )?)))),
+        annotation_type::I32 => Ok(AttributeValue::Int(i64::from(i32::from_be_bytes(fixed(
+            raw, "i32",
+        )?)))),
+        annotation_type::I64 => Ok(AttributeValue::Int(i64::from_be_bytes(fixed(raw, "i64")?))),
+        annotation_type::DOUBLE => Ok(AttributeValue::Float(f64::from_be_bytes(fixed(
+            raw, "double",
+        )?))),
+        annotation_type::STRING => String::from_utf8(raw.to_vec())
+            .map(AttributeValue::String)
+            .map_err(|e| {
+                Error::parse(
+                    "zipkin-thrift",
+                    format!("invalid UTF-8 binary annotation: {e}"),
+                )
+            }),
+        other => Err(Error::parse(
+            "zipkin-thrift",
+            format!("unknown AnnotationType: {other}"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_i32(out: &mut Vec<u8>, v: i32) {
+        out.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn write_i64(out: &mut Vec<u8>, v: i64) {
+        out.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn write_field_header(out: &mut Vec<u8>, field_type: u8, id: i16) {
+        out.push(field_type);
+        out.extend_from_slice(&id.to_be_bytes());
+    }
+
+    fn write_string_field(out: &mut Vec<u8>, id: i16, s: &str) {
+        write_field_header(out, ttype::STRING, id);
+        write_i32(out, s.len() as i32);
+        out.extend_from_slice(s.as_bytes());
+    }
+
+    fn write_i64_field(out: &mut Vec<u8>, id: i16, v: i64) {
+        write_field_header(out, ttype::I64, id);
+        write_i64(out, v);
+    }
+
+    fn write_list_header(out: &mut Vec<u8>, element_type: u8, len: i32) {
+        out.push(element_type);
+        write_i32(out, len);
+    }
+
+    fn encode_endpoint(service_name: &str) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_string_field(&mut out, 3, service_name);
+        out.push(0); // stop
+        out
+    }
+
+    fn encode_annotation(timestamp_micros: i64, value: &str, host: Option<&str>) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_i64_field(&mut out, 1, timestamp_micros);
+        write_string_field(&mut out, 2, value);
+        if let Some(service) = host {
+            write_field_header(&mut out, ttype::STRUCT, 3);
+            out.extend_from_slice(&encode_endpoint(service));
+        }
+        out.push(0); // stop
+        out
+    }
+
+    fn encode_binary_annotation(key: &str, value: &[u8], annotation_type_tag: i32) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_string_field(&mut out, 1, key);
+        write_field_header(&mut out, ttype::STRING, 2);
+        write_i32(&mut out, value.len() as i32);
+        out.extend_from_slice(value);
+        write_field_header(&mut out, ttype::I32, 3);
+        write_i32(&mut out, annotation_type_tag);
+        out.push(0); // stop
+        out
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn encode_span(
+        trace_id: i64,
+        trace_id_high: Option<i64>,
+        id: i64,
+        parent_id: Option<i64>,
+        name: &str,
+        timestamp_micros: Option<i64>,
+        duration_micros: Option<i64>,
+        annotations: &[Vec<u8>],
+        binary_annotations: &[Vec<u8>],
+    ) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_i64_field(&mut out, 1, trace_id);
+        write_string_field(&mut out, 3, name);
+        write_i64_field(&mut out, 4, id);
+        if let Some(parent_id) = parent_id {
+            write_i64_field(&mut out, 5, parent_id);
+        }
+        write_field_header(&mut out, ttype::LIST, 6);
+        write_list_header(&mut out, ttype::STRUCT, annotations.len() as i32);
+        for annotation in annotations {
+            out.extend_from_slice(annotation);
+        }
+        write_field_header(&mut out, ttype::LIST, 8);
+        write_list_header(&mut out, ttype::STRUCT, binary_annotations.len() as i32);
+        for binary_annotation in binary_annotations {
+            out.extend_from_slice(binary_annotation);
+        }
+        if let Some(timestamp_micros) = timestamp_micros {
+            write_i64_field(&mut out, 10, timestamp_micros);
+        }
+        if let Some(duration_micros) = duration_micros {
+            write_i64_field(&mut out, 11, duration_micros);
+        }
+        if let Some(trace_id_high) = trace_id_high {
+            write_i64_field(&mut out, 12, trace_id_high);
+        }
+        out.push(0); // stop
+        out
+    }
+
+    fn encode_span_list(spans: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_list_header(&mut out, ttype::STRUCT, spans.len() as i32);
+        for span in spans {
+            out.extend_from_slice(span);
+        }
+        out
+    }
+
+    #[test]
+    fn zipkin_thrift_ingestor_format_name() {
+        assert_eq!(ZipkinThriftIngestor.format_name(), "zipkin-thrift");
+    }
+
+    #[test]
+    fn zipkin_thrift_ingestor_can_handle_content_type_only() {
+        let ingestor = ZipkinThriftIngestor;
+        assert!(ingestor.can_handle(&[], Some("application/x-thrift")));
+        assert!(!ingestor.can_handle(&[], Some("application/json")));
+        assert!(!ingestor.can_handle(&[], None));
+    }
+
+    #[test]
+    fn zipkin_thrift_ingestor_detect_is_certain_only_with_content_type() {
+        let ingestor = ZipkinThriftIngestor;
+        assert_eq!(
+            ingestor.detect(&[], Some("application/x-thrift")),
+            Detection::Certain
+        );
+        assert_eq!(
+            ingestor.detect(&[], Some("application/json")),
+            Detection::No
+        );
+        assert_eq!(ingestor.detect(&[0x0c, 0, 0, 0, 1], None), Detection::No);
+    }
+
+    #[test]
+    fn zipkin_thrift_ingestor_ingest_decodes_a_basic_span() {
+        let span = encode_span(
+            0x0102_0304_0506_0708,
+            None,
+            0x1112_1314_1516_1718,
+            None,
+            "test-operation",
+            Some(1_000_000),
+            Some(100_000),
+            &[],
+            &[],
+        );
+        let data = encode_span_list(&[span]);
+
+        let traces = ZipkinThriftIngestor.ingest(&data).unwrap();
+        assert_eq!(traces.len(), 1);
+
+        let trace = &traces[0];
+        assert_eq!(trace.trace_id, "0102030405060708");
+        let span = &trace.spans()[0];
+        assert_eq!(span.span_id, "1112131415161718");
+        assert_eq!(span.name, "test-operation");
+        assert_eq!(span.start_time_ns, 1_000_000_000);
+        assert_eq!(span.duration, Duration::from_micros(100_000));
+        assert!(span.is_root());
+    }
+
+    #[test]
+    fn zipkin_thrift_ingestor_combines_trace_id_high_into_a_128_bit_trace_id() {
+        let span = encode_span(
+            0x0000_0000_0000_0002,
+            Some(0x0000_0000_0000_0001),
+            1,
+            None,
+            "op",
+            None,
+            None,
+            &[],
+            &[],
+        );
+        let data = encode_span_list(&[span]);
+
+        let traces = ZipkinThriftIngestor.ingest(&data).unwrap();
+        assert_eq!(traces[0].trace_id, "00000000000000010000000000000002");
+    }
+
+    #[test]
+    fn zipkin_thrift_ingestor_infers_server_kind_and_service_from_sr_annotation() {
+        let annotation = encode_annotation(1_000_000, "sr", Some("frontend"));
+        let span = encode_span(1, None, 1, None, "op", None, None, &[annotation], &[]);
+        let data = encode_span_list(&[span]);
+
+        let traces = ZipkinThriftIngestor.ingest(&data).unwrap();
+        let span = &traces[0].spans()[0];
+        assert_eq!(span.kind, SpanKind::Server);
+        assert_eq!(span.service, "frontend");
+        assert_eq!(span.events().len(), 1);
+        assert_eq!(span.events()[0].name, "sr");
+    }
+
+    #[test]
+    fn zipkin_thrift_ingestor_infers_client_kind_from_cs_annotation() {
+        let annotation = encode_annotation(1_000_000, "cs", Some("caller"));
+        let span = encode_span(1, None, 1, None, "op", None, None, &[annotation], &[]);
+        let data = encode_span_list(&[span]);
+
+        let traces = ZipkinThriftIngestor.ingest(&data).unwrap();
+        let span = &traces[0].spans()[0];
+        assert_eq!(span.kind, SpanKind::Client);
+        assert_eq!(span.service, "caller");
+    }
+
+    #[test]
+    fn zipkin_thrift_ingestor_converts_binary_annotations_by_type() {
+        let binary_annotations = vec![
+            encode_binary_annotation(
+                "http.status_code",
+                &200i32.to_be_bytes(),
+                annotation_type::I32,
+            ),
+            encode_binary_annotation("http.ok", &[1], annotation_type::BOOL),
+            encode_binary_annotation(
+                "request.size_bytes",
+                &1024i64.to_be_bytes(),
+                annotation_type::I64,
+            ),
+            encode_binary_annotation(
+                "sampling.rate",
+                &0.5f64.to_be_bytes(),
+                annotation_type::DOUBLE,
+            ),
+            encode_binary_annotation("http.method", b"GET", annotation_type::STRING),
+        ];
+        let span = encode_span(1, None, 1, None, "op", None, None, &[], &binary_annotations);
+        let data = encode_span_list(&[span]);
+
+        let traces = ZipkinThriftIngestor.ingest(&data).unwrap();
+        let span = &traces[0].spans()[0];
+
+        assert_eq!(
+            span.get_attribute("http.status_code"),
+            Some(&AttributeValue::Int(200))
+        );
+        assert_eq!(
+            span.get_attribute("http.ok"),
+            Some(&AttributeValue::Bool(true))
+        );
+        assert_eq!(
+            span.get_attribute("request.size_bytes"),
+            Some(&AttributeValue::Int(1024))
+        );
+        assert_eq!(
+            span.get_attribute("sampling.rate"),
+            Some(&AttributeValue::Float(0.5))
+        );
+        assert_eq!(
+            span.get_attribute("http.method"),
+            Some(&AttributeValue::String("GET".to_string()))
+        );
+    }
+
+    #[test]
+    fn zipkin_thrift_ingestor_groups_spans_by_trace_id() {
+        let parent = encode_span(7, None, 1, None, "parent", None, None, &[], &[]);
+        let child = encode_span(7, None, 2, Some(1), "child", None, None, &[], &[]);
+        let data = encode_span_list(&[parent, child]);
+
+        let traces = ZipkinThriftIngestor.ingest(&data).unwrap();
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].span_count, 2);
+    }
+}
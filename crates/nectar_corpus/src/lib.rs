@@ -40,21 +40,30 @@
 #![allow(clippy::must_use_candidate)]
 #![allow(clippy::items_after_statements)]
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary_impl;
+pub mod conversion;
 pub mod corpus;
 pub mod encoder;
 pub mod error;
 pub mod fixtures;
+pub mod fuzz;
 pub mod ingestor;
 pub mod loader;
 pub mod reservoir;
 pub mod span;
 pub mod trace;
 
+pub use conversion::{AttrValue, Conversion};
 pub use corpus::Corpus;
 pub use error::{Error, Result};
-pub use ingestor::{IngestorRegistry, TraceIngestor};
+pub use fuzz::{fuzz_corpus, fuzz_ingest, fuzz_ingest_once, FuzzInput, FuzzOutcome};
+pub use ingestor::{BindFunc, Detection, IngestorRegistry, TraceIngestor};
+#[cfg(feature = "otlp")]
+pub use ingestor::spans_from_otlp;
 pub use reservoir::{
-    EvictionEvent, EvictionReason, Reservoir, ReservoirConfig, ReservoirStats, SamplingStrategy,
+    DecayWeightFn, EvictionEvent, EvictionReason, Histogram, Reservoir, ReservoirConfig,
+    ReservoirStats, SamplingStrategy, TraceWeightFn,
 };
-pub use span::{AttributeValue, Span, SpanKind, SpanStatus, StatusCode};
-pub use trace::Trace;
+pub use span::{AttributeValue, Span, SpanEvent, SpanKind, SpanLink, SpanStatus, StatusCode};
+pub use trace::{CriticalSegment, ResourceScope, Trace};
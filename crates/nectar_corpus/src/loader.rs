@@ -2,9 +2,12 @@
 
 use crate::corpus::Corpus;
 use crate::error::{Error, Result};
+use crate::ingestor::decompress::{self, Compression};
+use crate::ingestor::DEFAULT_MAX_DECOMPRESSED_SIZE;
 use crate::trace::Trace;
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::io::BufRead;
 use std::path::Path;
 use std::time::Duration;
 use tracing::{debug, info, warn};
@@ -102,7 +105,93 @@ impl RawTrace {
     }
 }
 
-/// Parses a duration string like "150ms", "2.5s", "100".
+/// A full OTLP/JSON trace export: a top-level object with a
+/// `resourceSpans` array, the nested shape real OTel collectors and SDKs
+/// emit - as opposed to the flat [`RawTrace`] object [`Corpus::parse_json`]
+/// otherwise expects.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OtlpJsonExport {
+    #[serde(default)]
+    resource_spans: Vec<OtlpResourceSpans>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OtlpResourceSpans {
+    #[serde(default)]
+    resource: OtlpResource,
+    #[serde(default)]
+    scope_spans: Vec<OtlpScopeSpans>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OtlpResource {
+    #[serde(default)]
+    attributes: Vec<OtlpKeyValue>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OtlpScopeSpans {
+    #[serde(default)]
+    spans: Vec<OtlpSpan>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OtlpSpan {
+    #[serde(default)]
+    trace_id: String,
+    #[serde(default)]
+    span_id: String,
+    /// Decimal string nanoseconds since the epoch, per the OTLP/JSON
+    /// proto3 mapping for 64-bit integers.
+    #[serde(default)]
+    start_time_unix_nano: String,
+    #[serde(default)]
+    end_time_unix_nano: String,
+    #[serde(default)]
+    status: Option<OtlpStatus>,
+    #[serde(default)]
+    attributes: Vec<OtlpKeyValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OtlpStatus {
+    /// OTLP `StatusCode`; `2` is `STATUS_CODE_ERROR`.
+    #[serde(default)]
+    code: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OtlpKeyValue {
+    key: String,
+    value: serde_json::Value,
+}
+
+/// Extracts the scalar carried by an OTLP `AnyValue` JSON object (whichever
+/// of `stringValue`/`intValue`/`boolValue`/`doubleValue` is present),
+/// rendered as a string - mirroring how [`RawTrace::into_trace`] renders
+/// its own `extra` attribute values.
+fn otlp_value_to_string(value: &serde_json::Value) -> Option<String> {
+    let obj = value.as_object()?;
+    for key in ["stringValue", "intValue", "boolValue", "doubleValue"] {
+        match obj.get(key) {
+            Some(serde_json::Value::String(s)) => return Some(s.clone()),
+            Some(serde_json::Value::Number(n)) => return Some(n.to_string()),
+            Some(serde_json::Value::Bool(b)) => return Some(b.to_string()),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses a duration string like "150ms", "2.5s", "100", or a compound
+/// form summing several units like "1h30m", "2m15s", "1500us".
+///
+/// Recognizes `ns`, `us`/`µs`, `ms`, `s`, `m`, and `h` suffixes. A bare
+/// integer with no suffix at all is treated as milliseconds; a bare
+/// decimal such as "2.5" is rejected.
 fn parse_duration(s: &str) -> Result<Duration> {
     let s = s.trim();
 
@@ -111,26 +200,97 @@ fn parse_duration(s: &str) -> Result<Duration> {
         return Ok(Duration::from_millis(ms));
     }
 
-    // Parse with suffix
-    if let Some(ms_str) = s.strip_suffix("ms") {
-        let ms: u64 = ms_str
-            .trim()
+    let segment_re = regex::Regex::new(r"(\d+(?:\.\d+)?)(ns|us|µs|ms|h|m|s)")
+        .expect("duration segment regex is valid");
+
+    let mut total = Duration::ZERO;
+    let mut consumed = 0;
+    let mut matched_any = false;
+
+    for cap in segment_re.captures_iter(s) {
+        let whole = cap.get(0).expect("group 0 always matches");
+        if whole.start() != consumed {
+            return Err(Error::InvalidTrace(format!("invalid duration format: {s}")));
+        }
+        consumed = whole.end();
+
+        let value: f64 = cap[1]
             .parse()
             .map_err(|_| Error::InvalidTrace(format!("invalid duration: {s}")))?;
-        return Ok(Duration::from_millis(ms));
+        let secs = match &cap[2] {
+            "ns" => value / 1_000_000_000.0,
+            "us" | "µs" => value / 1_000_000.0,
+            "ms" => value / 1_000.0,
+            "s" => value,
+            "m" => value * 60.0,
+            "h" => value * 3_600.0,
+            _ => unreachable!("regex only captures known units"),
+        };
+        total += Duration::from_secs_f64(secs);
+        matched_any = true;
     }
 
-    if let Some(s_str) = s.strip_suffix('s') {
-        let secs: f64 = s_str
-            .trim()
-            .parse()
-            .map_err(|_| Error::InvalidTrace(format!("invalid duration: {s}")))?;
-        return Ok(Duration::from_secs_f64(secs));
+    if matched_any && consumed == s.len() {
+        return Ok(total);
     }
 
     Err(Error::InvalidTrace(format!("invalid duration format: {s}")))
 }
 
+/// Corpus file formats [`Corpus::load_directory_recursive`] recognizes by
+/// extension, and knows how to load into a [`Corpus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileFormat {
+    /// A `.json` file: a JSON array of traces, or a `{"traces": [...]}`
+    /// wrapper.
+    Json,
+    /// A `.ndjson` or `.jsonl` file: one trace object per line.
+    Ndjson,
+    /// A `.json.gz` file: gzip-compressed JSON, decompressed then parsed
+    /// the same way as [`Self::Json`].
+    GzippedJson,
+}
+
+impl FileFormat {
+    /// Determines the format of a file from its name, or `None` if it
+    /// doesn't match any recognized extension.
+    fn from_file_name(name: &str) -> Option<Self> {
+        let lower = name.to_ascii_lowercase();
+        if lower.ends_with(".json.gz") {
+            Some(Self::GzippedJson)
+        } else if lower.ends_with(".ndjson") || lower.ends_with(".jsonl") {
+            Some(Self::Ndjson)
+        } else if lower.ends_with(".json") {
+            Some(Self::Json)
+        } else {
+            None
+        }
+    }
+
+    /// Loads `path` as this format.
+    fn load(self, path: &Path) -> Result<Corpus> {
+        match self {
+            Self::Json => Corpus::load_file(path),
+            Self::Ndjson => {
+                let file = std::fs::File::open(path)?;
+                Corpus::load_ndjson_reader(std::io::BufReader::new(file))
+            }
+            Self::GzippedJson => {
+                let compressed = std::fs::read(path)?;
+                let json = decompress::decompress(
+                    &compressed,
+                    Compression::Gzip,
+                    DEFAULT_MAX_DECOMPRESSED_SIZE,
+                )?;
+                let json = String::from_utf8(json).map_err(|e| {
+                    Error::LoadError(format!("invalid UTF-8 after gzip decompression: {e}"))
+                })?;
+                Corpus::parse_json(&json)
+            }
+        }
+    }
+}
+
 impl Corpus {
     /// Loads a corpus from a JSON file.
     ///
@@ -185,12 +345,125 @@ impl Corpus {
         Ok(corpus)
     }
 
+    /// Loads a corpus by walking `path` depth-first, recursing into every
+    /// subdirectory it finds.
+    ///
+    /// Hidden entries (names starting with `.`) are skipped, directories
+    /// are recursed into, and files are dispatched by extension to the
+    /// right parser (see [`FileFormat`]) - anything else is skipped
+    /// silently. This lets a whole telemetry dump directory be pointed at
+    /// directly, instead of having to flatten it into one level of
+    /// `.json` files first, the way [`Self::load_directory`] requires.
+    ///
+    /// A file with a recognized extension that fails to parse produces a
+    /// warning and is otherwise skipped, exactly as [`Self::load_directory`]
+    /// already does for `.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` (or a subdirectory under it) cannot be
+    /// read.
+    pub fn load_directory_recursive(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        info!(
+            "Loading corpus recursively from directory {}",
+            path.display()
+        );
+
+        let mut corpus = Self::new();
+        Self::load_directory_recursive_into(path, &mut corpus)?;
+
+        info!("Loaded {} traces from directory tree", corpus.len());
+        Ok(corpus)
+    }
+
+    fn load_directory_recursive_into(dir: &Path, corpus: &mut Self) -> Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            if name.starts_with('.') {
+                continue;
+            }
+
+            if file_path.is_dir() {
+                Self::load_directory_recursive_into(&file_path, corpus)?;
+                continue;
+            }
+
+            let Some(format) = FileFormat::from_file_name(&name) else {
+                continue;
+            };
+
+            debug!("Loading {}", file_path.display());
+            match format.load(&file_path) {
+                Ok(file_corpus) => {
+                    for trace in file_corpus.iter() {
+                        corpus.add(trace.clone());
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to load {}: {}", file_path.display(), e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Loads a corpus from an NDJSON (`.ndjson`/`.jsonl`) reader, one JSON
+    /// trace object per line, parsing and adding each trace as it's read
+    /// rather than buffering the whole input - unlike [`Self::parse_json`],
+    /// which needs the full document in memory to deserialize the
+    /// enclosing array. This keeps memory at O(one trace) regardless of
+    /// how large the underlying export is.
+    ///
+    /// Blank lines are skipped. A malformed line is skipped with a
+    /// warning naming its line number, rather than failing the whole
+    /// stream, mirroring how [`Self::from_raw_traces`] tolerates one
+    /// invalid trace within a JSON array.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a line cannot be read from `reader`.
+    pub fn load_ndjson_reader<R: BufRead>(reader: R) -> Result<Self> {
+        let mut corpus = Self::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            match Self::parse_json_trace(line) {
+                Ok(trace) => corpus.add(trace),
+                Err(e) => warn!("Skipping invalid NDJSON trace at line {}: {e}", i + 1),
+            }
+        }
+
+        Ok(corpus)
+    }
+
     /// Parses a corpus from a JSON string.
     ///
     /// # Errors
     ///
     /// Returns an error if the JSON is invalid.
     pub fn parse_json(json: &str) -> Result<Self> {
+        // A full OTLP/JSON export is a top-level object with a
+        // `resourceSpans` array; detect it by that key before falling
+        // through to the flat array/`{"traces": [...]}` shapes below.
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(json) {
+            if value.get("resourceSpans").is_some() {
+                let export: OtlpJsonExport = serde_json::from_value(value)
+                    .map_err(|e| Error::LoadError(format!("invalid OTLP/JSON export: {e}")))?;
+                return Ok(Self::from_otlp_json(export));
+            }
+        }
+
         // Try parsing as an array first
         if let Ok(traces) = serde_json::from_str::<Vec<RawTrace>>(json) {
             return Ok(Self::from_raw_traces(traces));
@@ -211,6 +484,97 @@ impl Corpus {
         ))
     }
 
+    /// Parses a single JSON trace object, as opposed to an array or a
+    /// `{"traces": [...]}` wrapper.
+    ///
+    /// Used by the NDJSON ingestion path, where each line is one trace
+    /// object rather than a full corpus document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the line is not valid JSON or doesn't match
+    /// the expected trace shape.
+    pub(crate) fn parse_json_trace(line: &str) -> Result<Trace> {
+        let raw: RawTrace = serde_json::from_str(line)
+            .map_err(|e| Error::LoadError(format!("invalid JSON: {e}")))?;
+        raw.into_trace()
+    }
+
+    /// Converts a nested OTLP/JSON export into a corpus, one [`Trace`] per
+    /// span (not grouped by trace ID the way [`Trace::from_spans`] groups
+    /// protobuf OTLP spans - this path produces the same flat,
+    /// one-trace-per-record shape as the rest of [`Self::parse_json`]).
+    fn from_otlp_json(export: OtlpJsonExport) -> Self {
+        let mut corpus = Self::new();
+
+        for resource_spans in export.resource_spans {
+            let service = resource_spans
+                .resource
+                .attributes
+                .iter()
+                .find(|kv| kv.key == "service.name")
+                .and_then(|kv| otlp_value_to_string(&kv.value));
+
+            for scope_spans in resource_spans.scope_spans {
+                for span in scope_spans.spans {
+                    let id = if span.trace_id.is_empty() {
+                        span.span_id.as_str()
+                    } else {
+                        span.trace_id.as_str()
+                    };
+                    let mut trace = Trace::new(id);
+
+                    let start: u64 = span.start_time_unix_nano.parse().unwrap_or(0);
+                    let end: u64 = span.end_time_unix_nano.parse().unwrap_or(0);
+                    trace = trace.with_duration(Duration::from_nanos(end.saturating_sub(start)));
+
+                    if let Some(service) = &service {
+                        trace = trace.with_service(service.clone());
+                    }
+
+                    if let Some(status) = &span.status {
+                        // STATUS_CODE_ERROR
+                        if status.code == 2 {
+                            trace.is_error = true;
+                        }
+                    }
+
+                    let status_code = span
+                        .attributes
+                        .iter()
+                        .find(|kv| kv.key == "http.status_code")
+                        .and_then(|kv| otlp_value_to_string(&kv.value))
+                        .and_then(|s| s.parse::<u16>().ok());
+                    if let Some(status_code) = status_code {
+                        trace = trace.with_status(status_code);
+                    }
+
+                    let endpoint = span
+                        .attributes
+                        .iter()
+                        .find(|kv| kv.key == "http.route")
+                        .and_then(|kv| otlp_value_to_string(&kv.value));
+                    if let Some(endpoint) = endpoint {
+                        trace = trace.with_endpoint(endpoint);
+                    }
+
+                    for attr in &span.attributes {
+                        if matches!(attr.key.as_str(), "http.status_code" | "http.route") {
+                            continue;
+                        }
+                        if let Some(value) = otlp_value_to_string(&attr.value) {
+                            trace = trace.with_attribute(attr.key.clone(), value);
+                        }
+                    }
+
+                    corpus.add(trace);
+                }
+            }
+        }
+
+        corpus
+    }
+
     fn from_raw_traces(raw_traces: Vec<RawTrace>) -> Self {
         let mut corpus = Self::new();
 
@@ -285,6 +649,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_duration_additional_units() {
+        assert_eq!(parse_duration("500ns").unwrap(), Duration::from_nanos(500));
+        assert_eq!(
+            parse_duration("1500us").unwrap(),
+            Duration::from_micros(1500)
+        );
+        assert_eq!(parse_duration("100µs").unwrap(), Duration::from_micros(100));
+        assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+    }
+
+    #[test]
+    fn parse_duration_compound_forms() {
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5400));
+        assert_eq!(parse_duration("2m15s").unwrap(), Duration::from_secs(135));
+    }
+
+    #[test]
+    fn parse_duration_rejects_gaps_and_garbage() {
+        assert!(parse_duration("abc").is_err());
+        assert!(parse_duration("1h 30m").is_err());
+        assert!(parse_duration("10x").is_err());
+    }
+
     #[test]
     fn load_json_array() {
         let json = r#"[
@@ -329,10 +718,198 @@ mod tests {
         assert_eq!(trace.endpoint, Some("/api/v1/users".to_string()));
     }
 
+    #[test]
+    fn parse_json_detects_nested_otlp_export_and_flattens_spans() {
+        let json = r#"{
+            "resourceSpans": [
+                {
+                    "resource": {
+                        "attributes": [
+                            {"key": "service.name", "value": {"stringValue": "checkout"}}
+                        ]
+                    },
+                    "scopeSpans": [
+                        {
+                            "spans": [
+                                {
+                                    "traceId": "abc123",
+                                    "spanId": "def456",
+                                    "startTimeUnixNano": "1000000000",
+                                    "endTimeUnixNano": "1100000000",
+                                    "status": {"code": 2},
+                                    "attributes": [
+                                        {"key": "http.status_code", "value": {"intValue": "500"}},
+                                        {"key": "http.route", "value": {"stringValue": "/checkout"}},
+                                        {"key": "retry.count", "value": {"intValue": "3"}}
+                                    ]
+                                }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        }"#;
+
+        let corpus = Corpus::parse_json(json).unwrap();
+        assert_eq!(corpus.len(), 1);
+
+        let trace = corpus.iter().next().unwrap();
+        assert_eq!(trace.trace_id, "abc123");
+        assert_eq!(trace.duration, Duration::from_millis(100));
+        assert!(trace.is_error);
+        assert_eq!(trace.service, Some("checkout".to_string()));
+        assert_eq!(trace.status, Some(500));
+        assert_eq!(trace.endpoint, Some("/checkout".to_string()));
+        assert_eq!(
+            trace.attributes.get("retry.count").map(String::as_str),
+            Some("3")
+        );
+    }
+
+    #[test]
+    fn parse_json_otlp_falls_back_to_span_id_when_trace_id_is_missing() {
+        let json = r#"{
+            "resourceSpans": [
+                {
+                    "scopeSpans": [
+                        {"spans": [{"spanId": "only-span-id"}]}
+                    ]
+                }
+            ]
+        }"#;
+
+        let corpus = Corpus::parse_json(json).unwrap();
+        assert_eq!(corpus.iter().next().unwrap().trace_id, "only-span-id");
+    }
+
     #[test]
     fn example_corpus_has_errors() {
         let corpus = Corpus::example();
         assert!(!corpus.is_empty());
         assert!(!corpus.errors().is_empty());
     }
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn load_directory_recursive_walks_nested_dirs_and_skips_hidden_entries() {
+        let dir = temp_dir("nectar-loader-recursive");
+        let nested = dir.join("nested");
+        std::fs::create_dir_all(&nested).unwrap();
+        let hidden_dir = dir.join(".hidden");
+        std::fs::create_dir_all(&hidden_dir).unwrap();
+
+        std::fs::write(
+            dir.join("a.json"),
+            r#"[{"trace_id": "a", "duration_ms": 1, "status": 200}]"#,
+        )
+        .unwrap();
+        std::fs::write(
+            nested.join("b.ndjson"),
+            "{\"trace_id\": \"b\", \"duration_ms\": 2, \"status\": 200}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join(".hidden-file.json"), "not even valid json").unwrap();
+        std::fs::write(
+            hidden_dir.join("c.json"),
+            r#"[{"trace_id": "c", "duration_ms": 3, "status": 200}]"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("readme.txt"), "not a trace file").unwrap();
+
+        let corpus = Corpus::load_directory_recursive(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(corpus.len(), 2);
+        let ids: std::collections::HashSet<_> = corpus.iter().map(|t| t.trace_id.clone()).collect();
+        assert_eq!(
+            ids,
+            std::collections::HashSet::from(["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn load_directory_recursive_warns_on_unparseable_files_and_keeps_going() {
+        let dir = temp_dir("nectar-loader-recursive-bad");
+        std::fs::write(dir.join("bad.json"), "not valid json at all").unwrap();
+        std::fs::write(
+            dir.join("good.json"),
+            r#"[{"trace_id": "ok", "duration_ms": 1, "status": 200}]"#,
+        )
+        .unwrap();
+
+        let corpus = Corpus::load_directory_recursive(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(corpus.len(), 1);
+        assert_eq!(corpus.iter().next().unwrap().trace_id, "ok");
+    }
+
+    #[test]
+    #[cfg(feature = "compression")]
+    fn load_directory_recursive_decompresses_json_gz_files() {
+        use std::io::Write;
+
+        let dir = temp_dir("nectar-loader-recursive-gz");
+        let json = r#"[{"trace_id": "gz", "duration_ms": 1, "status": 200}]"#;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(json.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+        std::fs::write(dir.join("traces.json.gz"), compressed).unwrap();
+
+        let corpus = Corpus::load_directory_recursive(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(corpus.len(), 1);
+        assert_eq!(corpus.iter().next().unwrap().trace_id, "gz");
+    }
+
+    #[test]
+    fn load_ndjson_reader_parses_one_trace_per_line() {
+        let ndjson = "{\"trace_id\": \"a\", \"duration_ms\": 1, \"status\": 200}\n\
+                      \n\
+                      {\"trace_id\": \"b\", \"duration_ms\": 2, \"status\": 500}\n";
+
+        let corpus = Corpus::load_ndjson_reader(ndjson.as_bytes()).unwrap();
+
+        assert_eq!(corpus.len(), 2);
+    }
+
+    #[test]
+    fn load_ndjson_reader_warns_and_skips_bad_lines_with_their_line_number() {
+        let ndjson = "{\"trace_id\": \"a\", \"duration_ms\": 1, \"status\": 200}\n\
+                      not valid json\n\
+                      {\"trace_id\": \"b\", \"duration_ms\": 2, \"status\": 200}\n";
+
+        let corpus = Corpus::load_ndjson_reader(ndjson.as_bytes()).unwrap();
+
+        assert_eq!(corpus.len(), 2);
+        let ids: std::collections::HashSet<_> = corpus.iter().map(|t| t.trace_id.clone()).collect();
+        assert_eq!(
+            ids,
+            std::collections::HashSet::from(["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn file_format_dispatches_on_extension() {
+        assert_eq!(FileFormat::from_file_name("a.json"), Some(FileFormat::Json));
+        assert_eq!(
+            FileFormat::from_file_name("a.ndjson"),
+            Some(FileFormat::Ndjson)
+        );
+        assert_eq!(
+            FileFormat::from_file_name("a.jsonl"),
+            Some(FileFormat::Ndjson)
+        );
+        assert_eq!(
+            FileFormat::from_file_name("a.json.gz"),
+            Some(FileFormat::GzippedJson)
+        );
+        assert_eq!(FileFormat::from_file_name("a.txt"), None);
+    }
 }
@@ -1,7 +1,9 @@
 //! Reservoir sampling for bounded corpus growth.
 //!
-//! Implements Algorithm R (Vitter) for uniform reservoir sampling with
-//! extensions for stratified sampling and time-decay weighting.
+//! Implements Algorithm R (Vitter) for uniform reservoir sampling, with
+//! extensions for stratified sampling, weighted time-decay sampling via
+//! the Efraimidis-Spirakis A-Res algorithm, and a skip-counting fast path
+//! (Algorithm L) for uniform sampling at high ingestion rates.
 //!
 //! # Example
 //!
@@ -16,10 +18,16 @@
 //! reservoir.add(trace);
 //! ```
 
+use crate::error::{Error, Result};
 use crate::trace::Trace;
 use rand::Rng;
 use rand_chacha::ChaCha8Rng;
 use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Sampling strategy for the reservoir.
@@ -28,14 +36,40 @@ pub enum SamplingStrategy {
     /// Uniform random sampling (Algorithm R).
     #[default]
     Uniform,
+    /// Uniform random sampling via Algorithm L (Li). Statistically
+    /// equivalent to [`Self::Uniform`], but once the reservoir is full it
+    /// draws a skip count and jumps straight to the next replacement
+    /// instead of rolling the dice on every incoming trace, cutting RNG
+    /// draws from O(n) to O(k log(n/k)).
+    UniformFast,
     /// Stratified sampling preserving error and slow traces.
     Stratified,
     /// Time-decay sampling favoring recent traces.
     TimeDecay,
+    /// Weighted sampling (Efraimidis-Spirakis A-Res) favoring
+    /// high-weight traces, e.g. errors and slow requests, so they
+    /// survive eviction with probability proportional to their weight
+    /// rather than being diluted by uniform sampling under load.
+    Weighted,
 }
 
+/// A per-trace weight function for [`SamplingStrategy::TimeDecay`],
+/// given the trace and the highest trace timestamp (nanoseconds) seen so
+/// far. Defaults to exponential recency decay (see
+/// [`ReservoirConfig::with_decay_weight_fn`]); a custom function lets
+/// other criteria (e.g. span count) drive which traces survive instead
+/// of recency.
+pub type DecayWeightFn = Arc<dyn Fn(&Trace, u64) -> f64 + Send + Sync>;
+
+/// A per-trace weight function for [`SamplingStrategy::Weighted`].
+/// Defaults to `1.0` for normal traces, with a fixed extra weight added
+/// for errors and slow traces; a custom function (see
+/// [`ReservoirConfig::with_weight_fn`]) lets other criteria drive
+/// retention priority instead.
+pub type TraceWeightFn = Arc<dyn Fn(&Trace) -> f64 + Send + Sync>;
+
 /// Configuration for reservoir sampling.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ReservoirConfig {
     /// Maximum number of traces to keep.
     pub max_size: usize,
@@ -47,8 +81,45 @@ pub struct ReservoirConfig {
     pub slow_threshold: Option<Duration>,
     /// Half-life for time-decay sampling (in nanoseconds).
     pub decay_half_life_ns: Option<u64>,
+    /// Overrides the weight function used by `TimeDecay` sampling.
+    pub decay_weight_fn: Option<DecayWeightFn>,
+    /// Overrides the weight function used by `Weighted` sampling.
+    pub weight_fn: Option<TraceWeightFn>,
     /// Random seed for deterministic sampling.
     pub seed: u64,
+    /// Hard byte budget for the corpus's estimated memory footprint (see
+    /// [`crate::trace::Trace::estimated_size`]). When set, `add` evicts
+    /// sampled traces on top of whatever `strategy` already does once
+    /// `estimated_bytes` exceeds this, until back under `soft_ratio` of
+    /// it. Unset by default, meaning no byte-based bound.
+    pub max_bytes: Option<usize>,
+    /// Fraction of `max_bytes` that memory-pressure eviction targets,
+    /// so a single burst doesn't evict down to exactly the hard limit
+    /// only to immediately trip it again on the next trace.
+    pub soft_ratio: f64,
+    /// Token-bucket admission limit: at most `max_per_second` traces are
+    /// admitted into `add` per second of trace time, with up to `burst`
+    /// tokens banked for traffic spikes. Unset by default, meaning no
+    /// admission limiting.
+    pub rate_limit: Option<(f64, f64)>,
+}
+
+impl std::fmt::Debug for ReservoirConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReservoirConfig")
+            .field("max_size", &self.max_size)
+            .field("strategy", &self.strategy)
+            .field("preserve_errors", &self.preserve_errors)
+            .field("slow_threshold", &self.slow_threshold)
+            .field("decay_half_life_ns", &self.decay_half_life_ns)
+            .field("has_decay_weight_fn", &self.decay_weight_fn.is_some())
+            .field("has_weight_fn", &self.weight_fn.is_some())
+            .field("seed", &self.seed)
+            .field("max_bytes", &self.max_bytes)
+            .field("soft_ratio", &self.soft_ratio)
+            .field("rate_limit", &self.rate_limit)
+            .finish()
+    }
 }
 
 impl Default for ReservoirConfig {
@@ -59,7 +130,12 @@ impl Default for ReservoirConfig {
             preserve_errors: false,
             slow_threshold: None,
             decay_half_life_ns: None,
+            decay_weight_fn: None,
+            weight_fn: None,
             seed: 0,
+            max_bytes: None,
+            soft_ratio: 0.9,
+            rate_limit: None,
         }
     }
 }
@@ -74,7 +150,12 @@ impl ReservoirConfig {
             preserve_errors: false,
             slow_threshold: None,
             decay_half_life_ns: None,
+            decay_weight_fn: None,
+            weight_fn: None,
             seed: 0,
+            max_bytes: None,
+            soft_ratio: 0.9,
+            rate_limit: None,
         }
     }
 
@@ -108,12 +189,65 @@ impl ReservoirConfig {
         self
     }
 
+    /// Overrides the per-trace weight function used by `TimeDecay`
+    /// sampling, given the trace and the highest trace timestamp
+    /// (nanoseconds) seen so far. The default (exponential recency decay
+    /// using `decay_half_life_ns`) is used if this is never set.
+    #[must_use]
+    pub fn with_decay_weight_fn(
+        mut self,
+        f: impl Fn(&Trace, u64) -> f64 + Send + Sync + 'static,
+    ) -> Self {
+        self.decay_weight_fn = Some(Arc::new(f));
+        self
+    }
+
+    /// Overrides the per-trace weight function used by `Weighted`
+    /// sampling. The default (`1.0` for normal traces, with a fixed
+    /// extra weight added for errors and traces at or over
+    /// `slow_threshold`) is used if this is never set.
+    #[must_use]
+    pub fn with_weight_fn(mut self, f: impl Fn(&Trace) -> f64 + Send + Sync + 'static) -> Self {
+        self.weight_fn = Some(Arc::new(f));
+        self
+    }
+
     /// Sets the random seed for deterministic sampling.
     #[must_use]
     pub const fn with_seed(mut self, seed: u64) -> Self {
         self.seed = seed;
         self
     }
+
+    /// Bounds the corpus by estimated memory footprint (see
+    /// [`crate::trace::Trace::estimated_size`]) rather than trace count.
+    /// `max_size` still applies as the per-strategy eviction trigger;
+    /// this adds a byte-based check on top of it.
+    #[must_use]
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Sets the fraction of `max_bytes` that memory-pressure eviction
+    /// targets. Defaults to `0.9`.
+    #[must_use]
+    pub const fn with_soft_ratio(mut self, soft_ratio: f64) -> Self {
+        self.soft_ratio = soft_ratio;
+        self
+    }
+
+    /// Caps admission into `add` to a token bucket refilling at
+    /// `max_per_second` trace-time seconds, holding up to `burst` banked
+    /// tokens. Traces arriving once the bucket is empty are shed before
+    /// they reach the sampling strategy (see [`Reservoir::add`]), so
+    /// `total_seen` and sampling probabilities only reflect admitted
+    /// traffic.
+    #[must_use]
+    pub const fn with_rate_limit(mut self, max_per_second: f64, burst: f64) -> Self {
+        self.rate_limit = Some((max_per_second, burst));
+        self
+    }
 }
 
 /// Event emitted when a trace is evicted from the reservoir.
@@ -138,8 +272,12 @@ pub enum EvictionReason {
     RandomSample,
     /// Time-decay priority eviction.
     TimeDecay,
+    /// Weighted (Efraimidis-Spirakis) priority eviction.
+    Weighted,
     /// Stratified rebalancing.
     StratifiedRebalance,
+    /// Dropped to bring the corpus back under its byte budget.
+    MemoryPressure,
 }
 
 /// Statistics about the reservoir.
@@ -155,6 +293,160 @@ pub struct ReservoirStats {
     pub slow_count: usize,
     /// Number of evictions performed.
     pub eviction_count: u64,
+    /// Estimated total in-memory footprint of stored traces, in bytes.
+    pub estimated_bytes: u64,
+    /// Number of traces shed by admission rate limiting (see
+    /// [`ReservoirConfig::with_rate_limit`]) before they reached the
+    /// sampling strategy. Always `0` when no rate limit is configured.
+    pub throttled_count: u64,
+}
+
+/// A discretised latency distribution built from a reservoir's sampled
+/// trace durations, with fixed-width buckets spanning `[min, max]`.
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    /// Exclusive upper bound of each bucket, in ascending order. Bucket
+    /// `i` covers everything from `bucket_bounds[i - 1]` (or the
+    /// histogram's minimum for bucket `0`) up to, but not including,
+    /// `bucket_bounds[i]`; the last bucket also includes its bound, the
+    /// maximum.
+    pub bucket_bounds: Vec<Duration>,
+    /// Number of samples falling in each bucket, parallel to
+    /// `bucket_bounds`.
+    pub counts: Vec<u64>,
+    /// Total number of samples represented by the histogram.
+    pub total: u64,
+}
+
+impl Histogram {
+    /// Returns the duration at the given percentile (`p` in `[0, 100]`)
+    /// by walking cumulative bucket counts to the target rank. The
+    /// result is the upper bound of whichever bucket the rank falls in,
+    /// so it over-estimates within the bucket's width.
+    #[must_use]
+    pub fn percentile(&self, p: f64) -> Duration {
+        if self.total == 0 {
+            return Duration::ZERO;
+        }
+        let p = p.clamp(0.0, 100.0);
+        #[allow(clippy::cast_precision_loss, clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        let target_rank = ((p / 100.0) * self.total as f64).ceil().max(1.0) as u64;
+
+        let mut cumulative = 0u64;
+        for (bound, count) in self.bucket_bounds.iter().zip(&self.counts) {
+            cumulative += count;
+            if cumulative >= target_rank {
+                return *bound;
+            }
+        }
+
+        self.bucket_bounds
+            .last()
+            .copied()
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+/// Wraps a finite `f64` Efraimidis-Spirakis sampling key so it can order
+/// [`TimeDecayEntry`]s in a `BinaryHeap`. Keys are always in `[0, 1]` in
+/// practice, so `partial_cmp`'s `None` (NaN) case never triggers; it
+/// falls back to treating NaN as equal rather than panicking.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HeapKey(f64);
+
+impl Eq for HeapKey {}
+
+impl PartialOrd for HeapKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+/// One entry in `Reservoir`'s time-decay min-heap: a sampling key paired
+/// with the trace's index into `Reservoir::traces`, so popping the
+/// minimum tells us which slot to evict without storing traces twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct TimeDecayEntry {
+    key: HeapKey,
+    index: usize,
+}
+
+/// Admission token bucket backing [`ReservoirConfig::with_rate_limit`].
+/// Tracks `tokens` against `capacity`, refilling at `rate` tokens per
+/// trace-time second as incoming trace timestamps advance. A trace
+/// consumes one token if available, or is shed. Deliberately driven by
+/// trace timestamps rather than the wall clock so admission decisions
+/// stay deterministic and replayable, matching
+/// [`crate::reservoir::Reservoir::add_time_decay`]'s fallback of
+/// synthesising a timestamp from insertion order when a trace carries
+/// none.
+#[derive(Debug, Clone, Copy)]
+struct RateLimiter {
+    capacity: f64,
+    rate: f64,
+    tokens: f64,
+    last_ns: Option<u64>,
+}
+
+impl RateLimiter {
+    fn new(max_per_second: f64, burst: f64) -> Self {
+        Self {
+            capacity: burst,
+            rate: max_per_second,
+            tokens: burst,
+            last_ns: None,
+        }
+    }
+
+    /// Advances the bucket to `now_ns` and attempts to consume one
+    /// token; returns `true` if the trace is admitted, `false` if shed.
+    #[allow(clippy::cast_precision_loss)]
+    fn admit(&mut self, now_ns: u64) -> bool {
+        if let Some(last) = self.last_ns {
+            let delta_s = now_ns.saturating_sub(last) as f64 / 1_000_000_000.0;
+            self.tokens = (self.tokens + delta_s * self.rate).min(self.capacity);
+        }
+        self.last_ns = Some(now_ns);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Magic bytes identifying a [`Reservoir`] snapshot file (see
+/// [`Reservoir::save`]), checked before the body is parsed so a stale or
+/// unrelated file is rejected cleanly instead of failing deep inside
+/// JSON deserialisation.
+const SNAPSHOT_MAGIC: &[u8; 7] = b"NCTRRSV";
+
+/// Current snapshot format version. Bump this, and give [`Reservoir::load`]
+/// an explicit migration path, if `ReservoirSnapshot`'s shape ever changes.
+const SNAPSHOT_VERSION: u8 = 1;
+
+/// On-disk representation of a [`Reservoir`] written by
+/// [`Reservoir::save`]. Deliberately narrower than `Reservoir` itself:
+/// only the sampled traces and the counters needed to keep sampling
+/// probabilities correct after reload survive the round trip, not the
+/// active strategy's transient state (heaps, RNG, skip counters), which
+/// [`Reservoir::load`] rebuilds fresh.
+#[derive(Serialize, Deserialize)]
+struct ReservoirSnapshot {
+    total_seen: u64,
+    eviction_count: u64,
+    estimated_bytes: u64,
+    traces: Vec<Trace>,
+    error_stratum: Vec<Trace>,
+    slow_stratum: Vec<Trace>,
 }
 
 /// A reservoir for bounded trace sampling.
@@ -176,6 +468,36 @@ pub struct Reservoir {
     rng: ChaCha8Rng,
     /// Callback for eviction events.
     eviction_callback: Option<Box<dyn Fn(EvictionEvent) + Send + Sync>>,
+    /// Min-heap (by sampling key) of `TimeDecay` entries, so the
+    /// lowest-priority trace to evict is found in O(log k) instead of a
+    /// linear scan over `traces`.
+    time_decay_heap: BinaryHeap<Reverse<TimeDecayEntry>>,
+    /// Highest trace timestamp seen so far. `TimeDecay` weights are
+    /// computed relative to this at insertion time, never retroactively
+    /// recomputed for already-stored traces.
+    time_decay_high_water_ns: u64,
+    /// Min-heap (by sampling key) of `Weighted` entries, structurally
+    /// identical to `time_decay_heap` but keyed on [`Self::trace_weight`]
+    /// instead of recency.
+    weighted_heap: BinaryHeap<Reverse<TimeDecayEntry>>,
+    /// `UniformFast`'s current `w` parameter (Algorithm L): the running
+    /// probability factor from which the next skip count is drawn.
+    /// Meaningless until the reservoir first fills.
+    uniform_fast_w: f64,
+    /// `UniformFast`'s skip counter: the number of incoming traces left
+    /// to pass over before the next replacement. Decremented once per
+    /// `add` call; a replacement happens when it reaches zero.
+    uniform_fast_skip: u64,
+    /// Running estimate (see [`crate::trace::Trace::estimated_size`]) of
+    /// the total size of all currently stored traces, kept up to date
+    /// incrementally as traces are inserted, replaced, or dropped.
+    estimated_bytes: u64,
+    /// Admission token bucket, present only when
+    /// [`ReservoirConfig::with_rate_limit`] is set.
+    rate_limiter: Option<RateLimiter>,
+    /// Number of traces shed by `rate_limiter` before reaching the
+    /// sampling strategy.
+    throttled_count: u64,
 }
 
 impl std::fmt::Debug for Reservoir {
@@ -188,6 +510,12 @@ impl std::fmt::Debug for Reservoir {
             .field("total_seen", &self.total_seen)
             .field("eviction_count", &self.eviction_count)
             .field("has_callback", &self.eviction_callback.is_some())
+            .field("time_decay_heap_len", &self.time_decay_heap.len())
+            .field("weighted_heap_len", &self.weighted_heap.len())
+            .field("uniform_fast_skip", &self.uniform_fast_skip)
+            .field("estimated_bytes", &self.estimated_bytes)
+            .field("has_rate_limiter", &self.rate_limiter.is_some())
+            .field("throttled_count", &self.throttled_count)
             .finish_non_exhaustive()
     }
 }
@@ -197,6 +525,9 @@ impl Reservoir {
     #[must_use]
     pub fn new(config: ReservoirConfig) -> Self {
         let rng = ChaCha8Rng::seed_from_u64(config.seed);
+        let rate_limiter = config
+            .rate_limit
+            .map(|(max_per_second, burst)| RateLimiter::new(max_per_second, burst));
         Self {
             config,
             traces: Vec::new(),
@@ -206,6 +537,14 @@ impl Reservoir {
             eviction_count: 0,
             rng,
             eviction_callback: None,
+            time_decay_heap: BinaryHeap::new(),
+            time_decay_high_water_ns: 0,
+            weighted_heap: BinaryHeap::new(),
+            uniform_fast_w: 1.0,
+            uniform_fast_skip: 0,
+            estimated_bytes: 0,
+            rate_limiter,
+            throttled_count: 0,
         }
     }
 
@@ -215,6 +554,146 @@ impl Reservoir {
         Self::new(ReservoirConfig::new(max_size))
     }
 
+    /// Creates a new reservoir with a token-bucket admission limit of
+    /// `max_per_second` traces, burstable up to one second's worth of
+    /// traffic (see [`ReservoirConfig::with_rate_limit`]).
+    #[must_use]
+    pub fn with_rate_limit(max_size: usize, max_per_second: f64) -> Self {
+        Self::new(ReservoirConfig::new(max_size).with_rate_limit(max_per_second, max_per_second))
+    }
+
+    /// Creates a new reservoir using `Weighted` sampling, with `weight_fn`
+    /// overriding the default error/slow weighting (see
+    /// [`ReservoirConfig::with_weight_fn`]).
+    #[must_use]
+    pub fn with_weighting(
+        max_size: usize,
+        weight_fn: impl Fn(&Trace) -> f64 + Send + Sync + 'static,
+    ) -> Self {
+        Self::new(
+            ReservoirConfig::new(max_size)
+                .with_strategy(SamplingStrategy::Weighted)
+                .with_weight_fn(weight_fn),
+        )
+    }
+
+    /// Serialises the reservoir's sampled traces and counters to `path`,
+    /// behind a small magic-byte + version header (see
+    /// [`SNAPSHOT_MAGIC`]/[`SNAPSHOT_VERSION`]) so a long-running
+    /// collector can survive restarts without losing its sampled window.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written or serialisation
+    /// fails.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.snapshot_bytes()?)?;
+        Ok(())
+    }
+
+    /// Reconstructs a reservoir of the given `capacity` from a snapshot
+    /// written by [`Self::save`]. `total_seen` and the other counters
+    /// are restored as-is (rather than reset to a fresh window), so
+    /// sampling probabilities for traces added afterward stay correct.
+    /// The sampling strategy and its transient state (heaps, skip
+    /// counters) aren't part of the snapshot: the result uses
+    /// `ReservoirConfig::new(capacity)`'s defaults (uniform sampling,
+    /// seed `0`).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, its header doesn't
+    /// match `SNAPSHOT_MAGIC`/`SNAPSHOT_VERSION`, or its body fails to
+    /// parse.
+    pub fn load(path: impl AsRef<Path>, capacity: usize) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Self::from_snapshot_bytes(&bytes, capacity)
+    }
+
+    /// Serialises and encrypts the reservoir's sampled traces to `path`
+    /// with XChaCha20-Poly1305 AEAD under `key`: a random 24-byte nonce
+    /// is written as a plaintext prefix, followed by the sealed snapshot
+    /// (ciphertext with its authentication tag appended). Lets operators
+    /// archive reservoir contents for offline analysis without exposing
+    /// the (frequently sensitive) trace metadata at rest. Requires the
+    /// `encryption` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written, serialisation
+    /// fails, or (without the `encryption` feature) unconditionally.
+    pub fn export_encrypted(&self, path: impl AsRef<Path>, key: &[u8; 32]) -> Result<()> {
+        encrypt_to_file(path, key, &self.snapshot_bytes()?)
+    }
+
+    /// Reverses [`Self::export_encrypted`], reconstructing a reservoir
+    /// of the given `capacity`. Fails loudly rather than silently
+    /// returning garbage if `key` is wrong or the file was
+    /// truncated/tampered with, since AEAD decryption rejects any
+    /// ciphertext whose authentication tag doesn't match. Requires the
+    /// `encryption` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, decryption/tag
+    /// verification fails, the decrypted body fails to parse, or
+    /// (without the `encryption` feature) unconditionally.
+    pub fn import_encrypted(path: impl AsRef<Path>, key: &[u8; 32], capacity: usize) -> Result<Self> {
+        let plaintext = decrypt_from_file(path, key)?;
+        Self::from_snapshot_bytes(&plaintext, capacity)
+    }
+
+    /// Serialises the current traces and counters into the on-disk
+    /// snapshot format (magic bytes + version header + JSON body)
+    /// shared by [`Self::save`] and [`Self::export_encrypted`].
+    fn snapshot_bytes(&self) -> Result<Vec<u8>> {
+        let snapshot = ReservoirSnapshot {
+            total_seen: self.total_seen,
+            eviction_count: self.eviction_count,
+            estimated_bytes: self.estimated_bytes,
+            traces: self.traces.clone(),
+            error_stratum: self.error_stratum.clone(),
+            slow_stratum: self.slow_stratum.clone(),
+        };
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(SNAPSHOT_MAGIC);
+        bytes.push(SNAPSHOT_VERSION);
+        bytes.extend_from_slice(&serde_json::to_vec(&snapshot)?);
+        Ok(bytes)
+    }
+
+    /// Reverses [`Self::snapshot_bytes`]: checks the magic-byte/version
+    /// header and reconstructs a reservoir of the given `capacity` from
+    /// the JSON body. Shared by [`Self::load`] and
+    /// [`Self::import_encrypted`].
+    fn from_snapshot_bytes(bytes: &[u8], capacity: usize) -> Result<Self> {
+        let header_len = SNAPSHOT_MAGIC.len() + 1;
+
+        if bytes.len() < header_len || bytes[..SNAPSHOT_MAGIC.len()] != SNAPSHOT_MAGIC[..] {
+            return Err(Error::InvalidSnapshot(
+                "missing or invalid snapshot magic bytes".to_string(),
+            ));
+        }
+        let version = bytes[SNAPSHOT_MAGIC.len()];
+        if version != SNAPSHOT_VERSION {
+            return Err(Error::InvalidSnapshot(format!(
+                "unsupported snapshot version {version}, expected {SNAPSHOT_VERSION}"
+            )));
+        }
+
+        let snapshot: ReservoirSnapshot = serde_json::from_slice(&bytes[header_len..])?;
+
+        let mut reservoir = Self::new(ReservoirConfig::new(capacity));
+        reservoir.traces = snapshot.traces;
+        reservoir.error_stratum = snapshot.error_stratum;
+        reservoir.slow_stratum = snapshot.slow_stratum;
+        reservoir.total_seen = snapshot.total_seen;
+        reservoir.eviction_count = snapshot.eviction_count;
+        reservoir.estimated_bytes = snapshot.estimated_bytes;
+        Ok(reservoir)
+    }
+
     /// Sets a callback for eviction events.
     pub fn on_eviction<F>(&mut self, callback: F)
     where
@@ -230,7 +709,10 @@ impl Reservoir {
             SamplingStrategy::Stratified => {
                 self.traces.len() + self.error_stratum.len() + self.slow_stratum.len()
             }
-            SamplingStrategy::Uniform | SamplingStrategy::TimeDecay => self.traces.len(),
+            SamplingStrategy::Uniform
+            | SamplingStrategy::UniformFast
+            | SamplingStrategy::TimeDecay
+            | SamplingStrategy::Weighted => self.traces.len(),
         }
     }
 
@@ -251,7 +733,10 @@ impl Reservoir {
     pub fn stats(&self) -> ReservoirStats {
         let (error_count, slow_count) = match self.config.strategy {
             SamplingStrategy::Stratified => (self.error_stratum.len(), self.slow_stratum.len()),
-            SamplingStrategy::Uniform | SamplingStrategy::TimeDecay => {
+            SamplingStrategy::Uniform
+            | SamplingStrategy::UniformFast
+            | SamplingStrategy::TimeDecay
+            | SamplingStrategy::Weighted => {
                 let errors = self.traces.iter().filter(|t| t.is_error).count();
                 let slow = self.config.slow_threshold.map_or(0, |thresh| {
                     self.traces.iter().filter(|t| t.duration >= thresh).count()
@@ -266,20 +751,49 @@ impl Reservoir {
             error_count,
             slow_count,
             eviction_count: self.eviction_count,
+            estimated_bytes: self.estimated_bytes,
+            throttled_count: self.throttled_count,
         }
     }
 
     /// Adds a trace to the reservoir using the configured sampling strategy.
     ///
     /// Returns `Some(EvictionEvent)` if a trace was evicted, `None` otherwise.
+    /// If a byte budget is configured (see
+    /// [`ReservoirConfig::with_max_bytes`]), this may also trigger
+    /// additional memory-pressure evictions; those are only reported via
+    /// the eviction callback (see [`Self::on_eviction`]), not this
+    /// return value.
+    ///
+    /// If [`ReservoirConfig::with_rate_limit`] is set and the admission
+    /// token bucket is empty, `trace` is shed before reaching the
+    /// sampling strategy: `total_seen` isn't incremented and `stats()`'s
+    /// `throttled_count` goes up instead, so a traffic spike can't
+    /// distort sampling probabilities for the traces that do get in.
     pub fn add(&mut self, trace: Trace) -> Option<EvictionEvent> {
+        if let Some(limiter) = &mut self.rate_limiter {
+            let now_ns = trace.start_time_ns().unwrap_or(self.total_seen * 1_000_000);
+            if !limiter.admit(now_ns) {
+                self.throttled_count += 1;
+                return None;
+            }
+        }
+
         self.total_seen += 1;
 
-        match self.config.strategy {
+        let event = match self.config.strategy {
             SamplingStrategy::Uniform => self.add_uniform(trace),
+            SamplingStrategy::UniformFast => self.add_uniform_fast(trace),
             SamplingStrategy::Stratified => self.add_stratified(trace),
             SamplingStrategy::TimeDecay => self.add_time_decay(trace),
+            SamplingStrategy::Weighted => self.add_weighted(trace),
+        };
+
+        if self.config.max_bytes.is_some() {
+            self.enforce_memory_budget();
         }
+
+        event
     }
 
     /// Uniform reservoir sampling (Algorithm R).
@@ -287,6 +801,7 @@ impl Reservoir {
     fn add_uniform(&mut self, trace: Trace) -> Option<EvictionEvent> {
         if self.traces.len() < self.config.max_size {
             // Reservoir not full, just add
+            self.track_insert_bytes(trace.estimated_size());
             self.traces.push(trace);
             None
         } else {
@@ -294,6 +809,7 @@ impl Reservoir {
             // Truncation is safe: max_size is usize, so j < max_size fits in usize
             let j = self.rng.gen_range(0..self.total_seen) as usize;
             if j < self.config.max_size {
+                self.track_replace_bytes(self.traces[j].estimated_size(), trace.estimated_size());
                 let evicted = std::mem::replace(&mut self.traces[j], trace.clone());
                 self.eviction_count += 1;
                 let event = EvictionEvent {
@@ -311,6 +827,72 @@ impl Reservoir {
         }
     }
 
+    /// Uniform reservoir sampling via Algorithm L (Li): statistically
+    /// equivalent to [`Self::add_uniform`], but once the reservoir fills it
+    /// draws a skip count (`uniform_fast_skip`) instead of a fresh random
+    /// index for every trace, only touching the RNG again when a
+    /// replacement is actually due.
+    fn add_uniform_fast(&mut self, trace: Trace) -> Option<EvictionEvent> {
+        if self.config.max_size == 0 {
+            return None;
+        }
+
+        if self.traces.len() < self.config.max_size {
+            self.track_insert_bytes(trace.estimated_size());
+            self.traces.push(trace);
+            if self.traces.len() == self.config.max_size {
+                self.uniform_fast_w = self.uniform_fast_w_factor();
+                self.uniform_fast_skip = self.uniform_fast_next_skip();
+            }
+            return None;
+        }
+
+        self.uniform_fast_skip -= 1;
+        if self.uniform_fast_skip > 0 {
+            return None;
+        }
+
+        let j = self.rng.gen_range(0..self.config.max_size);
+        self.track_replace_bytes(self.traces[j].estimated_size(), trace.estimated_size());
+        let evicted = std::mem::replace(&mut self.traces[j], trace.clone());
+        self.eviction_count += 1;
+
+        self.uniform_fast_w *= self.uniform_fast_w_factor();
+        self.uniform_fast_skip = self.uniform_fast_next_skip();
+
+        let event = EvictionEvent {
+            evicted_trace_id: evicted.trace_id,
+            replacement_trace_id: trace.trace_id,
+            reason: EvictionReason::RandomSample,
+            reservoir_size: self.traces.len(),
+            total_seen: self.total_seen,
+        };
+        self.emit_eviction(&event);
+        Some(event)
+    }
+
+    /// Draws the next multiplicative factor for `uniform_fast_w`:
+    /// `exp(ln(u) / k)` for `u ~ Uniform(0, 1)`. Used both to seed `w` when
+    /// the reservoir first fills and to update it after each replacement.
+    #[allow(clippy::cast_precision_loss)]
+    fn uniform_fast_w_factor(&mut self) -> f64 {
+        let u: f64 = self.rng.gen_range(0.0..1.0_f64).max(f64::MIN_POSITIVE);
+        (u.ln() / self.config.max_size as f64).exp()
+    }
+
+    /// Draws the next Algorithm L skip count: `floor(ln(u) / ln(1 - w)) + 1`
+    /// for `u ~ Uniform(0, 1)`, given the current `uniform_fast_w`.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn uniform_fast_next_skip(&mut self) -> u64 {
+        let u: f64 = self.rng.gen_range(0.0..1.0_f64).max(f64::MIN_POSITIVE);
+        let gap = (u.ln() / (1.0 - self.uniform_fast_w).ln()).floor();
+        // `w` is in `(0, 1)` so `gap` is finite and non-negative in exact
+        // arithmetic; guard against float edge cases near `w == 1` rather
+        // than produce a NaN/negative skip.
+        let gap = if gap.is_finite() { gap.max(0.0) } else { 0.0 };
+        gap as u64 + 1
+    }
+
     /// Stratified reservoir sampling.
     ///
     /// Maintains separate strata for error traces and slow traces,
@@ -340,11 +922,13 @@ impl Reservoir {
     #[allow(clippy::cast_possible_truncation)]
     fn add_to_error_stratum(&mut self, trace: Trace, capacity: usize) -> Option<EvictionEvent> {
         if self.error_stratum.len() < capacity {
+            self.track_insert_bytes(trace.estimated_size());
             self.error_stratum.push(trace);
             None
         } else {
             let j = self.rng.gen_range(0..self.total_seen) as usize;
             if j < capacity && j < self.error_stratum.len() {
+                self.track_replace_bytes(self.error_stratum[j].estimated_size(), trace.estimated_size());
                 let evicted = std::mem::replace(&mut self.error_stratum[j], trace.clone());
                 self.eviction_count += 1;
                 let event = EvictionEvent {
@@ -366,11 +950,13 @@ impl Reservoir {
     #[allow(clippy::cast_possible_truncation)]
     fn add_to_slow_stratum(&mut self, trace: Trace, capacity: usize) -> Option<EvictionEvent> {
         if self.slow_stratum.len() < capacity {
+            self.track_insert_bytes(trace.estimated_size());
             self.slow_stratum.push(trace);
             None
         } else {
             let j = self.rng.gen_range(0..self.total_seen) as usize;
             if j < capacity && j < self.slow_stratum.len() {
+                self.track_replace_bytes(self.slow_stratum[j].estimated_size(), trace.estimated_size());
                 let evicted = std::mem::replace(&mut self.slow_stratum[j], trace.clone());
                 self.eviction_count += 1;
                 let event = EvictionEvent {
@@ -392,11 +978,13 @@ impl Reservoir {
     #[allow(clippy::cast_possible_truncation)]
     fn add_to_normal_stratum(&mut self, trace: Trace, capacity: usize) -> Option<EvictionEvent> {
         if self.traces.len() < capacity {
+            self.track_insert_bytes(trace.estimated_size());
             self.traces.push(trace);
             None
         } else {
             let j = self.rng.gen_range(0..self.total_seen) as usize;
             if j < capacity && j < self.traces.len() {
+                self.track_replace_bytes(self.traces[j].estimated_size(), trace.estimated_size());
                 let evicted = std::mem::replace(&mut self.traces[j], trace.clone());
                 self.eviction_count += 1;
                 let event = EvictionEvent {
@@ -414,62 +1002,288 @@ impl Reservoir {
         }
     }
 
-    /// Time-decay reservoir sampling.
-    ///
-    /// Traces are weighted by recency, with newer traces having higher
-    /// probability of being kept.
+    /// Time-decay reservoir sampling via the Efraimidis-Spirakis A-Res
+    /// algorithm: each incoming trace draws a key `u.powf(1.0 / w)` for
+    /// `u ~ Uniform(0, 1]` and a decay weight `w` (see
+    /// [`Self::decay_weight`]), and the reservoir keeps the `max_size`
+    /// traces with the largest keys. `time_decay_heap` tracks the current
+    /// minimum key, so eviction is O(log k) instead of the O(n) linear
+    /// scan a naive implementation needs.
     #[allow(clippy::cast_precision_loss)]
     fn add_time_decay(&mut self, trace: Trace) -> Option<EvictionEvent> {
+        let now_ns = trace.start_time_ns().unwrap_or(self.total_seen * 1_000_000);
+        self.time_decay_high_water_ns = self.time_decay_high_water_ns.max(now_ns);
+
+        let weight = self.decay_weight(&trace, now_ns);
+        let u: f64 = self.rng.gen_range(0.0..=1.0);
+        // Guard against a zero/negative weight (e.g. a pathological
+        // custom weight function) so `1.0 / weight` can't divide by zero.
+        let key = if weight > 0.0 { u.powf(1.0 / weight) } else { 0.0 };
+
+        if self.traces.len() < self.config.max_size {
+            let index = self.traces.len();
+            self.track_insert_bytes(trace.estimated_size());
+            self.traces.push(trace);
+            self.time_decay_heap
+                .push(Reverse(TimeDecayEntry { key: HeapKey(key), index }));
+            return None;
+        }
+
+        let min_entry = self.time_decay_heap.peek().map(|Reverse(entry)| *entry)?;
+        if key <= min_entry.key.0 {
+            return None;
+        }
+
+        self.time_decay_heap.pop();
+        self.track_replace_bytes(self.traces[min_entry.index].estimated_size(), trace.estimated_size());
+        let evicted = std::mem::replace(&mut self.traces[min_entry.index], trace.clone());
+        self.time_decay_heap.push(Reverse(TimeDecayEntry {
+            key: HeapKey(key),
+            index: min_entry.index,
+        }));
+        self.eviction_count += 1;
+
+        let event = EvictionEvent {
+            evicted_trace_id: evicted.trace_id,
+            replacement_trace_id: trace.trace_id,
+            reason: EvictionReason::TimeDecay,
+            reservoir_size: self.traces.len(),
+            total_seen: self.total_seen,
+        };
+        self.emit_eviction(&event);
+        Some(event)
+    }
+
+    /// Computes the decay weight for `trace` given its own timestamp
+    /// `now_ns`. Delegates to [`ReservoirConfig::decay_weight_fn`] if one
+    /// is configured; otherwise falls back to exponential recency decay
+    /// (`exp(-0.693 * age / half_life)`, age relative to the newest
+    /// timestamp seen so far).
+    #[allow(clippy::cast_precision_loss)]
+    fn decay_weight(&self, trace: &Trace, now_ns: u64) -> f64 {
+        if let Some(f) = &self.config.decay_weight_fn {
+            return f(trace, self.time_decay_high_water_ns);
+        }
+
+        let half_life = self
+            .config
+            .decay_half_life_ns
+            .unwrap_or(24 * 60 * 60 * 1_000_000_000); // 24h default
+        let age = self.time_decay_high_water_ns.saturating_sub(now_ns);
+        (-0.693 * age as f64 / half_life as f64).exp()
+    }
+
+    /// Weighted reservoir sampling via the Efraimidis-Spirakis A-Res
+    /// algorithm: each incoming trace draws a key `u.powf(1.0 / w)` for
+    /// `u ~ Uniform(0, 1]` and a retention weight `w` (see
+    /// [`Self::trace_weight`]), and the reservoir keeps the `max_size`
+    /// traces with the largest keys. Unlike `TimeDecay`, the weight
+    /// doesn't depend on when the trace arrived, so error and slow
+    /// traces survive eviction with probability proportional to their
+    /// weight regardless of age.
+    fn add_weighted(&mut self, trace: Trace) -> Option<EvictionEvent> {
+        let weight = self.trace_weight(&trace);
+        let u: f64 = self.rng.gen_range(0.0..=1.0);
+        // Guard against a zero/negative weight (e.g. a pathological
+        // custom weight function) so `1.0 / weight` can't divide by zero.
+        let key = if weight > 0.0 { u.powf(1.0 / weight) } else { 0.0 };
+
         if self.traces.len() < self.config.max_size {
+            let index = self.traces.len();
+            self.track_insert_bytes(trace.estimated_size());
             self.traces.push(trace);
+            self.weighted_heap
+                .push(Reverse(TimeDecayEntry { key: HeapKey(key), index }));
+            return None;
+        }
+
+        let min_entry = self.weighted_heap.peek().map(|Reverse(entry)| *entry)?;
+        if key <= min_entry.key.0 {
             return None;
         }
 
-        // Calculate weight based on time decay
-        let current_time = trace.start_time_ns().unwrap_or(self.total_seen * 1_000_000);
-        let half_life = self.config.decay_half_life_ns.unwrap_or(24 * 60 * 60 * 1_000_000_000); // 24h default
+        self.weighted_heap.pop();
+        self.track_replace_bytes(self.traces[min_entry.index].estimated_size(), trace.estimated_size());
+        let evicted = std::mem::replace(&mut self.traces[min_entry.index], trace.clone());
+        self.weighted_heap.push(Reverse(TimeDecayEntry {
+            key: HeapKey(key),
+            index: min_entry.index,
+        }));
+        self.eviction_count += 1;
+
+        let event = EvictionEvent {
+            evicted_trace_id: evicted.trace_id,
+            replacement_trace_id: trace.trace_id,
+            reason: EvictionReason::Weighted,
+            reservoir_size: self.traces.len(),
+            total_seen: self.total_seen,
+        };
+        self.emit_eviction(&event);
+        Some(event)
+    }
 
-        // Find trace with lowest weight (oldest adjusted for decay)
-        let mut min_weight = f64::MAX;
-        let mut min_idx = 0;
+    /// Computes the retention weight for `trace`. Delegates to
+    /// [`ReservoirConfig::weight_fn`] if one is configured; otherwise
+    /// starts from `1.0` and adds `10.0` for errors and `5.0` for traces
+    /// at or over `slow_threshold` (so `11.0`, `6.0`, or `16.0` if both
+    /// apply).
+    fn trace_weight(&self, trace: &Trace) -> f64 {
+        if let Some(f) = &self.config.weight_fn {
+            return f(trace);
+        }
 
-        for (i, t) in self.traces.iter().enumerate() {
-            let t_time = t.start_time_ns().unwrap_or(0);
-            let age = current_time.saturating_sub(t_time);
-            // Precision loss is acceptable for exponential decay calculation
-            let weight = (-0.693 * age as f64 / half_life as f64).exp();
-            if weight < min_weight {
-                min_weight = weight;
-                min_idx = i;
+        let mut weight = 1.0;
+        if trace.is_error {
+            weight += 10.0;
+        }
+        if let Some(thresh) = self.config.slow_threshold {
+            if trace.duration >= thresh {
+                weight += 5.0;
             }
         }
+        weight
+    }
+
+    /// Emits an eviction event to the callback if set.
+    fn emit_eviction(&self, event: &EvictionEvent) {
+        if let Some(ref callback) = self.eviction_callback {
+            callback(event.clone());
+        }
+    }
+
+    /// Accounts for a newly inserted trace of the given estimated size.
+    fn track_insert_bytes(&mut self, bytes: usize) {
+        self.estimated_bytes = self.estimated_bytes.saturating_add(bytes as u64);
+    }
+
+    /// Accounts for a trace of `evicted_bytes` being replaced by one of
+    /// `new_bytes`.
+    fn track_replace_bytes(&mut self, evicted_bytes: usize, new_bytes: usize) {
+        self.estimated_bytes = self
+            .estimated_bytes
+            .saturating_sub(evicted_bytes as u64)
+            .saturating_add(new_bytes as u64);
+    }
+
+    /// If a byte budget is configured (see
+    /// [`ReservoirConfig::with_max_bytes`]) and `estimated_bytes` has
+    /// grown past it, evicts sampled traces - preferring whatever the
+    /// active strategy would evict, and never touching the error stratum
+    /// while `preserve_errors` protects it - until back under
+    /// `max_bytes * soft_ratio`, or until nothing evictable is left (so
+    /// protected errors alone can keep the corpus above that threshold).
+    /// Each eviction is reported via the
+    /// eviction callback with `EvictionReason::MemoryPressure`; unlike
+    /// the per-strategy evictions, these are never returned from `add`.
+    fn enforce_memory_budget(&mut self) {
+        let Some(max_bytes) = self.config.max_bytes else {
+            return;
+        };
 
-        // Probabilistically replace based on relative weights
-        let new_weight = 1.0; // New trace has weight 1
-        let replace_prob = new_weight / (new_weight + min_weight);
+        #[allow(clippy::cast_precision_loss)]
+        let soft_threshold = (max_bytes as f64 * self.config.soft_ratio) as u64;
 
-        if self.rng.gen::<f64>() < replace_prob {
-            let evicted = std::mem::replace(&mut self.traces[min_idx], trace.clone());
+        while self.estimated_bytes > soft_threshold {
+            let Some(evicted) = self.evict_one_for_memory_pressure() else {
+                break;
+            };
+            self.track_replace_bytes(evicted.estimated_size(), 0);
             self.eviction_count += 1;
             let event = EvictionEvent {
                 evicted_trace_id: evicted.trace_id,
-                replacement_trace_id: trace.trace_id,
-                reason: EvictionReason::TimeDecay,
-                reservoir_size: self.traces.len(),
+                // There's no replacement trace for a pure memory-pressure
+                // drop - the slot is removed outright, not replaced.
+                replacement_trace_id: String::new(),
+                reason: EvictionReason::MemoryPressure,
+                reservoir_size: self.len(),
                 total_seen: self.total_seen,
             };
             self.emit_eviction(&event);
-            Some(event)
-        } else {
-            None
         }
     }
 
-    /// Emits an eviction event to the callback if set.
-    fn emit_eviction(&self, event: &EvictionEvent) {
-        if let Some(ref callback) = self.eviction_callback {
-            callback(event.clone());
+    /// Picks and removes one trace to drop for
+    /// [`Self::enforce_memory_budget`], matching the active sampling
+    /// strategy's own notion of what's least worth keeping.
+    fn evict_one_for_memory_pressure(&mut self) -> Option<Trace> {
+        match self.config.strategy {
+            SamplingStrategy::TimeDecay => {
+                let mut heap = std::mem::take(&mut self.time_decay_heap);
+                let evicted = Self::evict_lowest_priority(&mut self.traces, &mut heap);
+                self.time_decay_heap = heap;
+                evicted
+            }
+            SamplingStrategy::Weighted => {
+                let mut heap = std::mem::take(&mut self.weighted_heap);
+                let evicted = Self::evict_lowest_priority(&mut self.traces, &mut heap);
+                self.weighted_heap = heap;
+                evicted
+            }
+            SamplingStrategy::Stratified => self.evict_one_stratified(),
+            SamplingStrategy::Uniform | SamplingStrategy::UniformFast => self.evict_random_uniform(),
+        }
+    }
+
+    /// Drops a uniformly random trace from the main reservoir.
+    fn evict_random_uniform(&mut self) -> Option<Trace> {
+        if self.traces.is_empty() {
+            return None;
+        }
+        let idx = self.rng.gen_range(0..self.traces.len());
+        Some(self.traces.swap_remove(idx))
+    }
+
+    /// Drops the trace with the lowest retention priority (the current
+    /// minimum of `heap`) from `traces`, repairing the heap afterward
+    /// since the removal's `swap_remove` moves a different trace into
+    /// the vacated index. Shared by `TimeDecay` and `Weighted`, which
+    /// both maintain a min-heap of [`TimeDecayEntry`] keyed on their own
+    /// notion of priority.
+    fn evict_lowest_priority(
+        traces: &mut Vec<Trace>,
+        heap: &mut BinaryHeap<Reverse<TimeDecayEntry>>,
+    ) -> Option<Trace> {
+        let Reverse(victim) = heap.pop()?;
+        let last_idx = traces.len() - 1;
+        let evicted = traces.swap_remove(victim.index);
+
+        if victim.index != last_idx {
+            // `swap_remove` moved the trace that used to be at `last_idx`
+            // into `victim.index`; `BinaryHeap` has no in-place mutation,
+            // so rebuild it with that one entry's index corrected.
+            let mut entries: Vec<TimeDecayEntry> = std::mem::take(heap)
+                .into_iter()
+                .map(|Reverse(e)| e)
+                .collect();
+            for entry in &mut entries {
+                if entry.index == last_idx {
+                    entry.index = victim.index;
+                }
+            }
+            *heap = entries.into_iter().map(Reverse).collect();
+        }
+
+        Some(evicted)
+    }
+
+    /// Drops a trace for stratified mode: prefers the slow stratum, then
+    /// normal traces, and reaches into the error stratum only once both
+    /// are empty and `preserve_errors` is off. A protected error stratum
+    /// is never evicted from; `None` is returned instead.
+    fn evict_one_stratified(&mut self) -> Option<Trace> {
+        if !self.slow_stratum.is_empty() {
+            let idx = self.rng.gen_range(0..self.slow_stratum.len());
+            return Some(self.slow_stratum.swap_remove(idx));
+        }
+        if !self.traces.is_empty() {
+            let idx = self.rng.gen_range(0..self.traces.len());
+            return Some(self.traces.swap_remove(idx));
         }
+        if !self.config.preserve_errors && !self.error_stratum.is_empty() {
+            let idx = self.rng.gen_range(0..self.error_stratum.len());
+            return Some(self.error_stratum.swap_remove(idx));
+        }
+        None
     }
 
     /// Returns an iterator over all traces in the reservoir.
@@ -483,9 +1297,10 @@ impl Reservoir {
                         .chain(self.slow_stratum.iter()),
                 ) as Box<dyn Iterator<Item = &Trace>>
             }
-            SamplingStrategy::Uniform | SamplingStrategy::TimeDecay => {
-                Box::new(self.traces.iter())
-            }
+            SamplingStrategy::Uniform
+            | SamplingStrategy::UniformFast
+            | SamplingStrategy::TimeDecay
+            | SamplingStrategy::Weighted => Box::new(self.traces.iter()),
         }
     }
 
@@ -499,7 +1314,10 @@ impl Reservoir {
                 all.extend(self.slow_stratum);
                 all
             }
-            SamplingStrategy::Uniform | SamplingStrategy::TimeDecay => self.traces,
+            SamplingStrategy::Uniform
+            | SamplingStrategy::UniformFast
+            | SamplingStrategy::TimeDecay
+            | SamplingStrategy::Weighted => self.traces,
         }
     }
 
@@ -508,6 +1326,357 @@ impl Reservoir {
     pub const fn config(&self) -> &ReservoirConfig {
         &self.config
     }
+
+    /// Combines two independently sampled reservoirs into one
+    /// `max_size`-capacity uniform sample. Consumes both; see
+    /// [`Self::merge_from`] for the weighting used. Assumes `self` and
+    /// `other` share the same `config` (as shards of the same collection
+    /// topology would) - `self`'s `config` is kept for the result.
+    #[must_use]
+    pub fn merge(mut self, other: Self) -> Self {
+        self.merge_from(other);
+        self
+    }
+
+    /// Merges `other` into `self` in place, as an unbiased recombination
+    /// of two independently sampled shards of the same stream.
+    ///
+    /// Naively concatenating the two reservoirs' traces and re-trimming
+    /// would bias toward whichever shard saw fewer items (its traces
+    /// make up a larger fraction of its own smaller sample). Instead,
+    /// each output slot is filled by first choosing shard A (`self`) with
+    /// probability `a.total_seen / (a.total_seen + b.total_seen)`, then
+    /// drawing uniformly without replacement from that shard's retained
+    /// traces (falling back to the other shard once one side is
+    /// exhausted). `Stratified` reservoirs merge each stratum
+    /// independently with the same weighting, against capacities
+    /// recomputed for the merged `max_size`.
+    ///
+    /// The RNG is reseeded from a deterministic combination of both
+    /// reservoirs' configured seeds, so repeating a merge of the same two
+    /// shards always produces the same result regardless of how far
+    /// either shard's own RNG had drifted from sampling its stream.
+    pub fn merge_from(&mut self, other: Self) {
+        let mut rng = ChaCha8Rng::seed_from_u64(Self::combine_seeds(self.config.seed, other.config.seed));
+
+        let a_weight = self.total_seen;
+        let b_weight = other.total_seen;
+
+        match self.config.strategy {
+            SamplingStrategy::Stratified => {
+                let error_capacity = self.config.max_size / 5;
+                let slow_capacity = self.config.max_size / 10;
+                let normal_capacity = self.config.max_size - error_capacity - slow_capacity;
+
+                self.error_stratum = weighted_fill(
+                    &mut rng,
+                    error_capacity,
+                    std::mem::take(&mut self.error_stratum),
+                    a_weight,
+                    other.error_stratum,
+                    b_weight,
+                );
+                self.slow_stratum = weighted_fill(
+                    &mut rng,
+                    slow_capacity,
+                    std::mem::take(&mut self.slow_stratum),
+                    a_weight,
+                    other.slow_stratum,
+                    b_weight,
+                );
+                self.traces = weighted_fill(
+                    &mut rng,
+                    normal_capacity,
+                    std::mem::take(&mut self.traces),
+                    a_weight,
+                    other.traces,
+                    b_weight,
+                );
+                self.rng = rng;
+            }
+            SamplingStrategy::TimeDecay => {
+                self.time_decay_high_water_ns =
+                    self.time_decay_high_water_ns.max(other.time_decay_high_water_ns);
+                self.traces = weighted_fill(
+                    &mut rng,
+                    self.config.max_size,
+                    std::mem::take(&mut self.traces),
+                    a_weight,
+                    other.traces,
+                    b_weight,
+                );
+                // The merged sample no longer corresponds to any single
+                // priority ordering, so every surviving trace draws a
+                // fresh key instead of keeping its shard's original one.
+                self.rebuild_time_decay_heap(&mut rng);
+                self.rng = rng;
+            }
+            SamplingStrategy::Weighted => {
+                self.traces = weighted_fill(
+                    &mut rng,
+                    self.config.max_size,
+                    std::mem::take(&mut self.traces),
+                    a_weight,
+                    other.traces,
+                    b_weight,
+                );
+                // As with `TimeDecay`, the merged sample no longer
+                // corresponds to any single priority ordering, so every
+                // surviving trace draws a fresh key instead of keeping
+                // its shard's original one.
+                self.rebuild_weighted_heap(&mut rng);
+                self.rng = rng;
+            }
+            SamplingStrategy::Uniform | SamplingStrategy::UniformFast => {
+                self.traces = weighted_fill(
+                    &mut rng,
+                    self.config.max_size,
+                    std::mem::take(&mut self.traces),
+                    a_weight,
+                    other.traces,
+                    b_weight,
+                );
+                self.rng = rng;
+
+                // `UniformFast`'s skip counter is only meaningful relative
+                // to the sequence of items a single reservoir has seen,
+                // so recompute it (using the now-installed merge RNG)
+                // against the merged state - including the case where the
+                // merge already filled the reservoir, since
+                // `add_uniform_fast` only (re)initializes it on the
+                // transition into being full.
+                self.uniform_fast_w = 1.0;
+                self.uniform_fast_skip = 0;
+                if self.traces.len() == self.config.max_size {
+                    self.uniform_fast_w = self.uniform_fast_w_factor();
+                    self.uniform_fast_skip = self.uniform_fast_next_skip();
+                }
+            }
+        }
+
+        self.total_seen = a_weight + b_weight;
+        self.eviction_count += other.eviction_count;
+        self.estimated_bytes = self.traces.iter().map(Trace::estimated_size).sum::<usize>() as u64
+            + self.error_stratum.iter().map(Trace::estimated_size).sum::<usize>() as u64
+            + self.slow_stratum.iter().map(Trace::estimated_size).sum::<usize>() as u64;
+    }
+
+    /// Rebuilds `time_decay_heap` from scratch for whatever traces are
+    /// currently in `self.traces`, drawing a fresh Efraimidis-Spirakis
+    /// key for each with `rng`. Used by [`Self::merge_from`], since a
+    /// merged sample no longer corresponds to any single priority
+    /// ordering the old heap entries could be carried over for.
+    fn rebuild_time_decay_heap(&mut self, rng: &mut ChaCha8Rng) {
+        let high_water = self.time_decay_high_water_ns;
+        let keys: Vec<(usize, f64)> = self
+            .traces
+            .iter()
+            .enumerate()
+            .map(|(index, trace)| {
+                let now_ns = trace.start_time_ns().unwrap_or(high_water);
+                let weight = self.decay_weight(trace, now_ns);
+                let u: f64 = rng.gen_range(0.0..=1.0);
+                let key = if weight > 0.0 { u.powf(1.0 / weight) } else { 0.0 };
+                (index, key)
+            })
+            .collect();
+
+        self.time_decay_heap = keys
+            .into_iter()
+            .map(|(index, key)| Reverse(TimeDecayEntry { key: HeapKey(key), index }))
+            .collect();
+    }
+
+    /// Rebuilds `weighted_heap` from scratch for whatever traces are
+    /// currently in `self.traces`, drawing a fresh Efraimidis-Spirakis
+    /// key for each with `rng`. Used by [`Self::merge_from`], for the
+    /// same reason as [`Self::rebuild_time_decay_heap`].
+    fn rebuild_weighted_heap(&mut self, rng: &mut ChaCha8Rng) {
+        let keys: Vec<(usize, f64)> = self
+            .traces
+            .iter()
+            .enumerate()
+            .map(|(index, trace)| {
+                let weight = self.trace_weight(trace);
+                let u: f64 = rng.gen_range(0.0..=1.0);
+                let key = if weight > 0.0 { u.powf(1.0 / weight) } else { 0.0 };
+                (index, key)
+            })
+            .collect();
+
+        self.weighted_heap = keys
+            .into_iter()
+            .map(|(index, key)| Reverse(TimeDecayEntry { key: HeapKey(key), index }))
+            .collect();
+    }
+
+    /// Deterministically mixes two configured seeds into one (a simple
+    /// splitmix64-style multiply-and-add, not cryptographic), so merging
+    /// the same two shards always reseeds the merge's RNG the same way.
+    fn combine_seeds(a: u64, b: u64) -> u64 {
+        a.wrapping_mul(0x9E37_79B9_7F4A_7C15).wrapping_add(b)
+    }
+
+    /// Builds a discretised [`Histogram`] of trace durations currently
+    /// held in the reservoir, with `bucket_count` fixed-width buckets
+    /// spanning the observed `[min, max]` range.
+    ///
+    /// Returns `None` if there are fewer samples than `bucket_count`
+    /// (too few points to usefully fill that many buckets) or if
+    /// `bucket_count` is zero. If every duration is equal, the histogram
+    /// has a single bucket regardless of `bucket_count`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn duration_histogram(&self, bucket_count: usize) -> Option<Histogram> {
+        if bucket_count == 0 {
+            return None;
+        }
+
+        let durations: Vec<Duration> = self.iter().map(|t| t.duration).collect();
+        if durations.len() < bucket_count {
+            return None;
+        }
+
+        let min = *durations.iter().min()?;
+        let max = *durations.iter().max()?;
+
+        if min == max {
+            return Some(Histogram {
+                bucket_bounds: vec![max],
+                counts: vec![durations.len() as u64],
+                total: durations.len() as u64,
+            });
+        }
+
+        let bucket_count_ns = u128::try_from(bucket_count).unwrap_or(u128::MAX);
+        let bucket_size_ns = (max.as_nanos() - min.as_nanos() + 1) / bucket_count_ns;
+        let bucket_size_ns = bucket_size_ns.max(1);
+
+        let bucket_bounds: Vec<Duration> = (0..bucket_count)
+            .map(|i| {
+                let bound_ns = (min.as_nanos() + bucket_size_ns * (i as u128 + 1))
+                    .min(max.as_nanos())
+                    .min(u128::from(u64::MAX)) as u64;
+                Duration::from_nanos(bound_ns).min(max)
+            })
+            .collect();
+
+        let mut counts = vec![0u64; bucket_count];
+        for duration in &durations {
+            let offset_ns = duration.as_nanos() - min.as_nanos();
+            let bucket = usize::try_from(offset_ns / bucket_size_ns)
+                .unwrap_or(usize::MAX)
+                .min(bucket_count - 1);
+            counts[bucket] += 1;
+        }
+
+        Some(Histogram {
+            bucket_bounds,
+            counts,
+            total: durations.len() as u64,
+        })
+    }
+}
+
+/// Seals `plaintext` with XChaCha20-Poly1305 under `key` and writes it
+/// to `path` as a random 24-byte nonce followed by the ciphertext (with
+/// its authentication tag appended). Used by
+/// [`Reservoir::export_encrypted`]; gated behind the `encryption`
+/// feature so the dependency is optional.
+#[cfg(feature = "encryption")]
+fn encrypt_to_file(path: impl AsRef<Path>, key: &[u8; 32], plaintext: &[u8]) -> Result<()> {
+    use chacha20poly1305::aead::{Aead, AeadCore, OsRng};
+    use chacha20poly1305::{KeyInit, XChaCha20Poly1305};
+
+    let cipher = XChaCha20Poly1305::new(key.into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| Error::EncodingError("failed to encrypt reservoir snapshot".to_string()))?;
+
+    let mut out = Vec::with_capacity(nonce.len() + ciphertext.len());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    std::fs::write(path, out)?;
+    Ok(())
+}
+
+#[cfg(not(feature = "encryption"))]
+fn encrypt_to_file(_path: impl AsRef<Path>, _key: &[u8; 32], _plaintext: &[u8]) -> Result<()> {
+    Err(Error::EncodingError(
+        "encrypted export requested but this build was compiled without the `encryption` feature"
+            .to_string(),
+    ))
+}
+
+/// Reverses [`encrypt_to_file`]: reads `path`, splits off the leading
+/// 24-byte nonce, and opens the remaining ciphertext under `key`.
+/// Returns an error (rather than garbage bytes) if the authentication
+/// tag doesn't verify. Gated behind the `encryption` feature so the
+/// dependency is optional.
+#[cfg(feature = "encryption")]
+fn decrypt_from_file(path: impl AsRef<Path>, key: &[u8; 32]) -> Result<Vec<u8>> {
+    use chacha20poly1305::aead::Aead;
+    use chacha20poly1305::{KeyInit, XChaCha20Poly1305, XNonce};
+
+    let bytes = std::fs::read(path)?;
+    if bytes.len() < 24 {
+        return Err(Error::InvalidSnapshot(
+            "encrypted snapshot is shorter than its nonce".to_string(),
+        ));
+    }
+    let (nonce_bytes, ciphertext) = bytes.split_at(24);
+    let nonce = XNonce::from_slice(nonce_bytes);
+
+    let cipher = XChaCha20Poly1305::new(key.into());
+    cipher.decrypt(nonce, ciphertext).map_err(|_| {
+        Error::InvalidSnapshot(
+            "failed to decrypt reservoir snapshot (wrong key, or file corrupted/tampered with)"
+                .to_string(),
+        )
+    })
+}
+
+#[cfg(not(feature = "encryption"))]
+fn decrypt_from_file(_path: impl AsRef<Path>, _key: &[u8; 32]) -> Result<Vec<u8>> {
+    Err(Error::InvalidSnapshot(
+        "encrypted import requested but this build was compiled without the `encryption` feature"
+            .to_string(),
+    ))
+}
+
+/// Fills up to `capacity` slots by repeatedly choosing between `a` and
+/// `b` weighted by `a_weight`/`b_weight` (each shard's `total_seen`) and
+/// drawing uniformly without replacement from the chosen side, falling
+/// back to the other side once one is exhausted. Used by
+/// [`Reservoir::merge_from`] so a merge doesn't bias toward whichever
+/// shard happened to see fewer items.
+fn weighted_fill(
+    rng: &mut ChaCha8Rng,
+    capacity: usize,
+    mut a: Vec<Trace>,
+    a_weight: u64,
+    mut b: Vec<Trace>,
+    b_weight: u64,
+) -> Vec<Trace> {
+    let mut result = Vec::with_capacity(capacity.min(a.len() + b.len()));
+    let total_weight = (a_weight + b_weight).max(1);
+
+    while result.len() < capacity && (!a.is_empty() || !b.is_empty()) {
+        let take_from_a = if a.is_empty() {
+            false
+        } else if b.is_empty() {
+            true
+        } else {
+            rng.gen_range(0..total_weight) < a_weight
+        };
+
+        let pool = if take_from_a { &mut a } else { &mut b };
+        let idx = rng.gen_range(0..pool.len());
+        result.push(pool.swap_remove(idx));
+    }
+
+    result
 }
 
 #[cfg(test)]
@@ -545,6 +1714,49 @@ mod tests {
         assert_eq!(reservoir.stats().eviction_count, eviction_count);
     }
 
+    #[test]
+    fn reservoir_uniform_fast_matches_capacity_and_evicts() {
+        let config = ReservoirConfig::new(10)
+            .with_strategy(SamplingStrategy::UniformFast)
+            .with_seed(42);
+        let mut reservoir = Reservoir::new(config);
+
+        let mut eviction_count = 0;
+        for i in 0..1000 {
+            if reservoir.add(Trace::new(format!("trace-{i}"))).is_some() {
+                eviction_count += 1;
+            }
+        }
+
+        assert_eq!(reservoir.len(), 10);
+        assert!(eviction_count > 0, "Some evictions should have occurred");
+        assert_eq!(reservoir.stats().eviction_count, eviction_count);
+        assert_eq!(reservoir.stats().total_seen, 1000);
+    }
+
+    #[test]
+    fn reservoir_uniform_fast_deterministic_sampling() {
+        let config1 = ReservoirConfig::new(10)
+            .with_strategy(SamplingStrategy::UniformFast)
+            .with_seed(12345);
+        let config2 = ReservoirConfig::new(10)
+            .with_strategy(SamplingStrategy::UniformFast)
+            .with_seed(12345);
+
+        let mut reservoir1 = Reservoir::new(config1);
+        let mut reservoir2 = Reservoir::new(config2);
+
+        for i in 0..500 {
+            reservoir1.add(Trace::new(format!("trace-{i}")));
+            reservoir2.add(Trace::new(format!("trace-{i}")));
+        }
+
+        let ids1: Vec<_> = reservoir1.iter().map(|t| &t.trace_id).collect();
+        let ids2: Vec<_> = reservoir2.iter().map(|t| &t.trace_id).collect();
+
+        assert_eq!(ids1, ids2, "Deterministic sampling should produce same results");
+    }
+
     #[test]
     fn reservoir_deterministic_sampling() {
         // Two reservoirs with same seed should produce same results
@@ -631,6 +1843,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reservoir_max_bytes_caps_estimated_footprint() {
+        let one_trace_size = Trace::new("sizing-probe").estimated_size();
+        let budget = one_trace_size * 20;
+
+        let config = ReservoirConfig::new(10_000)
+            .with_max_bytes(budget)
+            .with_soft_ratio(0.9)
+            .with_seed(7);
+        let mut reservoir = Reservoir::new(config);
+
+        for i in 0..500 {
+            reservoir.add(Trace::new(format!("trace-{i}")));
+        }
+
+        let stats = reservoir.stats();
+        assert!(
+            stats.estimated_bytes <= budget as u64,
+            "estimated_bytes ({}) should stay within the configured budget ({budget})",
+            stats.estimated_bytes
+        );
+        assert!(reservoir.len() < 500, "memory pressure should have evicted some traces");
+    }
+
+    #[test]
+    fn reservoir_max_bytes_evicts_with_memory_pressure_reason() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+
+        let one_trace_size = Trace::new("sizing-probe").estimated_size();
+        let budget = one_trace_size * 5;
+
+        let config = ReservoirConfig::new(10_000).with_max_bytes(budget).with_seed(7);
+        let mut reservoir = Reservoir::new(config);
+
+        let pressure_evictions = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&pressure_evictions);
+        reservoir.on_eviction(move |event| {
+            if event.reason == EvictionReason::MemoryPressure {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        for i in 0..50 {
+            reservoir.add(Trace::new(format!("trace-{i}")));
+        }
+
+        assert!(
+            pressure_evictions.load(Ordering::SeqCst) > 0,
+            "Exceeding max_bytes should trigger MemoryPressure evictions"
+        );
+    }
+
     #[test]
     fn reservoir_time_decay_favors_recent() {
         let config = ReservoirConfig::new(10)
@@ -653,6 +1918,9 @@ mod tests {
                 kind: crate::span::SpanKind::Internal,
                 status: crate::span::SpanStatus::default(),
                 attributes: std::collections::HashMap::new(),
+                events: Vec::new(),
+                links: Vec::new(),
+                sample_rate: 1,
             });
             reservoir.add(trace);
         }
@@ -670,6 +1938,9 @@ mod tests {
                 kind: crate::span::SpanKind::Internal,
                 status: crate::span::SpanStatus::default(),
                 attributes: std::collections::HashMap::new(),
+                events: Vec::new(),
+                links: Vec::new(),
+                sample_rate: 1,
             });
             reservoir.add(trace);
         }
@@ -681,6 +1952,41 @@ mod tests {
         assert!(new_count > 5, "Time decay should favor recent traces, got {new_count}");
     }
 
+    #[test]
+    fn reservoir_time_decay_uses_custom_weight_fn() {
+        // A weight of ~0 gives keys that essentially never beat a weight
+        // of 1.0, so "important" traces should survive every "normal"
+        // arrival regardless of recency.
+        let config = ReservoirConfig::new(5)
+            .with_strategy(SamplingStrategy::TimeDecay)
+            .with_decay_weight_fn(|trace, _high_water_ns| {
+                if trace.trace_id.starts_with("important") {
+                    1.0
+                } else {
+                    1e-9
+                }
+            })
+            .with_seed(7);
+
+        let mut reservoir = Reservoir::new(config);
+
+        for i in 0..5 {
+            reservoir.add(Trace::new(format!("important-{i}")));
+        }
+        for i in 0..200 {
+            reservoir.add(Trace::new(format!("normal-{i}")));
+        }
+
+        let important_count = reservoir
+            .iter()
+            .filter(|t| t.trace_id.starts_with("important"))
+            .count();
+        assert_eq!(
+            important_count, 5,
+            "custom weight function should keep all high-weight traces"
+        );
+    }
+
     #[test]
     fn reservoir_stats() {
         let config = ReservoirConfig::new(100)
@@ -718,4 +2024,375 @@ mod tests {
         let traces = reservoir.into_traces();
         assert_eq!(traces.len(), 10);
     }
+
+    #[test]
+    fn reservoir_save_load_round_trips_traces_and_stats() {
+        let path = std::env::temp_dir().join(format!(
+            "nectar-reservoir-roundtrip-{}.snap",
+            std::process::id()
+        ));
+
+        let mut reservoir = Reservoir::with_capacity(50);
+        for i in 0..30 {
+            let mut trace = Trace::new(format!("trace-{i}"));
+            trace.is_error = i % 10 == 0;
+            reservoir.add(trace);
+        }
+
+        reservoir.save(&path).expect("save should succeed");
+        let loaded = Reservoir::load(&path, 50).expect("load should succeed");
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.stats().total_seen, reservoir.stats().total_seen);
+        assert_eq!(loaded.stats().current_size, reservoir.stats().current_size);
+        assert_eq!(loaded.stats().error_count, reservoir.stats().error_count);
+        assert_eq!(loaded.stats().eviction_count, reservoir.stats().eviction_count);
+    }
+
+    #[test]
+    fn reservoir_load_rejects_bad_magic() {
+        let path = std::env::temp_dir().join(format!(
+            "nectar-reservoir-badmagic-{}.snap",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"not a valid snapshot file").expect("write should succeed");
+
+        let result = Reservoir::load(&path, 50);
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.is_err(), "a bad magic header should be rejected");
+    }
+
+    #[test]
+    #[cfg(feature = "encryption")]
+    fn reservoir_export_import_encrypted_round_trips_traces_and_stats() {
+        let path = std::env::temp_dir().join(format!(
+            "nectar-reservoir-encrypted-{}.snap",
+            std::process::id()
+        ));
+        let key = [7u8; 32];
+
+        let mut reservoir = Reservoir::with_capacity(50);
+        for i in 0..30 {
+            let mut trace = Trace::new(format!("trace-{i}"));
+            trace.is_error = i % 10 == 0;
+            reservoir.add(trace);
+        }
+
+        reservoir
+            .export_encrypted(&path, &key)
+            .expect("export should succeed");
+        let loaded = Reservoir::import_encrypted(&path, &key, 50).expect("import should succeed");
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.stats().total_seen, reservoir.stats().total_seen);
+        assert_eq!(loaded.stats().current_size, reservoir.stats().current_size);
+        assert_eq!(loaded.stats().error_count, reservoir.stats().error_count);
+    }
+
+    #[test]
+    #[cfg(feature = "encryption")]
+    fn reservoir_import_encrypted_rejects_wrong_key() {
+        let path = std::env::temp_dir().join(format!(
+            "nectar-reservoir-encrypted-wrongkey-{}.snap",
+            std::process::id()
+        ));
+
+        let mut reservoir = Reservoir::with_capacity(50);
+        reservoir.add(Trace::new("trace-0"));
+        reservoir
+            .export_encrypted(&path, &[1u8; 32])
+            .expect("export should succeed");
+
+        let result = Reservoir::import_encrypted(&path, &[2u8; 32], 50);
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.is_err(), "a wrong key should fail tag verification");
+    }
+
+    #[test]
+    #[cfg(not(feature = "encryption"))]
+    fn reservoir_export_encrypted_errors_without_feature() {
+        let path = std::env::temp_dir().join(format!(
+            "nectar-reservoir-encrypted-nofeature-{}.snap",
+            std::process::id()
+        ));
+
+        let reservoir = Reservoir::with_capacity(10);
+        let result = reservoir.export_encrypted(&path, &[0u8; 32]);
+
+        assert!(
+            result.is_err(),
+            "export_encrypted should fail when the `encryption` feature is disabled"
+        );
+    }
+
+    #[test]
+    fn reservoir_merge_fills_to_capacity_and_sums_total_seen() {
+        let mut a = Reservoir::new(ReservoirConfig::new(10).with_seed(1));
+        let mut b = Reservoir::new(ReservoirConfig::new(10).with_seed(2));
+
+        for i in 0..100 {
+            a.add(Trace::new(format!("a-{i}")));
+        }
+        for i in 0..20 {
+            b.add(Trace::new(format!("b-{i}")));
+        }
+
+        let merged = a.merge(b);
+
+        assert_eq!(merged.len(), 10, "merge should fill the reservoir to capacity");
+        assert_eq!(merged.stats().total_seen, 120, "total_seen should sum both shards");
+    }
+
+    #[test]
+    fn reservoir_merge_is_deterministic() {
+        let build = || {
+            let mut a = Reservoir::new(ReservoirConfig::new(10).with_seed(11));
+            let mut b = Reservoir::new(ReservoirConfig::new(10).with_seed(22));
+            for i in 0..50 {
+                a.add(Trace::new(format!("a-{i}")));
+                b.add(Trace::new(format!("b-{i}")));
+            }
+            a.merge(b)
+        };
+
+        let merged1 = build();
+        let merged2 = build();
+
+        let ids1: Vec<_> = merged1.iter().map(|t| &t.trace_id).collect();
+        let ids2: Vec<_> = merged2.iter().map(|t| &t.trace_id).collect();
+        assert_eq!(ids1, ids2, "merging the same two shards should be reproducible");
+    }
+
+    #[test]
+    fn reservoir_merge_stratified_preserves_errors_from_both_shards() {
+        let config = || {
+            ReservoirConfig::new(100)
+                .with_strategy(SamplingStrategy::Stratified)
+                .with_preserve_errors(true)
+        };
+        let mut a = Reservoir::new(config().with_seed(5));
+        let mut b = Reservoir::new(config().with_seed(6));
+
+        for i in 0..10 {
+            a.add(Trace::new(format!("a-error-{i}")).with_status(500));
+        }
+        for i in 0..10 {
+            b.add(Trace::new(format!("b-error-{i}")).with_status(500));
+        }
+
+        let merged = a.merge(b);
+        assert_eq!(
+            merged.stats().error_count,
+            20,
+            "errors preserved by both shards should all survive the merge"
+        );
+    }
+
+    #[test]
+    fn reservoir_duration_histogram_buckets_and_sums_to_total() {
+        let mut reservoir = Reservoir::with_capacity(100);
+
+        for i in 0..50 {
+            reservoir.add(Trace::new(format!("trace-{i}")).with_duration(Duration::from_millis(i)));
+        }
+
+        let histogram = reservoir.duration_histogram(10).expect("enough samples for 10 buckets");
+
+        assert_eq!(histogram.bucket_bounds.len(), 10);
+        assert_eq!(histogram.counts.len(), 10);
+        assert_eq!(histogram.total, 50);
+        assert_eq!(histogram.counts.iter().sum::<u64>(), 50);
+    }
+
+    #[test]
+    fn reservoir_duration_histogram_none_when_too_few_samples() {
+        let mut reservoir = Reservoir::with_capacity(100);
+
+        for i in 0..5 {
+            reservoir.add(Trace::new(format!("trace-{i}")).with_duration(Duration::from_millis(i)));
+        }
+
+        assert!(reservoir.duration_histogram(10).is_none());
+    }
+
+    #[test]
+    fn reservoir_duration_histogram_single_bucket_when_durations_equal() {
+        let mut reservoir = Reservoir::with_capacity(100);
+
+        for i in 0..20 {
+            reservoir.add(Trace::new(format!("trace-{i}")).with_duration(Duration::from_millis(100)));
+        }
+
+        let histogram = reservoir.duration_histogram(5).expect("enough samples");
+        assert_eq!(histogram.bucket_bounds, vec![Duration::from_millis(100)]);
+        assert_eq!(histogram.counts, vec![20]);
+    }
+
+    #[test]
+    fn reservoir_duration_histogram_percentile_walks_cumulative_counts() {
+        let mut reservoir = Reservoir::with_capacity(200);
+
+        for i in 0..100 {
+            reservoir.add(Trace::new(format!("trace-{i}")).with_duration(Duration::from_millis(i)));
+        }
+
+        let histogram = reservoir.duration_histogram(10).expect("enough samples");
+        let p50 = histogram.percentile(50.0);
+        let p99 = histogram.percentile(99.0);
+
+        assert!(p50 < p99, "p50 should be smaller than p99");
+        assert!(p99 <= Duration::from_millis(99));
+    }
+
+    #[test]
+    fn reservoir_weighted_matches_capacity_and_evicts() {
+        let mut reservoir = Reservoir::with_weighting(10, |trace| if trace.is_error { 10.0 } else { 1.0 });
+
+        let mut eviction_count = 0;
+        for i in 0..200 {
+            if reservoir.add(Trace::new(format!("trace-{i}"))).is_some() {
+                eviction_count += 1;
+            }
+        }
+
+        assert_eq!(reservoir.len(), 10);
+        assert!(eviction_count > 0, "Some evictions should have occurred");
+        assert_eq!(reservoir.stats().eviction_count, eviction_count);
+        assert_eq!(reservoir.stats().total_seen, 200);
+    }
+
+    #[test]
+    fn reservoir_weighted_favors_errors() {
+        let config = ReservoirConfig::new(10)
+            .with_strategy(SamplingStrategy::Weighted)
+            .with_seed(42);
+        let mut reservoir = Reservoir::new(config);
+
+        // Mostly normal traces, with a handful of errors mixed in.
+        for i in 0..500 {
+            let mut trace = Trace::new(format!("normal-{i}"));
+            trace.is_error = i % 50 == 0;
+            reservoir.add(trace);
+        }
+        for i in 0..5 {
+            reservoir.add(Trace::new(format!("error-{i}")).with_status(500));
+        }
+
+        let survivors: Vec<_> = reservoir.iter().collect();
+        let error_survivors = survivors.iter().filter(|t| t.is_error).count();
+
+        assert!(
+            error_survivors >= 3,
+            "most of the high-weight error traces should survive eviction, got {error_survivors}"
+        );
+    }
+
+    #[test]
+    fn reservoir_weighted_deterministic_sampling() {
+        let config1 = ReservoirConfig::new(10)
+            .with_strategy(SamplingStrategy::Weighted)
+            .with_seed(12345);
+        let config2 = ReservoirConfig::new(10)
+            .with_strategy(SamplingStrategy::Weighted)
+            .with_seed(12345);
+
+        let mut reservoir1 = Reservoir::new(config1);
+        let mut reservoir2 = Reservoir::new(config2);
+
+        for i in 0..500 {
+            reservoir1.add(Trace::new(format!("trace-{i}")));
+            reservoir2.add(Trace::new(format!("trace-{i}")));
+        }
+
+        let ids1: Vec<_> = reservoir1.iter().map(|t| &t.trace_id).collect();
+        let ids2: Vec<_> = reservoir2.iter().map(|t| &t.trace_id).collect();
+
+        assert_eq!(ids1, ids2, "Deterministic sampling should produce same results");
+    }
+
+    #[test]
+    fn reservoir_weighted_uses_custom_weight_fn() {
+        let config = ReservoirConfig::new(10)
+            .with_strategy(SamplingStrategy::Weighted)
+            .with_weight_fn(|trace| if trace.trace_id.starts_with("important") { 100.0 } else { 1.0 })
+            .with_seed(7);
+        let mut reservoir = Reservoir::new(config);
+
+        for i in 0..500 {
+            reservoir.add(Trace::new(format!("filler-{i}")));
+        }
+        for i in 0..3 {
+            reservoir.add(Trace::new(format!("important-{i}")));
+        }
+
+        let survivors: Vec<_> = reservoir.iter().collect();
+        let important_survivors = survivors
+            .iter()
+            .filter(|t| t.trace_id.starts_with("important"))
+            .count();
+
+        assert!(
+            important_survivors >= 2,
+            "custom weight function should keep most important traces, got {important_survivors}"
+        );
+    }
+
+    #[test]
+    fn reservoir_rate_limit_sheds_excess_without_inflating_total_seen() {
+        let mut reservoir = Reservoir::with_rate_limit(50, 5.0);
+
+        for i in 0..20 {
+            reservoir.add(Trace::new(format!("trace-{i}")));
+        }
+
+        let stats = reservoir.stats();
+        assert!(
+            stats.throttled_count > 0,
+            "a burst well over the rate limit should shed some traces"
+        );
+        assert_eq!(
+            stats.total_seen + stats.throttled_count,
+            20,
+            "every trace should be either admitted or throttled, never both or neither"
+        );
+        assert!(
+            stats.total_seen <= 5,
+            "only the initial burst allowance should be admitted before the synthetic clock refills"
+        );
+    }
+
+    #[test]
+    fn reservoir_without_rate_limit_never_throttles() {
+        let mut reservoir = Reservoir::with_capacity(50);
+
+        for i in 0..20 {
+            reservoir.add(Trace::new(format!("trace-{i}")));
+        }
+
+        let stats = reservoir.stats();
+        assert_eq!(stats.throttled_count, 0);
+        assert_eq!(stats.total_seen, 20);
+    }
+
+    #[test]
+    fn reservoir_rate_limit_refills_over_time() {
+        let mut reservoir = Reservoir::new(ReservoirConfig::new(50).with_rate_limit(5.0, 5.0));
+
+        for i in 0..5 {
+            reservoir.add(Trace::new(format!("burst-{i}")));
+        }
+        assert_eq!(reservoir.stats().throttled_count, 0, "initial burst should fit in the bucket");
+
+        let mut later = Trace::new("later");
+        later.add_span(crate::span::Span::new("s0", "op").with_start_time_ns(2_000_000_000));
+        reservoir.add(later);
+
+        assert_eq!(
+            reservoir.stats().throttled_count,
+            0,
+            "a trace arriving after the bucket has had time to refill should be admitted"
+        );
+    }
 }
@@ -128,10 +128,25 @@ pub enum AttributeValue {
     Bool(bool),
     /// An array of string values.
     StringArray(Vec<String>),
+    /// Raw binary data (OTLP `bytes_value`).
+    Bytes(Vec<u8>),
+    /// A homogeneous array of integers.
+    IntArray(Vec<i64>),
+    /// A homogeneous array of floating-point numbers.
+    DoubleArray(Vec<f64>),
+    /// A homogeneous array of booleans.
+    BoolArray(Vec<bool>),
+    /// A nested key-value map (OTLP `kvlist_value`).
+    KvList(HashMap<String, AttributeValue>),
 }
 
 impl AttributeValue {
     /// Converts this value to a string representation.
+    ///
+    /// Bytes render as lowercase hex, and `KvList` renders as a stable,
+    /// JSON-ish `{key: value, ...}` form (entries sorted by key) so policy
+    /// predicates that match on structured attributes still get a
+    /// deterministic string to compare against.
     #[must_use]
     pub fn as_string(&self) -> String {
         match self {
@@ -140,6 +155,23 @@ impl AttributeValue {
             Self::Float(f) => f.to_string(),
             Self::Bool(b) => b.to_string(),
             Self::StringArray(arr) => arr.join(","),
+            Self::Bytes(bytes) => bytes.iter().map(|b| format!("{b:02x}")).collect(),
+            Self::IntArray(arr) => arr.iter().map(i64::to_string).collect::<Vec<_>>().join(","),
+            Self::DoubleArray(arr) => arr.iter().map(f64::to_string).collect::<Vec<_>>().join(","),
+            Self::BoolArray(arr) => arr
+                .iter()
+                .map(bool::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+            Self::KvList(map) => {
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                let entries: Vec<String> = keys
+                    .into_iter()
+                    .map(|k| format!("{k}: {}", map[k].as_string()))
+                    .collect();
+                format!("{{{}}}", entries.join(", "))
+            }
         }
     }
 
@@ -160,6 +192,26 @@ impl AttributeValue {
             _ => None,
         }
     }
+
+    /// Estimates this value's in-memory footprint in bytes: the length of
+    /// any string/byte payload plus a fixed cost per scalar, recursing
+    /// into arrays and nested `KvList`s. Used by
+    /// [`crate::trace::Trace::estimated_size`].
+    #[must_use]
+    pub fn estimated_size(&self) -> usize {
+        const SCALAR_BYTES: usize = 8;
+        match self {
+            Self::String(s) => s.len(),
+            Self::Int(_) | Self::Float(_) => SCALAR_BYTES,
+            Self::Bool(_) => 1,
+            Self::StringArray(arr) => arr.iter().map(String::len).sum(),
+            Self::Bytes(bytes) => bytes.len(),
+            Self::IntArray(arr) => arr.len() * SCALAR_BYTES,
+            Self::DoubleArray(arr) => arr.len() * SCALAR_BYTES,
+            Self::BoolArray(arr) => arr.len(),
+            Self::KvList(map) => map.iter().map(|(k, v)| k.len() + v.estimated_size()).sum(),
+        }
+    }
 }
 
 impl From<String> for AttributeValue {
@@ -192,6 +244,106 @@ impl From<bool> for AttributeValue {
     }
 }
 
+impl From<Vec<u8>> for AttributeValue {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl From<HashMap<String, AttributeValue>> for AttributeValue {
+    fn from(map: HashMap<String, AttributeValue>) -> Self {
+        Self::KvList(map)
+    }
+}
+
+/// A timestamped event recorded during a span's lifetime.
+///
+/// The OTLP convention for a caught exception is an event named
+/// `"exception"` carrying `exception.type` / `exception.message` /
+/// `exception.stacktrace` attributes — see [`Span::exception_type`] and
+/// [`Span::has_exception`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpanEvent {
+    /// The event name (e.g. `"exception"`).
+    pub name: String,
+    /// Event time in nanoseconds since Unix epoch.
+    pub time_ns: u64,
+    /// Event attributes.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub attributes: HashMap<String, AttributeValue>,
+}
+
+impl SpanEvent {
+    /// Creates a new span event with the given name and time.
+    #[must_use]
+    pub fn new(name: impl Into<String>, time_ns: u64) -> Self {
+        Self {
+            name: name.into(),
+            time_ns,
+            attributes: HashMap::new(),
+        }
+    }
+
+    /// Adds an attribute.
+    #[must_use]
+    pub fn with_attribute(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<AttributeValue>,
+    ) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns true if this event records a caught exception.
+    #[must_use]
+    pub fn is_exception(&self) -> bool {
+        self.name == "exception"
+    }
+
+    /// Gets an attribute value by key.
+    #[must_use]
+    pub fn get_attribute(&self, key: &str) -> Option<&AttributeValue> {
+        self.attributes.get(key)
+    }
+}
+
+/// A reference to a related span, typically in another trace (e.g. a
+/// producer/consumer link across a message queue).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpanLink {
+    /// The linked span's trace ID.
+    pub trace_id: String,
+    /// The linked span's ID.
+    pub span_id: String,
+    /// Link attributes.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub attributes: HashMap<String, AttributeValue>,
+}
+
+impl SpanLink {
+    /// Creates a new span link to the given trace and span ID.
+    #[must_use]
+    pub fn new(trace_id: impl Into<String>, span_id: impl Into<String>) -> Self {
+        Self {
+            trace_id: trace_id.into(),
+            span_id: span_id.into(),
+            attributes: HashMap::new(),
+        }
+    }
+
+    /// Adds an attribute.
+    #[must_use]
+    pub fn with_attribute(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<AttributeValue>,
+    ) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+}
+
 /// A span representing a unit of work within a trace.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Span {
@@ -217,6 +369,21 @@ pub struct Span {
     /// Span attributes.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub attributes: HashMap<String, AttributeValue>,
+    /// Timestamped events recorded during the span, notably exceptions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<SpanEvent>,
+    /// Links to related spans (e.g. producer/consumer across a queue).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<SpanLink>,
+    /// How many real-world events this one retained span statistically
+    /// represents (e.g. Honeycomb's `sample_rate`/`meta.sample_rate`).
+    /// Defaults to 1 for unsampled data; see [`Span::effective_weight`].
+    #[serde(default = "default_sample_rate")]
+    pub sample_rate: u32,
+}
+
+const fn default_sample_rate() -> u32 {
+    1
 }
 
 impl Span {
@@ -233,6 +400,9 @@ impl Span {
             kind: SpanKind::default(),
             status: SpanStatus::default(),
             attributes: HashMap::new(),
+            events: Vec::new(),
+            links: Vec::new(),
+            sample_rate: default_sample_rate(),
         }
     }
 
@@ -280,11 +450,66 @@ impl Span {
 
     /// Adds an attribute.
     #[must_use]
-    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<AttributeValue>) -> Self {
+    pub fn with_attribute(
+        mut self,
+        key: impl Into<String>,
+        value: impl Into<AttributeValue>,
+    ) -> Self {
         self.attributes.insert(key.into(), value.into());
         self
     }
 
+    /// Adds an event.
+    #[must_use]
+    pub fn with_event(mut self, event: SpanEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    /// Replaces this span's events wholesale, e.g. when an ingestor has
+    /// already assembled the full list instead of pushing one at a time.
+    #[must_use]
+    pub fn with_events(mut self, events: Vec<SpanEvent>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Returns this span's timestamped events.
+    #[must_use]
+    pub fn events(&self) -> &[SpanEvent] {
+        &self.events
+    }
+
+    /// Adds a link.
+    #[must_use]
+    pub fn with_link(mut self, link: SpanLink) -> Self {
+        self.links.push(link);
+        self
+    }
+
+    /// Returns this span's links to related spans.
+    #[must_use]
+    pub fn links(&self) -> &[SpanLink] {
+        &self.links
+    }
+
+    /// Sets the sample rate this span was retained at.
+    #[must_use]
+    pub const fn with_sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// How many real-world events this retained span represents, for
+    /// weighting aggregates over sampled data (see
+    /// [`Trace::weighted_span_summary`](crate::trace::Trace::weighted_span_summary)).
+    /// Clamps a missing/zero `sample_rate` to 1 so an unsampled span
+    /// still contributes its own weight of one.
+    #[must_use]
+    pub fn effective_weight(&self) -> u32 {
+        self.sample_rate.max(1)
+    }
+
     /// Returns true if this is a root span (no parent).
     #[must_use]
     pub const fn is_root(&self) -> bool {
@@ -319,6 +544,35 @@ impl Span {
             .get("http.route")
             .and_then(AttributeValue::as_str)
     }
+
+    /// Returns true if this span recorded an `exception` event.
+    ///
+    /// This catches errors that were handled (and so left `status` as
+    /// `Ok`) but are still worth retaining for drilldown.
+    #[must_use]
+    pub fn has_exception(&self) -> bool {
+        self.events.iter().any(SpanEvent::is_exception)
+    }
+
+    /// Gets the `exception.type` attribute of the first exception event, if any.
+    #[must_use]
+    pub fn exception_type(&self) -> Option<&str> {
+        self.events
+            .iter()
+            .find(|e| e.is_exception())
+            .and_then(|e| e.get_attribute("exception.type"))
+            .and_then(AttributeValue::as_str)
+    }
+
+    /// Gets the `exception.message` attribute of the first exception event, if any.
+    #[must_use]
+    pub fn exception_message(&self) -> Option<&str> {
+        self.events
+            .iter()
+            .find(|e| e.is_exception())
+            .and_then(|e| e.get_attribute("exception.message"))
+            .and_then(AttributeValue::as_str)
+    }
 }
 
 #[cfg(test)]
@@ -343,13 +597,31 @@ mod tests {
 
     #[test]
     fn span_with_parent() {
-        let span = Span::new("span-002", "db.query")
-            .with_parent("span-001");
+        let span = Span::new("span-002", "db.query").with_parent("span-001");
 
         assert!(!span.is_root());
         assert_eq!(span.parent_span_id, Some("span-001".to_string()));
     }
 
+    #[test]
+    fn span_sample_rate_defaults_to_one() {
+        let span = Span::new("s", "op");
+        assert_eq!(span.sample_rate, 1);
+        assert_eq!(span.effective_weight(), 1);
+    }
+
+    #[test]
+    fn span_effective_weight_clamps_a_zero_sample_rate() {
+        let span = Span::new("s", "op").with_sample_rate(0);
+        assert_eq!(span.effective_weight(), 1);
+    }
+
+    #[test]
+    fn span_effective_weight_honors_a_positive_sample_rate() {
+        let span = Span::new("s", "op").with_sample_rate(50);
+        assert_eq!(span.effective_weight(), 50);
+    }
+
     #[test]
     fn span_error_detection() {
         let ok = Span::new("ok", "op").with_status(SpanStatus::ok());
@@ -391,4 +663,80 @@ mod tests {
         let b = AttributeValue::from(true);
         assert_eq!(b.as_string(), "true");
     }
+
+    #[test]
+    fn attribute_value_bytes_renders_as_hex() {
+        let bytes = AttributeValue::from(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(bytes.as_string(), "deadbeef");
+    }
+
+    #[test]
+    fn attribute_value_arrays_render_joined() {
+        assert_eq!(AttributeValue::IntArray(vec![1, 2, 3]).as_string(), "1,2,3");
+        assert_eq!(
+            AttributeValue::DoubleArray(vec![1.5, 2.5]).as_string(),
+            "1.5,2.5"
+        );
+        assert_eq!(
+            AttributeValue::BoolArray(vec![true, false]).as_string(),
+            "true,false"
+        );
+    }
+
+    #[test]
+    fn attribute_value_kvlist_renders_sorted_stable_form() {
+        let mut map = HashMap::new();
+        map.insert("b".to_string(), AttributeValue::from(2i64));
+        map.insert("a".to_string(), AttributeValue::from("x"));
+
+        let kvlist = AttributeValue::from(map);
+        assert_eq!(kvlist.as_string(), "{a: x, b: 2}");
+    }
+
+    #[test]
+    fn span_exception_event_detection() {
+        let span = Span::new("span-003", "handler").with_event(
+            SpanEvent::new("exception", 100)
+                .with_attribute("exception.type", "ValueError")
+                .with_attribute("exception.message", "bad input"),
+        );
+
+        assert!(span.has_exception());
+        assert_eq!(span.exception_type(), Some("ValueError"));
+        assert_eq!(span.exception_message(), Some("bad input"));
+
+        let clean = Span::new("span-004", "handler").with_status(SpanStatus::ok());
+        assert!(!clean.has_exception());
+        assert_eq!(clean.exception_type(), None);
+    }
+
+    #[test]
+    fn span_links_to_related_spans() {
+        let span =
+            Span::new("span-005", "consume").with_link(SpanLink::new("trace-1", "producer-span"));
+
+        assert_eq!(span.links.len(), 1);
+        assert_eq!(span.links[0].trace_id, "trace-1");
+        assert_eq!(span.links[0].span_id, "producer-span");
+    }
+
+    #[test]
+    fn span_with_events_replaces_the_full_list() {
+        let span = Span::new("span-006", "handler").with_events(vec![
+            SpanEvent::new("start", 100),
+            SpanEvent::new("exception", 200).with_attribute("exception.type", "ValueError"),
+        ]);
+
+        assert_eq!(span.events().len(), 2);
+        assert_eq!(span.events()[1].name, "exception");
+    }
+
+    #[test]
+    fn span_links_accessor_matches_the_links_field() {
+        let span =
+            Span::new("span-007", "consume").with_link(SpanLink::new("trace-1", "producer-span"));
+
+        assert_eq!(span.links().len(), 1);
+        assert_eq!(span.links()[0].trace_id, "trace-1");
+    }
 }
@@ -1,10 +1,34 @@
 //! Trace data model.
 
+use crate::conversion::{AttrValue, Conversion};
+use crate::error::Result;
 use crate::span::Span;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
 
+/// Rough fixed overhead per span (ids, timestamps, kind, status) charged
+/// by [`Trace::estimated_size`]; not an exact memory accounting, just
+/// enough to compare traces of different shapes under a byte budget.
+const ESTIMATED_SPAN_OVERHEAD_BYTES: usize = 128;
+
+/// A resource + instrumentation scope pairing that contributed spans to a
+/// trace, as resolved during OTLP ingestion (see
+/// [`crate::ingestor::OtlpIngestor`]). Other ingestors leave
+/// [`Trace::resource_scopes`] empty, since they have no equivalent
+/// resource/scope grouping to report.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ResourceScope {
+    /// The resource's `service.name`, or empty if the resource didn't set one.
+    pub service: String,
+    /// The instrumentation scope's name, if the scope was present and named.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope_name: Option<String>,
+    /// The instrumentation scope's version, if the scope set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope_version: Option<String>,
+}
+
 /// A trace exemplar for policy simulation.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Trace {
@@ -28,6 +52,15 @@ pub struct Trace {
     /// Number of spans in this trace.
     #[serde(default)]
     pub span_count: usize,
+    /// Resource/scope groupings that contributed spans to this trace (see
+    /// [`ResourceScope`]). Populated by OTLP ingestion; empty otherwise.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resource_scopes: Vec<ResourceScope>,
+    /// Optional conversions for interpreting `attributes` as typed values.
+    /// Not part of the trace's serialized form; set it after
+    /// deserialization with [`Trace::with_attr_schema`].
+    #[serde(skip)]
+    pub attr_schema: HashMap<String, Conversion>,
 }
 
 impl Trace {
@@ -44,6 +77,8 @@ impl Trace {
             attributes: HashMap::new(),
             spans: Vec::new(),
             span_count: 0,
+            resource_scopes: Vec::new(),
+            attr_schema: HashMap::new(),
         }
     }
 
@@ -63,6 +98,8 @@ impl Trace {
             attributes: HashMap::new(),
             span_count: spans.len(),
             spans,
+            resource_scopes: Vec::new(),
+            attr_schema: HashMap::new(),
         };
         trace.compute_summary_from_spans();
         trace
@@ -160,6 +197,34 @@ impl Trace {
         self.spans.iter().map(|s| s.start_time_ns).min()
     }
 
+    /// Estimates this trace's in-memory footprint in bytes: a fixed
+    /// overhead per span plus the byte length of every attribute key and
+    /// value, on both the trace itself and its spans. Used by
+    /// [`crate::reservoir::ReservoirConfig::with_max_bytes`] to bound the
+    /// corpus by estimated memory instead of a raw trace count.
+    #[must_use]
+    pub fn estimated_size(&self) -> usize {
+        let span_count = self.span_count.max(self.spans.len()).max(1);
+        let mut size = span_count * ESTIMATED_SPAN_OVERHEAD_BYTES;
+        size += self.trace_id.len();
+        size += self
+            .attributes
+            .iter()
+            .map(|(k, v)| k.len() + v.len())
+            .sum::<usize>();
+
+        for span in &self.spans {
+            size += span.name.len() + span.service.len();
+            size += span
+                .attributes
+                .iter()
+                .map(|(k, v)| k.len() + v.estimated_size())
+                .sum::<usize>();
+        }
+
+        size
+    }
+
     /// Sets the duration.
     #[must_use]
     pub const fn with_duration(mut self, duration: Duration) -> Self {
@@ -167,6 +232,21 @@ impl Trace {
         self
     }
 
+    /// Attaches a pre-built span tree, setting `span_count` to match.
+    ///
+    /// Unlike [`Trace::from_spans`], this doesn't recompute
+    /// `duration`/`service`/`endpoint`/`status`/`is_error` from the
+    /// spans - for a builder chain that's already set those via
+    /// [`Trace::with_service`]/[`Trace::with_status`]/etc., call this
+    /// last to add span-level detail without overriding the trace-level
+    /// summary those calls established.
+    #[must_use]
+    pub fn with_spans(mut self, spans: Vec<Span>) -> Self {
+        self.span_count = spans.len();
+        self.spans = spans;
+        self
+    }
+
     /// Sets the HTTP status.
     #[must_use]
     pub const fn with_status(mut self, status: u16) -> Self {
@@ -195,6 +275,304 @@ impl Trace {
         self.attributes.insert(key.into(), value.into());
         self
     }
+
+    /// Sets the resource/scope groupings that contributed spans to this
+    /// trace (see [`ResourceScope`]).
+    #[must_use]
+    pub fn with_resource_scopes(mut self, resource_scopes: Vec<ResourceScope>) -> Self {
+        self.resource_scopes = resource_scopes;
+        self
+    }
+
+    /// Registers a [`Conversion`] for an attribute key, so it can later be
+    /// read with [`Trace::attr_typed`] or one of its typed convenience
+    /// methods.
+    #[must_use]
+    pub fn with_attr_schema(mut self, key: impl Into<String>, conversion: Conversion) -> Self {
+        self.attr_schema.insert(key.into(), conversion);
+        self
+    }
+
+    /// Reads an attribute and converts it using the schema registered for
+    /// that key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the attribute is present but fails to convert
+    /// under its registered schema.
+    pub fn attr_typed(&self, key: &str) -> Result<Option<AttrValue>> {
+        let Some(raw) = self.attributes.get(key) else {
+            return Ok(None);
+        };
+        let conversion = self.attr_schema.get(key).unwrap_or(&Conversion::Bytes);
+        conversion.convert(raw).map(Some)
+    }
+
+    /// Reads an attribute as an `i64`, using its registered conversion
+    /// (or `int` if none is registered).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the attribute is present but is not a valid
+    /// integer under its conversion.
+    pub fn attr_i64(&self, key: &str) -> Result<Option<i64>> {
+        let Some(raw) = self.attributes.get(key) else {
+            return Ok(None);
+        };
+        let conversion = match self.attr_schema.get(key) {
+            Some(c) => c,
+            None => &Conversion::Integer,
+        };
+        conversion.convert(raw).map(|v| v.as_i64())
+    }
+
+    /// Reads an attribute as an `f64`, using its registered conversion
+    /// (or `float` if none is registered).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the attribute is present but is not a valid
+    /// float under its conversion.
+    pub fn attr_f64(&self, key: &str) -> Result<Option<f64>> {
+        let Some(raw) = self.attributes.get(key) else {
+            return Ok(None);
+        };
+        let conversion = match self.attr_schema.get(key) {
+            Some(c) => c,
+            None => &Conversion::Float,
+        };
+        conversion.convert(raw).map(|v| v.as_f64())
+    }
+
+    /// Renders this trace's span graph as a Graphviz DOT digraph.
+    ///
+    /// Each span becomes a node labeled with its name and duration; edges
+    /// point from parent to child. Error spans are rendered red so a
+    /// failure's blast radius is visible at a glance.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut out = format!("digraph trace_{} {{\n", sanitize_id(&self.trace_id));
+
+        for span in &self.spans {
+            let color = if span.is_error() { ", color=red" } else { "" };
+            out.push_str(&format!(
+                "  \"{}\" [label=\"{}\\n{}ms\"{color}];\n",
+                span.span_id,
+                span.name.replace('"', "\\\""),
+                span.duration.as_millis(),
+            ));
+        }
+
+        for span in &self.spans {
+            if let Some(parent) = &span.parent_span_id {
+                out.push_str(&format!("  \"{parent}\" -> \"{}\";\n", span.span_id));
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Computes the critical path through this trace's span DAG: the
+    /// sequence of spans that actually determine the end-to-end
+    /// `duration`, with time not attributable to any child span charged
+    /// to its parent's own "self time".
+    ///
+    /// Walks backward from the root span's end, at each point picking the
+    /// child whose interval covers the time just before the cursor and
+    /// has the latest end (ties broken by span id), recursing into it and
+    /// charging any gap before it to the parent. Child intervals that
+    /// spill past their parent (clock skew) are clamped to the parent's
+    /// interval. Uses the first parentless span as the root, falling back
+    /// to the earliest-starting span when there is none (unlike
+    /// [`Trace::compute_summary_from_spans`], which falls back to the first
+    /// span in the list).
+    ///
+    /// Returns an empty list for a trace with no spans.
+    #[must_use]
+    pub fn critical_path(&self) -> Vec<CriticalSegment> {
+        if self.spans.is_empty() {
+            return Vec::new();
+        }
+
+        let mut children: HashMap<&str, Vec<&Span>> = HashMap::new();
+        for span in &self.spans {
+            if let Some(parent) = &span.parent_span_id {
+                children.entry(parent.as_str()).or_default().push(span);
+            }
+        }
+
+        let root = self.spans.iter().find(|s| s.is_root()).unwrap_or_else(|| {
+            self.spans
+                .iter()
+                .min_by_key(|s| s.start_time_ns)
+                .expect("checked non-empty above")
+        });
+
+        let root_start = root.start_time_ns;
+        #[allow(clippy::cast_possible_truncation)]
+        let root_end =
+            root_start.saturating_add(root.duration.as_nanos().min(u128::from(u64::MAX)) as u64);
+
+        let mut segments = Vec::new();
+        walk_critical_path(root, root_start, root_end, &children, &mut segments);
+        segments.reverse();
+        segments
+    }
+
+    /// Computes span and error counts weighted by each span's
+    /// [`Span::effective_weight`], alongside the raw (unweighted) span
+    /// count, so aggregates over sampled data (e.g. Honeycomb's
+    /// `sample_rate`) estimate the true population instead of undercounting
+    /// it by the retained span count.
+    ///
+    /// A span with `sample_rate: 50` contributes 50 to
+    /// [`WeightedSpanSummary::effective_span_count`] and, if it is an
+    /// error, 50 to [`WeightedSpanSummary::effective_error_count`]; a span
+    /// with no sample rate set contributes 1 to both, same as
+    /// `raw_span_count`.
+    #[must_use]
+    pub fn weighted_span_summary(&self) -> WeightedSpanSummary {
+        let mut effective_span_count = 0u64;
+        let mut effective_error_count = 0u64;
+        let mut service_weights: HashMap<String, u64> = HashMap::new();
+
+        for span in &self.spans {
+            let weight = u64::from(span.effective_weight());
+            effective_span_count += weight;
+            if span.is_error() {
+                effective_error_count += weight;
+            }
+            if !span.service.is_empty() {
+                *service_weights.entry(span.service.clone()).or_default() += weight;
+            }
+        }
+
+        WeightedSpanSummary {
+            raw_span_count: self.spans.len(),
+            effective_span_count,
+            effective_error_count,
+            service_weights,
+        }
+    }
+}
+
+/// A segment of a trace's [`Trace::critical_path`]: the portion of the
+/// trace's total duration actually spent in (or charged to) one span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CriticalSegment {
+    /// The span this segment is charged to.
+    pub span_id: String,
+    /// How much of the trace's end-to-end duration this segment covers.
+    pub duration_on_path: Duration,
+}
+
+/// Sample-rate-weighted span and error counts for a [`Trace`], from
+/// [`Trace::weighted_span_summary`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WeightedSpanSummary {
+    /// The number of spans actually retained on this trace, ignoring
+    /// sample rate.
+    pub raw_span_count: usize,
+    /// The estimated true span count: `sum(span.effective_weight())`.
+    pub effective_span_count: u64,
+    /// The estimated true error count: `sum(effective_weight where is_error)`.
+    pub effective_error_count: u64,
+    /// The estimated true span count per service, keyed by service name.
+    pub service_weights: HashMap<String, u64>,
+}
+
+impl WeightedSpanSummary {
+    /// The estimated true error rate: `effective_error_count /
+    /// effective_span_count`. Returns `0.0` for a trace with no spans,
+    /// rather than dividing by zero.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn weighted_error_rate(&self) -> f64 {
+        if self.effective_span_count == 0 {
+            return 0.0;
+        }
+        self.effective_error_count as f64 / self.effective_span_count as f64
+    }
+}
+
+/// Clamps a span's `[start, end]` interval (in nanoseconds) to its
+/// parent's interval, handling clock skew where the child would
+/// otherwise start before or end after the parent.
+fn clamped_interval(span: &Span, parent_start: u64, parent_end: u64) -> Option<(u64, u64)> {
+    #[allow(clippy::cast_possible_truncation)]
+    let raw_end = span
+        .start_time_ns
+        .saturating_add(span.duration.as_nanos().min(u128::from(u64::MAX)) as u64);
+
+    let start = span.start_time_ns.clamp(parent_start, parent_end);
+    let end = raw_end.clamp(parent_start, parent_end);
+
+    (start < end).then_some((start, end))
+}
+
+/// Walks backward from `end` to `start` within `span`'s own interval,
+/// recursing into whichever child covers the time just before the
+/// cursor, and charging any uncovered gap to `span` itself. Appends
+/// segments in reverse-chronological order; the caller is responsible for
+/// reversing the accumulated list once the full walk completes.
+fn walk_critical_path(
+    span: &Span,
+    start: u64,
+    end: u64,
+    children: &HashMap<&str, Vec<&Span>>,
+    segments: &mut Vec<CriticalSegment>,
+) {
+    let mut cursor = end;
+    let candidates = children.get(span.span_id.as_str());
+
+    while cursor > start {
+        let next = candidates.into_iter().flatten().filter_map(|child| {
+            clamped_interval(child, start, end)
+                .filter(|&(_, child_end)| child_end <= cursor)
+                .map(|(child_start, child_end)| (child, child_start, child_end))
+        });
+
+        let best = next.fold(
+            None::<(&Span, u64, u64)>,
+            |best, (child, child_start, child_end)| match best {
+                Some((best_child, _, best_end))
+                    if best_end > child_end
+                        || (best_end == child_end && best_child.span_id <= child.span_id) =>
+                {
+                    best
+                }
+                _ => Some((child, child_start, child_end)),
+            },
+        );
+
+        match best {
+            Some((child, child_start, child_end)) => {
+                if child_end < cursor {
+                    segments.push(CriticalSegment {
+                        span_id: span.span_id.clone(),
+                        duration_on_path: Duration::from_nanos(cursor - child_end),
+                    });
+                }
+                walk_critical_path(child, child_start, child_end, children, segments);
+                cursor = child_start;
+            }
+            None => {
+                segments.push(CriticalSegment {
+                    span_id: span.span_id.clone(),
+                    duration_on_path: Duration::from_nanos(cursor - start),
+                });
+                cursor = start;
+            }
+        }
+    }
+}
+
+/// Sanitizes a trace ID for use as a DOT graph identifier.
+fn sanitize_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
 }
 
 #[cfg(test)]
@@ -225,6 +603,98 @@ mod tests {
         assert!(error.is_error);
     }
 
+    #[test]
+    fn attr_i64_parses_using_default_conversion() {
+        let trace = Trace::new("abc").with_attribute("http.status_code", "500");
+        assert_eq!(trace.attr_i64("http.status_code").unwrap(), Some(500));
+        assert!(trace.attr_i64("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn attr_typed_uses_registered_schema() {
+        use crate::conversion::Conversion;
+
+        let trace = Trace::new("abc")
+            .with_attribute("retriable", "true")
+            .with_attr_schema("retriable", Conversion::Boolean);
+
+        let value = trace.attr_typed("retriable").unwrap().unwrap();
+        assert_eq!(value.as_bool(), Some(true));
+    }
+
+    #[test]
+    fn attr_i64_reports_conversion_error_on_malformed_value() {
+        let trace = Trace::new("abc").with_attribute("http.status_code", "not-a-number");
+        assert!(trace.attr_i64("http.status_code").is_err());
+    }
+
+    #[test]
+    fn critical_path_sums_to_trace_duration() {
+        let spans = vec![
+            Span::new("root", "GET /checkout")
+                .with_start_time_ns(0)
+                .with_duration(Duration::from_millis(100)),
+            Span::new("a", "payment.charge")
+                .with_parent("root")
+                .with_start_time_ns(60_000_000)
+                .with_duration(Duration::from_millis(30)),
+            Span::new("b", "inventory.reserve")
+                .with_parent("root")
+                .with_start_time_ns(10_000_000)
+                .with_duration(Duration::from_millis(30)),
+        ];
+        let trace = Trace::from_spans("t1", spans);
+
+        let path = trace.critical_path();
+        let total: Duration = path.iter().map(|s| s.duration_on_path).sum();
+        assert_eq!(total, trace.duration);
+
+        // Chronological order: root self, b, root self, a, root self.
+        let ids: Vec<&str> = path.iter().map(|s| s.span_id.as_str()).collect();
+        assert_eq!(ids, vec!["root", "b", "root", "a", "root"]);
+    }
+
+    #[test]
+    fn critical_path_clamps_child_exceeding_parent_interval() {
+        let spans = vec![
+            Span::new("root", "op")
+                .with_start_time_ns(0)
+                .with_duration(Duration::from_millis(50)),
+            Span::new("child", "skewed-op")
+                .with_parent("root")
+                .with_start_time_ns(0)
+                .with_duration(Duration::from_millis(1000)),
+        ];
+        let trace = Trace::from_spans("t2", spans);
+
+        let path = trace.critical_path();
+        let total: Duration = path.iter().map(|s| s.duration_on_path).sum();
+        assert_eq!(total, Duration::from_millis(50));
+    }
+
+    #[test]
+    fn critical_path_empty_for_trace_without_spans() {
+        let trace = Trace::new("empty");
+        assert!(trace.critical_path().is_empty());
+    }
+
+    #[test]
+    fn trace_to_dot_renders_parent_child_edges() {
+        use crate::span::Span;
+
+        let trace = Trace::from_spans(
+            "abc",
+            vec![
+                Span::new("s1", "GET /users"),
+                Span::new("s2", "db.query").with_parent("s1"),
+            ],
+        );
+
+        let dot = trace.to_dot();
+        assert!(dot.starts_with("digraph trace_abc"));
+        assert!(dot.contains("\"s1\" -> \"s2\""));
+    }
+
     #[test]
     fn trace_from_spans() {
         let start_ns = 1_000_000_000u64; // 1 second
@@ -293,13 +763,11 @@ mod tests {
     #[test]
     fn compute_summary_updates_fields() {
         let mut trace = Trace::new("trace-001");
-        trace.spans = vec![
-            Span::new("root", "GET /health")
-                .with_service("api")
-                .with_start_time_ns(0)
-                .with_duration(Duration::from_millis(25))
-                .with_attribute("http.status_code", 500i64),
-        ];
+        trace.spans = vec![Span::new("root", "GET /health")
+            .with_service("api")
+            .with_start_time_ns(0)
+            .with_duration(Duration::from_millis(25))
+            .with_attribute("http.status_code", 500i64)];
 
         trace.compute_summary_from_spans();
 
@@ -309,4 +777,68 @@ mod tests {
         assert_eq!(trace.status, Some(500));
         assert!(trace.is_error); // 500 status code
     }
+
+    #[test]
+    fn weighted_span_summary_weights_by_sample_rate() {
+        let spans = vec![
+            Span::new("root", "GET /checkout")
+                .with_service("api")
+                .with_start_time_ns(0)
+                .with_duration(Duration::from_millis(10))
+                .with_sample_rate(50),
+            Span::new("child", "payment.charge")
+                .with_parent("root")
+                .with_service("payments")
+                .with_start_time_ns(1_000_000)
+                .with_duration(Duration::from_millis(5))
+                .with_status(SpanStatus::error("failed")),
+        ];
+        let trace = Trace::from_spans("t1", spans);
+
+        let summary = trace.weighted_span_summary();
+        assert_eq!(summary.raw_span_count, 2);
+        assert_eq!(summary.effective_span_count, 51);
+        assert_eq!(summary.effective_error_count, 1);
+        assert_eq!(summary.service_weights.get("api"), Some(&50));
+        assert_eq!(summary.service_weights.get("payments"), Some(&1));
+    }
+
+    #[test]
+    fn weighted_error_rate_uses_sample_rate_weighted_counts() {
+        let spans = vec![Span::new("root", "op")
+            .with_start_time_ns(0)
+            .with_duration(Duration::from_millis(10))
+            .with_sample_rate(50)
+            .with_status(SpanStatus::error("failed"))];
+        let trace = Trace::from_spans("t2", spans);
+
+        let summary = trace.weighted_span_summary();
+        assert_eq!(summary.effective_error_count, 50);
+        assert_eq!(summary.effective_span_count, 50);
+        assert!((summary.weighted_error_rate() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn weighted_error_rate_is_zero_for_trace_without_spans() {
+        let trace = Trace::new("empty");
+        assert_eq!(trace.weighted_span_summary().weighted_error_rate(), 0.0);
+    }
+
+    #[test]
+    fn resource_scopes_default_to_empty() {
+        let trace = Trace::new("t1");
+        assert!(trace.resource_scopes.is_empty());
+    }
+
+    #[test]
+    fn with_resource_scopes_sets_the_field() {
+        let trace = Trace::new("t1").with_resource_scopes(vec![ResourceScope {
+            service: "api".to_string(),
+            scope_name: Some("my-instrumentation".to_string()),
+            scope_version: Some("1.2.3".to_string()),
+        }]);
+
+        assert_eq!(trace.resource_scopes.len(), 1);
+        assert_eq!(trace.resource_scopes[0].service, "api");
+    }
 }
@@ -0,0 +1,274 @@
+//! Pluggable policy storage.
+//!
+//! Callers previously had to construct a [`Policy`] in memory before
+//! calling [`crate::prover::Prover::verify`]/`analyze`. [`Adapter`]
+//! decouples the prover from how policies are actually persisted - a
+//! single file, a directory of them, an in-memory store for tests, or a
+//! user-supplied remote store/DB - the same role [`crate::checks::Check`]
+//! plays for verification logic: built-ins cover the common cases, and
+//! [`Adapter`] is a trait object so users can supply their own.
+
+use crate::error::{Error, Result};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use toon_policy::Policy;
+
+/// A policy loaded from an [`Adapter`], alongside the name it should be
+/// saved back under.
+#[derive(Debug, Clone)]
+pub struct LoadedPolicy {
+    /// Identifies this policy within its adapter (a file stem, a map
+    /// key, a DB row id) - pass this back to [`Adapter::save`].
+    pub name: String,
+    /// The loaded policy.
+    pub policy: Policy,
+}
+
+/// A source (and optional sink) of policies.
+///
+/// Implementations only need `load`; `save` defaults to rejecting
+/// writes, for read-only sources (e.g. a policy baked into a release
+/// artifact) that shouldn't silently accept an autofix.
+pub trait Adapter: std::fmt::Debug {
+    /// Loads every policy this adapter can see.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the underlying source can't be read or a
+    /// policy fails to parse.
+    fn load(&self) -> Result<Vec<LoadedPolicy>>;
+
+    /// Persists `policy` back under `name`, e.g. so an autofix (see
+    /// [`crate::result::PolicyPatch`]) can write a corrected policy back
+    /// through the same adapter it was loaded from.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails, or [`Error::Internal`] if
+    /// this adapter doesn't support saving.
+    fn save(&self, name: &str, policy: &Policy) -> Result<()> {
+        let _ = (name, policy);
+        Err(Error::Internal(format!(
+            "{self:?} does not support saving policies"
+        )))
+    }
+}
+
+/// Loads (and saves) a single policy file.
+#[derive(Debug, Clone)]
+pub struct FileAdapter {
+    path: PathBuf,
+}
+
+impl FileAdapter {
+    /// Creates an adapter over a single TOON policy file.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    fn name(&self) -> String {
+        self.path.file_stem().map_or_else(
+            || self.path.to_string_lossy().into_owned(),
+            |stem| stem.to_string_lossy().into_owned(),
+        )
+    }
+}
+
+impl Adapter for FileAdapter {
+    fn load(&self) -> Result<Vec<LoadedPolicy>> {
+        let contents = std::fs::read_to_string(&self.path)?;
+        let policy = toon_policy::parse(&contents)?;
+        Ok(vec![LoadedPolicy {
+            name: self.name(),
+            policy,
+        }])
+    }
+
+    fn save(&self, _name: &str, policy: &Policy) -> Result<()> {
+        std::fs::write(&self.path, toon_policy::serialize(policy))?;
+        Ok(())
+    }
+}
+
+/// Loads (and saves) every `*.toon` file in a directory, keyed by file
+/// stem - enables "verify every policy in this directory" workflows.
+#[derive(Debug, Clone)]
+pub struct DirectoryAdapter {
+    dir: PathBuf,
+}
+
+impl DirectoryAdapter {
+    /// Creates an adapter over every `*.toon` file directly inside `dir`
+    /// (not recursive).
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path_for(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.toon"))
+    }
+}
+
+impl Adapter for DirectoryAdapter {
+    fn load(&self) -> Result<Vec<LoadedPolicy>> {
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toon"))
+            .collect();
+        entries.sort();
+
+        entries
+            .into_iter()
+            .map(|path| {
+                let contents = std::fs::read_to_string(&path)?;
+                let policy = toon_policy::parse(&contents)?;
+                let name = path.file_stem().map_or_else(
+                    || path.to_string_lossy().into_owned(),
+                    |stem| stem.to_string_lossy().into_owned(),
+                );
+                Ok(LoadedPolicy { name, policy })
+            })
+            .collect()
+    }
+
+    fn save(&self, name: &str, policy: &Policy) -> Result<()> {
+        std::fs::write(self.path_for(name), toon_policy::serialize(policy))?;
+        Ok(())
+    }
+}
+
+/// An in-memory policy store, for tests and dev workflows that don't
+/// want to touch the filesystem.
+#[derive(Debug, Default)]
+pub struct MemoryAdapter {
+    policies: RefCell<BTreeMap<String, Policy>>,
+}
+
+impl MemoryAdapter {
+    /// Creates an empty in-memory store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seeds the store with a named policy.
+    #[must_use]
+    pub fn with_policy(self, name: impl Into<String>, policy: Policy) -> Self {
+        self.policies.borrow_mut().insert(name.into(), policy);
+        self
+    }
+}
+
+impl Adapter for MemoryAdapter {
+    fn load(&self) -> Result<Vec<LoadedPolicy>> {
+        Ok(self
+            .policies
+            .borrow()
+            .iter()
+            .map(|(name, policy)| LoadedPolicy {
+                name: name.clone(),
+                policy: policy.clone(),
+            })
+            .collect())
+    }
+
+    fn save(&self, name: &str, policy: &Policy) -> Result<()> {
+        self.policies
+            .borrow_mut()
+            .insert(name.to_string(), policy.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toon_policy::{Action, Rule};
+
+    fn sample_policy() -> Policy {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("keep-errors", "status >= 500", Action::Keep, 100));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+        policy
+    }
+
+    #[test]
+    fn memory_adapter_round_trips_a_policy() {
+        let adapter = MemoryAdapter::new().with_policy("prod", sample_policy());
+
+        let loaded = adapter.load().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "prod");
+
+        let mut policy = loaded[0].policy.clone();
+        policy.budget_per_second = Some(5000);
+        adapter.save("prod", &policy).unwrap();
+
+        let reloaded = adapter.load().unwrap();
+        assert_eq!(reloaded[0].policy.budget_per_second, Some(5000));
+    }
+
+    #[test]
+    fn file_adapter_round_trips_through_disk() {
+        let dir = std::env::temp_dir().join(format!(
+            "nectar-file-adapter-test-{:?}",
+            std::thread::current().id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("policy.toon");
+        let adapter = FileAdapter::new(&path);
+
+        adapter.save("policy", &sample_policy()).unwrap();
+        let loaded = adapter.load().unwrap();
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "policy");
+        assert!(loaded[0].policy.has_fallback());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn directory_adapter_loads_every_toon_file() {
+        let dir = std::env::temp_dir().join(format!(
+            "nectar-dir-adapter-test-{:?}",
+            std::thread::current().id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let adapter = DirectoryAdapter::new(&dir);
+
+        adapter.save("a", &sample_policy()).unwrap();
+        adapter.save("b", &sample_policy()).unwrap();
+        std::fs::write(dir.join("ignored.txt"), "not a policy").unwrap();
+
+        let loaded = adapter.load().unwrap();
+        let names: Vec<&str> = loaded.iter().map(|p| p.name.as_str()).collect();
+
+        assert_eq!(names, vec!["a", "b"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[derive(Debug)]
+    struct ReadOnlyAdapter;
+
+    impl Adapter for ReadOnlyAdapter {
+        fn load(&self) -> Result<Vec<LoadedPolicy>> {
+            Ok(vec![LoadedPolicy {
+                name: "readonly".to_string(),
+                policy: sample_policy(),
+            }])
+        }
+    }
+
+    #[test]
+    fn default_save_rejects_writes() {
+        let adapter = ReadOnlyAdapter;
+        let result = adapter.save("readonly", &sample_policy());
+        assert!(result.is_err());
+    }
+}
@@ -4,7 +4,9 @@
 //! enabling rapid feedback during policy development.
 
 use crate::result::{Severity, Violation};
+use nectar_compiler::match_expr::{MatchExpr, Operator, Value};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 use toon_policy::{Action, Policy};
 
 /// Analysis mode for policy verification.
@@ -57,6 +59,21 @@ impl Confidence {
             Self::High => "dynamic simulation passed",
         }
     }
+
+    /// Buckets a continuous `[0, 1]` score (see
+    /// [`crate::prover::AnalysisResult::confidence_score`]) into this
+    /// coarser three-level view, for callers that still want a discrete
+    /// tier rather than a number to set a gate against.
+    #[must_use]
+    pub fn from_score(score: f32) -> Self {
+        if score >= 0.8 {
+            Self::High
+        } else if score >= 0.4 {
+            Self::Medium
+        } else {
+            Self::Low
+        }
+    }
 }
 
 /// Result of static analysis.
@@ -72,6 +89,13 @@ pub struct StaticAnalysisResult {
     pub coverage: CoverageAnalysis,
     /// Conflict detection results.
     pub conflicts: Vec<RuleConflict>,
+    /// Names of rules the interval-based reasoning couldn't resolve: a
+    /// match expression outside the well-known attribute domains, or a
+    /// rule whose region overlaps another's without either containing
+    /// the other while their actions contradict (keep vs. drop), i.e. a
+    /// [`ConflictType::Overlapping`] conflict. These are exactly the regions [`AnalysisMode::Auto`]
+    /// falls back to dynamic simulation to verify.
+    pub unresolved_rules: Vec<String>,
     /// Confidence level of this analysis.
     pub confidence: Confidence,
 }
@@ -86,6 +110,7 @@ impl StaticAnalysisResult {
             warnings: Vec::new(),
             coverage,
             conflicts: Vec::new(),
+            unresolved_rules: Vec::new(),
             confidence: Confidence::Medium,
         }
     }
@@ -99,6 +124,7 @@ impl StaticAnalysisResult {
             warnings: Vec::new(),
             coverage,
             conflicts: Vec::new(),
+            unresolved_rules: Vec::new(),
             confidence: Confidence::Low,
         }
     }
@@ -116,6 +142,36 @@ impl StaticAnalysisResult {
         self.conflicts = conflicts;
         self
     }
+
+    /// Records the rules whose regions interval-based reasoning couldn't
+    /// resolve.
+    #[must_use]
+    pub fn with_unresolved_rules(mut self, unresolved_rules: Vec<String>) -> Self {
+        self.unresolved_rules = unresolved_rules;
+        self
+    }
+
+    /// True if static analysis resolved every rule's region concretely:
+    /// the policy passed, and no rule was left in [`Self::unresolved_rules`].
+    /// When this holds, a further dynamic simulation pass would only
+    /// re-verify already-proven ground rather than resolve anything new.
+    #[must_use]
+    pub fn is_fully_resolved(&self) -> bool {
+        self.passed && self.unresolved_rules.is_empty()
+    }
+
+    /// True if any rule is unreachable because an earlier rule already
+    /// covers its whole region - see [`StaticAnalyzer::analyze_dead_rules`].
+    /// A policy can still `pass` with dead rules present (they're not
+    /// violations), but their presence means the policy doesn't behave
+    /// the way its author likely intended, so callers should treat it as
+    /// less trustworthy than a policy with none.
+    #[must_use]
+    pub fn has_dead_rules(&self) -> bool {
+        self.conflicts
+            .iter()
+            .any(|conflict| conflict.conflict_type == ConflictType::Shadowed)
+    }
 }
 
 /// Warning from static analysis.
@@ -159,18 +215,25 @@ pub struct CoverageAnalysis {
     pub drop_rules: usize,
     /// Rules that match sample action.
     pub sample_rules: usize,
-    /// Whether a fallback rule exists.
+    /// Rules that match circuit-breaker action.
+    pub circuit_breaker_rules: usize,
+    /// Whether the rules provably cover the entire input space (no event
+    /// can fall through unmatched). Derived from [`exhaustiveness`]
+    /// rather than a literal `"true"` match.
     pub has_fallback: bool,
     /// Whether error handling exists.
     pub has_error_handling: bool,
-    /// Estimated coverage percentage (0-100).
+    /// `covered_cells / total_cells` over the interval decomposition
+    /// computed by [`exhaustiveness`], as a percentage (0-100).
     pub estimated_coverage: f64,
+    /// Concrete uncovered regions of the input space, if any. Empty iff
+    /// `has_fallback` is true.
+    pub gaps: Vec<CoverageGap>,
 }
 
 impl CoverageAnalysis {
     /// Analyzes a policy for coverage.
     #[must_use]
-    #[allow(clippy::cast_precision_loss)]
     pub fn analyze(policy: &Policy) -> Self {
         let mut analysis = Self {
             total_rules: policy.rules.len(),
@@ -182,11 +245,7 @@ impl CoverageAnalysis {
                 Action::Keep => analysis.keep_rules += 1,
                 Action::Drop => analysis.drop_rules += 1,
                 Action::Sample(_) => analysis.sample_rules += 1,
-            }
-
-            // Check for fallback
-            if rule.match_expr == "true" {
-                analysis.has_fallback = true;
+                Action::CircuitBreaker { .. } => analysis.circuit_breaker_rules += 1,
             }
 
             // Check for error handling
@@ -195,13 +254,62 @@ impl CoverageAnalysis {
             }
         }
 
-        // Estimate coverage based on rule types
-        analysis.estimated_coverage = calculate_coverage_estimate(&analysis);
+        let (estimated_coverage, gaps) = exhaustiveness(policy);
+        analysis.has_fallback = gaps.is_empty();
+        analysis.estimated_coverage = estimated_coverage;
+        analysis.gaps = gaps;
 
         analysis
     }
 }
 
+/// A region of the input space matched by no rule, found by
+/// [`exhaustiveness`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageGap {
+    /// Per-field constraints describing the gap, e.g. `"http.status" =>
+    /// ">= 100 and <= 399"`. A field absent here is unconstrained within
+    /// the gap (any value falls through).
+    pub field_constraints: std::collections::BTreeMap<String, String>,
+    /// A concrete event that falls inside the gap, keyed by field name.
+    pub example_event: std::collections::BTreeMap<String, String>,
+}
+
+impl CoverageGap {
+    /// The entire input space, for policies with no modeled rules at all.
+    fn universal() -> Self {
+        Self {
+            field_constraints: std::collections::BTreeMap::new(),
+            example_event: std::collections::BTreeMap::new(),
+        }
+    }
+
+    fn from_cells(combo: &[(String, FieldCell)]) -> Self {
+        let mut field_constraints = std::collections::BTreeMap::new();
+        let mut example_event = std::collections::BTreeMap::new();
+        for (field, cell) in combo {
+            field_constraints.insert(field.clone(), cell.label.clone());
+            example_event.insert(field.clone(), cell.example.clone());
+        }
+        Self {
+            field_constraints,
+            example_event,
+        }
+    }
+}
+
+/// Describes a gap for inclusion in a violation message.
+fn describe_gap(gap: &CoverageGap) -> String {
+    if gap.field_constraints.is_empty() {
+        return "any traffic".to_string();
+    }
+    gap.field_constraints
+        .iter()
+        .map(|(field, constraint)| format!("{field} {constraint}"))
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
 /// A conflict between two rules.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RuleConflict {
@@ -284,10 +392,14 @@ impl StaticAnalyzer {
 
         // Check for fallback rule
         if !coverage.has_fallback {
+            let detail = coverage.gaps.first().map_or_else(
+                || "some traffic would match no rule".to_string(),
+                |gap| format!("traffic matching {} would fall through unmatched", describe_gap(gap)),
+            );
             violations.push(Violation::new(
                 Severity::Critical,
                 "missing-fallback",
-                "Policy must have a fallback rule matching 'true'",
+                format!("Policy does not cover the full input space: {detail}"),
             ));
         }
 
@@ -317,6 +429,12 @@ impl StaticAnalyzer {
             conflicts.extend(self.detect_overlaps(policy));
         }
 
+        if self.check_shadowing {
+            let dead = self.analyze_dead_rules(policy);
+            conflicts.extend(dead.dead_rules);
+            violations.extend(dead.unsatisfiable);
+        }
+
         // Add warnings for conflicts
         for conflict in &conflicts {
             warnings.push(StaticWarning::new(
@@ -325,14 +443,18 @@ impl StaticAnalyzer {
             ));
         }
 
+        let unresolved_rules = unresolved_rules(policy, &conflicts);
+
         if violations.is_empty() {
             StaticAnalysisResult::passed(coverage)
                 .with_warnings(warnings)
                 .with_conflicts(conflicts)
+                .with_unresolved_rules(unresolved_rules)
         } else {
             StaticAnalysisResult::failed(violations, coverage)
                 .with_warnings(warnings)
                 .with_conflicts(conflicts)
+                .with_unresolved_rules(unresolved_rules)
         }
     }
 
@@ -383,98 +505,678 @@ impl StaticAnalyzer {
         conflicts
     }
 
-    /// Checks if two rules have a problematic overlap.
+    /// Checks if two rules have a problematic overlap, using the same
+    /// symbolic region reasoning as [`Self::analyze_dead_rules`] rather
+    /// than a same-field-name heuristic.
+    ///
+    /// Rules whose `match_expr` can't be parsed or modeled over the
+    /// well-known attribute domains are left unreported (no false
+    /// positives from a construct this analysis doesn't understand).
+    #[allow(clippy::unused_self)]
     fn check_overlap(
         &self,
         rule_a: &toon_policy::Rule,
         rule_b: &toon_policy::Rule,
     ) -> Option<RuleConflict> {
-        // Skip if same action
-        if rule_a.action == rule_b.action {
+        let boxes_a = rule_region(rule_a)?;
+        let boxes_b = rule_region(rule_b)?;
+
+        if regions_disjoint(&boxes_a, &boxes_b) {
             return None;
         }
 
-        // Check for contradictory conditions
-        // e.g., "status >= 500" (Keep) vs "status >= 400" (Drop)
-        if self.conditions_overlap(&rule_a.match_expr, &rule_b.match_expr) {
-            // Only flag if actions contradict (Keep vs Drop)
-            if is_contradictory_action(&rule_a.action, &rule_b.action) {
-                return Some(RuleConflict::overlapping(
-                    &rule_a.name,
-                    &rule_b.name,
-                    format!(
-                        "Rules '{}' and '{}' have overlapping conditions with contradictory actions",
-                        rule_a.name, rule_b.name
-                    ),
-                ));
-            }
+        let a_contains_b = region_subset(&boxes_b, &boxes_a);
+        let b_contains_a = region_subset(&boxes_a, &boxes_b);
+
+        if a_contains_b && rule_a.priority >= rule_b.priority {
+            return Some(RuleConflict::shadowed(&rule_a.name, &rule_b.name));
+        }
+        if b_contains_a && rule_b.priority >= rule_a.priority {
+            return Some(RuleConflict::shadowed(&rule_b.name, &rule_a.name));
+        }
+
+        if !a_contains_b && !b_contains_a && is_contradictory_action(&rule_a.action, &rule_b.action)
+        {
+            return Some(RuleConflict::overlapping(
+                &rule_a.name,
+                &rule_b.name,
+                format!(
+                    "Rules '{}' and '{}' have overlapping conditions with contradictory actions",
+                    rule_a.name, rule_b.name
+                ),
+            ));
         }
 
         None
     }
+}
 
-    /// Checks if two conditions might overlap.
-    /// This is a heuristic check - full overlap detection would require
-    /// symbolic execution.
-    #[allow(clippy::unused_self)]
-    fn conditions_overlap(&self, expr_a: &str, expr_b: &str) -> bool {
-        // "true" overlaps with everything
-        if expr_a == "true" || expr_b == "true" {
-            return true;
+/// Parses a rule's `match_expr` into its satisfying region (a union of
+/// conjunctive [`AttrBox`]es), or `None` if the expression fails to
+/// parse or uses a construct outside the well-known attribute domains.
+fn rule_region(rule: &toon_policy::Rule) -> Option<Vec<AttrBox>> {
+    let expr = MatchExpr::parse(&rule.match_expr).ok()?;
+    predicate_boxes(&expr)
+}
+
+/// Names of rules that interval-based reasoning couldn't pin down: a
+/// match expression outside the well-known attribute domains (no
+/// [`rule_region`]), or a rule left in an [`ConflictType::Overlapping`]
+/// conflict, where neither rule's region fully contains the other's.
+fn unresolved_rules(policy: &Policy, conflicts: &[RuleConflict]) -> Vec<String> {
+    let mut unresolved: BTreeSet<String> = policy
+        .rules
+        .iter()
+        .filter(|rule| rule_region(rule).is_none())
+        .map(|rule| rule.name.clone())
+        .collect();
+
+    for conflict in conflicts {
+        if conflict.conflict_type == ConflictType::Overlapping {
+            unresolved.insert(conflict.rule_a.clone());
+            unresolved.insert(conflict.rule_b.clone());
+        }
+    }
+
+    unresolved.into_iter().collect()
+}
+
+/// Returns true if every box in `a` is disjoint from every box in `b`,
+/// i.e. the two regions (unions of boxes) share no point.
+fn regions_disjoint(a: &[AttrBox], b: &[AttrBox]) -> bool {
+    a.iter().all(|box_a| b.iter().all(|box_b| box_a.disjoint_from(box_b)))
+}
+
+/// Returns true if region `a` (a union of boxes) is fully contained
+/// within region `b`.
+fn region_subset(a: &[AttrBox], b: &[AttrBox]) -> bool {
+    a.iter()
+        .all(|box_a| b.iter().any(|box_b| box_a.contained_in(box_b)))
+}
+
+/// A constraint on a single well-known attribute's domain.
+///
+/// Intervals are inclusive on both ends; an interval with `lo > hi` (or an
+/// empty string/bool set) represents an unsatisfiable constraint.
+#[derive(Debug, Clone, PartialEq)]
+enum FieldRange {
+    /// `http.status`.
+    Int(i64, i64),
+    /// `duration`, in milliseconds.
+    Duration(i64, i64),
+    /// `service.name` / `http.route` (equality only).
+    Strings(BTreeSet<String>),
+    /// `error`.
+    Bool(bool),
+}
+
+impl FieldRange {
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Int(lo, hi) | Self::Duration(lo, hi) => lo > hi,
+            Self::Strings(set) => set.is_empty(),
+            Self::Bool(_) => false,
+        }
+    }
+
+    /// Intersects this range with `other` in place, for accumulating an
+    /// AND of conditions on the same field. Returns `false` if the ranges
+    /// are for incompatible domains (a type error elsewhere should have
+    /// already caught this; here we just bail out conservatively).
+    fn intersect(&mut self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Int(lo, hi), Self::Int(lo2, hi2)) => {
+                *lo = (*lo).max(*lo2);
+                *hi = (*hi).min(*hi2);
+                true
+            }
+            (Self::Duration(lo, hi), Self::Duration(lo2, hi2)) => {
+                *lo = (*lo).max(*lo2);
+                *hi = (*hi).min(*hi2);
+                true
+            }
+            (Self::Strings(set), Self::Strings(set2)) => {
+                set.retain(|v| set2.contains(v));
+                true
+            }
+            (Self::Bool(a), Self::Bool(b)) => *a == *b,
+            _ => false,
+        }
+    }
+
+    /// Returns true if `self` is fully contained within `other`.
+    fn contained_in(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Int(lo, hi), Self::Int(lo2, hi2)) | (Self::Duration(lo, hi), Self::Duration(lo2, hi2)) => {
+                lo >= lo2 && hi <= hi2
+            }
+            (Self::Strings(set), Self::Strings(set2)) => set.is_subset(set2),
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// A conjunctive region over the known attribute domains (`http.status`,
+/// `duration`, `service.name`/`http.route`, `error`), used for
+/// corpus-independent dead-rule and shadowing analysis.
+///
+/// `None` for a field means "unconstrained" (the full domain).
+#[derive(Debug, Clone, Default, PartialEq)]
+struct AttrBox {
+    fields: std::collections::BTreeMap<String, FieldRange>,
+}
+
+impl AttrBox {
+    fn is_unsatisfiable(&self) -> bool {
+        self.fields.values().any(FieldRange::is_empty)
+    }
+
+    /// Merges a single condition into this box (an implicit AND).
+    /// Returns `false` if the condition could not be modeled
+    /// symbolically (unsupported operator/field), in which case the box
+    /// should be treated as unknown rather than reported as dead.
+    fn merge_condition(&mut self, field: &str, range: FieldRange) -> bool {
+        if let Some(existing) = self.fields.get_mut(field) {
+            existing.intersect(&range)
+        } else {
+            self.fields.insert(field.to_string(), range);
+            true
         }
+    }
 
-        // Same field comparisons might overlap
-        let fields_a = extract_fields(expr_a);
-        let fields_b = extract_fields(expr_b);
+    /// Returns true if this box is fully contained within `other`.
+    fn contained_in(&self, other: &Self) -> bool {
+        other.fields.iter().all(|(field, other_range)| {
+            self.fields
+                .get(field)
+                .is_some_and(|range| range.contained_in(other_range))
+        })
+    }
 
-        // If they operate on the same fields, they might overlap
-        fields_a.iter().any(|f| fields_b.contains(f))
+    /// Returns true if this box shares no point with `other`. Fields
+    /// constrained in only one of the two boxes don't affect
+    /// disjointness (a missing field means "unconstrained", the full
+    /// domain).
+    fn disjoint_from(&self, other: &Self) -> bool {
+        self.fields.iter().any(|(field, range)| {
+            other.fields.get(field).is_some_and(|other_range| {
+                let mut intersection = range.clone();
+                !intersection.intersect(other_range) || intersection.is_empty()
+            })
+        })
     }
 }
 
-/// Checks if expression is an error condition.
-fn is_error_condition(expr: &str) -> bool {
-    let lower = expr.to_lowercase();
-    lower.contains("error")
-        || lower.contains("status >= 500")
-        || lower.contains("status >= 400")
-        || lower.contains("is_error")
-        || lower.contains("exception")
+/// Builds a range for a single condition, if the field/operator/value
+/// combination is one of the well-known, symbolically-modeled domains.
+fn condition_range(field: &str, operator: Operator, value: &Value) -> Option<(String, FieldRange)> {
+    match (field, value) {
+        // A bare `status` is shorthand for `http.status`.
+        ("http.status" | "status", Value::Int(n)) => Some((
+            "http.status".to_string(),
+            match operator {
+                Operator::Eq => FieldRange::Int(*n, *n),
+                Operator::Ge => FieldRange::Int(*n, i64::MAX),
+                Operator::Gt => FieldRange::Int(n.saturating_add(1), i64::MAX),
+                Operator::Le => FieldRange::Int(i64::MIN, *n),
+                Operator::Lt => FieldRange::Int(i64::MIN, n.saturating_sub(1)),
+                _ => return None,
+            },
+        )),
+        ("duration", Value::Duration(ms)) => {
+            #[allow(clippy::cast_possible_wrap)]
+            let ms = *ms as i64;
+            Some((
+                field.to_string(),
+                match operator {
+                    Operator::Eq => FieldRange::Duration(ms, ms),
+                    Operator::Ge => FieldRange::Duration(ms, i64::MAX),
+                    Operator::Gt => FieldRange::Duration(ms.saturating_add(1), i64::MAX),
+                    Operator::Le => FieldRange::Duration(i64::MIN, ms),
+                    Operator::Lt => FieldRange::Duration(i64::MIN, ms.saturating_sub(1)),
+                    _ => return None,
+                },
+            ))
+        }
+        ("service.name" | "http.route", Value::String(s)) if operator == Operator::Eq => {
+            Some((field.to_string(), FieldRange::Strings(BTreeSet::from([s.clone()]))))
+        }
+        ("error", Value::Bool(b)) if operator == Operator::Eq => {
+            Some((field.to_string(), FieldRange::Bool(*b)))
+        }
+        _ => None,
+    }
 }
 
-/// Calculates coverage estimate based on rule analysis.
+/// Expands a match expression into disjunctive-normal-form boxes: a list
+/// of conjunctive [`AttrBox`]es whose union is the expression's satisfying
+/// region. Returns `None` if the expression uses a construct this
+/// analysis doesn't model (in which case callers must treat it
+/// conservatively, never reporting it as dead or shadowed).
+fn predicate_boxes(expr: &MatchExpr) -> Option<Vec<AttrBox>> {
+    match expr {
+        MatchExpr::True => Some(vec![AttrBox::default()]),
+        MatchExpr::Condition(cond) => {
+            let (field, range) = condition_range(&cond.field, cond.operator, &cond.value)?;
+            let mut attr_box = AttrBox::default();
+            if !attr_box.merge_condition(&field, range) {
+                return None;
+            }
+            Some(vec![attr_box])
+        }
+        MatchExpr::Or(parts) => {
+            let mut boxes = Vec::new();
+            for part in parts {
+                boxes.extend(predicate_boxes(part)?);
+            }
+            Some(boxes)
+        }
+        // A negated region generally isn't expressible as a union of
+        // convex boxes (e.g. `!(http.status >= 500)` is fine, but
+        // `!(a && b)` is `!a || !b`, which this function would need to
+        // distribute through first). Treat it as unmodeled rather than
+        // risk an unsound box.
+        MatchExpr::Not(_) => None,
+        // A "k of n" region isn't a union of convex boxes in general
+        // either (its boundary depends on counting satisfied subs, not
+        // a fixed AND/OR shape) - left unmodeled like `Not`.
+        MatchExpr::Threshold { .. } => None,
+        MatchExpr::And(parts) => {
+            let mut acc = vec![AttrBox::default()];
+            for part in parts {
+                let part_boxes = predicate_boxes(part)?;
+                let mut next = Vec::with_capacity(acc.len() * part_boxes.len());
+                for a in &acc {
+                    for b in &part_boxes {
+                        let mut merged = a.clone();
+                        let mut ok = true;
+                        for (field, range) in &b.fields {
+                            if !merged.merge_condition(field, range.clone()) {
+                                ok = false;
+                                break;
+                            }
+                        }
+                        if ok {
+                            next.push(merged);
+                        }
+                    }
+                }
+                acc = next;
+            }
+            Some(acc)
+        }
+    }
+}
+
+/// One disjoint cell of a single field's domain, produced by
+/// [`decompose_field`] for exhaustiveness analysis.
+#[derive(Debug, Clone, PartialEq)]
+struct FieldCell {
+    range: FieldRange,
+    /// Human-readable description of the cell, e.g. `">= 500"` or
+    /// `"not one of the above"`.
+    label: String,
+    /// A concrete value inside the cell, for building example events.
+    example: String,
+}
+
+/// Computes the fraction of the input space covered by `policy`'s rules,
+/// and the concrete uncovered regions (if any).
+///
+/// Decomposes each attribute touched by a rule into the disjoint cells
+/// induced by every rule's boundary points on that attribute, takes the
+/// cross product of those cells across fields, and tests each cell's
+/// membership against the union of all rule regions. Rules using a
+/// construct outside the well-known attribute domains are excluded from
+/// the covering union (never counted as covering anything), the same
+/// conservative stance as [`StaticAnalyzer::analyze_dead_rules`].
+///
+/// Returns `(estimated_coverage_percent, gaps)`, where `gaps` is empty
+/// iff the policy is exhaustive. A rule with a fully unconstrained
+/// region (e.g. a `"true"` fallback) makes any policy trivially
+/// exhaustive.
 #[allow(clippy::cast_precision_loss)]
-fn calculate_coverage_estimate(analysis: &CoverageAnalysis) -> f64 {
-    let mut score = 0.0;
+fn exhaustiveness(policy: &Policy) -> (f64, Vec<CoverageGap>) {
+    let regions: Vec<Vec<AttrBox>> = policy.rules.iter().filter_map(rule_region).collect();
 
-    // Base score from having rules
-    if analysis.total_rules > 0 {
-        score += 20.0;
+    if regions
+        .iter()
+        .any(|boxes| boxes.iter().any(|b| b.fields.is_empty()))
+    {
+        return (100.0, Vec::new());
     }
 
-    // Fallback is critical
-    if analysis.has_fallback {
-        score += 30.0;
+    if regions.is_empty() {
+        return (0.0, vec![CoverageGap::universal()]);
     }
 
-    // Error handling is important
-    if analysis.has_error_handling {
-        score += 20.0;
+    let all_boxes: Vec<&AttrBox> = regions.iter().flatten().collect();
+
+    let fields: BTreeSet<String> = all_boxes
+        .iter()
+        .flat_map(|b| b.fields.keys().cloned())
+        .collect();
+    let axes: Vec<(String, Vec<FieldCell>)> = fields
+        .iter()
+        .map(|field| (field.clone(), decompose_field(field, &all_boxes)))
+        .collect();
+
+    let mut total = 0usize;
+    let mut covered = 0usize;
+    let mut gap_cells = Vec::new();
+
+    for combo in cross_product(&axes) {
+        total += 1;
+        let is_covered = all_boxes.iter().any(|b| {
+            combo
+                .iter()
+                .all(|(field, cell)| b.fields.get(field).is_none_or(|range| cell.range.contained_in(range)))
+        });
+        if is_covered {
+            covered += 1;
+        } else {
+            gap_cells.push(combo);
+        }
     }
 
-    // Mix of rule types is good
-    let rule_type_diversity = [
-        analysis.keep_rules > 0,
-        analysis.drop_rules > 0,
-        analysis.sample_rules > 0,
-    ]
-    .iter()
-    .filter(|&&b| b)
-    .count();
+    let estimated_coverage = covered as f64 / total as f64 * 100.0;
+    let gaps = coalesce_gaps(gap_cells)
+        .iter()
+        .map(|combo| CoverageGap::from_cells(combo))
+        .collect();
 
-    score += (rule_type_diversity as f64) * 10.0;
+    (estimated_coverage, gaps)
+}
 
-    score.min(100.0)
+/// Decomposes a single field's domain into disjoint cells.
+fn decompose_field(field: &str, boxes: &[&AttrBox]) -> Vec<FieldCell> {
+    match field {
+        "http.status" => decompose_int(field, boxes, FieldRange::Int),
+        "duration" => decompose_int(field, boxes, FieldRange::Duration),
+        "error" => vec![
+            FieldCell {
+                range: FieldRange::Bool(true),
+                label: "== true".to_string(),
+                example: "true".to_string(),
+            },
+            FieldCell {
+                range: FieldRange::Bool(false),
+                label: "== false".to_string(),
+                example: "false".to_string(),
+            },
+        ],
+        _ => decompose_strings(field, boxes),
+    }
+}
+
+/// Decomposes an `Int`/`Duration` field into the disjoint intervals cut
+/// out by every rule's boundary points on that field.
+fn decompose_int(field: &str, boxes: &[&AttrBox], make: fn(i64, i64) -> FieldRange) -> Vec<FieldCell> {
+    let mut points: BTreeSet<i64> = BTreeSet::from([i64::MIN]);
+    for b in boxes {
+        if let Some(range) = b.fields.get(field) {
+            let (lo, hi) = match range {
+                FieldRange::Int(lo, hi) | FieldRange::Duration(lo, hi) => (*lo, *hi),
+                _ => continue,
+            };
+            points.insert(lo);
+            if hi < i64::MAX {
+                points.insert(hi.saturating_add(1));
+            }
+        }
+    }
+
+    let points: Vec<i64> = points.into_iter().collect();
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &lo)| {
+            let hi = points.get(i + 1).map_or(i64::MAX, |&next| next - 1);
+            FieldCell {
+                range: make(lo, hi),
+                label: int_label(lo, hi),
+                example: example_int(lo, hi),
+            }
+        })
+        .collect()
+}
+
+/// Renders an `Int`/`Duration` cell as a human-readable constraint.
+fn int_label(lo: i64, hi: i64) -> String {
+    match (lo == i64::MIN, hi == i64::MAX) {
+        (true, true) => "any value".to_string(),
+        (true, false) => format!("<= {hi}"),
+        (false, true) => format!(">= {lo}"),
+        (false, false) if lo == hi => format!("== {lo}"),
+        (false, false) => format!("{lo}..{hi}"),
+    }
+}
+
+/// Picks a concrete example value inside an `Int`/`Duration` cell.
+fn example_int(lo: i64, hi: i64) -> String {
+    if lo == i64::MIN {
+        if hi == i64::MAX { 0 } else { hi }
+    } else {
+        lo
+    }
+    .to_string()
+}
+
+/// Decomposes a `Strings` field (`service.name`/`http.route`) into one
+/// cell per literal value named by a rule, plus an "everything else"
+/// bucket for values no rule mentions.
+fn decompose_strings(field: &str, boxes: &[&AttrBox]) -> Vec<FieldCell> {
+    let mut values: BTreeSet<String> = BTreeSet::new();
+    for b in boxes {
+        if let Some(FieldRange::Strings(set)) = b.fields.get(field) {
+            values.extend(set.iter().cloned());
+        }
+    }
+
+    let mut cells: Vec<FieldCell> = values
+        .iter()
+        .map(|v| FieldCell {
+            range: FieldRange::Strings(BTreeSet::from([v.clone()])),
+            label: format!("== {v:?}"),
+            example: v.clone(),
+        })
+        .collect();
+
+    // A sentinel value guaranteed not to equal any literal a rule names,
+    // so it only matches a box that leaves this field unconstrained.
+    cells.push(FieldCell {
+        range: FieldRange::Strings(BTreeSet::from([format!("\u{0}other:{field}")])),
+        label: "not one of the above".to_string(),
+        example: format!("other-{field}-value"),
+    });
+
+    cells
+}
+
+/// Cross product of per-field cells, one combination per cell in the
+/// decomposed input space.
+fn cross_product(axes: &[(String, Vec<FieldCell>)]) -> Vec<Vec<(String, FieldCell)>> {
+    axes.iter().fold(vec![Vec::new()], |acc, (field, cells)| {
+        acc.iter()
+            .flat_map(|prefix| {
+                cells.iter().map(move |cell| {
+                    let mut next = prefix.clone();
+                    next.push((field.clone(), cell.clone()));
+                    next
+                })
+            })
+            .collect()
+    })
+}
+
+/// Merges adjacent uncovered cells that differ in exactly one numeric
+/// field into a single gap, so e.g. uncovered `100..199` and `200..299`
+/// cells next to each other are reported as one `100..299` gap rather
+/// than two.
+fn coalesce_gaps(mut gaps: Vec<Vec<(String, FieldCell)>>) -> Vec<Vec<(String, FieldCell)>> {
+    loop {
+        let mut merge = None;
+        'outer: for i in 0..gaps.len() {
+            for j in (i + 1)..gaps.len() {
+                if let Some(combined) = try_merge_adjacent(&gaps[i], &gaps[j]) {
+                    merge = Some((i, j, combined));
+                    break 'outer;
+                }
+            }
+        }
+        let Some((i, j, combined)) = merge else {
+            break;
+        };
+        gaps.remove(j);
+        gaps.remove(i);
+        gaps.push(combined);
+    }
+    gaps
+}
+
+fn try_merge_adjacent(
+    a: &[(String, FieldCell)],
+    b: &[(String, FieldCell)],
+) -> Option<Vec<(String, FieldCell)>> {
+    if a.len() != b.len() {
+        return None;
+    }
+
+    let mut merged = Vec::with_capacity(a.len());
+    let mut already_merged_one = false;
+
+    for (field, cell_a) in a {
+        let (_, cell_b) = b.iter().find(|(f, _)| f == field)?;
+
+        if cell_a.range == cell_b.range {
+            merged.push((field.clone(), cell_a.clone()));
+            continue;
+        }
+
+        if already_merged_one {
+            return None;
+        }
+        merged.push((field.clone(), adjacent_cell(cell_a, cell_b)?));
+        already_merged_one = true;
+    }
+
+    already_merged_one.then_some(merged)
+}
+
+/// Returns the union of two numeric cells if they're adjacent intervals,
+/// `None` otherwise (including for non-numeric fields, which never merge).
+fn adjacent_cell(a: &FieldCell, b: &FieldCell) -> Option<FieldCell> {
+    match (&a.range, &b.range) {
+        (FieldRange::Int(lo_a, hi_a), FieldRange::Int(lo_b, hi_b)) => {
+            merge_adjacent_ints(*lo_a, *hi_a, *lo_b, *hi_b, FieldRange::Int)
+        }
+        (FieldRange::Duration(lo_a, hi_a), FieldRange::Duration(lo_b, hi_b)) => {
+            merge_adjacent_ints(*lo_a, *hi_a, *lo_b, *hi_b, FieldRange::Duration)
+        }
+        _ => None,
+    }
+}
+
+fn merge_adjacent_ints(
+    lo_a: i64,
+    hi_a: i64,
+    lo_b: i64,
+    hi_b: i64,
+    make: fn(i64, i64) -> FieldRange,
+) -> Option<FieldCell> {
+    let (lo, hi) = if hi_a.checked_add(1) == Some(lo_b) {
+        (lo_a, hi_b)
+    } else if hi_b.checked_add(1) == Some(lo_a) {
+        (lo_b, hi_a)
+    } else {
+        return None;
+    };
+    Some(FieldCell {
+        range: make(lo, hi),
+        label: int_label(lo, hi),
+        example: example_int(lo, hi),
+    })
+}
+
+/// Result of the corpus-independent dead-rule analysis.
+#[derive(Debug, Clone, Default)]
+pub struct DeadRuleAnalysis {
+    /// Rules that can never fire because an earlier (higher-priority)
+    /// rule's region fully covers theirs.
+    pub dead_rules: Vec<RuleConflict>,
+    /// Rules whose own predicate is internally unsatisfiable (e.g.
+    /// `http.status >= 500 && http.status < 200`).
+    pub unsatisfiable: Vec<Violation>,
+}
+
+impl StaticAnalyzer {
+    /// Performs symbolic, corpus-independent dead-rule analysis: rules are
+    /// ordered by descending priority, and each rule's satisfying region is
+    /// checked against the union of all higher-priority rules' regions.
+    ///
+    /// This only models the well-known attribute domains (`http.status`,
+    /// `duration`, `service.name`/`http.route`, `error`); rules using
+    /// anything else are skipped rather than misreported.
+    #[must_use]
+    pub fn analyze_dead_rules(&self, policy: &Policy) -> DeadRuleAnalysis {
+        let mut rules: Vec<_> = policy.rules.iter().collect();
+        rules.sort_by(|a, b| b.priority.cmp(&a.priority));
+
+        let mut result = DeadRuleAnalysis::default();
+        let mut covered: Vec<(String, AttrBox)> = Vec::new();
+
+        for rule in rules {
+            let Ok(expr) = MatchExpr::parse(&rule.match_expr) else {
+                continue;
+            };
+            let Some(boxes) = predicate_boxes(&expr) else {
+                continue;
+            };
+
+            if boxes.iter().any(AttrBox::is_unsatisfiable) {
+                result.unsatisfiable.push(Violation::new(
+                    Severity::Critical,
+                    "unsatisfiable-rule",
+                    format!("Rule '{}' has an unsatisfiable match expression", rule.name),
+                ));
+                continue;
+            }
+
+            let fully_covered = !boxes.is_empty()
+                && boxes.iter().all(|rule_box| {
+                    covered
+                        .iter()
+                        .any(|(_, covering)| rule_box.contained_in(covering))
+                });
+
+            if fully_covered {
+                let shadowing = covered
+                    .iter()
+                    .find(|(_, covering)| boxes.iter().all(|b| b.contained_in(covering)))
+                    .map_or("a prior rule", |(name, _)| name.as_str());
+                result
+                    .dead_rules
+                    .push(RuleConflict::shadowed(shadowing, &rule.name));
+            }
+
+            for attr_box in boxes {
+                covered.push((rule.name.clone(), attr_box));
+            }
+        }
+
+        result
+    }
+}
+
+/// Checks if expression is an error condition.
+pub(crate) fn is_error_condition(expr: &str) -> bool {
+    let lower = expr.to_lowercase();
+    lower.contains("error")
+        || lower.contains("status >= 500")
+        || lower.contains("status >= 400")
+        || lower.contains("is_error")
+        || lower.contains("exception")
 }
 
 /// Checks if two actions are contradictory.
@@ -485,18 +1187,6 @@ const fn is_contradictory_action(a: &Action, b: &Action) -> bool {
     )
 }
 
-/// Extracts field names from an expression.
-fn extract_fields(expr: &str) -> Vec<&str> {
-    // Simple heuristic: extract words that look like field names
-    let field_patterns = ["status", "duration", "service", "endpoint", "error", "name"];
-
-    field_patterns
-        .iter()
-        .filter(|&&f| expr.contains(f))
-        .copied()
-        .collect()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -521,6 +1211,14 @@ mod tests {
         assert!(AnalysisMode::Auto.includes_dynamic());
     }
 
+    #[test]
+    fn confidence_from_score_buckets_into_three_tiers() {
+        assert_eq!(Confidence::from_score(1.0), Confidence::High);
+        assert_eq!(Confidence::from_score(0.8), Confidence::High);
+        assert_eq!(Confidence::from_score(0.6), Confidence::Medium);
+        assert_eq!(Confidence::from_score(0.0), Confidence::Low);
+    }
+
     #[test]
     fn coverage_analysis() {
         let policy = sample_policy();
@@ -529,10 +1227,34 @@ mod tests {
         assert_eq!(coverage.total_rules, 2);
         assert_eq!(coverage.keep_rules, 1);
         assert_eq!(coverage.sample_rules, 1);
+        assert_eq!(coverage.circuit_breaker_rules, 0);
         assert!(coverage.has_fallback);
         assert!(coverage.has_error_handling);
     }
 
+    #[test]
+    fn coverage_analysis_counts_circuit_breaker_rules() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("keep-errors", "status >= 500", Action::Keep, 100));
+        policy.add_rule(Rule::new(
+            "fallback",
+            "true",
+            Action::CircuitBreaker {
+                closed_rate: 0.01,
+                open_rate: 0.9,
+                window: 100,
+                failure_threshold: 0.2,
+                min_samples: 20,
+            },
+            0,
+        ));
+
+        let coverage = CoverageAnalysis::analyze(&policy);
+
+        assert_eq!(coverage.circuit_breaker_rules, 1);
+        assert_eq!(coverage.sample_rules, 0);
+    }
+
     #[test]
     fn static_analysis_passes() {
         let analyzer = StaticAnalyzer::new();
@@ -570,6 +1292,143 @@ mod tests {
         assert!(result.conflicts.iter().any(|c| c.conflict_type == ConflictType::Shadowed));
     }
 
+    #[test]
+    fn check_overlap_ignores_same_field_disjoint_ranges() {
+        // "status >= 500" and "status < 100" share the `http.status` field
+        // but their ranges never intersect, so this must not be flagged.
+        let analyzer = StaticAnalyzer::new();
+        let high = Rule::new("high", "status >= 500", Action::Keep, 50);
+        let low = Rule::new("low", "status < 100", Action::Drop, 50);
+
+        assert!(analyzer.check_overlap(&high, &low).is_none());
+    }
+
+    #[test]
+    fn check_overlap_detects_true_nested_overlap() {
+        // "status >= 500" is a strict subset of "status >= 400"; since the
+        // broader rule has higher priority, it fully shadows the narrower
+        // one regardless of their differing actions.
+        let analyzer = StaticAnalyzer::new();
+        let broad = Rule::new("broad", "status >= 400", Action::Drop, 50);
+        let narrow = Rule::new("narrow", "status >= 500", Action::Keep, 10);
+
+        let conflict = analyzer.check_overlap(&broad, &narrow).unwrap();
+        assert_eq!(conflict.conflict_type, ConflictType::Shadowed);
+        assert_eq!(conflict.rule_a, "broad");
+        assert_eq!(conflict.rule_b, "narrow");
+    }
+
+    #[test]
+    fn check_overlap_detects_genuine_intersection() {
+        // Neither range contains the other, but they share status 400-499,
+        // and the actions contradict.
+        let analyzer = StaticAnalyzer::new();
+        let a = Rule::new("a", "status >= 300 && status < 500", Action::Keep, 50);
+        let b = Rule::new("b", "status >= 400 && status < 600", Action::Drop, 50);
+
+        let conflict = analyzer.check_overlap(&a, &b).unwrap();
+        assert_eq!(conflict.conflict_type, ConflictType::Overlapping);
+    }
+
+    #[test]
+    fn analyze_dead_rules_flags_rule_shadowed_by_catch_all() {
+        let analyzer = StaticAnalyzer::new();
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("catch-all", "true", Action::Keep, 100));
+        policy.add_rule(Rule::new("errors", "http.status >= 500", Action::Drop, 50));
+
+        let dead = analyzer.analyze_dead_rules(&policy);
+        assert_eq!(dead.dead_rules.len(), 1);
+        assert_eq!(dead.dead_rules[0].rule_b, "errors");
+    }
+
+    #[test]
+    fn analyze_dead_rules_flags_unsatisfiable_predicate() {
+        let analyzer = StaticAnalyzer::new();
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "impossible",
+            "http.status >= 500 && http.status <= 200",
+            Action::Keep,
+            10,
+        ));
+
+        let dead = analyzer.analyze_dead_rules(&policy);
+        assert_eq!(dead.unsatisfiable.len(), 1);
+        assert_eq!(dead.unsatisfiable[0].check, "unsatisfiable-rule");
+    }
+
+    #[test]
+    fn exhaustive_policy_has_no_gaps() {
+        // A catch-all rule makes any policy trivially exhaustive.
+        let policy = sample_policy();
+        let coverage = CoverageAnalysis::analyze(&policy);
+
+        assert!(coverage.has_fallback);
+        assert!(coverage.gaps.is_empty());
+        assert!((coverage.estimated_coverage - 100.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn non_exhaustive_policy_reports_the_uncovered_range() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("errors", "status >= 500", Action::Keep, 100));
+
+        let coverage = CoverageAnalysis::analyze(&policy);
+
+        assert!(!coverage.has_fallback);
+        assert_eq!(coverage.gaps.len(), 1);
+        assert_eq!(
+            coverage.gaps[0].field_constraints.get("http.status").unwrap(),
+            "<= 499"
+        );
+    }
+
+    #[test]
+    fn adjacent_gap_cells_coalesce_into_one_range() {
+        // Two uncovered cells differing only in `http.status`, with
+        // adjacent ranges [100, 249] and [250, 499], should merge into a
+        // single [100, 499] gap rather than being reported separately.
+        let cell = |lo: i64, hi: i64| FieldCell {
+            range: FieldRange::Int(lo, hi),
+            label: int_label(lo, hi),
+            example: example_int(lo, hi),
+        };
+        let a = vec![("http.status".to_string(), cell(100, 249))];
+        let b = vec![("http.status".to_string(), cell(250, 499))];
+
+        let gaps = coalesce_gaps(vec![a, b]);
+
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0][0].1.range, FieldRange::Int(100, 499));
+        assert_eq!(gaps[0][0].1.label, "100..499");
+    }
+
+    #[test]
+    fn non_adjacent_gap_cells_do_not_merge() {
+        let cell = |lo: i64, hi: i64| FieldCell {
+            range: FieldRange::Int(lo, hi),
+            label: int_label(lo, hi),
+            example: example_int(lo, hi),
+        };
+        let a = vec![("http.status".to_string(), cell(100, 199))];
+        let b = vec![("http.status".to_string(), cell(300, 399))];
+
+        let gaps = coalesce_gaps(vec![a, b]);
+
+        assert_eq!(gaps.len(), 2);
+    }
+
+    #[test]
+    fn empty_policy_has_a_universal_gap() {
+        let policy = Policy::new("empty");
+        let coverage = CoverageAnalysis::analyze(&policy);
+
+        assert!(!coverage.has_fallback);
+        assert_eq!(coverage.gaps.len(), 1);
+        assert!(coverage.gaps[0].field_constraints.is_empty());
+    }
+
     #[test]
     fn confidence_ordering() {
         assert!(Confidence::Low < Confidence::Medium);
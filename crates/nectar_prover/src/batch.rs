@@ -0,0 +1,183 @@
+//! Parallel verification of many policies at once.
+//!
+//! `verify`/`analyze` are pure over `&self`, and each policy is
+//! independent, so CI pipelines validating dozens of policies shouldn't
+//! pay for them sequentially. [`Prover::verify_batch`]/`analyze_batch`
+//! run the same per-policy work across a rayon thread pool, preserving
+//! input order in the output - this pairs naturally with
+//! [`crate::adapter::DirectoryAdapter`], whose loaded policies (each
+//! [`crate::adapter::LoadedPolicy::policy`]) can be collected into the
+//! `&[Policy]` input these expect.
+
+use crate::error::Result;
+use crate::prover::{AnalysisResult, Prover};
+use crate::result::ProverResult;
+use crate::traffic::TrafficPattern;
+use nectar_corpus::Corpus;
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use std::collections::BTreeMap;
+use toon_policy::Policy;
+
+/// Aggregate counts over a batch of [`ProverResult`]s.
+#[derive(Debug, Clone, Default)]
+pub struct BatchSummary {
+    /// Number of policies approved (with or without warnings).
+    pub approved: usize,
+    /// Number of policies rejected.
+    pub rejected: usize,
+    /// Number of policies that errored before a verdict was reached.
+    pub errored: usize,
+    /// Total violations across the batch, grouped by the check that
+    /// raised them.
+    pub violations_by_check: BTreeMap<String, usize>,
+}
+
+impl BatchSummary {
+    /// Summarizes a batch of [`Prover::verify_batch`] results.
+    #[must_use]
+    pub fn summarize(results: &[Result<ProverResult>]) -> Self {
+        let mut summary = Self::default();
+        for result in results {
+            match result {
+                Ok(prover_result) => {
+                    if prover_result.is_approved() {
+                        summary.approved += 1;
+                    } else {
+                        summary.rejected += 1;
+                    }
+                    for violation in &prover_result.violations {
+                        *summary
+                            .violations_by_check
+                            .entry(violation.check.clone())
+                            .or_insert(0) += 1;
+                    }
+                }
+                Err(_) => summary.errored += 1,
+            }
+        }
+        summary
+    }
+}
+
+impl Prover {
+    /// Verifies `policies` against `corpus` concurrently, one
+    /// [`Prover::verify`] call per policy, using up to
+    /// `self.config.max_concurrency` threads.
+    ///
+    /// Output order matches `policies`' order regardless of which
+    /// thread finishes first.
+    #[must_use]
+    pub fn verify_batch(&self, policies: &[Policy], corpus: &Corpus) -> Vec<Result<ProverResult>> {
+        self.run_batch(|| {
+            policies
+                .par_iter()
+                .map(|policy| self.verify(policy, corpus))
+                .collect()
+        })
+    }
+
+    /// Analyzes `policies` against `corpus`/`traffic` concurrently - see
+    /// [`Self::verify_batch`] for the concurrency/ordering behavior.
+    #[must_use]
+    pub fn analyze_batch(
+        &self,
+        policies: &[Policy],
+        corpus: &Corpus,
+        traffic: Option<&TrafficPattern>,
+    ) -> Vec<Result<AnalysisResult>> {
+        self.run_batch(|| {
+            policies
+                .par_iter()
+                .map(|policy| self.analyze(policy, corpus, traffic))
+                .collect()
+        })
+    }
+
+    /// Runs `work` on an ad hoc thread pool capped at
+    /// `self.config.max_concurrency`, falling back to rayon's global
+    /// pool (one thread per available core) when unset or when building
+    /// the pool fails.
+    fn run_batch<T: Send>(&self, work: impl Fn() -> Vec<T> + Send + Sync) -> Vec<T> {
+        match self.config().max_concurrency {
+            Some(threads) => ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map_or_else(|_| work(), |pool| pool.install(work)),
+            None => work(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::ProverConfig;
+    use toon_policy::{Action, Rule};
+
+    fn valid_policy() -> Policy {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("keep-errors", "status >= 500", Action::Keep, 100));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+        policy
+    }
+
+    fn empty_policy() -> Policy {
+        Policy::new("empty")
+    }
+
+    #[test]
+    fn verify_batch_preserves_input_order() {
+        let prover = Prover::default();
+        let policies = vec![valid_policy(), empty_policy(), valid_policy()];
+        let corpus = Corpus::new();
+
+        let results = prover.verify_batch(&policies, &corpus);
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].as_ref().unwrap().is_approved());
+        assert!(results[1].as_ref().unwrap().is_rejected());
+        assert!(results[2].as_ref().unwrap().is_approved());
+    }
+
+    #[test]
+    fn batch_summary_counts_approved_rejected_and_violations() {
+        let prover = Prover::default();
+        let policies = vec![valid_policy(), empty_policy()];
+        let corpus = Corpus::new();
+
+        let results = prover.verify_batch(&policies, &corpus);
+        let summary = BatchSummary::summarize(&results);
+
+        assert_eq!(summary.approved, 1);
+        assert_eq!(summary.rejected, 1);
+        assert_eq!(summary.errored, 0);
+        assert!(!summary.violations_by_check.is_empty());
+    }
+
+    #[test]
+    fn verify_batch_respects_max_concurrency() {
+        let prover = Prover::new(ProverConfig {
+            max_concurrency: Some(1),
+            ..Default::default()
+        });
+        let policies = vec![valid_policy(); 4];
+        let corpus = Corpus::new();
+
+        let results = prover.verify_batch(&policies, &corpus);
+
+        assert!(results.iter().all(|r| r.as_ref().unwrap().is_approved()));
+    }
+
+    #[test]
+    fn analyze_batch_preserves_input_order() {
+        let prover = Prover::default();
+        let policies = vec![valid_policy(), empty_policy()];
+        let corpus = Corpus::new();
+
+        let results = prover.analyze_batch(&policies, &corpus, None);
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].as_ref().unwrap().all_passed());
+    }
+}
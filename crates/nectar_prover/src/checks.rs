@@ -1,8 +1,85 @@
 //! Individual verification checks.
 
-use crate::result::{Violation, Warning};
-use nectar_corpus::Corpus;
-use toon_policy::Policy;
+use crate::prover::ProverConfig;
+use crate::result::{PolicyPatch, Violation, Warning};
+use nectar_compiler::match_expr::{AttributeSource, Condition, MatchExpr, Operator, Value};
+use nectar_corpus::{Corpus, Trace};
+use toon_policy::{Action, Policy, Rule};
+
+/// Adapts a [`Trace`] to [`AttributeSource`] so a rule's parsed
+/// [`MatchExpr`] can be evaluated against it, mapping the attribute
+/// domains [`nectar_compiler::match_expr::type_check`] already treats as
+/// well-known onto the matching `Trace` field, and falling back to the
+/// trace's raw string `attributes` for anything else.
+struct TraceAttributes<'a>(&'a Trace);
+
+impl AttributeSource for TraceAttributes<'_> {
+    fn attribute(&self, field: &str) -> Option<Value> {
+        match field {
+            "http.status" => self.0.status.map(|s| Value::Int(i64::from(s))),
+            "duration" => Some(Value::Duration(truncate_to_millis(self.0.duration))),
+            "error" => Some(Value::Bool(self.0.is_error)),
+            "service.name" => self.0.service.clone().map(Value::String),
+            "http.route" => self.0.endpoint.clone().map(Value::String),
+            _ => self.0.attributes.get(field).cloned().map(Value::String),
+        }
+    }
+}
+
+/// Saturating `Duration` -> millisecond conversion for [`TraceAttributes`],
+/// so a pathologically long trace clamps to `u64::MAX` rather than
+/// panicking on overflow.
+fn truncate_to_millis(duration: std::time::Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Reports whether `rule`'s match expression structurally covers errors:
+/// a leaf condition on `error`/`is_error`, or a relational/equality
+/// comparison on `http.status`/`status` against a value `>= 400`. Walks
+/// through `And`/`Or`/`Not` so e.g. `http.status >= 500 && service.name
+/// == "checkout"` is recognized even though only one of its leaves is
+/// error-related.
+fn covers_errors(expr: &MatchExpr) -> bool {
+    match expr {
+        MatchExpr::True => false,
+        MatchExpr::Condition(condition) => condition_covers_errors(condition),
+        MatchExpr::And(exprs) | MatchExpr::Or(exprs) => exprs.iter().any(covers_errors),
+        MatchExpr::Not(inner) => covers_errors(inner),
+        // Same conservative "any sub might be the one guarding errors"
+        // stance as `And`/`Or` above, rather than reasoning about which
+        // `k`-sized combinations of subs an error trace could satisfy.
+        MatchExpr::Threshold { subs, .. } => subs.iter().any(covers_errors),
+    }
+}
+
+fn condition_covers_errors(condition: &Condition) -> bool {
+    match condition.field.as_str() {
+        "error" | "is_error" => true,
+        "http.status" | "status" => matches!(
+            (condition.operator, &condition.value),
+            (
+                Operator::Eq | Operator::Ge | Operator::Gt | Operator::In,
+                Value::Int(n)
+            ) if *n >= 400
+        ),
+        _ => false,
+    }
+}
+
+/// Default base (pre-sampling) ingest rate, in traces/sec,
+/// [`check_budget_feasibility`] projects against when the caller has no
+/// real traffic pattern to measure it from.
+pub const DEFAULT_BASE_INGEST_RATE: f64 = 1000.0;
+
+/// Default utilization ratio above which [`check_budget_feasibility`]
+/// warns even though the projected rate is still within budget.
+pub const DEFAULT_BUDGET_WARNING_MARGIN: f64 = 0.8;
+
+/// Utilization ratio below which the budget is considered set far above
+/// anything the policy could plausibly keep, worth an informational note
+/// since an unused budget is often picked arbitrarily rather than derived
+/// from the policy.
+const BUDGET_SLACK_INFO_THRESHOLD: f64 = 0.1;
 
 /// Checks that the policy has a fallback rule.
 pub fn check_fallback(policy: &Policy) -> Option<Violation> {
@@ -17,20 +94,32 @@ pub fn check_fallback(policy: &Policy) -> Option<Violation> {
 }
 
 /// Checks that error traces are never dropped.
+///
+/// Parses each `Drop` rule's `match_expr` into a real [`MatchExpr`] and
+/// flags it if any of its leaves structurally covers an error condition
+/// (see [`covers_errors`]), rather than substring-matching the source
+/// text. A rule whose expression fails to parse is treated the same as
+/// the old `"true"` fallback case - conservatively flagged, since an
+/// unparseable `Drop` rule can't be shown safe either.
 pub fn check_error_handling(policy: &Policy) -> Option<Violation> {
     for rule in &policy.rules {
-        // Check if any rule could drop errors
-        if matches!(rule.action, toon_policy::Action::Drop) {
-            // This is a simplified check - real implementation would parse match_expr
-            if rule.match_expr.contains("status") || rule.match_expr == "true" {
-                return Some(Violation::critical(
-                    "error-handling",
-                    format!(
-                        "Rule '{}' could drop error traces. Errors must always be kept.",
-                        rule.name
-                    ),
-                ));
-            }
+        if !matches!(rule.action, toon_policy::Action::Drop) {
+            continue;
+        }
+
+        let flags = match MatchExpr::parse(&rule.match_expr) {
+            Ok(expr) => matches!(expr, MatchExpr::True) || covers_errors(&expr),
+            Err(_) => true,
+        };
+
+        if flags {
+            return Some(Violation::critical(
+                "error-handling",
+                format!(
+                    "Rule '{}' could drop error traces. Errors must always be kept.",
+                    rule.name
+                ),
+            ));
         }
     }
     None
@@ -46,9 +135,17 @@ pub fn check_cardinality(_policy: &Policy, _corpus: &Corpus) -> Option<Warning>
 
 /// Simulates policy against corpus and checks must-keep traces.
 ///
+/// For each error trace in `corpus`, finds the first rule (in the
+/// policy's priority order) whose parsed `match_expr` actually evaluates
+/// true against that trace's attributes, and counts it as dropped if
+/// that rule's action is `Drop` or if no rule matched at all. A rule
+/// whose `match_expr` fails to parse is skipped rather than treated as a
+/// match, same as it contributing nothing at runtime.
+///
 /// # Errors
 ///
-/// Returns a `Violation` if the policy may drop error traces.
+/// Returns a `Violation` if the policy would drop any error trace in
+/// `corpus`.
 pub fn check_must_keep_coverage(
     policy: &Policy,
     corpus: &Corpus,
@@ -59,23 +156,517 @@ pub fn check_must_keep_coverage(
         return Ok(());
     }
 
-    // TODO: Implement actual policy evaluation
-    // For now, just check that we have keep rules for errors
-    let has_error_keep_rule = policy.rules.iter().any(|r| {
-        matches!(r.action, toon_policy::Action::Keep)
-            && (r.match_expr.contains("error") || r.match_expr.contains("status >= 500"))
-    });
+    let parsed_rules: Vec<(&Rule, MatchExpr)> = policy
+        .rules
+        .iter()
+        .filter_map(|r| MatchExpr::parse(&r.match_expr).ok().map(|expr| (r, expr)))
+        .collect();
 
-    if has_error_keep_rule {
+    let dropped = errors
+        .iter()
+        .filter(|trace| {
+            let attrs = TraceAttributes(trace);
+            let matched = parsed_rules.iter().find(|(_, expr)| expr.eval(&attrs));
+            !matches!(matched, Some((rule, _)) if !matches!(rule.action, Action::Drop))
+        })
+        .count();
+
+    if dropped == 0 {
         Ok(())
     } else {
         Err(Violation::critical(
             "must-keep-coverage",
+            format!("Policy may drop {dropped} error traces. Add a rule to keep errors."),
+        ))
+    }
+}
+
+/// Statically estimates whether `policy` can meet its
+/// `budget_per_second`, given `base_ingest_rate` (traces/sec before any
+/// sampling). Does nothing if the policy has no budget set.
+///
+/// There's no corpus-derived selectivity for each rule's match expression
+/// here, so this takes the conservative (upper-bound) view: every `keep`
+/// rule is assumed to capture the *entire* base ingest rate, `sample(r)`
+/// rules capture `r` of it, `drop` captures none, and a `circuit_breaker`
+/// is estimated at its closed-state rate (matching the compiler's own
+/// conservative choice when it can't represent the stateful open/closed
+/// escalation statically either). Contributions are summed rather than
+/// deduplicated against overlapping matches, since a policy that passes
+/// this worst-case projection is safe, while one that fails it merely
+/// *might* be over budget.
+///
+/// Returns a critical [`Violation`] if the projected rate exceeds budget;
+/// a [`Warning`] if it's within `margin` of the budget (little headroom)
+/// or if the budget is set far above anything achievable (likely picked
+/// arbitrarily); or `(None, None)` if the projection looks healthy.
+#[must_use]
+pub fn check_budget_feasibility(
+    policy: &Policy,
+    base_ingest_rate: f64,
+    margin: f64,
+) -> (Option<Violation>, Option<Warning>) {
+    let Some(budget) = policy.budget_per_second else {
+        return (None, None);
+    };
+    #[allow(clippy::cast_precision_loss)]
+    let budget = budget as f64;
+
+    let projected = estimated_kept_rate(policy, base_ingest_rate);
+
+    if projected > budget {
+        return (
+            Some(Violation::critical(
+                "budget-feasibility",
+                format!(
+                    "Projected kept traffic (~{projected:.0}/s at a base ingest rate of {base_ingest_rate:.0}/s) exceeds the budget of {budget:.0}/s"
+                ),
+            )),
+            None,
+        );
+    }
+
+    if budget <= 0.0 {
+        return (None, None);
+    }
+
+    let utilization = projected / budget;
+
+    if utilization >= margin {
+        return (
+            None,
+            Some(Warning::new(
+                "budget-feasibility",
+                format!(
+                    "Projected kept traffic (~{projected:.0}/s) uses {:.0}% of the {budget:.0}/s budget - little headroom left",
+                    utilization * 100.0
+                ),
+            )),
+        );
+    }
+
+    if utilization < BUDGET_SLACK_INFO_THRESHOLD {
+        return (
+            None,
+            Some(Warning::info(
+                "budget-feasibility",
+                format!(
+                    "budget_per_second of {budget:.0}/s is far above the ~{projected:.0}/s this policy could plausibly keep"
+                ),
+            )),
+        );
+    }
+
+    (None, None)
+}
+
+/// Sums each rule's worst-case contribution to kept traffic per second -
+/// see [`check_budget_feasibility`] for why this over-counts rather than
+/// modeling per-rule selectivity.
+fn estimated_kept_rate(policy: &Policy, base_ingest_rate: f64) -> f64 {
+    policy
+        .rules
+        .iter()
+        .map(|rule| base_ingest_rate * capture_fraction(&rule.action))
+        .sum()
+}
+
+/// The fraction of matched traffic an action keeps.
+const fn capture_fraction(action: &Action) -> f64 {
+    match action {
+        Action::Keep => 1.0,
+        Action::Drop => 0.0,
+        Action::Sample(rate) => *rate,
+        Action::CircuitBreaker { closed_rate, .. } => *closed_rate,
+    }
+}
+
+/// Estimates whether `policy` fits its `budget_per_second` using the
+/// corpus's own traffic instead of [`check_budget_feasibility`]'s assumed
+/// flat ingest rate. Does nothing if the policy has no budget set or
+/// `corpus` is empty.
+///
+/// For each rule, in the policy's priority order, estimates its match
+/// probability `p_i` by evaluating its parsed `match_expr` against every
+/// trace in `corpus`. Because a trace is only ever handled by the first
+/// rule that matches it, a rule's *effective* probability discounts the
+/// traces already claimed by higher-priority rules: `p_i * (1 - sum of
+/// prior effective probabilities)`. Multiplying each rule's effective
+/// probability by `Action::effective_rate()` and summing gives the
+/// expected kept fraction of the corpus, which is then scaled by the
+/// corpus's own throughput (see [`corpus_throughput`]) to project a
+/// traces/sec figure comparable to `budget_per_second`. A rule whose
+/// `match_expr` fails to parse contributes nothing, same as
+/// [`check_must_keep_coverage`].
+///
+/// Unlike [`check_budget_feasibility`], which can return a blocking
+/// [`Violation`], this is advisory only - it's an estimate from a sample,
+/// not a worst-case bound - so it only ever returns a [`Warning`].
+#[must_use]
+pub fn check_budget(policy: &Policy, corpus: &Corpus) -> Option<Warning> {
+    let budget = policy.budget_per_second?;
+    if corpus.is_empty() {
+        return None;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let budget = budget as f64;
+
+    let expected_rate = expected_kept_fraction(policy, corpus) * corpus_throughput(corpus);
+
+    if expected_rate > budget {
+        Some(Warning::new(
+            "budget",
             format!(
-                "Policy may drop {} error traces. Add a rule to keep errors.",
-                errors.len()
+                "Expected kept volume (~{expected_rate:.0}/s, projected from {} corpus traces) exceeds the budget of {budget:.0}/s",
+                corpus.len()
             ),
         ))
+    } else {
+        None
+    }
+}
+
+/// The expected fraction of `corpus` the policy would keep, composing
+/// each rule's corpus-measured match probability with first-match-wins
+/// deduplication. See [`check_budget`].
+fn expected_kept_fraction(policy: &Policy, corpus: &Corpus) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let total = corpus.len() as f64;
+    if total == 0.0 {
+        return 0.0;
+    }
+
+    let mut remaining = 1.0;
+    let mut kept = 0.0;
+
+    for rule in &policy.rules {
+        let Ok(expr) = MatchExpr::parse(&rule.match_expr) else {
+            continue;
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let matched = corpus
+            .iter()
+            .filter(|trace| expr.eval(&TraceAttributes(trace)))
+            .count() as f64;
+        let effective_probability = (matched / total) * remaining;
+
+        kept += effective_probability * rule.action.effective_rate();
+        remaining -= effective_probability;
+    }
+
+    kept
+}
+
+/// Estimates the corpus's throughput in traces/sec from the span between
+/// its earliest and latest trace start times. Falls back to
+/// [`DEFAULT_BASE_INGEST_RATE`] when the corpus has no usable timestamps
+/// (no traces with timestamps, or all traces sharing one instant), since
+/// a zero-width span can't give a rate.
+fn corpus_throughput(corpus: &Corpus) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let count = corpus.len() as f64;
+
+    match corpus.time_range_ns() {
+        Some((min, max)) if max > min => {
+            #[allow(clippy::cast_precision_loss)]
+            let seconds = (max - min) as f64 / 1_000_000_000.0;
+            count / seconds
+        }
+        _ => DEFAULT_BASE_INGEST_RATE,
+    }
+}
+
+/// Flags rules that can never fire because an earlier (higher-priority)
+/// rule's predicate already covers every trace they'd match - e.g. a
+/// `true` keep rule followed by anything, or a same-field comparison an
+/// earlier rule's threshold already subsumes. Walks `policy.rules` in
+/// evaluation order and checks each rule's parsed `match_expr` against
+/// every earlier one already seen (see [`subsumes`]).
+///
+/// Only the simplest sound cases are recognized - an earlier fallback
+/// (`true`) rule, or a same-field, same-operator numeric comparison
+/// whose threshold already covers this rule's - so this never reports a
+/// false positive, at the cost of missing subtler overlaps (cross-field
+/// reasoning, `And`/`Or`/`Threshold` combinations). See
+/// [`crate::analysis::StaticAnalyzer::analyze_dead_rules`] for the
+/// fuller symbolic-region version static analysis mode uses. A rule
+/// whose `match_expr` fails to parse is skipped, same as elsewhere in
+/// this module.
+#[must_use]
+pub fn check_reachability(policy: &Policy) -> Vec<Warning> {
+    let mut seen: Vec<(&str, MatchExpr)> = Vec::new();
+    let mut warnings = Vec::new();
+
+    for rule in &policy.rules {
+        let Ok(expr) = MatchExpr::parse(&rule.match_expr) else {
+            continue;
+        };
+
+        if let Some((shadowed_by, _)) = seen.iter().find(|(_, prior)| subsumes(prior, &expr)) {
+            warnings.push(Warning::new(
+                "reachability",
+                format!(
+                    "Rule '{}' is unreachable - it's already covered by rule '{shadowed_by}'",
+                    rule.name
+                ),
+            ));
+        }
+
+        seen.push((rule.name.as_str(), expr));
+    }
+
+    warnings
+}
+
+/// Returns true if every trace `current` matches, `prior` also matches,
+/// so `current` can never fire after `prior` - see [`check_reachability`] for which cases this recognizes. Anything
+/// other than an earlier `true` or a same-field `Condition` pair -
+/// including any `Threshold` on either side - falls through to `false`.
+fn subsumes(prior: &MatchExpr, current: &MatchExpr) -> bool {
+    if matches!(prior, MatchExpr::True) {
+        return true;
+    }
+
+    let (MatchExpr::Condition(prior), MatchExpr::Condition(current)) = (prior, current) else {
+        return false;
+    };
+
+    if prior.field != current.field || prior.operator != current.operator {
+        return false;
+    }
+
+    if prior.value == current.value {
+        return true;
+    }
+
+    match (
+        prior.operator,
+        reachability_numeric_value(&prior.value),
+        reachability_numeric_value(&current.value),
+    ) {
+        (Operator::Ge | Operator::Gt, Some(prior_value), Some(current_value)) => {
+            prior_value <= current_value
+        }
+        (Operator::Le | Operator::Lt, Some(prior_value), Some(current_value)) => {
+            prior_value >= current_value
+        }
+        _ => false,
+    }
+}
+
+/// Coerces a [`Value`] to a comparable number, for [`subsumes`]'s
+/// same-field threshold comparisons.
+fn reachability_numeric_value(value: &Value) -> Option<f64> {
+    match value {
+        #[allow(clippy::cast_precision_loss)]
+        Value::Int(n) => Some(*n as f64),
+        Value::Float(f) => Some(*f),
+        #[allow(clippy::cast_precision_loss)]
+        Value::Duration(ms) => Some(*ms as f64),
+        Value::String(_) | Value::Bool(_) | Value::List(_) => None,
+    }
+}
+
+/// A pluggable verification check, analogous to a lint rule. [`crate::Prover`]
+/// runs its built-in checks (see the `*Check` types in this module) plus
+/// any registered via `Prover::register_check` against every policy it
+/// verifies, rather than hardcoding a fixed pipeline.
+pub trait Check: std::fmt::Debug {
+    /// Stable identifier, used as the `check` field on any [`Violation`]
+    /// or [`Warning`] this check produces.
+    fn id(&self) -> &str;
+
+    /// Runs this check against `policy`, returning zero or more
+    /// violations. Only [`crate::result::Severity::Critical`] violations
+    /// block approval; [`Violation::warning`]/[`Violation::info`] ones
+    /// are folded into [`crate::result::ProverResult::warnings`] instead.
+    fn run(&self, policy: &Policy, corpus: &Corpus, config: &ProverConfig) -> Vec<Violation>;
+
+    /// Proposes a concrete fix for a violation this check raised, if one
+    /// exists. Most checks don't implement this.
+    fn suggest_fix(&self, _policy: &Policy) -> Option<PolicyPatch> {
+        None
+    }
+}
+
+/// Rejects a policy with no fallback (catch-all) rule. See
+/// [`check_fallback`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FallbackCheck;
+
+impl Check for FallbackCheck {
+    fn id(&self) -> &str {
+        "fallback-rule"
+    }
+
+    fn run(&self, policy: &Policy, _corpus: &Corpus, _config: &ProverConfig) -> Vec<Violation> {
+        check_fallback(policy).into_iter().collect()
+    }
+
+    fn suggest_fix(&self, policy: &Policy) -> Option<PolicyPatch> {
+        if policy.has_fallback() {
+            None
+        } else {
+            Some(PolicyPatch::AddRule(Rule::new(
+                "fallback",
+                "true",
+                Action::Sample(0.01),
+                0,
+            )))
+        }
+    }
+}
+
+/// Rejects a policy that could drop error traces, when
+/// [`ProverConfig::require_error_handling`] is set. See
+/// [`check_error_handling`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorHandlingCheck;
+
+impl Check for ErrorHandlingCheck {
+    fn id(&self) -> &str {
+        "error-handling"
+    }
+
+    fn run(&self, policy: &Policy, _corpus: &Corpus, config: &ProverConfig) -> Vec<Violation> {
+        if !config.require_error_handling {
+            return Vec::new();
+        }
+        check_error_handling(policy).into_iter().collect()
+    }
+}
+
+/// Rejects a policy that may drop error traces the corpus shows actually
+/// occurred, with no rule keeping them. See [`check_must_keep_coverage`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MustKeepCoverageCheck;
+
+impl Check for MustKeepCoverageCheck {
+    fn id(&self) -> &str {
+        "must-keep-coverage"
+    }
+
+    fn run(&self, policy: &Policy, corpus: &Corpus, _config: &ProverConfig) -> Vec<Violation> {
+        check_must_keep_coverage(policy, corpus).err().into_iter().collect()
+    }
+}
+
+/// Rejects a policy whose `budget_per_second` exceeds `max_budget`.
+/// Carries `max_budget` itself (rather than reading it off the
+/// `ProverConfig` passed to [`Self::run`]) so [`Self::suggest_fix`] can
+/// propose clamping down to it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BudgetComplianceCheck {
+    max_budget: Option<u64>,
+}
+
+impl BudgetComplianceCheck {
+    /// Creates a check that rejects any `budget_per_second` above `max_budget`.
+    #[must_use]
+    pub const fn new(max_budget: Option<u64>) -> Self {
+        Self { max_budget }
+    }
+}
+
+impl Check for BudgetComplianceCheck {
+    fn id(&self) -> &str {
+        "budget-compliance"
+    }
+
+    fn run(&self, policy: &Policy, _corpus: &Corpus, _config: &ProverConfig) -> Vec<Violation> {
+        let (Some(budget), Some(max)) = (policy.budget_per_second, self.max_budget) else {
+            return Vec::new();
+        };
+
+        if budget > max {
+            vec![Violation::critical(
+                self.id(),
+                format!("Policy budget {budget} exceeds maximum {max}"),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn suggest_fix(&self, policy: &Policy) -> Option<PolicyPatch> {
+        let budget = policy.budget_per_second?;
+        let max = self.max_budget?;
+        (budget > max).then_some(PolicyPatch::SetBudget(Some(max)))
+    }
+}
+
+/// Projects whether a policy can meet its budget given an assumed base
+/// ingest rate, warning if there's little headroom or the budget looks
+/// arbitrarily oversized. See [`check_budget_feasibility`].
+#[derive(Debug, Clone, Copy)]
+pub struct BudgetFeasibilityCheck {
+    base_ingest_rate: f64,
+    margin: f64,
+}
+
+impl BudgetFeasibilityCheck {
+    /// Creates a check projecting feasibility at `base_ingest_rate`,
+    /// warning once utilization reaches `margin`.
+    #[must_use]
+    pub const fn new(base_ingest_rate: f64, margin: f64) -> Self {
+        Self { base_ingest_rate, margin }
+    }
+}
+
+impl Default for BudgetFeasibilityCheck {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASE_INGEST_RATE, DEFAULT_BUDGET_WARNING_MARGIN)
+    }
+}
+
+impl Check for BudgetFeasibilityCheck {
+    fn id(&self) -> &str {
+        "budget-feasibility"
+    }
+
+    fn run(&self, policy: &Policy, _corpus: &Corpus, _config: &ProverConfig) -> Vec<Violation> {
+        let (violation, warning) =
+            check_budget_feasibility(policy, self.base_ingest_rate, self.margin);
+
+        violation.into_iter().chain(warning.map(Violation::from)).collect()
+    }
+}
+
+/// Projects whether a policy fits its budget from the corpus's own
+/// measured traffic, rather than [`BudgetFeasibilityCheck`]'s assumed
+/// flat ingest rate. See [`check_budget`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExpectedBudgetCheck;
+
+impl Check for ExpectedBudgetCheck {
+    fn id(&self) -> &str {
+        "budget"
+    }
+
+    fn run(&self, policy: &Policy, corpus: &Corpus, _config: &ProverConfig) -> Vec<Violation> {
+        check_budget(policy, corpus)
+            .map(Violation::from)
+            .into_iter()
+            .collect()
+    }
+}
+
+/// Flags rules shadowed by an earlier rule in the policy, regardless of
+/// corpus or traffic. See [`check_reachability`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReachabilityCheck;
+
+impl Check for ReachabilityCheck {
+    fn id(&self) -> &str {
+        "reachability"
+    }
+
+    fn run(&self, policy: &Policy, _corpus: &Corpus, _config: &ProverConfig) -> Vec<Violation> {
+        check_reachability(policy)
+            .into_iter()
+            .map(Violation::from)
+            .collect()
     }
 }
 
@@ -104,4 +695,414 @@ mod tests {
         policy.add_rule(Rule::new("drop-all", "true", Action::Drop, 0));
         assert!(check_error_handling(&policy).is_some());
     }
+
+    #[test]
+    fn check_error_handling_rejects_a_drop_rule_structurally_covering_errors() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "drop-5xx",
+            "http.status >= 500",
+            Action::Drop,
+            50,
+        ));
+        assert!(check_error_handling(&policy).is_some());
+    }
+
+    #[test]
+    fn check_error_handling_rejects_a_drop_rule_whose_threshold_covers_errors() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "drop-thresh",
+            "thresh(1, http.status >= 500, service.name == \"checkout\")",
+            Action::Drop,
+            50,
+        ));
+        assert!(check_error_handling(&policy).is_some());
+    }
+
+    #[test]
+    fn check_error_handling_passes_a_drop_rule_unrelated_to_errors() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "drop-health-checks",
+            "http.route == \"/health\"",
+            Action::Drop,
+            50,
+        ));
+        assert!(check_error_handling(&policy).is_none());
+    }
+
+    #[test]
+    fn check_must_keep_coverage_passes_when_no_errors_are_in_the_corpus() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Drop, 0));
+        let mut corpus = Corpus::new();
+        corpus.add(Trace::new("ok").with_status(200));
+
+        assert!(check_must_keep_coverage(&policy, &corpus).is_ok());
+    }
+
+    #[test]
+    fn check_must_keep_coverage_rejects_a_policy_that_drops_a_real_error_trace() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Drop, 0));
+        let mut corpus = Corpus::new();
+        corpus.add(Trace::new("err").with_status(500));
+
+        let err = check_must_keep_coverage(&policy, &corpus).unwrap_err();
+        assert!(err.is_blocking());
+    }
+
+    #[test]
+    fn check_must_keep_coverage_passes_when_a_matching_rule_keeps_the_error() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "keep-5xx",
+            "http.status >= 500",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new("fallback", "true", Action::Drop, 0));
+        let mut corpus = Corpus::new();
+        corpus.add(Trace::new("err").with_status(500));
+
+        assert!(check_must_keep_coverage(&policy, &corpus).is_ok());
+    }
+
+    #[test]
+    fn check_must_keep_coverage_understands_a_threshold_rule() {
+        // No special-casing needed in `check_must_keep_coverage` itself -
+        // `MatchExpr::eval`'s own `Threshold` support covers this.
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "keep-2-of-3",
+            "thresh(2, http.status >= 500, duration > 1s, error == true)",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new("fallback", "true", Action::Drop, 0));
+        let mut corpus = Corpus::new();
+        corpus.add(
+            Trace::new("err")
+                .with_status(500)
+                .with_duration(std::time::Duration::from_secs(2)),
+        );
+
+        assert!(check_must_keep_coverage(&policy, &corpus).is_ok());
+    }
+
+    #[test]
+    fn check_must_keep_coverage_rejects_a_rule_that_merely_mentions_status_in_its_name() {
+        // A real evaluator shouldn't be fooled by a rule whose *name*
+        // mentions errors/status but whose match_expr doesn't actually
+        // cover the error trace.
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "looks-like-error-handling",
+            "http.route == \"/checkout\"",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new("fallback", "true", Action::Drop, 0));
+        let mut corpus = Corpus::new();
+        corpus.add(Trace::new("err").with_status(500));
+
+        assert!(check_must_keep_coverage(&policy, &corpus).is_err());
+    }
+
+    #[test]
+    fn budget_feasibility_skips_policies_without_a_budget() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+        let (violation, warning) = check_budget_feasibility(&policy, 1000.0, 0.8);
+        assert!(violation.is_none());
+        assert!(warning.is_none());
+    }
+
+    #[test]
+    fn budget_feasibility_rejects_projection_over_budget() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(100);
+        policy.add_rule(Rule::new("keep-errors", "status >= 500", Action::Keep, 100));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        let (violation, warning) = check_budget_feasibility(&policy, 1000.0, 0.8);
+        assert!(violation.is_some());
+        assert!(warning.is_none());
+    }
+
+    #[test]
+    fn budget_feasibility_warns_near_the_margin() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(100);
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.09), 0));
+
+        // Projected = 1000 * 0.09 = 90, 90% of a budget of 100.
+        let (violation, warning) = check_budget_feasibility(&policy, 1000.0, 0.8);
+        assert!(violation.is_none());
+        assert!(matches!(warning, Some(w) if w.severity == crate::result::Severity::Warning));
+    }
+
+    #[test]
+    fn budget_feasibility_notes_a_budget_far_above_achievable() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(100_000);
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        // Projected = 1000 * 0.01 = 10, 0.01% of a budget of 100,000.
+        let (violation, warning) = check_budget_feasibility(&policy, 1000.0, 0.8);
+        assert!(violation.is_none());
+        assert!(matches!(warning, Some(w) if w.severity == crate::result::Severity::Info));
+    }
+
+    #[test]
+    fn fallback_check_suggests_adding_a_fallback_rule() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("errors", "error", Action::Keep, 100));
+        let config = ProverConfig::default();
+
+        let violations = FallbackCheck.run(&policy, &Corpus::new(), &config);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].is_blocking());
+
+        match FallbackCheck.suggest_fix(&policy) {
+            Some(PolicyPatch::AddRule(rule)) => assert_eq!(rule.match_expr, "true"),
+            other => panic!("expected an AddRule patch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn fallback_check_suggests_nothing_once_fixed() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+        assert!(FallbackCheck.suggest_fix(&policy).is_none());
+    }
+
+    #[test]
+    fn error_handling_check_is_inert_unless_required() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("drop-all", "true", Action::Drop, 0));
+        let config = ProverConfig {
+            require_error_handling: false,
+            ..Default::default()
+        };
+
+        assert!(ErrorHandlingCheck.run(&policy, &Corpus::new(), &config).is_empty());
+    }
+
+    #[test]
+    fn budget_compliance_check_suggests_clamping_to_the_max() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(5000);
+        let check = BudgetComplianceCheck::new(Some(1000));
+        let config = ProverConfig::default();
+
+        let violations = check.run(&policy, &Corpus::new(), &config);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(check.suggest_fix(&policy), Some(PolicyPatch::SetBudget(Some(1000))));
+    }
+
+    #[test]
+    fn check_budget_skips_policies_without_a_budget() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Keep, 0));
+        let mut corpus = Corpus::new();
+        corpus.add(Trace::new("a").with_status(200));
+
+        assert!(check_budget(&policy, &corpus).is_none());
+    }
+
+    #[test]
+    fn check_budget_skips_an_empty_corpus() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(10);
+        policy.add_rule(Rule::new("fallback", "true", Action::Keep, 0));
+
+        assert!(check_budget(&policy, &Corpus::new()).is_none());
+    }
+
+    #[test]
+    fn check_budget_passes_when_projected_volume_fits() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(100);
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+        let mut corpus = Corpus::new();
+        for i in 0..3 {
+            corpus.add(Trace::new(format!("t{i}")).with_status(200));
+        }
+
+        // No timestamps, so throughput falls back to DEFAULT_BASE_INGEST_RATE
+        // (1000/s): projected = 1000 * 0.01 = 10/s, well within budget.
+        assert!(check_budget(&policy, &corpus).is_none());
+    }
+
+    #[test]
+    fn check_budget_warns_when_projected_volume_exceeds_budget() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(5);
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+        let mut corpus = Corpus::new();
+        for i in 0..3 {
+            corpus.add(Trace::new(format!("t{i}")).with_status(200));
+        }
+
+        // Projected = 1000 * 0.01 = 10/s, over the budget of 5/s.
+        assert!(check_budget(&policy, &corpus).is_some());
+    }
+
+    #[test]
+    fn check_budget_deduplicates_overlapping_rules_via_first_match_wins() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(1500);
+        // Both rules match everything; a naive sum of full contributions
+        // would double-count to ~2000/s and blow the budget, but the
+        // higher-priority rule should claim every trace first, leaving
+        // nothing for the second.
+        policy.add_rule(Rule::new("keep-all", "true", Action::Keep, 100));
+        policy.add_rule(Rule::new("also-keep-all", "true", Action::Keep, 50));
+        let mut corpus = Corpus::new();
+        for i in 0..3 {
+            corpus.add(Trace::new(format!("t{i}")).with_status(200));
+        }
+
+        assert!(check_budget(&policy, &corpus).is_none());
+    }
+
+    #[test]
+    fn expected_budget_check_reports_as_a_warning_not_a_violation() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(5);
+        policy.add_rule(Rule::new("fallback", "true", Action::Keep, 0));
+        let mut corpus = Corpus::new();
+        corpus.add(Trace::new("a").with_status(200));
+
+        let violations = ExpectedBudgetCheck.run(&policy, &corpus, &ProverConfig::default());
+        assert_eq!(violations.len(), 1);
+        assert!(!violations[0].is_blocking());
+    }
+
+    #[test]
+    fn budget_compliance_check_passes_without_a_max() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(5000);
+        let check = BudgetComplianceCheck::new(None);
+
+        assert!(check.run(&policy, &Corpus::new(), &ProverConfig::default()).is_empty());
+        assert!(check.suggest_fix(&policy).is_none());
+    }
+
+    #[test]
+    fn check_reachability_passes_a_well_ordered_policy() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("keep-errors", "error", Action::Keep, 100));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        assert!(check_reachability(&policy).is_empty());
+    }
+
+    #[test]
+    fn check_reachability_flags_a_rule_after_an_earlier_fallback() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Keep, 100));
+        policy.add_rule(Rule::new("keep-errors", "error", Action::Keep, 50));
+
+        let warnings = check_reachability(&policy);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("keep-errors"));
+        assert!(warnings[0].message.contains("fallback"));
+    }
+
+    #[test]
+    fn check_reachability_flags_an_identical_match_expr() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "keep-5xx",
+            "http.status >= 500",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new(
+            "also-keep-5xx",
+            "http.status >= 500",
+            Action::Sample(0.5),
+            50,
+        ));
+
+        let warnings = check_reachability(&policy);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("also-keep-5xx"));
+    }
+
+    #[test]
+    fn check_reachability_flags_a_weaker_same_field_comparison() {
+        let mut policy = Policy::new("test");
+        // Anything >= 400 already matches everything >= 500 would, so
+        // the narrower rule below can never fire.
+        policy.add_rule(Rule::new(
+            "keep-4xx-and-5xx",
+            "http.status >= 400",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new(
+            "keep-5xx",
+            "http.status >= 500",
+            Action::Drop,
+            50,
+        ));
+
+        let warnings = check_reachability(&policy);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("keep-5xx"));
+    }
+
+    #[test]
+    fn check_reachability_does_not_flag_a_narrower_earlier_rule() {
+        let mut policy = Policy::new("test");
+        // The narrower rule comes first (higher priority), so it isn't
+        // shadowed by the broader one that follows.
+        policy.add_rule(Rule::new(
+            "keep-5xx",
+            "http.status >= 500",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new(
+            "keep-4xx-and-5xx",
+            "http.status >= 400",
+            Action::Sample(0.1),
+            50,
+        ));
+
+        assert!(check_reachability(&policy).is_empty());
+    }
+
+    #[test]
+    fn check_reachability_does_not_flag_a_threshold_rule() {
+        // `subsumes` doesn't reason about `Threshold` combinators, so a
+        // rule using one is never reported as shadowing or shadowed -
+        // conservatively missing a real overlap rather than risking a
+        // false positive.
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "keep-2-of-3",
+            "thresh(2, http.status >= 500, duration > 1s, error == true)",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+
+        assert!(check_reachability(&policy).is_empty());
+    }
+
+    #[test]
+    fn reachability_check_reports_as_a_warning_not_a_violation() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Keep, 100));
+        policy.add_rule(Rule::new("keep-errors", "error", Action::Keep, 50));
+
+        let violations = ReachabilityCheck.run(&policy, &Corpus::new(), &ProverConfig::default());
+        assert_eq!(violations.len(), 1);
+        assert!(!violations[0].is_blocking());
+    }
 }
@@ -25,6 +25,10 @@ pub enum Error {
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
+    /// Policy could not be parsed or serialized by `toon_policy`.
+    #[error(transparent)]
+    Policy(#[from] toon_policy::Error),
+
     /// Internal prover error.
     #[error("prover error: {0}")]
     Internal(String),
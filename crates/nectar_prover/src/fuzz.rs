@@ -0,0 +1,274 @@
+//! Seeded fuzz traffic generation for worst-case budget discovery.
+//!
+//! A hand-written [`TrafficPattern`] only covers the rate shapes its
+//! author thought to try. [`Prover::fuzz_traffic`] instead draws many
+//! patterns from a seeded RNG - varying base rate, burst magnitude, and
+//! timestamp spacing - simulates each one, and keeps whichever produced
+//! the largest budget overrun. Because the RNG is seeded, a discovered
+//! counterexample is exactly reproducible by re-running with the same
+//! seed and iteration count.
+
+use crate::prover::Prover;
+use crate::simulation::BudgetViolation;
+use crate::traffic::{TrafficPattern, TrafficPoint};
+use chrono::{DateTime, TimeZone, Utc};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use toon_policy::Policy;
+
+const MIN_BASE_RATE: f64 = 10.0;
+const MAX_BASE_RATE: f64 = 2000.0;
+const MIN_POINTS: usize = 5;
+const MAX_POINTS: usize = 60;
+const MIN_STEP_SECS: i64 = 1;
+const MAX_STEP_SECS: i64 = 30;
+const BURST_CHANCE: f64 = 0.2;
+const MIN_BURST_FACTOR: f64 = 1.0;
+const MAX_BURST_FACTOR: f64 = 20.0;
+
+/// A single fuzzed traffic pattern paired with the worst budget
+/// violation its simulation produced.
+#[derive(Debug, Clone)]
+pub struct FuzzCase {
+    /// The traffic pattern that triggered the violation.
+    pub pattern: TrafficPattern,
+    /// The worst violation this pattern's simulation produced.
+    pub violation: BudgetViolation,
+}
+
+/// Result of a [`Prover::fuzz_traffic`] run.
+#[derive(Debug, Clone)]
+pub struct FuzzReport {
+    /// Seed the RNG was initialized with - re-running `fuzz_traffic` with
+    /// this seed and [`Self::iterations`] reproduces this report exactly.
+    pub seed: u64,
+    /// Number of randomized patterns simulated.
+    pub iterations: usize,
+    /// The pattern across all iterations that produced the largest
+    /// budget overrun, if any iteration violated budget.
+    pub worst_case: Option<FuzzCase>,
+    /// [`Self::worst_case`] with its burst magnitudes repeatedly halved
+    /// while a violation still occurs - the smallest reproducer found.
+    /// `None` if there is no worst case, or if even one halving already
+    /// loses the violation.
+    pub minimal_case: Option<FuzzCase>,
+}
+
+/// A randomly generated traffic shape: each point's rate is `base_rate
+/// * burst_factor`, with most factors near baseline and a handful
+/// spiking to simulate a burst. Kept apart from the rendered
+/// [`TrafficPattern`] so shrinking can directly attenuate the burst
+/// component instead of re-deriving it from already-combined rates.
+#[derive(Debug, Clone)]
+struct FuzzShape {
+    base_rate: f64,
+    start: DateTime<Utc>,
+    step_secs: i64,
+    burst_factors: Vec<f64>,
+    error_rates: Vec<f64>,
+}
+
+impl FuzzShape {
+    fn random(rng: &mut ChaCha8Rng) -> Self {
+        let point_count = rng.gen_range(MIN_POINTS..=MAX_POINTS);
+        let base_rate = rng.gen_range(MIN_BASE_RATE..=MAX_BASE_RATE);
+        let step_secs = rng.gen_range(MIN_STEP_SECS..=MAX_STEP_SECS);
+
+        let burst_factors = (0..point_count)
+            .map(|_| {
+                if rng.gen_bool(BURST_CHANCE) {
+                    rng.gen_range(MIN_BURST_FACTOR..=MAX_BURST_FACTOR)
+                } else {
+                    rng.gen_range(0.5..=1.5)
+                }
+            })
+            .collect();
+        let error_rates = (0..point_count).map(|_| rng.gen_range(0.0..=0.1)).collect();
+
+        Self {
+            base_rate,
+            // Fixed epoch rather than `Utc::now()` so the whole shape -
+            // and the `FuzzReport` built from it - is a pure function of
+            // the seed.
+            start: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            step_secs,
+            burst_factors,
+            error_rates,
+        }
+    }
+
+    fn render(&self) -> TrafficPattern {
+        let mut pattern = TrafficPattern::new();
+        for (i, (&factor, &error_rate)) in
+            self.burst_factors.iter().zip(&self.error_rates).enumerate()
+        {
+            #[allow(clippy::cast_possible_wrap)]
+            let offset = chrono::Duration::seconds(self.step_secs * i as i64);
+            let eps = (self.base_rate * factor).max(0.0);
+            pattern
+                .add_point(TrafficPoint::new(self.start + offset, eps).with_error_rate(error_rate));
+        }
+        pattern
+    }
+
+    /// Halves every burst factor's distance above baseline (`1.0`),
+    /// shrinking spikes toward the base rate without changing it.
+    fn shrink_bursts(&self) -> Self {
+        Self {
+            burst_factors: self
+                .burst_factors
+                .iter()
+                .map(|factor| 1.0 + (factor - 1.0) * 0.5)
+                .collect(),
+            ..self.clone()
+        }
+    }
+}
+
+impl Prover {
+    /// Stress-tests `policy`'s budget compliance against `iterations`
+    /// randomized traffic patterns drawn from a seeded RNG, varying base
+    /// rate, burst magnitude, and timestamp spacing within fixed bounds.
+    ///
+    /// Returns the seed alongside the pattern that produced the highest
+    /// budget overrun, so a discovered counterexample is exactly
+    /// reproducible, plus a shrunk variant - see
+    /// [`FuzzReport::minimal_case`].
+    #[must_use]
+    pub fn fuzz_traffic(&self, policy: &Policy, iterations: usize, seed: u64) -> FuzzReport {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut worst: Option<(FuzzShape, FuzzCase)> = None;
+
+        for _ in 0..iterations {
+            let shape = FuzzShape::random(&mut rng);
+            let pattern = shape.render();
+            if let Some(case) = self.worst_violation(policy, pattern) {
+                let is_worse = worst
+                    .as_ref()
+                    .is_none_or(|(_, w)| case.violation.excess_events > w.violation.excess_events);
+                if is_worse {
+                    worst = Some((shape, case));
+                }
+            }
+        }
+
+        let (worst_case, minimal_case) = match worst {
+            Some((shape, case)) => {
+                let minimal = self.shrink(policy, &shape);
+                (Some(case), minimal)
+            }
+            None => (None, None),
+        };
+
+        FuzzReport {
+            seed,
+            iterations,
+            worst_case,
+            minimal_case,
+        }
+    }
+
+    /// Simulates `pattern` against `policy` and returns its peak
+    /// violation, if any.
+    fn worst_violation(&self, policy: &Policy, pattern: TrafficPattern) -> Option<FuzzCase> {
+        let result = self.simulate_traffic(policy, &pattern).ok()?;
+        let violation = result.peak_violation()?.clone();
+        Some(FuzzCase { pattern, violation })
+    }
+
+    /// Repeatedly halves `shape`'s burst magnitudes while the resulting
+    /// pattern still violates budget (stopping once halving no longer
+    /// changes any factor), returning the smallest reproducer found.
+    fn shrink(&self, policy: &Policy, shape: &FuzzShape) -> Option<FuzzCase> {
+        let mut current = shape.clone();
+        let mut smallest = None;
+        loop {
+            let shrunk = current.shrink_bursts();
+            if shrunk.burst_factors == current.burst_factors {
+                break;
+            }
+            match self.worst_violation(policy, shrunk.render()) {
+                Some(case) => {
+                    current = shrunk;
+                    smallest = Some(case);
+                }
+                None => break,
+            }
+        }
+        smallest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prover::ProverConfig;
+    use toon_policy::{Action, Rule};
+
+    fn tight_budget_policy() -> Policy {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("keep-errors", "status >= 500", Action::Keep, 100));
+        policy.add_rule(Rule::new("fallback", "true", Action::Keep, 0));
+        policy
+    }
+
+    #[test]
+    fn fuzz_traffic_is_reproducible_for_the_same_seed() {
+        let prover = Prover::new(ProverConfig {
+            max_budget: Some(500),
+            ..Default::default()
+        });
+        let policy = tight_budget_policy();
+
+        let first = prover.fuzz_traffic(&policy, 50, 42);
+        let second = prover.fuzz_traffic(&policy, 50, 42);
+
+        assert_eq!(first.seed, second.seed);
+        assert_eq!(
+            first.worst_case.as_ref().map(|c| c.violation.excess_events),
+            second
+                .worst_case
+                .as_ref()
+                .map(|c| c.violation.excess_events),
+        );
+    }
+
+    #[test]
+    fn fuzz_traffic_finds_a_budget_violation_under_a_tight_budget() {
+        let prover = Prover::new(ProverConfig {
+            max_budget: Some(500),
+            ..Default::default()
+        });
+        let policy = tight_budget_policy();
+
+        let report = prover.fuzz_traffic(&policy, 50, 7);
+
+        assert!(report.worst_case.is_some());
+    }
+
+    #[test]
+    fn fuzz_traffic_never_finds_a_violation_without_a_budget() {
+        let prover = Prover::default();
+        let policy = tight_budget_policy();
+
+        let report = prover.fuzz_traffic(&policy, 20, 1);
+
+        assert!(report.worst_case.is_none());
+        assert!(report.minimal_case.is_none());
+    }
+
+    #[test]
+    fn minimal_case_is_no_larger_than_the_worst_case() {
+        let prover = Prover::new(ProverConfig {
+            max_budget: Some(500),
+            ..Default::default()
+        });
+        let policy = tight_budget_policy();
+
+        let report = prover.fuzz_traffic(&policy, 50, 7);
+
+        if let (Some(worst), Some(minimal)) = (&report.worst_case, &report.minimal_case) {
+            assert!(minimal.violation.excess_events <= worst.violation.excess_events);
+        }
+    }
+}
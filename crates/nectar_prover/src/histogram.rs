@@ -0,0 +1,243 @@
+//! Log-linear latency histogram for percentile queries.
+//!
+//! A self-contained HDR-style histogram: values are bucketed first by the
+//! position of their highest set bit (the "exponent"), then each
+//! exponent's range `[2^e, 2^(e+1))` is subdivided into
+//! `2^SUB_BUCKET_BITS` equal-width linear sub-buckets. This bounds the
+//! relative error of any recorded value to roughly `1 / 2^SUB_BUCKET_BITS`
+//! regardless of magnitude, while keeping `record` O(1) and storage fixed
+//! size (no external histogram crate required).
+
+use serde::{Deserialize, Serialize};
+
+/// Number of linear sub-buckets per exponent, as a power of two.
+const SUB_BUCKET_BITS: u32 = 5;
+/// Number of linear sub-buckets per exponent (`2^SUB_BUCKET_BITS`).
+const SUB_BUCKETS: usize = 1 << SUB_BUCKET_BITS;
+/// Number of exponents needed to cover the full range of a `u64`.
+const EXPONENTS: usize = 64;
+
+/// A log-linear histogram of latency samples, recorded in nanoseconds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyHistogram {
+    counts: Vec<[u64; SUB_BUCKETS]>,
+    total_count: u64,
+    min_ns: u64,
+    max_ns: u64,
+}
+
+impl LatencyHistogram {
+    /// Creates a new, empty histogram.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            counts: vec![[0u64; SUB_BUCKETS]; EXPONENTS],
+            total_count: 0,
+            min_ns: u64::MAX,
+            max_ns: 0,
+        }
+    }
+
+    /// Records a latency value, in nanoseconds.
+    pub fn record(&mut self, value_ns: u64) {
+        let (exponent, sub_bucket) = Self::bucket_for(value_ns);
+        self.counts[exponent][sub_bucket] += 1;
+        self.total_count += 1;
+        self.min_ns = self.min_ns.min(value_ns);
+        self.max_ns = self.max_ns.max(value_ns);
+    }
+
+    /// Returns the total number of recorded values.
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.total_count
+    }
+
+    /// Returns the exact minimum recorded value, if any.
+    #[must_use]
+    pub fn min_ns(&self) -> Option<u64> {
+        (self.total_count > 0).then_some(self.min_ns)
+    }
+
+    /// Returns the exact maximum recorded value, if any.
+    #[must_use]
+    pub fn max_ns(&self) -> Option<u64> {
+        (self.total_count > 0).then_some(self.max_ns)
+    }
+
+    /// Returns the approximate value at `percentile` (0.0-100.0).
+    ///
+    /// Walks buckets in ascending value order, accumulating counts until
+    /// the running total crosses `percentile / 100 * count()`, then
+    /// returns that bucket's representative (midpoint) value. Returns `0`
+    /// for an empty histogram.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss,
+        clippy::cast_possible_truncation
+    )]
+    pub fn value_at_percentile(&self, percentile: f64) -> u64 {
+        if self.total_count == 0 {
+            return 0;
+        }
+        let percentile = percentile.clamp(0.0, 100.0);
+        let target = ((percentile / 100.0) * self.total_count as f64)
+            .ceil()
+            .max(1.0) as u64;
+
+        let mut cumulative = 0u64;
+        for exponent in 0..EXPONENTS {
+            for sub_bucket in 0..SUB_BUCKETS {
+                let count = self.counts[exponent][sub_bucket];
+                if count == 0 {
+                    continue;
+                }
+                cumulative += count;
+                if cumulative >= target {
+                    return Self::representative_value(exponent, sub_bucket);
+                }
+            }
+        }
+
+        self.max_ns
+    }
+
+    /// The 50th percentile (median) latency, in nanoseconds.
+    #[must_use]
+    pub fn p50(&self) -> u64 {
+        self.value_at_percentile(50.0)
+    }
+
+    /// The 90th percentile latency, in nanoseconds.
+    #[must_use]
+    pub fn p90(&self) -> u64 {
+        self.value_at_percentile(90.0)
+    }
+
+    /// The 99th percentile latency, in nanoseconds.
+    #[must_use]
+    pub fn p99(&self) -> u64 {
+        self.value_at_percentile(99.0)
+    }
+
+    /// The 99.9th percentile latency, in nanoseconds.
+    #[must_use]
+    pub fn p999(&self) -> u64 {
+        self.value_at_percentile(99.9)
+    }
+
+    /// Merges another histogram's recorded values into this one, so that
+    /// per-window histograms can be rolled up into an overall summary.
+    pub fn merge(&mut self, other: &Self) {
+        for (exponent, other_counts) in other.counts.iter().enumerate() {
+            for (sub_bucket, count) in other_counts.iter().enumerate() {
+                self.counts[exponent][sub_bucket] += count;
+            }
+        }
+        self.total_count += other.total_count;
+        self.min_ns = self.min_ns.min(other.min_ns);
+        self.max_ns = self.max_ns.max(other.max_ns);
+    }
+
+    /// Maps a value to its `(exponent, sub_bucket)` cell.
+    ///
+    /// Values below `SUB_BUCKETS` are recorded exactly (one unit per
+    /// sub-bucket in exponent 0); larger values fall into the exponent
+    /// matching their highest set bit, subdivided linearly.
+    #[allow(clippy::cast_possible_truncation)]
+    const fn bucket_for(value: u64) -> (usize, usize) {
+        if value < SUB_BUCKETS as u64 {
+            return (0, value as usize);
+        }
+        let exponent = (63 - value.leading_zeros()) as usize;
+        let base = 1u64 << exponent;
+        let width_shift = exponent as u32 - SUB_BUCKET_BITS;
+        let sub_bucket = ((value - base) >> width_shift) as usize;
+        // Guards against the top cell of an exponent rounding up to SUB_BUCKETS.
+        (exponent, if sub_bucket >= SUB_BUCKETS { SUB_BUCKETS - 1 } else { sub_bucket })
+    }
+
+    /// Returns the representative (midpoint) value for a `(exponent, sub_bucket)` cell.
+    const fn representative_value(exponent: usize, sub_bucket: usize) -> u64 {
+        if exponent == 0 {
+            return sub_bucket as u64;
+        }
+        let base = 1u64 << exponent;
+        let width_shift = exponent as u32 - SUB_BUCKET_BITS;
+        let width = 1u64 << width_shift;
+        base + (sub_bucket as u64) * width + width / 2
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_histogram_reports_zero() {
+        let hist = LatencyHistogram::new();
+        assert_eq!(hist.count(), 0);
+        assert_eq!(hist.min_ns(), None);
+        assert_eq!(hist.max_ns(), None);
+        assert_eq!(hist.value_at_percentile(50.0), 0);
+    }
+
+    #[test]
+    fn records_exact_small_values() {
+        let mut hist = LatencyHistogram::new();
+        for v in [1u64, 2, 3, 4, 5] {
+            hist.record(v);
+        }
+        assert_eq!(hist.count(), 5);
+        assert_eq!(hist.min_ns(), Some(1));
+        assert_eq!(hist.max_ns(), Some(5));
+        assert_eq!(hist.value_at_percentile(100.0), 5);
+    }
+
+    #[test]
+    fn percentiles_are_approximately_correct() {
+        let mut hist = LatencyHistogram::new();
+        for v in 1..=1000u64 {
+            hist.record(v * 1_000_000); // 1ms .. 1000ms
+        }
+
+        let p50 = hist.p50();
+        let p99 = hist.p99();
+
+        // Within a reasonable relative error of the true values (500ms, 990ms).
+        assert!(
+            (450_000_000..=550_000_000).contains(&p50),
+            "p50 = {p50}"
+        );
+        assert!(
+            (950_000_000..=1_010_000_000).contains(&p99),
+            "p99 = {p99}"
+        );
+        assert!(hist.p999() >= p99);
+    }
+
+    #[test]
+    fn merge_combines_two_histograms() {
+        let mut a = LatencyHistogram::new();
+        let mut b = LatencyHistogram::new();
+        for v in 1..=50u64 {
+            a.record(v);
+        }
+        for v in 51..=100u64 {
+            b.record(v);
+        }
+
+        a.merge(&b);
+
+        assert_eq!(a.count(), 100);
+        assert_eq!(a.min_ns(), Some(1));
+        assert_eq!(a.max_ns(), Some(100));
+    }
+}
@@ -9,6 +9,10 @@
 //! - **Fallback rule**: Policy has a catch-all rule
 //! - **No error dropping**: Errors are always kept
 //!
+//! These are built-in implementations of the [`Check`] trait; a
+//! [`Prover`] can grow more via [`Prover::register_check`] without
+//! touching [`Prover::verify`] itself.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -60,28 +64,49 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::must_use_candidate)]
 
+pub mod adapter;
 pub mod analysis;
+pub mod batch;
 pub mod checks;
+pub mod clock;
 pub mod error;
+pub mod fuzz;
+pub mod histogram;
+pub mod perturbation;
 pub mod prover;
 pub mod replay;
 pub mod result;
 pub mod simulation;
 pub mod traffic;
 
+pub use adapter::{Adapter, DirectoryAdapter, FileAdapter, LoadedPolicy, MemoryAdapter};
 pub use analysis::{
-    AnalysisMode, Confidence, CoverageAnalysis, RuleConflict, StaticAnalysisResult,
+    AnalysisMode, Confidence, CoverageAnalysis, CoverageGap, RuleConflict, StaticAnalysisResult,
     StaticAnalyzer, StaticWarning,
 };
+pub use batch::BatchSummary;
+pub use checks::{
+    BudgetComplianceCheck, BudgetFeasibilityCheck, Check, ErrorHandlingCheck, ExpectedBudgetCheck,
+    FallbackCheck, MustKeepCoverageCheck, ReachabilityCheck,
+};
+pub use clock::{Clock, MockClock, SystemClock};
 pub use error::{Error, Result};
-pub use prover::{AnalysisResult, Prover, ProverConfig};
+pub use fuzz::{FuzzCase, FuzzReport};
+pub use histogram::LatencyHistogram;
+pub use perturbation::TrafficPerturbation;
+pub use prover::{AnalysisResult, Prover, ProverConfig, WatchConfig};
 pub use replay::{
-    ReplayConfig, ReplayResult, ReplaySpeed, ReplaySummary, ReplayTimeRange, ReplayWindow,
-    Replayer, TimeWindow,
+    BudgetEnforcement, IntervalReport, LineProtocolPrecision, ReplayConfig, ReplayResult,
+    ReplaySpeed, ReplayStreamResult, ReplaySummary, ReplayTimeRange, ReplayWindow, Replayer,
+    TimeWindow,
 };
-pub use result::{ProverResult, Severity, Violation, Warning};
+pub use result::{PolicyPatch, ProverResult, Severity, Violation, Warning};
 pub use simulation::{
-    BudgetViolation, Recommendation, RecommendationKind, SimulationPoint, SimulationResult,
+    BudgetViolation, MonteCarloResult, OptimizationResult, PercentileStats, Recommendation,
+    RecommendationKind, RuleCoverage, RuleHit, SimulationPoint, SimulationResult,
     SimulationSummary, Simulator,
 };
-pub use traffic::{TrafficPattern, TrafficPoint, TrafficStats};
+pub use traffic::{
+    BurstEvent, RangeQueryMapping, SyntheticTrafficConfig, TrafficPattern, TrafficPoint,
+    TrafficStats,
+};
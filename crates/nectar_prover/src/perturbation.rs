@@ -0,0 +1,336 @@
+//! Reproducible fault and variance injection for stress-testing traffic
+//! patterns before simulation.
+//!
+//! Real traffic feeds are noisier than the clean points a hand-written
+//! [`TrafficPattern`] assumes. [`TrafficPerturbation`] runs each point
+//! through a configurable set of distortions - spikes, dropouts, jitter,
+//! and error-rate bursts - driven by a seeded `xorshift32` generator (the
+//! lightweight scheme behind renet's fault injector) so a perturbed
+//! pattern, and any budget violation [`crate::simulation::Simulator`]
+//! finds in it, is exactly reproducible from the seed alone.
+
+use crate::traffic::{TrafficPattern, TrafficPoint};
+
+/// Minimal, dependency-free 32-bit xorshift generator. Not suitable for
+/// anything security-sensitive, but fast and good enough to drive
+/// perturbation decisions deterministically from a seed.
+struct Xorshift32 {
+    state: u32,
+}
+
+impl Xorshift32 {
+    /// Seeds the generator, folding a 64-bit seed down to the
+    /// non-zero 32-bit state xorshift32 requires.
+    fn new(seed: u64) -> Self {
+        #[allow(clippy::cast_possible_truncation)]
+        let folded = (seed ^ (seed >> 32)) as u32;
+        Self {
+            state: if folded == 0 { 0x9E37_79B9 } else { folded },
+        }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Returns a float uniformly distributed over 0.0 (inclusive) to 1.0
+    /// (exclusive).
+    #[allow(clippy::cast_precision_loss)]
+    fn next_f64(&mut self) -> f64 {
+        f64::from(self.next_u32()) / f64::from(u32::MAX)
+    }
+
+    /// Returns `true` with probability `probability` (clamped to `[0, 1]`).
+    fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability.clamp(0.0, 1.0)
+    }
+
+    /// Returns a value drawn uniformly from `[low, high]`.
+    fn range(&mut self, low: f64, high: f64) -> f64 {
+        low + self.next_f64() * (high - low)
+    }
+
+    /// Draws a standard normal variate via the Box-Muller transform over
+    /// two uniform draws.
+    fn next_gaussian(&mut self) -> f64 {
+        let u1 = self.next_f64().max(f64::MIN_POSITIVE); // avoid ln(0.0)
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+    }
+
+    /// Returns a multiplicative lognormal factor `exp(sigma * z)` for a
+    /// standard normal `z`, so `sigma` is the log-space standard
+    /// deviation of the factor.
+    fn lognormal_factor(&mut self, sigma: f64) -> f64 {
+        (sigma * self.next_gaussian()).exp()
+    }
+}
+
+/// Configures the fault/variance injection [`Self::apply`] runs over a
+/// [`TrafficPattern`] - the chance and magnitude of spikes, dropouts,
+/// jitter, and error-rate bursts applied per point.
+///
+/// All knobs default to off, so `TrafficPerturbation::new()` is a no-op;
+/// chain the `with_*` builders to enable the distortions a stress test
+/// needs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrafficPerturbation {
+    spike_chance: f64,
+    spike_factor: (f64, f64),
+    dropout_chance: f64,
+    jitter: f64,
+    error_burst_chance: f64,
+    error_burst_rate: (f64, f64),
+}
+
+impl Default for TrafficPerturbation {
+    fn default() -> Self {
+        Self {
+            spike_chance: 0.0,
+            spike_factor: (2.0, 5.0),
+            dropout_chance: 0.0,
+            jitter: 0.0,
+            error_burst_chance: 0.0,
+            error_burst_rate: (0.2, 0.5),
+        }
+    }
+}
+
+impl TrafficPerturbation {
+    /// Creates a perturbation with every distortion disabled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables traffic spikes: with probability `chance`, a point's
+    /// `events_per_second` is multiplied by a factor drawn uniformly from
+    /// `factor_range`.
+    #[must_use]
+    pub const fn with_spikes(mut self, chance: f64, factor_range: (f64, f64)) -> Self {
+        self.spike_chance = chance;
+        self.spike_factor = factor_range;
+        self
+    }
+
+    /// Enables dropouts: with probability `chance`, a point's
+    /// `events_per_second` is zeroed entirely.
+    #[must_use]
+    pub const fn with_dropouts(mut self, chance: f64) -> Self {
+        self.dropout_chance = chance;
+        self
+    }
+
+    /// Enables jitter: every non-dropped point's `events_per_second` is
+    /// multiplied by `1.0 + u` for `u` drawn uniformly from
+    /// `[-fraction, fraction]`.
+    #[must_use]
+    pub const fn with_jitter(mut self, fraction: f64) -> Self {
+        self.jitter = fraction;
+        self
+    }
+
+    /// Enables error-rate bursts: with probability `chance`, a point's
+    /// `error_rate` is overridden with a value drawn uniformly from
+    /// `rate_range`.
+    #[must_use]
+    pub const fn with_error_bursts(mut self, chance: f64, rate_range: (f64, f64)) -> Self {
+        self.error_burst_chance = chance;
+        self.error_burst_rate = rate_range;
+        self
+    }
+
+    /// Applies this perturbation to every point in `pattern`, seeded by
+    /// `seed`, returning a new pattern. Running with the same pattern and
+    /// seed always produces the same result.
+    #[must_use]
+    pub fn apply(&self, pattern: &TrafficPattern, seed: u64) -> TrafficPattern {
+        let mut rng = Xorshift32::new(seed);
+        let points = pattern
+            .points()
+            .iter()
+            .map(|point| self.apply_point(point, &mut rng))
+            .collect();
+
+        let mut perturbed = TrafficPattern::from_points(points);
+        perturbed.name = pattern.name.clone();
+        perturbed
+    }
+
+    fn apply_point(&self, point: &TrafficPoint, rng: &mut Xorshift32) -> TrafficPoint {
+        let mut events_per_second = point.events_per_second;
+
+        if rng.chance(self.dropout_chance) {
+            events_per_second = 0.0;
+        } else {
+            if rng.chance(self.spike_chance) {
+                events_per_second *= rng.range(self.spike_factor.0, self.spike_factor.1);
+            }
+            if self.jitter > 0.0 {
+                events_per_second *= 1.0 + rng.range(-self.jitter, self.jitter);
+            }
+        }
+
+        let error_rate = if rng.chance(self.error_burst_chance) {
+            rng.range(self.error_burst_rate.0, self.error_burst_rate.1)
+        } else {
+            point.error_rate
+        };
+
+        TrafficPoint {
+            timestamp: point.timestamp,
+            events_per_second: events_per_second.max(0.0),
+            error_rate,
+            p99_latency: point.p99_latency,
+        }
+    }
+}
+
+/// Applies independent multiplicative lognormal jitter to every point's
+/// `events_per_second` and `error_rate`, seeded by `seed`, for
+/// [`crate::simulation::Simulator::simulate_monte_carlo`]'s randomized
+/// traffic realizations.
+///
+/// `sigma` is the log-space standard deviation: each value is multiplied
+/// by `exp(sigma * z)` for `z` drawn from a standard normal distribution,
+/// so `sigma = 0.0` reproduces `pattern` unchanged. `error_rate` is
+/// clamped back to `[0, 1]` after jittering.
+pub(crate) fn lognormal_jitter(pattern: &TrafficPattern, seed: u64, sigma: f64) -> TrafficPattern {
+    let mut rng = Xorshift32::new(seed);
+    let points = pattern
+        .points()
+        .iter()
+        .map(|point| TrafficPoint {
+            timestamp: point.timestamp,
+            events_per_second: (point.events_per_second * rng.lognormal_factor(sigma)).max(0.0),
+            error_rate: (point.error_rate * rng.lognormal_factor(sigma)).clamp(0.0, 1.0),
+            p99_latency: point.p99_latency,
+        })
+        .collect();
+
+    let mut jittered = TrafficPattern::from_points(points);
+    jittered.name = pattern.name.clone();
+    jittered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn sample_pattern() -> TrafficPattern {
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        TrafficPattern::from_points(
+            (0..20)
+                .map(|i| TrafficPoint::new(base + chrono::Duration::seconds(i), 100.0))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn apply_is_reproducible_for_the_same_seed() {
+        let pattern = sample_pattern();
+        let perturbation = TrafficPerturbation::new()
+            .with_spikes(0.3, (2.0, 5.0))
+            .with_dropouts(0.1)
+            .with_jitter(0.2)
+            .with_error_bursts(0.2, (0.3, 0.6));
+
+        let first = perturbation.apply(&pattern, 42);
+        let second = perturbation.apply(&pattern, 42);
+
+        assert_eq!(first.points(), second.points());
+    }
+
+    #[test]
+    fn different_seeds_usually_diverge() {
+        let pattern = sample_pattern();
+        let perturbation = TrafficPerturbation::new().with_jitter(0.5);
+
+        let first = perturbation.apply(&pattern, 1);
+        let second = perturbation.apply(&pattern, 2);
+
+        assert_ne!(first.points(), second.points());
+    }
+
+    #[test]
+    fn no_op_perturbation_leaves_the_pattern_unchanged() {
+        let pattern = sample_pattern();
+        let perturbed = TrafficPerturbation::new().apply(&pattern, 7);
+
+        assert_eq!(pattern.points(), perturbed.points());
+    }
+
+    #[test]
+    fn full_dropout_zeroes_every_point() {
+        let pattern = sample_pattern();
+        let perturbed = TrafficPerturbation::new()
+            .with_dropouts(1.0)
+            .apply(&pattern, 7);
+
+        assert!(perturbed
+            .points()
+            .iter()
+            .all(|p| p.events_per_second == 0.0));
+    }
+
+    #[test]
+    fn guaranteed_error_burst_overrides_the_error_rate() {
+        let pattern = sample_pattern();
+        let perturbed = TrafficPerturbation::new()
+            .with_error_bursts(1.0, (0.4, 0.4))
+            .apply(&pattern, 7);
+
+        assert!(perturbed
+            .points()
+            .iter()
+            .all(|p| (p.error_rate - 0.4).abs() < 1e-9));
+    }
+
+    #[test]
+    fn lognormal_jitter_is_reproducible_for_the_same_seed() {
+        let pattern = sample_pattern();
+
+        let first = lognormal_jitter(&pattern, 42, 0.2);
+        let second = lognormal_jitter(&pattern, 42, 0.2);
+
+        assert_eq!(first.points(), second.points());
+    }
+
+    #[test]
+    fn zero_sigma_leaves_the_pattern_unchanged() {
+        let pattern = sample_pattern();
+
+        let jittered = lognormal_jitter(&pattern, 42, 0.0);
+
+        for (original, perturbed) in pattern.points().iter().zip(jittered.points()) {
+            assert!((original.events_per_second - perturbed.events_per_second).abs() < 1e-9);
+            assert!((original.error_rate - perturbed.error_rate).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn lognormal_jitter_keeps_error_rate_in_bounds() {
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let pattern = TrafficPattern::from_points(
+            (0..50)
+                .map(|i| {
+                    TrafficPoint::new(base + chrono::Duration::seconds(i), 100.0)
+                        .with_error_rate(0.9)
+                })
+                .collect(),
+        );
+
+        let jittered = lognormal_jitter(&pattern, 7, 0.8);
+
+        assert!(jittered
+            .points()
+            .iter()
+            .all(|p| (0.0..=1.0).contains(&p.error_rate)));
+    }
+}
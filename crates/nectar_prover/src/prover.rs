@@ -1,24 +1,37 @@
 //! Main prover implementation.
 
-use crate::analysis::{AnalysisMode, Confidence, StaticAnalysisResult, StaticAnalyzer};
-use crate::checks;
+use crate::adapter::Adapter;
+use crate::analysis::{
+    AnalysisMode, Confidence, ConflictType, StaticAnalysisResult, StaticAnalyzer, StaticWarning,
+};
+use crate::checks::{
+    self, BudgetComplianceCheck, BudgetFeasibilityCheck, Check, ErrorHandlingCheck,
+    ExpectedBudgetCheck, FallbackCheck, MustKeepCoverageCheck, ReachabilityCheck,
+};
+use crate::clock::Clock;
 use crate::error::{Error, Result};
-use crate::result::{ProverResult, Violation};
+use crate::result::{ProverResult, Warning};
 use crate::simulation::{SimulationResult, Simulator};
 use crate::traffic::TrafficPattern;
-use nectar_corpus::Corpus;
+use nectar_corpus::{Corpus, Trace};
 use std::path::Path;
+use std::time::Duration;
 use toon_policy::Policy;
 
 /// Policy prover that validates policies before compilation.
-#[derive(Debug, Clone)]
+///
+/// Owns a registry of [`Check`]s - the seven built-ins plus any added via
+/// [`Self::register_check`] - run against every policy [`Self::verify`]
+/// processes, rather than a fixed, hardcoded pipeline.
+#[derive(Debug)]
 pub struct Prover {
     config: ProverConfig,
     static_analyzer: StaticAnalyzer,
+    checks: Vec<Box<dyn Check + Send + Sync>>,
 }
 
 /// Configuration for the prover.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct ProverConfig {
     /// Maximum allowed budget per second.
     pub max_budget: Option<u64>,
@@ -26,6 +39,29 @@ pub struct ProverConfig {
     pub require_error_handling: bool,
     /// Analysis mode to use.
     pub analysis_mode: AnalysisMode,
+    /// Base (pre-sampling) ingest rate in traces/sec, used to project
+    /// budget feasibility when no real traffic pattern is available.
+    pub base_ingest_rate: f64,
+    /// Utilization ratio above which budget feasibility warns even though
+    /// the projection is still within budget.
+    pub budget_warning_margin: f64,
+    /// Maximum number of policies [`Prover::verify_batch`]/`analyze_batch`
+    /// process concurrently. `None` uses rayon's default (one thread per
+    /// available core).
+    pub max_concurrency: Option<usize>,
+}
+
+impl Default for ProverConfig {
+    fn default() -> Self {
+        Self {
+            max_budget: None,
+            require_error_handling: false,
+            analysis_mode: AnalysisMode::default(),
+            base_ingest_rate: checks::DEFAULT_BASE_INGEST_RATE,
+            budget_warning_margin: checks::DEFAULT_BUDGET_WARNING_MARGIN,
+            max_concurrency: None,
+        }
+    }
 }
 
 impl Default for Prover {
@@ -34,22 +70,74 @@ impl Default for Prover {
     }
 }
 
+/// Configuration for [`Prover::watch`].
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    /// How often to check the watched files for changes.
+    pub poll_interval: Duration,
+    /// How long to wait, after a change is first observed, before
+    /// re-analyzing - absorbs editors that write a file in several quick
+    /// bursts so those collapse into a single re-analysis once the file
+    /// settles.
+    pub debounce: Duration,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_millis(200),
+            debounce: Duration::from_millis(300),
+        }
+    }
+}
+
 impl Prover {
-    /// Creates a new prover with the given configuration.
+    /// Creates a new prover with the given configuration, populated with
+    /// the built-in checks (fallback rule, error handling, must-keep
+    /// coverage, budget compliance, budget feasibility, expected budget,
+    /// reachability).
     #[must_use]
-    pub const fn new(config: ProverConfig) -> Self {
+    pub fn new(config: ProverConfig) -> Self {
+        let checks: Vec<Box<dyn Check + Send + Sync>> = vec![
+            Box::new(FallbackCheck),
+            Box::new(ErrorHandlingCheck),
+            Box::new(MustKeepCoverageCheck),
+            Box::new(BudgetComplianceCheck::new(config.max_budget)),
+            Box::new(BudgetFeasibilityCheck::new(
+                config.base_ingest_rate,
+                config.budget_warning_margin,
+            )),
+            Box::new(ExpectedBudgetCheck),
+            Box::new(ReachabilityCheck),
+        ];
+
         Self {
             config,
             static_analyzer: StaticAnalyzer::new(),
+            checks,
         }
     }
 
+    /// Registers an additional check, run alongside the built-ins on
+    /// every subsequent [`Self::verify`] call.
+    pub fn register_check(&mut self, check: impl Check + Send + Sync + 'static) {
+        self.checks.push(Box::new(check));
+    }
+
     /// Returns the analysis mode.
     #[must_use]
     pub const fn analysis_mode(&self) -> AnalysisMode {
         self.config.analysis_mode
     }
 
+    /// Returns this prover's configuration, for modules (e.g.
+    /// [`crate::batch`]) that need a config value `verify`/`analyze`
+    /// don't otherwise expose.
+    #[must_use]
+    pub(crate) const fn config(&self) -> &ProverConfig {
+        &self.config
+    }
+
     /// Performs static analysis only (fast path).
     ///
     /// This is O(rules) and suitable for rapid iteration.
@@ -62,7 +150,11 @@ impl Prover {
     ///
     /// - Static mode: Fast rule analysis only
     /// - Dynamic mode: Full traffic simulation (requires traffic pattern)
-    /// - Auto mode: Static first, dynamic if traffic provided
+    /// - Auto mode: Static first; dynamic simulation only runs if static
+    ///   analysis left rules unresolved (see
+    ///   [`StaticAnalysisResult::is_fully_resolved`]) and traffic is
+    ///   provided, so a policy static reasoning can already fully prove
+    ///   doesn't also pay the O(rules Ã— events) simulation cost.
     ///
     /// # Errors
     ///
@@ -76,7 +168,7 @@ impl Prover {
         let mode = self.config.analysis_mode;
 
         // Perform static analysis if needed
-        let static_result = if mode.includes_static() {
+        let mut static_result = if mode.includes_static() {
             Some(self.analyze_static(policy))
         } else {
             None
@@ -85,8 +177,18 @@ impl Prover {
         // Perform verification (combines static checks with corpus)
         let prover_result = self.verify(policy, corpus)?;
 
+        // In Auto mode, skip dynamic simulation once static analysis has
+        // already fully resolved the policy - there's nothing left for
+        // simulation to verify. Dynamic mode always simulates, since it
+        // doesn't run static analysis to resolve anything in the first
+        // place.
+        let skip_dynamic = mode == AnalysisMode::Auto
+            && static_result
+                .as_ref()
+                .is_some_and(StaticAnalysisResult::is_fully_resolved);
+
         // Perform dynamic simulation if needed and traffic is available
-        let simulation_result = if mode.includes_dynamic() {
+        let simulation_result = if mode.includes_dynamic() && !skip_dynamic {
             if let Some(traffic) = traffic {
                 Some(self.simulate_traffic(policy, traffic)?)
             } else {
@@ -96,6 +198,29 @@ impl Prover {
             None
         };
 
+        // Surface rules that dynamic simulation never exercised, so a rule
+        // the static analyzer thought reachable but that no simulated
+        // event actually hit is reported concretely rather than silently
+        // passing.
+        if let (Some(sim), Some(static_res)) =
+            (simulation_result.as_ref(), static_result.as_mut())
+        {
+            for dead in sim.rule_coverage.dead_rules() {
+                static_res.warnings.push(
+                    StaticWarning::new(
+                        &dead.rule_name,
+                        format!(
+                            "rule never fired in {:.0} simulated events",
+                            sim.summary.total_incoming
+                        ),
+                    )
+                    .with_suggestion(
+                        "Check whether this rule's condition can ever be reached given this traffic pattern",
+                    ),
+                );
+            }
+        }
+
         // Determine confidence level
         let confidence = determine_confidence(static_result.as_ref(), simulation_result.as_ref());
 
@@ -119,61 +244,41 @@ impl Prover {
         }
 
         let mut violations = Vec::new();
+        let mut warnings = Vec::new();
+        let mut fixes = Vec::new();
         let mut checks_passed = 0;
-        let checks_total = 4;
-
-        // Check 1: Fallback rule
-        if let Some(v) = checks::check_fallback(policy) {
-            violations.push(v);
-        } else {
-            checks_passed += 1;
-        }
+        let checks_total = self.checks.len();
+
+        for check in &self.checks {
+            let mut blocked = false;
+            for violation in check.run(policy, corpus, &self.config) {
+                if violation.is_blocking() {
+                    blocked = true;
+                    violations.push(violation);
+                } else {
+                    warnings.push(Warning::from(violation));
+                }
+            }
 
-        // Check 2: Error handling
-        if self.config.require_error_handling {
-            if let Some(v) = checks::check_error_handling(policy) {
-                violations.push(v);
+            if blocked {
+                fixes.extend(check.suggest_fix(policy));
             } else {
                 checks_passed += 1;
             }
-        } else {
-            checks_passed += 1;
         }
 
-        // Check 3: Must-keep coverage
-        if let Err(v) = checks::check_must_keep_coverage(policy, corpus) {
-            violations.push(v);
+        let mut result = if violations.is_empty() {
+            ProverResult::approved(checks_passed)
         } else {
-            checks_passed += 1;
-        }
+            ProverResult::rejected(violations, checks_passed, checks_total)
+        };
+        result.fixes = fixes;
 
-        // Check 4: Budget compliance
-        if let Some(budget) = policy.budget_per_second {
-            if let Some(max) = self.config.max_budget {
-                if budget > max {
-                    violations.push(Violation::critical(
-                        "budget-compliance",
-                        format!("Policy budget {budget} exceeds maximum {max}"),
-                    ));
-                } else {
-                    checks_passed += 1;
-                }
-            } else {
-                checks_passed += 1;
-            }
-        } else {
-            checks_passed += 1;
+        for warning in warnings {
+            result.add_warning(warning);
         }
 
-        if violations.is_empty() {
-            Ok(ProverResult::approved(checks_passed))
-        } else {
-            Ok(ProverResult::rejected(
-                violations,
-                checks_passed,
-                checks_total,
-            ))
-        }
+        Ok(result)
     }
 
     /// Simulates a policy against a traffic pattern.
@@ -281,6 +386,175 @@ impl Prover {
         let config = crate::replay::ReplayConfig::new().with_budget(budget_per_second);
         self.replay_corpus(policy, corpus, config)
     }
+
+    /// Replays corpus traces in timestamp order, paced to wall-clock time
+    /// per `config.speed`, invoking `sink` with each trace and whether
+    /// the policy would keep it.
+    ///
+    /// `clock` is injected rather than read from an ambient source, so
+    /// callers can drive a real-time demo against [`crate::clock::SystemClock`]
+    /// and tests can drive the same code instantly against
+    /// [`crate::clock::MockClock`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the corpus is empty or has no timestamps.
+    pub fn replay_corpus_paced<C: Clock>(
+        &self,
+        policy: &Policy,
+        corpus: &Corpus,
+        config: crate::replay::ReplayConfig,
+        clock: &C,
+        sink: impl FnMut(&Trace, bool),
+    ) -> Result<()> {
+        let replayer = crate::replay::Replayer::new(config);
+        replayer.replay_paced(policy, corpus, clock, sink)
+    }
+
+    /// Loads every policy `adapter` can see and runs [`Self::analyze`]
+    /// on each, paired with the name it should be saved back under.
+    ///
+    /// This is what powers "verify every policy in this directory"
+    /// workflows: point a [`crate::adapter::DirectoryAdapter`] at a repo
+    /// of policies and get back one [`AnalysisResult`] per file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the adapter fails to load, a policy fails to
+    /// parse, or any individual `analyze` call fails.
+    pub fn analyze_from_adapter(
+        &self,
+        adapter: &dyn Adapter,
+        corpus: &Corpus,
+        traffic: Option<&TrafficPattern>,
+    ) -> Result<Vec<(String, AnalysisResult)>> {
+        adapter
+            .load()?
+            .into_iter()
+            .map(|loaded| {
+                let result = self.analyze(&loaded.policy, corpus, traffic)?;
+                Ok((loaded.name, result))
+            })
+            .collect()
+    }
+
+    /// Applies `fixes` (e.g. [`ProverResult::fixes`]) to `policy` in
+    /// place and writes the corrected policy back through `adapter`
+    /// under `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `adapter` doesn't support saving, or the
+    /// write itself fails.
+    pub fn apply_fixes_via_adapter(
+        &self,
+        adapter: &dyn Adapter,
+        name: &str,
+        policy: &mut Policy,
+        fixes: &[crate::result::PolicyPatch],
+    ) -> Result<()> {
+        for fix in fixes {
+            fix.apply(policy);
+        }
+        adapter.save(name, policy)
+    }
+
+    /// Watches `policy_path` (and, if given, the traffic CSV at
+    /// `traffic_path`) for changes, invoking `on_result` with a fresh
+    /// [`Self::analyze`] result every time the watched content actually
+    /// changes - the static/dynamic mode and confidence reporting are
+    /// identical to a one-shot `analyze` call, so a policy author gets the
+    /// same verdict they'd get from re-running a CLI invocation, without
+    /// re-running it.
+    ///
+    /// Polls every [`WatchConfig::poll_interval`] and waits out
+    /// [`WatchConfig::debounce`] after a change is first seen, re-checking
+    /// that the files have stopped moving before analyzing, so several
+    /// quick saves from an editor only trigger one re-analysis. A change
+    /// whose content is byte-identical to the last analyzed content (e.g.
+    /// a save that only touched a timestamp elsewhere) is skipped.
+    ///
+    /// `clock` is injected rather than read from an ambient source, so
+    /// tests can drive this instantly against [`crate::clock::MockClock`]
+    /// instead of [`crate::clock::SystemClock`]'s real delays. Returns
+    /// once `on_result` returns `false`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `policy_path` cannot be read on the very first
+    /// poll.
+    pub fn watch<C: Clock>(
+        &self,
+        policy_path: impl AsRef<Path>,
+        corpus: &Corpus,
+        traffic_path: Option<impl AsRef<Path>>,
+        config: &WatchConfig,
+        clock: &C,
+        mut on_result: impl FnMut(Result<AnalysisResult>) -> bool,
+    ) -> Result<()> {
+        let policy_path = policy_path.as_ref();
+        let traffic_path = traffic_path.as_ref().map(AsRef::as_ref);
+
+        let mut last_policy = Some(std::fs::read(policy_path)?);
+        let mut last_traffic = traffic_path.and_then(|path| std::fs::read(path).ok());
+        if !on_result(self.analyze_watched(last_policy.as_deref(), last_traffic.as_deref(), corpus))
+        {
+            return Ok(());
+        }
+
+        loop {
+            clock.sleep(config.poll_interval);
+
+            let Ok(policy_bytes) = std::fs::read(policy_path) else {
+                continue;
+            };
+            let traffic_bytes = traffic_path.and_then(|path| std::fs::read(path).ok());
+
+            let unchanged = last_policy.as_deref() == Some(policy_bytes.as_slice())
+                && last_traffic.as_deref() == traffic_bytes.as_deref();
+            if unchanged {
+                continue;
+            }
+
+            clock.sleep(config.debounce);
+
+            let settled_policy =
+                std::fs::read(policy_path).unwrap_or_else(|_| policy_bytes.clone());
+            let settled_traffic = traffic_path.and_then(|path| std::fs::read(path).ok());
+            if settled_policy != policy_bytes
+                || settled_traffic.as_deref() != traffic_bytes.as_deref()
+            {
+                // Still being written - wait for the next poll to settle.
+                continue;
+            }
+
+            last_policy = Some(settled_policy);
+            last_traffic = settled_traffic;
+            if !on_result(self.analyze_watched(
+                last_policy.as_deref(),
+                last_traffic.as_deref(),
+                corpus,
+            )) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Parses `policy_bytes`/`traffic_bytes` (as read by [`Self::watch`])
+    /// and runs [`Self::analyze`] over them.
+    fn analyze_watched(
+        &self,
+        policy_bytes: Option<&[u8]>,
+        traffic_bytes: Option<&[u8]>,
+        corpus: &Corpus,
+    ) -> Result<AnalysisResult> {
+        let policy =
+            toon_policy::parse(&String::from_utf8_lossy(policy_bytes.unwrap_or_default()))?;
+        let traffic = traffic_bytes
+            .map(TrafficPattern::from_csv_reader)
+            .transpose()?;
+        self.analyze(&policy, corpus, traffic.as_ref())
+    }
 }
 
 /// Combined result from mode-aware analysis.
@@ -326,23 +600,133 @@ impl AnalysisResult {
     pub fn all_passed(&self) -> bool {
         self.is_approved() && self.static_passed() && self.simulation_compliant()
     }
+
+    /// Returns a continuous `[0, 1]` confidence score, blending how
+    /// complete static coverage is with how much budget margin dynamic
+    /// simulation left - a finer-grained alternative to [`Self::confidence`]
+    /// for ranking policies or gating on a numeric threshold (e.g. "reject
+    /// below 0.9") rather than only the three discrete tiers.
+    ///
+    /// A component whose analysis didn't run contributes zero rather than
+    /// being skipped, so a policy that was never simulated can't reach a
+    /// high score on static coverage alone.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn confidence_score(&self) -> f32 {
+        const STATIC_WEIGHT: f32 = 0.3;
+        const SIMULATION_WEIGHT: f32 = 0.7;
+
+        let static_component = self.static_result.as_ref().map_or(0.0, |result| {
+            (result.coverage.estimated_coverage / 100.0).clamp(0.0, 1.0) as f32
+        });
+
+        let simulation_component = self.simulation_result.as_ref().map_or(0.0, |result| {
+            let budget = result.summary.budget_limit;
+            if budget > 0.0 {
+                (1.0 - result.summary.peak_kept_eps / budget).clamp(0.0, 1.0) as f32
+            } else {
+                0.0
+            }
+        });
+
+        static_component * STATIC_WEIGHT + simulation_component * SIMULATION_WEIGHT
+    }
+
+    /// Renders `policy`'s rule-evaluation order as a Graphviz DOT digraph:
+    /// one node per rule (id, condition, action, priority), edges
+    /// following fall-through order from the highest-priority rule down
+    /// to the fallback. Nodes are colored by this analysis's outcome -
+    /// grey for a rule static analysis found shadowed or simulation never
+    /// exercised, red for a rule flagged as overlapping or contradicting
+    /// another, green otherwise - so piping the output into `dot -Tsvg`
+    /// shows at a glance why a policy was approved or rejected and where
+    /// coverage gaps or dead rules sit in the ordering.
+    #[must_use]
+    pub fn to_dot(&self, policy: &Policy) -> String {
+        let mut grey = std::collections::BTreeSet::new();
+        let mut red = std::collections::BTreeSet::new();
+
+        if let Some(static_result) = &self.static_result {
+            for conflict in &static_result.conflicts {
+                match conflict.conflict_type {
+                    ConflictType::Shadowed => {
+                        grey.insert(conflict.rule_b.clone());
+                    }
+                    ConflictType::Overlapping | ConflictType::Contradictory => {
+                        red.insert(conflict.rule_a.clone());
+                        red.insert(conflict.rule_b.clone());
+                    }
+                }
+            }
+        }
+        if let Some(simulation_result) = &self.simulation_result {
+            for dead in simulation_result.rule_coverage.dead_rules() {
+                grey.insert(dead.rule_name.clone());
+            }
+        }
+
+        let mut out = String::from("digraph policy {\n");
+        let mut previous_id: Option<String> = None;
+        for rule in &policy.rules {
+            let id = dot_sanitize_id(&rule.name);
+            let color = if red.contains(&rule.name) {
+                "red"
+            } else if grey.contains(&rule.name) {
+                "grey"
+            } else {
+                "green"
+            };
+            out.push_str(&format!(
+                "  \"{id}\" [label=\"{}\\n{}\\n{:?}\\npriority {}\", color={color}];\n",
+                dot_escape(&rule.name),
+                dot_escape(&rule.match_expr),
+                rule.action,
+                rule.priority,
+            ));
+            if let Some(previous_id) = previous_id {
+                out.push_str(&format!("  \"{previous_id}\" -> \"{id}\";\n"));
+            }
+            previous_id = Some(id);
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Sanitizes a rule name into a DOT node identifier.
+fn dot_sanitize_id(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Escapes a string for use inside a DOT quoted label.
+fn dot_escape(s: &str) -> String {
+    s.replace('"', "\\\"")
 }
 
 /// Determines confidence level based on analysis results.
-const fn determine_confidence(
+///
+/// Dynamic simulation only earns `High` confidence when it actually
+/// exercised every non-fallback rule: a rule the static analyzer thought
+/// reachable but that simulation never hit stays at `Medium`, since the
+/// budget-compliance result can't vouch for a rule it never ran.
+fn determine_confidence(
     static_result: Option<&StaticAnalysisResult>,
     simulation_result: Option<&SimulationResult>,
 ) -> Confidence {
     // Dynamic simulation gives highest confidence
     if let Some(sim) = simulation_result {
-        if sim.is_compliant() {
+        if sim.is_compliant() && sim.rule_coverage.fully_exercised() {
             return Confidence::High;
         }
     }
 
-    // Static analysis gives medium confidence
+    // Static analysis gives medium confidence - unless it found a dead
+    // rule, in which case the policy isn't behaving as its rule order
+    // suggests and we can't vouch for it as confidently.
     if let Some(static_res) = static_result {
-        if static_res.passed {
+        if static_res.passed && !static_res.has_dead_rules() {
             return Confidence::Medium;
         }
     }
@@ -394,6 +778,52 @@ mod tests {
         assert!(result.is_rejected());
     }
 
+    #[test]
+    fn verify_suggests_a_fix_for_the_missing_fallback() {
+        let prover = Prover::default();
+        let mut policy = Policy::new("no-fallback");
+        policy.add_rule(Rule::new("errors", "error", Action::Keep, 100));
+        let corpus = Corpus::new();
+
+        let result = prover.verify(&policy, &corpus).unwrap();
+        assert_eq!(result.fixes.len(), 1);
+        assert!(matches!(
+            &result.fixes[0],
+            crate::result::PolicyPatch::AddRule(rule) if rule.match_expr == "true"
+        ));
+    }
+
+    #[derive(Debug, Default)]
+    struct AlwaysFailsCheck;
+
+    impl crate::checks::Check for AlwaysFailsCheck {
+        fn id(&self) -> &str {
+            "always-fails"
+        }
+
+        fn run(
+            &self,
+            _policy: &Policy,
+            _corpus: &Corpus,
+            _config: &ProverConfig,
+        ) -> Vec<crate::result::Violation> {
+            vec![crate::result::Violation::critical(self.id(), "this check always fails")]
+        }
+    }
+
+    #[test]
+    fn register_check_runs_alongside_the_built_ins() {
+        let mut prover = Prover::default();
+        prover.register_check(AlwaysFailsCheck);
+        let policy = valid_policy();
+        let corpus = Corpus::new();
+
+        let result = prover.verify(&policy, &corpus).unwrap();
+        assert!(result.is_rejected());
+        assert!(result.violations.iter().any(|v| v.check == "always-fails"));
+        assert_eq!(result.checks_total, 8);
+    }
+
     #[test]
     fn analyze_static_mode() {
         let config = ProverConfig {
@@ -412,6 +842,25 @@ mod tests {
         assert_eq!(result.confidence, Confidence::Medium);
     }
 
+    #[test]
+    fn analyze_static_mode_downgrades_confidence_for_a_dead_rule() {
+        let config = ProverConfig {
+            analysis_mode: AnalysisMode::Static,
+            ..Default::default()
+        };
+        let prover = Prover::new(config);
+        let mut policy = valid_policy();
+        // Shadowed by the existing "fallback" (true) rule above it.
+        policy.add_rule(Rule::new("unreachable", "status >= 500", Action::Drop, 0));
+        let corpus = Corpus::new();
+
+        let result = prover.analyze(&policy, &corpus, None).unwrap();
+
+        let static_result = result.static_result.as_ref().unwrap();
+        assert!(static_result.has_dead_rules());
+        assert_eq!(result.confidence, Confidence::Low);
+    }
+
     #[test]
     fn analyze_dynamic_mode_without_traffic() {
         let config = ProverConfig {
@@ -450,6 +899,18 @@ mod tests {
         assert_eq!(result.confidence, Confidence::Medium);
     }
 
+    /// A policy with a rule on a field interval-based static reasoning
+    /// can't model (`is_error`, not the well-known `error` field), so
+    /// `Auto` mode always has something left to escalate to dynamic
+    /// simulation. `is_error_condition`'s substring heuristic still
+    /// recognizes it, so simulation can credit it with hits.
+    fn unresolvable_rule_policy() -> Policy {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("keep-errors", "is_error == true", Action::Keep, 100));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+        policy
+    }
+
     #[test]
     fn analyze_with_traffic_gives_high_confidence() {
         use chrono::{TimeZone, Utc};
@@ -461,13 +922,15 @@ mod tests {
             ..Default::default()
         };
         let prover = Prover::new(config);
-        let policy = valid_policy();
+        let policy = unresolvable_rule_policy();
         let corpus = Corpus::new();
 
         let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // Non-zero error rate so the "keep-errors" rule is actually
+        // exercised by the simulation, not just the fallback.
         let traffic = TrafficPattern::from_points(vec![
-            TrafficPoint::new(base, 5000.0),
-            TrafficPoint::new(base + chrono::Duration::minutes(1), 6000.0),
+            TrafficPoint::new(base, 5000.0).with_error_rate(0.01),
+            TrafficPoint::new(base + chrono::Duration::minutes(1), 6000.0).with_error_rate(0.01),
         ]);
 
         let result = prover.analyze(&policy, &corpus, Some(&traffic)).unwrap();
@@ -476,9 +939,101 @@ mod tests {
         assert!(result.static_result.is_some());
         assert!(result.simulation_result.is_some());
         assert!(result.simulation_compliant());
+        assert!(result
+            .simulation_result
+            .as_ref()
+            .unwrap()
+            .rule_coverage
+            .fully_exercised());
         assert_eq!(result.confidence, Confidence::High);
     }
 
+    #[test]
+    fn analyze_with_traffic_stays_medium_when_a_rule_never_fires() {
+        use chrono::{TimeZone, Utc};
+        use crate::traffic::{TrafficPattern, TrafficPoint};
+
+        let config = ProverConfig {
+            analysis_mode: AnalysisMode::Auto,
+            max_budget: Some(100_000),
+            ..Default::default()
+        };
+        let prover = Prover::new(config);
+        let policy = unresolvable_rule_policy();
+        let corpus = Corpus::new();
+
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // No errors in the traffic, so "keep-errors" never fires.
+        let traffic = TrafficPattern::from_points(vec![TrafficPoint::new(base, 5000.0)]);
+
+        let result = prover.analyze(&policy, &corpus, Some(&traffic)).unwrap();
+
+        assert!(result.simulation_compliant());
+        assert_eq!(result.confidence, Confidence::Medium);
+        let static_result = result.static_result.unwrap();
+        assert!(static_result
+            .warnings
+            .iter()
+            .any(|w| w.rule_name == "keep-errors" && w.message.contains("never fired")));
+    }
+
+    #[test]
+    fn analyze_auto_mode_skips_simulation_when_statically_resolved() {
+        use chrono::{TimeZone, Utc};
+        use crate::traffic::{TrafficPattern, TrafficPoint};
+
+        let config = ProverConfig {
+            analysis_mode: AnalysisMode::Auto,
+            max_budget: Some(100_000),
+            ..Default::default()
+        };
+        let prover = Prover::new(config);
+        // "status >= 500" is fully resolvable by interval-based static
+        // reasoning, so there's nothing left for simulation to prove.
+        let policy = valid_policy();
+        let corpus = Corpus::new();
+
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let traffic = TrafficPattern::from_points(vec![
+            TrafficPoint::new(base, 5000.0).with_error_rate(0.01),
+        ]);
+
+        let result = prover.analyze(&policy, &corpus, Some(&traffic)).unwrap();
+
+        assert!(result.static_result.as_ref().unwrap().is_fully_resolved());
+        assert!(result.simulation_result.is_none());
+        assert_eq!(result.confidence, Confidence::Medium);
+    }
+
+    #[test]
+    fn analyze_auto_mode_escalates_when_statically_unresolved() {
+        use chrono::{TimeZone, Utc};
+        use crate::traffic::{TrafficPattern, TrafficPoint};
+
+        let config = ProverConfig {
+            analysis_mode: AnalysisMode::Auto,
+            max_budget: Some(100_000),
+            ..Default::default()
+        };
+        let prover = Prover::new(config);
+        let policy = unresolvable_rule_policy();
+        let corpus = Corpus::new();
+
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let traffic = TrafficPattern::from_points(vec![
+            TrafficPoint::new(base, 5000.0).with_error_rate(0.01),
+        ]);
+
+        let result = prover.analyze(&policy, &corpus, Some(&traffic)).unwrap();
+
+        assert!(!result
+            .static_result
+            .as_ref()
+            .unwrap()
+            .is_fully_resolved());
+        assert!(result.simulation_result.is_some());
+    }
+
     #[test]
     fn analyze_all_passed() {
         let prover = Prover::default();
@@ -501,4 +1056,242 @@ mod tests {
         assert!(result.coverage.has_fallback);
         assert!(result.coverage.has_error_handling);
     }
+
+    #[test]
+    fn analyze_from_adapter_runs_every_loaded_policy() {
+        use crate::adapter::MemoryAdapter;
+
+        let adapter = MemoryAdapter::new()
+            .with_policy("good", valid_policy())
+            .with_policy("also-good", valid_policy());
+        let prover = Prover::default();
+        let corpus = Corpus::new();
+
+        let results = prover
+            .analyze_from_adapter(&adapter, &corpus, None)
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, result)| result.all_passed()));
+    }
+
+    #[test]
+    fn apply_fixes_via_adapter_writes_the_corrected_policy_back() {
+        use crate::adapter::MemoryAdapter;
+        use crate::result::PolicyPatch;
+        use toon_policy::Rule;
+
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("keep-errors", "status >= 500", Action::Keep, 100));
+        let adapter = MemoryAdapter::new().with_policy("missing-fallback", policy.clone());
+        let prover = Prover::default();
+
+        let fixes = vec![PolicyPatch::AddRule(Rule::new(
+            "fallback",
+            "true",
+            Action::Sample(0.01),
+            0,
+        ))];
+        prover
+            .apply_fixes_via_adapter(&adapter, "missing-fallback", &mut policy, &fixes)
+            .unwrap();
+
+        assert!(policy.has_fallback());
+        let reloaded = adapter.load().unwrap();
+        assert!(reloaded[0].policy.has_fallback());
+    }
+
+    fn watch_test_dir(tag: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "nectar-watch-test-{tag}-{:?}",
+            std::thread::current().id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn watch_reanalyzes_when_the_policy_file_changes_and_stops_on_false() {
+        use crate::clock::MockClock;
+
+        let dir = watch_test_dir("reanalyze");
+        let path = dir.join("policy.toon");
+        std::fs::write(&path, toon_policy::serialize(&valid_policy())).unwrap();
+
+        let prover = Prover::default();
+        let corpus = Corpus::new();
+        let clock = MockClock::new();
+        let mut approvals = Vec::new();
+
+        prover
+            .watch(
+                &path,
+                &corpus,
+                None::<&std::path::Path>,
+                &WatchConfig::default(),
+                &clock,
+                |result| {
+                    approvals.push(result.unwrap().prover_result.is_approved());
+                    if approvals.len() == 1 {
+                        let mut no_fallback = Policy::new("no-fallback");
+                        no_fallback.add_rule(Rule::new("errors", "error", Action::Keep, 100));
+                        std::fs::write(&path, toon_policy::serialize(&no_fallback)).unwrap();
+                        true
+                    } else {
+                        false
+                    }
+                },
+            )
+            .unwrap();
+
+        assert_eq!(approvals, vec![true, false]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn watch_surfaces_a_parse_error_when_the_file_becomes_unparseable() {
+        use crate::clock::MockClock;
+
+        let dir = watch_test_dir("parse-error");
+        let path = dir.join("policy.toon");
+        std::fs::write(&path, toon_policy::serialize(&valid_policy())).unwrap();
+
+        let prover = Prover::default();
+        let corpus = Corpus::new();
+        let clock = MockClock::new();
+        let mut calls = 0;
+
+        prover
+            .watch(
+                &path,
+                &corpus,
+                None::<&std::path::Path>,
+                &WatchConfig::default(),
+                &clock,
+                |result| {
+                    calls += 1;
+                    if calls == 1 {
+                        assert!(result.unwrap().prover_result.is_approved());
+                        std::fs::write(&path, b"not a valid policy {{{").unwrap();
+                        true
+                    } else {
+                        assert!(result.is_err());
+                        false
+                    }
+                },
+            )
+            .unwrap();
+
+        assert_eq!(calls, 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn confidence_score_is_zero_when_neither_analysis_ran() {
+        let result = AnalysisResult {
+            mode: AnalysisMode::Static,
+            prover_result: ProverResult::approved(1),
+            static_result: None,
+            simulation_result: None,
+            confidence: Confidence::Low,
+        };
+
+        assert!((result.confidence_score()).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn confidence_score_rewards_high_coverage_and_simulation_margin() {
+        use crate::traffic::{TrafficPattern, TrafficPoint};
+        use chrono::{TimeZone, Utc};
+
+        let config = ProverConfig {
+            analysis_mode: AnalysisMode::Auto,
+            max_budget: Some(100_000),
+            ..Default::default()
+        };
+        let prover = Prover::new(config);
+        let policy = unresolvable_rule_policy();
+        let corpus = Corpus::new();
+
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let traffic = TrafficPattern::from_points(vec![
+            TrafficPoint::new(base, 100.0),
+            TrafficPoint::new(base + chrono::Duration::minutes(1), 100.0),
+        ]);
+
+        let result = prover.analyze(&policy, &corpus, Some(&traffic)).unwrap();
+
+        // Full coverage, deep within budget - the blended score should sit
+        // close to the top of the [0, 1] range.
+        assert!(result.confidence_score() > 0.9);
+    }
+
+    #[test]
+    fn confidence_score_drops_when_simulation_exceeds_budget() {
+        use crate::traffic::{TrafficPattern, TrafficPoint};
+        use chrono::{TimeZone, Utc};
+
+        let config = ProverConfig {
+            analysis_mode: AnalysisMode::Auto,
+            max_budget: Some(100),
+            ..Default::default()
+        };
+        let prover = Prover::new(config);
+        let policy = unresolvable_rule_policy();
+        let corpus = Corpus::new();
+
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let traffic = TrafficPattern::from_points(vec![
+            TrafficPoint::new(base, 100_000.0),
+            TrafficPoint::new(base + chrono::Duration::minutes(1), 100_000.0),
+        ]);
+
+        let result = prover.analyze(&policy, &corpus, Some(&traffic)).unwrap();
+
+        assert!(!result.simulation_compliant());
+        assert!(result.confidence_score() < 0.4);
+    }
+
+    #[test]
+    fn to_dot_renders_one_node_per_rule_in_priority_order() {
+        let config = ProverConfig {
+            analysis_mode: AnalysisMode::Static,
+            ..Default::default()
+        };
+        let prover = Prover::new(config);
+        let policy = valid_policy();
+        let corpus = Corpus::new();
+
+        let result = prover.analyze(&policy, &corpus, None).unwrap();
+        let dot = result.to_dot(&policy);
+
+        assert!(dot.starts_with("digraph policy {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("\"keep_errors\""));
+        assert!(dot.contains("\"fallback\""));
+        assert!(dot.contains("keep_errors\" -> \"fallback\""));
+        assert!(dot.contains("color=green"));
+    }
+
+    #[test]
+    fn to_dot_colors_a_shadowed_rule_grey() {
+        let config = ProverConfig {
+            analysis_mode: AnalysisMode::Static,
+            ..Default::default()
+        };
+        let prover = Prover::new(config);
+        let mut policy = valid_policy();
+        // Shadowed by the existing "fallback" (true) rule above it.
+        policy.add_rule(Rule::new("unreachable", "status >= 500", Action::Drop, 0));
+        let corpus = Corpus::new();
+
+        let result = prover.analyze(&policy, &corpus, None).unwrap();
+        let dot = result.to_dot(&policy);
+
+        let unreachable_line = dot
+            .lines()
+            .find(|line| line.contains("\"unreachable\" ["))
+            .unwrap();
+        assert!(unreachable_line.contains("color=grey"));
+    }
 }
@@ -3,9 +3,12 @@
 //! Replays traces in timestamp order to simulate real traffic flow,
 //! enabling validation of time-based rules and budget compliance.
 
+use crate::clock::Clock;
 use crate::error::{Error, Result};
+use crate::histogram::LatencyHistogram;
 use nectar_corpus::{Corpus, Trace};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::time::Duration;
 use toon_policy::Policy;
 
@@ -113,6 +116,78 @@ impl Default for TimeWindow {
     }
 }
 
+/// Budget enforcement strategy simulated during replay.
+///
+/// `budget_per_second` alone only flags windows as `exceeds_budget` after
+/// the fact; setting an enforcement mode additionally runs traces that
+/// pass policy through a simulated rate limiter and sheds the ones that
+/// don't fit, so a window's `shed_count`/`effective_throughput` show what
+/// a real-world limiter would actually pass through.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BudgetEnforcement {
+    /// Token bucket: capacity equals `budget_per_second`, so idle time
+    /// lets the bucket fill and a burst up to a full second of budget can
+    /// pass through at once.
+    TokenBucket,
+    /// Leaky bucket: capacity is a single token, so traces are admitted
+    /// no faster than the refill rate regardless of how long the bucket
+    /// sat idle beforehand - smooth rather than bursty.
+    LeakyBucket,
+}
+
+impl BudgetEnforcement {
+    /// Returns the bucket capacity (in tokens) for this mode.
+    const fn capacity(self, budget_per_second: f64) -> f64 {
+        match self {
+            Self::TokenBucket => budget_per_second,
+            Self::LeakyBucket => 1.0,
+        }
+    }
+}
+
+/// A simulated rate limiter used by budget enforcement.
+///
+/// Tracks `tokens` against `capacity`, refilling at `rate` tokens/sec as
+/// trace timestamps advance. A trace consumes one token if available, or
+/// is shed.
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    capacity: f64,
+    rate: f64,
+    tokens: f64,
+    last_ns: Option<u64>,
+}
+
+impl TokenBucket {
+    fn new(mode: BudgetEnforcement, budget_per_second: f64) -> Self {
+        let capacity = mode.capacity(budget_per_second);
+        Self {
+            capacity,
+            rate: budget_per_second,
+            tokens: capacity,
+            last_ns: None,
+        }
+    }
+
+    /// Advances the bucket to `timestamp_ns` and attempts to consume one
+    /// token; returns `true` if the trace is admitted, `false` if shed.
+    #[allow(clippy::cast_precision_loss)]
+    fn admit(&mut self, timestamp_ns: u64) -> bool {
+        if let Some(last) = self.last_ns {
+            let delta_s = timestamp_ns.saturating_sub(last) as f64 / 1_000_000_000.0;
+            self.tokens = (self.tokens + delta_s * self.rate).min(self.capacity);
+        }
+        self.last_ns = Some(timestamp_ns);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
 /// Configuration for replay.
 #[derive(Debug, Clone, Default)]
 pub struct ReplayConfig {
@@ -122,6 +197,10 @@ pub struct ReplayConfig {
     pub window: TimeWindow,
     /// Budget limit (events per second).
     pub budget_per_second: Option<f64>,
+    /// Budget enforcement mode. `None` (default) only reports violations,
+    /// as before; `Some` additionally sheds over-budget traces via a
+    /// simulated rate limiter.
+    pub enforcement: Option<BudgetEnforcement>,
 }
 
 impl ReplayConfig {
@@ -132,6 +211,7 @@ impl ReplayConfig {
             speed: ReplaySpeed::Max,
             window: TimeWindow::one_second(),
             budget_per_second: None,
+            enforcement: None,
         }
     }
 
@@ -155,6 +235,14 @@ impl ReplayConfig {
         self.budget_per_second = Some(budget_per_second);
         self
     }
+
+    /// Sets the budget enforcement mode. Only takes effect if
+    /// `budget_per_second` is also set.
+    #[must_use]
+    pub const fn with_enforcement(mut self, enforcement: BudgetEnforcement) -> Self {
+        self.enforcement = Some(enforcement);
+        self
+    }
 }
 
 /// A single window of aggregated replay data.
@@ -174,18 +262,40 @@ pub struct ReplayWindow {
     pub kept_count: usize,
     /// Traces dropped after policy application.
     pub dropped_count: usize,
-    /// Effective throughput (traces per second).
+    /// Traces that passed policy but were shed by budget enforcement -
+    /// distinct from `dropped_count`, which policy itself dropped.
+    pub shed_count: usize,
+    /// Raw throughput (kept traces per second), ignoring enforcement.
     pub throughput: f64,
+    /// Throughput after budget enforcement:
+    /// `(kept_count - shed_count) / window_seconds`. Equal to
+    /// `throughput` when enforcement is disabled.
+    pub effective_throughput: f64,
     /// Whether this window exceeds budget.
     pub exceeds_budget: bool,
     /// Amount over budget (if exceeding).
     pub over_budget_by: f64,
+    /// Median (p50) trace latency in this window, in nanoseconds.
+    pub p50_latency_ns: u64,
+    /// p90 trace latency in this window, in nanoseconds.
+    pub p90_latency_ns: u64,
+    /// p99 trace latency in this window, in nanoseconds.
+    pub p99_latency_ns: u64,
+    /// p999 trace latency in this window, in nanoseconds.
+    pub p999_latency_ns: u64,
+    /// Latency histogram backing the percentiles above.
+    ///
+    /// Not part of the window's serialized form - it exists to be merged
+    /// into the overall replay's latency histogram in
+    /// [`Replayer::calculate_summary`].
+    #[serde(skip)]
+    pub latency_histogram: LatencyHistogram,
 }
 
 impl ReplayWindow {
     /// Creates a new empty window.
     #[must_use]
-    pub const fn new(index: u64, window_duration_ns: u64) -> Self {
+    pub fn new(index: u64, window_duration_ns: u64) -> Self {
         let start_ns = index * window_duration_ns;
         let end_ns = start_ns + window_duration_ns;
         Self {
@@ -196,9 +306,16 @@ impl ReplayWindow {
             error_count: 0,
             kept_count: 0,
             dropped_count: 0,
+            shed_count: 0,
             throughput: 0.0,
+            effective_throughput: 0.0,
             exceeds_budget: false,
             over_budget_by: 0.0,
+            p50_latency_ns: 0,
+            p90_latency_ns: 0,
+            p99_latency_ns: 0,
+            p999_latency_ns: 0,
+            latency_histogram: LatencyHistogram::new(),
         }
     }
 
@@ -209,6 +326,15 @@ impl ReplayWindow {
         self.throughput = self.kept_count as f64 / window_seconds;
     }
 
+    /// Calculates effective (post-enforcement) throughput based on window
+    /// duration.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn calculate_effective_throughput(&mut self, window_duration_ns: u64) {
+        let window_seconds = window_duration_ns as f64 / 1_000_000_000.0;
+        self.effective_throughput = self.kept_count.saturating_sub(self.shed_count) as f64
+            / window_seconds;
+    }
+
     /// Checks if this window exceeds budget.
     pub fn check_budget(&mut self, budget_per_second: Option<f64>) {
         if let Some(budget) = budget_per_second {
@@ -218,6 +344,66 @@ impl ReplayWindow {
             }
         }
     }
+
+    /// Reads the window's percentile fields from its latency histogram.
+    pub fn calculate_latency_percentiles(&mut self) {
+        self.p50_latency_ns = self.latency_histogram.p50();
+        self.p90_latency_ns = self.latency_histogram.p90();
+        self.p99_latency_ns = self.latency_histogram.p99();
+        self.p999_latency_ns = self.latency_histogram.p999();
+    }
+
+    /// Renders this window as a single InfluxDB line-protocol line.
+    ///
+    /// `measurement` is the measurement name (e.g. `"nektor_replay"`) and
+    /// `tags` are static tags supplied by the caller (e.g. policy name,
+    /// corpus id) in addition to the window's own fields. `timestamp_ns`
+    /// (the window's `start_ns`) is rendered at `precision`.
+    #[must_use]
+    pub fn to_line_protocol(
+        &self,
+        measurement: &str,
+        tags: &[(&str, &str)],
+        precision: LineProtocolPrecision,
+    ) -> String {
+        let mut line = escape_measurement(measurement);
+
+        for (key, value) in tags {
+            line.push(',');
+            line.push_str(&escape_tag(key));
+            line.push('=');
+            line.push_str(&escape_tag(value));
+        }
+
+        line.push(' ');
+        line.push_str(&format!(
+            "trace_count={}i,kept={}i,dropped={}i,error_count={}i,throughput={},exceeds_budget={}",
+            self.trace_count,
+            self.kept_count,
+            self.dropped_count,
+            self.error_count,
+            self.throughput,
+            self.exceeds_budget,
+        ));
+        line.push(' ');
+        line.push_str(&precision.convert(self.start_ns).to_string());
+
+        line
+    }
+}
+
+/// Escapes an InfluxDB line-protocol measurement name (commas and spaces).
+fn escape_measurement(value: &str) -> String {
+    value.replace(',', "\\,").replace(' ', "\\ ")
+}
+
+/// Escapes an InfluxDB line-protocol tag key or value (commas, spaces, and
+/// equals signs).
+fn escape_tag(value: &str) -> String {
+    value
+        .replace(',', "\\,")
+        .replace(' ', "\\ ")
+        .replace('=', "\\=")
 }
 
 /// Result of replaying a corpus.
@@ -233,6 +419,9 @@ pub struct ReplayResult {
     pub total_dropped: usize,
     /// Total error traces.
     pub total_errors: usize,
+    /// Total traces shed by budget enforcement (distinct from
+    /// `total_dropped`, which policy itself dropped).
+    pub total_shed: usize,
     /// Per-window results.
     pub windows: Vec<ReplayWindow>,
     /// Windows that exceeded budget.
@@ -264,6 +453,22 @@ impl ReplayResult {
             .map(|w| w.throughput)
             .fold(0.0, f64::max)
     }
+
+    /// Renders every window as InfluxDB line protocol, one line per window,
+    /// newline-separated, ready to ship to a time-series backend.
+    #[must_use]
+    pub fn to_line_protocol(
+        &self,
+        measurement: &str,
+        tags: &[(&str, &str)],
+        precision: LineProtocolPrecision,
+    ) -> String {
+        self.windows
+            .iter()
+            .map(|w| w.to_line_protocol(measurement, tags, precision))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 /// Summary statistics for replay.
@@ -287,6 +492,175 @@ pub struct ReplaySummary {
     pub windows_over_budget: usize,
     /// Percentage of time over budget.
     pub percent_time_over_budget: f64,
+    /// Median (p50) trace latency across the whole replay, in nanoseconds.
+    pub p50_latency_ns: u64,
+    /// p90 trace latency across the whole replay, in nanoseconds.
+    pub p90_latency_ns: u64,
+    /// p99 trace latency across the whole replay, in nanoseconds.
+    pub p99_latency_ns: u64,
+    /// p999 trace latency across the whole replay, in nanoseconds.
+    pub p999_latency_ns: u64,
+}
+
+/// Online mean/variance accumulator for window throughput, maintained via
+/// Welford's algorithm.
+///
+/// Avoids the two-pass "sum then sum-of-squared-deviations" approach,
+/// which needs every throughput sample in memory at once - this only
+/// needs `count`, `mean`, and `m2`, so [`Replayer::replay_streaming`] can
+/// fold windows in as they close instead of buffering them.
+#[derive(Debug, Clone, Copy, Default)]
+struct WelfordStats {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl WelfordStats {
+    /// Folds in one more sample.
+    #[allow(clippy::cast_precision_loss)]
+    fn update(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        let delta2 = value - self.mean;
+        self.m2 += delta * delta2;
+    }
+
+    /// Returns the running mean.
+    fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the running (population) variance.
+    #[allow(clippy::cast_precision_loss)]
+    fn variance(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.m2 / self.count as f64
+        }
+    }
+
+    /// Returns the running standard deviation.
+    fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+/// Running state for [`Replayer::replay_streaming`]: the totals and
+/// Welford accumulators needed to report a partial [`ReplaySummary`]
+/// after every window, without keeping the windows themselves around.
+#[derive(Debug)]
+struct StreamAccumulator {
+    windows_processed: usize,
+    total_kept: usize,
+    total_dropped: usize,
+    total_errors: usize,
+    total_shed: usize,
+    throughput_stats: WelfordStats,
+    peak_throughput: f64,
+    min_throughput: f64,
+    overall_histogram: LatencyHistogram,
+    violations: Vec<ReplayWindow>,
+}
+
+impl StreamAccumulator {
+    fn new() -> Self {
+        Self {
+            windows_processed: 0,
+            total_kept: 0,
+            total_dropped: 0,
+            total_errors: 0,
+            total_shed: 0,
+            throughput_stats: WelfordStats::default(),
+            peak_throughput: 0.0,
+            min_throughput: f64::INFINITY,
+            overall_histogram: LatencyHistogram::new(),
+            violations: Vec::new(),
+        }
+    }
+
+    /// Folds a just-closed window into the running totals and returns the
+    /// partial summary reflecting every window seen so far (including this
+    /// one).
+    fn record(&mut self, window: &ReplayWindow) -> ReplaySummary {
+        self.windows_processed += 1;
+        self.total_kept += window.kept_count;
+        self.total_dropped += window.dropped_count;
+        self.total_errors += window.error_count;
+        self.total_shed += window.shed_count;
+        self.throughput_stats.update(window.throughput);
+        self.peak_throughput = self.peak_throughput.max(window.throughput);
+        self.min_throughput = self.min_throughput.min(window.throughput);
+        self.overall_histogram.merge(&window.latency_histogram);
+        if window.exceeds_budget {
+            self.violations.push(window.clone());
+        }
+        self.partial_summary()
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn partial_summary(&self) -> ReplaySummary {
+        let total = self.total_kept + self.total_dropped;
+        ReplaySummary {
+            avg_throughput: self.throughput_stats.mean(),
+            peak_throughput: self.peak_throughput,
+            min_throughput: if self.min_throughput.is_finite() {
+                self.min_throughput
+            } else {
+                0.0
+            },
+            throughput_std_dev: self.throughput_stats.std_dev(),
+            overall_sample_rate: if total > 0 {
+                self.total_kept as f64 / total as f64
+            } else {
+                0.0
+            },
+            error_rate: if total > 0 {
+                self.total_errors as f64 / total as f64
+            } else {
+                0.0
+            },
+            window_count: self.windows_processed,
+            windows_over_budget: self.violations.len(),
+            percent_time_over_budget: if self.windows_processed > 0 {
+                (self.violations.len() as f64 / self.windows_processed as f64) * 100.0
+            } else {
+                0.0
+            },
+            p50_latency_ns: self.overall_histogram.p50(),
+            p90_latency_ns: self.overall_histogram.p90(),
+            p99_latency_ns: self.overall_histogram.p99(),
+            p999_latency_ns: self.overall_histogram.p999(),
+        }
+    }
+}
+
+/// Timestamp precision for InfluxDB line-protocol export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineProtocolPrecision {
+    /// Nanoseconds (line protocol's native precision).
+    #[default]
+    Nanoseconds,
+    /// Microseconds.
+    Microseconds,
+    /// Milliseconds.
+    Milliseconds,
+    /// Seconds.
+    Seconds,
+}
+
+impl LineProtocolPrecision {
+    /// Converts a nanosecond timestamp to this precision.
+    const fn convert(self, ns: u64) -> u64 {
+        match self {
+            Self::Nanoseconds => ns,
+            Self::Microseconds => ns / 1_000,
+            Self::Milliseconds => ns / 1_000_000,
+            Self::Seconds => ns / 1_000_000_000,
+        }
+    }
 }
 
 /// Time range information for replay.
@@ -317,6 +691,127 @@ impl ReplayTimeRange {
     }
 }
 
+/// Result of a streaming replay via [`Replayer::replay_streaming`].
+///
+/// Mirrors [`ReplayResult`] but drops the `windows` buffer: every window
+/// was already handed to the caller's `on_window` callback as it closed,
+/// so keeping a second copy here would defeat the point of streaming.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayStreamResult {
+    /// Whether replay completed successfully.
+    pub success: bool,
+    /// Total traces processed.
+    pub total_traces: usize,
+    /// Total traces kept.
+    pub total_kept: usize,
+    /// Total traces dropped.
+    pub total_dropped: usize,
+    /// Total error traces.
+    pub total_errors: usize,
+    /// Total traces shed by budget enforcement (distinct from
+    /// `total_dropped`, which policy itself dropped).
+    pub total_shed: usize,
+    /// Windows that exceeded budget.
+    pub violations: Vec<ReplayWindow>,
+    /// Summary statistics.
+    pub summary: ReplaySummary,
+    /// Time range of the replay.
+    pub time_range: Option<ReplayTimeRange>,
+}
+
+impl ReplayStreamResult {
+    /// Returns true if no budget violations occurred.
+    #[must_use]
+    pub fn is_compliant(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// A periodic progress snapshot emitted by [`Replayer::replay_streaming`]
+/// every `report_every` windows, the way a load tester prints live
+/// progress mid-run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntervalReport {
+    /// Windows closed so far.
+    pub windows_processed: usize,
+    /// Traces processed so far.
+    pub traces_processed: usize,
+    /// Partial summary computed from the windows seen so far.
+    pub summary: ReplaySummary,
+}
+
+/// Rolling error-ratio state for one [`toon_policy::Action::CircuitBreaker`]
+/// rule: a ring buffer of the last `window` traces' error/non-error
+/// outcomes plus a running error count, so the current ratio is O(1) to
+/// update per trace.
+#[derive(Debug)]
+struct CircuitBreakerState {
+    window: usize,
+    recent: VecDeque<bool>,
+    errors: usize,
+}
+
+impl CircuitBreakerState {
+    fn new(window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            window,
+            recent: VecDeque::with_capacity(window),
+            errors: 0,
+        }
+    }
+
+    /// Records `is_error` against the rolling window and returns whether
+    /// the breaker should be considered open afterward.
+    fn record(&mut self, is_error: bool, failure_threshold: f64, min_samples: usize) -> bool {
+        self.recent.push_back(is_error);
+        if is_error {
+            self.errors += 1;
+        }
+        while self.recent.len() > self.window {
+            if self.recent.pop_front() == Some(true) {
+                self.errors -= 1;
+            }
+        }
+
+        let total = self.recent.len();
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = self.errors as f64 / total as f64;
+        total >= min_samples && ratio >= failure_threshold
+    }
+}
+
+/// Per-rule [`CircuitBreakerState`], keyed by rule name so a policy with
+/// more than one circuit-breaker rule tracks each independently. Built
+/// fresh at the start of each top-level [`Replayer::replay`],
+/// [`Replayer::replay_streaming`], or [`Replayer::replay_paced`] call and
+/// threaded through that call's trace loop in corpus order.
+#[derive(Debug, Default)]
+struct CircuitBreakerStates(HashMap<String, CircuitBreakerState>);
+
+impl CircuitBreakerStates {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `trace`'s outcome against `rule_name`'s rolling window
+    /// (creating it on first use) and returns whether the breaker is open
+    /// after incorporating this trace.
+    fn record(
+        &mut self,
+        rule_name: &str,
+        window: usize,
+        failure_threshold: f64,
+        min_samples: usize,
+        is_error: bool,
+    ) -> bool {
+        self.0
+            .entry(rule_name.to_string())
+            .or_insert_with(|| CircuitBreakerState::new(window))
+            .record(is_error, failure_threshold, min_samples)
+    }
+}
+
 /// Replayer for corpus traces.
 #[derive(Debug, Clone)]
 pub struct Replayer {
@@ -365,7 +860,9 @@ impl Replayer {
         let window_duration_ns = self.config.window.duration_ns();
         for window in &mut windows {
             window.calculate_throughput(window_duration_ns);
+            window.calculate_effective_throughput(window_duration_ns);
             window.check_budget(self.config.budget_per_second);
+            window.calculate_latency_percentiles();
         }
 
         // Collect violations
@@ -383,6 +880,7 @@ impl Replayer {
         let total_kept: usize = windows.iter().map(|w| w.kept_count).sum();
         let total_dropped: usize = windows.iter().map(|w| w.dropped_count).sum();
         let total_errors: usize = windows.iter().map(|w| w.error_count).sum();
+        let total_shed: usize = windows.iter().map(|w| w.shed_count).sum();
 
         Ok(ReplayResult {
             success: true,
@@ -390,6 +888,7 @@ impl Replayer {
             total_kept,
             total_dropped,
             total_errors,
+            total_shed,
             windows,
             violations,
             summary,
@@ -397,6 +896,193 @@ impl Replayer {
         })
     }
 
+    /// Streams a corpus against a policy in timestamp order, pacing
+    /// emission to wall-clock time according to `self.config.speed`.
+    ///
+    /// For each gap between consecutive trace timestamps, sleeps
+    /// `speed.virtual_to_real(delta_ns)` worth of real time (no sleep at
+    /// all under [`ReplaySpeed::Max`], since its multiplier is infinite)
+    /// before invoking `sink` with the trace and whether the policy would
+    /// keep it.
+    ///
+    /// Sleeps are scheduled against *cumulative* target time since the
+    /// start of replay, rather than the gap since the previous trace, so
+    /// a single slow `sink` call or scheduler overrun doesn't compound
+    /// into permanent lag for the rest of the stream - the next sleep is
+    /// simply shortened (or skipped) to make up the difference.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the corpus is empty or has no timestamps.
+    pub fn replay_paced<C: Clock>(
+        &self,
+        policy: &Policy,
+        corpus: &Corpus,
+        clock: &C,
+        mut sink: impl FnMut(&Trace, bool),
+    ) -> Result<()> {
+        if corpus.is_empty() {
+            return Err(Error::InvalidCorpus("corpus is empty".to_string()));
+        }
+
+        let traces = corpus.sorted_by_time();
+        let base_time = traces[0].start_time_ns().unwrap_or(0);
+
+        let start = clock.now();
+        let mut target_elapsed = Duration::ZERO;
+        let mut previous_ts = base_time;
+        let mut breakers = CircuitBreakerStates::new();
+
+        for trace in &traces {
+            let timestamp = trace.start_time_ns().unwrap_or(previous_ts);
+            let delta_ns = timestamp.saturating_sub(previous_ts);
+            previous_ts = timestamp;
+
+            target_elapsed += self.config.speed.virtual_to_real(delta_ns);
+
+            let actual_elapsed = clock.now().saturating_duration_since(start);
+            let remaining = target_elapsed.saturating_sub(actual_elapsed);
+            clock.sleep(remaining);
+
+            sink(trace, self.should_keep_trace(policy, trace, &mut breakers));
+        }
+
+        Ok(())
+    }
+    // NOTE: `trace` above is `&&Trace` (iterating `&traces` where
+    // `traces: Vec<&Trace>`); `should_keep_trace` and `sink` both take
+    // `&Trace`, and auto-deref/reborrowing makes both calls line up.
+
+    /// Streams a corpus against a policy, emitting each [`ReplayWindow`]
+    /// to `on_window` as soon as it closes, and an [`IntervalReport`] to
+    /// `on_interval` every `report_every` windows (`report_every == 0`
+    /// disables interval reports).
+    ///
+    /// Unlike [`Replayer::replay`], only the window currently being
+    /// filled is held in memory - not the full `windows` vec - and
+    /// throughput mean/variance are maintained online via
+    /// [`WelfordStats`] rather than a second pass once replay finishes.
+    /// This bounds memory for multi-hour corpora and lets a caller watch
+    /// progress, or pipe windows to a downstream sink, as replay runs
+    /// instead of blocking until it completes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the corpus is empty or has no timestamps.
+    pub fn replay_streaming(
+        &self,
+        policy: &Policy,
+        corpus: &Corpus,
+        report_every: usize,
+        mut on_window: impl FnMut(&ReplayWindow),
+        mut on_interval: impl FnMut(&IntervalReport),
+    ) -> Result<ReplayStreamResult> {
+        if corpus.is_empty() {
+            return Err(Error::InvalidCorpus("corpus is empty".to_string()));
+        }
+
+        let traces = corpus.sorted_by_time();
+        let time_range = corpus
+            .time_range_ns()
+            .map(|(start, end)| ReplayTimeRange::new(start, end));
+        let base_time = time_range.as_ref().map_or(0, |r| r.start_ns);
+        let window_duration_ns = self.config.window.duration_ns();
+
+        let mut acc = StreamAccumulator::new();
+        let mut current: Option<ReplayWindow> = None;
+        let mut bucket = self.budget_bucket();
+        let mut breakers = CircuitBreakerStates::new();
+
+        for trace in &traces {
+            let timestamp = trace.start_time_ns().unwrap_or(base_time);
+            let relative_time = timestamp.saturating_sub(base_time);
+            let window_index = self.config.window.window_index(relative_time);
+
+            if current.as_ref().is_some_and(|w| w.index != window_index) {
+                let closed = current.take().unwrap();
+                self.emit_closed_window(
+                    closed,
+                    window_duration_ns,
+                    &mut acc,
+                    report_every,
+                    &mut on_window,
+                    &mut on_interval,
+                );
+            }
+
+            let window = current
+                .get_or_insert_with(|| ReplayWindow::new(window_index, window_duration_ns));
+            window.trace_count += 1;
+            window.latency_histogram.record(trace_duration_ns(trace));
+            if trace.is_error {
+                window.error_count += 1;
+            }
+            if self.should_keep_trace(policy, trace, &mut breakers) {
+                window.kept_count += 1;
+                if let Some(bucket) = bucket.as_mut() {
+                    if !bucket.admit(timestamp) {
+                        window.shed_count += 1;
+                    }
+                }
+            } else {
+                window.dropped_count += 1;
+            }
+        }
+
+        if let Some(closed) = current.take() {
+            self.emit_closed_window(
+                closed,
+                window_duration_ns,
+                &mut acc,
+                report_every,
+                &mut on_window,
+                &mut on_interval,
+            );
+        }
+
+        let summary = acc.partial_summary();
+        Ok(ReplayStreamResult {
+            success: true,
+            total_traces: traces.len(),
+            total_kept: acc.total_kept,
+            total_dropped: acc.total_dropped,
+            total_errors: acc.total_errors,
+            total_shed: acc.total_shed,
+            violations: acc.violations,
+            summary,
+            time_range,
+        })
+    }
+
+    /// Finalizes one closed window (throughput/budget/percentiles), folds
+    /// it into the running `acc`umulator, and reports it to the caller's
+    /// callbacks.
+    fn emit_closed_window(
+        &self,
+        mut window: ReplayWindow,
+        window_duration_ns: u64,
+        acc: &mut StreamAccumulator,
+        report_every: usize,
+        on_window: &mut impl FnMut(&ReplayWindow),
+        on_interval: &mut impl FnMut(&IntervalReport),
+    ) {
+        window.calculate_throughput(window_duration_ns);
+        window.calculate_effective_throughput(window_duration_ns);
+        window.check_budget(self.config.budget_per_second);
+        window.calculate_latency_percentiles();
+
+        let summary = acc.record(&window);
+        on_window(&window);
+
+        if report_every != 0 && acc.windows_processed % report_every == 0 {
+            on_interval(&IntervalReport {
+                windows_processed: acc.windows_processed,
+                traces_processed: acc.total_kept + acc.total_dropped,
+                summary,
+            });
+        }
+    }
+
     /// Processes traces into time windows.
     fn process_traces(
         &self,
@@ -407,6 +1093,8 @@ impl Replayer {
         let window_duration = self.config.window.duration_ns();
         let mut windows: std::collections::HashMap<u64, ReplayWindow> =
             std::collections::HashMap::new();
+        let mut bucket = self.budget_bucket();
+        let mut breakers = CircuitBreakerStates::new();
 
         for trace in traces {
             let timestamp = trace.start_time_ns().unwrap_or(base_time);
@@ -418,14 +1106,20 @@ impl Replayer {
                 .or_insert_with(|| ReplayWindow::new(window_index, window_duration));
 
             window.trace_count += 1;
+            window.latency_histogram.record(trace_duration_ns(trace));
 
             if trace.is_error {
                 window.error_count += 1;
             }
 
             // Apply policy to determine if trace is kept
-            if self.should_keep_trace(policy, trace) {
+            if self.should_keep_trace(policy, trace, &mut breakers) {
                 window.kept_count += 1;
+                if let Some(bucket) = bucket.as_mut() {
+                    if !bucket.admit(timestamp) {
+                        window.shed_count += 1;
+                    }
+                }
             } else {
                 window.dropped_count += 1;
             }
@@ -437,9 +1131,22 @@ impl Replayer {
         window_vec
     }
 
+    /// Builds a fresh token-bucket rate limiter if both a budget and an
+    /// enforcement mode are configured.
+    fn budget_bucket(&self) -> Option<TokenBucket> {
+        let budget = self.config.budget_per_second?;
+        let mode = self.config.enforcement?;
+        Some(TokenBucket::new(mode, budget))
+    }
+
     /// Determines if a trace should be kept based on policy.
     #[allow(clippy::unused_self)]
-    fn should_keep_trace(&self, policy: &Policy, trace: &Trace) -> bool {
+    fn should_keep_trace(
+        &self,
+        policy: &Policy,
+        trace: &Trace,
+        breakers: &mut CircuitBreakerStates,
+    ) -> bool {
         // Apply policy rules in priority order
         for rule in &policy.rules {
             if self.matches_rule(&rule.match_expr, trace) {
@@ -453,6 +1160,28 @@ impl Replayer {
                         let normalized = (hash as f64) / (u64::MAX as f64);
                         normalized < rate
                     }
+                    toon_policy::Action::CircuitBreaker {
+                        closed_rate,
+                        open_rate,
+                        window,
+                        failure_threshold,
+                        min_samples,
+                    } => {
+                        let open = breakers.record(
+                            &rule.name,
+                            window,
+                            failure_threshold,
+                            min_samples,
+                            trace.is_error,
+                        );
+                        let rate = if open { open_rate } else { closed_rate };
+
+                        // Deterministic sampling based on trace_id
+                        let hash = simple_hash(&trace.trace_id);
+                        #[allow(clippy::cast_precision_loss)]
+                        let normalized = (hash as f64) / (u64::MAX as f64);
+                        normalized < rate
+                    }
                 };
             }
         }
@@ -504,19 +1233,16 @@ impl Replayer {
             return ReplaySummary::default();
         }
 
-        let throughputs: Vec<f64> = windows.iter().map(|w| w.throughput).collect();
-
-        let avg_throughput = throughputs.iter().sum::<f64>() / throughputs.len() as f64;
-        let peak_throughput = throughputs.iter().copied().fold(0.0, f64::max);
-        let min_throughput = throughputs.iter().copied().fold(f64::INFINITY, f64::min);
-
-        // Calculate standard deviation
-        let variance = throughputs
-            .iter()
-            .map(|&t| (t - avg_throughput).powi(2))
-            .sum::<f64>()
-            / throughputs.len() as f64;
-        let throughput_std_dev = variance.sqrt();
+        let mut throughput_stats = WelfordStats::default();
+        let mut peak_throughput = 0.0_f64;
+        let mut min_throughput = f64::INFINITY;
+        for window in windows {
+            throughput_stats.update(window.throughput);
+            peak_throughput = peak_throughput.max(window.throughput);
+            min_throughput = min_throughput.min(window.throughput);
+        }
+        let avg_throughput = throughput_stats.mean();
+        let throughput_std_dev = throughput_stats.std_dev();
 
         let total_kept: usize = windows.iter().map(|w| w.kept_count).sum();
         let total_errors: usize = windows.iter().map(|w| w.error_count).sum();
@@ -541,6 +1267,13 @@ impl Replayer {
             (windows_over_budget as f64 / windows.len() as f64) * 100.0
         };
 
+        // Roll up per-window histograms into one overall histogram for the
+        // whole-replay percentiles.
+        let mut overall_histogram = LatencyHistogram::new();
+        for window in windows {
+            overall_histogram.merge(&window.latency_histogram);
+        }
+
         ReplaySummary {
             avg_throughput,
             peak_throughput,
@@ -551,6 +1284,10 @@ impl Replayer {
             window_count: windows.len(),
             windows_over_budget,
             percent_time_over_budget,
+            p50_latency_ns: overall_histogram.p50(),
+            p90_latency_ns: overall_histogram.p90(),
+            p99_latency_ns: overall_histogram.p99(),
+            p999_latency_ns: overall_histogram.p999(),
         }
     }
 }
@@ -571,6 +1308,24 @@ fn format_duration(duration: Duration) -> String {
     }
 }
 
+/// Sums a trace's span durations, in nanoseconds, for latency recording.
+///
+/// This is the sum of work done across all spans, not the trace's
+/// wall-clock `duration` (earliest start to latest end), so that
+/// concurrent spans still each contribute their own latency sample. Traces
+/// loaded without span-level detail fall back to their overall `duration`.
+#[allow(clippy::cast_possible_truncation)]
+fn trace_duration_ns(trace: &Trace) -> u64 {
+    if trace.spans().is_empty() {
+        return trace.duration.as_nanos().min(u128::from(u64::MAX)) as u64;
+    }
+    trace
+        .spans()
+        .iter()
+        .map(|span| span.duration.as_nanos().min(u128::from(u64::MAX)) as u64)
+        .sum()
+}
+
 /// Simple hash function for deterministic sampling.
 fn simple_hash(s: &str) -> u64 {
     let mut hash = 0u64;
@@ -742,6 +1497,273 @@ mod tests {
         assert_eq!(result.total_errors, 2);
     }
 
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn replay_latency_percentiles_reflect_span_durations() {
+        let policy = sample_policy();
+        let corpus = sample_corpus();
+
+        let config = ReplayConfig::new().with_window(TimeWindow::one_minute());
+        let replayer = Replayer::new(config);
+
+        let result = replayer.replay(&policy, &corpus).unwrap();
+
+        // All 5 traces have a single 50ms span, so every percentile should
+        // land on 50ms, both per-window and in the overall summary.
+        let window = &result.windows[0];
+        assert_eq!(window.p50_latency_ns, Duration::from_millis(50).as_nanos() as u64);
+        assert_eq!(window.p99_latency_ns, Duration::from_millis(50).as_nanos() as u64);
+        assert_eq!(result.summary.p50_latency_ns, window.p50_latency_ns);
+        assert_eq!(result.summary.p999_latency_ns, window.p999_latency_ns);
+    }
+
+    #[test]
+    fn window_line_protocol_format() {
+        let mut window = ReplayWindow::new(0, 1_000_000_000);
+        window.trace_count = 5;
+        window.kept_count = 2;
+        window.dropped_count = 3;
+        window.error_count = 2;
+        window.throughput = 5.0;
+        window.exceeds_budget = true;
+
+        let line = window.to_line_protocol(
+            "nektor_replay",
+            &[("policy", "test")],
+            LineProtocolPrecision::Nanoseconds,
+        );
+
+        assert_eq!(
+            line,
+            "nektor_replay,policy=test trace_count=5i,kept=2i,dropped=3i,error_count=2i,throughput=5,exceeds_budget=true 0"
+        );
+    }
+
+    #[test]
+    fn line_protocol_escapes_tag_values() {
+        let window = ReplayWindow::new(0, 1_000_000_000);
+
+        let line = window.to_line_protocol(
+            "nektor replay",
+            &[("corpus", "a,b=c d")],
+            LineProtocolPrecision::Nanoseconds,
+        );
+
+        assert!(line.starts_with("nektor\\ replay,corpus=a\\,b\\=c\\ d "));
+    }
+
+    #[test]
+    fn line_protocol_precision_conversion() {
+        let mut window = ReplayWindow::new(1, 1_000_000_000);
+        window.start_ns = 1_500_000_000;
+
+        let line = window.to_line_protocol("m", &[], LineProtocolPrecision::Milliseconds);
+        assert!(line.ends_with(" 1500"));
+    }
+
+    #[test]
+    fn replay_result_line_protocol_one_line_per_window() {
+        let policy = sample_policy();
+        let corpus = sample_corpus();
+        let replayer = Replayer::default();
+
+        let result = replayer.replay(&policy, &corpus).unwrap();
+        let output = result.to_line_protocol(
+            "nektor_replay",
+            &[("policy", "test")],
+            LineProtocolPrecision::Nanoseconds,
+        );
+
+        assert_eq!(output.lines().count(), result.windows.len());
+    }
+
+    #[test]
+    fn replay_paced_sleeps_scaled_by_speed() {
+        use crate::clock::MockClock;
+
+        let policy = sample_policy();
+        let corpus = sample_corpus();
+
+        let config = ReplayConfig::new().with_speed(ReplaySpeed::RealTime);
+        let replayer = Replayer::new(config);
+        let clock = MockClock::new();
+
+        let mut seen = Vec::new();
+        replayer
+            .replay_paced(&policy, &corpus, &clock, |trace, _kept| {
+                seen.push(trace.trace_id.clone());
+            })
+            .unwrap();
+
+        assert_eq!(seen.len(), 5);
+        // Corpus spans 2s at 1x real-time speed.
+        assert_eq!(clock.elapsed(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn replay_paced_max_speed_never_sleeps() {
+        use crate::clock::MockClock;
+
+        let policy = sample_policy();
+        let corpus = sample_corpus();
+
+        let replayer = Replayer::default(); // defaults to ReplaySpeed::Max
+        let clock = MockClock::new();
+
+        replayer
+            .replay_paced(&policy, &corpus, &clock, |_trace, _kept| {})
+            .unwrap();
+
+        assert_eq!(clock.elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn replay_paced_empty_corpus_fails() {
+        let policy = sample_policy();
+        let corpus = Corpus::new();
+        let replayer = Replayer::default();
+        let clock = crate::clock::MockClock::new();
+
+        let result = replayer.replay_paced(&policy, &corpus, &clock, |_, _| {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn replay_streaming_matches_batch_replay() {
+        let policy = sample_policy();
+        let corpus = sample_corpus();
+
+        let config = ReplayConfig::new().with_window(TimeWindow::one_second());
+        let replayer = Replayer::new(config);
+
+        let batch = replayer.replay(&policy, &corpus).unwrap();
+
+        let mut windows = Vec::new();
+        let stream = replayer
+            .replay_streaming(
+                &policy,
+                &corpus,
+                0,
+                |window| windows.push(window.clone()),
+                |_| panic!("report_every == 0 must disable interval reports"),
+            )
+            .unwrap();
+
+        assert_eq!(windows.len(), batch.windows.len());
+        assert_eq!(stream.total_traces, batch.total_traces);
+        assert_eq!(stream.total_kept, batch.total_kept);
+        assert_eq!(stream.total_dropped, batch.total_dropped);
+        assert_eq!(stream.total_errors, batch.total_errors);
+        assert!((stream.summary.avg_throughput - batch.summary.avg_throughput).abs() < 1e-9);
+        assert!(
+            (stream.summary.throughput_std_dev - batch.summary.throughput_std_dev).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn replay_streaming_reports_at_cadence() {
+        let policy = sample_policy();
+        let corpus = sample_corpus();
+
+        let config = ReplayConfig::new().with_window(TimeWindow::one_second());
+        let replayer = Replayer::new(config);
+
+        let mut reports = Vec::new();
+        let result = replayer
+            .replay_streaming(&policy, &corpus, 2, |_| {}, |report| {
+                reports.push(report.clone());
+            })
+            .unwrap();
+
+        // Corpus spans ~2s in 1-second windows, so windows close at
+        // indices 0, 1, 2 - a cadence of 2 reports once, after window 2.
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].windows_processed, 2);
+        assert_eq!(
+            result.summary.window_count,
+            reports.last().unwrap().summary.window_count + 1
+        );
+    }
+
+    #[test]
+    fn replay_streaming_empty_corpus_fails() {
+        let policy = sample_policy();
+        let corpus = Corpus::new();
+        let replayer = Replayer::default();
+
+        let result = replayer.replay_streaming(&policy, &corpus, 1, |_| {}, |_| {});
+        assert!(result.is_err());
+    }
+
+    fn keep_all_policy() -> Policy {
+        let mut policy = Policy::new("keep-all");
+        policy.add_rule(Rule::new("keep-all", "true", Action::Keep, 100));
+        policy
+    }
+
+    /// 5 traces 0.1s apart, all inside one 1-second window.
+    fn bursty_corpus() -> Corpus {
+        let base_ns = 1_000_000_000_000u64;
+        let traces = (0..5)
+            .map(|i| create_trace(&format!("t{i}"), base_ns + i * 100_000_000, 200, false))
+            .collect::<Vec<_>>();
+        traces.into_iter().collect()
+    }
+
+    #[test]
+    fn token_bucket_sheds_beyond_budget_but_allows_bursts() {
+        let policy = keep_all_policy();
+        let corpus = bursty_corpus();
+
+        let config = ReplayConfig::new()
+            .with_window(TimeWindow::one_second())
+            .with_budget(2.0)
+            .with_enforcement(BudgetEnforcement::TokenBucket);
+        let replayer = Replayer::new(config);
+
+        let result = replayer.replay(&policy, &corpus).unwrap();
+
+        assert_eq!(result.total_kept, 5);
+        assert_eq!(result.total_shed, 3);
+        assert_eq!(result.windows[0].effective_throughput, 2.0);
+    }
+
+    #[test]
+    fn leaky_bucket_sheds_more_than_token_bucket_for_same_burst() {
+        let policy = keep_all_policy();
+        let corpus = bursty_corpus();
+
+        let config = ReplayConfig::new()
+            .with_window(TimeWindow::one_second())
+            .with_budget(2.0)
+            .with_enforcement(BudgetEnforcement::LeakyBucket);
+        let replayer = Replayer::new(config);
+
+        let result = replayer.replay(&policy, &corpus).unwrap();
+
+        assert_eq!(result.total_kept, 5);
+        assert_eq!(result.total_shed, 4);
+    }
+
+    #[test]
+    fn no_enforcement_leaves_shed_count_zero() {
+        let policy = keep_all_policy();
+        let corpus = bursty_corpus();
+
+        let config = ReplayConfig::new()
+            .with_window(TimeWindow::one_second())
+            .with_budget(2.0);
+        let replayer = Replayer::new(config);
+
+        let result = replayer.replay(&policy, &corpus).unwrap();
+
+        assert_eq!(result.total_shed, 0);
+        assert_eq!(
+            result.windows[0].effective_throughput,
+            result.windows[0].throughput
+        );
+    }
+
     #[test]
     fn deterministic_sampling() {
         let policy = sample_policy();
@@ -755,4 +1777,91 @@ mod tests {
         assert_eq!(result1.total_kept, result2.total_kept);
         assert_eq!(result1.total_dropped, result2.total_dropped);
     }
+
+    /// A corpus of `normal_count` non-error traces followed by
+    /// `error_count` error traces followed by `normal_count` more
+    /// non-error traces, one second apart, so the breaker has room to
+    /// trip open during the error burst and reset closed afterward.
+    fn circuit_breaker_corpus(normal_count: usize, error_count: usize) -> Corpus {
+        let base_ns = 1_000_000_000_000u64;
+        let mut traces = Vec::new();
+        let mut i = 0u64;
+        for _ in 0..normal_count {
+            traces.push(create_trace(&format!("pre-{i}"), base_ns + i * 1_000_000_000, 200, false));
+            i += 1;
+        }
+        for _ in 0..error_count {
+            traces.push(create_trace(&format!("err-{i}"), base_ns + i * 1_000_000_000, 500, true));
+            i += 1;
+        }
+        for _ in 0..normal_count {
+            traces.push(create_trace(&format!("post-{i}"), base_ns + i * 1_000_000_000, 200, false));
+            i += 1;
+        }
+        traces.into_iter().collect()
+    }
+
+    fn circuit_breaker_policy() -> Policy {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "fallback",
+            "true",
+            Action::CircuitBreaker {
+                closed_rate: 0.0,
+                open_rate: 1.0,
+                window: 10,
+                failure_threshold: 0.5,
+                min_samples: 5,
+            },
+            0,
+        ));
+        policy
+    }
+
+    #[test]
+    fn circuit_breaker_stays_closed_without_enough_errors() {
+        let policy = circuit_breaker_policy();
+        let corpus = circuit_breaker_corpus(20, 0);
+        let replayer = Replayer::default();
+
+        let result = replayer.replay(&policy, &corpus).unwrap();
+
+        // closed_rate is 0.0, so nothing should be kept while healthy.
+        assert_eq!(result.total_kept, 0);
+    }
+
+    #[test]
+    fn circuit_breaker_trips_open_then_resets_closed() {
+        let policy = circuit_breaker_policy();
+        // 20 healthy traces (breaker stays closed), 20 errors (trips the
+        // breaker open once the rolling window's error ratio crosses
+        // 50%), then 20 more healthy traces (ratio falls back below 50%
+        // once enough of them roll through the window, so the breaker
+        // closes again).
+        let corpus = circuit_breaker_corpus(20, 20);
+        let replayer = Replayer::default();
+
+        let result = replayer.replay(&policy, &corpus).unwrap();
+
+        // closed_rate is 0.0 and open_rate is 1.0, so every trace kept
+        // must have been kept while the breaker was open.
+        assert!(result.total_kept > 0, "breaker should have tripped open");
+        assert!(
+            result.total_kept < corpus.len(),
+            "breaker should have closed again once errors stopped"
+        );
+    }
+
+    #[test]
+    fn circuit_breaker_replay_is_deterministic() {
+        let policy = circuit_breaker_policy();
+        let corpus = circuit_breaker_corpus(20, 20);
+        let replayer = Replayer::default();
+
+        let result1 = replayer.replay(&policy, &corpus).unwrap();
+        let result2 = replayer.replay(&policy, &corpus).unwrap();
+
+        assert_eq!(result1.total_kept, result2.total_kept);
+        assert_eq!(result1.total_dropped, result2.total_dropped);
+    }
 }
@@ -1,9 +1,10 @@
 //! Prover result types.
 
 use serde::{Deserialize, Serialize};
+use toon_policy::{Policy, Rule};
 
 /// Result of policy verification.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProverResult {
     /// Overall status.
     pub status: Status,
@@ -15,6 +16,9 @@ pub struct ProverResult {
     pub violations: Vec<Violation>,
     /// Non-critical warnings.
     pub warnings: Vec<Warning>,
+    /// Concrete fixes suggested by checks that raised a violation, in
+    /// violation order - see [`crate::checks::Check::suggest_fix`].
+    pub fixes: Vec<PolicyPatch>,
 }
 
 /// Verification status.
@@ -81,6 +85,7 @@ impl ProverResult {
             checks_total: checks_passed,
             violations: Vec::new(),
             warnings: Vec::new(),
+            fixes: Vec::new(),
         }
     }
 
@@ -93,6 +98,7 @@ impl ProverResult {
             checks_total,
             violations,
             warnings: Vec::new(),
+            fixes: Vec::new(),
         }
     }
 
@@ -127,6 +133,56 @@ impl Violation {
             message: message.into(),
         }
     }
+
+    /// Creates a non-blocking violation - noted in
+    /// [`ProverResult::warnings`] rather than causing rejection.
+    #[must_use]
+    pub fn warning(check: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            check: check.into(),
+            severity: Severity::Warning,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an informational violation - the lowest priority, for
+    /// observations worth surfacing but that don't suggest anything is
+    /// wrong.
+    #[must_use]
+    pub fn info(check: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            check: check.into(),
+            severity: Severity::Info,
+            message: message.into(),
+        }
+    }
+
+    /// Returns true if this violation's severity should reject the
+    /// policy rather than merely being noted as a warning.
+    #[must_use]
+    pub const fn is_blocking(&self) -> bool {
+        matches!(self.severity, Severity::Critical)
+    }
+}
+
+impl From<Violation> for Warning {
+    fn from(violation: Violation) -> Self {
+        Self {
+            check: violation.check,
+            severity: violation.severity,
+            message: violation.message,
+        }
+    }
+}
+
+impl From<Warning> for Violation {
+    fn from(warning: Warning) -> Self {
+        Self {
+            check: warning.check,
+            severity: warning.severity,
+            message: warning.message,
+        }
+    }
 }
 
 impl Warning {
@@ -139,6 +195,42 @@ impl Warning {
             message: message.into(),
         }
     }
+
+    /// Creates a new informational note - lower priority than
+    /// [`Self::new`]'s warning, for observations worth surfacing but that
+    /// don't suggest anything is wrong (e.g. a budget with unused slack).
+    #[must_use]
+    pub fn info(check: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            check: check.into(),
+            severity: Severity::Info,
+            message: message.into(),
+        }
+    }
+}
+
+/// A concrete, directly-applyable fix for a policy violation, as
+/// proposed by a [`crate::checks::Check::suggest_fix`] implementation -
+/// e.g. inserting a `true` fallback rule or clamping an over-budget
+/// `budget_per_second` down to the configured maximum. Downstream
+/// tooling (a CLI `--fix` flag, an editor quick-fix) can apply one
+/// without re-deriving what "fixed" means for a given check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PolicyPatch {
+    /// Append the given rule to the policy.
+    AddRule(Rule),
+    /// Replace `budget_per_second` with this value.
+    SetBudget(Option<u64>),
+}
+
+impl PolicyPatch {
+    /// Applies this patch to `policy` in place.
+    pub fn apply(&self, policy: &mut Policy) {
+        match self {
+            Self::AddRule(rule) => policy.add_rule(rule.clone()),
+            Self::SetBudget(budget) => policy.budget_per_second = *budget,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -167,4 +259,43 @@ mod tests {
         assert_eq!(result.status, Status::ApprovedWithWarnings);
         assert!(result.is_approved());
     }
+
+    #[test]
+    fn a_critical_violation_is_blocking_but_warning_and_info_are_not() {
+        assert!(Violation::critical("c", "m").is_blocking());
+        assert!(!Violation::warning("c", "m").is_blocking());
+        assert!(!Violation::info("c", "m").is_blocking());
+    }
+
+    #[test]
+    fn violation_and_warning_convert_into_each_other_preserving_fields() {
+        let violation = Violation::warning("budget-feasibility", "little headroom left");
+        let warning: Warning = violation.clone().into();
+        assert_eq!(warning.check, violation.check);
+        assert_eq!(warning.severity, violation.severity);
+        assert_eq!(warning.message, violation.message);
+
+        let back: Violation = warning.into();
+        assert_eq!(back, violation);
+    }
+
+    #[test]
+    fn add_rule_patch_appends_a_rule() {
+        use toon_policy::Action;
+
+        let mut policy = Policy::new("test");
+        let patch = PolicyPatch::AddRule(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+        patch.apply(&mut policy);
+
+        assert!(policy.has_fallback());
+    }
+
+    #[test]
+    fn set_budget_patch_replaces_the_budget() {
+        let mut policy = Policy::new("test");
+        policy.budget_per_second = Some(5000);
+
+        PolicyPatch::SetBudget(Some(1000)).apply(&mut policy);
+        assert_eq!(policy.budget_per_second, Some(1000));
+    }
 }
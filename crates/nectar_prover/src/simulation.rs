@@ -3,10 +3,13 @@
 //! Simulates policy behavior over time-series traffic data to
 //! verify budget compliance under realistic conditions.
 
+use crate::analysis::is_error_condition;
+use crate::perturbation::{lognormal_jitter, TrafficPerturbation};
 use crate::traffic::{TrafficPattern, TrafficPoint};
 use chrono::{DateTime, Timelike, Utc};
 use serde::{Deserialize, Serialize};
-use toon_policy::Policy;
+use std::collections::{BTreeMap, VecDeque};
+use toon_policy::{Policy, Rule};
 
 /// Result of simulating a policy against a traffic pattern.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +24,14 @@ pub struct SimulationResult {
     pub summary: SimulationSummary,
     /// Recommendations for fixing violations.
     pub recommendations: Vec<Recommendation>,
+    /// Per-rule hit counts accumulated while replaying the traffic pattern.
+    pub rule_coverage: RuleCoverage,
+    /// The seed used to perturb the traffic before this simulation, if it
+    /// ran through [`Simulator::simulate_with_perturbation`]. `None` for
+    /// a plain [`Simulator::simulate`] run. Recording it here means a
+    /// violation found under perturbation is reproducible just by
+    /// re-running with this seed, without separately tracking it.
+    pub seed: Option<u64>,
 }
 
 impl SimulationResult {
@@ -67,7 +78,8 @@ pub struct BudgetViolation {
 }
 
 impl BudgetViolation {
-    /// Creates a new budget violation.
+    /// Creates a new budget violation, with the excess computed as
+    /// `actual_events - budget_limit`.
     #[must_use]
     pub fn new(
         timestamp: DateTime<Utc>,
@@ -76,6 +88,27 @@ impl BudgetViolation {
         point_index: usize,
     ) -> Self {
         let excess_events = (actual_events - budget_limit).max(0.0);
+        Self::with_excess(
+            timestamp,
+            budget_limit,
+            actual_events,
+            excess_events,
+            point_index,
+        )
+    }
+
+    /// Creates a violation with an explicit excess amount, for models
+    /// where the overage isn't simply `actual_events - budget_limit` -
+    /// e.g. a [`BudgetModel::TokenBucket`] underflow, where the excess is
+    /// the shortfall the bucket couldn't cover.
+    #[must_use]
+    pub fn with_excess(
+        timestamp: DateTime<Utc>,
+        budget_limit: f64,
+        actual_events: f64,
+        excess_events: f64,
+        point_index: usize,
+    ) -> Self {
         let excess_percent = if budget_limit > 0.0 {
             (excess_events / budget_limit) * 100.0
         } else {
@@ -133,6 +166,64 @@ pub struct SimulationSummary {
     pub peak_kept_eps: f64,
     /// Average kept events per second.
     pub avg_kept_eps: f64,
+    /// The budget this simulation was run against - see
+    /// [`Simulator::new`]. Retained so callers can relate
+    /// [`Self::peak_kept_eps`] back to its limit (e.g. a compliance
+    /// margin) without threading the budget through separately.
+    pub budget_limit: f64,
+    /// The highest per-point rejection probability
+    /// [`SamplingMode::Adaptive`] applied on top of the policy's sample
+    /// rate, i.e. how aggressively the controller had to shed to track
+    /// budget. `0.0` under [`SamplingMode::Static`], or if the adaptive
+    /// controller never needed to shed anything.
+    pub peak_shed_probability: f64,
+    /// Total events that would have been kept under the policy's fixed
+    /// fallback rate for the whole run, ignoring any [`SamplingMode`]
+    /// adjustment. Comparing this against [`Self::total_kept`] shows how
+    /// much a non-static mode - in particular [`SamplingMode::Aimd`] -
+    /// moved the kept total away from the static baseline. Equal to
+    /// [`Self::total_kept`] under [`SamplingMode::Static`].
+    pub static_total_kept: f64,
+}
+
+/// Per-rule hit counts recorded while simulating a traffic pattern.
+///
+/// Rules are listed in the same (priority) order as `policy.rules`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleCoverage {
+    /// One entry per rule in the policy.
+    pub hits: Vec<RuleHit>,
+}
+
+impl RuleCoverage {
+    /// Rules that never matched a single simulated event.
+    pub fn dead_rules(&self) -> impl Iterator<Item = &RuleHit> {
+        self.hits.iter().filter(|hit| hit.dead)
+    }
+
+    /// Returns true if every non-fallback rule matched at least one
+    /// simulated event. The fallback rule is exempt, since it exists
+    /// precisely to catch whatever no other rule matches.
+    #[must_use]
+    pub fn fully_exercised(&self) -> bool {
+        self.hits.iter().all(|hit| hit.is_fallback || !hit.dead)
+    }
+}
+
+/// Hit-count data for a single rule over a simulated traffic pattern.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleHit {
+    /// The rule's name.
+    pub rule_name: String,
+    /// Number of simulated events this rule matched (first-match-wins,
+    /// respecting rule priority).
+    pub hit_count: u64,
+    /// Fraction of all simulated events this rule matched (0.0 - 1.0).
+    pub hit_fraction: f64,
+    /// Whether this is the policy's fallback (`match_expr == "true"`) rule.
+    pub is_fallback: bool,
+    /// True if this rule never matched a single simulated event.
+    pub dead: bool,
 }
 
 /// A recommendation for addressing budget issues.
@@ -201,18 +292,289 @@ impl Recommendation {
     }
 }
 
+/// How a [`Simulator`] decides whether a point's kept throughput
+/// violates budget.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BudgetModel {
+    /// Flags a point the instant its kept throughput exceeds `budget`,
+    /// with no tolerance for short bursts even when average traffic is
+    /// well under budget.
+    Instantaneous,
+    /// Token bucket: refills at `budget` tokens/sec up to a capacity of
+    /// `budget * burst_seconds`. Each timeline point consumes
+    /// `kept_eps * dt` tokens, where `dt` is the gap to the next
+    /// [`TrafficPoint`]; a violation is only recorded when the bucket
+    /// underflows, with the shortfall reported as the excess.
+    ///
+    /// Unused credit accrued during quiet periods banks up to the
+    /// capacity, but expires after `credit_window_seconds`: credit older
+    /// than that is forfeited, so a long idle stretch can't silently fund
+    /// an unbounded spike much later on.
+    TokenBucket {
+        /// How many seconds of budget the bucket can hold, i.e. capacity
+        /// is `budget * burst_seconds`.
+        burst_seconds: f64,
+        /// How long banked credit stays usable before it's forfeited.
+        credit_window_seconds: f64,
+    },
+}
+
+impl Default for BudgetModel {
+    fn default() -> Self {
+        Self::Instantaneous
+    }
+}
+
+/// Stateful token bucket backing [`BudgetModel::TokenBucket`] simulation.
+///
+/// Unlike a plain token bucket, credit is tracked as it's granted so it
+/// can expire: each refill is recorded in `ledger` alongside the elapsed
+/// time it was granted at, and [`Self::consume`] forfeits any credit
+/// older than `credit_window_seconds` before refilling or spending.
+struct CreditBucket {
+    capacity: f64,
+    rate: f64,
+    credit_window_seconds: f64,
+    tokens: f64,
+    /// Unexpired refills, oldest first, as `(granted_at_seconds, amount)`.
+    ledger: VecDeque<(f64, f64)>,
+    elapsed_seconds: f64,
+}
+
+impl CreditBucket {
+    fn new(budget: f64, burst_seconds: f64, credit_window_seconds: f64) -> Self {
+        let capacity = budget * burst_seconds;
+        Self {
+            capacity,
+            rate: budget,
+            credit_window_seconds,
+            tokens: capacity,
+            ledger: VecDeque::new(),
+            elapsed_seconds: 0.0,
+        }
+    }
+
+    /// Advances the bucket by `dt` seconds - expiring stale credit, then
+    /// refilling at `rate`, then spending `amount` tokens - and returns
+    /// the shortfall (`0.0` if fully funded).
+    fn consume(&mut self, dt: f64, amount: f64) -> f64 {
+        self.elapsed_seconds += dt;
+        self.expire_stale_credit();
+
+        let refill = (self.rate * dt).min((self.capacity - self.tokens).max(0.0));
+        if refill > 0.0 {
+            self.tokens += refill;
+            self.ledger.push_back((self.elapsed_seconds, refill));
+        }
+
+        if self.tokens >= amount {
+            self.tokens -= amount;
+            0.0
+        } else {
+            let shortfall = amount - self.tokens;
+            self.tokens = 0.0;
+            shortfall
+        }
+    }
+
+    /// Forfeits any banked credit older than `credit_window_seconds`.
+    fn expire_stale_credit(&mut self) {
+        while let Some(&(granted_at, amount)) = self.ledger.front() {
+            if self.elapsed_seconds - granted_at > self.credit_window_seconds {
+                self.tokens = (self.tokens - amount).max(0.0);
+                self.ledger.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+/// Gap in seconds from `points[index]` to the next point, used as the
+/// consumption interval for [`BudgetModel::TokenBucket`]. Falls back to
+/// the previous gap at the last point, or `1.0` second if there's no
+/// adjacent point to measure against - matching
+/// [`TrafficPattern::total_events`]'s assumption that a lone point
+/// otherwise represents one second of traffic.
+#[allow(clippy::cast_precision_loss)]
+fn point_interval_seconds(points: &[TrafficPoint], index: usize) -> f64 {
+    if let Some(next) = points.get(index + 1) {
+        let secs = (next.timestamp - points[index].timestamp).num_milliseconds() as f64 / 1000.0;
+        if secs > 0.0 {
+            return secs;
+        }
+    }
+    if index > 0 {
+        let secs = (points[index].timestamp - points[index - 1].timestamp).num_milliseconds()
+            as f64
+            / 1000.0;
+        if secs > 0.0 {
+            return secs;
+        }
+    }
+    1.0
+}
+
+/// How a [`Simulator`] derives each point's effective sample rate.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SamplingMode {
+    /// Apply the policy's fixed fallback sample rate to every point, per
+    /// [`Simulator::effective_sample_rate`] - the sample rate never
+    /// changes in response to traffic.
+    Static,
+    /// Adapt the sample rate point-by-point to track `budget`, modeled on
+    /// a probabilistic load shedder: each point's kept EPS at the
+    /// policy's static rate is smoothed over the last `window` points,
+    /// and once that smoothed projection nears or exceeds budget, a
+    /// rejection probability `p = max(0, (projected - budget) /
+    /// projected)` sheds a further fraction of non-error traffic on top
+    /// of it, so `kept_eps = error_events + non_error_events *
+    /// sample_rate * (1 - p)`.
+    ///
+    /// Smoothing over a window rather than reacting to each point alone
+    /// keeps a single spike from triggering a full-strength shed.
+    Adaptive {
+        /// Number of recent points averaged when projecting kept EPS.
+        window: usize,
+    },
+    /// Self-tune the sample rate point-by-point with an additive-increase
+    /// / multiplicative-decrease (AIMD) loop, the congestion-control
+    /// scheme behind Google Congestion Control: start at the policy's
+    /// configured rate, then for each point, if the kept EPS it achieved
+    /// is comfortably under budget (below `90%` of it) raise the rate by
+    /// `increase_step` (capped at `1.0`), if it went over budget cut the
+    /// rate by `decrease_factor` (floored at `0.001`), and otherwise hold
+    /// steady. Unlike [`Self::Adaptive`], which sheds a probability on
+    /// top of a fixed rate, this mode evolves the rate itself, so it can
+    /// over- and under-shoot while converging - useful for surfacing
+    /// oscillation a real self-tuning collector would exhibit.
+    Aimd {
+        /// Additive step added to the rate each point it stays
+        /// comfortably under budget (e.g. `0.02`).
+        increase_step: f64,
+        /// Multiplicative factor applied to the rate each point it goes
+        /// over budget (e.g. `0.85`).
+        decrease_factor: f64,
+    },
+}
+
+impl Default for SamplingMode {
+    fn default() -> Self {
+        Self::Static
+    }
+}
+
+/// Rolling average of recent kept-EPS projections, backing
+/// [`SamplingMode::Adaptive`]'s rejection-probability calculation.
+struct SlidingAverage {
+    window: usize,
+    samples: VecDeque<f64>,
+    sum: f64,
+}
+
+impl SlidingAverage {
+    fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            samples: VecDeque::new(),
+            sum: 0.0,
+        }
+    }
+
+    /// Records `value` and returns the average over the trailing window.
+    #[allow(clippy::cast_precision_loss)]
+    fn push_and_average(&mut self, value: f64) -> f64 {
+        self.samples.push_back(value);
+        self.sum += value;
+        while self.samples.len() > self.window {
+            if let Some(oldest) = self.samples.pop_front() {
+                self.sum -= oldest;
+            }
+        }
+        self.sum / self.samples.len() as f64
+    }
+}
+
+/// Additive-increase/multiplicative-decrease rate controller backing
+/// [`SamplingMode::Aimd`]. Carries the rate it converged on from one
+/// point to the next across a `simulate` run.
+struct AimdController {
+    rate: f64,
+    increase_step: f64,
+    decrease_factor: f64,
+}
+
+impl AimdController {
+    /// Floor the rate never decays past, so a long overage streak can't
+    /// sample the policy down to nothing.
+    const MIN_RATE: f64 = 0.001;
+    /// Ceiling the rate never climbs past.
+    const MAX_RATE: f64 = 1.0;
+    /// Fraction of budget below which the controller treats a point as
+    /// comfortably under and ramps the rate back up.
+    const LOW_WATERMARK: f64 = 0.9;
+
+    fn new(initial_rate: f64, increase_step: f64, decrease_factor: f64) -> Self {
+        Self {
+            rate: initial_rate.clamp(Self::MIN_RATE, Self::MAX_RATE),
+            increase_step,
+            decrease_factor,
+        }
+    }
+
+    /// Applies the controller's current rate to this point's events,
+    /// then adjusts the rate for the next point based on the outcome.
+    /// Returns `(kept_eps, rate_applied_this_point)`.
+    fn apply(&mut self, error_events: f64, non_error_events: f64, budget: f64) -> (f64, f64) {
+        let rate_applied = self.rate;
+        let kept_eps = error_events + non_error_events * rate_applied;
+
+        if kept_eps < budget * Self::LOW_WATERMARK {
+            self.rate = (self.rate + self.increase_step).min(Self::MAX_RATE);
+        } else if kept_eps > budget {
+            self.rate = (self.rate * self.decrease_factor).max(Self::MIN_RATE);
+        }
+
+        (kept_eps, rate_applied)
+    }
+}
+
 /// Simulator for running policies against traffic patterns.
 #[derive(Debug, Clone)]
 pub struct Simulator {
     /// Budget limit (events per second).
     budget: f64,
+    /// How budget violations are determined. Defaults to
+    /// [`BudgetModel::Instantaneous`].
+    budget_model: BudgetModel,
+    /// How the effective sample rate is derived. Defaults to
+    /// [`SamplingMode::Static`].
+    sampling_mode: SamplingMode,
 }
 
 impl Simulator {
     /// Creates a new simulator with the given budget.
     #[must_use]
     pub const fn new(budget: f64) -> Self {
-        Self { budget }
+        Self {
+            budget,
+            budget_model: BudgetModel::Instantaneous,
+            sampling_mode: SamplingMode::Static,
+        }
+    }
+
+    /// Sets the budget enforcement model.
+    #[must_use]
+    pub const fn with_budget_model(mut self, budget_model: BudgetModel) -> Self {
+        self.budget_model = budget_model;
+        self
+    }
+
+    /// Sets the sampling mode.
+    #[must_use]
+    pub const fn with_sampling_mode(mut self, sampling_mode: SamplingMode) -> Self {
+        self.sampling_mode = sampling_mode;
+        self
     }
 
     /// Simulates a policy against a traffic pattern.
@@ -224,11 +586,90 @@ impl Simulator {
         let mut total_incoming = 0.0;
         let mut total_kept = 0.0;
         let mut peak_kept = 0.0f64;
+        let mut matched_events: BTreeMap<String, f64> = BTreeMap::new();
+
+        let mut bucket = match self.budget_model {
+            BudgetModel::Instantaneous => None,
+            BudgetModel::TokenBucket {
+                burst_seconds,
+                credit_window_seconds,
+            } => Some(CreditBucket::new(
+                self.budget,
+                burst_seconds,
+                credit_window_seconds,
+            )),
+        };
+
+        let mut sampling_window = match self.sampling_mode {
+            SamplingMode::Adaptive { window } => Some(SlidingAverage::new(window)),
+            SamplingMode::Static | SamplingMode::Aimd { .. } => None,
+        };
+        let mut aimd = match self.sampling_mode {
+            SamplingMode::Aimd {
+                increase_step,
+                decrease_factor,
+            } => Some(AimdController::new(
+                self.effective_sample_rate(policy),
+                increase_step,
+                decrease_factor,
+            )),
+            SamplingMode::Static | SamplingMode::Adaptive { .. } => None,
+        };
+        let mut peak_shed_probability = 0.0f64;
+        let mut static_total_kept = 0.0;
 
         for (index, point) in traffic.points().iter().enumerate() {
-            let sim_point = self.simulate_point(policy, point, index);
+            let mut sim_point = self.simulate_point(policy, point, index);
+            record_rule_hits(policy, point, &mut matched_events);
+            static_total_kept += sim_point.kept_eps;
+
+            if let Some(controller) = aimd.as_mut() {
+                let non_error_events = sim_point.incoming_eps - sim_point.error_events_kept;
+                let (kept_eps, rate_applied) =
+                    controller.apply(sim_point.error_events_kept, non_error_events, self.budget);
+                sim_point.kept_eps = kept_eps;
+                sim_point.dropped_eps = sim_point.incoming_eps - kept_eps;
+                sim_point.sample_rate = rate_applied;
+                sim_point.exceeds_budget = kept_eps > self.budget;
+            }
 
-            if sim_point.exceeds_budget {
+            if let Some(sliding_average) = sampling_window.as_mut() {
+                let projected = sliding_average.push_and_average(sim_point.kept_eps);
+                let shed_probability = if projected > 0.0 {
+                    ((projected - self.budget) / projected).max(0.0)
+                } else {
+                    0.0
+                };
+                peak_shed_probability = peak_shed_probability.max(shed_probability);
+
+                let non_error_kept = sim_point.kept_eps - sim_point.error_events_kept;
+                sim_point.kept_eps =
+                    sim_point.error_events_kept + non_error_kept * (1.0 - shed_probability);
+                sim_point.dropped_eps = sim_point.incoming_eps - sim_point.kept_eps;
+                sim_point.sample_rate = if sim_point.incoming_eps > 0.0 {
+                    sim_point.kept_eps / sim_point.incoming_eps
+                } else {
+                    0.0
+                };
+                // Re-derive against the post-shedding kept EPS; the value
+                // `simulate_point` set reflects the pre-shedding rate.
+                sim_point.exceeds_budget = sim_point.kept_eps > self.budget;
+            }
+
+            if let Some(bucket) = bucket.as_mut() {
+                let dt = point_interval_seconds(traffic.points(), index);
+                let shortfall = bucket.consume(dt, sim_point.kept_eps * dt);
+                sim_point.exceeds_budget = shortfall > 0.0;
+                if sim_point.exceeds_budget {
+                    violations.push(BudgetViolation::with_excess(
+                        point.timestamp,
+                        self.budget,
+                        sim_point.kept_eps,
+                        shortfall / dt,
+                        index,
+                    ));
+                }
+            } else if sim_point.exceeds_budget {
                 violations.push(BudgetViolation::new(
                     point.timestamp,
                     self.budget,
@@ -244,6 +685,8 @@ impl Simulator {
             timeline.push(sim_point);
         }
 
+        let rule_coverage = build_rule_coverage(policy, &matched_events, total_incoming);
+
         let total_dropped = total_incoming - total_kept;
         let overall_sample_rate = if total_incoming > 0.0 {
             total_kept / total_incoming
@@ -277,6 +720,9 @@ impl Simulator {
             percent_time_over_budget,
             peak_kept_eps: peak_kept,
             avg_kept_eps: avg_kept,
+            budget_limit: self.budget,
+            peak_shed_probability,
+            static_total_kept,
         };
 
         let recommendations = self.generate_recommendations(
@@ -292,6 +738,82 @@ impl Simulator {
             timeline,
             summary,
             recommendations,
+            rule_coverage,
+            seed: None,
+        }
+    }
+
+    /// Runs [`Self::simulate`] against `traffic` after distorting it with
+    /// `perturbation`, seeded by `seed` for exact reproducibility - see
+    /// [`TrafficPerturbation`]. Lets callers confirm a policy stays
+    /// budget-compliant under adversarial, noisy conditions rather than
+    /// only the clean pattern an author hand-wrote, with any violation
+    /// found reproducible by re-running with the same seed.
+    #[must_use]
+    pub fn simulate_with_perturbation(
+        &self,
+        policy: &Policy,
+        traffic: &TrafficPattern,
+        seed: u64,
+        perturbation: &TrafficPerturbation,
+    ) -> SimulationResult {
+        let perturbed = perturbation.apply(traffic, seed);
+        let mut result = self.simulate(policy, &perturbed);
+        result.seed = Some(seed);
+        result
+    }
+
+    /// Runs `runs` independent simulations over randomized realizations
+    /// of `traffic` and aggregates the result into percentile
+    /// distributions, so a single point estimate of
+    /// [`SimulationSummary::percent_time_over_budget`] doesn't hide the
+    /// policy's sensitivity to traffic uncertainty.
+    ///
+    /// Each run's realization applies [`lognormal_jitter`] (at
+    /// [`MONTE_CARLO_JITTER_SIGMA`]) to `events_per_second` and
+    /// `error_rate`, seeded by combining `seed` with the run index so
+    /// every run is reproducible and independent of the others.
+    #[must_use]
+    pub fn simulate_monte_carlo(
+        &self,
+        policy: &Policy,
+        traffic: &TrafficPattern,
+        runs: usize,
+        seed: u64,
+    ) -> MonteCarloResult {
+        let mut percent_over_budget = Vec::with_capacity(runs);
+        let mut peak_kept_eps = Vec::with_capacity(runs);
+        let mut violation_counts = Vec::with_capacity(runs);
+        let mut compliant_runs = 0usize;
+
+        for run in 0..runs {
+            let run_seed = combine_seeds(seed, run as u64);
+            let realization = lognormal_jitter(traffic, run_seed, MONTE_CARLO_JITTER_SIGMA);
+            let result = self.simulate(policy, &realization);
+
+            percent_over_budget.push(result.summary.percent_time_over_budget);
+            peak_kept_eps.push(result.summary.peak_kept_eps);
+            #[allow(clippy::cast_precision_loss)]
+            violation_counts.push(result.violation_count() as f64);
+            if result.is_compliant() {
+                compliant_runs += 1;
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let compliance_probability = if runs > 0 {
+            compliant_runs as f64 / runs as f64
+        } else {
+            0.0
+        };
+
+        MonteCarloResult {
+            runs,
+            seed,
+            percent_time_over_budget: PercentileStats::from_samples(&mut percent_over_budget),
+            peak_kept_eps: PercentileStats::from_samples(&mut peak_kept_eps),
+            violation_count: PercentileStats::from_samples(&mut violation_counts),
+            compliance_probability,
         }
     }
 
@@ -342,8 +864,15 @@ impl Simulator {
         // Find the fallback rule's sample rate
         for rule in &policy.rules {
             if rule.match_expr == "true" {
-                if let toon_policy::Action::Sample(rate) = rule.action {
-                    return rate;
+                match rule.action {
+                    toon_policy::Action::Sample(rate) => return rate,
+                    // Aggregate traffic simulation has no notion of a
+                    // rolling per-trace error window, so approximate with
+                    // the breaker's resting (closed) rate.
+                    toon_policy::Action::CircuitBreaker { closed_rate, .. } => {
+                        return closed_rate;
+                    }
+                    toon_policy::Action::Keep | toon_policy::Action::Drop => {}
                 }
             }
         }
@@ -405,6 +934,330 @@ impl Simulator {
 
         recommendations
     }
+
+    /// Branch-and-bound search (modeled on bdk's coin selector) for the
+    /// per-rule `Sample` rates maximizing total kept events subject to
+    /// `kept_eps <= budget` at every [`TrafficPattern`] point.
+    ///
+    /// Only rules whose action is [`toon_policy::Action::Sample`] are
+    /// searched - `Keep`/`Drop`/`CircuitBreaker` rules are left as-is.
+    /// Candidates are branched in order of descending matched-volume
+    /// contribution (see [`record_rule_hits`]), so the rule most likely
+    /// to matter is tried first, at [`RATE_CANDIDATES`]. A partial
+    /// assignment is pruned once even its best-case completion - every
+    /// still-unassigned rule kept at `1.0` - can't beat the best
+    /// complete assignment found so far.
+    #[must_use]
+    pub fn optimize(&self, policy: &Policy, traffic: &TrafficPattern) -> OptimizationResult {
+        let matched = matched_volume_by_rule(policy, traffic);
+
+        let mut order: Vec<usize> = policy
+            .rules
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| matches!(rule.action, toon_policy::Action::Sample(_)))
+            .map(|(index, _)| index)
+            .collect();
+        order.sort_by(|&a, &b| {
+            let volume_a = matched.get(&policy.rules[a].name).copied().unwrap_or(0.0);
+            let volume_b = matched.get(&policy.rules[b].name).copied().unwrap_or(0.0);
+            volume_b
+                .partial_cmp(&volume_a)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let optimized = if order.is_empty() {
+            policy.clone()
+        } else {
+            let mut assignment = vec![1.0; order.len()];
+            let mut best: Option<(Vec<f64>, f64)> = None;
+            self.branch(policy, traffic, &order, 0, &mut assignment, &mut best);
+
+            let (rates, _) = best.expect("RATE_CANDIDATES is non-empty, so a leaf always runs");
+            let mut optimized = policy.clone();
+            for (&rule_index, &rate) in order.iter().zip(&rates) {
+                optimized.rules[rule_index].action = toon_policy::Action::Sample(rate);
+            }
+            optimized
+        };
+
+        let (total_kept, total_dropped, points_over_budget) = self.evaluate(&optimized, traffic);
+        OptimizationResult {
+            policy: optimized,
+            total_kept,
+            total_dropped,
+            points_over_budget,
+        }
+    }
+
+    /// Recursive branch-and-bound step: assigns `order[depth]`'s rate
+    /// across [`RATE_CANDIDATES`], recursing until every searched rule
+    /// is assigned, pruning branches whose best-case bound can't improve
+    /// on `best`.
+    fn branch(
+        &self,
+        policy: &Policy,
+        traffic: &TrafficPattern,
+        order: &[usize],
+        depth: usize,
+        assignment: &mut [f64],
+        best: &mut Option<(Vec<f64>, f64)>,
+    ) {
+        if depth == order.len() {
+            let waste = self.waste(&apply_rates(policy, order, assignment), traffic);
+            if best
+                .as_ref()
+                .is_none_or(|(_, best_waste)| waste < *best_waste)
+            {
+                *best = Some((assignment.to_vec(), waste));
+            }
+            return;
+        }
+
+        for &rate in &RATE_CANDIDATES {
+            assignment[depth] = rate;
+
+            if let Some((_, best_waste)) = best.as_ref() {
+                // Best-case bound: every still-unassigned rule kept at
+                // 1.0, the most it could possibly keep. Dropped events
+                // can only rise as remaining rules are pinned down to
+                // something lower, so if even this optimistic,
+                // penalty-free drop count can't beat the best waste
+                // found so far, nothing deeper in this branch can
+                // either.
+                let mut bound_assignment = assignment.to_vec();
+                bound_assignment[depth + 1..].fill(1.0);
+                let (_, bound_dropped, _) =
+                    self.evaluate(&apply_rates(policy, order, &bound_assignment), traffic);
+                if bound_dropped >= *best_waste {
+                    continue;
+                }
+            }
+
+            self.branch(policy, traffic, order, depth + 1, assignment, best);
+        }
+    }
+
+    /// Total kept/dropped events and over-budget point count for
+    /// `policy` against `traffic`, reusing the same per-point model
+    /// [`Self::simulate`] is built on.
+    fn evaluate(&self, policy: &Policy, traffic: &TrafficPattern) -> (f64, f64, usize) {
+        let mut total_kept = 0.0;
+        let mut total_dropped = 0.0;
+        let mut points_over_budget = 0;
+
+        for (index, point) in traffic.points().iter().enumerate() {
+            let sim_point = self.simulate_point(policy, point, index);
+            total_kept += sim_point.kept_eps;
+            total_dropped += sim_point.dropped_eps;
+            if sim_point.exceeds_budget {
+                points_over_budget += 1;
+            }
+        }
+
+        (total_kept, total_dropped, points_over_budget)
+    }
+
+    /// Scores a candidate policy for [`Self::optimize`]: dropped events
+    /// plus a steep penalty per over-budget point, so a budget-compliant
+    /// candidate always outranks a non-compliant one regardless of how
+    /// few events the latter happened to drop.
+    fn waste(&self, policy: &Policy, traffic: &TrafficPattern) -> f64 {
+        let (_, total_dropped, points_over_budget) = self.evaluate(policy, traffic);
+        #[allow(clippy::cast_precision_loss)]
+        let penalty = points_over_budget as f64 * OVER_BUDGET_PENALTY;
+        total_dropped + penalty
+    }
+}
+
+/// Penalty [`Simulator::waste`] adds per timeline point that exceeds
+/// budget - large enough that any budget-compliant candidate always
+/// outranks a non-compliant one found by [`Simulator::optimize`].
+const OVER_BUDGET_PENALTY: f64 = 1.0e9;
+
+/// Discretized candidate rates [`Simulator::optimize`] branches on for
+/// each searched rule, from fully dropped to fully kept.
+const RATE_CANDIDATES: [f64; 11] = [0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
+
+/// Clones `policy` with each rule at `order[i]` set to `Sample(rates[i])`.
+fn apply_rates(policy: &Policy, order: &[usize], rates: &[f64]) -> Policy {
+    let mut policy = policy.clone();
+    for (&rule_index, &rate) in order.iter().zip(rates) {
+        policy.rules[rule_index].action = toon_policy::Action::Sample(rate);
+    }
+    policy
+}
+
+/// Aggregate matched-volume per rule across `traffic` - see
+/// [`record_rule_hits`]. Used to order [`Simulator::optimize`]'s
+/// branch-and-bound search by which rule is most likely to matter.
+fn matched_volume_by_rule(policy: &Policy, traffic: &TrafficPattern) -> BTreeMap<String, f64> {
+    let mut matched = BTreeMap::new();
+    for point in traffic.points() {
+        record_rule_hits(policy, point, &mut matched);
+    }
+    matched
+}
+
+/// The per-rule `Sample` rate assignment [`Simulator::optimize`] found,
+/// and the kept/dropped split it achieves.
+#[derive(Debug, Clone)]
+pub struct OptimizationResult {
+    /// `policy` with every searched rule's `Sample` rate replaced by the
+    /// best assignment [`Simulator::optimize`] found.
+    pub policy: Policy,
+    /// Total events kept under [`Self::policy`].
+    pub total_kept: f64,
+    /// Total events dropped under [`Self::policy`].
+    pub total_dropped: f64,
+    /// Timeline points still over budget under [`Self::policy`].
+    pub points_over_budget: usize,
+}
+
+/// Log-space standard deviation of the multiplicative jitter
+/// [`Simulator::simulate_monte_carlo`] applies to `events_per_second` and
+/// `error_rate` in each run's realization.
+const MONTE_CARLO_JITTER_SIGMA: f64 = 0.2;
+
+/// Deterministically derives a per-run seed from the base seed and run
+/// index, so [`Simulator::simulate_monte_carlo`]'s runs are reproducible
+/// and independent of each other.
+fn combine_seeds(seed: u64, run: u64) -> u64 {
+    seed.wrapping_mul(0x9E37_79B9_7F4A_7C15).wrapping_add(run)
+}
+
+/// The p50/p95/p99 percentiles of a metric collected across
+/// [`Simulator::simulate_monte_carlo`]'s runs.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct PercentileStats {
+    /// Median value.
+    pub p50: f64,
+    /// 95th percentile value.
+    pub p95: f64,
+    /// 99th percentile value.
+    pub p99: f64,
+}
+
+impl PercentileStats {
+    /// Computes p50/p95/p99 over `samples`, sorting them in place.
+    /// Returns all-zero stats for an empty slice.
+    fn from_samples(samples: &mut [f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        Self {
+            p50: Self::value_at_percentile(samples, 50.0),
+            p95: Self::value_at_percentile(samples, 95.0),
+            p99: Self::value_at_percentile(samples, 99.0),
+        }
+    }
+
+    /// Returns the value at `percentile` (0.0-100.0) from `sorted`, a
+    /// slice already sorted ascending. Mirrors
+    /// [`crate::histogram::LatencyHistogram::value_at_percentile`]'s
+    /// nearest-rank approach.
+    #[allow(clippy::cast_precision_loss, clippy::cast_sign_loss)]
+    fn value_at_percentile(sorted: &[f64], percentile: f64) -> f64 {
+        let percentile = percentile.clamp(0.0, 100.0);
+        let rank = ((percentile / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+        sorted[rank.min(sorted.len() - 1)]
+    }
+}
+
+/// Distribution of a policy's budget compliance over many randomized
+/// traffic realizations, as produced by
+/// [`Simulator::simulate_monte_carlo`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloResult {
+    /// Number of realizations simulated.
+    pub runs: usize,
+    /// The base seed `simulate_monte_carlo` was called with.
+    pub seed: u64,
+    /// Distribution of [`SimulationSummary::percent_time_over_budget`]
+    /// across runs.
+    pub percent_time_over_budget: PercentileStats,
+    /// Distribution of [`SimulationSummary::peak_kept_eps`] across runs.
+    pub peak_kept_eps: PercentileStats,
+    /// Distribution of [`SimulationResult::violation_count`] across runs.
+    pub violation_count: PercentileStats,
+    /// Fraction of runs that were fully budget-compliant - e.g. `0.97`
+    /// means the policy stayed compliant in 97% of randomized scenarios.
+    pub compliance_probability: f64,
+}
+
+/// Credits the rule that would have won first-match-wins evaluation for
+/// this point's error and non-error events, respecting rule priority.
+///
+/// Aggregate traffic points carry no concrete per-event attributes (no
+/// status codes, no service names), so only two synthetic event classes
+/// can be distinguished: "error" and "non-error". A rule is credited
+/// with a class's events if it's the highest-priority rule matching that
+/// class; a rule whose condition this model can't evaluate (anything
+/// beyond [`is_error_condition`] or the literal fallback) simply never
+/// gets credited, which is the honest answer given what the traffic
+/// pattern actually tells us.
+fn record_rule_hits(policy: &Policy, point: &TrafficPoint, matched: &mut BTreeMap<String, f64>) {
+    let error_events = point.events_per_second * point.error_rate;
+    let non_error_events = point.events_per_second * (1.0 - point.error_rate);
+
+    if let Some(rule) = first_matching_rule(policy, true) {
+        *matched.entry(rule.name.clone()).or_insert(0.0) += error_events;
+    }
+    if let Some(rule) = first_matching_rule(policy, false) {
+        *matched.entry(rule.name.clone()).or_insert(0.0) += non_error_events;
+    }
+}
+
+/// Finds the first (highest-priority) rule that matches the given
+/// synthetic event class.
+fn first_matching_rule(policy: &Policy, is_error: bool) -> Option<&Rule> {
+    policy
+        .rules
+        .iter()
+        .find(|rule| rule_matches_synthetic_class(rule, is_error))
+}
+
+/// Whether a rule matches one of the two synthetic event classes this
+/// simulation model can distinguish.
+fn rule_matches_synthetic_class(rule: &Rule, is_error: bool) -> bool {
+    if rule.match_expr.trim().eq_ignore_ascii_case("true") {
+        return true;
+    }
+    is_error && is_error_condition(&rule.match_expr)
+}
+
+/// Builds per-rule hit statistics from the matched-event totals
+/// accumulated over a traffic pattern.
+#[allow(clippy::cast_precision_loss, clippy::cast_sign_loss)]
+fn build_rule_coverage(
+    policy: &Policy,
+    matched: &BTreeMap<String, f64>,
+    total_events: f64,
+) -> RuleCoverage {
+    let hits = policy
+        .rules
+        .iter()
+        .map(|rule| {
+            let matched_events = matched.get(&rule.name).copied().unwrap_or(0.0);
+            let hit_count = matched_events.round() as u64;
+            let hit_fraction = if total_events > 0.0 {
+                matched_events / total_events
+            } else {
+                0.0
+            };
+
+            RuleHit {
+                rule_name: rule.name.clone(),
+                hit_count,
+                hit_fraction,
+                is_fallback: rule.match_expr == "true",
+                dead: hit_count == 0,
+            }
+        })
+        .collect();
+
+    RuleCoverage { hits }
 }
 
 #[cfg(test)]
@@ -430,6 +1283,73 @@ mod tests {
         ])
     }
 
+    #[test]
+    fn rule_coverage_credits_hits_to_both_rules() {
+        let policy = sample_policy();
+        let traffic = sample_traffic();
+        let simulator = Simulator::new(10000.0);
+
+        let result = simulator.simulate(&policy, &traffic);
+
+        assert!(result.rule_coverage.fully_exercised());
+        assert!(result.rule_coverage.dead_rules().next().is_none());
+        assert!(result
+            .rule_coverage
+            .hits
+            .iter()
+            .find(|hit| hit.rule_name == "keep-errors")
+            .unwrap()
+            .hit_count
+            > 0);
+    }
+
+    #[test]
+    fn rule_coverage_flags_a_rule_the_model_never_matches() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "checkout-only",
+            "service.name == \"checkout\"",
+            Action::Keep,
+            100,
+        ));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.1), 0));
+
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let traffic = TrafficPattern::from_points(vec![TrafficPoint::new(base, 1000.0)]);
+        let simulator = Simulator::new(10000.0);
+
+        let result = simulator.simulate(&policy, &traffic);
+
+        assert!(!result.rule_coverage.fully_exercised());
+        let dead: Vec<_> = result.rule_coverage.dead_rules().collect();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].rule_name, "checkout-only");
+    }
+
+    #[test]
+    fn fallback_rule_is_exempt_from_fully_exercised() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("keep-errors", "error", Action::Keep, 100));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.1), 0));
+
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // All traffic is errors, so the fallback never fires.
+        let traffic =
+            TrafficPattern::from_points(vec![TrafficPoint::new(base, 1000.0).with_error_rate(1.0)]);
+        let simulator = Simulator::new(10000.0);
+
+        let result = simulator.simulate(&policy, &traffic);
+
+        let fallback = result
+            .rule_coverage
+            .hits
+            .iter()
+            .find(|hit| hit.rule_name == "fallback")
+            .unwrap();
+        assert!(fallback.dead);
+        assert!(result.rule_coverage.fully_exercised());
+    }
+
     #[test]
     fn simulation_within_budget() {
         let policy = sample_policy();
@@ -508,4 +1428,355 @@ mod tests {
         assert!((violation.excess_events - 500.0).abs() < f64::EPSILON);
         assert!((violation.excess_percent - 50.0).abs() < f64::EPSILON);
     }
+
+    /// A policy that keeps 100% of traffic, so `kept_eps` tracks
+    /// `incoming_eps` directly - useful for budget-model tests that care
+    /// about bucket arithmetic, not sampling.
+    fn full_pass_policy() -> Policy {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(1.0), 0));
+        policy
+    }
+
+    #[test]
+    fn token_bucket_allows_a_burst_within_capacity() {
+        let policy = full_pass_policy();
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // A single one-second burst at 2x budget, exactly matching the
+        // bucket's capacity (budget * burst_seconds).
+        let traffic = TrafficPattern::from_points(vec![
+            TrafficPoint::new(base, 2000.0),
+            TrafficPoint::new(base + chrono::Duration::seconds(1), 0.0),
+        ]);
+
+        let instantaneous = Simulator::new(1000.0);
+        let instant_result = instantaneous.simulate(&policy, &traffic);
+        assert!(!instant_result.is_compliant());
+
+        let bucketed = Simulator::new(1000.0).with_budget_model(BudgetModel::TokenBucket {
+            burst_seconds: 2.0,
+            credit_window_seconds: 60.0,
+        });
+        let bucket_result = bucketed.simulate(&policy, &traffic);
+        assert!(bucket_result.is_compliant());
+    }
+
+    #[test]
+    fn token_bucket_flags_sustained_overage_once_capacity_drains() {
+        let policy = full_pass_policy();
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // 2x budget sustained for 5 seconds drains a 5-second-capacity
+        // bucket, so the later points should violate even though the
+        // burst allowance absorbs the early ones.
+        let traffic = TrafficPattern::from_points(
+            (0..6)
+                .map(|i| TrafficPoint::new(base + chrono::Duration::seconds(i), 200.0))
+                .collect(),
+        );
+
+        let simulator = Simulator::new(100.0).with_budget_model(BudgetModel::TokenBucket {
+            burst_seconds: 5.0,
+            credit_window_seconds: 60.0,
+        });
+        let result = simulator.simulate(&policy, &traffic);
+
+        assert!(!result.is_compliant());
+    }
+
+    #[test]
+    fn token_bucket_credit_expires_after_window() {
+        let policy = full_pass_policy();
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // Drain the bucket, sit idle long enough to bank a full refill,
+        // then stay idle just long enough that the banked credit ages
+        // past a short expiration window before a short, sharp burst.
+        let traffic = TrafficPattern::from_points(vec![
+            TrafficPoint::new(base, 500.0),
+            TrafficPoint::new(base + chrono::Duration::milliseconds(1000), 0.0),
+            TrafficPoint::new(base + chrono::Duration::milliseconds(9990), 0.0),
+            TrafficPoint::new(base + chrono::Duration::milliseconds(19_980), 1000.0),
+            TrafficPoint::new(base + chrono::Duration::milliseconds(20_010), 0.0),
+        ]);
+
+        let short_window = Simulator::new(100.0).with_budget_model(BudgetModel::TokenBucket {
+            burst_seconds: 5.0,
+            credit_window_seconds: 10.0,
+        });
+        let expired_result = short_window.simulate(&policy, &traffic);
+        assert!(!expired_result.is_compliant());
+
+        let long_window = Simulator::new(100.0).with_budget_model(BudgetModel::TokenBucket {
+            burst_seconds: 5.0,
+            credit_window_seconds: 1000.0,
+        });
+        let fresh_result = long_window.simulate(&policy, &traffic);
+        assert!(fresh_result.is_compliant());
+    }
+
+    #[test]
+    fn adaptive_sampling_sheds_to_track_budget() {
+        let policy = full_pass_policy();
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // Sustained 2x-budget traffic: with no smoothing lag (window 1)
+        // the controller should shed exactly half of it every point,
+        // landing right at budget instead of violating.
+        let traffic = TrafficPattern::from_points(
+            (0..3)
+                .map(|i| TrafficPoint::new(base + chrono::Duration::seconds(i), 200.0))
+                .collect(),
+        );
+
+        let static_result = Simulator::new(100.0).simulate(&policy, &traffic);
+        assert!(!static_result.is_compliant());
+
+        let adaptive =
+            Simulator::new(100.0).with_sampling_mode(SamplingMode::Adaptive { window: 1 });
+        let adaptive_result = adaptive.simulate(&policy, &traffic);
+
+        assert!(adaptive_result.is_compliant());
+        assert!((adaptive_result.summary.peak_shed_probability - 0.5).abs() < 1e-9);
+        for point in &adaptive_result.timeline {
+            assert!((point.kept_eps - 100.0).abs() < 1e-9);
+            assert!((point.sample_rate - 0.5).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn adaptive_sampling_window_smooths_a_single_spike() {
+        let policy = full_pass_policy();
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // A single spike surrounded by traffic well under budget.
+        let traffic = TrafficPattern::from_points(
+            [50.0, 50.0, 500.0, 50.0, 50.0]
+                .into_iter()
+                .enumerate()
+                .map(|(i, eps)| {
+                    #[allow(clippy::cast_possible_wrap)]
+                    TrafficPoint::new(base + chrono::Duration::seconds(i as i64), eps)
+                })
+                .collect(),
+        );
+
+        let reactive = Simulator::new(100.0)
+            .with_sampling_mode(SamplingMode::Adaptive { window: 1 })
+            .simulate(&policy, &traffic);
+        let smoothed = Simulator::new(100.0)
+            .with_sampling_mode(SamplingMode::Adaptive { window: 3 })
+            .simulate(&policy, &traffic);
+
+        // Reacting to the spike alone sheds harder than averaging it in
+        // with two points of quiet traffic either side.
+        assert!(smoothed.summary.peak_shed_probability < reactive.summary.peak_shed_probability);
+        assert!(smoothed.summary.peak_shed_probability > 0.0);
+    }
+
+    #[test]
+    fn static_sampling_never_sheds() {
+        let policy = sample_policy();
+        let traffic = sample_traffic();
+        let simulator = Simulator::new(1000.0); // Static is the default.
+
+        let result = simulator.simulate(&policy, &traffic);
+
+        assert_eq!(result.summary.peak_shed_probability, 0.0);
+    }
+
+    #[test]
+    fn aimd_decreases_rate_to_track_a_sustained_overage() {
+        let policy = full_pass_policy(); // AIMD starts at the policy's rate of 1.0.
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let traffic = TrafficPattern::from_points(
+            (0..10)
+                .map(|i| TrafficPoint::new(base + chrono::Duration::seconds(i), 1000.0))
+                .collect(),
+        );
+
+        let aimd = Simulator::new(100.0).with_sampling_mode(SamplingMode::Aimd {
+            increase_step: 0.02,
+            decrease_factor: 0.85,
+        });
+        let result = aimd.simulate(&policy, &traffic);
+
+        // The controller should back the rate down from its 1.0 starting
+        // point toward the budget, landing the run's kept total well
+        // below what the static (1.0) rate would have kept.
+        let first_rate = result.timeline.first().unwrap().sample_rate;
+        let last_rate = result.timeline.last().unwrap().sample_rate;
+        assert!((first_rate - 1.0).abs() < 1e-9);
+        assert!(last_rate < first_rate);
+        assert!(result.summary.total_kept < result.summary.static_total_kept);
+    }
+
+    #[test]
+    fn aimd_holds_rate_steady_once_inside_the_target_band() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.5), 0));
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // 190 eps at the policy's starting 0.5 rate keeps exactly 95,
+        // inside the band between the low watermark (90) and the budget
+        // (100), so the controller should never move off the starting
+        // rate.
+        let traffic = TrafficPattern::from_points(
+            (0..5)
+                .map(|i| TrafficPoint::new(base + chrono::Duration::seconds(i), 190.0))
+                .collect(),
+        );
+
+        let aimd = Simulator::new(100.0).with_sampling_mode(SamplingMode::Aimd {
+            increase_step: 0.02,
+            decrease_factor: 0.85,
+        });
+        let result = aimd.simulate(&policy, &traffic);
+
+        for point in &result.timeline {
+            assert!((point.sample_rate - 0.5).abs() < 1e-9);
+            assert!((point.kept_eps - 95.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn aimd_oscillates_around_budget_for_steady_overage() {
+        let policy = full_pass_policy();
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // Sustained 2x-budget traffic: the rate should repeatedly
+        // overshoot past the point where it sheds enough, get cut back,
+        // then climb again - a sawtooth rather than a single settle.
+        let traffic = TrafficPattern::from_points(
+            (0..40)
+                .map(|i| TrafficPoint::new(base + chrono::Duration::seconds(i), 200.0))
+                .collect(),
+        );
+
+        let aimd = Simulator::new(100.0).with_sampling_mode(SamplingMode::Aimd {
+            increase_step: 0.02,
+            decrease_factor: 0.85,
+        });
+        let result = aimd.simulate(&policy, &traffic);
+
+        let rates: Vec<f64> = result.timeline.iter().map(|p| p.sample_rate).collect();
+        let increased = rates.windows(2).any(|w| w[1] > w[0]);
+        let decreased = rates.windows(2).any(|w| w[1] < w[0]);
+        assert!(increased && decreased);
+    }
+
+    #[test]
+    fn optimize_finds_the_compliant_rate_for_sustained_overage() {
+        let policy = full_pass_policy(); // fallback starts at Sample(1.0).
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let traffic = TrafficPattern::from_points(
+            (0..10)
+                .map(|i| TrafficPoint::new(base + chrono::Duration::seconds(i), 1000.0))
+                .collect(),
+        );
+
+        let simulator = Simulator::new(100.0);
+        let result = simulator.optimize(&policy, &traffic);
+
+        assert_eq!(result.points_over_budget, 0);
+        assert!((result.total_dropped - 9000.0).abs() < 1e-6);
+        match result.policy.rules[0].action {
+            Action::Sample(rate) => assert!((rate - 0.1).abs() < 1e-9),
+            ref other => panic!("expected a Sample action, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn optimize_leaves_non_sample_rules_untouched() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("keep-errors", "error", Action::Keep, 100));
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(1.0), 0));
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let traffic = TrafficPattern::from_points(
+            (0..5)
+                .map(|i| {
+                    TrafficPoint::new(base + chrono::Duration::seconds(i), 500.0)
+                        .with_error_rate(0.1)
+                })
+                .collect(),
+        );
+
+        let simulator = Simulator::new(100.0);
+        let result = simulator.optimize(&policy, &traffic);
+
+        let keep_rule = result
+            .policy
+            .rules
+            .iter()
+            .find(|rule| rule.name == "keep-errors")
+            .unwrap();
+        assert_eq!(keep_rule.action, Action::Keep);
+    }
+
+    #[test]
+    fn optimize_never_leaves_more_waste_than_the_original_static_rate() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.5), 0));
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        // 150 eps at 0.5 already stays under a budget of 100, but 0.6
+        // would keep more while still complying.
+        let traffic = TrafficPattern::from_points(
+            (0..5)
+                .map(|i| TrafficPoint::new(base + chrono::Duration::seconds(i), 150.0))
+                .collect(),
+        );
+
+        let simulator = Simulator::new(100.0);
+        let static_result = simulator.simulate(&policy, &traffic);
+        let optimized = simulator.optimize(&policy, &traffic);
+
+        assert_eq!(optimized.points_over_budget, 0);
+        assert!(optimized.total_dropped <= static_result.summary.total_dropped + 1e-9);
+    }
+
+    #[test]
+    fn monte_carlo_is_reproducible_for_the_same_seed() {
+        let policy = sample_policy();
+        let traffic = sample_traffic();
+        let simulator = Simulator::new(10000.0);
+
+        let first = simulator.simulate_monte_carlo(&policy, &traffic, 50, 7);
+        let second = simulator.simulate_monte_carlo(&policy, &traffic, 50, 7);
+
+        assert_eq!(
+            first.percent_time_over_budget.p50,
+            second.percent_time_over_budget.p50
+        );
+        assert!((first.compliance_probability - second.compliance_probability).abs() < 1e-9);
+    }
+
+    #[test]
+    fn monte_carlo_reports_near_certain_compliance_for_a_generous_budget() {
+        let policy = full_pass_policy();
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let traffic = TrafficPattern::from_points(
+            (0..10)
+                .map(|i| TrafficPoint::new(base + chrono::Duration::seconds(i), 100.0))
+                .collect(),
+        );
+
+        let simulator = Simulator::new(100_000.0);
+        let result = simulator.simulate_monte_carlo(&policy, &traffic, 100, 1);
+
+        assert!(result.compliance_probability > 0.99);
+    }
+
+    #[test]
+    fn monte_carlo_reports_partial_compliance_for_a_tight_budget() {
+        let policy = full_pass_policy();
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let traffic = TrafficPattern::from_points(
+            (0..10)
+                .map(|i| TrafficPoint::new(base + chrono::Duration::seconds(i), 1000.0))
+                .collect(),
+        );
+
+        let simulator = Simulator::new(1000.0);
+        let result = simulator.simulate_monte_carlo(&policy, &traffic, 200, 11);
+
+        assert!(result.compliance_probability < 1.0);
+        assert!(
+            result.percent_time_over_budget.p50 <= result.percent_time_over_budget.p95
+                && result.percent_time_over_budget.p95 <= result.percent_time_over_budget.p99
+        );
+    }
 }
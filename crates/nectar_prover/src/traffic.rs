@@ -4,11 +4,58 @@
 //! enabling realistic budget compliance verification.
 
 use crate::error::{Error, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Timelike, Utc};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::Read;
 use std::path::Path;
 
+/// Hour of day (0-23) around which synthetic diurnal traffic peaks.
+const SYNTHETIC_PEAK_HOUR: f64 = 14.0;
+
+/// Default number of CSV records between
+/// [`TrafficPattern::from_csv_reader_with_progress`] progress callbacks.
+const DEFAULT_PROGRESS_INTERVAL: usize = 4_000_000;
+
+/// Magic bytes identifying a [`TrafficPattern`] binary cache file (see
+/// [`TrafficPattern::to_binary_file`]), checked before the body is parsed
+/// so a stale or unrelated file is rejected cleanly.
+const BINARY_MAGIC: &[u8; 8] = b"NCTRTRFB";
+
+/// Current binary cache format version. Bump this, and give
+/// [`TrafficPattern::from_binary_file`] an explicit migration path, if
+/// the on-disk layout ever changes.
+const BINARY_VERSION: u8 = 1;
+
+/// Slices `len` bytes starting at `*cursor`, advancing it past them, or
+/// returns an error if the buffer is too short.
+fn read_bytes<'a>(bytes: &'a [u8], cursor: &mut usize, len: usize) -> Result<&'a [u8]> {
+    let end = cursor
+        .checked_add(len)
+        .ok_or_else(|| Error::InvalidTraffic("binary cache file is truncated".to_string()))?;
+    let slice = bytes
+        .get(*cursor..end)
+        .ok_or_else(|| Error::InvalidTraffic("binary cache file is truncated".to_string()))?;
+    *cursor = end;
+    Ok(slice)
+}
+
+/// Reads a fixed-size little-endian byte array, for `from_le_bytes`.
+fn read_array<const N: usize>(bytes: &[u8], cursor: &mut usize) -> Result<[u8; N]> {
+    read_bytes(bytes, cursor, N)?
+        .try_into()
+        .map_err(|_| Error::InvalidTraffic("binary cache file is truncated".to_string()))
+}
+
+/// Reads `count` consecutive little-endian `f64`s into a `Vec`.
+fn read_f64_column(bytes: &[u8], cursor: &mut usize, count: usize) -> Result<Vec<f64>> {
+    (0..count)
+        .map(|_| read_array(bytes, cursor).map(f64::from_le_bytes))
+        .collect()
+}
+
 /// A single data point in a traffic pattern.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TrafficPoint {
@@ -51,6 +98,206 @@ impl TrafficPoint {
     }
 }
 
+/// A temporary spike layered on top of a [`TrafficPattern::synthetic`]
+/// baseline, e.g. a flash sale or a deploy-triggered retry storm.
+#[derive(Debug, Clone, Copy)]
+pub struct BurstEvent {
+    /// Offset from the pattern's start time at which the burst begins.
+    pub offset: chrono::Duration,
+    /// How long the burst lasts.
+    pub duration: chrono::Duration,
+    /// Multiplier applied to the baseline EPS while the burst is active.
+    pub multiplier: f64,
+}
+
+impl BurstEvent {
+    /// Creates a new burst event.
+    #[must_use]
+    pub const fn new(
+        offset: chrono::Duration,
+        duration: chrono::Duration,
+        multiplier: f64,
+    ) -> Self {
+        Self {
+            offset,
+            duration,
+            multiplier,
+        }
+    }
+
+    fn is_active_at(&self, elapsed: chrono::Duration) -> bool {
+        elapsed >= self.offset && elapsed <= self.offset + self.duration
+    }
+}
+
+/// Configuration for [`TrafficPattern::synthetic`].
+#[derive(Debug, Clone)]
+pub struct SyntheticTrafficConfig {
+    /// Timestamp of the first sample.
+    pub start: DateTime<Utc>,
+    /// Total span of the generated pattern.
+    pub duration: chrono::Duration,
+    /// Spacing between samples.
+    pub interval: chrono::Duration,
+    /// Events per second at the trough of the diurnal cycle.
+    pub baseline_eps: f64,
+    /// How far the diurnal cycle swings above (and below) baseline. `0.5`
+    /// means the daily peak reaches 1.5x baseline and the trough 0.5x.
+    pub peak_multiplier: f64,
+    /// Temporary spikes layered on top of the diurnal baseline.
+    pub bursts: Vec<BurstEvent>,
+    /// Standard deviation of the Gaussian jitter added to each sample's EPS.
+    pub jitter_stddev: f64,
+    /// Error rate at zero load.
+    pub base_error_rate: f64,
+    /// How much the error rate rises as load approaches the theoretical peak.
+    pub error_rate_gain: f64,
+    /// Seed for the deterministic jitter RNG.
+    pub seed: u64,
+}
+
+impl SyntheticTrafficConfig {
+    /// Creates a config with no jitter, bursts, or error-rate growth - just
+    /// a pure diurnal curve at half-amplitude around `baseline_eps`.
+    #[must_use]
+    pub fn new(
+        start: DateTime<Utc>,
+        duration: chrono::Duration,
+        interval: chrono::Duration,
+        baseline_eps: f64,
+    ) -> Self {
+        Self {
+            start,
+            duration,
+            interval,
+            baseline_eps,
+            peak_multiplier: 0.5,
+            bursts: Vec::new(),
+            jitter_stddev: 0.0,
+            base_error_rate: 0.0,
+            error_rate_gain: 0.0,
+            seed: 0,
+        }
+    }
+
+    /// Sets how far the diurnal cycle swings above and below baseline.
+    #[must_use]
+    pub fn with_peak_multiplier(mut self, peak_multiplier: f64) -> Self {
+        self.peak_multiplier = peak_multiplier;
+        self
+    }
+
+    /// Sets the burst events layered on top of the diurnal baseline.
+    #[must_use]
+    pub fn with_bursts(mut self, bursts: Vec<BurstEvent>) -> Self {
+        self.bursts = bursts;
+        self
+    }
+
+    /// Sets the standard deviation and seed of the Gaussian EPS jitter.
+    #[must_use]
+    pub fn with_jitter(mut self, stddev: f64, seed: u64) -> Self {
+        self.jitter_stddev = stddev;
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the error-rate-under-load model: `base + gain * (eps / peak_eps)`.
+    #[must_use]
+    pub fn with_error_model(mut self, base_error_rate: f64, gain: f64) -> Self {
+        self.base_error_rate = base_error_rate;
+        self.error_rate_gain = gain;
+        self
+    }
+}
+
+/// Which metric names to pull out of a Prometheus-style range-query JSON
+/// response (see [`TrafficPattern::from_range_query_json`]). Only
+/// `eps_metric` is required; the error-rate and p99-latency series are
+/// joined by timestamp on top of it when configured.
+#[derive(Debug, Clone)]
+pub struct RangeQueryMapping {
+    /// `__name__` label value of the series to use as `events_per_second`.
+    pub eps_metric: String,
+    /// `__name__` label value of the series to join as `error_rate`.
+    pub error_rate_metric: Option<String>,
+    /// `__name__` label value of the series to join as `p99_latency`.
+    pub p99_latency_metric: Option<String>,
+}
+
+impl RangeQueryMapping {
+    /// Creates a mapping that only pulls out the EPS series.
+    #[must_use]
+    pub fn new(eps_metric: impl Into<String>) -> Self {
+        Self {
+            eps_metric: eps_metric.into(),
+            error_rate_metric: None,
+            p99_latency_metric: None,
+        }
+    }
+
+    /// Sets the metric name to join as `error_rate`.
+    #[must_use]
+    pub fn with_error_rate_metric(mut self, metric: impl Into<String>) -> Self {
+        self.error_rate_metric = Some(metric.into());
+        self
+    }
+
+    /// Sets the metric name to join as `p99_latency`.
+    #[must_use]
+    pub fn with_p99_latency_metric(mut self, metric: impl Into<String>) -> Self {
+        self.p99_latency_metric = Some(metric.into());
+        self
+    }
+}
+
+/// Top-level shape of a Prometheus range-query response's `data` field.
+#[derive(Debug, Deserialize)]
+struct RangeQueryResponse {
+    data: RangeQueryData,
+}
+
+#[derive(Debug, Deserialize)]
+struct RangeQueryData {
+    result: Vec<RangeQuerySeries>,
+}
+
+/// A single time series: labels (including `__name__`) plus
+/// `[unix_timestamp, "string_value"]` samples.
+#[derive(Debug, Deserialize)]
+struct RangeQuerySeries {
+    metric: HashMap<String, String>,
+    values: Vec<(f64, String)>,
+}
+
+impl RangeQuerySeries {
+    fn name(&self) -> Option<&str> {
+        self.metric.get("__name__").map(String::as_str)
+    }
+
+    /// Parses this series' samples into a map of epoch-millisecond
+    /// timestamp to value, for joining onto another series by timestamp.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn values_by_millis(&self) -> Result<HashMap<i64, f64>> {
+        self.values
+            .iter()
+            .map(|(secs, value)| {
+                let value: f64 = value.parse().map_err(|_| {
+                    Error::InvalidTraffic(format!("invalid range query value '{value}'"))
+                })?;
+                Ok((timestamp_millis_from_secs(*secs), value))
+            })
+            .collect()
+    }
+}
+
+/// Converts Prometheus' float (possibly fractional) unix seconds into
+/// epoch milliseconds.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn timestamp_millis_from_secs(secs: f64) -> i64 {
+    (secs * 1000.0).round() as i64
+}
+
 /// A time-series traffic pattern.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TrafficPattern {
@@ -139,6 +386,20 @@ impl TrafficPattern {
         self.points.iter().map(|p| p.events_per_second).sum()
     }
 
+    /// Returns the total time the pattern spends with `events_per_second`
+    /// above `threshold`, measured as the gap to the following sample -
+    /// budget compliance is dominated by how long a spike lasts, not just
+    /// how high it reaches. The final point's trailing duration isn't
+    /// counted, since there's no following sample to bound it.
+    #[must_use]
+    pub fn duration_above(&self, threshold: f64) -> chrono::Duration {
+        self.points
+            .windows(2)
+            .filter(|pair| pair[0].events_per_second > threshold)
+            .map(|pair| pair[1].timestamp - pair[0].timestamp)
+            .fold(chrono::Duration::zero(), |acc, gap| acc + gap)
+    }
+
     /// Loads a traffic pattern from a CSV file.
     ///
     /// Expected format:
@@ -163,6 +424,25 @@ impl TrafficPattern {
     ///
     /// Returns an error if the CSV cannot be parsed.
     pub fn from_csv_reader<R: Read>(reader: R) -> Result<Self> {
+        Self::from_csv_reader_with_progress(reader, DEFAULT_PROGRESS_INTERVAL, |_| {})
+    }
+
+    /// Loads a traffic pattern from a CSV reader, the same as
+    /// [`Self::from_csv_reader`], but invokes `on_progress` with the
+    /// running record count every `progress_interval` records (pass `0`
+    /// to disable the callback). Records stream straight into the
+    /// pattern's single `points` vector rather than being collected into
+    /// an intermediate `Vec<CsvRecord>` first, so a CLI can show ingest
+    /// progress on a multi-million-row export without doubling memory use.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the CSV cannot be parsed.
+    pub fn from_csv_reader_with_progress<R: Read>(
+        reader: R,
+        progress_interval: usize,
+        mut on_progress: impl FnMut(usize),
+    ) -> Result<Self> {
         let mut csv_reader = csv::ReaderBuilder::new()
             .has_headers(true)
             .flexible(true)
@@ -170,11 +450,14 @@ impl TrafficPattern {
 
         let mut pattern = Self::new();
 
-        for result in csv_reader.deserialize() {
-            let record: CsvRecord = result.map_err(|e| {
-                Error::InvalidTraffic(format!("CSV parse error: {e}"))
-            })?;
+        for (i, result) in csv_reader.deserialize::<CsvRecord>().enumerate() {
+            let record =
+                result.map_err(|e| Error::InvalidTraffic(format!("CSV parse error: {e}")))?;
             pattern.add_point(record.into_point()?);
+
+            if progress_interval > 0 && (i + 1) % progress_interval == 0 {
+                on_progress(i + 1);
+            }
         }
 
         if pattern.is_empty() {
@@ -187,6 +470,246 @@ impl TrafficPattern {
         Ok(pattern)
     }
 
+    /// Loads a traffic pattern from a Prometheus-style range-query JSON
+    /// response: `{"data": {"result": [{"metric": {...}, "values": [[ts,
+    /// "value"], ...]}, ...]}}`. `mapping.eps_metric` selects the series
+    /// (by its `__name__` label) to use as `events_per_second`; the
+    /// optional error-rate and p99-latency series are joined onto it by
+    /// millisecond-precision timestamp, the same way separate observability
+    /// exports line up. A timestamp missing from a joined series defaults
+    /// that field to `0.0`, matching [`CsvRecord::into_point`]'s handling
+    /// of absent CSV columns.
+    ///
+    /// Timestamps arrive as possibly-fractional unix seconds and values as
+    /// JSON strings, both standard Prometheus API conventions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON cannot be parsed, the configured
+    /// `eps_metric` has no matching series, or the resulting pattern is
+    /// empty.
+    pub fn from_range_query_json<R: Read>(reader: R, mapping: &RangeQueryMapping) -> Result<Self> {
+        let response: RangeQueryResponse = serde_json::from_reader(reader)
+            .map_err(|e| Error::InvalidTraffic(format!("range query JSON parse error: {e}")))?;
+
+        if response.data.result.is_empty() {
+            return Err(Error::InvalidTraffic(
+                "traffic pattern is empty".to_string(),
+            ));
+        }
+
+        let eps_series = response
+            .data
+            .result
+            .iter()
+            .find(|series| series.name() == Some(mapping.eps_metric.as_str()))
+            .ok_or_else(|| {
+                Error::InvalidTraffic(format!(
+                    "no series found for eps metric '{}'",
+                    mapping.eps_metric
+                ))
+            })?;
+
+        let find_joined = |metric: &Option<String>| -> Result<Option<HashMap<i64, f64>>> {
+            metric
+                .as_ref()
+                .map(|name| {
+                    response
+                        .data
+                        .result
+                        .iter()
+                        .find(|series| series.name() == Some(name.as_str()))
+                        .ok_or_else(|| {
+                            Error::InvalidTraffic(format!("no series found for metric '{name}'"))
+                        })
+                        .and_then(RangeQuerySeries::values_by_millis)
+                })
+                .transpose()
+        };
+
+        let error_rates = find_joined(&mapping.error_rate_metric)?;
+        let p99_latencies = find_joined(&mapping.p99_latency_metric)?;
+
+        let mut pattern = Self::new();
+        for (secs, value) in &eps_series.values {
+            let events_per_second: f64 = value.parse().map_err(|_| {
+                Error::InvalidTraffic(format!("invalid range query value '{value}'"))
+            })?;
+            let millis = timestamp_millis_from_secs(*secs);
+            let timestamp = DateTime::from_timestamp_millis(millis).ok_or_else(|| {
+                Error::InvalidTraffic(format!("invalid range query timestamp {secs}"))
+            })?;
+
+            let error_rate = error_rates
+                .as_ref()
+                .and_then(|joined| joined.get(&millis))
+                .copied()
+                .unwrap_or(0.0);
+            let p99_latency = p99_latencies
+                .as_ref()
+                .and_then(|joined| joined.get(&millis))
+                .copied()
+                .unwrap_or(0.0);
+
+            pattern.add_point(
+                TrafficPoint::new(timestamp, events_per_second)
+                    .with_error_rate(error_rate)
+                    .with_p99_latency(p99_latency),
+            );
+        }
+
+        if pattern.is_empty() {
+            return Err(Error::InvalidTraffic(
+                "traffic pattern is empty".to_string(),
+            ));
+        }
+
+        pattern.points.sort_by_key(|p| p.timestamp);
+
+        Ok(pattern)
+    }
+
+    /// Loads a traffic pattern from `csv_path`, using a `.bin` binary
+    /// cache sidecar (see [`Self::to_binary_file`]) instead of
+    /// re-parsing the CSV whenever one exists and is newer than it. If
+    /// no usable cache is found, parses the CSV and writes a fresh
+    /// sidecar for next time - best-effort, since a read-only cache
+    /// directory shouldn't stop the caller from getting a pattern back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if neither the cache nor the CSV can be read.
+    pub fn from_csv_file_cached(csv_path: impl AsRef<Path>) -> Result<Self> {
+        let csv_path = csv_path.as_ref();
+        let bin_path = csv_path.with_extension("bin");
+
+        if let Some(true) = Self::bin_cache_is_fresh(csv_path, &bin_path) {
+            return Self::from_binary_file(&bin_path);
+        }
+
+        let pattern = Self::from_csv_file(csv_path)?;
+        let _ = pattern.to_binary_file(&bin_path);
+        Ok(pattern)
+    }
+
+    /// Returns `Some(true)` if `bin_path` exists and was modified more
+    /// recently than `csv_path`, `Some(false)` if it exists but is
+    /// stale, or `None` if either file's metadata/mtime can't be read.
+    fn bin_cache_is_fresh(csv_path: &Path, bin_path: &Path) -> Option<bool> {
+        let csv_modified = std::fs::metadata(csv_path).ok()?.modified().ok()?;
+        let bin_modified = std::fs::metadata(bin_path).ok()?.modified().ok()?;
+        Some(bin_modified > csv_modified)
+    }
+
+    /// Serialises the pattern to a compact columnar binary cache file:
+    /// a magic/version header, the (optional) pattern name, the point
+    /// count, then delta-encoded epoch-millisecond timestamps and three
+    /// parallel `f64` arrays (`events_per_second`, `error_rate`,
+    /// `p99_latency`). Loading this is far cheaper than re-parsing a
+    /// multi-million-row CSV export, since there's no text to tokenize
+    /// and no per-field allocation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_binary_file(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(BINARY_MAGIC);
+        bytes.push(BINARY_VERSION);
+
+        let name_bytes = self.name.as_deref().unwrap_or_default().as_bytes();
+        bytes.extend_from_slice(&(name_bytes.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(name_bytes);
+
+        bytes.extend_from_slice(&(self.points.len() as u64).to_le_bytes());
+
+        let mut prev_ms = 0i64;
+        for (i, point) in self.points.iter().enumerate() {
+            let ms = point.timestamp.timestamp_millis();
+            let delta = if i == 0 { ms } else { ms - prev_ms };
+            bytes.extend_from_slice(&delta.to_le_bytes());
+            prev_ms = ms;
+        }
+        for point in &self.points {
+            bytes.extend_from_slice(&point.events_per_second.to_le_bytes());
+        }
+        for point in &self.points {
+            bytes.extend_from_slice(&point.error_rate.to_le_bytes());
+        }
+        for point in &self.points {
+            bytes.extend_from_slice(&point.p99_latency.to_le_bytes());
+        }
+
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Loads a pattern from a file written by [`Self::to_binary_file`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, its header doesn't
+    /// match [`BINARY_MAGIC`]/[`BINARY_VERSION`], or it is truncated.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_binary_file(path: impl AsRef<Path>) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let mut cursor = 0usize;
+
+        let magic = read_bytes(&bytes, &mut cursor, BINARY_MAGIC.len())?;
+        if magic != BINARY_MAGIC {
+            return Err(Error::InvalidTraffic(
+                "missing or invalid binary cache magic bytes".to_string(),
+            ));
+        }
+
+        let version = read_bytes(&bytes, &mut cursor, 1)?[0];
+        if version != BINARY_VERSION {
+            return Err(Error::InvalidTraffic(format!(
+                "unsupported binary cache version {version}, expected {BINARY_VERSION}"
+            )));
+        }
+
+        let name_len = u32::from_le_bytes(read_array(&bytes, &mut cursor)?) as usize;
+        let name_bytes = read_bytes(&bytes, &mut cursor, name_len)?;
+        let name = if name_bytes.is_empty() {
+            None
+        } else {
+            Some(String::from_utf8(name_bytes.to_vec()).map_err(|e| {
+                Error::InvalidTraffic(format!("invalid UTF-8 in cached pattern name: {e}"))
+            })?)
+        };
+
+        let point_count = u64::from_le_bytes(read_array(&bytes, &mut cursor)?) as usize;
+
+        let mut timestamps = Vec::with_capacity(point_count);
+        let mut running_ms = 0i64;
+        for i in 0..point_count {
+            let delta = i64::from_le_bytes(read_array(&bytes, &mut cursor)?);
+            running_ms = if i == 0 { delta } else { running_ms + delta };
+            timestamps.push(running_ms);
+        }
+
+        let eps = read_f64_column(&bytes, &mut cursor, point_count)?;
+        let error_rates = read_f64_column(&bytes, &mut cursor, point_count)?;
+        let p99_latencies = read_f64_column(&bytes, &mut cursor, point_count)?;
+
+        let mut points = Vec::with_capacity(point_count);
+        for i in 0..point_count {
+            let timestamp = DateTime::from_timestamp_millis(timestamps[i]).ok_or_else(|| {
+                Error::InvalidTraffic(format!("invalid cached timestamp {}", timestamps[i]))
+            })?;
+            points.push(TrafficPoint {
+                timestamp,
+                events_per_second: eps[i],
+                error_rate: error_rates[i],
+                p99_latency: p99_latencies[i],
+            });
+        }
+
+        Ok(Self { points, name })
+    }
+
     /// Creates a traffic pattern from raw data points.
     #[must_use]
     pub fn from_points(points: Vec<TrafficPoint>) -> Self {
@@ -195,6 +718,133 @@ impl TrafficPattern {
         pattern
     }
 
+    /// Generates a synthetic diurnal traffic pattern, for exercising
+    /// budget logic when no real CSV export is available.
+    ///
+    /// Each sample's baseline EPS follows `baseline * (1 + amplitude *
+    /// sin(2π * seconds_of_day / 86400 - phase))`, a sinusoid phased so the
+    /// daily peak lands around [`SYNTHETIC_PEAK_HOUR`]. Any
+    /// [`BurstEvent`] active at a sample multiplies its EPS further, the
+    /// result is clamped to non-negative, and deterministic Gaussian
+    /// jitter (seeded [`ChaCha8Rng`] via a Box-Muller transform) is added
+    /// on top. The error rate rises with load via
+    /// `base_error_rate + error_rate_gain * (eps / peak_eps)`.
+    #[must_use]
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    pub fn synthetic(config: &SyntheticTrafficConfig) -> Self {
+        use std::f64::consts::PI;
+
+        let interval_ms = config.interval.num_milliseconds().max(1);
+        let total_ms = config.duration.num_milliseconds().max(0);
+        let sample_count = total_ms / interval_ms + 1;
+
+        let phase = 2.0 * PI * (SYNTHETIC_PEAK_HOUR / 24.0) - PI / 2.0;
+        let peak_eps = (config.baseline_eps * (1.0 + config.peak_multiplier.max(0.0))).max(1.0);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+        let mut points = Vec::with_capacity(sample_count.max(0) as usize);
+
+        for i in 0..sample_count {
+            let elapsed = chrono::Duration::milliseconds(i * interval_ms);
+            let timestamp = config.start + elapsed;
+
+            let seconds_of_day = f64::from(timestamp.num_seconds_from_midnight());
+            let angle = 2.0 * PI * (seconds_of_day / 86_400.0) - phase;
+            let mut eps = config.baseline_eps * (1.0 + config.peak_multiplier * angle.sin());
+
+            for burst in &config.bursts {
+                if burst.is_active_at(elapsed) {
+                    eps *= burst.multiplier;
+                }
+            }
+
+            if config.jitter_stddev > 0.0 {
+                eps += config.jitter_stddev * gaussian_sample(&mut rng);
+            }
+            eps = eps.max(0.0);
+
+            let error_rate = (config.base_error_rate + config.error_rate_gain * (eps / peak_eps))
+                .clamp(0.0, 1.0);
+
+            points.push(TrafficPoint::new(timestamp, eps).with_error_rate(error_rate));
+        }
+
+        Self {
+            points,
+            name: Some("synthetic".to_string()),
+        }
+    }
+
+    /// Returns the subset of points within `[start, end]`.
+    ///
+    /// Since [`from_points`](Self::from_points) and [`from_csv_reader`](Self::from_csv_reader)
+    /// both guarantee chronological order, the bounds are found with a
+    /// binary search rather than a linear scan.
+    #[must_use]
+    pub fn slice(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        let start_idx = self.points.partition_point(|p| p.timestamp < start);
+        let end_idx = self.points.partition_point(|p| p.timestamp <= end);
+
+        Self {
+            points: self.points[start_idx..end_idx].to_vec(),
+            name: self.name.clone(),
+        }
+    }
+
+    /// Downsamples the pattern into fixed-size `bucket` intervals.
+    ///
+    /// Points are grouped by `floor((timestamp - t0) / bucket)`, where `t0`
+    /// is the first point's timestamp. Each non-empty bucket emits one
+    /// [`TrafficPoint`] at the bucket's start timestamp: `events_per_second`
+    /// is the mean of the member points, while `error_rate` and
+    /// `p99_latency` are weighted by each point's `events_per_second` so a
+    /// quiet second doesn't dominate the bucket's error rate. Empty buckets
+    /// are skipped.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn resample(&self, bucket: chrono::Duration) -> Self {
+        let bucket_ms = bucket.num_milliseconds().max(1);
+
+        let Some(t0) = self.points.first().map(|p| p.timestamp) else {
+            return Self {
+                points: Vec::new(),
+                name: self.name.clone(),
+            };
+        };
+
+        let mut out = Vec::new();
+        let mut current: Option<ResampleBucket> = None;
+
+        for point in &self.points {
+            let index = (point.timestamp - t0).num_milliseconds() / bucket_ms;
+
+            match &mut current {
+                Some(bucket) if bucket.index == index => bucket.add(point),
+                _ => {
+                    if let Some(bucket) = current.take() {
+                        out.push(bucket.finish());
+                    }
+                    let start = t0 + chrono::Duration::milliseconds(index * bucket_ms);
+                    let mut bucket = ResampleBucket::new(index, start);
+                    bucket.add(point);
+                    current = Some(bucket);
+                }
+            }
+        }
+        if let Some(bucket) = current.take() {
+            out.push(bucket.finish());
+        }
+
+        Self {
+            points: out,
+            name: self.name.clone(),
+        }
+    }
+
     /// Finds the peak period (highest traffic window).
     ///
     /// Returns the index of the peak point.
@@ -237,6 +887,12 @@ impl TrafficPattern {
             / eps_values.len() as f64;
         let std_dev = variance.sqrt();
 
+        let peak_to_mean_ratio = if avg_eps > 0.0 {
+            peak_eps / avg_eps
+        } else {
+            0.0
+        };
+
         TrafficStats {
             point_count: self.points.len(),
             peak_eps,
@@ -246,8 +902,34 @@ impl TrafficPattern {
             avg_error_rate,
             max_error_rate,
             total_events: self.total_events(),
+            peak_to_mean_ratio,
+            percentiles: None,
         }
     }
+
+    /// Like [`Self::stats`], but also sorts a copy of the EPS values to
+    /// fill in [`TrafficStats::percentiles`]. Budget compliance is
+    /// dominated by tail spikes, not the mean, so p90/p95/p99 matter for
+    /// risk assessment - but the sort is `O(n log n)`, so it's opt-in
+    /// rather than part of the default (cheap) `stats()`.
+    #[must_use]
+    pub fn stats_with_percentiles(&self) -> TrafficStats {
+        let mut stats = self.stats();
+        if self.is_empty() {
+            return stats;
+        }
+
+        let mut eps_values: Vec<f64> = self.points.iter().map(|p| p.events_per_second).collect();
+        eps_values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        stats.percentiles = Some(TrafficPercentiles {
+            p50: percentile(&eps_values, 50.0),
+            p90: percentile(&eps_values, 90.0),
+            p95: percentile(&eps_values, 95.0),
+            p99: percentile(&eps_values, 99.0),
+        });
+        stats
+    }
 }
 
 /// Statistics about a traffic pattern.
@@ -269,6 +951,114 @@ pub struct TrafficStats {
     pub max_error_rate: f64,
     /// Total events in the pattern.
     pub total_events: f64,
+    /// Burstiness ratio: `peak_eps / avg_eps`. A policy sized to the
+    /// average can still blow its `budget_per_second` at this multiple.
+    pub peak_to_mean_ratio: f64,
+    /// EPS percentiles, filled in only by
+    /// [`TrafficPattern::stats_with_percentiles`]; `None` from the plain
+    /// [`TrafficPattern::stats`].
+    pub percentiles: Option<TrafficPercentiles>,
+}
+
+/// EPS percentiles computed by [`TrafficPattern::stats_with_percentiles`],
+/// via linear interpolation between ranks over the sorted samples.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrafficPercentiles {
+    /// Median events per second.
+    pub p50: f64,
+    /// 90th percentile events per second.
+    pub p90: f64,
+    /// 95th percentile events per second.
+    pub p95: f64,
+    /// 99th percentile events per second.
+    pub p99: f64,
+}
+
+/// Linearly interpolates the `p`th percentile (0-100) out of
+/// already-sorted `values`.
+fn percentile(sorted_values: &[f64], p: f64) -> f64 {
+    match sorted_values.len() {
+        0 => 0.0,
+        1 => sorted_values[0],
+        #[allow(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        len => {
+            let rank = (p / 100.0) * (len - 1) as f64;
+            let lower = rank.floor() as usize;
+            let upper = rank.ceil() as usize;
+            if lower == upper {
+                sorted_values[lower]
+            } else {
+                let frac = rank - lower as f64;
+                sorted_values[lower] + frac * (sorted_values[upper] - sorted_values[lower])
+            }
+        }
+    }
+}
+
+/// Draws one standard-normal sample via the Box-Muller transform, using
+/// the RNG's own uniform distribution rather than pulling in a
+/// distributions crate for a single use site.
+fn gaussian_sample(rng: &mut ChaCha8Rng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Accumulates the points falling into one [`TrafficPattern::resample`] bucket.
+struct ResampleBucket {
+    index: i64,
+    start: DateTime<Utc>,
+    eps_sum: f64,
+    weighted_error: f64,
+    weighted_latency: f64,
+    weight: f64,
+    count: usize,
+}
+
+impl ResampleBucket {
+    const fn new(index: i64, start: DateTime<Utc>) -> Self {
+        Self {
+            index,
+            start,
+            eps_sum: 0.0,
+            weighted_error: 0.0,
+            weighted_latency: 0.0,
+            weight: 0.0,
+            count: 0,
+        }
+    }
+
+    fn add(&mut self, point: &TrafficPoint) {
+        self.eps_sum += point.events_per_second;
+        self.weighted_error += point.error_rate * point.events_per_second;
+        self.weighted_latency += point.p99_latency * point.events_per_second;
+        self.weight += point.events_per_second;
+        self.count += 1;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn finish(self) -> TrafficPoint {
+        let mean_eps = self.eps_sum / self.count as f64;
+        let (error_rate, p99_latency) = if self.weight > 0.0 {
+            (
+                self.weighted_error / self.weight,
+                self.weighted_latency / self.weight,
+            )
+        } else {
+            (0.0, 0.0)
+        };
+
+        TrafficPoint {
+            timestamp: self.start,
+            events_per_second: mean_eps,
+            error_rate,
+            p99_latency,
+        }
+    }
 }
 
 /// CSV record for deserializing traffic data.
@@ -322,6 +1112,33 @@ mod tests {
         assert!((stats.peak_eps - 12000.0).abs() < f64::EPSILON);
         assert!((stats.min_eps - 4000.0).abs() < f64::EPSILON);
         assert!((stats.avg_eps - 7300.0).abs() < f64::EPSILON);
+        assert!((stats.peak_to_mean_ratio - 12000.0 / 7300.0).abs() < f64::EPSILON);
+        assert!(stats.percentiles.is_none());
+    }
+
+    #[test]
+    fn stats_with_percentiles_interpolates_between_ranks() {
+        let pattern = sample_pattern();
+        let stats = pattern.stats_with_percentiles();
+
+        let percentiles = stats.percentiles.expect("percentiles should be filled in");
+        assert!((percentiles.p50 - 7000.0).abs() < f64::EPSILON);
+        assert!((percentiles.p90 - 10600.0).abs() < 1e-9);
+        assert!((percentiles.p95 - 11300.0).abs() < 1e-9);
+        assert!((percentiles.p99 - 11860.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn duration_above_sums_gaps_where_the_prior_sample_exceeds_the_threshold() {
+        let pattern = sample_pattern();
+        let above = pattern.duration_above(6000.0);
+        assert_eq!(above, chrono::Duration::minutes(3));
+    }
+
+    #[test]
+    fn duration_above_is_zero_when_nothing_exceeds_the_threshold() {
+        let pattern = sample_pattern();
+        assert_eq!(pattern.duration_above(20_000.0), chrono::Duration::zero());
     }
 
     #[test]
@@ -373,6 +1190,290 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn from_range_query_json_maps_and_joins_series_by_timestamp() {
+        let json = r#"{
+            "data": {
+                "result": [
+                    {
+                        "metric": {"__name__": "http_requests_per_second"},
+                        "values": [[1705312800, "5000"], [1705312860, "8500"]]
+                    },
+                    {
+                        "metric": {"__name__": "http_error_rate"},
+                        "values": [[1705312800, "0.02"]]
+                    },
+                    {
+                        "metric": {"__name__": "http_p99_latency_ms"},
+                        "values": [[1705312800, "1.2"], [1705312860, "0.8"]]
+                    }
+                ]
+            }
+        }"#;
+
+        let mapping = RangeQueryMapping::new("http_requests_per_second")
+            .with_error_rate_metric("http_error_rate")
+            .with_p99_latency_metric("http_p99_latency_ms");
+
+        let pattern = TrafficPattern::from_range_query_json(json.as_bytes(), &mapping).unwrap();
+        assert_eq!(pattern.len(), 2);
+        assert!((pattern.points()[0].events_per_second - 5000.0).abs() < f64::EPSILON);
+        assert!((pattern.points()[0].error_rate - 0.02).abs() < f64::EPSILON);
+        assert!((pattern.points()[1].p99_latency - 0.8).abs() < f64::EPSILON);
+
+        // No error-rate sample at the second timestamp: defaults to 0.0.
+        assert!((pattern.points()[1].error_rate - 0.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn from_range_query_json_rejects_empty_result() {
+        let json = r#"{"data": {"result": []}}"#;
+        let mapping = RangeQueryMapping::new("http_requests_per_second");
+        let result = TrafficPattern::from_range_query_json(json.as_bytes(), &mapping);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn from_range_query_json_rejects_missing_eps_metric() {
+        let json = r#"{
+            "data": {
+                "result": [
+                    {"metric": {"__name__": "some_other_metric"}, "values": [[1705312800, "5000"]]}
+                ]
+            }
+        }"#;
+        let mapping = RangeQueryMapping::new("http_requests_per_second");
+        let result = TrafficPattern::from_range_query_json(json.as_bytes(), &mapping);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn from_csv_reader_with_progress_reports_every_interval() {
+        let csv_data = r#"timestamp,events_per_second
+2024-01-15T09:00:00Z,5000
+2024-01-15T09:01:00Z,8500
+2024-01-15T09:02:00Z,6000
+2024-01-15T09:03:00Z,7000
+"#;
+
+        let mut counts = Vec::new();
+        let pattern = TrafficPattern::from_csv_reader_with_progress(csv_data.as_bytes(), 2, |n| {
+            counts.push(n);
+        })
+        .unwrap();
+
+        assert_eq!(pattern.len(), 4);
+        assert_eq!(counts, vec![2, 4]);
+    }
+
+    #[test]
+    fn binary_round_trips_points_and_name_exactly() {
+        let mut pattern = sample_pattern();
+        pattern.name = Some("peak-window".to_string());
+
+        let path = std::env::temp_dir().join(format!(
+            "nectar-traffic-roundtrip-{}.bin",
+            std::process::id()
+        ));
+
+        pattern.to_binary_file(&path).unwrap();
+        let loaded = TrafficPattern::from_binary_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(loaded.points(), pattern.points());
+        assert_eq!(loaded.name, pattern.name);
+    }
+
+    #[test]
+    fn from_binary_file_rejects_bad_magic() {
+        let path = std::env::temp_dir().join(format!(
+            "nectar-traffic-badmagic-{}.bin",
+            std::process::id()
+        ));
+        std::fs::write(&path, b"not a valid cache file").unwrap();
+
+        let result = TrafficPattern::from_binary_file(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn from_csv_file_cached_uses_the_sidecar_when_it_is_newer() {
+        let dir = std::env::temp_dir().join(format!("nectar-traffic-cache-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let csv_path = dir.join("traffic.csv");
+        let bin_path = dir.join("traffic.bin");
+
+        std::fs::write(
+            &csv_path,
+            "timestamp,events_per_second\n2024-01-15T09:00:00Z,5000\n",
+        )
+        .unwrap();
+
+        // First load parses the CSV and writes the sidecar.
+        let first = TrafficPattern::from_csv_file_cached(&csv_path).unwrap();
+        assert_eq!(first.len(), 1);
+        assert!(bin_path.exists());
+
+        // A pattern written directly to the sidecar should now win,
+        // proving the second call reads the cache rather than the CSV.
+        // The sleep guards against filesystems with coarse mtime
+        // granularity reporting the same timestamp for both writes.
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        let mut cached = sample_pattern();
+        cached.name = Some("from-cache".to_string());
+        cached.to_binary_file(&bin_path).unwrap();
+
+        let second = TrafficPattern::from_csv_file_cached(&csv_path).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(second.name.as_deref(), Some("from-cache"));
+        assert_eq!(second.len(), 5);
+    }
+
+    #[test]
+    fn traffic_pattern_slice_keeps_points_in_range() {
+        let pattern = sample_pattern();
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+
+        let sliced = pattern.slice(
+            base + chrono::Duration::minutes(1),
+            base + chrono::Duration::minutes(3),
+        );
+
+        assert_eq!(sliced.len(), 3);
+        assert_eq!(sliced.points()[0].events_per_second, 8500.0);
+        assert_eq!(sliced.points()[2].events_per_second, 7000.0);
+    }
+
+    #[test]
+    fn traffic_pattern_slice_excludes_points_outside_bounds() {
+        let pattern = sample_pattern();
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+
+        let sliced = pattern.slice(base, base + chrono::Duration::seconds(30));
+        assert_eq!(sliced.len(), 1);
+        assert_eq!(sliced.points()[0].events_per_second, 5000.0);
+    }
+
+    #[test]
+    fn traffic_pattern_resample_averages_and_weighs_by_eps() {
+        let pattern = sample_pattern();
+
+        let resampled = pattern.resample(chrono::Duration::minutes(2));
+
+        // Buckets: [0,1]min -> 5000,8500 ; [2,3]min -> 12000,7000 ; [4]min -> 4000
+        assert_eq!(resampled.len(), 3);
+
+        let first = &resampled.points()[0];
+        assert!((first.events_per_second - 6750.0).abs() < f64::EPSILON);
+        let expected_error = (5000.0 * 0.02 + 8500.0 * 0.01) / (5000.0 + 8500.0);
+        assert!((first.error_rate - expected_error).abs() < 1e-9);
+
+        let last = &resampled.points()[2];
+        assert!((last.events_per_second - 4000.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn traffic_pattern_resample_skips_no_buckets_and_preserves_name() {
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 9, 0, 0).unwrap();
+        let pattern = TrafficPattern {
+            points: vec![
+                TrafficPoint::new(base, 100.0),
+                TrafficPoint::new(base + chrono::Duration::seconds(1), 200.0),
+            ],
+            name: Some("per-second export".to_string()),
+        };
+
+        let resampled = pattern.resample(chrono::Duration::seconds(1));
+        assert_eq!(resampled.len(), 2);
+        assert_eq!(resampled.name.as_deref(), Some("per-second export"));
+    }
+
+    #[test]
+    fn synthetic_is_deterministic_for_a_given_seed() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let config = SyntheticTrafficConfig::new(
+            start,
+            chrono::Duration::hours(6),
+            chrono::Duration::minutes(30),
+            1000.0,
+        )
+        .with_jitter(50.0, 42);
+
+        let a = TrafficPattern::synthetic(&config);
+        let b = TrafficPattern::synthetic(&config);
+
+        assert_eq!(a.points(), b.points());
+    }
+
+    #[test]
+    fn synthetic_produces_non_negative_eps_and_covers_the_requested_span() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let duration = chrono::Duration::hours(2);
+        let interval = chrono::Duration::minutes(15);
+        let config = SyntheticTrafficConfig::new(start, duration, interval, 500.0)
+            .with_peak_multiplier(0.9)
+            .with_jitter(200.0, 7);
+
+        let pattern = TrafficPattern::synthetic(&config);
+
+        assert_eq!(pattern.len(), 9);
+        assert!(pattern.points().iter().all(|p| p.events_per_second >= 0.0));
+        assert_eq!(pattern.points()[0].timestamp, start);
+    }
+
+    #[test]
+    fn synthetic_burst_raises_eps_within_its_window() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let burst = BurstEvent::new(
+            chrono::Duration::minutes(30),
+            chrono::Duration::minutes(10),
+            5.0,
+        );
+        let config = SyntheticTrafficConfig::new(
+            start,
+            chrono::Duration::hours(1),
+            chrono::Duration::minutes(10),
+            100.0,
+        )
+        .with_peak_multiplier(0.0)
+        .with_bursts(vec![burst]);
+
+        let pattern = TrafficPattern::synthetic(&config);
+
+        let during_burst = pattern.points()[3].events_per_second;
+        let outside_burst = pattern.points()[0].events_per_second;
+        assert!(during_burst > outside_burst * 4.0);
+    }
+
+    #[test]
+    fn synthetic_error_rate_rises_with_load() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let config = SyntheticTrafficConfig::new(
+            start,
+            chrono::Duration::hours(24),
+            chrono::Duration::hours(1),
+            1000.0,
+        )
+        .with_peak_multiplier(0.8)
+        .with_error_model(0.01, 0.2);
+
+        let pattern = TrafficPattern::synthetic(&config);
+        let peak_idx = pattern.find_peak_index().unwrap();
+        let peak_point = &pattern.points()[peak_idx];
+
+        let quietest_error_rate = pattern
+            .points()
+            .iter()
+            .map(|p| p.error_rate)
+            .fold(f64::INFINITY, f64::min);
+
+        assert!(peak_point.error_rate > quietest_error_rate);
+        assert!(peak_point.error_rate >= 0.01);
+    }
+
     #[test]
     fn traffic_point_builder() {
         let ts = Utc::now();
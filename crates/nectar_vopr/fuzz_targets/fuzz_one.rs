@@ -0,0 +1,14 @@
+//! cargo-fuzz (libFuzzer) entry point for `nectar_vopr::fuzz::fuzz_one`.
+//!
+//! Run with `cargo fuzz run fuzz_one` from `crates/nectar_vopr`. Shares
+//! the same target with `hfuzz_targets/fuzz_one.rs` - any crash file
+//! either fuzzer writes out replays deterministically through
+//! `nectar_vopr::fuzz::fuzz_one` on its own.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    nectar_vopr::fuzz::fuzz_one(data);
+});
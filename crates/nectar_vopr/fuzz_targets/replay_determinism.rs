@@ -0,0 +1,15 @@
+//! cargo-fuzz (libFuzzer) entry point for
+//! `nectar_vopr::fuzz::fuzz_replay_determinism`.
+//!
+//! Run with `cargo fuzz run replay_determinism` from `crates/nectar_vopr`.
+//! Shares the same target with `hfuzz_targets/replay_determinism.rs` - any
+//! crash file either fuzzer writes out replays deterministically through
+//! `nectar_vopr::fuzz::fuzz_replay_determinism` on its own.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    nectar_vopr::fuzz::fuzz_replay_determinism(data);
+});
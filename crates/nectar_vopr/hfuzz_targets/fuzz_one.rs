@@ -0,0 +1,14 @@
+//! honggfuzz entry point for `nectar_vopr::fuzz::fuzz_one`.
+//!
+//! Run with `cargo hfuzz run fuzz_one` (or `run-debug` for a debug build
+//! with full backtraces) from `crates/nectar_vopr`.
+
+use honggfuzz::fuzz;
+
+fn main() {
+    loop {
+        fuzz!(|data: &[u8]| {
+            nectar_vopr::fuzz::fuzz_one(data);
+        });
+    }
+}
@@ -0,0 +1,15 @@
+//! honggfuzz entry point for
+//! `nectar_vopr::fuzz::fuzz_replay_determinism`.
+//!
+//! Run with `cargo hfuzz run replay_determinism` (or `run-debug` for a
+//! debug build with full backtraces) from `crates/nectar_vopr`.
+
+use honggfuzz::fuzz;
+
+fn main() {
+    loop {
+        fuzz!(|data: &[u8]| {
+            nectar_vopr::fuzz::fuzz_replay_determinism(data);
+        });
+    }
+}
@@ -12,12 +12,18 @@
 #![allow(clippy::cast_precision_loss)]
 
 use crate::chaos::{chaos_campaign, ChaosConfig, ChaosInjector};
-use crate::replay::{PolicyEvolutionSim, SimAction, StepResult};
+use crate::ddmin::{minimize_actions, MinimalRepro};
+use crate::regressions::{self, RegressionEntry};
+use crate::replay::{PolicyEvolutionSim, ReplayArtifact, SimAction, StepResult};
+use crate::shrink::{shrink_panic, MinimizedPanic};
 use crate::synthetic::{SyntheticConfig, SyntheticCorpus};
+use crate::verify::{verify_property, Property, Verdict};
 use nectar_compiler::Compiler;
 use nectar_prover::Prover;
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 use toon_policy::{Action, Policy, Rule};
 
@@ -38,6 +44,31 @@ pub struct CampaignResult {
     pub failure_details: Vec<String>,
     /// Whether all invariants held.
     pub all_passed: bool,
+    /// On-disk corpus directory, for campaigns (like
+    /// [`run_fuzz_campaign`]) that persist a coverage-guided corpus
+    /// across runs so CI can archive it. `None` for campaigns that
+    /// don't maintain one.
+    pub corpus_path: Option<PathBuf>,
+    /// Count of distinct new crashing inputs discovered this run.
+    ///
+    /// Distinct from `failures`, which also covers the non-crash
+    /// invariant violations the other campaigns record.
+    pub new_crashes: usize,
+    /// A delta-debugged minimal reproducer for the first failure this
+    /// run hit, for campaigns (like [`run_evolution_campaign`]) that
+    /// record one. `None` for campaigns that don't, or that passed.
+    pub minimal_repro: Option<MinimalRepro>,
+    /// A self-contained, serializable record of this run's seed and
+    /// action trace, for campaigns (like [`run_evolution_campaign`])
+    /// that emit one. Feed it to [`replay_artifact`] to reconstruct the
+    /// identical run later. `None` for campaigns that don't record one.
+    pub replay_artifact: Option<ReplayArtifact>,
+    /// A delta-debugged minimal `(seed, SyntheticConfig, rules)` triple
+    /// reproducing the first panic this run hit, for campaigns (like
+    /// [`run_cascading_failure_campaign`] and [`run_decade_simulation`])
+    /// that shrink one via [`crate::shrink::shrink_panic`]. `None` for
+    /// campaigns that don't, or that panicked.
+    pub minimized_panic: Option<MinimizedPanic>,
 }
 
 impl CampaignResult {
@@ -57,6 +88,11 @@ impl CampaignResult {
             failures: 0,
             failure_details: Vec::new(),
             all_passed: true,
+            corpus_path: None,
+            new_crashes: 0,
+            minimal_repro: None,
+            replay_artifact: None,
+            minimized_panic: None,
         }
     }
 
@@ -71,6 +107,11 @@ impl CampaignResult {
             failures: failures.len(),
             failure_details: failures,
             all_passed: false,
+            corpus_path: None,
+            new_crashes: 0,
+            minimal_repro: None,
+            replay_artifact: None,
+            minimized_panic: None,
         }
     }
 }
@@ -130,9 +171,13 @@ pub fn run_evolution_campaign(seed: u64, simulated_days: usize) -> CampaignResul
 
     let policy = standard_policy();
     let corpus = SyntheticCorpus::new(SyntheticConfig::default().with_seed(seed)).generate();
+    let initial_policy = policy.clone();
+    let initial_corpus = corpus.clone();
 
     let mut sim = PolicyEvolutionSim::new(policy, corpus);
     let mut failures = Vec::new();
+    let mut action_log: Vec<SimAction> = Vec::new();
+    let mut minimal_repro: Option<MinimalRepro> = None;
 
     for day in 0..simulated_days {
         let is_weekday = day % 7 < 5;
@@ -163,12 +208,14 @@ pub fn run_evolution_campaign(seed: u64, simulated_days: usize) -> CampaignResul
                 let action = generate_random_action(&mut rng);
                 let match_expr = generate_random_match_expr(&mut rng);
 
-                sim.step(SimAction::AddRule {
+                let action = SimAction::AddRule {
                     name: rule_name,
                     match_expr,
                     action,
                     priority,
-                });
+                };
+                sim.step(action.clone());
+                action_log.push(action);
             } else if sim.policy.rules.len() > 2 {
                 let removable: Vec<_> = sim
                     .policy
@@ -178,28 +225,49 @@ pub fn run_evolution_campaign(seed: u64, simulated_days: usize) -> CampaignResul
                     .map(|r| r.name.clone())
                     .collect();
                 if let Some(name) = removable.choose(&mut rng) {
-                    sim.step(SimAction::RemoveRule { name: name.clone() });
+                    let action = SimAction::RemoveRule { name: name.clone() };
+                    sim.step(action.clone());
+                    action_log.push(action);
                 }
             }
         }
 
         if is_incident {
             let incident_rule = format!("incident-response-day{day}");
-            sim.step(SimAction::AddRule {
+            let action = SimAction::AddRule {
                 name: incident_rule,
                 match_expr: "http.status >= 500".to_string(),
                 action: Action::Keep,
                 priority: 255,
-            });
+            };
+            sim.step(action.clone());
+            action_log.push(action);
         }
 
+        action_log.push(SimAction::Verify);
         let _verify_result = sim.step(SimAction::Verify);
 
+        action_log.push(SimAction::Compile);
         if let StepResult::CompileFailed { error } = sim.step(SimAction::Compile) {
             failures.push(format!("Day {day}: compilation failed: {error}"));
+
+            if minimal_repro.is_none() {
+                let minimized = minimize_actions(&action_log, |candidate| {
+                    let mut replay =
+                        PolicyEvolutionSim::new(initial_policy.clone(), initial_corpus.clone());
+                    candidate
+                        .iter()
+                        .any(|a| matches!(replay.step(a.clone()), StepResult::CompileFailed { .. }))
+                });
+                minimal_repro = Some(MinimalRepro {
+                    seed,
+                    actions: minimized,
+                });
+            }
         }
 
         if day % 7 == 0 {
+            action_log.push(SimAction::Checkpoint);
             sim.step(SimAction::Checkpoint);
         }
     }
@@ -207,7 +275,7 @@ pub fn run_evolution_campaign(seed: u64, simulated_days: usize) -> CampaignResul
     let elapsed = start.elapsed().as_millis() as u64;
     let simulated_seconds = (simulated_days as u64) * 86400;
 
-    if failures.is_empty() {
+    let mut result = if failures.is_empty() {
         CampaignResult::pass(
             "evolution_campaign",
             simulated_days,
@@ -216,7 +284,55 @@ pub fn run_evolution_campaign(seed: u64, simulated_days: usize) -> CampaignResul
         )
     } else {
         CampaignResult::fail("evolution_campaign", simulated_days, failures)
+    };
+    result.minimal_repro = minimal_repro;
+    result.replay_artifact = Some(ReplayArtifact::new("evolution_campaign", seed, action_log));
+    result
+}
+
+/// Reconstructs and replays a previously recorded [`ReplayArtifact`],
+/// reproducing the identical [`CampaignResult`] the original run
+/// produced.
+///
+/// Unlike rerunning the originating campaign function with the same
+/// seed, this drives the simulation purely from the recorded action
+/// trace - it never calls the randomized mutation logic that generated
+/// those actions, so it keeps working even after that logic is
+/// refactored.
+///
+/// # Panics
+///
+/// Panics if `artifact.campaign` isn't a campaign this function knows
+/// how to replay.
+#[must_use]
+pub fn replay_artifact(artifact: &ReplayArtifact) -> CampaignResult {
+    assert_eq!(
+        artifact.campaign, "evolution_campaign",
+        "don't know how to replay campaign {:?}",
+        artifact.campaign
+    );
+
+    let start = Instant::now();
+    let policy = standard_policy();
+    let corpus =
+        SyntheticCorpus::new(SyntheticConfig::default().with_seed(artifact.seed)).generate();
+    let mut sim = PolicyEvolutionSim::new(policy, corpus);
+
+    let mut failures = Vec::new();
+    for (i, action) in artifact.actions.iter().enumerate() {
+        if let StepResult::CompileFailed { error } = sim.step(action.clone()) {
+            failures.push(format!("Action {i}: compilation failed: {error}"));
+        }
     }
+
+    let elapsed = start.elapsed().as_millis() as u64;
+    let mut result = if failures.is_empty() {
+        CampaignResult::pass(&artifact.campaign, artifact.actions.len(), 0, elapsed)
+    } else {
+        CampaignResult::fail(&artifact.campaign, artifact.actions.len(), failures)
+    };
+    result.replay_artifact = Some(artifact.clone());
+    result
 }
 
 /// Runs determinism verification across many iterations.
@@ -298,6 +414,52 @@ pub fn run_cardinality_campaign(seed: u64, max_services: usize, steps: usize) ->
     }
 }
 
+/// Runs exhaustive SAT-backed verification of policy properties.
+///
+/// Unlike every other campaign in this module, which samples a
+/// [`SyntheticCorpus`] and checks policy behavior probabilistically,
+/// this one *proves* each property in `properties` over the full
+/// symbolic space of request attributes via [`crate::verify`]. A
+/// property the solver can't model soundly (an operator or field
+/// [`crate::verify`] doesn't encode) is recorded informationally
+/// rather than as a failure - there's no counterexample to report,
+/// only a gap in what got checked.
+#[must_use]
+pub fn run_sat_verification_campaign(policy: &Policy, properties: &[Property]) -> CampaignResult {
+    let start = Instant::now();
+    let mut failures = Vec::new();
+    let mut unmodeled = 0;
+
+    for property in properties {
+        match verify_property(policy, property) {
+            Verdict::Proven => {}
+            Verdict::Counterexample { trace, detail } => {
+                failures.push(format!("{detail}: {trace:?}"));
+            }
+            Verdict::Unmodeled(reason) => {
+                unmodeled += 1;
+                eprintln!("SAT verification campaign: property not modeled ({reason})");
+            }
+        }
+    }
+
+    let elapsed = start.elapsed().as_millis() as u64;
+    let proven = properties.len() - failures.len() - unmodeled;
+    eprintln!(
+        "SAT verification campaign: {proven}/{} proven, {unmodeled}/{} unmodeled, {}/{} falsified",
+        properties.len(),
+        properties.len(),
+        failures.len(),
+        properties.len()
+    );
+
+    if failures.is_empty() {
+        CampaignResult::pass("sat_verification_campaign", properties.len(), 0, elapsed)
+    } else {
+        CampaignResult::fail("sat_verification_campaign", properties.len(), failures)
+    }
+}
+
 /// Runs combined fault injection campaign.
 #[must_use]
 pub fn run_combined_faults_campaign(seed: u64, iterations: usize) -> CampaignResult {
@@ -357,6 +519,345 @@ pub fn run_combined_faults_campaign(seed: u64, iterations: usize) -> CampaignRes
     }
 }
 
+/// Configuration for [`run_scheduled_combined_faults_campaign`].
+#[derive(Debug, Clone)]
+pub struct ScheduledCampaignConfig {
+    /// Seed used to initialize the power schedule's seed pool.
+    pub seed: u64,
+    /// Number of iterations to run.
+    pub iterations: usize,
+    /// Number of seeds the schedule tracks in its pool at once.
+    pub pool_size: usize,
+}
+
+impl Default for ScheduledCampaignConfig {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            iterations: 5_000,
+            pool_size: 16,
+        }
+    }
+}
+
+impl ScheduledCampaignConfig {
+    /// Sets the seed used to initialize the power schedule's seed pool.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the number of iterations to run.
+    #[must_use]
+    pub const fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Sets the number of seeds the schedule tracks in its pool at once.
+    #[must_use]
+    pub const fn with_pool_size(mut self, pool_size: usize) -> Self {
+        self.pool_size = pool_size;
+        self
+    }
+}
+
+/// A single seed tracked by a [`PowerSchedule`], with an AFL-style
+/// "energy" score controlling how much of the fault-intensity budget it
+/// gets relative to the rest of the pool.
+#[derive(Debug, Clone)]
+struct ScheduleEntry {
+    seed: u64,
+    energy: f64,
+}
+
+/// AFL-inspired adaptive power schedule.
+///
+/// `run_combined_faults_campaign` ramps fault intensity linearly
+/// (`intensity = i / iterations`) regardless of whether that region of
+/// the input space is producing anything interesting. `PowerSchedule`
+/// instead keeps a small pool of seeds with an energy score: a seed
+/// whose run turns up a fingerprint not seen before (a new panic/error
+/// combination) gets its energy boosted and is revisited at higher
+/// intensity, while a seed that keeps reproducing the same outcome
+/// decays and is eventually replaced with a fresh one. The iteration
+/// budget ends up concentrated on the regions of the fault-space that
+/// are actually producing new behavior.
+///
+/// There's no real branch-coverage instrumentation available here (see
+/// [`crate::fuzz`]'s equivalent caveat), so "coverage" is approximated
+/// by the same kind of cheap outcome fingerprint [`run_fuzz_campaign`]
+/// uses.
+#[derive(Debug, Clone)]
+pub struct PowerSchedule {
+    entries: Vec<ScheduleEntry>,
+    rng: ChaCha8Rng,
+    seen_fingerprints: HashSet<String>,
+}
+
+impl PowerSchedule {
+    /// Creates a schedule seeded from `base_seed`, with `pool_size` seeds
+    /// (minimum 1) all starting at energy 1.0.
+    #[must_use]
+    pub fn new(base_seed: u64, pool_size: usize) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(base_seed);
+        let entries = (0..pool_size.max(1))
+            .map(|_| ScheduleEntry {
+                seed: rng.gen(),
+                energy: 1.0,
+            })
+            .collect();
+        Self {
+            entries,
+            rng,
+            seen_fingerprints: HashSet::new(),
+        }
+    }
+
+    /// Picks the next seed and fault intensity to run.
+    ///
+    /// The seed is drawn from the pool's highest-energy entry; intensity
+    /// is that entry's energy normalized against the pool's maximum, so
+    /// a seed that's currently "hot" gets mutated harder rather than
+    /// merely run more often.
+    pub fn next(&mut self) -> (u64, f64) {
+        let max_energy = self
+            .entries
+            .iter()
+            .map(|e| e.energy)
+            .fold(0.0_f64, f64::max)
+            .max(1.0);
+        let index = self
+            .entries
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.energy.total_cmp(&b.energy))
+            .map_or(0, |(i, _)| i);
+
+        let entry = &self.entries[index];
+        (entry.seed, (entry.energy / max_energy).clamp(0.0, 1.0))
+    }
+
+    /// Records the fingerprint a run against `seed` produced.
+    ///
+    /// Boosts that entry's energy if `fingerprint` hasn't been seen
+    /// before across this schedule's lifetime, decays it otherwise. An
+    /// entry whose energy decays below a small floor is replaced with a
+    /// freshly drawn seed at energy 1.0 so the pool doesn't stagnate on
+    /// an exhausted region.
+    pub fn record_outcome(&mut self, seed: u64, fingerprint: String) {
+        let novel = self.seen_fingerprints.insert(fingerprint);
+        let Some(entry) = self.entries.iter_mut().find(|e| e.seed == seed) else {
+            return;
+        };
+
+        entry.energy *= if novel { 2.0 } else { 0.5 };
+        if entry.energy < 0.05 {
+            entry.seed = self.rng.gen();
+            entry.energy = 1.0;
+        }
+    }
+}
+
+/// A cheap stand-in for a coverage fingerprint: which side of the
+/// compile/verify calls panicked versus returned normally. Good enough
+/// to tell "this seed found a new kind of trouble" apart from "this seed
+/// reproduces what we already know about".
+fn schedule_fingerprint(compile_panicked: bool, verify_panicked: bool) -> String {
+    format!("compile:{compile_panicked}:verify:{verify_panicked}")
+}
+
+/// Like [`run_combined_faults_campaign`], but replaces its linear
+/// `intensity = i / iterations` ramp with a [`PowerSchedule`]: seeds
+/// whose runs turn up new behavior get revisited at higher intensity,
+/// seeds that keep reproducing the same outcome decay out of the pool.
+/// See [`ScheduledCampaignConfig`] and [`PowerSchedule`] for the
+/// details.
+#[must_use]
+pub fn run_scheduled_combined_faults_campaign(config: &ScheduledCampaignConfig) -> CampaignResult {
+    let start = Instant::now();
+    let mut schedule = PowerSchedule::new(config.seed, config.pool_size);
+    let mut failures = Vec::new();
+
+    let compiler = Compiler::new();
+    let prover = Prover::default();
+
+    for i in 0..config.iterations {
+        let (sub_seed, intensity) = schedule.next();
+        let mut rng = ChaCha8Rng::seed_from_u64(sub_seed);
+
+        let corpus_config = SyntheticConfig::default()
+            .with_seed(rng.gen())
+            .with_trace_count(100)
+            .with_error_rate(intensity * 0.5)
+            .with_slow_rate(intensity * 0.5);
+        let corpus = SyntheticCorpus::new(corpus_config).generate();
+
+        let mut chaos = ChaosInjector::new(
+            ChaosConfig::default()
+                .with_seed(rng.gen())
+                .with_rule_corruption_rate(intensity * 0.3),
+        );
+        let base_policy = standard_policy();
+        let policy = if rng.gen_bool(intensity) {
+            chaos.corrupt_policy(&base_policy)
+        } else {
+            base_policy
+        };
+
+        let compile_result = std::panic::catch_unwind(|| compiler.compile(&policy));
+        let compile_panicked = compile_result.is_err();
+        if compile_panicked {
+            failures.push(format!(
+                "Iteration {i}: compiler panicked at intensity {intensity:.2} (seed {sub_seed:016x})"
+            ));
+        }
+
+        let verify_result = std::panic::catch_unwind(|| prover.verify(&policy, &corpus));
+        let verify_panicked = verify_result.is_err();
+        if verify_panicked {
+            failures.push(format!(
+                "Iteration {i}: prover panicked at intensity {intensity:.2} (seed {sub_seed:016x})"
+            ));
+        }
+
+        schedule.record_outcome(
+            sub_seed,
+            schedule_fingerprint(compile_panicked, verify_panicked),
+        );
+    }
+
+    let elapsed = start.elapsed().as_millis() as u64;
+    let simulated_seconds = (config.iterations as u64) * 60;
+
+    if failures.is_empty() {
+        CampaignResult::pass(
+            "scheduled_combined_faults",
+            config.iterations,
+            simulated_seconds,
+            elapsed,
+        )
+    } else {
+        CampaignResult::fail("scheduled_combined_faults", config.iterations, failures)
+    }
+}
+
+/// Runs a coverage-guided fuzz campaign over the `Policy`/`SyntheticConfig`
+/// input space, with a persistent on-disk corpus and crash archive.
+///
+/// Unlike `run_chaos_campaign`'s/`run_combined_faults_campaign`'s blind
+/// random and linearly-ramped corpora, this keeps a mutation-fed pool of
+/// byte buffers under `workspace_dir`, mirroring honggfuzz's
+/// `hfuzz_workspace/<target>/{input,crashes}` layout: interesting inputs
+/// land in `workspace_dir/input`, crash reproducers in
+/// `workspace_dir/crashes`. An input is "interesting" - worth saving and
+/// mutating further - when it produces a fingerprint that hasn't been
+/// seen before in this run. The fingerprint is a cheap proxy for edge
+/// coverage (rule/trace counts plus the compiler/prover outcome
+/// variant), not real branch-coverage instrumentation, since this crate
+/// has no access to that outside of an actual `cargo hfuzz` run; see
+/// [`crate::fuzz`] for the real honggfuzz entry point this complements.
+///
+/// The corpus persists across runs - rerunning with the same
+/// `workspace_dir` resumes from whatever was saved last time instead of
+/// starting cold - which is why this isn't wired into
+/// [`run_all_campaigns`]/[`run_extended_campaigns`] alongside the other
+/// seed-only campaigns.
+#[cfg(feature = "fuzz")]
+#[must_use]
+pub fn run_fuzz_campaign(
+    seed: u64,
+    iterations: usize,
+    workspace_dir: &std::path::Path,
+) -> CampaignResult {
+    use arbitrary::{Arbitrary, Unstructured};
+    use std::collections::HashSet;
+
+    let start = Instant::now();
+    let input_dir = workspace_dir.join("input");
+    let crashes_dir = workspace_dir.join("crashes");
+
+    let mut corpus = fuzz_campaign_load_corpus(&input_dir);
+    if corpus.is_empty() {
+        corpus.push(vec![0u8; 32]);
+        corpus.push(vec![0xFFu8; 32]);
+    }
+
+    let mut seen_fingerprints: HashSet<String> = HashSet::new();
+    let mut seen_crashes: HashSet<String> = HashSet::new();
+    let mut new_crashes = 0usize;
+    let mut failures = Vec::new();
+
+    let compiler = Compiler::new();
+    let prover = Prover::default();
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    for i in 0..iterations {
+        let base_index = rng.gen_range(0..corpus.len());
+        let candidate = fuzz_campaign_mutate(&corpus[base_index], &mut rng);
+
+        let mut u = Unstructured::new(&candidate);
+        let Ok(policy) = Policy::arbitrary(&mut u) else {
+            continue;
+        };
+        let Ok(config) = SyntheticConfig::arbitrary(&mut u) else {
+            continue;
+        };
+        let trace_corpus = SyntheticCorpus::new(config).generate();
+
+        let compile_result =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| compiler.compile(&policy)));
+        let verify_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            prover.verify(&policy, &trace_corpus)
+        }));
+
+        if compile_result.is_err() || verify_result.is_err() {
+            let crash_fingerprint = format!("{}:{}", policy.rules.len(), trace_corpus.len());
+            if seen_crashes.insert(crash_fingerprint) {
+                new_crashes += 1;
+                fuzz_campaign_persist(&crashes_dir, &candidate);
+                failures.push(format!(
+                    "Iteration {i}: {} panicked on a {}-rule policy against {} traces",
+                    if compile_result.is_err() && verify_result.is_err() {
+                        "compiler and prover"
+                    } else if compile_result.is_err() {
+                        "compiler"
+                    } else {
+                        "prover"
+                    },
+                    policy.rules.len(),
+                    trace_corpus.len(),
+                ));
+            }
+            continue;
+        }
+
+        let fingerprint = format!(
+            "{}:{}:{}:{}",
+            policy.rules.len(),
+            trace_corpus.len(),
+            fuzz_campaign_outcome_tag(&compile_result.unwrap()),
+            fuzz_campaign_outcome_tag(&verify_result.unwrap()),
+        );
+        if seen_fingerprints.insert(fingerprint) {
+            fuzz_campaign_persist(&input_dir, &candidate);
+            corpus.push(candidate);
+        }
+    }
+
+    let elapsed = start.elapsed().as_millis() as u64;
+
+    let mut result = if failures.is_empty() {
+        CampaignResult::pass("fuzz_campaign", iterations, 0, elapsed)
+    } else {
+        CampaignResult::fail("fuzz_campaign", iterations, failures)
+    };
+    result.corpus_path = Some(workspace_dir.to_path_buf());
+    result.new_crashes = new_crashes;
+    result
+}
+
 // =============================================================================
 // INFRASTRUCTURE FAULT CAMPAIGNS
 // =============================================================================
@@ -420,7 +921,9 @@ pub fn run_infrastructure_faults_campaign(seed: u64, iterations: usize) -> Campa
 
         let compile_result = std::panic::catch_unwind(|| compiler.compile(&policy));
         if compile_result.is_err() {
-            failures.push(format!("Iteration {i}: compiler panicked during infra fault"));
+            failures.push(format!(
+                "Iteration {i}: compiler panicked during infra fault"
+            ));
         }
 
         let verify_result = std::panic::catch_unwind(|| prover.verify(&policy, &corpus));
@@ -575,15 +1078,14 @@ pub fn run_distributed_faults_campaign(seed: u64, iterations: usize) -> Campaign
 
         let corpus = SyntheticCorpus::new(config).generate();
 
-        let policy = if active_faults.contains(&"split_brain")
-            || active_faults.contains(&"quorum_loss")
-        {
-            emergency_policy()
-        } else if active_faults.len() > 3 {
-            degraded_policy(&mut rng)
-        } else {
-            standard_policy()
-        };
+        let policy =
+            if active_faults.contains(&"split_brain") || active_faults.contains(&"quorum_loss") {
+                emergency_policy()
+            } else if active_faults.len() > 3 {
+                degraded_policy(&mut rng)
+            } else {
+                standard_policy()
+            };
 
         let compile_result = std::panic::catch_unwind(|| compiler.compile(&policy));
         if compile_result.is_err() {
@@ -717,67 +1219,184 @@ pub fn run_deployment_faults_campaign(seed: u64, iterations: usize) -> CampaignR
 /// - Timeout cascades through dependencies
 /// - Circuit breaker state transitions
 /// - Recovery and stabilization
+///
+/// Before its normal randomized sweep, this first replays every
+/// [`RegressionEntry`] persisted for `"cascading_failure"` in
+/// [`regressions::DEFAULT_PATH`] (see the [`crate::regressions`] module
+/// doc), so a seed that panicked the compiler or prover in some past run
+/// stays covered even if it never turns up again by chance. Any
+/// iteration - replayed or fresh - that still panics gets (re-)recorded,
+/// so the corpus only grows.
 #[must_use]
 pub fn run_cascading_failure_campaign(seed: u64, iterations: usize) -> CampaignResult {
     let start = Instant::now();
     let mut rng = ChaCha8Rng::seed_from_u64(seed);
     let mut failures = Vec::new();
+    let mut minimized_panic: Option<MinimizedPanic> = None;
 
     let compiler = Compiler::new();
     let prover = Prover::default();
 
     let cascade_duration = 100;
+    let regression_path = Path::new(regressions::DEFAULT_PATH);
+
+    for entry in regressions::load(regression_path, "cascading_failure") {
+        // Re-derive every case from iteration 0, the same way the sweep
+        // below does, so any rng draws a degraded-policy phase made on
+        // the way to `entry.iteration` land in the same state they did
+        // in the run that first recorded this entry.
+        let mut replay_rng = ChaCha8Rng::seed_from_u64(entry.base_seed);
+        for i in 0..=entry.iteration {
+            let cascade_phase = i % cascade_duration;
+            let cascade_number = i / cascade_duration;
+            let (config, policy) = cascading_failure_case(
+                entry.base_seed + (cascade_number as u64 * 1000),
+                i,
+                cascade_phase,
+                &mut replay_rng,
+            );
+
+            if i == entry.iteration {
+                cascading_failure_check(
+                    &compiler,
+                    &prover,
+                    entry.base_seed,
+                    cascade_phase,
+                    &config,
+                    &policy,
+                    &mut failures,
+                    &mut minimized_panic,
+                    &format!("Replayed iteration {i}"),
+                );
+            }
+        }
+    }
 
     for i in 0..iterations {
         let cascade_phase = i % cascade_duration;
         let cascade_number = i / cascade_duration;
+        let (config, policy) = cascading_failure_case(
+            seed + (cascade_number as u64 * 1000),
+            i,
+            cascade_phase,
+            &mut rng,
+        );
 
-        let (error_rate, slow_rate) = match cascade_phase {
-            0..=19 => (0.02, 0.05),
-            20..=39 => (((cascade_phase - 20) as f64).mul_add(0.02, 0.10), 0.20),
-            40..=59 => (((cascade_phase - 40) as f64).mul_add(0.02, 0.50), 0.60),
-            60..=79 => (((cascade_phase - 60) as f64).mul_add(-0.03, 0.70), 0.40),
-            _ => (0.05, 0.08),
-        };
-
-        let config = SyntheticConfig::default()
-            .with_seed(seed + i as u64 + (cascade_number as u64 * 1000))
-            .with_trace_count(100)
-            .with_error_rate(error_rate)
-            .with_slow_rate(slow_rate);
-
-        let corpus = SyntheticCorpus::new(config).generate();
-
-        let policy = if (40..60).contains(&cascade_phase) {
-            emergency_policy()
-        } else if (20..40).contains(&cascade_phase) {
-            degraded_policy(&mut rng)
-        } else {
-            standard_policy()
-        };
-
-        let compile_result = std::panic::catch_unwind(|| compiler.compile(&policy));
-        if compile_result.is_err() {
-            failures.push(format!(
-                "Iteration {i}: compiler panicked in cascade phase {cascade_phase}"
-            ));
-        }
-
-        let verify_result = std::panic::catch_unwind(|| prover.verify(&policy, &corpus));
-        if verify_result.is_err() {
-            failures.push(format!(
-                "Iteration {i}: prover panicked in cascade phase {cascade_phase}"
-            ));
+        let failures_before = failures.len();
+        cascading_failure_check(
+            &compiler,
+            &prover,
+            seed,
+            cascade_phase,
+            &config,
+            &policy,
+            &mut failures,
+            &mut minimized_panic,
+            &format!("Iteration {i}"),
+        );
+        if failures.len() > failures_before {
+            regressions::record(
+                regression_path,
+                &RegressionEntry {
+                    campaign: "cascading_failure".to_string(),
+                    base_seed: seed,
+                    iteration: i,
+                    cascade_phase: Some(cascade_phase),
+                },
+            );
         }
     }
 
     let elapsed = start.elapsed().as_millis() as u64;
     let simulated_seconds = (iterations as u64) * 30;
 
-    if failures.is_empty() {
+    let mut result = if failures.is_empty() {
         CampaignResult::pass("cascading_failure", iterations, simulated_seconds, elapsed)
     } else {
         CampaignResult::fail("cascading_failure", iterations, failures)
+    };
+    result.minimized_panic = minimized_panic;
+    result
+}
+
+/// Builds the `(SyntheticConfig, Policy)` pair for cascade phase
+/// `cascade_phase` at iteration `i` of a [`run_cascading_failure_campaign`]
+/// run seeded with `seed` - factored out so the replay pass above can
+/// reconstruct the exact same case a persisted [`RegressionEntry`]
+/// recorded.
+fn cascading_failure_case(
+    seed: u64,
+    i: usize,
+    cascade_phase: usize,
+    rng: &mut ChaCha8Rng,
+) -> (SyntheticConfig, Policy) {
+    let (error_rate, slow_rate) = match cascade_phase {
+        0..=19 => (0.02, 0.05),
+        20..=39 => (((cascade_phase - 20) as f64).mul_add(0.02, 0.10), 0.20),
+        40..=59 => (((cascade_phase - 40) as f64).mul_add(0.02, 0.50), 0.60),
+        60..=79 => (((cascade_phase - 60) as f64).mul_add(-0.03, 0.70), 0.40),
+        _ => (0.05, 0.08),
+    };
+
+    let config = SyntheticConfig::default()
+        .with_seed(seed + i as u64)
+        .with_trace_count(100)
+        .with_error_rate(error_rate)
+        .with_slow_rate(slow_rate);
+
+    let policy = if (40..60).contains(&cascade_phase) {
+        emergency_policy()
+    } else if (20..40).contains(&cascade_phase) {
+        degraded_policy(rng)
+    } else {
+        standard_policy()
+    };
+
+    (config, policy)
+}
+
+/// Runs the compile/verify panic check a [`run_cascading_failure_campaign`]
+/// iteration performs against `config`/`policy`, appending to `failures`
+/// and shrinking the first panic into `minimized_panic`. `label` prefixes
+/// each failure message, so the normal sweep and the replay pass can
+/// share this without their messages becoming ambiguous.
+#[allow(clippy::too_many_arguments)]
+fn cascading_failure_check(
+    compiler: &Compiler,
+    prover: &Prover,
+    seed: u64,
+    cascade_phase: usize,
+    config: &SyntheticConfig,
+    policy: &Policy,
+    failures: &mut Vec<String>,
+    minimized_panic: &mut Option<MinimizedPanic>,
+    label: &str,
+) {
+    let corpus = SyntheticCorpus::new(config.clone()).generate();
+
+    let compile_result = std::panic::catch_unwind(|| compiler.compile(policy));
+    if compile_result.is_err() {
+        failures.push(format!(
+            "{label}: compiler panicked in cascade phase {cascade_phase}"
+        ));
+        if minimized_panic.is_none() {
+            *minimized_panic = Some(shrink_panic(seed, config, policy, |config, policy| {
+                std::panic::catch_unwind(|| compiler.compile(policy)).is_err()
+            }));
+        }
+    }
+
+    let verify_result = std::panic::catch_unwind(|| prover.verify(policy, &corpus));
+    if verify_result.is_err() {
+        failures.push(format!(
+            "{label}: prover panicked in cascade phase {cascade_phase}"
+        ));
+        if minimized_panic.is_none() {
+            *minimized_panic = Some(shrink_panic(seed, config, policy, |config, policy| {
+                let corpus = SyntheticCorpus::new(config.clone()).generate();
+                std::panic::catch_unwind(|| prover.verify(policy, &corpus)).is_err()
+            }));
+        }
     }
 }
 
@@ -785,6 +1404,134 @@ pub fn run_cascading_failure_campaign(seed: u64, iterations: usize) -> CampaignR
 // LONG-TERM STABILITY CAMPAIGN
 // =============================================================================
 
+/// The running counters a [`run_decade_simulation`] day accumulates -
+/// `policy_version` in particular drives that day's policy choice, so
+/// reconstructing a persisted failing day requires replaying every prior
+/// day's state transitions, not just its own.
+#[derive(Debug, Clone, Default)]
+struct DecadeState {
+    major_incidents: u32,
+    deployments: u32,
+    policy_version: u32,
+}
+
+impl DecadeState {
+    const fn new() -> Self {
+        Self {
+            major_incidents: 0,
+            deployments: 0,
+            policy_version: 1,
+        }
+    }
+}
+
+/// Builds the `(SyntheticConfig, Policy)` pair for `day` of a
+/// [`run_decade_simulation`] run seeded with `seed`, advancing `state`
+/// and `rng` exactly as the main loop below does - factored out so the
+/// replay pass can reconstruct a persisted [`RegressionEntry`] by
+/// re-running every day from 0 up to it.
+fn decade_day_case(
+    seed: u64,
+    day: usize,
+    rng: &mut ChaCha8Rng,
+    state: &mut DecadeState,
+) -> (SyntheticConfig, Policy, usize, usize) {
+    let year = day / 365;
+    let day_of_year = day % 365;
+    let month = day_of_year / 30;
+
+    let is_black_friday = month == 10 && (day_of_year % 365) > 320;
+    let is_holiday_season = month == 11;
+    let is_summer_lull = (5..=7).contains(&month);
+    let is_quarter_end = month % 3 == 2 && day_of_year % 30 > 25;
+
+    let traffic_multiplier = if is_black_friday {
+        10.0
+    } else if is_holiday_season {
+        3.0
+    } else if is_summer_lull {
+        0.7
+    } else {
+        1.0
+    };
+
+    let incident_probability = 0.005 * traffic_multiplier;
+    let has_incident = rng.gen_bool(f64::min(incident_probability, 0.5));
+
+    if has_incident {
+        state.major_incidents += 1;
+    }
+
+    let is_deploy_day = day % 7 == 2 && !has_incident && !is_quarter_end;
+    if is_deploy_day {
+        state.deployments += 1;
+        if rng.gen_bool(0.1) {
+            state.policy_version += 1;
+        }
+    }
+
+    let base_error_rate = if has_incident { 0.30 } else { 0.03 };
+    let error_rate = f64::min(base_error_rate * traffic_multiplier.sqrt(), 0.9);
+
+    let config = SyntheticConfig::default()
+        .with_seed(seed + day as u64)
+        .with_trace_count(50)
+        .with_error_rate(error_rate)
+        .with_slow_rate(if has_incident { 0.40 } else { 0.10 });
+
+    let policy = if has_incident {
+        emergency_policy()
+    } else if is_black_friday || is_holiday_season {
+        high_traffic_policy()
+    } else {
+        match state.policy_version % 5 {
+            0 => minimal_policy(),
+            3 => aggressive_policy(rng),
+            _ => standard_policy(),
+        }
+    };
+
+    (config, policy, year, day_of_year)
+}
+
+/// Runs the compile/verify panic check a [`run_decade_simulation`] day
+/// performs against `config`/`policy`, appending to `failures` and
+/// shrinking the first panic into `minimized_panic`.
+fn decade_day_check(
+    compiler: &Compiler,
+    prover: &Prover,
+    seed: u64,
+    year: usize,
+    day_of_year: usize,
+    config: &SyntheticConfig,
+    policy: &Policy,
+    failures: &mut Vec<String>,
+    minimized_panic: &mut Option<MinimizedPanic>,
+) {
+    let corpus = SyntheticCorpus::new(config.clone()).generate();
+
+    let compile_result = std::panic::catch_unwind(|| compiler.compile(policy));
+    if compile_result.is_err() {
+        failures.push(format!("Year {year} Day {day_of_year}: compiler panicked"));
+        if minimized_panic.is_none() {
+            *minimized_panic = Some(shrink_panic(seed, config, policy, |config, policy| {
+                std::panic::catch_unwind(|| compiler.compile(policy)).is_err()
+            }));
+        }
+    }
+
+    let verify_result = std::panic::catch_unwind(|| prover.verify(policy, &corpus));
+    if verify_result.is_err() {
+        failures.push(format!("Year {year} Day {day_of_year}: prover panicked"));
+        if minimized_panic.is_none() {
+            *minimized_panic = Some(shrink_panic(seed, config, policy, |config, policy| {
+                let corpus = SyntheticCorpus::new(config.clone()).generate();
+                std::panic::catch_unwind(|| prover.verify(policy, &corpus)).is_err()
+            }));
+        }
+    }
+}
+
 /// Simulates 10 years of production operation.
 ///
 /// Comprehensive long-term stability test combining all fault types:
@@ -793,87 +1540,76 @@ pub fn run_cascading_failure_campaign(seed: u64, iterations: usize) -> CampaignR
 /// - Major version upgrades
 /// - Incident response cycles
 /// - Gradual system evolution
+///
+/// Before its normal sweep, this first replays every [`RegressionEntry`]
+/// persisted for `"decade_simulation"` in [`regressions::DEFAULT_PATH`]
+/// (see the [`crate::regressions`] module doc). Unlike
+/// [`run_cascading_failure_campaign`], a day's policy choice here depends
+/// on [`DecadeState`] accumulated over every prior day, so replaying a
+/// persisted day re-runs the whole history from day 0 up to it rather
+/// than reconstructing that day in isolation.
 #[must_use]
 pub fn run_decade_simulation(seed: u64) -> CampaignResult {
     let start = Instant::now();
     let mut rng = ChaCha8Rng::seed_from_u64(seed);
     let mut failures = Vec::new();
+    let mut minimized_panic: Option<MinimizedPanic> = None;
 
     let compiler = Compiler::new();
     let prover = Prover::default();
 
     let total_days = 3650;
-    let mut major_incidents = 0;
-    let mut deployments = 0;
-    let mut policy_version = 1;
-
-    for day in 0..total_days {
-        let year = day / 365;
-        let day_of_year = day % 365;
-        let month = day_of_year / 30;
-
-        let is_black_friday = month == 10 && (day_of_year % 365) > 320;
-        let is_holiday_season = month == 11;
-        let is_summer_lull = (5..=7).contains(&month);
-        let is_quarter_end = month % 3 == 2 && day_of_year % 30 > 25;
-
-        let traffic_multiplier = if is_black_friday {
-            10.0
-        } else if is_holiday_season {
-            3.0
-        } else if is_summer_lull {
-            0.7
-        } else {
-            1.0
-        };
-
-        let incident_probability = 0.005 * traffic_multiplier;
-        let has_incident = rng.gen_bool(f64::min(incident_probability, 0.5));
-
-        if has_incident {
-            major_incidents += 1;
-        }
-
-        let is_deploy_day = day % 7 == 2 && !has_incident && !is_quarter_end;
-        if is_deploy_day {
-            deployments += 1;
-            if rng.gen_bool(0.1) {
-                policy_version += 1;
+    let mut state = DecadeState::new();
+    let regression_path = Path::new(regressions::DEFAULT_PATH);
+
+    for entry in regressions::load(regression_path, "decade_simulation") {
+        let mut replay_rng = ChaCha8Rng::seed_from_u64(entry.base_seed);
+        let mut replay_state = DecadeState::new();
+        for day in 0..=entry.iteration {
+            let (config, policy, year, day_of_year) =
+                decade_day_case(entry.base_seed, day, &mut replay_rng, &mut replay_state);
+            if day == entry.iteration {
+                decade_day_check(
+                    &compiler,
+                    &prover,
+                    entry.base_seed,
+                    year,
+                    day_of_year,
+                    &config,
+                    &policy,
+                    &mut failures,
+                    &mut minimized_panic,
+                );
             }
         }
+    }
 
-        let base_error_rate = if has_incident { 0.30 } else { 0.03 };
-        let error_rate = f64::min(base_error_rate * traffic_multiplier.sqrt(), 0.9);
-
-        let config = SyntheticConfig::default()
-            .with_seed(seed + day as u64)
-            .with_trace_count(50)
-            .with_error_rate(error_rate)
-            .with_slow_rate(if has_incident { 0.40 } else { 0.10 });
-
-        let corpus = SyntheticCorpus::new(config).generate();
-
-        let policy = if has_incident {
-            emergency_policy()
-        } else if is_black_friday || is_holiday_season {
-            high_traffic_policy()
-        } else {
-            match policy_version % 5 {
-                0 => minimal_policy(),
-                3 => aggressive_policy(&mut rng),
-                _ => standard_policy(),
-            }
-        };
+    for day in 0..total_days {
+        let (config, policy, year, day_of_year) = decade_day_case(seed, day, &mut rng, &mut state);
 
         if day % 10 == 0 {
-            let compile_result = std::panic::catch_unwind(|| compiler.compile(&policy));
-            if compile_result.is_err() {
-                failures.push(format!("Year {year} Day {day_of_year}: compiler panicked"));
-            }
-
-            let verify_result = std::panic::catch_unwind(|| prover.verify(&policy, &corpus));
-            if verify_result.is_err() {
-                failures.push(format!("Year {year} Day {day_of_year}: prover panicked"));
+            let failures_before = failures.len();
+            decade_day_check(
+                &compiler,
+                &prover,
+                seed,
+                year,
+                day_of_year,
+                &config,
+                &policy,
+                &mut failures,
+                &mut minimized_panic,
+            );
+            if failures.len() > failures_before {
+                regressions::record(
+                    regression_path,
+                    &RegressionEntry {
+                        campaign: "decade_simulation".to_string(),
+                        base_seed: seed,
+                        iteration: day,
+                        cascade_phase: None,
+                    },
+                );
             }
         }
     }
@@ -882,16 +1618,119 @@ pub fn run_decade_simulation(seed: u64) -> CampaignResult {
     let simulated_seconds = (total_days as u64) * 86400;
 
     eprintln!(
-        "Decade simulation: {total_days} days, {major_incidents} major incidents, {deployments} deployments, {policy_version} policy versions"
+        "Decade simulation: {total_days} days, {} major incidents, {} deployments, {} policy versions",
+        state.major_incidents, state.deployments, state.policy_version
     );
 
-    if failures.is_empty() {
+    let mut result = if failures.is_empty() {
         CampaignResult::pass("decade_simulation", total_days, simulated_seconds, elapsed)
     } else {
         CampaignResult::fail("decade_simulation", total_days, failures)
+    };
+    result.minimized_panic = minimized_panic;
+    result
+}
+
+// =============================================================================
+// BUG-CLASS CLASSIFICATION
+// =============================================================================
+
+/// One unique class of panic reproduced across VOPR campaigns, so a
+/// developer scanning [`classify_bug_classes`]'s output sees a handful
+/// of distinct triage entries instead of scrolling through thousands of
+/// near-duplicate `failures.push(...)` lines.
+#[derive(Debug, Clone)]
+pub struct BugClass {
+    /// Canonical signature: the panicking call site plus the shrunk
+    /// policy's action shape, e.g. `"prover panicked | [keep,circuit_breaker]"`.
+    pub signature: String,
+    /// Names of every campaign that hit this signature.
+    pub campaigns: Vec<String>,
+    /// The smallest seed found reproducing this class.
+    pub smallest_seed: u64,
+    /// How many campaign results hit this class.
+    pub occurrences: usize,
+    /// The simulated-time context of the smallest-seed reproducer's
+    /// run, taken verbatim from its first failure detail (e.g.
+    /// `"cascade phase 45"`, `"Year 3 Day 12"`).
+    pub context: String,
+}
+
+/// Maps a rule's action to the coarse shape [`bug_signature`] groups by
+/// - specific rates/windows don't matter for "is this the same bug",
+/// only which action variant panicked.
+fn action_shape(rule: &Rule) -> &'static str {
+    match rule.action {
+        Action::Keep => "keep",
+        Action::Drop => "drop",
+        Action::Sample(_) => "sample",
+        Action::CircuitBreaker { .. } => "circuit_breaker",
+    }
+}
+
+/// Canonicalizes a signature from `location` (the panicking call site,
+/// e.g. `"prover panicked"`) and the shrunk policy's action shape, so
+/// reproducers differing only in match expressions or rule names but
+/// hitting the same code path collapse into one [`BugClass`].
+fn bug_signature(location: &str, panic: &MinimizedPanic) -> String {
+    let shape: Vec<&str> = panic.rules.iter().map(action_shape).collect();
+    format!("{location} | [{}]", shape.join(","))
+}
+
+/// Extracts the panicking call site from a campaign's first failure
+/// detail, falling back to the whole string if neither phrase appears -
+/// every [`shrink_panic`] call site's failure message contains one of
+/// these two phrases.
+fn panic_location(detail: &str) -> &str {
+    if detail.contains("compiler panicked") {
+        "compiler panicked"
+    } else if detail.contains("prover panicked") {
+        "prover panicked"
+    } else {
+        detail
     }
 }
 
+/// Groups every campaign result that recorded a [`MinimizedPanic`] into
+/// distinct bug classes, deduplicating identical `(panic location,
+/// minimized policy shape)` signatures across campaigns. Turns ten
+/// independent campaigns' failures into a handful of unique triage
+/// entries, each with the smallest reproducing seed found and the
+/// simulated-time context it occurred in.
+#[must_use]
+pub fn classify_bug_classes(results: &[CampaignResult]) -> Vec<BugClass> {
+    let mut classes: Vec<BugClass> = Vec::new();
+
+    for result in results {
+        let Some(panic) = &result.minimized_panic else {
+            continue;
+        };
+        let context = result.failure_details.first().cloned().unwrap_or_default();
+        let signature = bug_signature(panic_location(&context), panic);
+
+        if let Some(class) = classes.iter_mut().find(|c| c.signature == signature) {
+            class.occurrences += 1;
+            if !class.campaigns.contains(&result.name) {
+                class.campaigns.push(result.name.clone());
+            }
+            if panic.seed < class.smallest_seed {
+                class.smallest_seed = panic.seed;
+                class.context.clone_from(&context);
+            }
+        } else {
+            classes.push(BugClass {
+                signature,
+                campaigns: vec![result.name.clone()],
+                smallest_seed: panic.seed,
+                occurrences: 1,
+                context,
+            });
+        }
+    }
+
+    classes
+}
+
 // =============================================================================
 // CAMPAIGN RUNNERS
 // =============================================================================
@@ -925,6 +1764,82 @@ pub fn run_extended_campaigns(seed: u64) -> Vec<CampaignResult> {
 // HELPER FUNCTIONS
 // =============================================================================
 
+/// Loads a previously-persisted corpus from `input_dir`, returning an
+/// empty `Vec` if the directory doesn't exist yet (first run) or can't
+/// be read.
+#[cfg(feature = "fuzz")]
+fn fuzz_campaign_load_corpus(input_dir: &std::path::Path) -> Vec<Vec<u8>> {
+    let Ok(entries) = std::fs::read_dir(input_dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| std::fs::read(entry.path()).ok())
+        .collect()
+}
+
+/// Writes `data` into `dir` under a content-addressed filename, so
+/// re-saving the same input is a no-op and concurrent campaigns against
+/// the same workspace never collide. IO failures are swallowed - a
+/// campaign shouldn't fail just because its workspace directory
+/// couldn't be written to.
+#[cfg(feature = "fuzz")]
+fn fuzz_campaign_persist(dir: &std::path::Path, data: &[u8]) {
+    if std::fs::create_dir_all(dir).is_err() {
+        return;
+    }
+    let _ = std::fs::write(dir.join(format!("{:016x}", fuzz_campaign_hash(data))), data);
+}
+
+#[cfg(feature = "fuzz")]
+fn fuzz_campaign_hash(data: &[u8]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Mutates `base` into a new candidate buffer: a few random bit flips,
+/// plus an occasional random tail extension, mirroring the byte-level
+/// mutations a real coverage-guided fuzzer applies to seeds plucked
+/// from its corpus.
+#[cfg(feature = "fuzz")]
+fn fuzz_campaign_mutate(base: &[u8], rng: &mut ChaCha8Rng) -> Vec<u8> {
+    let mut data = if base.is_empty() {
+        vec![0u8; 32]
+    } else {
+        base.to_vec()
+    };
+
+    for _ in 0..rng.gen_range(1..=4) {
+        let idx = rng.gen_range(0..data.len());
+        data[idx] ^= 1 << rng.gen_range(0..8);
+    }
+
+    if rng.gen_bool(0.2) {
+        let extra = rng.gen_range(1..16);
+        data.extend((0..extra).map(|_| rng.gen::<u8>()));
+    }
+
+    data
+}
+
+/// Reduces a compiler/prover `Result` down to an `"ok"`/`"err:<variant>"`
+/// tag for fingerprinting, without having to exhaustively match every
+/// variant of two different crates' error enums (and staying correct if
+/// either gains a variant later).
+#[cfg(feature = "fuzz")]
+fn fuzz_campaign_outcome_tag<T, E: std::fmt::Debug>(result: &std::result::Result<T, E>) -> String {
+    match result {
+        Ok(_) => "ok".to_string(),
+        Err(e) => {
+            let debug = format!("{e:?}");
+            let variant = debug.split(['(', ' ', '{']).next().unwrap_or("err");
+            format!("err:{variant}")
+        }
+    }
+}
+
 fn generate_random_action(rng: &mut ChaCha8Rng) -> Action {
     if rng.gen_bool(0.3) {
         Action::Keep
@@ -972,14 +1887,30 @@ fn minimal_policy() -> Policy {
 
 fn emergency_policy() -> Policy {
     let mut policy = Policy::new("emergency-policy");
-    policy.add_rule(Rule::new("keep-all-errors", "error == true", Action::Keep, 100));
+    policy.add_rule(Rule::new(
+        "keep-all-errors",
+        "error == true",
+        Action::Keep,
+        100,
+    ));
     policy.add_rule(Rule::new(
         "keep-all-slow",
         "duration > 1000ms",
         Action::Keep,
         90,
     ));
-    policy.add_rule(Rule::new("sample-rest", "true", Action::Sample(0.10), 0));
+    policy.add_rule(Rule::new(
+        "sample-rest",
+        "true",
+        Action::CircuitBreaker {
+            closed_rate: 0.10,
+            open_rate: 0.75,
+            window: 200,
+            failure_threshold: 0.25,
+            min_samples: 50,
+        },
+        0,
+    ));
     policy
 }
 
@@ -994,7 +1925,13 @@ fn degraded_policy(rng: &mut ChaCha8Rng) -> Policy {
     policy.add_rule(Rule::new(
         "fallback",
         "true",
-        Action::Sample(rng.gen_range(0.01..0.05)),
+        Action::CircuitBreaker {
+            closed_rate: rng.gen_range(0.01..0.05),
+            open_rate: 0.9,
+            window: 200,
+            failure_threshold: 0.2,
+            min_samples: 50,
+        },
         0,
     ));
     policy
@@ -1049,7 +1986,18 @@ fn high_traffic_policy() -> Policy {
         Action::Sample(0.05),
         50,
     ));
-    policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.001), 0));
+    policy.add_rule(Rule::new(
+        "fallback",
+        "true",
+        Action::CircuitBreaker {
+            closed_rate: 0.001,
+            open_rate: 0.5,
+            window: 500,
+            failure_threshold: 0.1,
+            min_samples: 100,
+        },
+        0,
+    ));
     policy
 }
 
@@ -1105,6 +2053,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn evolution_campaign_has_no_minimal_repro_when_it_passes() {
+        // `minimal_repro` is only populated once a `Compile` step fails;
+        // a clean run shouldn't have wasted time delta-debugging nothing.
+        let result = run_evolution_campaign(42, 365);
+        assert!(result.all_passed);
+        assert!(result.minimal_repro.is_none());
+    }
+
+    #[test]
+    fn evolution_campaign_emits_a_replayable_artifact() {
+        let result = run_evolution_campaign(42, 365);
+        let artifact = result
+            .replay_artifact
+            .as_ref()
+            .expect("evolution campaign should record a replay artifact");
+        assert_eq!(artifact.seed, 42);
+        assert!(!artifact.actions.is_empty());
+
+        let replayed = replay_artifact(artifact);
+        assert_eq!(replayed.all_passed, result.all_passed);
+        assert_eq!(replayed.failure_details, result.failure_details);
+    }
+
     #[test]
     fn determinism_campaign_5000_iterations() {
         let result = run_determinism_campaign(42, 5000);
@@ -1147,6 +2119,113 @@ mod tests {
         );
     }
 
+    #[test]
+    fn scheduled_combined_faults_campaign_1000_iterations() {
+        let config = ScheduledCampaignConfig::default()
+            .with_seed(42)
+            .with_iterations(1000);
+        let result = run_scheduled_combined_faults_campaign(&config);
+        assert!(
+            result.all_passed,
+            "Scheduled combined faults failed: {:?}",
+            result.failure_details
+        );
+        assert_eq!(result.iterations, 1000);
+    }
+
+    #[test]
+    fn power_schedule_boosts_energy_on_novel_fingerprints_and_decays_on_repeats() {
+        let mut schedule = PowerSchedule::new(7, 4);
+        let (seed, _) = schedule.next();
+
+        schedule.record_outcome(seed, "novel".to_string());
+        let boosted = schedule
+            .entries
+            .iter()
+            .find(|e| e.seed == seed)
+            .unwrap()
+            .energy;
+        assert!(
+            boosted > 1.0,
+            "energy should increase on a novel fingerprint"
+        );
+
+        schedule.record_outcome(seed, "novel".to_string());
+        let decayed = schedule
+            .entries
+            .iter()
+            .find(|e| e.seed == seed)
+            .unwrap()
+            .energy;
+        assert!(
+            decayed < boosted,
+            "energy should decrease on a repeated fingerprint"
+        );
+    }
+
+    #[test]
+    fn power_schedule_replaces_an_exhausted_seed() {
+        let mut schedule = PowerSchedule::new(7, 1);
+        let seed = schedule.entries[0].seed;
+
+        // Decay the single pool entry below the replacement floor.
+        for _ in 0..10 {
+            schedule.record_outcome(seed, "same".to_string());
+        }
+
+        assert_ne!(
+            schedule.entries[0].seed, seed,
+            "an exhausted seed should be replaced rather than run forever"
+        );
+        assert!((schedule.entries[0].energy - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn fuzz_campaign_persists_a_corpus_and_finds_no_panics() {
+        let workspace =
+            std::env::temp_dir().join(format!("nektor-fuzz-campaign-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&workspace);
+
+        let result = run_fuzz_campaign(42, 500, &workspace);
+        assert!(
+            result.all_passed,
+            "Fuzz campaign found crashes: {:?}",
+            result.failure_details
+        );
+        assert_eq!(result.corpus_path.as_deref(), Some(workspace.as_path()));
+        assert_eq!(result.new_crashes, 0);
+        assert!(
+            workspace.join("input").read_dir().unwrap().next().is_some(),
+            "expected at least one interesting input to be retained"
+        );
+
+        let _ = std::fs::remove_dir_all(&workspace);
+    }
+
+    #[cfg(feature = "fuzz")]
+    #[test]
+    fn fuzz_campaign_resumes_from_a_persisted_corpus() {
+        let workspace = std::env::temp_dir().join(format!(
+            "nektor-fuzz-campaign-resume-test-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&workspace);
+
+        let first = run_fuzz_campaign(7, 200, &workspace);
+        let saved_inputs = std::fs::read_dir(workspace.join("input")).unwrap().count();
+        assert!(saved_inputs > 0);
+
+        // A second run against the same workspace should load the
+        // already-saved corpus rather than starting from the two
+        // built-in seed buffers.
+        let loaded = fuzz_campaign_load_corpus(&workspace.join("input"));
+        assert_eq!(loaded.len(), saved_inputs);
+        assert!(first.all_passed);
+
+        let _ = std::fs::remove_dir_all(&workspace);
+    }
+
     #[test]
     fn infrastructure_faults_campaign() {
         let result = run_infrastructure_faults_campaign(42, 500);
@@ -1217,6 +2296,109 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cascading_failure_campaign_has_no_minimized_panic_when_it_passes() {
+        // `minimized_panic` is only populated once a compile/verify call
+        // panics; a clean run shouldn't have wasted time shrinking nothing.
+        let result = run_cascading_failure_campaign(42, 500);
+        assert!(result.all_passed);
+        assert!(result.minimized_panic.is_none());
+    }
+
+    #[test]
+    fn classify_bug_classes_deduplicates_identical_signatures_across_campaigns() {
+        let shape = vec![Rule::new("fallback", "true", Action::Keep, 0)];
+
+        let mut a = CampaignResult::fail(
+            "chaos",
+            1_000,
+            vec!["Iteration 612: prover panicked in cascade phase 60".to_string()],
+        );
+        a.minimized_panic = Some(MinimizedPanic {
+            seed: 99,
+            config: SyntheticConfig::default(),
+            rules: shape.clone(),
+        });
+
+        let mut b = CampaignResult::fail(
+            "decade_simulation",
+            3_650,
+            vec!["Year 3 Day 12: prover panicked".to_string()],
+        );
+        b.minimized_panic = Some(MinimizedPanic {
+            seed: 7,
+            config: SyntheticConfig::default(),
+            rules: shape,
+        });
+
+        let classes = classify_bug_classes(&[a, b]);
+
+        assert_eq!(
+            classes.len(),
+            1,
+            "same panic location and action shape should collapse into one class"
+        );
+        assert_eq!(classes[0].occurrences, 2);
+        assert_eq!(classes[0].smallest_seed, 7, "should keep the smaller seed");
+        assert_eq!(classes[0].context, "Year 3 Day 12: prover panicked");
+        assert_eq!(classes[0].campaigns, vec!["chaos", "decade_simulation"]);
+    }
+
+    #[test]
+    fn cascading_failure_fallback_circuit_breaker_trips_open_and_resets_deterministically() {
+        // The cascading-failure campaign's `degraded_policy` fallback rule
+        // uses `Action::CircuitBreaker` so a prolonged incident escalates
+        // sampling on its own. Replay a corpus that goes healthy -> error
+        // burst -> healthy again through it directly, independent of the
+        // campaign's own random fault schedule, and assert the breaker
+        // actually opens mid-corpus, closes again once traces recover, and
+        // trips at the exact same point on every run given the same seed.
+        use nectar_corpus::{Corpus, Trace};
+        use nectar_prover::replay::ReplayConfig;
+
+        let policy = degraded_policy(&mut ChaCha8Rng::seed_from_u64(42));
+
+        let mut traces = Vec::new();
+        for i in 0..100 {
+            traces.push(Trace::new(format!("healthy-{i}")).with_status(200));
+        }
+        for i in 0..150 {
+            traces.push(Trace::new(format!("incident-{i}")).with_status(200));
+            // `keep-critical` intercepts real 5xx traces before the
+            // fallback rule ever sees them, so the incident is modeled as
+            // attribute-level degradation the fallback rule's match
+            // expression ("true") still routes to the breaker.
+            traces.last_mut().unwrap().is_error = true;
+        }
+        for i in 0..220 {
+            traces.push(Trace::new(format!("recovered-{i}")).with_status(200));
+        }
+        let corpus: Corpus = traces.into_iter().collect();
+
+        let prover = Prover::default();
+        let replay = || {
+            prover
+                .replay_corpus(&policy, &corpus, ReplayConfig::new())
+                .unwrap()
+        };
+
+        let result1 = replay();
+        let result2 = replay();
+
+        assert_eq!(
+            result1.total_kept, result2.total_kept,
+            "same seed must trip (and reset) the breaker at identical points"
+        );
+        assert!(
+            result1.total_kept > 0,
+            "breaker should have escalated sampling during the incident"
+        );
+        assert!(
+            result1.total_kept < corpus.len(),
+            "breaker should have closed again once traces recovered"
+        );
+    }
+
     #[test]
     fn full_vopr_suite() {
         let results = run_all_campaigns(42);
@@ -1249,6 +2431,24 @@ mod tests {
             total_real as f64 / 1000.0
         );
 
+        let bug_classes = classify_bug_classes(&results);
+        if !bug_classes.is_empty() {
+            println!(
+                "{} unique failure signature(s) across {} iterations:",
+                bug_classes.len(),
+                total_iterations
+            );
+            for class in &bug_classes {
+                println!(
+                    "  [{}] hit by {} campaign(s), smallest seed {} ({})",
+                    class.signature,
+                    class.campaigns.len(),
+                    class.smallest_seed,
+                    class.context
+                );
+            }
+        }
+
         assert!(all_passed, "Some campaigns failed");
     }
 }
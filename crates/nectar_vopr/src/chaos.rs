@@ -5,6 +5,7 @@
 //! - Policy mutation (invalid rules, missing fallbacks)
 //! - Timing anomalies (simulated delays, timeouts)
 
+use crate::ddmin;
 use nectar_corpus::{Corpus, Trace};
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
@@ -254,6 +255,17 @@ pub fn chaos_campaign(policy: &Policy, corpus: &Corpus, iterations: usize) -> Ve
         let compiler = nectar_compiler::Compiler::new();
         let compiler_result = compiler.compile(&chaotic_policy);
 
+        let prover_failed = prover_result.is_err();
+        let compiler_failed = compiler_result.is_err();
+        let minimized = (prover_failed || compiler_failed).then(|| {
+            ddmin::minimize(&chaotic_policy, &chaotic_corpus, |p, c| {
+                let prover = nectar_prover::Prover::default();
+                let compiler = nectar_compiler::Compiler::new();
+                (prover_failed && prover.verify(p, c).is_err())
+                    || (compiler_failed && compiler.compile(p).is_err())
+            })
+        });
+
         results.push(ChaosResult {
             iteration: i,
             intensity,
@@ -261,6 +273,7 @@ pub fn chaos_campaign(policy: &Policy, corpus: &Corpus, iterations: usize) -> Ve
             compiler_survived: compiler_result.is_ok(),
             prover_error: prover_result.err().map(|e| e.to_string()),
             compiler_error: compiler_result.err().map(|e| e.to_string()),
+            minimized,
         });
     }
 
@@ -282,6 +295,9 @@ pub struct ChaosResult {
     pub prover_error: Option<String>,
     /// Compiler error message (if any).
     pub compiler_error: Option<String>,
+    /// Minimal `(Policy, Corpus)` reproducer, delta-debugged down from
+    /// the chaotic pair, if either check failed.
+    pub minimized: Option<(Policy, Corpus)>,
 }
 
 impl ChaosResult {
@@ -0,0 +1,286 @@
+//! Delta-debugging minimization of failing chaos inputs.
+//!
+//! [`crate::chaos::chaos_campaign`] reports *that* a corrupted
+//! policy/corpus pair broke the prover or compiler, but a 50%-corrupted
+//! corpus is a poor bug report. [`minimize`] shrinks a failing pair down
+//! to a 1-minimal reproducer — removing any single remaining rule or
+//! trace makes the failure go away — using Zeller's ddmin algorithm.
+
+use crate::replay::SimAction;
+use nectar_corpus::{Corpus, Trace};
+use std::collections::HashSet;
+use toon_policy::{Policy, Rule};
+
+/// Shrinks a failing `(Policy, Corpus)` pair to a smaller pair that
+/// still reproduces the failure.
+///
+/// `predicate(policy, corpus)` should return `true` if the pair still
+/// triggers the failure being minimized. The policy's rules are
+/// minimized first (against the original corpus), then the corpus's
+/// traces are minimized against the already-minimized policy.
+#[must_use]
+pub fn minimize(
+    policy: &Policy,
+    corpus: &Corpus,
+    mut predicate: impl FnMut(&Policy, &Corpus) -> bool,
+) -> (Policy, Corpus) {
+    let rules = policy.rules.clone();
+    let minimal_rules = ddmin(rules.len(), |indices| {
+        predicate(&policy_with_rules(policy, &rules, indices), corpus)
+    });
+    let minimized_policy = policy_with_rules(policy, &rules, &minimal_rules);
+
+    let traces: Vec<Trace> = corpus.iter().cloned().collect();
+    let minimal_traces = ddmin(traces.len(), |indices| {
+        predicate(&minimized_policy, &corpus_with_traces(&traces, indices))
+    });
+    let minimized_corpus = corpus_with_traces(&traces, &minimal_traces);
+
+    (minimized_policy, minimized_corpus)
+}
+
+/// A minimized reproducer for a campaign failure: the seed the original
+/// (unminimized) run used, plus the shrunk [`SimAction`] sequence that
+/// still reproduces it - small enough for a developer to read directly
+/// instead of re-running the whole campaign.
+#[derive(Debug, Clone)]
+pub struct MinimalRepro {
+    /// The seed the original run used.
+    pub seed: u64,
+    /// The shrunk sequence of actions that still reproduces the failure.
+    pub actions: Vec<SimAction>,
+}
+
+/// Shrinks a failing [`SimAction`] sequence down to a minimal
+/// reproducer, using the same ddmin engine as [`minimize`].
+///
+/// Unlike a bare policy/corpus pair, an action sequence has sequencing
+/// dependencies - a `RemoveRule` only makes sense after the matching
+/// `AddRule` - that plain index-subset removal can break, producing a
+/// "minimized" sequence that doesn't even replay the same way the
+/// original did. [`drop_dangling_removes`] is applied to every
+/// candidate (including the final result) so a `RemoveRule` never
+/// survives without its `AddRule`.
+///
+/// `predicate(actions)` should return `true` if replaying the candidate
+/// sequence still reproduces the failure being minimized.
+#[must_use]
+pub fn minimize_actions(
+    actions: &[SimAction],
+    mut predicate: impl FnMut(&[SimAction]) -> bool,
+) -> Vec<SimAction> {
+    let minimal_indices = ddmin(actions.len(), |indices| {
+        let candidate: Vec<SimAction> = indices.iter().map(|&i| actions[i].clone()).collect();
+        predicate(&drop_dangling_removes(&candidate))
+    });
+
+    let minimal: Vec<SimAction> = minimal_indices
+        .iter()
+        .map(|&i| actions[i].clone())
+        .collect();
+    drop_dangling_removes(&minimal)
+}
+
+/// Drops any `RemoveRule` whose matching `AddRule` isn't present earlier
+/// in `actions`, so a ddmin reduction can never hand back a sequence
+/// that removes a rule which (in this subsequence) was never added.
+fn drop_dangling_removes(actions: &[SimAction]) -> Vec<SimAction> {
+    let mut added = HashSet::new();
+    let mut kept = Vec::with_capacity(actions.len());
+
+    for action in actions {
+        match action {
+            SimAction::AddRule { name, .. } => {
+                added.insert(name.clone());
+                kept.push(action.clone());
+            }
+            SimAction::RemoveRule { name } => {
+                if added.remove(name) {
+                    kept.push(action.clone());
+                }
+            }
+            _ => kept.push(action.clone()),
+        }
+    }
+
+    kept
+}
+
+/// Zeller's ddmin algorithm, generic over the element count.
+///
+/// `still_fails(indices)` tests the candidate subset (by index into the
+/// original `len`-element set) and returns `true` if it still
+/// reproduces the failure. Returns the index set of a 1-minimal failing
+/// subset.
+fn ddmin(len: usize, mut still_fails: impl FnMut(&[usize]) -> bool) -> Vec<usize> {
+    let mut current: Vec<usize> = (0..len).collect();
+    let mut granularity = 2usize;
+
+    while current.len() >= 2 {
+        let chunk_size = current.len().div_ceil(granularity);
+        let chunks: Vec<Vec<usize>> = current.chunks(chunk_size).map(<[usize]>::to_vec).collect();
+
+        if let Some(subset) = chunks.iter().find(|chunk| still_fails(chunk)) {
+            current = subset.clone();
+            granularity = 2;
+            continue;
+        }
+
+        let complement = chunks.iter().find_map(|chunk| {
+            let complement: Vec<usize> = current
+                .iter()
+                .copied()
+                .filter(|i| !chunk.contains(i))
+                .collect();
+            still_fails(&complement).then_some(complement)
+        });
+        if let Some(complement) = complement {
+            current = complement;
+            granularity = (granularity - 1).max(2);
+            continue;
+        }
+
+        if granularity >= current.len() {
+            break;
+        }
+        granularity = (granularity * 2).min(current.len());
+    }
+
+    current
+}
+
+fn policy_with_rules(policy: &Policy, rules: &[Rule], indices: &[usize]) -> Policy {
+    Policy {
+        version: policy.version,
+        name: policy.name.clone(),
+        budget_per_second: policy.budget_per_second,
+        rules: indices.iter().map(|&i| rules[i].clone()).collect(),
+    }
+}
+
+fn corpus_with_traces(traces: &[Trace], indices: &[usize]) -> Corpus {
+    indices.iter().map(|&i| traces[i].clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toon_policy::Action;
+
+    #[test]
+    fn ddmin_finds_the_single_failing_element() {
+        // Only index 3 is "bad"; ddmin should whittle down to just it.
+        let minimal = ddmin(10, |indices| indices.contains(&3));
+        assert_eq!(minimal, vec![3]);
+    }
+
+    #[test]
+    fn ddmin_finds_a_failing_pair() {
+        // The failure only reproduces when both 1 and 7 are present.
+        let minimal = ddmin(10, |indices| indices.contains(&1) && indices.contains(&7));
+        assert_eq!(minimal.len(), 2);
+        assert!(minimal.contains(&1) && minimal.contains(&7));
+    }
+
+    #[test]
+    fn minimize_shrinks_to_the_single_offending_rule() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("a", "true", Action::Keep, 10));
+        policy.add_rule(Rule::new("bad", "((invalid && ||", Action::Keep, 20));
+        policy.add_rule(Rule::new("c", "status == 200", Action::Keep, 5));
+        let corpus = Corpus::new();
+
+        let (minimized_policy, minimized_corpus) = minimize(&policy, &corpus, |p, _| {
+            p.rules.iter().any(|r| r.match_expr.contains("((invalid"))
+        });
+
+        assert_eq!(minimized_policy.rules.len(), 1);
+        assert_eq!(minimized_policy.rules[0].name, "bad");
+        assert!(minimized_corpus.is_empty());
+    }
+
+    #[test]
+    fn minimize_shrinks_to_the_single_offending_trace() {
+        let policy = Policy::new("test");
+        let corpus: Corpus = (0..5)
+            .map(|i| Trace::new(format!("trace-{i}")).with_status(if i == 2 { 999 } else { 200 }))
+            .collect();
+
+        let (_, minimized_corpus) = minimize(&policy, &corpus, |_, c| {
+            c.iter().any(|t| t.status == Some(999))
+        });
+
+        assert_eq!(minimized_corpus.len(), 1);
+        assert_eq!(minimized_corpus.iter().next().unwrap().status, Some(999));
+    }
+
+    fn add(name: &str) -> SimAction {
+        SimAction::AddRule {
+            name: name.to_string(),
+            match_expr: "true".to_string(),
+            action: Action::Keep,
+            priority: 10,
+        }
+    }
+
+    fn remove(name: &str) -> SimAction {
+        SimAction::RemoveRule {
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn minimize_actions_shrinks_to_the_single_offending_add() {
+        let actions = vec![
+            add("a"),
+            add("b"),
+            add("bad"),
+            SimAction::Verify,
+            add("c"),
+            SimAction::Compile,
+        ];
+
+        let minimal = minimize_actions(&actions, |candidate| {
+            candidate
+                .iter()
+                .any(|a| matches!(a, SimAction::AddRule { name, .. } if name == "bad"))
+        });
+
+        assert_eq!(minimal.len(), 1);
+        assert!(matches!(&minimal[0], SimAction::AddRule { name, .. } if name == "bad"));
+    }
+
+    #[test]
+    fn minimize_actions_drops_dangling_removes() {
+        // "keep" is never removed by the failing predicate's criterion,
+        // but ddmin's index-subset search will try candidates that keep
+        // the RemoveRule{"keep"} without its AddRule{"keep"} - those
+        // must come back out.
+        let actions = vec![add("keep"), add("bad"), remove("keep"), SimAction::Compile];
+
+        let minimal = minimize_actions(&actions, |candidate| {
+            candidate
+                .iter()
+                .any(|a| matches!(a, SimAction::AddRule { name, .. } if name == "bad"))
+        });
+
+        assert!(!minimal
+            .iter()
+            .any(|a| matches!(a, SimAction::RemoveRule { name } if name == "keep")));
+    }
+
+    #[test]
+    fn drop_dangling_removes_keeps_paired_add_remove() {
+        let actions = vec![add("a"), remove("a")];
+        let kept = drop_dangling_removes(&actions);
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[test]
+    fn drop_dangling_removes_drops_unpaired_remove() {
+        let actions = vec![add("a"), remove("b")];
+        let kept = drop_dangling_removes(&actions);
+        assert_eq!(kept.len(), 1);
+        assert!(matches!(&kept[0], SimAction::AddRule { name, .. } if name == "a"));
+    }
+}
@@ -0,0 +1,240 @@
+//! Coverage-guided structured fuzzing over [`Policy`]/[`Corpus`] pairs.
+//!
+//! Unlike [`crate::chaos`], which mutates known-good fixtures along a
+//! fixed corruption menu, this module builds policies and corpora
+//! directly from a raw byte buffer via `arbitrary`. That lets an
+//! external coverage-guided fuzzer - honggfuzz, via the
+//! `hfuzz_targets/fuzz_one.rs` binary, or libFuzzer/cargo-fuzz, via
+//! `fuzz_targets/fuzz_one.rs` - explore the input space on its own,
+//! while [`fuzz_campaign`] offers the same entry point for
+//! deterministic, seeded in-process fuzzing.
+//!
+//! Gated behind the `fuzz` feature so normal builds don't pull in the
+//! `arbitrary` dependency.
+
+use crate::replay::{PolicyEvolutionSim, SimAction};
+use crate::synthetic::SyntheticConfig;
+use arbitrary::{Arbitrary, Result as ArbitraryResult, Unstructured};
+use nectar_corpus::Corpus;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use std::panic;
+use toon_policy::{Action, Policy};
+
+/// Hand-rolled rather than derived so the drawn values stay small and
+/// plausible (a handful of services, a bounded trace count, rates
+/// clamped to `0.0..=1.0`) instead of whatever a derived impl would
+/// pull out of raw bytes for `Vec<String>`/`f64` fields - the same
+/// reasoning as [`toon_policy`]'s hand-written `Policy`/`Rule` impls.
+impl<'a> Arbitrary<'a> for SyntheticConfig {
+    fn arbitrary(u: &mut Unstructured<'a>) -> ArbitraryResult<Self> {
+        let service_count = u.int_in_range(1..=5u8)?;
+        let services = (0..service_count)
+            .map(|i| format!("fuzz-service-{i}"))
+            .collect();
+
+        let config = Self::default()
+            .with_seed(u.arbitrary()?)
+            .with_trace_count(u.int_in_range(1..=200usize)?)
+            .with_error_rate(f64::from(u.int_in_range(0..=100u8)?) / 100.0)
+            .with_slow_rate(f64::from(u.int_in_range(0..=100u8)?) / 100.0);
+
+        Ok(Self { services, ..config })
+    }
+}
+
+/// Builds a [`Policy`] and [`Corpus`] from `data` and runs them through
+/// the prover and compiler, asserting that neither ever panics.
+///
+/// Malformed draws (buffer exhausted before a valid value could be
+/// built) are silently skipped, same as a real `arbitrary`-based target
+/// would do on a `Result::Err` from `Arbitrary::arbitrary`.
+///
+/// # Panics
+///
+/// Panics if `Prover::verify` or `Compiler::compile` panics instead of
+/// returning a `Result`.
+pub fn fuzz_one(data: &[u8]) {
+    let mut u = Unstructured::new(data);
+    let Ok(policy) = Policy::arbitrary(&mut u) else {
+        return;
+    };
+    let Ok(corpus) = Corpus::arbitrary(&mut u) else {
+        return;
+    };
+
+    let prover = nectar_prover::Prover::default();
+    let prover_result =
+        panic::catch_unwind(panic::AssertUnwindSafe(|| prover.verify(&policy, &corpus)));
+    assert!(
+        prover_result.is_ok(),
+        "Prover::verify panicked instead of returning a Result"
+    );
+
+    let compiler = nectar_compiler::Compiler::new();
+    let compiler_result =
+        panic::catch_unwind(panic::AssertUnwindSafe(|| compiler.compile(&policy)));
+    assert!(
+        compiler_result.is_ok(),
+        "Compiler::compile panicked instead of returning a Result"
+    );
+}
+
+/// Runs `iterations` rounds of seeded, deterministic fuzzing, returning
+/// the first input that triggers a panic in [`fuzz_one`].
+///
+/// This mirrors [`nectar_corpus::fuzz_ingest`]'s in-process campaign
+/// entry point, but drives the policy/corpus model directly instead of
+/// the ingest path.
+#[must_use]
+pub fn fuzz_campaign(seed: u64, iterations: usize) -> Option<Vec<u8>> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    for _ in 0..iterations {
+        let len = rng.gen_range(0..4096);
+        let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+        if panic::catch_unwind(panic::AssertUnwindSafe(|| fuzz_one(&data))).is_err() {
+            return Some(data);
+        }
+    }
+    None
+}
+
+/// A handful of deliberately well-formed rule names and match
+/// expressions, mirroring `toon_policy::arbitrary_impl`'s approach: the
+/// generated `SimAction` sequence should mostly exercise valid
+/// `AddRule`/`RemoveRule` grammar rather than drowning in strings the
+/// parser rejects outright.
+const FUZZ_RULE_NAMES: &[&str] = &["rule-a", "rule-b", "rule-c", "fallback"];
+const FUZZ_MATCH_EXPRS: &[&str] = &[
+    "true",
+    "status >= 500",
+    "status == 200",
+    "error",
+    "is_error",
+];
+
+impl<'a> Arbitrary<'a> for SimAction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> ArbitraryResult<Self> {
+        Ok(match u.int_in_range(0..=6u8)? {
+            0 => Self::AddRule {
+                name: (*u.choose(FUZZ_RULE_NAMES)?).to_string(),
+                match_expr: (*u.choose(FUZZ_MATCH_EXPRS)?).to_string(),
+                action: Action::arbitrary(u)?,
+                priority: u.arbitrary()?,
+            },
+            1 => Self::RemoveRule {
+                name: (*u.choose(FUZZ_RULE_NAMES)?).to_string(),
+            },
+            2 => Self::IngestTraces {
+                bytes: Vec::<u8>::arbitrary(u)?,
+                content_type: u
+                    .arbitrary::<bool>()?
+                    .then(|| "application/json".to_string()),
+            },
+            3 => Self::EvictTraces {
+                count: u.int_in_range(0..=32usize)?,
+            },
+            4 => Self::Verify,
+            5 => Self::Compile,
+            _ => Self::Checkpoint,
+        })
+    }
+}
+
+/// Builds a bounded [`SimAction`] sequence from `data` and replays it
+/// through two fresh [`PolicyEvolutionSim`] instances, asserting they
+/// stay replay-identical and that neither panics.
+///
+/// Surfaces nondeterminism - e.g. hash-map iteration order leaking into
+/// `policy_hash`/`compiled_hash` - and compiler/prover crashes on
+/// adversarial rule sets, neither of which the hand-written
+/// `replay_verification_detects_changes` test can reach.
+///
+/// # Panics
+///
+/// Panics if replaying the action sequence panics instead of returning,
+/// or if the two simulations' replay logs diverge.
+pub fn fuzz_replay_determinism(data: &[u8]) {
+    let mut u = Unstructured::new(data);
+    let Ok(actions) = <Vec<SimAction>>::arbitrary(&mut u) else {
+        return;
+    };
+
+    let policy = Policy::new("fuzz-replay");
+    let corpus = Corpus::new();
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let mut sim1 = PolicyEvolutionSim::new(policy.clone(), corpus.clone());
+        sim1.run_sequence(&actions);
+        let mut sim2 = PolicyEvolutionSim::new(policy, corpus);
+        sim2.run_sequence(&actions);
+        sim1.log.verify_replay(&sim2.log)
+    }));
+
+    match result {
+        Ok(logs_match) => assert!(
+            logs_match,
+            "identical action sequences produced divergent replay logs"
+        ),
+        Err(_) => panic!("PolicyEvolutionSim panicked while replaying an action sequence"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzz_one_is_a_no_op_on_empty_input() {
+        fuzz_one(&[]);
+    }
+
+    #[test]
+    fn fuzz_campaign_finds_no_panics_over_many_iterations() {
+        assert!(fuzz_campaign(42, 256).is_none());
+    }
+
+    #[test]
+    fn arbitrary_synthetic_config_stays_within_bounds() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(512).collect();
+        let mut u = Unstructured::new(&bytes);
+        let config = SyntheticConfig::arbitrary(&mut u).unwrap();
+
+        assert!((1..=200).contains(&config.trace_count));
+        assert!((0.0..=1.0).contains(&config.error_rate));
+        assert!((0.0..=1.0).contains(&config.slow_rate));
+        assert!((1..=5).contains(&config.services.len()));
+    }
+
+    #[test]
+    fn fuzz_replay_determinism_is_a_no_op_on_empty_input() {
+        fuzz_replay_determinism(&[]);
+    }
+
+    #[test]
+    fn fuzz_replay_determinism_finds_no_divergence_over_many_seeds() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        for _ in 0..256 {
+            let len = rng.gen_range(0..256);
+            let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            fuzz_replay_determinism(&data);
+        }
+    }
+
+    #[test]
+    fn arbitrary_sim_action_builds_from_a_small_buffer() {
+        let bytes = [0u8; 32];
+        let mut u = Unstructured::new(&bytes);
+        let action = SimAction::arbitrary(&mut u).unwrap();
+        assert!(matches!(
+            action,
+            SimAction::AddRule { .. }
+                | SimAction::RemoveRule { .. }
+                | SimAction::IngestTraces { .. }
+                | SimAction::EvictTraces { .. }
+                | SimAction::Verify
+                | SimAction::Compile
+                | SimAction::Checkpoint
+        ));
+    }
+}
@@ -102,10 +102,33 @@ pub fn action() -> impl Strategy<Value = Action> {
     prop_oneof![
         3 => Just(Action::Keep),
         1 => Just(Action::Drop),
-        6 => (0.001f64..1.0).prop_map(Action::Sample),
+        5 => (0.001f64..1.0).prop_map(Action::Sample),
+        1 => circuit_breaker_action(),
     ]
 }
 
+/// Strategy for generating valid circuit-breaker actions.
+fn circuit_breaker_action() -> impl Strategy<Value = Action> {
+    (
+        0.001f64..0.2,
+        0.5f64..1.0,
+        10usize..500,
+        0.05f64..0.5,
+        5usize..50,
+    )
+        .prop_map(
+            |(closed_rate, open_rate, window, failure_threshold, min_samples)| {
+                Action::CircuitBreaker {
+                    closed_rate,
+                    open_rate,
+                    window,
+                    failure_threshold,
+                    min_samples,
+                }
+            },
+        )
+}
+
 /// Strategy for generating valid priorities (u8).
 pub fn priority() -> impl Strategy<Value = u8> {
     0u8..=100
@@ -184,5 +207,17 @@ mod tests {
             let result = nectar_compiler::match_expr::MatchExpr::parse(&expr);
             prop_assert!(result.is_ok(), "Failed to parse: {}", expr);
         }
+
+        #[test]
+        fn generated_circuit_breaker_actions_are_in_range(action in circuit_breaker_action()) {
+            let Action::CircuitBreaker { closed_rate, open_rate, window, failure_threshold, min_samples } = action else {
+                panic!("circuit_breaker_action() must only generate Action::CircuitBreaker");
+            };
+            prop_assert!((0.0..=1.0).contains(&closed_rate));
+            prop_assert!((0.0..=1.0).contains(&open_rate));
+            prop_assert!((0.0..=1.0).contains(&failure_threshold));
+            prop_assert!(window >= 1);
+            prop_assert!(min_samples >= 1);
+        }
     }
 }
@@ -8,13 +8,349 @@ use crate::simulation::{Scenario, SimResult};
 use crate::synthetic::{SyntheticConfig, SyntheticCorpus};
 use nectar_compiler::Compiler;
 use nectar_corpus::Corpus;
-use nectar_prover::{Prover, ProverConfig};
+use nectar_prover::{Error as ProverError, Prover};
+use nectar_prover::{ProverConfig, ProverResult};
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 use toon_policy::Policy;
 use xxhash_rust::xxh64::xxh64;
 
+/// Key identifying a memoized verification or compilation outcome: a hash
+/// of the serialized policy, combined with the corpus hash (or `0` for
+/// compile-only lookups, which do not depend on a corpus).
+type CacheKey = (u64, u64);
+
+/// LRU cache of [`Prover::verify`]/[`Compiler::compile`] outcomes, keyed on
+/// `xxh64` of the serialized policy plus the corpus hash.
+///
+/// Opt in via [`SimConfig::with_result_cache`]. The cache is bypassed
+/// entirely when chaos injection is enabled, since chaos mutates the
+/// corpus between calls and a cached verification would no longer be
+/// valid for the mutated input.
+#[derive(Debug)]
+struct ResultCache {
+    capacity: usize,
+    verify: HashMap<CacheKey, Result<ProverResult, String>>,
+    compile: HashMap<CacheKey, Result<String, String>>,
+    order: VecDeque<CacheKey>,
+}
+
+impl ResultCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            verify: HashMap::new(),
+            compile: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn touch(&mut self, key: CacheKey) {
+        self.order.retain(|k| *k != key);
+        self.order.push_back(key);
+        while self.order.len() > self.capacity {
+            if let Some(evict) = self.order.pop_front() {
+                self.verify.remove(&evict);
+                self.compile.remove(&evict);
+            }
+        }
+    }
+
+    fn verify_cached(
+        &mut self,
+        prover: &Prover,
+        policy: &Policy,
+        corpus: &Corpus,
+    ) -> Result<ProverResult, ProverError> {
+        let key = (policy_hash(policy), corpus_hash(corpus));
+        if let Some(cached) = self.verify.get(&key).cloned() {
+            self.touch(key);
+            return cached.map_err(ProverError::Internal);
+        }
+
+        let result = prover.verify(policy, corpus);
+        self.verify
+            .insert(key, result.clone().map_err(|e| e.to_string()));
+        self.touch(key);
+        result
+    }
+
+    fn compile_cached(
+        &mut self,
+        compiler: &Compiler,
+        policy: &Policy,
+    ) -> Result<String, nectar_compiler::Error> {
+        let key = (policy_hash(policy), 0);
+        if let Some(cached) = self.compile.get(&key).cloned() {
+            self.touch(key);
+            return cached.map_err(nectar_compiler::Error::Unsupported);
+        }
+
+        let result = compiler.compile(policy);
+        self.compile
+            .insert(key, result.clone().map_err(|e| e.to_string()));
+        self.touch(key);
+        result
+    }
+}
+
+/// Produces candidate "smaller" scenarios for shrinking: one rule removed
+/// from the policy, or the corpus cut in half, or the cardinality halved.
+/// Each candidate is strictly smaller than `scenario`; the caller keeps
+/// whichever candidates still reproduce the failure.
+fn shrink_candidates(scenario: &Scenario) -> Vec<Scenario> {
+    match scenario {
+        Scenario::CompileDeterminism { policy } => shrink_policy(policy)
+            .into_iter()
+            .map(|policy| Scenario::CompileDeterminism { policy })
+            .collect(),
+        Scenario::RoundTrip { policy } => shrink_policy(policy)
+            .into_iter()
+            .map(|policy| Scenario::RoundTrip { policy })
+            .collect(),
+        Scenario::ProverConsistency { policy, corpus } => {
+            let mut out: Vec<Scenario> = shrink_policy(policy)
+                .into_iter()
+                .map(|policy| Scenario::ProverConsistency {
+                    policy,
+                    corpus: corpus.clone(),
+                })
+                .collect();
+            out.extend(shrink_corpus(corpus).into_iter().map(|corpus| {
+                Scenario::ProverConsistency {
+                    policy: policy.clone(),
+                    corpus,
+                }
+            }));
+            out
+        }
+        Scenario::ChaosResilience {
+            policy,
+            corpus,
+            seed,
+        } => {
+            let mut out: Vec<Scenario> = shrink_policy(policy)
+                .into_iter()
+                .map(|policy| Scenario::ChaosResilience {
+                    policy,
+                    corpus: corpus.clone(),
+                    seed: *seed,
+                })
+                .collect();
+            out.extend(
+                shrink_corpus(corpus)
+                    .into_iter()
+                    .map(|corpus| Scenario::ChaosResilience {
+                        policy: policy.clone(),
+                        corpus,
+                        seed: *seed,
+                    }),
+            );
+            out
+        }
+        Scenario::OutputContract {
+            policy,
+            corpus,
+            expected,
+        } => {
+            let mut out: Vec<Scenario> = shrink_policy(policy)
+                .into_iter()
+                .map(|policy| Scenario::OutputContract {
+                    policy,
+                    corpus: corpus.clone(),
+                    expected: expected.clone(),
+                })
+                .collect();
+            out.extend(
+                shrink_corpus(corpus)
+                    .into_iter()
+                    .map(|corpus| Scenario::OutputContract {
+                        policy: policy.clone(),
+                        corpus,
+                        expected: expected.clone(),
+                    }),
+            );
+            out
+        }
+        Scenario::HighCardinality {
+            unique_services,
+            seed,
+        } if *unique_services > 1 => {
+            vec![Scenario::HighCardinality {
+                unique_services: unique_services / 2,
+                seed: *seed,
+            }]
+        }
+        Scenario::HighCardinality { .. } => Vec::new(),
+        Scenario::IngestFuzz { seed, iterations } if *iterations > 1 => {
+            vec![Scenario::IngestFuzz {
+                seed: *seed,
+                iterations: iterations / 2,
+            }]
+        }
+        Scenario::IngestFuzz { .. } => Vec::new(),
+    }
+}
+
+/// Removes each rule from `policy` in turn, one candidate per rule.
+fn shrink_policy(policy: &Policy) -> Vec<Policy> {
+    if policy.rules.len() <= 1 {
+        return Vec::new();
+    }
+
+    (0..policy.rules.len())
+        .map(|i| {
+            let mut smaller = policy.clone();
+            smaller.rules.remove(i);
+            smaller
+        })
+        .collect()
+}
+
+/// Splits `corpus` in half, offering each half as a candidate.
+fn shrink_corpus(corpus: &Corpus) -> Vec<Corpus> {
+    let traces: Vec<_> = corpus.iter().cloned().collect();
+    if traces.len() <= 1 {
+        return Vec::new();
+    }
+
+    let mid = traces.len() / 2;
+    let halves = [&traces[..mid], &traces[mid..]];
+
+    halves
+        .into_iter()
+        .map(|half| {
+            let mut smaller = Corpus::new();
+            for trace in half {
+                smaller.add(trace.clone());
+            }
+            smaller
+        })
+        .collect()
+}
+
+/// Derives a deterministic per-scenario seed from the master seed and a
+/// stable scenario identifier, so that inserting a new scenario or running
+/// existing ones out of order or concurrently never disturbs another
+/// scenario's generated corpus.
+fn derive_scenario_seed(master_seed: u64, scenario_name: &str) -> u64 {
+    let mut buf = Vec::with_capacity(8 + scenario_name.len());
+    buf.extend_from_slice(&master_seed.to_le_bytes());
+    buf.extend_from_slice(scenario_name.as_bytes());
+    xxh64(&buf, 0)
+}
+
+fn policy_hash(policy: &Policy) -> u64 {
+    let serialized = serde_json::to_vec(policy).unwrap_or_default();
+    xxh64(&serialized, 0)
+}
+
+/// A single persisted failure record, one line in a `.nektar-failures` file.
+///
+/// The format mirrors proptest's failure-persistence files: a compact,
+/// append-only, human-diffable line per failing scenario so regressions can
+/// be checked into version control alongside the tests that found them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureRecord {
+    /// Master seed the simulation was run with.
+    pub master_seed: u64,
+    /// Name of the scenario that failed.
+    pub scenario_name: String,
+    /// Seed derived for this scenario at the time of failure.
+    pub sub_seed: u64,
+    /// Hash of the corpus configuration in effect when the scenario failed.
+    pub corpus_config_hash: u64,
+}
+
+impl FailureRecord {
+    /// Key used to deduplicate records for the same scenario inputs.
+    #[must_use]
+    pub fn key(&self) -> u64 {
+        let mut buf = Vec::with_capacity(self.scenario_name.len() + 24);
+        buf.extend_from_slice(&self.master_seed.to_le_bytes());
+        buf.extend_from_slice(self.scenario_name.as_bytes());
+        buf.extend_from_slice(&self.sub_seed.to_le_bytes());
+        buf.extend_from_slice(&self.corpus_config_hash.to_le_bytes());
+        xxh64(&buf, 0)
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{:016x} {} {:016x} {:016x}",
+            self.master_seed, self.scenario_name, self.sub_seed, self.corpus_config_hash
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut parts = line.split_whitespace();
+        let master_seed = u64::from_str_radix(parts.next()?, 16).ok()?;
+        let scenario_name = parts.next()?.to_string();
+        let sub_seed = u64::from_str_radix(parts.next()?, 16).ok()?;
+        let corpus_config_hash = u64::from_str_radix(parts.next()?, 16).ok()?;
+        Some(Self {
+            master_seed,
+            scenario_name,
+            sub_seed,
+            corpus_config_hash,
+        })
+    }
+}
+
+/// Append-only store of failing scenario records, backed by a
+/// `.nektar-failures` file.
+#[derive(Debug, Clone)]
+pub struct FailurePersistence {
+    path: PathBuf,
+    records: Vec<FailureRecord>,
+}
+
+impl FailurePersistence {
+    /// Loads failure records from `path`, creating an empty store if the
+    /// file does not yet exist.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let records = match fs::File::open(&path) {
+            Ok(file) => io::BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+                .filter_map(|line| FailureRecord::from_line(&line))
+                .collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self { path, records })
+    }
+
+    /// Returns the persisted failure records.
+    #[must_use]
+    pub fn records(&self) -> &[FailureRecord] {
+        &self.records
+    }
+
+    /// Records a new failure, appending it to the file unless an identical
+    /// record is already present.
+    pub fn record_failure(&mut self, record: FailureRecord) -> io::Result<()> {
+        if self.records.iter().any(|r| r.key() == record.key()) {
+            return Ok(());
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", record.to_line())?;
+        self.records.push(record);
+        Ok(())
+    }
+}
+
 /// Configuration for the simulation.
 #[derive(Debug, Clone)]
 pub struct SimConfig {
@@ -32,6 +368,11 @@ pub struct SimConfig {
     pub verify_determinism: bool,
     /// Maximum duration for a single scenario.
     pub timeout: Duration,
+    /// Path to a `.nektar-failures` file used to persist and replay failing
+    /// scenario seeds, if enabled.
+    pub failure_persistence_path: Option<PathBuf>,
+    /// Capacity of the opt-in verify/compile result cache, if enabled.
+    pub result_cache_capacity: Option<usize>,
 }
 
 impl Default for SimConfig {
@@ -44,6 +385,8 @@ impl Default for SimConfig {
             corpus_config: SyntheticConfig::default(),
             verify_determinism: true,
             timeout: Duration::from_secs(30),
+            failure_persistence_path: None,
+            result_cache_capacity: None,
         }
     }
 }
@@ -77,6 +420,24 @@ impl SimConfig {
         self.verify_determinism = false;
         self
     }
+
+    /// Enables failure persistence: failing scenarios are recorded to
+    /// `path`, and [`Simulation::replay`] re-runs only the persisted
+    /// failures before any fresh generation happens.
+    #[must_use]
+    pub fn with_failure_persistence(mut self, path: impl Into<PathBuf>) -> Self {
+        self.failure_persistence_path = Some(path.into());
+        self
+    }
+
+    /// Enables an opt-in LRU cache of verify/compile outcomes with the
+    /// given capacity, so identical policy/corpus pairs are not
+    /// re-verified or re-compiled across iterations.
+    #[must_use]
+    pub const fn with_result_cache(mut self, capacity: usize) -> Self {
+        self.result_cache_capacity = Some(capacity);
+        self
+    }
 }
 
 /// Simulation harness for deterministic testing.
@@ -87,6 +448,8 @@ pub struct Simulation {
     chaos: Option<ChaosInjector>,
     #[allow(dead_code)]
     results: Vec<SimResult>,
+    failures: Option<FailurePersistence>,
+    result_cache: Option<ResultCache>,
 }
 
 impl Simulation {
@@ -99,41 +462,113 @@ impl Simulation {
         } else {
             None
         };
+        let failures = config
+            .failure_persistence_path
+            .as_ref()
+            .and_then(|path| FailurePersistence::load(path).ok());
+        let result_cache = config
+            .result_cache_capacity
+            .map(ResultCache::new)
+            .filter(|_| !config.chaos_enabled);
 
         Self {
             config,
             rng,
             chaos,
             results: Vec::new(),
+            failures,
+            result_cache,
         }
     }
 
     /// Runs a scenario and returns the result.
+    ///
+    /// If failure persistence is enabled, a failing result is recorded to
+    /// the `.nektar-failures` file so a later [`Simulation::replay`] call
+    /// can reproduce it in isolation.
     pub fn run_scenario(&mut self, scenario: &Scenario) -> SimResult {
         let _start = Instant::now();
 
-        match scenario {
+        let result = match scenario {
             Scenario::CompileDeterminism { policy } => self.test_compile_determinism(policy),
             Scenario::ProverConsistency { policy, corpus } => {
                 self.test_prover_consistency(policy, corpus)
             }
             Scenario::RoundTrip { policy } => self.test_roundtrip(policy),
-            Scenario::ChaosResilience { policy, corpus } => {
-                self.test_chaos_resilience(policy, corpus)
-            }
-            Scenario::HighCardinality { unique_services } => {
-                self.test_high_cardinality(*unique_services)
+            Scenario::ChaosResilience {
+                policy,
+                corpus,
+                seed,
+            } => self.test_chaos_resilience(policy, corpus, *seed),
+            Scenario::HighCardinality {
+                unique_services,
+                seed,
+            } => self.test_high_cardinality(*unique_services, *seed),
+            Scenario::IngestFuzz { seed, iterations } => self.test_ingest_fuzz(*seed, *iterations),
+            Scenario::OutputContract {
+                policy,
+                corpus,
+                expected,
+            } => self.test_output_contract(policy, corpus, expected),
+        };
+
+        let derived_seed = derive_scenario_seed(self.config.seed, scenario.name());
+        let result = result.with_derived_seed(derived_seed);
+
+        if !result.passed {
+            if let Some(failures) = &mut self.failures {
+                let record = FailureRecord {
+                    master_seed: self.config.seed,
+                    scenario_name: scenario.name().to_string(),
+                    sub_seed: derived_seed,
+                    corpus_config_hash: corpus_config_hash(&self.config.corpus_config),
+                };
+                let _ = failures.record_failure(record);
             }
         }
+
+        result
     }
 
-    #[allow(clippy::unused_self)]
-    fn test_compile_determinism(&self, policy: &Policy) -> SimResult {
+    /// Returns a forked RNG for `scenario_name`, seeded deterministically
+    /// from the master seed so that adding, reordering, or running
+    /// scenarios concurrently never perturbs another scenario's stream.
+    #[must_use]
+    pub fn scenario_rng(&self, scenario_name: &str) -> ChaCha8Rng {
+        ChaCha8Rng::seed_from_u64(derive_scenario_seed(self.config.seed, scenario_name))
+    }
+
+    /// Re-runs only the scenarios persisted in the failure file, in the
+    /// order they were recorded, before any fresh generation happens.
+    ///
+    /// Returns an empty vector (rather than an error) when failure
+    /// persistence was not enabled, since there is nothing to replay.
+    pub fn replay(&mut self, scenarios: &[Scenario]) -> Vec<SimResult> {
+        let Some(failures) = &self.failures else {
+            return Vec::new();
+        };
+
+        let records = failures.records().to_vec();
+        records
+            .into_iter()
+            .filter_map(|record| {
+                scenarios
+                    .iter()
+                    .find(|s| s.name() == record.scenario_name)
+                    .map(|s| self.run_scenario(s))
+            })
+            .collect()
+    }
+
+    fn test_compile_determinism(&mut self, policy: &Policy) -> SimResult {
         let compiler = Compiler::new();
 
         // Compile multiple times
         let outputs: Vec<String> = (0..10)
-            .map(|_| compiler.compile(policy).unwrap_or_default())
+            .map(|_| match &mut self.result_cache {
+                Some(cache) => cache.compile_cached(&compiler, policy).unwrap_or_default(),
+                None => compiler.compile(policy).unwrap_or_default(),
+            })
             .collect();
 
         // All outputs should be identical
@@ -153,15 +588,19 @@ impl Simulation {
         }
     }
 
-    #[allow(clippy::unused_self)]
-    fn test_prover_consistency(&self, policy: &Policy, corpus: &Corpus) -> SimResult {
+    fn test_prover_consistency(&mut self, policy: &Policy, corpus: &Corpus) -> SimResult {
         let prover = Prover::new(ProverConfig {
             require_error_handling: true,
             ..Default::default()
         });
 
         // Verify multiple times
-        let results: Vec<_> = (0..10).map(|_| prover.verify(policy, corpus)).collect();
+        let results: Vec<_> = (0..10)
+            .map(|_| match &mut self.result_cache {
+                Some(cache) => cache.verify_cached(&prover, policy, corpus),
+                None => prover.verify(policy, corpus),
+            })
+            .collect();
 
         // All results should be identical
         let first = &results[0];
@@ -211,10 +650,16 @@ impl Simulation {
         }
     }
 
-    fn test_chaos_resilience(&mut self, policy: &Policy, corpus: &Corpus) -> SimResult {
-        let Some(chaos) = &mut self.chaos else {
+    fn test_chaos_resilience(&mut self, policy: &Policy, corpus: &Corpus, seed: u64) -> SimResult {
+        if !self.config.chaos_enabled {
             return SimResult::skip("chaos_resilience", "Chaos injection not enabled");
-        };
+        }
+
+        // Re-derive a fresh injector from this scenario's own seed, rather
+        // than reusing `self.chaos`, so the corruption is reproducible from
+        // the `SimResult` diagnostic alone - independent of how many other
+        // scenarios have drawn from the harness's shared RNG beforehand.
+        let mut chaos = ChaosInjector::new(self.config.chaos_config.clone().with_seed(seed));
 
         let prover = Prover::new(ProverConfig::default());
         let compiler = Compiler::new();
@@ -242,16 +687,13 @@ impl Simulation {
             (_, Err(e)) => SimResult::fail(
                 "chaos_resilience",
                 format!("Compiler failed under chaos: {e}"),
-            ),
+            )
+            .with_diagnostic(format!("seed={seed}")),
         }
     }
 
-    fn test_high_cardinality(&self, unique_services: usize) -> SimResult {
-        let config = self
-            .config
-            .corpus_config
-            .clone()
-            .with_seed(self.config.seed);
+    fn test_high_cardinality(&self, unique_services: usize, seed: u64) -> SimResult {
+        let config = self.config.corpus_config.clone().with_seed(seed);
         let mut gen = SyntheticCorpus::new(config);
         let corpus = gen.generate_high_cardinality(unique_services);
 
@@ -280,7 +722,8 @@ impl Simulation {
             return SimResult::fail(
                 "high_cardinality",
                 format!("Verification took too long: {elapsed:?}"),
-            );
+            )
+            .with_diagnostic(format!("seed={seed}"));
         }
 
         match result {
@@ -288,7 +731,99 @@ impl Simulation {
                 "high_cardinality",
                 format!("Handled {unique_services} unique services in {elapsed:?}"),
             ),
-            Err(e) => SimResult::fail("high_cardinality", format!("Verification failed: {e}")),
+            Err(e) => SimResult::fail("high_cardinality", format!("Verification failed: {e}"))
+                .with_diagnostic(format!("seed={seed}")),
+        }
+    }
+
+    fn test_ingest_fuzz(&self, seed: u64, iterations: usize) -> SimResult {
+        match nectar_corpus::fuzz_ingest(seed, iterations) {
+            None => SimResult::pass(
+                "ingest_fuzz",
+                format!("{iterations} mutated ingest inputs parsed or rejected cleanly"),
+            ),
+            Some(input) => SimResult::fail(
+                "ingest_fuzz",
+                format!(
+                    "ingest panicked on a mutated input ({} bytes, content_type={:?})",
+                    input.data.len(),
+                    input.content_type
+                ),
+            )
+            .with_diagnostic(format!("{:?}", input.data)),
+        }
+    }
+
+    /// Compiles `policy`, verifies it against `corpus`, and asserts that
+    /// every pattern in `expected` matches at least one diagnostic
+    /// (violation or warning message) the prover produced - a golden-style
+    /// behavioral contract for policy output, rather than only a
+    /// pass/fail invariant.
+    fn test_output_contract(
+        &mut self,
+        policy: &Policy,
+        corpus: &Corpus,
+        expected: &[String],
+    ) -> SimResult {
+        let compiler = Compiler::new();
+        let compiled = match &mut self.result_cache {
+            Some(cache) => cache.compile_cached(&compiler, policy),
+            None => compiler.compile(policy),
+        };
+        if let Err(e) = compiled {
+            return SimResult::fail("output_contract", format!("Policy failed to compile: {e}"));
+        }
+
+        let prover = Prover::new(ProverConfig::default());
+        let result = match &mut self.result_cache {
+            Some(cache) => cache.verify_cached(&prover, policy, corpus),
+            None => prover.verify(policy, corpus),
+        };
+        let result = match result {
+            Ok(r) => r,
+            Err(e) => {
+                return SimResult::fail("output_contract", format!("Verification failed: {e}"));
+            }
+        };
+
+        let diagnostics: Vec<String> = result
+            .violations
+            .iter()
+            .map(|v| format!("{}: {}: {}", v.severity, v.check, v.message))
+            .chain(
+                result
+                    .warnings
+                    .iter()
+                    .map(|w| format!("{}: {}: {}", w.severity, w.check, w.message)),
+            )
+            .collect();
+
+        let missing: Vec<&String> = expected
+            .iter()
+            .filter(|pattern| match regex::Regex::new(pattern) {
+                Ok(re) => !diagnostics.iter().any(|d| re.is_match(d)),
+                Err(_) => true,
+            })
+            .collect();
+
+        if missing.is_empty() {
+            SimResult::pass(
+                "output_contract",
+                format!("All {} expected patterns matched", expected.len()),
+            )
+        } else {
+            let mut result = SimResult::fail(
+                "output_contract",
+                format!(
+                    "{} of {} expected pattern(s) did not match any diagnostic",
+                    missing.len(),
+                    expected.len()
+                ),
+            );
+            for pattern in missing {
+                result = result.with_diagnostic(format!("missing pattern: {pattern}"));
+            }
+            result.with_diagnostic(format!("actual diagnostics: {diagnostics:?}"))
         }
     }
 
@@ -297,6 +832,30 @@ impl Simulation {
         scenarios.iter().map(|s| self.run_scenario(s)).collect()
     }
 
+    /// Shrinks a failing scenario to a smaller, still-failing
+    /// counterexample.
+    ///
+    /// Greedily tries each candidate produced by [`shrink_candidates`] (one
+    /// rule removed from the policy, the corpus halved, the cardinality
+    /// halved, ...) and keeps the first one that still fails, repeating
+    /// until no smaller failing candidate can be found. This mirrors
+    /// proptest's delta-debugging shrink loop, just specialized to
+    /// Nektar's scenario shapes instead of a generic `Arbitrary` tree.
+    pub fn shrink_failure(&mut self, scenario: &Scenario) -> Scenario {
+        let mut current = scenario.clone();
+
+        loop {
+            let smaller = shrink_candidates(&current)
+                .into_iter()
+                .find(|candidate| !self.run_scenario(candidate).passed);
+
+            match smaller {
+                Some(candidate) => current = candidate,
+                None => return current,
+            }
+        }
+    }
+
     /// Verifies that running the same simulation twice produces identical results.
     pub fn verify_determinism(&self) -> bool {
         if !self.config.verify_determinism {
@@ -314,6 +873,16 @@ impl Simulation {
     }
 }
 
+/// Hashes a [`SyntheticConfig`] so failure records can detect when the
+/// corpus-generation parameters in effect at failure time have since
+/// changed.
+fn corpus_config_hash(config: &SyntheticConfig) -> u64 {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&config.seed.to_le_bytes());
+    buf.extend_from_slice(&config.trace_count.to_le_bytes());
+    xxh64(&buf, 0)
+}
+
 fn corpus_hash(corpus: &Corpus) -> u64 {
     let mut hasher_input = String::new();
     for trace in corpus.iter() {
@@ -360,4 +929,232 @@ mod tests {
         let result = sim.run_scenario(&Scenario::CompileDeterminism { policy });
         assert!(result.passed, "Result: {result:?}");
     }
+
+    #[test]
+    fn shrink_candidates_remove_one_rule_at_a_time() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(toon_policy::Rule::new(
+            "a",
+            "true",
+            toon_policy::Action::Keep,
+            10,
+        ));
+        policy.add_rule(toon_policy::Rule::new(
+            "b",
+            "true",
+            toon_policy::Action::Drop,
+            5,
+        ));
+
+        let candidates = shrink_candidates(&Scenario::CompileDeterminism { policy });
+        assert_eq!(candidates.len(), 2);
+        for candidate in candidates {
+            if let Scenario::CompileDeterminism { policy } = candidate {
+                assert_eq!(policy.rules.len(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn shrink_candidates_halve_cardinality_until_exhausted() {
+        let candidates = shrink_candidates(&Scenario::HighCardinality {
+            unique_services: 64,
+            seed: 7,
+        });
+        assert_eq!(candidates.len(), 1);
+        assert!(shrink_candidates(&Scenario::HighCardinality {
+            unique_services: 1,
+            seed: 7,
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn ingest_fuzz_scenario_passes_on_a_clean_run() {
+        let mut sim = Simulation::new(SimConfig::default());
+        let result = sim.run_scenario(&Scenario::IngestFuzz {
+            seed: 42,
+            iterations: 200,
+        });
+        assert!(result.passed, "Result: {result:?}");
+    }
+
+    #[test]
+    fn shrink_candidates_halve_ingest_fuzz_iterations_until_exhausted() {
+        let candidates = shrink_candidates(&Scenario::IngestFuzz {
+            seed: 1,
+            iterations: 16,
+        });
+        assert_eq!(candidates.len(), 1);
+        assert!(shrink_candidates(&Scenario::IngestFuzz {
+            seed: 1,
+            iterations: 1
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn scenario_seed_derivation_is_stable_and_distinct_per_scenario() {
+        let sim = Simulation::new(SimConfig::default().with_seed(7));
+        let a = derive_scenario_seed(7, "roundtrip");
+        let b = derive_scenario_seed(7, "compile_determinism");
+        assert_eq!(a, derive_scenario_seed(7, "roundtrip"));
+        assert_ne!(a, b);
+
+        use rand::RngCore;
+        let first: u64 = sim.scenario_rng("roundtrip").next_u64();
+        let second: u64 = sim.scenario_rng("roundtrip").next_u64();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn high_cardinality_scenario_is_deterministic_for_a_given_seed() {
+        let mut sim = Simulation::new(SimConfig::default());
+        let scenario = Scenario::high_cardinality(8, 99);
+
+        let first = sim.run_scenario(&scenario);
+        let second = sim.run_scenario(&scenario);
+        assert_eq!(first.message, second.message);
+    }
+
+    #[test]
+    fn chaos_resilience_scenario_records_its_seed_on_failure() {
+        // An unparseable match expression always fails to compile,
+        // independent of how chaos corrupts the corpus, so this
+        // deterministically exercises the failure diagnostic path.
+        let mut policy = Policy::new("test");
+        policy.add_rule(toon_policy::Rule::new(
+            "broken",
+            "((( not valid",
+            toon_policy::Action::Keep,
+            100,
+        ));
+        let corpus = Corpus::new();
+
+        let config = SimConfig::default().with_chaos(ChaosConfig::default());
+        let mut sim = Simulation::new(config);
+        let result = sim.test_chaos_resilience(&policy, &corpus, 99);
+
+        assert!(!result.passed);
+        assert!(result.diagnostics.iter().any(|d| d == "seed=99"));
+    }
+
+    #[test]
+    fn chaos_resilience_scenario_is_deterministic_for_a_given_seed() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(toon_policy::Rule::new(
+            "keep-errors",
+            "http.status >= 500",
+            toon_policy::Action::Keep,
+            100,
+        ));
+        let mut corpus = Corpus::new();
+        for i in 0..20 {
+            corpus.add(nectar_corpus::Trace::new(format!("trace-{i}")).with_status(200));
+        }
+
+        let config = SimConfig::default().with_chaos(ChaosConfig::default());
+        let mut sim = Simulation::new(config);
+
+        let first = sim.test_chaos_resilience(&policy, &corpus, 7);
+        let second = sim.test_chaos_resilience(&policy, &corpus, 7);
+        assert_eq!(first.message, second.message);
+    }
+
+    #[test]
+    fn output_contract_scenario_passes_when_every_pattern_matches_a_diagnostic() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(toon_policy::Rule::new(
+            "keep-errors",
+            "http.status >= 500",
+            toon_policy::Action::Keep,
+            100,
+        ));
+        let corpus = Corpus::new();
+
+        let mut sim = Simulation::new(SimConfig::default());
+        let result = sim.run_scenario(&Scenario::output_contract(
+            policy,
+            corpus,
+            vec!["(?i)fallback".to_string()],
+        ));
+        assert!(result.passed, "Result: {result:?}");
+    }
+
+    #[test]
+    fn output_contract_scenario_fails_and_lists_missing_patterns() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(toon_policy::Rule::new(
+            "keep-errors",
+            "http.status >= 500",
+            toon_policy::Action::Keep,
+            100,
+        ));
+        policy.add_rule(toon_policy::Rule::new(
+            "fallback",
+            "true",
+            toon_policy::Action::Sample(0.01),
+            0,
+        ));
+        let corpus = Corpus::new();
+
+        let mut sim = Simulation::new(SimConfig::default());
+        let result = sim.run_scenario(&Scenario::output_contract(
+            policy,
+            corpus,
+            vec!["this pattern matches nothing".to_string()],
+        ));
+
+        assert!(!result.passed);
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|d| d.contains("this pattern matches nothing")));
+    }
+
+    #[test]
+    fn result_cache_is_disabled_under_chaos() {
+        let config = SimConfig::default()
+            .with_chaos(ChaosConfig::default())
+            .with_result_cache(16);
+        let sim = Simulation::new(config);
+        assert!(sim.result_cache.is_none());
+    }
+
+    #[test]
+    fn failure_record_round_trips_through_its_line_format() {
+        let record = FailureRecord {
+            master_seed: 42,
+            scenario_name: "roundtrip".to_string(),
+            sub_seed: 1234,
+            corpus_config_hash: 5678,
+        };
+
+        let line = record.to_line();
+        let parsed = FailureRecord::from_line(&line).expect("line should parse");
+        assert_eq!(record, parsed);
+    }
+
+    #[test]
+    fn failure_persistence_records_and_reloads_failures() {
+        let dir = std::env::temp_dir().join(format!("nektar-failures-test-{}", std::process::id()));
+        let _ = std::fs::remove_file(&dir);
+
+        let mut store = FailurePersistence::load(&dir).unwrap();
+        assert!(store.records().is_empty());
+
+        store
+            .record_failure(FailureRecord {
+                master_seed: 1,
+                scenario_name: "roundtrip".to_string(),
+                sub_seed: 2,
+                corpus_config_hash: 3,
+            })
+            .unwrap();
+
+        let reloaded = FailurePersistence::load(&dir).unwrap();
+        assert_eq!(reloaded.records().len(), 1);
+
+        let _ = std::fs::remove_file(&dir);
+    }
 }
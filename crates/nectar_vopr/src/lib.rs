@@ -31,14 +31,29 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::must_use_candidate)]
 
+pub mod campaigns;
 pub mod chaos;
+pub mod ddmin;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 pub mod generators;
 pub mod harness;
+pub mod regressions;
 pub mod replay;
+pub mod sat;
+pub mod shrink;
 pub mod simulation;
 pub mod synthetic;
+pub mod verify;
+pub mod watch;
 
+pub use campaigns::CampaignResult;
 pub use harness::{SimConfig, Simulation};
-pub use replay::{ReplayLog, TimeCompressor};
+pub use replay::{
+    population_stability_index, ChainError, DriftFingerprint, RegressionFlag, ReplayArtifact,
+    ReplayLog, TimeCompressor,
+};
 pub use simulation::{Scenario, SimResult};
 pub use synthetic::SyntheticCorpus;
+pub use verify::{verify_property, Property, Verdict};
+pub use watch::run_watch;
@@ -0,0 +1,193 @@
+//! Persistent corpus of seeds/iterations that have previously failed a
+//! VOPR campaign.
+//!
+//! Modeled on how property-test runners save counterexamples to disk:
+//! whenever a campaign observes a failure, it calls [`record`] to append
+//! a line to the `.nektor-vopr-regressions` file recording just enough
+//! to reconstruct that exact case again later - `campaign`, `base_seed`,
+//! the failing `iteration` (or simulated day), and, for campaigns like
+//! [`crate::campaigns::run_cascading_failure_campaign`] that cycle
+//! through a repeating phase, `cascade_phase`. [`load`] reads every
+//! entry back for a given campaign name, so that campaign's next run can
+//! replay its historically-bad inputs first, before its normal
+//! randomized sweep. A persisted entry that now passes just stays in the
+//! file - it's a cheap regression guard, not something worth deleting.
+
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Default location of the regression corpus, relative to the process's
+/// working directory.
+pub const DEFAULT_PATH: &str = ".nektor-vopr-regressions";
+
+/// One previously-observed campaign failure, stable-text-encoded as a
+/// single `|`-delimited line so the file stays diffable and greppable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegressionEntry {
+    /// Name of the campaign that failed (matches [`crate::campaigns::CampaignResult::name`]).
+    pub campaign: String,
+    /// The seed the original run used.
+    pub base_seed: u64,
+    /// The iteration (or simulated day) that failed.
+    pub iteration: usize,
+    /// The repeating cascade phase the failure occurred at, for
+    /// campaigns that have one. `None` for campaigns without a phase
+    /// concept.
+    pub cascade_phase: Option<usize>,
+}
+
+impl fmt::Display for RegressionEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phase = self.cascade_phase.map_or(String::new(), |p| p.to_string());
+        write!(
+            f,
+            "{}|{}|{}|{}",
+            self.campaign, self.base_seed, self.iteration, phase
+        )
+    }
+}
+
+impl FromStr for RegressionEntry {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut fields = line.splitn(4, '|');
+        let campaign = fields.next().ok_or("missing campaign field")?.to_string();
+        let base_seed = fields
+            .next()
+            .ok_or("missing base_seed field")?
+            .parse()
+            .map_err(|e| format!("invalid base_seed: {e}"))?;
+        let iteration = fields
+            .next()
+            .ok_or("missing iteration field")?
+            .parse()
+            .map_err(|e| format!("invalid iteration: {e}"))?;
+        let cascade_phase = match fields.next().unwrap_or("") {
+            "" => None,
+            phase => Some(
+                phase
+                    .parse()
+                    .map_err(|e| format!("invalid cascade_phase: {e}"))?,
+            ),
+        };
+        Ok(Self {
+            campaign,
+            base_seed,
+            iteration,
+            cascade_phase,
+        })
+    }
+}
+
+/// Appends `entry` to the regression corpus at `path`, creating the file
+/// if it doesn't exist yet. IO failures are swallowed - persistence is a
+/// best-effort regression guard, not something a campaign should fail
+/// over.
+pub fn record(path: &Path, entry: &RegressionEntry) {
+    let Ok(mut file) = fs::OpenOptions::new().create(true).append(true).open(path) else {
+        return;
+    };
+    let _ = writeln!(file, "{entry}");
+}
+
+/// Loads every persisted entry for `campaign` from `path`, in the order
+/// they were recorded. Returns an empty `Vec` if the file doesn't exist
+/// yet or its lines can't be parsed - a missing or corrupted regression
+/// corpus just means there's nothing to replay yet, not a campaign
+/// failure.
+#[must_use]
+pub fn load(path: &Path, campaign: &str) -> Vec<RegressionEntry> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| line.parse::<RegressionEntry>().ok())
+        .filter(|entry| entry.campaign == campaign)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_display_and_from_str() {
+        let entry = RegressionEntry {
+            campaign: "cascading_failure".to_string(),
+            base_seed: 42,
+            iteration: 612,
+            cascade_phase: Some(60),
+        };
+        let parsed: RegressionEntry = entry.to_string().parse().unwrap();
+        assert_eq!(parsed, entry);
+    }
+
+    #[test]
+    fn round_trips_without_a_cascade_phase() {
+        let entry = RegressionEntry {
+            campaign: "decade_simulation".to_string(),
+            base_seed: 7,
+            iteration: 365,
+            cascade_phase: None,
+        };
+        let parsed: RegressionEntry = entry.to_string().parse().unwrap();
+        assert_eq!(parsed, entry);
+    }
+
+    #[test]
+    fn record_and_load_round_trip_through_a_file() {
+        let dir = std::env::temp_dir().join(format!(
+            "nektor-vopr-regressions-test-{}",
+            std::process::id()
+        ));
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join(".nektor-vopr-regressions");
+        let _ = fs::remove_file(&path);
+
+        record(
+            &path,
+            &RegressionEntry {
+                campaign: "cascading_failure".to_string(),
+                base_seed: 1,
+                iteration: 10,
+                cascade_phase: Some(5),
+            },
+        );
+        record(
+            &path,
+            &RegressionEntry {
+                campaign: "decade_simulation".to_string(),
+                base_seed: 2,
+                iteration: 20,
+                cascade_phase: None,
+            },
+        );
+        record(
+            &path,
+            &RegressionEntry {
+                campaign: "cascading_failure".to_string(),
+                base_seed: 3,
+                iteration: 30,
+                cascade_phase: Some(15),
+            },
+        );
+
+        let loaded = load(&path, "cascading_failure");
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].base_seed, 1);
+        assert_eq!(loaded[1].base_seed, 3);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn load_returns_empty_when_the_file_doesnt_exist() {
+        let path = Path::new("/tmp/does-not-exist-nektor-vopr-regressions-test");
+        assert!(load(path, "anything").is_empty());
+    }
+}
@@ -6,11 +6,16 @@
 //! - Corpus drift simulation
 //! - Regression detection
 
-use nectar_compiler::Compiler;
-use nectar_corpus::Corpus;
+use nectar_compiler::tsr::{self, TimestampToken};
+use nectar_compiler::{Compiler, Result as CompilerResult};
+use nectar_corpus::{Corpus, Reservoir};
 use nectar_prover::Prover;
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fs;
+use std::io;
+use std::path::Path;
+use thiserror::Error;
 use toon_policy::{Action, Policy, Rule};
 use xxhash_rust::xxh64::xxh64;
 
@@ -122,19 +127,142 @@ impl ReplayLog {
         });
     }
 
-    /// Creates a checkpoint of the current state.
+    /// Records a distribution-drift fingerprint, as taken by a
+    /// corpus-mutating [`SimAction`] (`IngestTraces`/`EvictTraces`).
+    pub fn record_drift(
+        &mut self,
+        timestamp_ms: u64,
+        fingerprint: DriftFingerprint,
+        rule_names: Vec<String>,
+    ) {
+        self.events.push_back(ReplayEvent::Drift {
+            timestamp_ms,
+            fingerprint,
+            rule_names,
+        });
+    }
+
+    /// Walks the recorded [`ReplayEvent::Drift`] events and flags every
+    /// one whose fingerprint has drifted from the one `window` steps
+    /// earlier by at least `threshold`, population-stability-index
+    /// style: `sum((p_now - p_ref) * ln(p_now / p_ref))` over the
+    /// `error`/status-code/`latency` buckets from
+    /// [`DriftFingerprint::buckets`].
+    ///
+    /// Returns one [`RegressionFlag`] per flagged step, each carrying
+    /// the rules active in the policy at that point - the rules
+    /// responsible for whatever verdict the drifting corpus was given.
+    #[must_use]
+    pub fn detect_regressions(&self, window: usize, threshold: f64) -> Vec<RegressionFlag> {
+        let drift_events: Vec<(u64, &DriftFingerprint, &[String])> = self
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                ReplayEvent::Drift {
+                    timestamp_ms,
+                    fingerprint,
+                    rule_names,
+                } => Some((*timestamp_ms, fingerprint, rule_names.as_slice())),
+                _ => None,
+            })
+            .collect();
+
+        if window == 0 {
+            return Vec::new();
+        }
+
+        drift_events
+            .iter()
+            .enumerate()
+            .skip(window)
+            .filter_map(|(index, (timestamp_ms, fingerprint, rule_names))| {
+                let (_, reference, _) = &drift_events[index - window];
+                let psi = population_stability_index(&fingerprint.buckets(), &reference.buckets());
+                (psi >= threshold).then(|| RegressionFlag {
+                    timestamp_ms: *timestamp_ms,
+                    psi,
+                    rule_names: rule_names.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Creates a checkpoint of the current state, chained onto the
+    /// previous checkpoint (if any) via [`Checkpoint::chain_digest`] so
+    /// [`Self::verify_chain`] can later detect tampering or dropped
+    /// checkpoints.
     pub fn checkpoint(&mut self, timestamp_ms: u64, policy: &Policy, corpus: &Corpus) {
         let comp = Compiler::new();
         let output = comp.compile(policy).unwrap_or_default();
+        let prev_digest = self.checkpoints.last().map_or(0, Checkpoint::chain_digest);
 
         self.checkpoints.push(Checkpoint {
+            sequence_number: self.checkpoints.len() as u64,
             timestamp_ms,
             policy_hash: policy_hash(policy),
             corpus_hash: corpus_hash(corpus),
             compiled_hash: xxh64(output.as_bytes(), 0),
+            prev_digest,
+            tsa_digest_sha256: None,
+            tsa_url: None,
+            tsa_token_base64: None,
+            tsa_gen_time: None,
         });
     }
 
+    /// Walks the checkpoint chain, recomputing each link's
+    /// [`Checkpoint::chain_digest`] and checking it against the next
+    /// checkpoint's `prev_digest`, and that `sequence_number` is
+    /// contiguous from zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`ChainError`] encountered: a sequence gap or a
+    /// broken digest link, reported by the index of the offending
+    /// checkpoint.
+    pub fn verify_chain(&self) -> Result<(), ChainError> {
+        for (index, checkpoint) in self.checkpoints.iter().enumerate() {
+            let expected_seq = index as u64;
+            if checkpoint.sequence_number != expected_seq {
+                return Err(ChainError::SequenceGap {
+                    index,
+                    expected: expected_seq,
+                    found: checkpoint.sequence_number,
+                });
+            }
+
+            let expected_prev = index
+                .checked_sub(1)
+                .map_or(0, |prev| self.checkpoints[prev].chain_digest());
+            if checkpoint.prev_digest != expected_prev {
+                return Err(ChainError::DigestMismatch {
+                    index,
+                    expected: expected_prev,
+                    stored: checkpoint.prev_digest,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-verifies every checkpoint's RFC 3161 token (if any) against its
+    /// own `compiled_hash`, stopping at the first checkpoint whose token
+    /// no longer matches.
+    ///
+    /// Checkpoints with no token attached are treated as untimestamped
+    /// and skipped, not as a failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error from [`Checkpoint::verify_tsa_token`] for the
+    /// first checkpoint whose stored token fails to re-verify.
+    pub fn verify_timestamps(&self) -> CompilerResult<()> {
+        for checkpoint in &self.checkpoints {
+            checkpoint.verify_tsa_token()?;
+        }
+        Ok(())
+    }
+
     /// Verifies that replaying produces the same checkpoints.
     #[must_use]
     pub fn verify_replay(&self, other: &Self) -> bool {
@@ -179,11 +307,183 @@ pub enum ReplayEvent {
         /// Verification result message.
         message: String,
     },
+    /// A corpus-mutating step (`IngestTraces`/`EvictTraces`) recorded a
+    /// fresh distribution fingerprint of the corpus, plus the rules
+    /// active at the time - see [`ReplayLog::detect_regressions`].
+    Drift {
+        /// Simulated timestamp in milliseconds.
+        timestamp_ms: u64,
+        /// The corpus's distribution fingerprint at this step.
+        fingerprint: DriftFingerprint,
+        /// Names of the rules in the policy active at this step, so a
+        /// flagged regression can report which rules were governing the
+        /// drifting corpus.
+        rule_names: Vec<String>,
+    },
+}
+
+/// A distribution summary of a corpus at one point in simulated time:
+/// how much of it errors, its status-code mix, and its tail latency -
+/// the inputs [`ReplayLog::detect_regressions`] compares across a
+/// trailing window to catch silent drift.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftFingerprint {
+    /// Number of traces the fingerprint was computed over.
+    pub total: usize,
+    /// Fraction of traces with `is_error` set.
+    pub error_rate: f64,
+    /// Number of traces per HTTP status code (0 standing in for "no
+    /// status recorded").
+    pub status_histogram: BTreeMap<u16, usize>,
+    /// Median trace duration, in milliseconds.
+    pub p50_ms: u64,
+    /// 99th-percentile trace duration, in milliseconds.
+    pub p99_ms: u64,
+}
+
+impl DriftFingerprint {
+    /// Computes a fingerprint over `corpus`'s current contents.
+    #[must_use]
+    pub fn from_corpus(corpus: &Corpus) -> Self {
+        let total = corpus.len();
+        if total == 0 {
+            return Self {
+                total: 0,
+                error_rate: 0.0,
+                status_histogram: BTreeMap::new(),
+                p50_ms: 0,
+                p99_ms: 0,
+            };
+        }
+
+        let mut status_histogram = BTreeMap::new();
+        let mut error_count = 0usize;
+        #[allow(clippy::cast_possible_truncation)]
+        let mut durations_ms: Vec<u64> = Vec::with_capacity(total);
+        for trace in corpus.iter() {
+            if trace.is_error {
+                error_count += 1;
+            }
+            *status_histogram
+                .entry(trace.status.unwrap_or(0))
+                .or_insert(0) += 1;
+            durations_ms.push(trace.duration.as_millis() as u64);
+        }
+        durations_ms.sort_unstable();
+
+        #[allow(clippy::cast_precision_loss)]
+        let error_rate = error_count as f64 / total as f64;
+        Self {
+            total,
+            error_rate,
+            status_histogram,
+            p50_ms: percentile_ms(&durations_ms, 50.0),
+            p99_ms: percentile_ms(&durations_ms, 99.0),
+        }
+    }
+
+    /// Flattens this fingerprint into a named set of `[0, 1]`
+    /// proportions, comparable bucket-for-bucket against another
+    /// fingerprint's via [`population_stability_index`]: an `error`/`ok`
+    /// pair, one bucket per status code seen, and a `p50`/`p99` pair
+    /// capturing how much the tail dominates the median.
+    #[must_use]
+    pub fn buckets(&self) -> BTreeMap<String, f64> {
+        let mut buckets = BTreeMap::new();
+        if self.total == 0 {
+            return buckets;
+        }
+
+        buckets.insert("error:true".to_string(), self.error_rate);
+        buckets.insert("error:false".to_string(), 1.0 - self.error_rate);
+
+        #[allow(clippy::cast_precision_loss)]
+        for (status, count) in &self.status_histogram {
+            buckets.insert(
+                format!("status:{status}"),
+                *count as f64 / self.total as f64,
+            );
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let latency_total = (self.p50_ms + self.p99_ms).max(1) as f64;
+        #[allow(clippy::cast_precision_loss)]
+        {
+            buckets.insert(
+                "latency:p50".to_string(),
+                self.p50_ms as f64 / latency_total,
+            );
+            buckets.insert(
+                "latency:p99".to_string(),
+                self.p99_ms as f64 / latency_total,
+            );
+        }
+
+        buckets
+    }
+}
+
+/// Returns the value at percentile `p` (`0..=100`) of `sorted_ms`,
+/// which must already be sorted ascending. Returns `0` for an empty
+/// slice.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_truncation
+)]
+fn percentile_ms(sorted_ms: &[u64], p: f64) -> u64 {
+    if sorted_ms.is_empty() {
+        return 0;
+    }
+    let rank = ((p / 100.0) * sorted_ms.len() as f64).ceil().max(1.0) as usize;
+    sorted_ms[rank.min(sorted_ms.len()) - 1]
+}
+
+/// A vanishingly small floor applied to each bucket proportion before
+/// taking its logarithm, so a bucket present in one fingerprint but
+/// absent from the other contributes a large-but-finite PSI term
+/// instead of `ln(0)`.
+const PSI_EPSILON: f64 = 1e-6;
+
+/// Computes the population-stability-index-style divergence between two
+/// bucketed proportion maps: `sum((p_now - p_ref) * ln(p_now / p_ref))`
+/// over the union of bucket keys, treating a key missing from one side
+/// as [`PSI_EPSILON`] rather than zero.
+#[must_use]
+pub fn population_stability_index(
+    now: &BTreeMap<String, f64>,
+    reference: &BTreeMap<String, f64>,
+) -> f64 {
+    let keys: BTreeSet<&String> = now.keys().chain(reference.keys()).collect();
+    keys.into_iter()
+        .map(|key| {
+            let p_now = now.get(key).copied().unwrap_or(0.0).max(PSI_EPSILON);
+            let p_ref = reference.get(key).copied().unwrap_or(0.0).max(PSI_EPSILON);
+            (p_now - p_ref) * (p_now / p_ref).ln()
+        })
+        .sum()
+}
+
+/// One step flagged by [`ReplayLog::detect_regressions`] as having
+/// drifted too far from its trailing-window reference.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegressionFlag {
+    /// Simulated timestamp the drift was observed at.
+    pub timestamp_ms: u64,
+    /// The population-stability-index-style divergence score that
+    /// crossed the caller's threshold.
+    pub psi: f64,
+    /// Names of the rules active in the policy at this step.
+    pub rule_names: Vec<String>,
 }
 
 /// A checkpoint for state verification.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Checkpoint {
+    /// Position of this checkpoint in its log, starting at zero and
+    /// increasing by one each time - lets [`ReplayLog::verify_chain`]
+    /// notice a checkpoint that was silently dropped.
+    pub sequence_number: u64,
     /// Simulated timestamp.
     pub timestamp_ms: u64,
     /// Hash of the policy.
@@ -192,6 +492,114 @@ pub struct Checkpoint {
     pub corpus_hash: u64,
     /// Hash of the compiled output.
     pub compiled_hash: u64,
+    /// [`Checkpoint::chain_digest`] of the previous checkpoint in the
+    /// log (zero for the first one), committing this checkpoint to the
+    /// full history before it.
+    pub prev_digest: u64,
+    /// SHA-256 digest (hex) of `compiled_hash` that was timestamped, as
+    /// attested by `tsa_token_base64`. `timestamp_ms` is self-reported by
+    /// the simulation's own [`TimeCompressor`] and trivially forgeable;
+    /// this is the wall-clock proof that survives outside the sim.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tsa_digest_sha256: Option<String>,
+    /// URL of the RFC 3161 Time-Stamp Authority that issued the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tsa_url: Option<String>,
+    /// The raw RFC 3161 `TimeStampToken`, base64-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tsa_token_base64: Option<String>,
+    /// The `genTime` the TSA attested to, recovered from the token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tsa_gen_time: Option<String>,
+}
+
+impl Checkpoint {
+    /// Folds `(prev_digest, policy_hash, corpus_hash, compiled_hash,
+    /// timestamp_ms)` through `xxh64`, committing this checkpoint to
+    /// everything before it in the chain. The result becomes the next
+    /// checkpoint's `prev_digest`.
+    #[must_use]
+    pub fn chain_digest(&self) -> u64 {
+        let mut input = Vec::with_capacity(40);
+        input.extend_from_slice(&self.prev_digest.to_be_bytes());
+        input.extend_from_slice(&self.policy_hash.to_be_bytes());
+        input.extend_from_slice(&self.corpus_hash.to_be_bytes());
+        input.extend_from_slice(&self.compiled_hash.to_be_bytes());
+        input.extend_from_slice(&self.timestamp_ms.to_be_bytes());
+        xxh64(&input, 0)
+    }
+
+    /// The digest a TSA timestamp token for this checkpoint must cover:
+    /// the SHA-256 hash of `compiled_hash`'s big-endian bytes.
+    ///
+    /// Obtaining the token itself means reaching out to a TSA over the
+    /// network, which the rest of this crate avoids to stay pure and
+    /// deterministic; this method only prepares the digest, mirroring
+    /// how `nectar_compiler::Lockfile` leaves the HTTP exchange to its
+    /// caller (see `cmd/nectar`'s compile command).
+    #[must_use]
+    pub fn tsa_message_digest(&self) -> [u8; 32] {
+        tsr::sha256(&self.compiled_hash.to_be_bytes())
+    }
+
+    /// Attaches an RFC 3161 trusted-timestamp token obtained from `tsa_url`
+    /// over [`Self::tsa_message_digest`], recording its digest and the
+    /// `genTime` recovered from the token.
+    pub fn seal_with_tsa_token(&mut self, tsa_url: impl Into<String>, token: &TimestampToken) {
+        let digest = self.tsa_message_digest();
+        self.tsa_digest_sha256 = Some(tsr::hex(&digest));
+        self.tsa_url = Some(tsa_url.into());
+        self.tsa_token_base64 = Some(tsr::base64_encode(&token.raw_der));
+        self.tsa_gen_time = token.gen_time.clone();
+    }
+
+    /// Re-verifies a stored RFC 3161 token against this checkpoint's
+    /// `compiled_hash`, recomputing the digest, re-parsing the stored
+    /// token, and confirming its `messageImprint` still matches.
+    ///
+    /// Returns `Ok(None)` if this checkpoint carries no TSA token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a token is present but its stored bytes are
+    /// not valid base64, or if the digest no longer matches.
+    pub fn verify_tsa_token(&self) -> CompilerResult<Option<TimestampToken>> {
+        let Some(token_base64) = &self.tsa_token_base64 else {
+            return Ok(None);
+        };
+
+        let raw_der = tsr::base64_decode(token_base64)?;
+        let token = tsr::parse_timestamp_response(&raw_der);
+        tsr::verify_timestamp_token(&token, &self.tsa_message_digest())?;
+        Ok(Some(token))
+    }
+}
+
+/// Errors from [`ReplayLog::verify_chain`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ChainError {
+    /// A checkpoint's `sequence_number` wasn't the next contiguous
+    /// value, meaning a checkpoint was dropped or reordered.
+    #[error("checkpoint {index} has sequence number {found}, expected {expected}")]
+    SequenceGap {
+        /// Index into `checkpoints` where the gap was found.
+        index: usize,
+        /// The contiguous sequence number that was expected.
+        expected: u64,
+        /// The sequence number actually stored.
+        found: u64,
+    },
+    /// A checkpoint's `prev_digest` doesn't match the recomputed
+    /// [`Checkpoint::chain_digest`] of the checkpoint before it.
+    #[error("checkpoint {index} has prev_digest {stored:#018x}, expected {expected:#018x}")]
+    DigestMismatch {
+        /// Index into `checkpoints` where the chain broke.
+        index: usize,
+        /// The digest recomputed from the previous checkpoint.
+        expected: u64,
+        /// The `prev_digest` actually stored.
+        stored: u64,
+    },
 }
 
 /// Simulates policy evolution over time.
@@ -247,6 +655,35 @@ impl PolicyEvolutionSim {
                     .record_policy_change(self.time.simulated_time_ms, &self.policy);
                 StepResult::PolicyChanged
             }
+            SimAction::IngestTraces {
+                bytes,
+                content_type,
+            } => match Corpus::ingest_with_content_type(&bytes, content_type.as_deref()) {
+                Ok(ingested) => {
+                    for trace in ingested.into_traces() {
+                        self.corpus.add(trace);
+                    }
+                    self.record_drift();
+                    StepResult::CorpusChanged {
+                        trace_count: self.corpus.len(),
+                    }
+                }
+                Err(e) => StepResult::IngestFailed {
+                    error: e.to_string(),
+                },
+            },
+            SimAction::EvictTraces { count } => {
+                let target_capacity = self.corpus.len().saturating_sub(count).max(1);
+                let mut reservoir = Reservoir::with_capacity(target_capacity);
+                for trace in std::mem::take(&mut self.corpus).into_traces() {
+                    reservoir.add(trace);
+                }
+                self.corpus = reservoir.into_traces().into_iter().collect();
+                self.record_drift();
+                StepResult::CorpusChanged {
+                    trace_count: self.corpus.len(),
+                }
+            }
             SimAction::Verify => {
                 let result = self.prover.verify(&self.policy, &self.corpus);
                 let (passed, message) = match &result {
@@ -278,10 +715,96 @@ impl PolicyEvolutionSim {
     pub fn run_sequence(&mut self, actions: &[SimAction]) -> Vec<StepResult> {
         actions.iter().map(|a| self.step(a.clone())).collect()
     }
+
+    /// Records a [`CorpusChange`](ReplayEvent::CorpusChange) and a fresh
+    /// [`DriftFingerprint`] for the corpus's current contents, tagged
+    /// with the rules active right now.
+    fn record_drift(&mut self) {
+        self.log
+            .record_corpus_change(self.time.simulated_time_ms, &self.corpus);
+        let fingerprint = DriftFingerprint::from_corpus(&self.corpus);
+        let rule_names = self.policy.rules.iter().map(|r| r.name.clone()).collect();
+        self.log
+            .record_drift(self.time.simulated_time_ms, fingerprint, rule_names);
+    }
+}
+
+/// Current on-disk format version for [`ReplayArtifact`]. Bump this
+/// whenever the struct's shape changes in a way that would break
+/// loading older artifacts, so [`ReplayArtifact::load`] can reject an
+/// artifact it no longer knows how to read instead of silently
+/// misinterpreting it.
+pub const REPLAY_ARTIFACT_FORMAT_VERSION: u32 = 1;
+
+/// A self-contained, serializable record of one campaign run: the seed
+/// it started from and the exact ordered [`SimAction`] trace that was
+/// applied.
+///
+/// Rerunning a campaign with the same seed only reproduces a failure
+/// until the randomized mutation logic that generates actions changes
+/// underneath it. Capturing the concrete action stream instead lets a
+/// recorded failure survive those refactors, and lets a developer step
+/// through a single incident with [`crate::campaigns::replay_artifact`]
+/// instead of re-running the whole campaign.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayArtifact {
+    /// Format version this artifact was written with.
+    pub format_version: u32,
+    /// Name of the campaign that produced this artifact.
+    pub campaign: String,
+    /// Seed the original run used to build its initial policy/corpus.
+    pub seed: u64,
+    /// The exact ordered action trace that was applied.
+    pub actions: Vec<SimAction>,
+}
+
+impl ReplayArtifact {
+    /// Creates an artifact for `campaign`/`seed` with the given action trace.
+    #[must_use]
+    pub fn new(campaign: impl Into<String>, seed: u64, actions: Vec<SimAction>) -> Self {
+        Self {
+            format_version: REPLAY_ARTIFACT_FORMAT_VERSION,
+            campaign: campaign.into(),
+            seed,
+            actions,
+        }
+    }
+
+    /// Serializes the artifact to a compact JSON file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` can't be written to.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_vec(self)?;
+        fs::write(path, json)
+    }
+
+    /// Loads an artifact previously written by [`ReplayArtifact::save`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` can't be read, isn't valid JSON, or was
+    /// written with a format version newer than this crate understands.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = fs::read(path)?;
+        let artifact: Self = serde_json::from_slice(&json)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if artifact.format_version > REPLAY_ARTIFACT_FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "replay artifact format version {} is newer than supported version {REPLAY_ARTIFACT_FORMAT_VERSION}",
+                    artifact.format_version
+                ),
+            ));
+        }
+        Ok(artifact)
+    }
 }
 
 /// An action to perform in the simulation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SimAction {
     /// Add a rule to the policy.
     AddRule {
@@ -299,6 +822,22 @@ pub enum SimAction {
         /// Name of the rule to remove.
         name: String,
     },
+    /// Ingest trace data into the corpus, simulating real traffic
+    /// arriving and shifting its distribution.
+    IngestTraces {
+        /// Raw trace data, in any format `nectar_corpus`'s ingestor
+        /// registry recognizes.
+        bytes: Vec<u8>,
+        /// Content-type hint for format detection, as accepted by
+        /// [`Corpus::ingest_with_content_type`].
+        content_type: Option<String>,
+    },
+    /// Age `count` traces out of the corpus, simulating a sampling
+    /// reservoir's capacity being exceeded.
+    EvictTraces {
+        /// Number of traces to evict.
+        count: usize,
+    },
     /// Verify the current policy.
     Verify,
     /// Compile the current policy.
@@ -329,6 +868,17 @@ pub enum StepResult {
         /// Error message.
         error: String,
     },
+    /// The corpus was mutated (`IngestTraces`/`EvictTraces`) and a fresh
+    /// drift fingerprint recorded.
+    CorpusChanged {
+        /// Number of traces in the corpus after the mutation.
+        trace_count: usize,
+    },
+    /// Ingesting trace data into the corpus failed to parse.
+    IngestFailed {
+        /// Error message.
+        error: String,
+    },
     /// Checkpoint created.
     Checkpointed,
 }
@@ -418,6 +968,58 @@ mod tests {
         assert!(matches!(results[0], StepResult::Checkpointed));
     }
 
+    #[test]
+    fn replay_artifact_round_trips_through_json() {
+        let artifact = ReplayArtifact::new(
+            "evolution_campaign",
+            42,
+            vec![
+                SimAction::AddRule {
+                    name: "a".to_string(),
+                    match_expr: "true".to_string(),
+                    action: Action::Keep,
+                    priority: 10,
+                },
+                SimAction::Verify,
+                SimAction::Compile,
+            ],
+        );
+
+        let path = std::env::temp_dir().join(format!(
+            "nektor-replay-artifact-test-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        artifact.save(&path).unwrap();
+        let loaded = ReplayArtifact::load(&path).unwrap();
+
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.format_version, REPLAY_ARTIFACT_FORMAT_VERSION);
+        assert_eq!(loaded.seed, 42);
+        assert_eq!(loaded.actions.len(), 3);
+    }
+
+    #[test]
+    fn replay_artifact_load_rejects_a_future_format_version() {
+        let mut artifact = ReplayArtifact::new("evolution_campaign", 1, Vec::new());
+        artifact.format_version = REPLAY_ARTIFACT_FORMAT_VERSION + 1;
+
+        let path = std::env::temp_dir().join(format!(
+            "nektor-replay-artifact-future-test-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        artifact.save(&path).unwrap();
+        let result = ReplayArtifact::load(&path);
+
+        let _ = std::fs::remove_file(&path);
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn replay_verification_detects_changes() {
         let policy = test_policy();
@@ -432,4 +1034,305 @@ mod tests {
         // Same sequence should produce same checkpoints
         assert!(sim1.log.verify_replay(&sim2.log));
     }
+
+    #[test]
+    fn verify_chain_accepts_a_freshly_recorded_log() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+
+        let mut log = ReplayLog::new();
+        log.checkpoint(0, &policy, &corpus);
+        log.checkpoint(100, &policy, &corpus);
+        log.checkpoint(200, &policy, &corpus);
+
+        assert_eq!(log.checkpoints[0].sequence_number, 0);
+        assert_eq!(log.checkpoints[0].prev_digest, 0);
+        assert_eq!(log.checkpoints[2].sequence_number, 2);
+        assert_eq!(log.verify_chain(), Ok(()));
+    }
+
+    #[test]
+    fn verify_chain_detects_a_dropped_checkpoint() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+
+        let mut log = ReplayLog::new();
+        log.checkpoint(0, &policy, &corpus);
+        log.checkpoint(100, &policy, &corpus);
+        log.checkpoint(200, &policy, &corpus);
+
+        log.checkpoints.remove(1);
+
+        assert_eq!(
+            log.verify_chain(),
+            Err(ChainError::SequenceGap {
+                index: 1,
+                expected: 1,
+                found: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn verify_chain_detects_a_tampered_checkpoint() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+
+        let mut log = ReplayLog::new();
+        log.checkpoint(0, &policy, &corpus);
+        log.checkpoint(100, &policy, &corpus);
+
+        log.checkpoints[0].compiled_hash = log.checkpoints[0].compiled_hash.wrapping_add(1);
+
+        assert!(matches!(
+            log.verify_chain(),
+            Err(ChainError::DigestMismatch { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn checkpoint_tsa_token_round_trips_and_verifies() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+
+        let mut log = ReplayLog::new();
+        log.checkpoint(0, &policy, &corpus);
+        let digest = log.checkpoints[0].tsa_message_digest();
+
+        // Build a minimal synthetic token DER containing just the
+        // algorithm identifier + digest, as a real TSTInfo would nest it.
+        let raw_der = tsr::TimestampRequest::new(digest, 1).to_der();
+        let token = tsr::parse_timestamp_response(&raw_der);
+
+        log.checkpoints[0].seal_with_tsa_token("https://tsa.example.com", &token);
+
+        assert_eq!(
+            log.checkpoints[0].tsa_url.as_deref(),
+            Some("https://tsa.example.com")
+        );
+        assert!(log.checkpoints[0].tsa_token_base64.is_some());
+        assert!(log.verify_timestamps().is_ok());
+    }
+
+    #[test]
+    fn verify_timestamps_fails_when_a_checkpoint_hash_drifts() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+
+        let mut log = ReplayLog::new();
+        log.checkpoint(0, &policy, &corpus);
+        let digest = log.checkpoints[0].tsa_message_digest();
+        let raw_der = tsr::TimestampRequest::new(digest, 1).to_der();
+        let token = tsr::parse_timestamp_response(&raw_der);
+        log.checkpoints[0].seal_with_tsa_token("https://tsa.example.com", &token);
+
+        // Tamper with the hash the token was supposed to attest to.
+        log.checkpoints[0].compiled_hash = log.checkpoints[0].compiled_hash.wrapping_add(1);
+
+        assert!(log.verify_timestamps().is_err());
+    }
+
+    #[test]
+    fn verify_timestamps_is_ok_when_no_checkpoint_is_sealed() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+
+        let mut log = ReplayLog::new();
+        log.checkpoint(0, &policy, &corpus);
+
+        assert!(log.verify_timestamps().is_ok());
+    }
+
+    fn trace(
+        trace_id: &str,
+        status: u16,
+        is_error: bool,
+        duration_ms: u64,
+    ) -> nectar_corpus::Trace {
+        let mut trace = nectar_corpus::Trace::new(trace_id);
+        trace.status = Some(status);
+        trace.is_error = is_error;
+        trace.duration = std::time::Duration::from_millis(duration_ms);
+        trace
+    }
+
+    #[test]
+    fn drift_fingerprint_computes_error_rate_and_status_histogram() {
+        let mut corpus = Corpus::new();
+        corpus.add(trace("a", 200, false, 10));
+        corpus.add(trace("b", 200, false, 20));
+        corpus.add(trace("c", 500, true, 30));
+
+        let fingerprint = DriftFingerprint::from_corpus(&corpus);
+
+        assert_eq!(fingerprint.total, 3);
+        assert!((fingerprint.error_rate - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(fingerprint.status_histogram[&200], 2);
+        assert_eq!(fingerprint.status_histogram[&500], 1);
+    }
+
+    #[test]
+    fn drift_fingerprint_computes_duration_percentiles() {
+        let mut corpus = Corpus::new();
+        for (i, ms) in (1..=100u64).enumerate() {
+            corpus.add(trace(&format!("t{i}"), 200, false, ms));
+        }
+
+        let fingerprint = DriftFingerprint::from_corpus(&corpus);
+
+        assert_eq!(fingerprint.p50_ms, 50);
+        assert_eq!(fingerprint.p99_ms, 99);
+    }
+
+    #[test]
+    fn population_stability_index_is_zero_for_identical_distributions() {
+        let corpus = Corpus::new();
+        let fingerprint = DriftFingerprint::from_corpus(&corpus);
+        let buckets = fingerprint.buckets();
+        assert_eq!(population_stability_index(&buckets, &buckets), 0.0);
+
+        let mut identical = Corpus::new();
+        identical.add(trace("a", 200, false, 10));
+        identical.add(trace("b", 500, true, 90));
+        let b = DriftFingerprint::from_corpus(&identical).buckets();
+        assert!(population_stability_index(&b, &b).abs() < 1e-12);
+    }
+
+    #[test]
+    fn population_stability_index_is_positive_when_error_rate_shifts() {
+        let mut reference = Corpus::new();
+        reference.add(trace("a", 200, false, 10));
+        reference.add(trace("b", 200, false, 10));
+        reference.add(trace("c", 500, true, 10));
+
+        let mut now = Corpus::new();
+        now.add(trace("a", 200, false, 10));
+        now.add(trace("b", 500, true, 10));
+        now.add(trace("c", 500, true, 10));
+
+        let ref_buckets = DriftFingerprint::from_corpus(&reference).buckets();
+        let now_buckets = DriftFingerprint::from_corpus(&now).buckets();
+
+        assert!(population_stability_index(&now_buckets, &ref_buckets) > 0.0);
+    }
+
+    #[test]
+    fn ingest_traces_action_adds_to_corpus_and_records_drift() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+        let mut sim = PolicyEvolutionSim::new(policy, corpus);
+
+        let data = br#"[
+            {"trace_id": "abc", "duration_ms": 100, "status": 200, "service": "api"},
+            {"trace_id": "def", "duration_ms": 200, "status": 500, "service": "db"}
+        ]"#;
+
+        let result = sim.step(SimAction::IngestTraces {
+            bytes: data.to_vec(),
+            content_type: Some("application/json".to_string()),
+        });
+
+        assert!(matches!(
+            result,
+            StepResult::CorpusChanged { trace_count: 2 }
+        ));
+        assert_eq!(sim.corpus.len(), 2);
+        assert!(sim
+            .log
+            .events
+            .iter()
+            .any(|e| matches!(e, ReplayEvent::Drift { .. })));
+    }
+
+    #[test]
+    fn ingest_traces_action_reports_failure_on_malformed_input() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+        let mut sim = PolicyEvolutionSim::new(policy, corpus);
+
+        let result = sim.step(SimAction::IngestTraces {
+            bytes: b"not a trace payload".to_vec(),
+            content_type: Some("application/json".to_string()),
+        });
+
+        assert!(matches!(result, StepResult::IngestFailed { .. }));
+    }
+
+    #[test]
+    fn evict_traces_action_shrinks_corpus_via_reservoir() {
+        let policy = test_policy();
+        let mut corpus = Corpus::new();
+        for i in 0..10 {
+            corpus.add(trace(&format!("t{i}"), 200, false, i));
+        }
+        let mut sim = PolicyEvolutionSim::new(policy, corpus);
+
+        let result = sim.step(SimAction::EvictTraces { count: 4 });
+
+        assert!(matches!(
+            result,
+            StepResult::CorpusChanged { trace_count: 6 }
+        ));
+        assert_eq!(sim.corpus.len(), 6);
+    }
+
+    #[test]
+    fn detect_regressions_flags_a_drifted_window() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+        let mut sim = PolicyEvolutionSim::new(policy, corpus);
+
+        // A stable reference window, then a step that shifts heavily
+        // towards errors.
+        for i in 0..3 {
+            sim.step(SimAction::IngestTraces {
+                bytes: format!(r#"[{{"trace_id": "ok{i}", "duration_ms": 10, "status": 200}}]"#)
+                    .into_bytes(),
+                content_type: Some("application/json".to_string()),
+            });
+        }
+        sim.step(SimAction::IngestTraces {
+            bytes: br#"[
+                {"trace_id": "bad1", "duration_ms": 10, "status": 500},
+                {"trace_id": "bad2", "duration_ms": 10, "status": 500},
+                {"trace_id": "bad3", "duration_ms": 10, "status": 500}
+            ]"#
+            .to_vec(),
+            content_type: Some("application/json".to_string()),
+        });
+
+        let flags = sim.log.detect_regressions(1, 0.1);
+        assert!(!flags.is_empty());
+        assert!(flags[0].psi >= 0.1);
+    }
+
+    #[test]
+    fn detect_regressions_is_empty_when_stable() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+        let mut sim = PolicyEvolutionSim::new(policy, corpus);
+
+        for i in 0..4 {
+            sim.step(SimAction::IngestTraces {
+                bytes: format!(r#"[{{"trace_id": "ok{i}", "duration_ms": 10, "status": 200}}]"#)
+                    .into_bytes(),
+                content_type: Some("application/json".to_string()),
+            });
+        }
+
+        assert!(sim.log.detect_regressions(1, 0.1).is_empty());
+    }
+
+    #[test]
+    fn detect_regressions_is_empty_with_a_zero_window() {
+        let policy = test_policy();
+        let corpus = Corpus::new();
+        let mut sim = PolicyEvolutionSim::new(policy, corpus);
+        sim.step(SimAction::IngestTraces {
+            bytes: br#"[{"trace_id": "a", "duration_ms": 10, "status": 200}]"#.to_vec(),
+            content_type: Some("application/json".to_string()),
+        });
+
+        assert!(sim.log.detect_regressions(0, 0.0).is_empty());
+    }
 }
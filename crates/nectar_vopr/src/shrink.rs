@@ -0,0 +1,219 @@
+//! Delta-debugging minimization of panicking `(seed, SyntheticConfig,
+//! Policy)` triples.
+//!
+//! Campaigns like [`crate::campaigns::run_cascading_failure_campaign`]
+//! and [`crate::campaigns::run_decade_simulation`] only catch a panic in
+//! `compiler.compile`/`prover.verify` and stash a human-readable message
+//! like `"Iteration 612: prover panicked in cascade phase 60"`. That's
+//! enough to know *that* something broke, not enough to reproduce it
+//! without re-running the whole campaign. [`shrink_panic`] takes the
+//! seed, config, and policy a panic was first observed with and greedily
+//! reduces each dimension - trace count, error/slow rate, rule list -
+//! while re-running `still_panics` (the same compile/verify path, under
+//! `catch_unwind`) after every candidate, stopping once no single
+//! reduction still reproduces the panic.
+
+use crate::synthetic::SyntheticConfig;
+use toon_policy::{Action, Policy, Rule};
+
+/// A minimized `(seed, SyntheticConfig, rules)` triple that still
+/// reproduces a campaign panic - small enough to paste into a unit test.
+#[derive(Debug, Clone)]
+pub struct MinimizedPanic {
+    /// The seed the original (unminimized) run used.
+    pub seed: u64,
+    /// The shrunk synthetic-corpus configuration.
+    pub config: SyntheticConfig,
+    /// The shrunk rule list.
+    pub rules: Vec<Rule>,
+}
+
+/// Greedily shrinks `config` and `policy` down to a minimal pair that
+/// still reproduces a panic, per the module doc comment.
+///
+/// `still_panics(config, policy)` should return `true` if that pair
+/// still triggers the panic being minimized - callers pass the exact
+/// same `compiler.compile`/`prover.verify` path (under `catch_unwind`)
+/// the original campaign iteration used. `policy`'s non-`rules` fields
+/// (name, version, budget) are carried through unchanged; only its
+/// rule list is shrunk.
+#[must_use]
+pub fn shrink_panic(
+    seed: u64,
+    config: &SyntheticConfig,
+    policy: &Policy,
+    mut still_panics: impl FnMut(&SyntheticConfig, &Policy) -> bool,
+) -> MinimizedPanic {
+    let mut config = config.clone();
+    let mut rules = policy.rules.clone();
+    let defaults = SyntheticConfig::default();
+
+    loop {
+        let mut reduced = false;
+
+        if config.trace_count > 1 {
+            let candidate = SyntheticConfig {
+                trace_count: config.trace_count / 2,
+                ..config.clone()
+            };
+            if still_panics(&candidate, &policy_with_rules(policy, &rules)) {
+                config = candidate;
+                reduced = true;
+            }
+        }
+
+        let base = config.clone();
+        let mut error_rate = config.error_rate;
+        if bisect_rate(&mut error_rate, defaults.error_rate, |rate| {
+            let candidate = SyntheticConfig {
+                error_rate: rate,
+                ..base.clone()
+            };
+            still_panics(&candidate, &policy_with_rules(policy, &rules))
+        }) {
+            config.error_rate = error_rate;
+            reduced = true;
+        }
+
+        let base = config.clone();
+        let mut slow_rate = config.slow_rate;
+        if bisect_rate(&mut slow_rate, defaults.slow_rate, |rate| {
+            let candidate = SyntheticConfig {
+                slow_rate: rate,
+                ..base.clone()
+            };
+            still_panics(&candidate, &policy_with_rules(policy, &rules))
+        }) {
+            config.slow_rate = slow_rate;
+            reduced = true;
+        }
+
+        if let Some(index) = (0..rules.len()).find(|&i| {
+            rules[i].match_expr != "true" && {
+                let mut candidate = rules.clone();
+                candidate.remove(i);
+                ensure_fallback_rule(&mut candidate);
+                still_panics(&config, &policy_with_rules(policy, &candidate))
+            }
+        }) {
+            rules.remove(index);
+            ensure_fallback_rule(&mut rules);
+            reduced = true;
+        }
+
+        if !reduced {
+            break;
+        }
+    }
+
+    MinimizedPanic {
+        seed,
+        config,
+        rules,
+    }
+}
+
+/// Bisects `value` toward `target` by halving the gap each step, keeping
+/// a candidate only if `still_fails` reports the panic still reproduces
+/// - the same "keep the smaller candidate, else revert" rule
+/// [`shrink_panic`] uses for `trace_count`, generalized to continuous
+/// rates. Returns `true` if `value` moved any closer to `target`.
+fn bisect_rate(value: &mut f64, target: f64, mut still_fails: impl FnMut(f64) -> bool) -> bool {
+    let mut moved = false;
+    let mut step = (target - *value) / 2.0;
+    while step.abs() > 1e-3 {
+        let candidate = *value + step;
+        if still_fails(candidate) {
+            *value = candidate;
+            moved = true;
+        }
+        step /= 2.0;
+    }
+    moved
+}
+
+/// Guarantees `rules` still has a catch-all `"fallback"` rule after a
+/// removal, so a shrunk policy stays the same kind of complete policy
+/// the campaign started with instead of one that silently drops every
+/// trace it doesn't match.
+fn ensure_fallback_rule(rules: &mut Vec<Rule>) {
+    if !rules.iter().any(|r| r.name == "fallback") {
+        rules.push(Rule::new("fallback", "true", Action::Sample(0.01), 0));
+    }
+}
+
+fn policy_with_rules(template: &Policy, rules: &[Rule]) -> Policy {
+    Policy {
+        rules: rules.to_vec(),
+        ..template.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_trace_count_toward_one() {
+        let config = SyntheticConfig::default().with_trace_count(1000);
+        let policy = Policy::new("test");
+
+        let minimized = shrink_panic(7, &config, &policy, |config, _policy| {
+            config.trace_count >= 3
+        });
+
+        assert_eq!(minimized.seed, 7);
+        assert_eq!(minimized.config.trace_count, 3);
+    }
+
+    #[test]
+    fn bisects_error_rate_toward_the_failing_threshold() {
+        let config = SyntheticConfig::default().with_error_rate(0.9);
+        let policy = Policy::new("test");
+
+        let minimized = shrink_panic(1, &config, &policy, |config, _policy| {
+            config.error_rate >= 0.4
+        });
+
+        assert!(minimized.config.error_rate >= 0.4);
+        assert!(minimized.config.error_rate < 0.41);
+    }
+
+    #[test]
+    fn drops_every_rule_that_isnt_needed_to_reproduce() {
+        let config = SyntheticConfig::default();
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("noise-a", "http.status == 200", Action::Keep, 10));
+        policy.add_rule(Rule::new("bad", "((invalid", Action::Keep, 20));
+        policy.add_rule(Rule::new("noise-b", "duration > 1ms", Action::Drop, 5));
+
+        let minimized = shrink_panic(3, &config, &policy, |_config, policy| {
+            policy
+                .rules
+                .iter()
+                .any(|r| r.match_expr.contains("((invalid"))
+        });
+
+        assert!(minimized.rules.iter().any(|r| r.name == "bad"));
+        assert!(!minimized.rules.iter().any(|r| r.name == "noise-a"));
+        assert!(!minimized.rules.iter().any(|r| r.name == "noise-b"));
+    }
+
+    #[test]
+    fn keeps_a_fallback_rule_even_when_the_original_had_none() {
+        let config = SyntheticConfig::default();
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("bad", "((invalid", Action::Keep, 20));
+        policy.add_rule(Rule::new("noise", "http.status == 200", Action::Keep, 10));
+
+        let minimized = shrink_panic(9, &config, &policy, |_config, policy| {
+            policy
+                .rules
+                .iter()
+                .any(|r| r.match_expr.contains("((invalid"))
+        });
+
+        assert!(!minimized.rules.iter().any(|r| r.name == "noise"));
+        assert!(minimized.rules.iter().any(|r| r.name == "fallback"));
+    }
+}
@@ -2,6 +2,7 @@
 
 use nectar_corpus::Corpus;
 use std::fmt;
+use std::io::{self, Write};
 use toon_policy::Policy;
 
 /// A test scenario to run in the simulation.
@@ -30,11 +31,36 @@ pub enum Scenario {
         policy: Policy,
         /// Corpus to corrupt.
         corpus: Corpus,
+        /// Seed for the deterministic corruption RNG, so a failing
+        /// corruption can be replayed byte-for-byte.
+        seed: u64,
     },
     /// Test handling of high-cardinality data.
     HighCardinality {
         /// Number of unique services to generate.
         unique_services: usize,
+        /// Seed for the deterministic service-name generator, so a
+        /// failing dataset can be replayed byte-for-byte.
+        seed: u64,
+    },
+    /// Fuzz the trace ingest path with mutated bytes, asserting it never
+    /// panics and that any parsed corpus round-trips cleanly.
+    IngestFuzz {
+        /// Seed for the deterministic input mutator.
+        seed: u64,
+        /// Number of mutated inputs to try.
+        iterations: usize,
+    },
+    /// Assert the prover's emitted diagnostics against a golden-style set
+    /// of regex patterns, instead of only checking pass/fail.
+    OutputContract {
+        /// Policy to compile and verify.
+        policy: Policy,
+        /// Corpus to verify against.
+        corpus: Corpus,
+        /// Regex patterns that must each match at least one diagnostic
+        /// line produced during verification.
+        expected: Vec<String>,
     },
 }
 
@@ -59,14 +85,37 @@ impl Scenario {
 
     /// Creates a chaos resilience scenario.
     #[must_use]
-    pub const fn chaos_resilience(policy: Policy, corpus: Corpus) -> Self {
-        Self::ChaosResilience { policy, corpus }
+    pub const fn chaos_resilience(policy: Policy, corpus: Corpus, seed: u64) -> Self {
+        Self::ChaosResilience {
+            policy,
+            corpus,
+            seed,
+        }
     }
 
     /// Creates a high cardinality scenario.
     #[must_use]
-    pub const fn high_cardinality(unique_services: usize) -> Self {
-        Self::HighCardinality { unique_services }
+    pub const fn high_cardinality(unique_services: usize, seed: u64) -> Self {
+        Self::HighCardinality {
+            unique_services,
+            seed,
+        }
+    }
+
+    /// Creates an ingest-fuzzing scenario.
+    #[must_use]
+    pub const fn ingest_fuzz(seed: u64, iterations: usize) -> Self {
+        Self::IngestFuzz { seed, iterations }
+    }
+
+    /// Creates an output contract scenario.
+    #[must_use]
+    pub const fn output_contract(policy: Policy, corpus: Corpus, expected: Vec<String>) -> Self {
+        Self::OutputContract {
+            policy,
+            corpus,
+            expected,
+        }
     }
 
     /// Returns the name of this scenario.
@@ -78,6 +127,8 @@ impl Scenario {
             Self::RoundTrip { .. } => "roundtrip",
             Self::ChaosResilience { .. } => "chaos_resilience",
             Self::HighCardinality { .. } => "high_cardinality",
+            Self::IngestFuzz { .. } => "ingest_fuzz",
+            Self::OutputContract { .. } => "output_contract",
         }
     }
 }
@@ -95,6 +146,11 @@ pub struct SimResult {
     pub message: String,
     /// Detailed diagnostics (if any).
     pub diagnostics: Vec<String>,
+    /// Per-scenario seed this result was generated with, derived from the
+    /// master seed and the scenario's name. `None` for results produced
+    /// before hierarchical seed derivation was threaded through (e.g. in
+    /// standalone tests that construct a `SimResult` directly).
+    pub derived_seed: Option<u64>,
 }
 
 impl SimResult {
@@ -107,6 +163,7 @@ impl SimResult {
             skipped: false,
             message: message.into(),
             diagnostics: Vec::new(),
+            derived_seed: None,
         }
     }
 
@@ -119,6 +176,7 @@ impl SimResult {
             skipped: false,
             message: message.into(),
             diagnostics: Vec::new(),
+            derived_seed: None,
         }
     }
 
@@ -131,6 +189,7 @@ impl SimResult {
             skipped: true,
             message: reason.into(),
             diagnostics: Vec::new(),
+            derived_seed: None,
         }
     }
 
@@ -140,6 +199,13 @@ impl SimResult {
         self.diagnostics.push(diagnostic.into());
         self
     }
+
+    /// Records the per-scenario seed this result was generated with.
+    #[must_use]
+    pub const fn with_derived_seed(mut self, seed: u64) -> Self {
+        self.derived_seed = Some(seed);
+        self
+    }
 }
 
 impl fmt::Display for SimResult {
@@ -206,6 +272,76 @@ impl SimSummary {
     pub const fn all_invariants_held(&self) -> bool {
         self.failed == 0
     }
+
+    /// Renders this summary as a JUnit-compatible XML report, the same
+    /// shape `cargo2junit` produces from `cargo test` output, so a
+    /// simulation run can be wired into CI dashboards the same way.
+    #[must_use]
+    pub fn to_junit_xml(&self) -> String {
+        let mut buf = Vec::new();
+        self.write_junit(&mut buf)
+            .expect("writing to a Vec<u8> is infallible");
+        String::from_utf8(buf).expect("JUnit XML is always valid UTF-8")
+    }
+
+    /// Writes this summary as a JUnit-compatible XML report to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to `writer` fails.
+    pub fn write_junit<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            writer,
+            "<testsuite name=\"nektor.sim\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+            self.total, self.failed, self.skipped
+        )?;
+
+        for result in &self.results {
+            write!(
+                writer,
+                "  <testcase name=\"{}\" classname=\"nektor.sim\">",
+                xml_escape(&result.name)
+            )?;
+            if result.skipped {
+                write!(writer, "<skipped/>")?;
+            } else if !result.passed {
+                let diagnostics = result.diagnostics.join("\n");
+                write!(
+                    writer,
+                    "<failure message=\"{}\">{}{}</failure>",
+                    xml_escape(&result.message),
+                    xml_escape(&result.message),
+                    if diagnostics.is_empty() {
+                        String::new()
+                    } else {
+                        format!("\n{}", xml_escape(&diagnostics))
+                    }
+                )?;
+            }
+            writeln!(writer, "</testcase>")?;
+        }
+
+        writeln!(writer, "</testsuite>")?;
+        Ok(())
+    }
+}
+
+/// Escapes `&`, `<`, `>`, `"`, and `'` for safe use in XML attribute
+/// values and text content.
+fn xml_escape(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            other => escaped.push(other),
+        }
+    }
+    escaped
 }
 
 impl fmt::Display for SimSummary {
@@ -255,4 +391,31 @@ mod tests {
         assert_eq!(summary.skipped, 1);
         assert!(!summary.all_passed());
     }
+
+    #[test]
+    fn junit_xml_reports_counts_and_one_testcase_per_result() {
+        let results = vec![
+            SimResult::pass("a", "ok"),
+            SimResult::fail("b", "not ok").with_diagnostic("first clue"),
+            SimResult::skip("c", "not applicable"),
+        ];
+        let summary = SimSummary::from_results(results);
+        let xml = summary.to_junit_xml();
+
+        assert!(xml
+            .contains("<testsuite name=\"nektor.sim\" tests=\"3\" failures=\"1\" skipped=\"1\">"));
+        assert!(xml.contains("<testcase name=\"a\" classname=\"nektor.sim\"></testcase>"));
+        assert!(xml.contains("<failure message=\"not ok\">not ok\nfirst clue</failure>"));
+        assert!(xml.contains("<testcase name=\"c\" classname=\"nektor.sim\"><skipped/></testcase>"));
+    }
+
+    #[test]
+    fn junit_xml_escapes_special_characters() {
+        let results = vec![SimResult::fail("a<b>", "bad & \"quoted\" 'value'")];
+        let summary = SimSummary::from_results(results);
+        let xml = summary.to_junit_xml();
+
+        assert!(xml.contains("name=\"a&lt;b&gt;\""));
+        assert!(xml.contains("bad &amp; &quot;quoted&quot; &apos;value&apos;"));
+    }
 }
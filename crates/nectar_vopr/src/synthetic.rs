@@ -6,6 +6,7 @@
 //! - Service topologies
 //! - Traffic patterns
 
+use nectar_corpus::span::{Span, SpanStatus};
 use nectar_corpus::{Corpus, Trace};
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
@@ -29,6 +30,12 @@ pub struct SyntheticConfig {
     pub services: Vec<String>,
     /// Routes per service.
     pub routes_per_service: usize,
+    /// Service call-graph to generate each trace's span hierarchy from.
+    /// `None` (the default) keeps the flat, single-span-per-trace
+    /// behavior of [`SyntheticCorpus::generate`].
+    pub topology: Option<ServiceTopology>,
+    /// Distribution non-slow request durations are drawn from.
+    pub latency_model: LatencyModel,
 }
 
 impl Default for SyntheticConfig {
@@ -47,10 +54,73 @@ impl Default for SyntheticConfig {
                 "inventory-service".to_string(),
             ],
             routes_per_service: 5,
+            topology: None,
+            latency_model: LatencyModel::Uniform,
         }
     }
 }
 
+/// A latency distribution [`SyntheticCorpus`] can draw non-slow request
+/// durations from, so benchmark/regression tests can exercise policies
+/// against realistic fat-tailed latency instead of uniform noise.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LatencyModel {
+    /// Flat `gen_range(10..max)`, matching the original behavior.
+    #[default]
+    Uniform,
+    /// Log-normal: `exp(mu + sigma * Z)` for a standard normal `Z`, the
+    /// textbook shape for web service latency (a typical-case cluster
+    /// with a moderate right tail).
+    LogNormal {
+        /// Mean of the underlying normal distribution.
+        mu: f64,
+        /// Standard deviation of the underlying normal distribution.
+        sigma: f64,
+    },
+    /// Pareto: `x_min / U^(1/alpha)`, a heavy (power-law) tail useful for
+    /// exercising p99-style policies against occasional very slow calls.
+    Pareto {
+        /// Minimum possible value.
+        x_min: f64,
+        /// Shape parameter; smaller values produce a heavier tail.
+        alpha: f64,
+    },
+}
+
+impl LatencyModel {
+    /// Draws a duration (in milliseconds) from this distribution using
+    /// `rng`, clamped to `[1, max_ms)` so a non-slow sample never reaches
+    /// the caller's "slow" threshold - that contract is `is_slow`'s job,
+    /// not the latency model's.
+    fn sample_ms(self, rng: &mut ChaCha8Rng, max_ms: u64) -> u64 {
+        let max_ms = max_ms.max(11);
+        match self {
+            Self::Uniform => rng.gen_range(10..max_ms),
+            Self::LogNormal { mu, sigma } => {
+                // Box-Muller transform: U1, U2 ~ Uniform(0, 1] produce a
+                // standard normal Z, which log-normal latency is `exp` of.
+                let u1: f64 = rng.gen_range(f64::EPSILON..=1.0);
+                let u2: f64 = rng.gen_range(0.0..1.0);
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                clamp_ms((mu + sigma * z).exp(), max_ms)
+            }
+            Self::Pareto { x_min, alpha } => {
+                // Inverse transform sampling: U ~ Uniform(0, 1].
+                let u: f64 = rng.gen_range(f64::EPSILON..=1.0);
+                clamp_ms(x_min / u.powf(1.0 / alpha), max_ms)
+            }
+        }
+    }
+}
+
+/// Clamps a raw latency sample to `[1, max_ms)` and rounds it to whole
+/// milliseconds, shared by [`LatencyModel::sample_ms`]'s non-uniform
+/// branches.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn clamp_ms(raw_ms: f64, max_ms: u64) -> u64 {
+    raw_ms.clamp(1.0, (max_ms - 1) as f64) as u64
+}
+
 impl SyntheticConfig {
     /// Creates a new config with the given seed.
     #[must_use]
@@ -79,6 +149,226 @@ impl SyntheticConfig {
         self.slow_rate = rate;
         self
     }
+
+    /// Sets an explicit service call-graph, so generated traces get a
+    /// span per hop instead of a single flat span.
+    #[must_use]
+    pub fn with_topology(mut self, topology: ServiceTopology) -> Self {
+        self.topology = Some(topology);
+        self
+    }
+
+    /// Auto-generates a layered call-graph over `services` (see
+    /// [`ServiceTopology::layered`]) and uses it for generation.
+    #[must_use]
+    pub fn with_layered_topology(mut self, fanout: usize) -> Self {
+        self.topology = Some(ServiceTopology::layered(&self.services, fanout));
+        self
+    }
+
+    /// Sets the distribution non-slow request durations are drawn from.
+    #[must_use]
+    pub const fn with_latency_model(mut self, model: LatencyModel) -> Self {
+        self.latency_model = model;
+        self
+    }
+}
+
+/// A directed service call-graph: which services call which, rooted at
+/// the service that receives each trace's inbound request.
+///
+/// [`SyntheticCorpus::generate`] walks this depth-first for every trace,
+/// emitting one [`Span`] per visited service, with the trace id shared
+/// across all of them and each parent's duration covering its own work
+/// plus every downstream call it made.
+#[derive(Debug, Clone)]
+pub struct ServiceTopology {
+    /// The entry-point service that receives the trace's root span.
+    pub root: String,
+    /// `service -> downstream services it calls`, in call order.
+    pub calls: HashMap<String, Vec<String>>,
+}
+
+impl ServiceTopology {
+    /// Starts an empty topology rooted at `root`.
+    #[must_use]
+    pub fn new(root: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            calls: HashMap::new(),
+        }
+    }
+
+    /// Declares that `from` calls `to`, appending to any calls already
+    /// declared for `from`.
+    #[must_use]
+    pub fn with_call(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.calls.entry(from.into()).or_default().push(to.into());
+        self
+    }
+
+    /// Auto-generates a layered topology over `services`: `api-gateway`
+    /// (or the first service, if `services` doesn't contain it) is the
+    /// root, and the remaining services are split into layers of
+    /// `fanout` entries. Each service in a layer is called by one of the
+    /// services in the layer above it (round-robin, so a multi-service
+    /// layer spreads its calls out rather than every caller hitting
+    /// every callee), producing a call-graph that fans out progressively
+    /// deeper instead of one hub calling everything directly.
+    #[must_use]
+    pub fn layered(services: &[String], fanout: usize) -> Self {
+        let root = services
+            .iter()
+            .find(|s| s.as_str() == "api-gateway")
+            .or_else(|| services.first())
+            .cloned()
+            .unwrap_or_else(|| "api-gateway".to_string());
+
+        let mut topology = Self::new(root.clone());
+        let downstream: Vec<String> = services.iter().filter(|s| **s != root).cloned().collect();
+        let fanout = fanout.max(1);
+
+        let mut callers = vec![root];
+        for layer in downstream.chunks(fanout) {
+            for (i, callee) in layer.iter().enumerate() {
+                let caller = &callers[i % callers.len()];
+                topology = topology.with_call(caller.clone(), callee.clone());
+            }
+            callers = layer.to_vec();
+        }
+
+        topology
+    }
+}
+
+/// A first-order Markov chain over per-service traffic, learned from an
+/// existing [`Corpus`] and used to generate new traces whose
+/// service-transition and status/duration statistics resemble the
+/// training data, rather than the uniform random choices
+/// [`SyntheticCorpus::generate`] makes.
+#[derive(Debug, Clone, Default)]
+pub struct MarkovTraceModel {
+    /// Weighted `service -> next service` transition counts, learned from
+    /// consecutive traces in corpus order.
+    transitions: HashMap<String, Vec<(String, u32)>>,
+    /// Weighted starting services (the first trace of each corpus seen).
+    start_services: Vec<(String, u32)>,
+    /// Observed status codes per service, for resampling.
+    statuses_by_service: HashMap<String, Vec<u16>>,
+    /// Observed durations (ms) per service, for resampling.
+    durations_by_service: HashMap<String, Vec<u64>>,
+}
+
+impl MarkovTraceModel {
+    /// Learns service-transition, status, and duration statistics from an
+    /// existing corpus.
+    #[must_use]
+    pub fn learn(corpus: &Corpus) -> Self {
+        let mut model = Self::default();
+        let mut prev_service: Option<String> = None;
+
+        for trace in corpus.iter() {
+            let Some(service) = trace.service.clone() else {
+                prev_service = None;
+                continue;
+            };
+
+            if let Some(status) = trace.status {
+                model
+                    .statuses_by_service
+                    .entry(service.clone())
+                    .or_default()
+                    .push(status);
+            }
+            model
+                .durations_by_service
+                .entry(service.clone())
+                .or_default()
+                .push(u64::try_from(trace.duration.as_millis()).unwrap_or(u64::MAX));
+
+            match &prev_service {
+                Some(prev) => {
+                    increment_weight(model.transitions.entry(prev.clone()).or_default(), &service)
+                }
+                None => increment_weight(&mut model.start_services, &service),
+            }
+
+            prev_service = Some(service);
+        }
+
+        model
+    }
+
+    /// Returns true if this model learned nothing (an empty training
+    /// corpus, or one with no serviced traces).
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.start_services.is_empty()
+    }
+
+    /// Generates `count` traces by walking the learned Markov chain,
+    /// resampling statuses and durations from the observations recorded
+    /// for each visited service.
+    #[must_use]
+    pub fn generate(&self, rng: &mut ChaCha8Rng, count: usize) -> Corpus {
+        let mut corpus = Corpus::new();
+        if self.is_empty() {
+            return corpus;
+        }
+
+        let mut current = weighted_choice(rng, &self.start_services).clone();
+
+        for i in 0..count {
+            let status = self
+                .statuses_by_service
+                .get(&current)
+                .filter(|v| !v.is_empty())
+                .map_or(200, |v| *v.choose(rng).unwrap());
+            let duration_ms = self
+                .durations_by_service
+                .get(&current)
+                .filter(|v| !v.is_empty())
+                .map_or(50, |v| *v.choose(rng).unwrap());
+
+            corpus.add(
+                Trace::new(format!("markov-{i:08x}"))
+                    .with_service(current.clone())
+                    .with_status(status)
+                    .with_duration(Duration::from_millis(duration_ms)),
+            );
+
+            current = match self.transitions.get(&current) {
+                Some(next) if !next.is_empty() => weighted_choice(rng, next).clone(),
+                _ => weighted_choice(rng, &self.start_services).clone(),
+            };
+        }
+
+        corpus
+    }
+}
+
+/// Increments the weight of `value` in a `(value, weight)` list, adding a
+/// new entry with weight 1 if it isn't present yet.
+fn increment_weight(weights: &mut Vec<(String, u32)>, value: &str) {
+    if let Some(entry) = weights.iter_mut().find(|(v, _)| v == value) {
+        entry.1 += 1;
+    } else {
+        weights.push((value.to_string(), 1));
+    }
+}
+
+/// Picks an entry from a `(value, weight)` list proportionally to weight.
+/// Panics if `weights` is empty, matching `slice::choose`'s contract.
+fn weighted_choice<'a>(rng: &mut ChaCha8Rng, weights: &'a [(String, u32)]) -> &'a String {
+    let total: u32 = weights.iter().map(|(_, w)| *w).sum();
+    let mut pick = rng.gen_range(0..total.max(1));
+    for (value, weight) in weights {
+        if pick < *weight {
+            return value;
+        }
+        pick = pick.saturating_sub(*weight);
+    }
+    &weights[weights.len() - 1].0
 }
 
 /// Synthetic corpus generator.
@@ -147,6 +437,27 @@ impl SyntheticCorpus {
     }
 
     fn generate_trace(&mut self, index: usize) -> Trace {
+        match self.config.topology.clone() {
+            Some(topology) => self.generate_trace_with_topology(index, &topology),
+            None => self.generate_trace_flat(index),
+        }
+    }
+
+    /// Draws a request duration in milliseconds: `slow_threshold_ms` plus
+    /// jitter when `is_slow`, otherwise a sample from
+    /// [`SyntheticConfig::latency_model`], so the `slow_rate` contract
+    /// holds regardless of which distribution normal requests use.
+    fn sample_duration_ms(&mut self, is_slow: bool) -> u64 {
+        if is_slow {
+            self.config.slow_threshold_ms + self.rng.gen_range(0..10000)
+        } else {
+            self.config
+                .latency_model
+                .sample_ms(&mut self.rng, self.config.slow_threshold_ms)
+        }
+    }
+
+    fn generate_trace_flat(&mut self, index: usize) -> Trace {
         let service = self.config.services.choose(&mut self.rng).unwrap().clone();
         let routes = self.routes.get(&service).unwrap();
         let route = routes.choose(&mut self.rng).unwrap().clone();
@@ -161,11 +472,7 @@ impl SyntheticCorpus {
             *[200u16, 201, 204].choose(&mut self.rng).unwrap()
         };
 
-        let duration_ms = if is_slow {
-            self.config.slow_threshold_ms + self.rng.gen_range(0..10000)
-        } else {
-            self.rng.gen_range(10..self.config.slow_threshold_ms)
-        };
+        let duration_ms = self.sample_duration_ms(is_slow);
 
         Trace::new(format!("trace-{index:08x}"))
             .with_service(service)
@@ -174,6 +481,100 @@ impl SyntheticCorpus {
             .with_duration(Duration::from_millis(duration_ms))
     }
 
+    /// Generates a trace by walking `topology` depth-first from its
+    /// root, emitting one span per visited service (see
+    /// [`Self::generate_span`]) and deriving the trace's summary fields
+    /// (service, status, duration, ...) from the resulting span tree.
+    fn generate_trace_with_topology(&mut self, index: usize, topology: &ServiceTopology) -> Trace {
+        let mut spans = Vec::new();
+        let mut span_counter = 0usize;
+        self.generate_span(
+            index,
+            &topology.root,
+            None,
+            0,
+            topology,
+            &mut spans,
+            &mut span_counter,
+        );
+        Trace::from_spans(format!("trace-{index:08x}"), spans)
+    }
+
+    /// Recursively builds the span for `service` starting at
+    /// `start_time_ns`, then its downstream calls in `topology` in turn,
+    /// so each child span is nested strictly after its prior sibling.
+    /// Pushes `service`'s span (and every descendant's) onto `spans` and
+    /// returns its total duration — its own simulated work plus the sum
+    /// of every downstream call — so the caller can place the next
+    /// sibling after it ends.
+    #[allow(clippy::too_many_arguments, clippy::cast_possible_truncation)]
+    fn generate_span(
+        &mut self,
+        index: usize,
+        service: &str,
+        parent_span_id: Option<String>,
+        start_time_ns: u64,
+        topology: &ServiceTopology,
+        spans: &mut Vec<Span>,
+        span_counter: &mut usize,
+    ) -> Duration {
+        let span_id = format!("trace-{index:08x}-span-{span_counter:04x}");
+        *span_counter += 1;
+
+        let is_error = self.rng.gen_bool(self.config.error_rate);
+        let is_slow = self.rng.gen_bool(self.config.slow_rate);
+        let own_duration_ms = self.sample_duration_ms(is_slow);
+        let own_duration = Duration::from_millis(own_duration_ms);
+
+        let route = self
+            .routes
+            .get(service)
+            .and_then(|routes| routes.choose(&mut self.rng))
+            .cloned()
+            .unwrap_or_else(|| service.to_string());
+        let status = if is_error {
+            *[500u16, 502, 503, 504].choose(&mut self.rng).unwrap()
+        } else {
+            *[200u16, 201, 204].choose(&mut self.rng).unwrap()
+        };
+
+        let children: Vec<String> = topology.calls.get(service).cloned().unwrap_or_default();
+        let mut child_start_ns = start_time_ns + own_duration.as_nanos() as u64;
+        let mut total_duration = own_duration;
+
+        for child_service in &children {
+            let child_duration = self.generate_span(
+                index,
+                child_service,
+                Some(span_id.clone()),
+                child_start_ns,
+                topology,
+                spans,
+                span_counter,
+            );
+            child_start_ns += child_duration.as_nanos() as u64;
+            total_duration += child_duration;
+        }
+
+        let mut span = Span::new(span_id, route.clone())
+            .with_service(service)
+            .with_start_time_ns(start_time_ns)
+            .with_duration(total_duration)
+            .with_attribute("http.route", route)
+            .with_attribute("http.status_code", i64::from(status))
+            .with_status(if is_error {
+                SpanStatus::error("synthetic downstream failure")
+            } else {
+                SpanStatus::ok()
+            });
+        if let Some(parent) = parent_span_id {
+            span = span.with_parent(parent);
+        }
+        spans.push(span);
+
+        total_duration
+    }
+
     /// Generates a corpus specifically designed to test edge cases.
     #[must_use]
     pub fn generate_edge_cases(&mut self) -> Corpus {
@@ -234,6 +635,78 @@ impl SyntheticCorpus {
 
         corpus
     }
+
+    /// Renders a generated corpus's observed call-graph as a Graphviz
+    /// DOT digraph: one node per service, one directed edge per
+    /// caller-callee pair actually seen in the traces' spans, labeled
+    /// with the call count and error rate. Lets users sanity-check a
+    /// [`ServiceTopology`] (custom or [`ServiceTopology::layered`])
+    /// before feeding its traces into policy tests.
+    #[must_use]
+    pub fn topology_dot(corpus: &Corpus) -> String {
+        #[derive(Default)]
+        struct EdgeStats {
+            calls: u64,
+            errors: u64,
+        }
+
+        let mut services: Vec<String> = Vec::new();
+        let mut edges: HashMap<(String, String), EdgeStats> = HashMap::new();
+
+        for trace in corpus.iter() {
+            let service_by_span: HashMap<&str, &str> = trace
+                .spans
+                .iter()
+                .map(|s| (s.span_id.as_str(), s.service.as_str()))
+                .collect();
+
+            for span in &trace.spans {
+                if !services.contains(&span.service) {
+                    services.push(span.service.clone());
+                }
+
+                let Some(parent_id) = &span.parent_span_id else {
+                    continue;
+                };
+                let Some(parent_service) = service_by_span.get(parent_id.as_str()) else {
+                    continue;
+                };
+
+                let stats = edges
+                    .entry(((*parent_service).to_string(), span.service.clone()))
+                    .or_default();
+                stats.calls += 1;
+                if span.is_error() {
+                    stats.errors += 1;
+                }
+            }
+        }
+
+        services.sort();
+        let mut edges: Vec<_> = edges.into_iter().collect();
+        edges.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut out = String::from("digraph topology {\n");
+        for service in &services {
+            out.push_str(&format!("  \"{}\";\n", service.replace('"', "\\\"")));
+        }
+        for ((from, to), stats) in edges {
+            #[allow(clippy::cast_precision_loss)]
+            let error_rate = if stats.calls == 0 {
+                0.0
+            } else {
+                stats.errors as f64 / stats.calls as f64 * 100.0
+            };
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\" [label=\"calls={}, errors={error_rate:.1}%\"];\n",
+                from.replace('"', "\\\""),
+                to.replace('"', "\\\""),
+                stats.calls,
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
 }
 
 /// Generates deterministic corpora for snapshot testing.
@@ -297,6 +770,43 @@ mod tests {
         assert!(corpus.len() > 20);
     }
 
+    #[test]
+    fn markov_model_learns_transitions_from_corpus() {
+        let corpus: Corpus = vec![
+            Trace::new("a").with_service("api-gateway").with_status(200),
+            Trace::new("b")
+                .with_service("order-service")
+                .with_status(200),
+            Trace::new("c")
+                .with_service("order-service")
+                .with_status(500),
+        ]
+        .into_iter()
+        .collect();
+
+        let model = MarkovTraceModel::learn(&corpus);
+        assert!(!model.is_empty());
+
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let generated = model.generate(&mut rng, 50);
+        assert_eq!(generated.len(), 50);
+
+        // Every generated service should be one we actually observed.
+        for trace in generated.iter() {
+            let service = trace.service.as_deref().unwrap();
+            assert!(service == "api-gateway" || service == "order-service");
+        }
+    }
+
+    #[test]
+    fn markov_model_from_empty_corpus_generates_nothing() {
+        let model = MarkovTraceModel::learn(&Corpus::new());
+        assert!(model.is_empty());
+
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        assert!(model.generate(&mut rng, 10).is_empty());
+    }
+
     #[test]
     #[allow(clippy::cast_precision_loss)]
     fn error_rate_is_respected() {
@@ -317,4 +827,155 @@ mod tests {
             "Error rate {error_rate} not within tolerance of 0.10"
         );
     }
+
+    #[test]
+    fn layered_topology_roots_at_api_gateway_and_fans_out() {
+        let services = vec![
+            "api-gateway".to_string(),
+            "user-service".to_string(),
+            "order-service".to_string(),
+            "payment-service".to_string(),
+            "inventory-service".to_string(),
+        ];
+        let topology = ServiceTopology::layered(&services, 2);
+
+        assert_eq!(topology.root, "api-gateway");
+        let root_calls = topology.calls.get("api-gateway").unwrap();
+        assert_eq!(root_calls.len(), 2, "root should call the first layer");
+        assert!(root_calls.contains(&"user-service".to_string()));
+        assert!(root_calls.contains(&"order-service".to_string()));
+
+        // The remaining services form a second layer, called by the first.
+        let second_layer_callers: usize = ["user-service", "order-service"]
+            .iter()
+            .filter(|s| topology.calls.contains_key(**s))
+            .count();
+        assert!(
+            second_layer_callers > 0,
+            "the first layer should call deeper services"
+        );
+    }
+
+    #[test]
+    fn topology_generated_trace_nests_child_spans_in_parent_duration() {
+        let topology = ServiceTopology::new("api-gateway")
+            .with_call("api-gateway", "order-service")
+            .with_call("api-gateway", "payment-service");
+
+        let config = SyntheticConfig::default()
+            .with_seed(1)
+            .with_trace_count(1)
+            .with_topology(topology);
+        let mut gen = SyntheticCorpus::new(config);
+        let corpus = gen.generate();
+
+        let trace = corpus.iter().next().expect("one trace generated");
+        assert_eq!(trace.spans.len(), 3, "root plus two downstream calls");
+
+        let root = trace
+            .spans
+            .iter()
+            .find(|s| s.parent_span_id.is_none())
+            .unwrap();
+        assert_eq!(root.service, "api-gateway");
+
+        let children: Vec<_> = trace
+            .spans
+            .iter()
+            .filter(|s| s.parent_span_id.as_deref() == Some(root.span_id.as_str()))
+            .collect();
+        assert_eq!(children.len(), 2);
+
+        let root_end = root.start_time_ns + u64::try_from(root.duration.as_nanos()).unwrap();
+        for child in &children {
+            let child_end = child.start_time_ns + u64::try_from(child.duration.as_nanos()).unwrap();
+            assert!(
+                child.start_time_ns >= root.start_time_ns && child_end <= root_end,
+                "child span should be nested within its parent's interval"
+            );
+        }
+
+        // Every span shares the same trace id.
+        assert!(trace.trace_id.starts_with("trace-"));
+    }
+
+    #[test]
+    fn topology_dot_reports_edges_with_call_counts() {
+        let topology =
+            ServiceTopology::new("api-gateway").with_call("api-gateway", "order-service");
+        let config = SyntheticConfig::default()
+            .with_seed(3)
+            .with_trace_count(20)
+            .with_topology(topology);
+        let mut gen = SyntheticCorpus::new(config);
+        let corpus = gen.generate();
+
+        let dot = SyntheticCorpus::topology_dot(&corpus);
+
+        assert!(dot.starts_with("digraph topology"));
+        assert!(dot.contains("\"api-gateway\";"));
+        assert!(dot.contains("\"order-service\";"));
+        assert!(dot.contains("\"api-gateway\" -> \"order-service\" [label=\"calls=20,"));
+    }
+
+    #[test]
+    fn log_normal_latency_model_stays_within_bounds() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let model = LatencyModel::LogNormal {
+            mu: 4.0,
+            sigma: 1.5,
+        };
+        for _ in 0..200 {
+            let ms = model.sample_ms(&mut rng, 1000);
+            assert!(
+                (1..1000).contains(&ms),
+                "log-normal sample out of bounds: {ms}"
+            );
+        }
+    }
+
+    #[test]
+    fn pareto_latency_model_stays_within_bounds() {
+        let mut rng = ChaCha8Rng::seed_from_u64(11);
+        let model = LatencyModel::Pareto {
+            x_min: 10.0,
+            alpha: 1.2,
+        };
+        for _ in 0..200 {
+            let ms = model.sample_ms(&mut rng, 1000);
+            assert!((1..1000).contains(&ms), "pareto sample out of bounds: {ms}");
+        }
+    }
+
+    #[test]
+    fn uniform_latency_model_matches_previous_gen_range_behavior() {
+        let mut rng = ChaCha8Rng::seed_from_u64(13);
+        let model = LatencyModel::Uniform;
+        for _ in 0..200 {
+            let ms = model.sample_ms(&mut rng, 500);
+            assert!(
+                (10..500).contains(&ms),
+                "uniform sample out of bounds: {ms}"
+            );
+        }
+    }
+
+    #[test]
+    fn slow_override_holds_regardless_of_latency_model() {
+        let config = SyntheticConfig::default()
+            .with_seed(5)
+            .with_trace_count(50)
+            .with_error_rate(0.0)
+            .with_slow_rate(1.0)
+            .with_latency_model(LatencyModel::Pareto {
+                x_min: 5.0,
+                alpha: 2.0,
+            });
+        let mut gen = SyntheticCorpus::new(config);
+        let corpus = gen.generate();
+
+        for trace in corpus.iter() {
+            assert!(trace.duration.as_millis() as u64 >= 5000);
+        }
+    }
 }
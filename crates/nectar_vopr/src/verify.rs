@@ -0,0 +1,759 @@
+//! SAT-backed symbolic verification of policy properties.
+//!
+//! Unlike the rest of this crate's campaigns, which sample a
+//! [`crate::synthetic::SyntheticCorpus`] and check policy behavior
+//! probabilistically, [`verify_property`] *proves* a property over the
+//! full symbolic space of request attributes: it encodes a policy's
+//! rules and priority ordering as a CNF formula, asserts the negation
+//! of the desired property, and hands the result to [`crate::sat`]. An
+//! UNSAT result is a proof; a SAT result decodes straight into a
+//! concrete counterexample [`Trace`].
+//!
+//! # What's modeled
+//!
+//! Only the well-known attribute domains [`nectar_prover`]'s own static
+//! analysis models - `http.status` and `duration` (order-encoded finite
+//! integer domains built from the thresholds actually appearing in the
+//! policy), `error` (a plain boolean), and `service.name`/`http.route`
+//! (one indicator variable per literal seen in the policy, with an
+//! at-most-one constraint - so a never-mentioned string is "none of the
+//! known literals") - and only `==`, `!=`, `>`, `>=`, `<`, `<=` on them.
+//! A rule or property using `contains`/`starts-with`/`exists`, an
+//! unknown field, or a value of the wrong type for its field makes the
+//! whole check [`Verdict::Unmodeled`] rather than risk an unsound proof.
+
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_sign_loss)]
+
+use crate::sat::{self, CnfFormula, Lit, SatResult};
+use nectar_compiler::match_expr::{Condition, MatchExpr, Operator, Value};
+use nectar_corpus::Trace;
+use std::collections::BTreeSet;
+use std::time::Duration;
+use toon_policy::{Action, Policy, Rule};
+
+/// A property to check against a [`Policy`].
+#[derive(Debug, Clone)]
+pub enum Property {
+    /// No trace matching `match_expr` is ever kept (`Action::Keep`).
+    NeverKept {
+        /// A match expression, same syntax as [`toon_policy::Rule::match_expr`].
+        match_expr: String,
+    },
+    /// Every pair of rules that can simultaneously match some trace has
+    /// a distinct priority - i.e. which one wins is always determined
+    /// by priority, never by insertion-order tie-breaking.
+    PrecedenceIsTotal,
+}
+
+/// The result of checking a [`Property`] against a [`Policy`].
+#[derive(Debug, Clone)]
+pub enum Verdict {
+    /// The property holds for every representable trace.
+    Proven,
+    /// The property is false, demonstrated by this concrete trace.
+    Counterexample {
+        /// A minimal synthetic trace witnessing the violation.
+        trace: Trace,
+        /// Human-readable explanation of why `trace` violates the property.
+        detail: String,
+    },
+    /// A rule (or the property itself) uses something this module can't
+    /// model soundly, so neither a proof nor a counterexample is safe
+    /// to report.
+    Unmodeled(String),
+}
+
+/// Checks `property` against `policy`, proving it or returning a
+/// counterexample.
+#[must_use]
+pub fn verify_property(policy: &Policy, property: &Property) -> Verdict {
+    match property {
+        Property::NeverKept { match_expr } => verify_never_kept(policy, match_expr),
+        Property::PrecedenceIsTotal => verify_precedence_is_total(policy),
+    }
+}
+
+fn verify_never_kept(policy: &Policy, match_expr: &str) -> Verdict {
+    let target = match MatchExpr::parse(match_expr) {
+        Ok(expr) => expr,
+        Err(e) => return Verdict::Unmodeled(format!("property expression doesn't parse: {e}")),
+    };
+
+    let rule_exprs = match parse_rules(&policy.rules) {
+        Ok(exprs) => exprs,
+        Err(detail) => return Verdict::Unmodeled(detail),
+    };
+
+    let mut model = match FieldModel::build(rule_exprs.iter().map(|(_, e)| e).chain([&target])) {
+        Ok(model) => model,
+        Err(detail) => return Verdict::Unmodeled(detail),
+    };
+
+    let target_matches = match model.encode(&target) {
+        Ok(lit) => lit,
+        Err(detail) => return Verdict::Unmodeled(detail),
+    };
+
+    let mut already_matched: Vec<Lit> = Vec::new();
+    let mut kept_terms: Vec<Lit> = Vec::new();
+    for (rule, expr) in &rule_exprs {
+        let matches = match model.encode(expr) {
+            Ok(lit) => lit,
+            Err(detail) => return Verdict::Unmodeled(detail),
+        };
+        let applies = applies_literal(&mut model.formula, matches, &already_matched);
+        if matches!(rule.action, Action::Keep) {
+            kept_terms.push(applies);
+        }
+        already_matched.push(matches);
+    }
+
+    let kept = if kept_terms.is_empty() {
+        model.false_lit()
+    } else {
+        or_lit(&mut model.formula, &kept_terms)
+    };
+
+    model.formula.add_clause(vec![target_matches]);
+    model.formula.add_clause(vec![kept]);
+
+    match sat::solve(&model.formula) {
+        SatResult::Unsat => Verdict::Proven,
+        SatResult::Sat(assignment) => {
+            let trace = model.decode_trace(&assignment);
+            Verdict::Counterexample {
+                trace,
+                detail: format!("a trace matching `{match_expr}` is kept"),
+            }
+        }
+    }
+}
+
+fn verify_precedence_is_total(policy: &Policy) -> Verdict {
+    let rule_exprs = match parse_rules(&policy.rules) {
+        Ok(exprs) => exprs,
+        Err(detail) => return Verdict::Unmodeled(detail),
+    };
+
+    for i in 0..rule_exprs.len() {
+        for j in (i + 1)..rule_exprs.len() {
+            let (rule_a, expr_a) = &rule_exprs[i];
+            let (rule_b, expr_b) = &rule_exprs[j];
+            if rule_a.priority != rule_b.priority {
+                continue;
+            }
+
+            let mut model = match FieldModel::build([expr_a, expr_b]) {
+                Ok(model) => model,
+                Err(detail) => return Verdict::Unmodeled(detail),
+            };
+            let matches_a = match model.encode(expr_a) {
+                Ok(lit) => lit,
+                Err(detail) => return Verdict::Unmodeled(detail),
+            };
+            let matches_b = match model.encode(expr_b) {
+                Ok(lit) => lit,
+                Err(detail) => return Verdict::Unmodeled(detail),
+            };
+            model.formula.add_clause(vec![matches_a]);
+            model.formula.add_clause(vec![matches_b]);
+
+            if let SatResult::Sat(assignment) = sat::solve(&model.formula) {
+                let trace = model.decode_trace(&assignment);
+                return Verdict::Counterexample {
+                    trace,
+                    detail: format!(
+                        "rules '{}' and '{}' share priority {} and can both match this trace",
+                        rule_a.name, rule_b.name, rule_a.priority
+                    ),
+                };
+            }
+        }
+    }
+
+    Verdict::Proven
+}
+
+fn parse_rules(rules: &[Rule]) -> Result<Vec<(&Rule, MatchExpr)>, String> {
+    rules
+        .iter()
+        .map(|rule| {
+            MatchExpr::parse(&rule.match_expr)
+                .map(|expr| (rule, expr))
+                .map_err(|e| format!("rule '{}' doesn't parse: {e}", rule.name))
+        })
+        .collect()
+}
+
+/// `applies_i = matches_i AND NOT(OR of every earlier rule's matches)` -
+/// the Tseitin encoding of first-match-wins over [`Policy::rules`]'
+/// priority-descending order.
+fn applies_literal(formula: &mut CnfFormula, matches: Lit, earlier_matches: &[Lit]) -> Lit {
+    if earlier_matches.is_empty() {
+        return matches;
+    }
+    let any_earlier = or_lit(formula, earlier_matches);
+    and_lit(formula, &[matches, -any_earlier])
+}
+
+/// Tseitin AND: returns `y` with `y <-> (lits[0] & lits[1] & ...)`.
+fn and_lit(formula: &mut CnfFormula, lits: &[Lit]) -> Lit {
+    let y = formula.fresh_var();
+    for &l in lits {
+        formula.add_clause(vec![-y, l]);
+    }
+    let mut clause: Vec<Lit> = lits.iter().map(|&l| -l).collect();
+    clause.push(y);
+    formula.add_clause(clause);
+    y
+}
+
+/// Tseitin OR: returns `y` with `y <-> (lits[0] | lits[1] | ...)`.
+fn or_lit(formula: &mut CnfFormula, lits: &[Lit]) -> Lit {
+    let y = formula.fresh_var();
+    let mut clause: Vec<Lit> = vec![-y];
+    clause.extend(lits.iter().copied());
+    formula.add_clause(clause);
+    for &l in lits {
+        formula.add_clause(vec![-l, y]);
+    }
+    y
+}
+
+/// Which of the well-known attribute domains a field belongs to - the
+/// same four [`nectar_prover`]'s static analysis models.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldKind {
+    HttpStatus,
+    Duration,
+    Error,
+    ServiceName,
+    HttpRoute,
+}
+
+fn field_kind(field: &str) -> Option<FieldKind> {
+    match field {
+        "http.status" => Some(FieldKind::HttpStatus),
+        "duration" => Some(FieldKind::Duration),
+        "error" => Some(FieldKind::Error),
+        "service.name" => Some(FieldKind::ServiceName),
+        "http.route" => Some(FieldKind::HttpRoute),
+        _ => None,
+    }
+}
+
+/// An order-encoded finite integer domain: one `le[i]` variable per
+/// breakpoint, with `le[i] -> le[i+1]` clauses enforcing monotonicity,
+/// so `field <= breakpoints[i]` is exactly `le[i]`.
+struct IntDomain {
+    breakpoints: Vec<i64>,
+    le_vars: Vec<Lit>,
+}
+
+/// One indicator variable per distinct string literal seen for a field,
+/// with a pairwise at-most-one constraint (an unmentioned string is
+/// "none of these").
+struct StringDomain {
+    literals: Vec<(String, Lit)>,
+}
+
+/// Shared CNF state for encoding a group of [`MatchExpr`]s over the same
+/// field domains.
+struct FieldModel {
+    formula: CnfFormula,
+    true_lit: Lit,
+    http_status: Option<IntDomain>,
+    duration: Option<IntDomain>,
+    error: Option<Lit>,
+    service_name: Option<StringDomain>,
+    http_route: Option<StringDomain>,
+}
+
+impl FieldModel {
+    fn build<'a>(exprs: impl IntoIterator<Item = &'a MatchExpr>) -> Result<Self, String> {
+        let mut int_breakpoints: [BTreeSet<i64>; 2] = [BTreeSet::new(), BTreeSet::new()];
+        let mut string_literals: [BTreeSet<String>; 2] = [BTreeSet::new(), BTreeSet::new()];
+        let mut needs_error = false;
+
+        let exprs: Vec<&MatchExpr> = exprs.into_iter().collect();
+        for expr in &exprs {
+            collect(
+                expr,
+                &mut int_breakpoints,
+                &mut string_literals,
+                &mut needs_error,
+            )?;
+        }
+
+        let mut formula = CnfFormula::new(0);
+        let true_lit = formula.fresh_var();
+        formula.add_clause(vec![true_lit]);
+
+        let http_status = build_int_domain(&mut formula, &int_breakpoints[0]);
+        let duration = build_int_domain(&mut formula, &int_breakpoints[1]);
+        let error = needs_error.then(|| formula.fresh_var());
+        let service_name = build_string_domain(&mut formula, &string_literals[0]);
+        let http_route = build_string_domain(&mut formula, &string_literals[1]);
+
+        Ok(Self {
+            formula,
+            true_lit,
+            http_status,
+            duration,
+            error,
+            service_name,
+            http_route,
+        })
+    }
+
+    fn false_lit(&self) -> Lit {
+        -self.true_lit
+    }
+
+    fn encode(&mut self, expr: &MatchExpr) -> Result<Lit, String> {
+        match expr {
+            MatchExpr::True => Ok(self.true_lit),
+            MatchExpr::Condition(cond) => self.encode_condition(cond),
+            MatchExpr::And(parts) => {
+                let lits: Vec<Lit> = parts
+                    .iter()
+                    .map(|p| self.encode(p))
+                    .collect::<Result<_, _>>()?;
+                Ok(and_lit(&mut self.formula, &lits))
+            }
+            MatchExpr::Or(parts) => {
+                let lits: Vec<Lit> = parts
+                    .iter()
+                    .map(|p| self.encode(p))
+                    .collect::<Result<_, _>>()?;
+                Ok(or_lit(&mut self.formula, &lits))
+            }
+            MatchExpr::Not(inner) => Ok(-self.encode(inner)?),
+            MatchExpr::Threshold { k, .. } => Err(format!(
+                "thresh({k}, ...) expressions aren't modeled by the SAT verifier"
+            )),
+        }
+    }
+
+    fn encode_condition(&mut self, cond: &Condition) -> Result<Lit, String> {
+        let Some(kind) = field_kind(&cond.field) else {
+            return Err(format!("field '{}' isn't modeled", cond.field));
+        };
+
+        match kind {
+            FieldKind::HttpStatus => {
+                let Value::Int(v) = cond.value else {
+                    return Err(format!("'{}' expects an integer value", cond.field));
+                };
+                let domain = self
+                    .http_status
+                    .as_ref()
+                    .expect("collected a breakpoint for http.status during build");
+                encode_order(&mut self.formula, self.true_lit, domain, cond.operator, v)
+            }
+            FieldKind::Duration => {
+                let Value::Duration(ms) = cond.value else {
+                    return Err("'duration' requires a value with a unit (e.g. 500ms)".to_string());
+                };
+                let domain = self
+                    .duration
+                    .as_ref()
+                    .expect("collected a breakpoint for duration during build");
+                encode_order(
+                    &mut self.formula,
+                    self.true_lit,
+                    domain,
+                    cond.operator,
+                    ms as i64,
+                )
+            }
+            FieldKind::Error => {
+                let Value::Bool(b) = cond.value else {
+                    return Err("'error' expects a boolean value".to_string());
+                };
+                if !matches!(cond.operator, Operator::Eq | Operator::Ne) {
+                    return Err("'error' only supports == and !=".to_string());
+                }
+                let var = self.error.expect("collected the error field during build");
+                let eq = if b { var } else { -var };
+                Ok(if cond.operator == Operator::Eq {
+                    eq
+                } else {
+                    -eq
+                })
+            }
+            FieldKind::ServiceName | FieldKind::HttpRoute => {
+                let Value::String(ref s) = cond.value else {
+                    return Err(format!("'{}' expects a string value", cond.field));
+                };
+                if !matches!(cond.operator, Operator::Eq | Operator::Ne) {
+                    return Err(format!("'{}' only supports == and !=", cond.field));
+                }
+                let domain = if kind == FieldKind::ServiceName {
+                    self.service_name.as_ref()
+                } else {
+                    self.http_route.as_ref()
+                }
+                .expect("collected this literal during build");
+                let lit = domain
+                    .literals
+                    .iter()
+                    .find(|(lit_str, _)| lit_str == s)
+                    .map(|(_, lit)| *lit)
+                    .expect("collected this literal during build");
+                Ok(if cond.operator == Operator::Eq {
+                    lit
+                } else {
+                    -lit
+                })
+            }
+        }
+    }
+
+    fn decode_trace(&self, assignment: &[bool]) -> Trace {
+        let mut trace = Trace::new("sat-counterexample");
+
+        if let Some(domain) = &self.http_status {
+            let status = decode_int(domain, assignment);
+            trace = trace.with_status(status.clamp(0, i64::from(u16::MAX)) as u16);
+        }
+        if let Some(domain) = &self.duration {
+            let ms = decode_int(domain, assignment);
+            trace = trace.with_duration(Duration::from_millis(ms.max(0) as u64));
+        }
+        if let Some(service) = decode_string(&self.service_name, assignment) {
+            trace = trace.with_service(service);
+        }
+        if let Some(route) = decode_string(&self.http_route, assignment) {
+            trace = trace.with_endpoint(route);
+        }
+        if let Some(var) = self.error {
+            trace.is_error = assignment[sat::var_index(var)];
+        }
+
+        trace
+    }
+}
+
+fn collect(
+    expr: &MatchExpr,
+    int_breakpoints: &mut [BTreeSet<i64>; 2],
+    string_literals: &mut [BTreeSet<String>; 2],
+    needs_error: &mut bool,
+) -> Result<(), String> {
+    match expr {
+        MatchExpr::True => Ok(()),
+        MatchExpr::And(parts) | MatchExpr::Or(parts) => {
+            for part in parts {
+                collect(part, int_breakpoints, string_literals, needs_error)?;
+            }
+            Ok(())
+        }
+        MatchExpr::Not(inner) => collect(inner, int_breakpoints, string_literals, needs_error),
+        MatchExpr::Threshold { k, .. } => Err(format!(
+            "thresh({k}, ...) expressions aren't modeled by the SAT verifier"
+        )),
+        MatchExpr::Condition(cond) => {
+            let Some(kind) = field_kind(&cond.field) else {
+                return Err(format!("field '{}' isn't modeled", cond.field));
+            };
+            match kind {
+                FieldKind::HttpStatus => {
+                    let Value::Int(v) = cond.value else {
+                        return Err(format!("'{}' expects an integer value", cond.field));
+                    };
+                    add_breakpoints(&mut int_breakpoints[0], cond.operator, v);
+                }
+                FieldKind::Duration => {
+                    let Value::Duration(ms) = cond.value else {
+                        return Err(
+                            "'duration' requires a value with a unit (e.g. 500ms)".to_string()
+                        );
+                    };
+                    add_breakpoints(&mut int_breakpoints[1], cond.operator, ms as i64);
+                }
+                FieldKind::Error => {
+                    if !matches!(cond.value, Value::Bool(_)) {
+                        return Err("'error' expects a boolean value".to_string());
+                    }
+                    *needs_error = true;
+                }
+                FieldKind::ServiceName | FieldKind::HttpRoute => {
+                    let Value::String(ref s) = cond.value else {
+                        return Err(format!("'{}' expects a string value", cond.field));
+                    };
+                    let set = if kind == FieldKind::ServiceName {
+                        &mut string_literals[0]
+                    } else {
+                        &mut string_literals[1]
+                    };
+                    set.insert(s.clone());
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Adds the breakpoints a condition on an integer-like field needs: `>
+/// v` and `<= v` need `v`; `>= v` and `< v` need `v - 1` (the largest
+/// value strictly below `v`); `== v`/`!= v` need both, to pin the exact
+/// value. A condition needing `v - 1` where `v` is already the domain's
+/// minimum contributes no breakpoint - the corresponding bound is
+/// vacuously true or false, handled in [`encode_order`]/[`decode_int`].
+fn add_breakpoints(breakpoints: &mut BTreeSet<i64>, operator: Operator, v: i64) {
+    match operator {
+        Operator::Gt | Operator::Le => {
+            breakpoints.insert(v);
+        }
+        Operator::Ge | Operator::Lt => {
+            if let Some(pred) = v.checked_sub(1) {
+                breakpoints.insert(pred);
+            }
+        }
+        Operator::Eq | Operator::Ne => {
+            breakpoints.insert(v);
+            if let Some(pred) = v.checked_sub(1) {
+                breakpoints.insert(pred);
+            }
+        }
+        Operator::Contains
+        | Operator::StartsWith
+        | Operator::Exists
+        | Operator::In
+        | Operator::NotIn
+        | Operator::Matches => {}
+    }
+}
+
+fn build_int_domain(formula: &mut CnfFormula, breakpoints: &BTreeSet<i64>) -> Option<IntDomain> {
+    if breakpoints.is_empty() {
+        return None;
+    }
+    let breakpoints: Vec<i64> = breakpoints.iter().copied().collect();
+    let le_vars: Vec<Lit> = (0..breakpoints.len())
+        .map(|_| formula.fresh_var())
+        .collect();
+    for i in 0..le_vars.len() - 1 {
+        formula.add_clause(vec![-le_vars[i], le_vars[i + 1]]);
+    }
+    Some(IntDomain {
+        breakpoints,
+        le_vars,
+    })
+}
+
+fn build_string_domain(
+    formula: &mut CnfFormula,
+    literals: &BTreeSet<String>,
+) -> Option<StringDomain> {
+    if literals.is_empty() {
+        return None;
+    }
+    let vars: Vec<Lit> = (0..literals.len()).map(|_| formula.fresh_var()).collect();
+    for i in 0..vars.len() {
+        for j in (i + 1)..vars.len() {
+            formula.add_clause(vec![-vars[i], -vars[j]]);
+        }
+    }
+    let literals = literals.iter().cloned().zip(vars).collect();
+    Some(StringDomain { literals })
+}
+
+fn encode_order(
+    formula: &mut CnfFormula,
+    true_lit: Lit,
+    domain: &IntDomain,
+    operator: Operator,
+    v: i64,
+) -> Result<Lit, String> {
+    let le = |value: i64| -> Option<Lit> {
+        domain
+            .breakpoints
+            .binary_search(&value)
+            .ok()
+            .map(|i| domain.le_vars[i])
+    };
+
+    let lit = match operator {
+        Operator::Gt => -le(v).expect("collected during build"),
+        Operator::Le => le(v).expect("collected during build"),
+        Operator::Ge => v.checked_sub(1).and_then(le).map_or(true_lit, |l| -l),
+        Operator::Lt => v.checked_sub(1).and_then(le).unwrap_or(-true_lit),
+        Operator::Eq => {
+            let upper = le(v).expect("collected during build");
+            match v.checked_sub(1).and_then(le) {
+                Some(lower) => return Ok(and_lit_standalone(formula, upper, -lower)),
+                None => upper,
+            }
+        }
+        Operator::Ne => {
+            let upper = le(v).expect("collected during build");
+            let eq = match v.checked_sub(1).and_then(le) {
+                Some(lower) => and_lit_standalone(formula, upper, -lower),
+                None => upper,
+            };
+            -eq
+        }
+        Operator::Contains
+        | Operator::StartsWith
+        | Operator::Exists
+        | Operator::In
+        | Operator::NotIn
+        | Operator::Matches => return Err("only ==, !=, >, >=, <, <= are modeled".to_string()),
+    };
+    Ok(lit)
+}
+
+fn and_lit_standalone(formula: &mut CnfFormula, a: Lit, b: Lit) -> Lit {
+    and_lit(formula, &[a, b])
+}
+
+fn decode_int(domain: &IntDomain, assignment: &[bool]) -> i64 {
+    for (i, &bp) in domain.breakpoints.iter().enumerate() {
+        if assignment[sat::var_index(domain.le_vars[i])] {
+            return bp;
+        }
+    }
+    domain.breakpoints.last().map_or(0, |b| b + 1)
+}
+
+fn decode_string(domain: &Option<StringDomain>, assignment: &[bool]) -> Option<String> {
+    let domain = domain.as_ref()?;
+    domain
+        .literals
+        .iter()
+        .find(|(_, lit)| assignment[sat::var_index(*lit)])
+        .map(|(s, _)| s.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toon_policy::{Action, Policy};
+
+    #[test]
+    fn proves_that_a_dropped_status_range_is_never_kept() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "drop-client-errors",
+            "http.status >= 400 && http.status < 500",
+            Action::Drop,
+            50,
+        ));
+        policy.add_rule(Rule::new("fallback", "true", Action::Keep, 0));
+
+        let verdict = verify_property(
+            &policy,
+            &Property::NeverKept {
+                match_expr: "http.status >= 400 && http.status < 500".to_string(),
+            },
+        );
+        assert!(matches!(verdict, Verdict::Proven));
+    }
+
+    #[test]
+    fn finds_a_counterexample_when_a_fallback_keeps_the_target_range() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Keep, 0));
+
+        let verdict = verify_property(
+            &policy,
+            &Property::NeverKept {
+                match_expr: "http.status >= 500".to_string(),
+            },
+        );
+        let Verdict::Counterexample { trace, .. } = verdict else {
+            panic!("expected a counterexample, got {verdict:?}");
+        };
+        assert!(trace.status.unwrap_or(0) >= 500);
+    }
+
+    #[test]
+    fn precedence_is_total_when_same_priority_rules_cant_both_match() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("errors", "http.status >= 500", Action::Keep, 50));
+        policy.add_rule(Rule::new("ok", "http.status < 500", Action::Drop, 50));
+
+        assert!(matches!(
+            verify_property(&policy, &Property::PrecedenceIsTotal),
+            Verdict::Proven
+        ));
+    }
+
+    #[test]
+    fn precedence_is_not_total_when_same_priority_rules_overlap() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "a",
+            "service.name == \"checkout\"",
+            Action::Keep,
+            50,
+        ));
+        policy.add_rule(Rule::new("b", "http.status >= 500", Action::Drop, 50));
+
+        let verdict = verify_property(&policy, &Property::PrecedenceIsTotal);
+        assert!(matches!(verdict, Verdict::Counterexample { .. }));
+    }
+
+    #[test]
+    fn unparseable_rule_is_reported_as_unmodeled() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("bad", "((invalid && ||", Action::Keep, 10));
+
+        let verdict = verify_property(
+            &policy,
+            &Property::NeverKept {
+                match_expr: "true".to_string(),
+            },
+        );
+        assert!(matches!(verdict, Verdict::Unmodeled(_)));
+    }
+
+    #[test]
+    fn unmodeled_operator_is_reported_rather_than_guessed_at() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "a",
+            "service.name contains \"check\"",
+            Action::Keep,
+            10,
+        ));
+
+        let verdict = verify_property(
+            &policy,
+            &Property::NeverKept {
+                match_expr: "true".to_string(),
+            },
+        );
+        assert!(matches!(verdict, Verdict::Unmodeled(_)));
+    }
+
+    #[test]
+    fn scales_to_a_high_cardinality_style_policy() {
+        let mut policy = Policy::new("test");
+        for i in 0..200 {
+            policy.add_rule(Rule::new(
+                format!("service-{i}"),
+                format!("service.name == \"service-{i}\""),
+                Action::Drop,
+                10,
+            ));
+        }
+        policy.add_rule(Rule::new(
+            "keep-errors",
+            "http.status >= 500",
+            Action::Keep,
+            100,
+        ));
+
+        let verdict = verify_property(
+            &policy,
+            &Property::NeverKept {
+                match_expr: "http.status < 500".to_string(),
+            },
+        );
+        assert!(matches!(verdict, Verdict::Proven));
+    }
+}
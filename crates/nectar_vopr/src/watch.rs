@@ -0,0 +1,185 @@
+//! Watch mode: monitor a corpus directory for changes and re-run a
+//! registered set of scenarios, the way a test-runner watch loop
+//! re-executes a suite whenever a source file changes.
+
+use crate::harness::{SimConfig, Simulation};
+use crate::simulation::{Scenario, SimSummary};
+use nectar_corpus::Corpus;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use xxhash_rust::xxh64::xxh64;
+
+/// How often the watcher thread polls the directory for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// A content fingerprint of a directory's `.json` files: each file's path
+/// paired with a hash of its bytes. Cheap enough to recompute on every
+/// poll, and - unlike comparing modification times - immune to filesystem
+/// mtime granularity hiding a fast edit.
+type DirFingerprint = HashMap<PathBuf, u64>;
+
+fn fingerprint(dir: &Path) -> io::Result<DirFingerprint> {
+    let mut out = HashMap::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e == "json") {
+            let bytes = std::fs::read(&path)?;
+            out.insert(path, xxh64(&bytes, 0));
+        }
+    }
+    Ok(out)
+}
+
+/// Watches `dir` for `.json` file add/modify/remove events, and whenever a
+/// batch of changes settles for at least `debounce`, reloads the corpus
+/// via [`Corpus::load_directory`], rebuilds the scenario list with
+/// `build_scenarios`, runs it, and prints a fresh [`SimSummary`].
+///
+/// An initial run happens immediately, before waiting for any changes.
+///
+/// Blocks until `running` is set to `false` - typically by a Ctrl-C
+/// handler the caller installs (e.g. via the `ctrlc` crate), since
+/// watching indefinitely and trapping the signal itself would make this
+/// function unable to coexist with a caller that wants to manage its own
+/// shutdown, and untestable without a real SIGINT in play.
+///
+/// # Errors
+///
+/// Returns an error if `dir` cannot be read.
+pub fn run_watch(
+    dir: impl AsRef<Path>,
+    build_scenarios: impl Fn(&Corpus) -> Vec<Scenario>,
+    debounce: Duration,
+    running: &AtomicBool,
+) -> io::Result<()> {
+    let dir = dir.as_ref();
+
+    run_once(dir, &build_scenarios);
+
+    let (tx, rx) = mpsc::channel();
+    let watch_dir = dir.to_path_buf();
+    let watcher = thread::spawn(move || {
+        let mut last = fingerprint(&watch_dir).unwrap_or_default();
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let Ok(current) = fingerprint(&watch_dir) else {
+                continue;
+            };
+            if current != last {
+                last = current;
+                if tx.send(()).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    while running.load(Ordering::SeqCst) {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(()) => {
+                // Debounce: keep draining until a full `debounce` window
+                // passes with no further events.
+                while rx.recv_timeout(debounce).is_ok() {}
+                run_once(dir, &build_scenarios);
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    // Dropping `rx` makes the watcher thread's next `tx.send` fail, so it
+    // exits within one `POLL_INTERVAL` instead of running forever detached.
+    drop(rx);
+    let _ = watcher.join();
+    Ok(())
+}
+
+/// Loads the corpus from `dir`, runs `build_scenarios(&corpus)` through a
+/// fresh [`Simulation`], and prints the resulting [`SimSummary`].
+fn run_once(dir: &Path, build_scenarios: &impl Fn(&Corpus) -> Vec<Scenario>) {
+    let corpus = match Corpus::load_directory(dir) {
+        Ok(corpus) => corpus,
+        Err(e) => {
+            eprintln!("Failed to load corpus from {}: {e}", dir.display());
+            return;
+        }
+    };
+
+    let scenarios = build_scenarios(&corpus);
+    let mut sim = Simulation::new(SimConfig::default());
+    let summary = SimSummary::from_results(sim.run_all(&scenarios));
+    println!("{summary}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        dir
+    }
+
+    #[test]
+    fn fingerprint_changes_when_a_file_is_added_or_modified() {
+        let dir = temp_dir("nectar-watch-fingerprint");
+        std::fs::write(dir.join("a.json"), "[]").unwrap();
+
+        let before = fingerprint(&dir).unwrap();
+        std::fs::write(dir.join("a.json"), "[1]").unwrap();
+        let after_modify = fingerprint(&dir).unwrap();
+        assert_ne!(before, after_modify);
+
+        std::fs::write(dir.join("b.json"), "[]").unwrap();
+        let after_add = fingerprint(&dir).unwrap();
+        assert_ne!(after_modify, after_add);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn fingerprint_ignores_non_json_files() {
+        let dir = temp_dir("nectar-watch-fingerprint-ignore");
+        std::fs::write(dir.join("readme.txt"), "hello").unwrap();
+
+        let fp = fingerprint(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert!(fp.is_empty());
+    }
+
+    #[test]
+    fn run_watch_runs_once_immediately_and_stops_when_told_to() {
+        let dir = temp_dir("nectar-watch-run-once");
+        std::fs::write(
+            dir.join("a.json"),
+            r#"[{"trace_id": "a", "duration_ms": 1, "status": 200}]"#,
+        )
+        .unwrap();
+
+        let running = AtomicBool::new(true);
+        running.store(false, Ordering::SeqCst);
+
+        let result = run_watch(
+            &dir,
+            |corpus| {
+                vec![Scenario::roundtrip(toon_policy::Policy::new(format!(
+                    "watch-{}",
+                    corpus.len()
+                )))]
+            },
+            Duration::from_millis(10),
+            &running,
+        );
+
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(result.is_ok());
+    }
+}
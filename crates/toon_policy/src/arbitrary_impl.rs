@@ -0,0 +1,108 @@
+//! `Arbitrary` implementations for the policy model.
+//!
+//! These let a coverage-guided fuzzer (see `nectar_vopr::fuzz::fuzz_one`)
+//! build [`Policy`]/[`Rule`]/[`Action`] values directly from its mutated
+//! byte buffer, instead of the fixed corruption menu in
+//! `nectar_vopr::chaos`. Gated behind the `arbitrary` feature so normal
+//! builds don't pull in the dependency.
+
+use crate::model::{Action, Policy, Rule};
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// A handful of deliberately well-formed match expressions, mixed in
+/// with raw arbitrary strings so the fuzzer explores both "valid
+/// grammar, adversarial values" (e.g. `status >= 500` with a status of
+/// `0`) and "garbage the parser must reject cleanly" - malformed
+/// expressions like `"((invalid && ||"` arise naturally from the latter
+/// without needing to hand-write them.
+const VALID_MATCH_EXPRS: &[&str] = &[
+    "true",
+    "status >= 500",
+    "status == 200",
+    "error",
+    "is_error",
+];
+
+impl<'a> Arbitrary<'a> for Action {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=3u8)? {
+            0 => Self::Keep,
+            1 => Self::Drop,
+            2 => Self::Sample(u.arbitrary()?),
+            _ => Self::CircuitBreaker {
+                closed_rate: u.arbitrary()?,
+                open_rate: u.arbitrary()?,
+                window: u.arbitrary()?,
+                failure_threshold: u.arbitrary()?,
+                min_samples: u.arbitrary()?,
+            },
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Rule {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let match_expr = if u.ratio(3u8, 4u8)? {
+            (*u.choose(VALID_MATCH_EXPRS)?).to_string()
+        } else {
+            u.arbitrary()?
+        };
+
+        Ok(Self {
+            name: u.arbitrary()?,
+            description: u.arbitrary()?,
+            match_expr,
+            action: u.arbitrary()?,
+            priority: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Policy {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            version: u.arbitrary()?,
+            name: u.arbitrary()?,
+            budget_per_second: u.arbitrary()?,
+            rules: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arbitrary_policy_builds_from_any_byte_buffer() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let mut u = Unstructured::new(&bytes);
+        let policy = Policy::arbitrary(&mut u).unwrap();
+        // Just needs to construct without panicking; fields are
+        // deliberately unconstrained adversarial values.
+        let _ = policy.rules.len();
+    }
+
+    #[test]
+    fn arbitrary_rule_builds_from_a_small_buffer() {
+        let bytes = [0u8; 64];
+        let mut u = Unstructured::new(&bytes);
+        let rule = Rule::arbitrary(&mut u).unwrap();
+        assert!(rule.priority <= u8::MAX);
+    }
+
+    #[test]
+    fn arbitrary_can_draw_one_of_the_valid_match_exprs() {
+        // Over enough draws, at least one rule should land on the
+        // well-formed grammar branch rather than a raw arbitrary string.
+        let seeds: Vec<Vec<u8>> = (0u8..32)
+            .map(|b| vec![b; 32])
+            .collect();
+        let hit_valid = seeds.iter().any(|bytes| {
+            let mut u = Unstructured::new(bytes);
+            let rule = Rule::arbitrary(&mut u).unwrap();
+            VALID_MATCH_EXPRS.contains(&rule.match_expr.as_str())
+        });
+        assert!(hit_valid, "expected at least one draw to hit a valid match expr");
+    }
+}
@@ -28,10 +28,14 @@
 #![allow(clippy::module_name_repetitions)]
 #![allow(clippy::must_use_candidate)]
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary_impl;
 pub mod error;
+pub mod lint;
 pub mod model;
 pub mod parser;
 
 pub use error::{Error, Result};
+pub use lint::{apply_fixes, lint, Diagnostic, Fix, Severity};
 pub use model::{Action, Policy, Rule};
 pub use parser::{parse, parse_action, serialize};
@@ -0,0 +1,400 @@
+//! Non-fatal linting pass over TOON policy source.
+//!
+//! `crate::parser::parse` only reports hard failures - syntax errors,
+//! missing fields, count mismatches - that keep a policy from becoming a
+//! [`Policy`] at all. This module runs *after* a policy parses
+//! successfully and flags things that are structurally valid but almost
+//! certainly wrong: a rule that can never fire, a sample rate that rounds
+//! to a 0% keep rate, a `service.name` filter that can't match anything.
+//! Each finding is a [`Diagnostic`] carrying a [`Severity`], a source
+//! line/column, and - where the fix is unambiguous - a [`Fix`] text edit
+//! that [`apply_fixes`] can apply mechanically.
+//!
+//! This is a fast, file-local pass over the raw match-expression text; it
+//! does not reason about attribute value domains the way
+//! `nectar_prover`'s static analysis does (that crate sits above this one
+//! and already covers symbolic overlap detection against a trace corpus).
+
+use crate::model::{Action, Policy};
+use crate::parser::parse;
+use crate::Result;
+use serde::Serialize;
+use std::ops::Range;
+
+/// How serious a [`Diagnostic`] is, in increasing order of severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Style/housekeeping observation.
+    Info,
+    /// Worth a human's attention but not necessarily wrong.
+    Warning,
+    /// The policy still parses but this is almost certainly a mistake.
+    Error,
+}
+
+/// A mechanical text edit a [`Diagnostic`] can be turned into.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Fix {
+    /// Short description of what the fix does.
+    pub description: String,
+    /// Byte range in the original source this fix replaces.
+    #[serde(skip)]
+    pub span: Range<usize>,
+    /// Text to substitute into `span`.
+    pub replacement: String,
+}
+
+/// A single lint finding.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostic {
+    /// How serious this finding is.
+    pub severity: Severity,
+    /// 1-based source line the finding is anchored to.
+    pub line: usize,
+    /// 1-based column within that line.
+    pub column: usize,
+    /// Human-readable description of the finding.
+    pub message: String,
+    /// A machine-applicable fix, if one is unambiguous.
+    pub fix: Option<Fix>,
+}
+
+/// Parses `source` and runs every lint over the resulting policy.
+///
+/// Diagnostics are sorted by source line, most severe first within a
+/// line.
+///
+/// # Errors
+///
+/// Returns an error if `source` fails to parse - lints only run over a
+/// structurally valid policy; a policy that doesn't parse gets the
+/// parse error from [`crate::parse`] instead.
+pub fn lint(source: &str) -> Result<Vec<Diagnostic>> {
+    let policy = parse(source)?;
+    let rows = scan_rule_rows(source);
+
+    let mut diagnostics = Vec::new();
+    diagnostics.extend(lint_shadowed_rules(&policy, &rows));
+    diagnostics.extend(lint_zero_percent_samples(&policy, &rows));
+    diagnostics.extend(lint_unused_service_names(&policy, &rows));
+
+    diagnostics.sort_by(|a, b| a.line.cmp(&b.line).then(b.severity.cmp(&a.severity)));
+    Ok(diagnostics)
+}
+
+/// Applies every [`Fix`] attached to `diagnostics` to `source`, returning
+/// the edited text.
+///
+/// Fixes are applied back-to-front by byte offset so an earlier edit's
+/// span never gets invalidated by a later one. If two fixes' spans
+/// overlap, the one further left wins and the other is left unapplied -
+/// same as a human applying suggestions top-to-bottom and skipping ones
+/// that no longer make sense once an earlier edit landed.
+#[must_use]
+pub fn apply_fixes(source: &str, diagnostics: &[Diagnostic]) -> String {
+    let mut fixes: Vec<&Fix> = diagnostics.iter().filter_map(|d| d.fix.as_ref()).collect();
+    fixes.sort_by(|a, b| b.span.start.cmp(&a.span.start));
+
+    let mut output = source.to_string();
+    let mut applied_up_to = output.len();
+    for fix in fixes {
+        if fix.span.end > applied_up_to {
+            continue;
+        }
+        output.replace_range(fix.span.clone(), &fix.replacement);
+        applied_up_to = fix.span.start;
+    }
+    output
+}
+
+/// A rule row's location within the original source text.
+struct RuleRow {
+    name: String,
+    line: usize,
+    column: usize,
+    /// Byte span of the row's whole line, including its trailing
+    /// newline, so a delete-rule fix removes the row cleanly.
+    span: Range<usize>,
+}
+
+impl RuleRow {
+    fn find<'a>(rows: &'a [Self], name: &str) -> Option<&'a Self> {
+        rows.iter().find(|row| row.name == name)
+    }
+}
+
+/// Re-scans `source` for rule rows (independent of [`parse`], which
+/// discards line/column info once it builds the typed [`Policy`]) so
+/// lints can anchor diagnostics and fixes to real byte spans.
+fn scan_rule_rows(source: &str) -> Vec<RuleRow> {
+    let mut rows = Vec::new();
+    let mut offset = 0usize;
+    let mut in_rules = false;
+
+    for (idx, line) in source.split_inclusive('\n').enumerate() {
+        let line_start = offset;
+        let line_end = offset + line.len();
+        offset = line_end;
+
+        let bare = line.trim_end_matches(['\n', '\r']);
+        let trimmed = bare.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if !in_rules {
+            if trimmed.starts_with("rules[") && trimmed.ends_with(':') {
+                in_rules = true;
+            }
+            continue;
+        }
+
+        let indent = bare.len() - bare.trim_start().len();
+        if indent < 4 {
+            in_rules = false;
+            continue;
+        }
+
+        if let Some(raw_name) = trimmed.split(',').next() {
+            rows.push(RuleRow {
+                name: raw_name.trim().trim_matches('"').to_string(),
+                line: idx + 1,
+                column: indent + 1,
+                span: line_start..line_end,
+            });
+        }
+    }
+
+    rows
+}
+
+/// Flags rules that can never fire: an earlier (equal-or-higher
+/// priority) rule with the literal `true` match expression, or an
+/// earlier rule with the exact same match expression, already matches
+/// everything this rule would.
+fn lint_shadowed_rules(policy: &Policy, rows: &[RuleRow]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (i, rule) in policy.rules.iter().enumerate() {
+        for earlier in policy.rules.iter().take(i) {
+            let shadows = (earlier.match_expr == "true" || earlier.match_expr == rule.match_expr)
+                && earlier.priority >= rule.priority;
+            if !shadows {
+                continue;
+            }
+            let Some(row) = RuleRow::find(rows, &rule.name) else {
+                continue;
+            };
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                line: row.line,
+                column: row.column,
+                message: format!(
+                    "rule '{}' can never fire: '{}' (priority {}) already matches everything it would, at priority {}",
+                    rule.name, earlier.name, earlier.priority, rule.priority
+                ),
+                fix: Some(Fix {
+                    description: format!("remove unreachable rule '{}'", rule.name),
+                    span: row.span.clone(),
+                    replacement: String::new(),
+                }),
+            });
+            break;
+        }
+    }
+
+    diagnostics
+}
+
+/// Flags `sample(rate)` actions whose rate rounds to a 0% keep rate,
+/// which is almost always meant to be `drop` (explicit) or a higher rate
+/// (a typo).
+fn lint_zero_percent_samples(policy: &Policy, rows: &[RuleRow]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for rule in &policy.rules {
+        let Action::Sample(rate) = &rule.action else {
+            continue;
+        };
+        let rate = *rate;
+        if rate <= 0.0 || rate >= 0.005 {
+            continue;
+        }
+        let Some(row) = RuleRow::find(rows, &rule.name) else {
+            continue;
+        };
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            line: row.line,
+            column: row.column,
+            message: format!(
+                "rule '{}' samples at {rate} ({:.2}%), which rounds to a 0% keep rate - use 'drop' if that's intended, or raise the rate",
+                rule.name,
+                rate * 100.0
+            ),
+            fix: None,
+        });
+    }
+
+    diagnostics
+}
+
+/// Flags `service.name == "..."` (or `!=`) comparisons against an empty
+/// or whitespace-only literal - a service name that, by construction,
+/// can never equal a real service's name and is almost always copy-paste
+/// debris left over while drafting the rule.
+fn lint_unused_service_names(policy: &Policy, rows: &[RuleRow]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for rule in &policy.rules {
+        if !service_name_literals(&rule.match_expr)
+            .iter()
+            .any(|literal| literal.trim().is_empty())
+        {
+            continue;
+        }
+        let Some(row) = RuleRow::find(rows, &rule.name) else {
+            continue;
+        };
+        diagnostics.push(Diagnostic {
+            severity: Severity::Info,
+            line: row.line,
+            column: row.column,
+            message: format!(
+                "rule '{}' compares service.name against an empty literal, which no real service name can ever equal",
+                rule.name
+            ),
+            fix: None,
+        });
+    }
+
+    diagnostics
+}
+
+/// Extracts the quoted string literal from each `service.name == "..."`
+/// (or `!=`) comparison in `expr`, in source order.
+fn service_name_literals(expr: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    let mut rest = expr;
+
+    while let Some(field_pos) = rest.find("service.name") {
+        let after_field = &rest[field_pos + "service.name".len()..];
+        rest = after_field;
+
+        let after_op = after_field
+            .trim_start()
+            .strip_prefix("==")
+            .or_else(|| after_field.trim_start().strip_prefix("!="));
+        let Some(after_op) = after_op else {
+            continue;
+        };
+
+        let after_op = after_op.trim_start();
+        let Some(quoted) = after_op.strip_prefix('"') else {
+            continue;
+        };
+        if let Some(end) = quoted.find('"') {
+            literals.push(quoted[..end].to_string());
+        }
+    }
+
+    literals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Action, Policy, Rule};
+    use crate::parser::serialize;
+
+    #[test]
+    fn flags_rule_shadowed_by_earlier_fallback() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Sample(0.1), 100));
+        policy.add_rule(Rule::new("dead", "http.status >= 500", Action::Keep, 0));
+        let source = serialize(&policy);
+
+        let diagnostics = lint(&source).unwrap();
+        let shadowed = diagnostics
+            .iter()
+            .find(|d| d.message.contains("dead"))
+            .expect("expected a shadowed-rule diagnostic");
+        assert_eq!(shadowed.severity, Severity::Error);
+        assert!(shadowed.fix.is_some());
+    }
+
+    #[test]
+    fn flags_duplicate_match_expression() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("first", "error == true", Action::Keep, 50));
+        policy.add_rule(Rule::new("second", "error == true", Action::Drop, 10));
+        let source = serialize(&policy);
+
+        let diagnostics = lint(&source).unwrap();
+        assert!(diagnostics.iter().any(|d| d.message.contains("second")));
+    }
+
+    #[test]
+    fn does_not_flag_distinct_rules() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("errors", "http.status >= 500", Action::Keep, 100));
+        policy.add_rule(Rule::new("rest", "true", Action::Sample(0.05), 0));
+        let source = serialize(&policy);
+
+        assert!(lint(&source).unwrap().is_empty());
+    }
+
+    #[test]
+    fn flags_sample_rate_that_rounds_to_zero() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("trickle", "true", Action::Sample(0.0001), 0));
+        let source = serialize(&policy);
+
+        let diagnostics = lint(&source).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(diagnostics[0].fix.is_none());
+    }
+
+    #[test]
+    fn does_not_flag_reasonable_sample_rate() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("baseline", "true", Action::Sample(0.01), 0));
+        let source = serialize(&policy);
+
+        assert!(lint(&source).unwrap().is_empty());
+    }
+
+    #[test]
+    fn flags_empty_service_name_literal() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new(
+            "stub",
+            "service.name == \"\"",
+            Action::Drop,
+            0,
+        ));
+        let source = serialize(&policy);
+
+        let diagnostics = lint(&source).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Info);
+    }
+
+    #[test]
+    fn apply_fixes_removes_shadowed_rule_and_keeps_policy_parseable() {
+        let mut policy = Policy::new("test");
+        policy.add_rule(Rule::new("fallback", "true", Action::Keep, 100));
+        policy.add_rule(Rule::new("dead", "http.status >= 500", Action::Drop, 0));
+        let source = serialize(&policy);
+
+        let diagnostics = lint(&source).unwrap();
+        let fixed = apply_fixes(&source, &diagnostics);
+
+        let reparsed = parse(&fixed).unwrap();
+        assert_eq!(reparsed.rules.len(), 1);
+        assert_eq!(reparsed.rules[0].name, "fallback");
+        assert!(lint(&fixed).unwrap().is_empty());
+    }
+}
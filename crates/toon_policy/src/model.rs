@@ -42,6 +42,29 @@ pub enum Action {
     Drop,
     /// Sample at the given rate (0.0 to 1.0).
     Sample(f64),
+    /// Sliding-window circuit breaker. While the rolling error ratio over
+    /// the most recent `window` traces stays below `failure_threshold`,
+    /// samples at `closed_rate`; once at least `min_samples` traces have
+    /// been observed and the ratio trips `failure_threshold`, escalates to
+    /// `open_rate` until the ratio falls back below it. This lets a single
+    /// fallback rule auto-escalate sampling during an error spike instead
+    /// of holding a fixed rate throughout.
+    ///
+    /// Evaluated statefully by `nectar_prover`'s corpus replay; outside of
+    /// that, [`Action::effective_rate`] reports `closed_rate` as the
+    /// resting-state approximation.
+    CircuitBreaker {
+        /// Sample rate applied while the breaker is closed.
+        closed_rate: f64,
+        /// Sample rate applied while the breaker is open.
+        open_rate: f64,
+        /// Number of most recent traces the rolling error ratio considers.
+        window: usize,
+        /// Error ratio (0.0 to 1.0) that trips the breaker open.
+        failure_threshold: f64,
+        /// Minimum traces observed before the breaker is eligible to trip.
+        min_samples: usize,
+    },
 }
 
 impl Policy {
@@ -63,9 +86,16 @@ impl Policy {
     }
 
     /// Returns true if the policy has a fallback rule (matches all).
+    ///
+    /// Compares case-insensitively and ignores surrounding whitespace
+    /// (`" True "` counts), since `toon_policy` has no dependency on the
+    /// full match-expression parser and can't otherwise tell a fallback
+    /// apart from any other single-condition `match_expr`.
     #[must_use]
     pub fn has_fallback(&self) -> bool {
-        self.rules.iter().any(|r| r.match_expr == "true")
+        self.rules
+            .iter()
+            .any(|r| r.match_expr.trim().eq_ignore_ascii_case("true"))
     }
 }
 
@@ -103,6 +133,7 @@ impl Action {
             Self::Keep => 1.0,
             Self::Drop => 0.0,
             Self::Sample(rate) => *rate,
+            Self::CircuitBreaker { closed_rate, .. } => *closed_rate,
         }
     }
 }
@@ -128,5 +159,14 @@ mod tests {
         assert!((Action::Keep.effective_rate() - 1.0).abs() < f64::EPSILON);
         assert!((Action::Drop.effective_rate() - 0.0).abs() < f64::EPSILON);
         assert!((Action::Sample(0.5).effective_rate() - 0.5).abs() < f64::EPSILON);
+
+        let breaker = Action::CircuitBreaker {
+            closed_rate: 0.01,
+            open_rate: 0.9,
+            window: 100,
+            failure_threshold: 0.2,
+            min_samples: 20,
+        };
+        assert!((breaker.effective_rate() - 0.01).abs() < f64::EPSILON);
     }
 }
@@ -426,6 +426,8 @@ fn parse_csv_row(row: &str) -> Vec<String> {
 /// - `keep` → `Action::Keep`
 /// - `drop` → `Action::Drop`
 /// - `sample(0.1)` → `Action::Sample(0.1)`
+/// - `circuit_breaker(closed_rate,open_rate,window,failure_threshold,min_samples)`
+///   → `Action::CircuitBreaker { .. }`
 ///
 /// # Errors
 ///
@@ -449,13 +451,79 @@ pub fn parse_action(s: &str) -> Result<Action> {
             }
             Ok(Action::Sample(rate))
         }
+        _ if s.starts_with("circuit_breaker(") && s.ends_with(')') => {
+            parse_circuit_breaker_action(s)
+        }
         _ => Err(Error::InvalidAction {
             action: s.to_string(),
-            reason: "expected 'keep', 'drop', or 'sample(rate)'".to_string(),
+            reason: "expected 'keep', 'drop', 'sample(rate)', or 'circuit_breaker(...)'"
+                .to_string(),
         }),
     }
 }
 
+/// Parses the body of a `circuit_breaker(closed_rate,open_rate,window,failure_threshold,min_samples)`
+/// action string.
+fn parse_circuit_breaker_action(s: &str) -> Result<Action> {
+    let inner = &s["circuit_breaker(".len()..s.len() - 1];
+    let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
+    if parts.len() != 5 {
+        return Err(Error::InvalidAction {
+            action: s.to_string(),
+            reason: format!(
+                "circuit_breaker expects 5 fields (closed_rate,open_rate,window,failure_threshold,min_samples), found {}",
+                parts.len()
+            ),
+        });
+    }
+
+    let parse_f64 = |field: &str, label: &str| -> Result<f64> {
+        field.parse().map_err(|_| Error::InvalidAction {
+            action: s.to_string(),
+            reason: format!("invalid {label}: {field}"),
+        })
+    };
+    let parse_usize = |field: &str, label: &str| -> Result<usize> {
+        field.parse().map_err(|_| Error::InvalidAction {
+            action: s.to_string(),
+            reason: format!("invalid {label}: {field}"),
+        })
+    };
+
+    let closed_rate = parse_f64(parts[0], "closed_rate")?;
+    let open_rate = parse_f64(parts[1], "open_rate")?;
+    let window = parse_usize(parts[2], "window")?;
+    let failure_threshold = parse_f64(parts[3], "failure_threshold")?;
+    let min_samples = parse_usize(parts[4], "min_samples")?;
+
+    for (rate, label) in [
+        (closed_rate, "closed_rate"),
+        (open_rate, "open_rate"),
+        (failure_threshold, "failure_threshold"),
+    ] {
+        if !(0.0..=1.0).contains(&rate) {
+            return Err(Error::InvalidAction {
+                action: s.to_string(),
+                reason: format!("{label} must be between 0.0 and 1.0"),
+            });
+        }
+    }
+    if window == 0 {
+        return Err(Error::InvalidAction {
+            action: s.to_string(),
+            reason: "window must be at least 1".to_string(),
+        });
+    }
+
+    Ok(Action::CircuitBreaker {
+        closed_rate,
+        open_rate,
+        window,
+        failure_threshold,
+        min_samples,
+    })
+}
+
 /// Serializes a [`Policy`] to TOON format.
 pub fn serialize(policy: &Policy) -> String {
     let mut output = String::new();
@@ -489,12 +557,22 @@ pub fn serialize(policy: &Policy) -> String {
             Action::Keep => "keep".to_string(),
             Action::Drop => "drop".to_string(),
             Action::Sample(rate) => format!("sample({rate})"),
+            Action::CircuitBreaker {
+                closed_rate,
+                open_rate,
+                window,
+                failure_threshold,
+                min_samples,
+            } => format!(
+                "circuit_breaker({closed_rate},{open_rate},{window},{failure_threshold},{min_samples})"
+            ),
         };
 
         // Escape values that contain commas
         let name = escape_csv_value(&rule.name);
         let description = escape_csv_value(description);
         let match_expr = escape_csv_value(&rule.match_expr);
+        let action_str = escape_csv_value(&action_str);
 
         let _ = writeln!(
             output,
@@ -550,6 +628,36 @@ mod tests {
         assert!(parse_action("").is_err());
     }
 
+    #[test]
+    fn parse_action_circuit_breaker() {
+        assert_eq!(
+            parse_action("circuit_breaker(0.01,0.9,100,0.2,20)").unwrap(),
+            Action::CircuitBreaker {
+                closed_rate: 0.01,
+                open_rate: 0.9,
+                window: 100,
+                failure_threshold: 0.2,
+                min_samples: 20,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_action_circuit_breaker_wrong_field_count() {
+        assert!(parse_action("circuit_breaker(0.01,0.9,100)").is_err());
+    }
+
+    #[test]
+    fn parse_action_circuit_breaker_invalid_rate() {
+        assert!(parse_action("circuit_breaker(1.5,0.9,100,0.2,20)").is_err());
+        assert!(parse_action("circuit_breaker(0.01,0.9,100,1.5,20)").is_err());
+    }
+
+    #[test]
+    fn parse_action_circuit_breaker_zero_window() {
+        assert!(parse_action("circuit_breaker(0.01,0.9,0,0.2,20)").is_err());
+    }
+
     #[test]
     fn parse_simple_policy() {
         let input = r#"
@@ -658,6 +766,28 @@ nectar_policy{version,budget_per_second,rules}:
         assert_eq!(parsed.rules.len(), policy.rules.len());
     }
 
+    #[test]
+    fn roundtrip_serialize_parse_circuit_breaker() {
+        let mut policy = Policy::new("breaker-test");
+        policy.add_rule(Rule::new(
+            "fallback",
+            "true",
+            Action::CircuitBreaker {
+                closed_rate: 0.01,
+                open_rate: 0.9,
+                window: 100,
+                failure_threshold: 0.2,
+                min_samples: 20,
+            },
+            0,
+        ));
+
+        let serialized = serialize(&policy);
+        let parsed = parse(&serialized).unwrap();
+
+        assert_eq!(parsed.rules[0].action, policy.rules[0].action);
+    }
+
     #[test]
     fn parse_csv_row_simple() {
         let values = parse_csv_row("a,b,c");